@@ -0,0 +1,14 @@
+#!/bin/sh
+# Downloads the files embedded by the `graphiql-embedded` feature. The versions
+# match the ones loaded from the CDN by `graphiql::GraphiQLSource` by default.
+set -e
+
+cd "$(dirname "$0")"
+
+curl -fsSL -o graphiql.css https://cdnjs.cloudflare.com/ajax/libs/graphiql/0.10.2/graphiql.css
+curl -fsSL -o graphiql.js https://cdnjs.cloudflare.com/ajax/libs/graphiql/0.10.2/graphiql.js
+curl -fsSL -o fetch.js https://cdnjs.cloudflare.com/ajax/libs/fetch/2.0.3/fetch.js
+curl -fsSL -o react.js https://cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react.js
+curl -fsSL -o react-dom.js https://cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react-dom.js
+curl -fsSL -o subscriptions-transport-ws.js https://unpkg.com/subscriptions-transport-ws@0.8.3/browser/client.js
+curl -fsSL -o graphiql-subscriptions-fetcher.js https://unpkg.com/graphiql-subscriptions-fetcher@0.0.2/browser/client.js
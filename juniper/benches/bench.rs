@@ -3,12 +3,83 @@ extern crate juniper;
 
 use bencher::Bencher;
 
-use juniper::{execute, RootNode, EmptyMutation, Variables};
+use juniper::{execute, Variables};
+use juniper::bench::{self, FRAGMENTS, HERO_FRIENDS, HERO_NAME, INTROSPECTION, VARIABLES};
 use juniper::tests::model::Database;
 
+fn parse_hero_friends(b: &mut Bencher) {
+    b.iter(|| bench::parse(HERO_FRIENDS).unwrap());
+}
+
+fn parse_introspection(b: &mut Bencher) {
+    b.iter(|| bench::parse(INTROSPECTION).unwrap());
+}
+
+fn parse_many_operations(b: &mut Bencher) {
+    let doc = bench::many_operations(100);
+
+    b.bytes = doc.len() as u64;
+    b.iter(|| bench::parse(&doc).unwrap());
+}
+
+fn validate_fragments(b: &mut Bencher) {
+    let schema = bench::schema();
+    let document = bench::parse(FRAGMENTS).unwrap();
+
+    b.iter(|| bench::validate(&schema, &document));
+}
+
+fn validate_introspection(b: &mut Bencher) {
+    let schema = bench::schema();
+    let document = bench::parse(INTROSPECTION).unwrap();
+
+    b.iter(|| bench::validate(&schema, &document));
+}
+
+fn validate_many_operations(b: &mut Bencher) {
+    let schema = bench::schema();
+    let doc = bench::many_operations(100);
+    let document = bench::parse(&doc).unwrap();
+
+    b.iter(|| bench::validate(&schema, &document));
+}
+
+fn execute_hero_name(b: &mut Bencher) {
+    let schema = bench::schema();
+    let database = Database::new();
+    let document = bench::parse(HERO_NAME).unwrap();
+
+    b.iter(|| bench::execute(&schema, &document, None, &Variables::new(), &database));
+}
+
+fn execute_variables(b: &mut Bencher) {
+    let schema = bench::schema();
+    let database = Database::new();
+    let document = bench::parse(VARIABLES).unwrap();
+
+    b.iter(|| bench::execute(&schema, &document, None, &Variables::new(), &database));
+}
+
+fn execute_nested_friends(b: &mut Bencher) {
+    let schema = bench::schema();
+    let database = Database::new();
+    let doc = bench::nested_friends(4);
+    let document = bench::parse(&doc).unwrap();
+
+    b.iter(|| bench::execute(&schema, &document, None, &Variables::new(), &database));
+}
+
+fn execute_introspection(b: &mut Bencher) {
+    let schema = bench::schema();
+    let database = Database::new();
+    let document = bench::parse(INTROSPECTION).unwrap();
+
+    b.iter(|| bench::execute(&schema, &document, None, &Variables::new(), &database));
+}
+
 fn query_type_name(b: &mut Bencher) {
+    let schema = bench::schema();
     let database = Database::new();
-    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
 
     let doc = r#"
         query IntrospectionQueryTypeQuery {
@@ -23,105 +94,14 @@ fn query_type_name(b: &mut Bencher) {
 }
 
 fn introspection_query(b: &mut Bencher) {
+    let schema = bench::schema();
     let database = Database::new();
-    let schema = RootNode::new(&database, EmptyMutation::<Database>::new());
 
-    let doc = r#"
-  query IntrospectionQuery {
-    __schema {
-      queryType { name }
-      mutationType { name }
-      subscriptionType { name }
-      types {
-        ...FullType
-      }
-      directives {
-        name
-        description
-        locations
-        args {
-          ...InputValue
-        }
-      }
-    }
-  }
-
-  fragment FullType on __Type {
-    kind
-    name
-    description
-    fields(includeDeprecated: true) {
-      name
-      description
-      args {
-        ...InputValue
-      }
-      type {
-        ...TypeRef
-      }
-      isDeprecated
-      deprecationReason
-    }
-    inputFields {
-      ...InputValue
-    }
-    interfaces {
-      ...TypeRef
-    }
-    enumValues(includeDeprecated: true) {
-      name
-      description
-      isDeprecated
-      deprecationReason
-    }
-    possibleTypes {
-      ...TypeRef
-    }
-  }
-
-  fragment InputValue on __InputValue {
-    name
-    description
-    type { ...TypeRef }
-    defaultValue
-  }
-
-  fragment TypeRef on __Type {
-    kind
-    name
-    ofType {
-      kind
-      name
-      ofType {
-        kind
-        name
-        ofType {
-          kind
-          name
-          ofType {
-            kind
-            name
-            ofType {
-              kind
-              name
-              ofType {
-                kind
-                name
-                ofType {
-                  kind
-                  name
-                }
-              }
-            }
-          }
-        }
-      }
-    }
-  }
-"#;
-
-    b.iter(|| execute(doc, None, &schema, &Variables::new(), &database));
+    b.iter(|| execute(INTROSPECTION, None, &schema, &Variables::new(), &database));
 }
 
+benchmark_group!(parser, parse_hero_friends, parse_introspection, parse_many_operations);
+benchmark_group!(validator, validate_fragments, validate_introspection, validate_many_operations);
+benchmark_group!(executor, execute_hero_name, execute_variables, execute_nested_friends, execute_introspection);
 benchmark_group!(queries, query_type_name, introspection_query);
-benchmark_main!(queries);
+benchmark_main!(parser, validator, executor, queries);
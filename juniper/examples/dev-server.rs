@@ -0,0 +1,23 @@
+extern crate juniper;
+
+use std::env;
+use std::net::SocketAddr;
+
+use juniper::{EmptyMutation, RootNode};
+use juniper::dev_server::DevServer;
+use juniper::http::context::{ContextError, RequestMetadata};
+use juniper::tests::model::Database;
+
+fn context_factory(_: &RequestMetadata) -> Result<Database, ContextError> {
+    Ok(Database::new())
+}
+
+fn main() {
+    let root_node = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+    let host = env::var("LISTEN").unwrap_or("0.0.0.0:8080".to_owned());
+    let addr: SocketAddr = host.parse().expect("LISTEN is not a socket address");
+
+    println!("GraphQL server started on {}", host);
+    DevServer::new(root_node, context_factory).run(&addr).unwrap();
+}
@@ -7,6 +7,7 @@ use std::slice;
 
 use executor::Variables;
 use parser::Spanning;
+use value::{ScalarValue, DefaultScalarValue};
 
 /// A type literal in the syntax tree
 ///
@@ -34,9 +35,12 @@ pub enum Type<'a> {
 ///
 /// Lists and objects variants are _spanned_, i.e. they contain a reference to
 /// their position in the source file, if available.
+///
+/// Scalars that don't map to any of the built-in variants are stored in the
+/// `Scalar` variant, see `ScalarValue`.
 #[derive(Clone, PartialEq, Debug)]
 #[allow(missing_docs)]
-pub enum InputValue {
+pub enum InputValue<S = DefaultScalarValue> {
     Null,
     Int(i32),
     Float(f64),
@@ -44,46 +48,47 @@ pub enum InputValue {
     Boolean(bool),
     Enum(String),
     Variable(String),
-    List(Vec<Spanning<InputValue>>),
-    Object(Vec<(Spanning<String>, Spanning<InputValue>)>),
+    List(Vec<Spanning<InputValue<S>>>),
+    Object(Vec<(Spanning<String>, Spanning<InputValue<S>>)>),
+    Scalar(S),
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct VariableDefinition<'a> {
+pub struct VariableDefinition<'a, S = DefaultScalarValue> {
     pub var_type: Spanning<Type<'a>>,
-    pub default_value: Option<Spanning<InputValue>>,
+    pub default_value: Option<Spanning<InputValue<S>>>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct Arguments<'a> {
-    pub items: Vec<(Spanning<&'a str>, Spanning<InputValue>)>,
+pub struct Arguments<'a, S = DefaultScalarValue> {
+    pub items: Vec<(Spanning<&'a str>, Spanning<InputValue<S>>)>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct VariableDefinitions<'a> {
-    pub items: Vec<(Spanning<&'a str>, VariableDefinition<'a>)>,
+pub struct VariableDefinitions<'a, S = DefaultScalarValue> {
+    pub items: Vec<(Spanning<&'a str>, VariableDefinition<'a, S>)>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct Field<'a> {
+pub struct Field<'a, S = DefaultScalarValue> {
     pub alias: Option<Spanning<&'a str>>,
     pub name: Spanning<&'a str>,
-    pub arguments: Option<Spanning<Arguments<'a>>>,
-    pub directives: Option<Vec<Spanning<Directive<'a>>>>,
-    pub selection_set: Option<Vec<Selection<'a>>>,
+    pub arguments: Option<Spanning<Arguments<'a, S>>>,
+    pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    pub selection_set: Option<Vec<Selection<'a, S>>>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct FragmentSpread<'a> {
+pub struct FragmentSpread<'a, S = DefaultScalarValue> {
     pub name: Spanning<&'a str>,
-    pub directives: Option<Vec<Spanning<Directive<'a>>>>,
+    pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct InlineFragment<'a> {
+pub struct InlineFragment<'a, S = DefaultScalarValue> {
     pub type_condition: Option<Spanning<&'a str>>,
-    pub directives: Option<Vec<Spanning<Directive<'a>>>>,
-    pub selection_set: Vec<Selection<'a>>,
+    pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    pub selection_set: Vec<Selection<'a, S>>,
 }
 
 /// Entry in a GraphQL selection set
@@ -103,16 +108,16 @@ pub struct InlineFragment<'a> {
 /// ```
 #[derive(Clone, PartialEq, Debug)]
 #[allow(missing_docs)]
-pub enum Selection<'a> {
-    Field(Spanning<Field<'a>>),
-    FragmentSpread(Spanning<FragmentSpread<'a>>),
-    InlineFragment(Spanning<InlineFragment<'a>>),
+pub enum Selection<'a, S = DefaultScalarValue> {
+    Field(Spanning<Field<'a, S>>),
+    FragmentSpread(Spanning<FragmentSpread<'a, S>>),
+    InlineFragment(Spanning<InlineFragment<'a, S>>),
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct Directive<'a> {
+pub struct Directive<'a, S = DefaultScalarValue> {
     pub name: Spanning<&'a str>,
-    pub arguments: Option<Spanning<Arguments<'a>>>,
+    pub arguments: Option<Spanning<Arguments<'a, S>>>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -122,29 +127,33 @@ pub enum OperationType {
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct Operation<'a> {
+pub struct Operation<'a, S = DefaultScalarValue> {
     pub operation_type: OperationType,
     pub name: Option<Spanning<&'a str>>,
-    pub variable_definitions: Option<Spanning<VariableDefinitions<'a>>>,
-    pub directives: Option<Vec<Spanning<Directive<'a>>>>,
-    pub selection_set: Vec<Selection<'a>>,
+    pub variable_definitions: Option<Spanning<VariableDefinitions<'a, S>>>,
+    pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    pub selection_set: Vec<Selection<'a, S>>,
 }
 
 #[derive(Clone, PartialEq, Debug)]
-pub struct Fragment<'a> {
+pub struct Fragment<'a, S = DefaultScalarValue> {
     pub name: Spanning<&'a str>,
     pub type_condition: Spanning<&'a str>,
-    pub directives: Option<Vec<Spanning<Directive<'a>>>>,
-    pub selection_set: Vec<Selection<'a>>,
+    pub directives: Option<Vec<Spanning<Directive<'a, S>>>>,
+    pub selection_set: Vec<Selection<'a, S>>,
 }
 
+/// An operation or fragment defined in a document
 #[derive(Clone, PartialEq, Debug)]
-pub enum Definition<'a> {
-    Operation(Spanning<Operation<'a>>),
-    Fragment(Spanning<Fragment<'a>>),
+pub enum Definition<'a, S = DefaultScalarValue> {
+    /// A query or mutation
+    Operation(Spanning<Operation<'a, S>>),
+    /// A named fragment
+    Fragment(Spanning<Fragment<'a, S>>),
 }
 
-pub type Document<'a> = Vec<Definition<'a>>;
+/// A parsed GraphQL document, with its definitions in the order of the source
+pub type Document<'a, S = DefaultScalarValue> = Vec<Definition<'a, S>>;
 
 /// Parse an unstructured input value into a Rust data type.
 ///
@@ -152,15 +161,24 @@ pub type Document<'a> = Vec<Definition<'a>>;
 /// automatically by the convenience macros `graphql_enum!` and
 /// `graphql_scalar!`. Must be implemented manually when manually exposing new
 /// enums or scalars.
-pub trait FromInputValue: Sized {
+pub trait FromInputValue<S = DefaultScalarValue>: Sized {
     /// Performs the conversion.
-    fn from(v: &InputValue) -> Option<Self>;
+    fn from(v: &InputValue<S>) -> Option<Self>;
+
+    /// Performs the conversion for an argument or input field that was
+    /// omitted entirely.
+    ///
+    /// Defaults to converting an explicit `null`. Types that need to tell the
+    /// two apart, like `Nullable`, can override this.
+    fn from_implicit_null() -> Option<Self> {
+        Self::from(&InputValue::Null)
+    }
 }
 
 /// Losslessly clones a Rust data type into an InputValue.
-pub trait ToInputValue: Sized {
+pub trait ToInputValue<S = DefaultScalarValue>: Sized {
     /// Performs the conversion.
-    fn to(&self) -> InputValue;
+    fn to(&self) -> InputValue<S>;
 }
 
 impl<'a> Type<'a> {
@@ -204,31 +222,34 @@ impl<'a> fmt::Display for Type<'a> {
     }
 }
 
-impl InputValue {
+impl<S> InputValue<S> where S: ScalarValue {
     /// Construct a null value.
-    pub fn null() -> InputValue { InputValue::Null }
+    pub fn null() -> InputValue<S> { InputValue::Null }
 
     /// Construct an integer value.
-    pub fn int(i: i32) -> InputValue { InputValue::Int(i) }
+    pub fn int(i: i32) -> InputValue<S> { InputValue::Int(i) }
 
     /// Construct a floating point value.
-    pub fn float(f: f64) -> InputValue { InputValue::Float(f) }
+    pub fn float(f: f64) -> InputValue<S> { InputValue::Float(f) }
 
     /// Construct a boolean value.
-    pub fn boolean(b: bool) -> InputValue { InputValue::Boolean(b) }
+    pub fn boolean(b: bool) -> InputValue<S> { InputValue::Boolean(b) }
 
     /// Construct a string value.
-    pub fn string<T: AsRef<str>>(s: T) -> InputValue {
+    pub fn string<T: AsRef<str>>(s: T) -> InputValue<S> {
         InputValue::String(s.as_ref().to_owned())
     }
 
     /// Construct an enum value.
-    pub fn enum_value<T: AsRef<str>>(s: T) -> InputValue {
+    pub fn enum_value<T: AsRef<str>>(s: T) -> InputValue<S> {
         InputValue::Enum(s.as_ref().to_owned())
     }
 
+    /// Construct a custom scalar value.
+    pub fn scalar(s: S) -> InputValue<S> { InputValue::Scalar(s) }
+
     /// Construct a variable value.
-    pub fn variable<T: AsRef<str>>(v: T) -> InputValue {
+    pub fn variable<T: AsRef<str>>(v: T) -> InputValue<S> {
         InputValue::Variable(v.as_ref().to_owned())
     }
 
@@ -237,12 +258,12 @@ impl InputValue {
     /// Convenience function to make each `InputValue` in the input vector
     /// not contain any location information. Can be used from `ToInputValue`
     /// implementations, where no source code position information is available.
-    pub fn list(l: Vec<InputValue>) -> InputValue {
+    pub fn list(l: Vec<InputValue<S>>) -> InputValue<S> {
         InputValue::List(l.into_iter().map(Spanning::unlocated).collect())
     }
 
     /// Construct a located list.
-    pub fn parsed_list(l: Vec<Spanning<InputValue>>) -> InputValue {
+    pub fn parsed_list(l: Vec<Spanning<InputValue<S>>>) -> InputValue<S> {
         InputValue::List(l)
     }
 
@@ -250,7 +271,7 @@ impl InputValue {
     ///
     /// Similar to `InputValue::list`, it makes each key and value in the given
     /// hash map not contain any location information.
-    pub fn object<K>(o: HashMap<K, InputValue>) -> InputValue
+    pub fn object<K>(o: HashMap<K, InputValue<S>>) -> InputValue<S>
         where K: AsRef<str> + Eq + Hash
     {
         InputValue::Object(
@@ -262,12 +283,12 @@ impl InputValue {
     }
 
     /// Construct a located object.
-    pub fn parsed_object(o: Vec<(Spanning<String>, Spanning<InputValue>)>) -> InputValue {
+    pub fn parsed_object(o: Vec<(Spanning<String>, Spanning<InputValue<S>>)>) -> InputValue<S> {
         InputValue::Object(o)
     }
 
     /// Resolve all variables to their values.
-    pub fn into_const(self, vars: &Variables) -> InputValue {
+    pub fn into_const(self, vars: &Variables<S>) -> InputValue<S> {
         match self {
             InputValue::Variable(v) => vars.get(&v)
                 .map_or_else(InputValue::null, Clone::clone),
@@ -282,8 +303,8 @@ impl InputValue {
     }
 
     /// Shorthand form of invoking `FromInputValue::from()`.
-    pub fn convert<T>(&self) -> Option<T> where T: FromInputValue {
-        <T as FromInputValue>::from(self)
+    pub fn convert<T>(&self) -> Option<T> where T: FromInputValue<S> {
+        <T as FromInputValue<S>>::from(self)
     }
 
     /// Does the value represent null?
@@ -318,6 +339,14 @@ impl InputValue {
         }
     }
 
+    /// View the underlying custom scalar value, if present.
+    pub fn as_scalar_value(&self) -> Option<&S> {
+        match *self {
+            InputValue::Scalar(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// View the underlying string value, if present.
     pub fn as_string_value(&self) -> Option<&str> {
         match *self {
@@ -330,7 +359,7 @@ impl InputValue {
     ///
     /// This constructs a new hashmap that contain references to the keys
     /// and values in `self`.
-    pub fn to_object_value(&self) -> Option<HashMap<&str, &InputValue>> {
+    pub fn to_object_value(&self) -> Option<HashMap<&str, &InputValue<S>>> {
         match *self {
             InputValue::Object(ref o) => Some(
                 o.iter().map(|&(ref sk, ref sv)| (sk.item.as_str(), &sv.item)).collect()),
@@ -342,7 +371,7 @@ impl InputValue {
     ///
     /// This constructs a new vector that contain references to the values
     /// in `self`.
-    pub fn to_list_value(&self) -> Option<Vec<&InputValue>> {
+    pub fn to_list_value(&self) -> Option<Vec<&InputValue<S>>> {
         match *self {
             InputValue::List(ref l) => Some(l.iter().map(|s| &s.item).collect()),
             _ => None,
@@ -360,7 +389,7 @@ impl InputValue {
     }
 
     /// Compare equality with another `InputValue` ignoring any source position information.
-    pub fn unlocated_eq(&self, other: &InputValue) -> bool {
+    pub fn unlocated_eq(&self, other: &InputValue<S>) -> bool {
         use InputValue::*;
 
         match (self, other) {
@@ -371,6 +400,7 @@ impl InputValue {
             (&Enum(ref s1), &Enum(ref s2)) |
             (&Variable(ref s1), &Variable(ref s2)) => s1 == s2,
             (&Boolean(b1), &Boolean(b2)) => b1 == b2,
+            (&Scalar(ref s1), &Scalar(ref s2)) => s1 == s2,
             (&List(ref l1), &List(ref l2)) =>
                 l1.iter().zip(l2.iter()).all(|(v1, v2)| v1.item.unlocated_eq(&v2.item)),
             (&Object(ref o1), &Object(ref o2)) =>
@@ -383,16 +413,58 @@ impl InputValue {
     }
 }
 
-impl fmt::Display for InputValue {
+impl<'a, S> From<&'a str> for InputValue<S> where S: ScalarValue {
+    fn from(s: &'a str) -> InputValue<S> {
+        InputValue::string(s)
+    }
+}
+
+impl<S> From<String> for InputValue<S> where S: ScalarValue {
+    fn from(s: String) -> InputValue<S> {
+        InputValue::String(s)
+    }
+}
+
+impl<S> From<bool> for InputValue<S> where S: ScalarValue {
+    fn from(b: bool) -> InputValue<S> {
+        InputValue::boolean(b)
+    }
+}
+
+impl<S> From<i32> for InputValue<S> where S: ScalarValue {
+    fn from(i: i32) -> InputValue<S> {
+        InputValue::int(i)
+    }
+}
+
+impl<S> From<f64> for InputValue<S> where S: ScalarValue {
+    fn from(f: f64) -> InputValue<S> {
+        InputValue::float(f)
+    }
+}
+
+impl<T, S> From<Option<T>> for InputValue<S> where InputValue<S>: From<T>, S: ScalarValue {
+    fn from(v: Option<T>) -> InputValue<S> {
+        match v {
+            Some(v) => v.into(),
+            None => InputValue::null(),
+        }
+    }
+}
+
+impl<S> fmt::Display for InputValue<S> where S: ScalarValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             InputValue::Null => write!(f, "null"),
             InputValue::Int(v) => write!(f, "{}", v),
-            InputValue::Float(v) => write!(f, "{}", v),
-            InputValue::String(ref v) => write!(f, "\"{}\"", v),
+            // Debug keeps the decimal point of whole numbers, so they're
+            // parsed back as floats
+            InputValue::Float(v) => write!(f, "{:?}", v),
+            InputValue::String(ref v) => write_string_literal(v, f),
             InputValue::Boolean(v) => write!(f, "{}", v),
             InputValue::Enum(ref v) => write!(f, "{}", v),
             InputValue::Variable(ref v) => write!(f, "${}", v),
+            InputValue::Scalar(ref v) => write!(f, "{}", v),
             InputValue::List(ref v) => {
                 try!(write!(f, "["));
 
@@ -418,16 +490,154 @@ impl fmt::Display for InputValue {
     }
 }
 
-impl<'a> Arguments<'a> {
-    pub fn into_iter(self) -> vec::IntoIter<(Spanning<&'a str>, Spanning<InputValue>)> {
+// Definitions and selections are printed on a single line, so a printed
+// document parses back into the same document, positions aside
+
+impl<'a, S> fmt::Display for Definition<'a, S> where S: ScalarValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Definition::Operation(Spanning { item: ref op, .. }) => {
+                try!(f.write_str(match op.operation_type {
+                    OperationType::Query => "query",
+                    OperationType::Mutation => "mutation",
+                }));
+
+                if let Some(ref name) = op.name {
+                    try!(write!(f, " {}", name.item));
+                }
+
+                if let Some(ref defs) = op.variable_definitions {
+                    for (i, &(ref name, ref def)) in defs.item.items.iter().enumerate() {
+                        try!(f.write_str(if i == 0 { "(" } else { ", " }));
+                        try!(write!(f, "${}: {}", name.item, def.var_type.item));
+
+                        if let Some(ref default) = def.default_value {
+                            try!(write!(f, " = {}", default.item));
+                        }
+                    }
+
+                    if !defs.item.items.is_empty() {
+                        try!(f.write_str(")"));
+                    }
+                }
+
+                try!(write_directives(&op.directives, f));
+                write_selection_set(&op.selection_set, f)
+            },
+            Definition::Fragment(Spanning { item: ref fragment, .. }) => {
+                try!(write!(f, "fragment {} on {}", fragment.name.item, fragment.type_condition.item));
+                try!(write_directives(&fragment.directives, f));
+                write_selection_set(&fragment.selection_set, f)
+            },
+        }
+    }
+}
+
+impl<'a, S> fmt::Display for Selection<'a, S> where S: ScalarValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Selection::Field(Spanning { item: ref field, .. }) => {
+                if let Some(ref alias) = field.alias {
+                    try!(write!(f, "{}: ", alias.item));
+                }
+
+                try!(f.write_str(field.name.item));
+                try!(write_arguments(&field.arguments, f));
+                try!(write_directives(&field.directives, f));
+
+                match field.selection_set {
+                    Some(ref selection_set) => write_selection_set(selection_set, f),
+                    None => Ok(()),
+                }
+            },
+            Selection::FragmentSpread(Spanning { item: ref spread, .. }) => {
+                try!(write!(f, "...{}", spread.name.item));
+                write_directives(&spread.directives, f)
+            },
+            Selection::InlineFragment(Spanning { item: ref fragment, .. }) => {
+                try!(f.write_str("..."));
+
+                if let Some(ref type_condition) = fragment.type_condition {
+                    try!(write!(f, " on {}", type_condition.item));
+                }
+
+                try!(write_directives(&fragment.directives, f));
+                write_selection_set(&fragment.selection_set, f)
+            },
+        }
+    }
+}
+
+fn write_selection_set<S>(selection_set: &[Selection<S>], f: &mut fmt::Formatter) -> fmt::Result
+    where S: ScalarValue
+{
+    try!(f.write_str(" {"));
+
+    for selection in selection_set {
+        try!(write!(f, " {}", selection));
+    }
+
+    f.write_str(" }")
+}
+
+fn write_arguments<S>(arguments: &Option<Spanning<Arguments<S>>>, f: &mut fmt::Formatter) -> fmt::Result
+    where S: ScalarValue
+{
+    if let Some(ref arguments) = *arguments {
+        for (i, &(ref name, ref value)) in arguments.item.items.iter().enumerate() {
+            try!(f.write_str(if i == 0 { "(" } else { ", " }));
+            try!(write!(f, "{}: {}", name.item, value.item));
+        }
+
+        if !arguments.item.items.is_empty() {
+            try!(f.write_str(")"));
+        }
+    }
+
+    Ok(())
+}
+
+fn write_directives<S>(directives: &Option<Vec<Spanning<Directive<S>>>>, f: &mut fmt::Formatter) -> fmt::Result
+    where S: ScalarValue
+{
+    if let Some(ref directives) = *directives {
+        for directive in directives {
+            try!(write!(f, " @{}", directive.item.name.item));
+            try!(write_arguments(&directive.item.arguments, f));
+        }
+    }
+
+    Ok(())
+}
+
+fn write_string_literal(s: &str, f: &mut fmt::Formatter) -> fmt::Result {
+    try!(f.write_str("\""));
+
+    for ch in s.chars() {
+        match ch {
+            '"' => try!(f.write_str("\\\"")),
+            '\\' => try!(f.write_str("\\\\")),
+            '\n' => try!(f.write_str("\\n")),
+            '\r' => try!(f.write_str("\\r")),
+            '\t' => try!(f.write_str("\\t")),
+            c if c < ' ' => try!(write!(f, "\\u{:04x}", c as u32)),
+            c => try!(write!(f, "{}", c)),
+        }
+    }
+
+    f.write_str("\"")
+}
+
+impl<'a, S> Arguments<'a, S> {
+    pub fn into_iter(self) -> vec::IntoIter<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.into_iter()
     }
 
-    pub fn iter(&self) -> slice::Iter<(Spanning<&'a str>, Spanning<InputValue>)> {
+    pub fn iter(&self) -> slice::Iter<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.iter()
     }
 
-    pub fn iter_mut(&mut self) -> slice::IterMut<(Spanning<&'a str>, Spanning<InputValue>)> {
+    pub fn iter_mut(&mut self) -> slice::IterMut<(Spanning<&'a str>, Spanning<InputValue<S>>)> {
         self.items.iter_mut()
     }
 
@@ -435,7 +645,7 @@ impl<'a> Arguments<'a> {
         self.items.len()
     }
 
-    pub fn get(&self, key: &str) -> Option<&Spanning<InputValue>> {
+    pub fn get(&self, key: &str) -> Option<&Spanning<InputValue<S>>> {
         self.items
             .iter()
             .filter(|&&(ref k, _)| k.item == key)
@@ -444,8 +654,8 @@ impl<'a> Arguments<'a> {
     }
 }
 
-impl<'a> VariableDefinitions<'a> {
-    pub fn iter(&self) -> slice::Iter<(Spanning<&'a str>, VariableDefinition)> {
+impl<'a, S> VariableDefinitions<'a, S> {
+    pub fn iter(&self) -> slice::Iter<(Spanning<&'a str>, VariableDefinition<S>)> {
         self.items.iter()
     }
 }
@@ -457,36 +667,36 @@ mod tests {
 
     #[test]
     fn test_input_value_fmt() {
-        let value = InputValue::null();
+        let value: InputValue = InputValue::null();
         assert_eq!(format!("{}", value), "null");
 
-        let value = InputValue::int(123);
+        let value: InputValue = InputValue::int(123);
         assert_eq!(format!("{}", value), "123");
 
-        let value = InputValue::float(12.3);
+        let value: InputValue = InputValue::float(12.3);
         assert_eq!(format!("{}", value), "12.3");
 
-        let value = InputValue::string("FOO".to_owned());
+        let value: InputValue = InputValue::string("FOO".to_owned());
         assert_eq!(format!("{}", value), "\"FOO\"");
 
-        let value = InputValue::boolean(true);
+        let value: InputValue = InputValue::boolean(true);
         assert_eq!(format!("{}", value), "true");
 
-        let value = InputValue::enum_value("BAR".to_owned());
+        let value: InputValue = InputValue::enum_value("BAR".to_owned());
         assert_eq!(format!("{}", value), "BAR");
 
-        let value = InputValue::variable("baz".to_owned());
+        let value: InputValue = InputValue::variable("baz".to_owned());
         assert_eq!(format!("{}", value), "$baz");
 
         let list = vec![InputValue::int(1), InputValue::int(2)];
-        let value = InputValue::list(list);
+        let value: InputValue = InputValue::list(list);
         assert_eq!(format!("{}", value), "[1, 2]");
 
         let object = vec![
             (Spanning::unlocated("foo".to_owned()), Spanning::unlocated(InputValue::int(1))),
             (Spanning::unlocated("bar".to_owned()), Spanning::unlocated(InputValue::int(2))),
         ];
-        let value = InputValue::parsed_object(object);
+        let value: InputValue = InputValue::parsed_object(object);
         assert_eq!(format!("{}", value), "{foo: 1, bar: 2}");
     }
 }
@@ -0,0 +1,277 @@
+//! Fixtures for measuring the parser, validator, and executor
+//!
+//! `benches/bench.rs` measures each stage of executing a query on its own,
+//! using the schema and documents defined here, so a change to e.g. the
+//! validator can be judged by the numbers of the validator alone. Run the
+//! benchmarks with:
+//!
+//! ```text
+//! cargo bench --features expose-test-schema
+//! ```
+//!
+//! The stages can be run one by one with the same functions the benchmarks
+//! use:
+//!
+//! ```rust
+//! use juniper::Variables;
+//! use juniper::bench;
+//! use juniper::tests::model::Database;
+//!
+//! let schema = bench::schema();
+//! let database = Database::new();
+//!
+//! let document = bench::parse(bench::HERO_FRIENDS).unwrap();
+//! assert!(bench::validate(&schema, &document).is_empty());
+//!
+//! let (_, errors) = bench::execute(&schema, &document, None, &Variables::new(), &database).unwrap();
+//! assert!(errors.is_empty());
+//! ```
+
+use std::borrow::Cow;
+
+use ast::Document;
+use executor::{execute_validated_query, ExecutionError, Variables};
+use parser::parse_document_source;
+use schema::model::RootNode;
+use tests::model::Database;
+use types::scalars::EmptyMutation;
+use validation::{ValidatorContext, visit_all_rules, RuleError};
+use value::Value;
+
+use GraphQLError;
+
+/// The Star Wars schema all fixtures are written for
+pub type Schema = RootNode<'static, Database, EmptyMutation<Database>>;
+
+/// A single field of a single object
+pub const HERO_NAME: &'static str = "query HeroName { hero { name } }";
+
+/// Lists of interfaces, nested two levels deep
+pub const HERO_FRIENDS: &'static str = r#"
+    query HeroFriends {
+      hero {
+        id
+        name
+        friends {
+          name
+          appearsIn
+          friends {
+            name
+          }
+        }
+      }
+    }"#;
+
+/// Fragment spreads, inline fragments on concrete types, and aliases
+pub const FRAGMENTS: &'static str = r#"
+    query Fragments {
+      luke: human(id: "1000") { ...CharacterFields }
+      r2: droid(id: "2001") { ...CharacterFields }
+      hero {
+        ...CharacterFields
+        ... on Human { homePlanet }
+        ... on Droid { primaryFunction }
+      }
+    }
+
+    fragment CharacterFields on Character {
+      id
+      name
+      appearsIn
+      friends { name }
+    }"#;
+
+/// Variables with default values, and `@skip` and `@include` directives
+pub const VARIABLES: &'static str = r#"
+    query Variables($id: String = "1000", $withFriends: Boolean = true, $short: Boolean = false) {
+      human(id: $id) {
+        name
+        homePlanet @skip(if: $short)
+        friends @include(if: $withFriends) {
+          name
+        }
+      }
+    }"#;
+
+/// The query GraphiQL sends to learn about the schema
+pub const INTROSPECTION: &'static str = r#"
+    query IntrospectionQuery {
+      __schema {
+        queryType { name }
+        mutationType { name }
+        subscriptionType { name }
+        types {
+          ...FullType
+        }
+        directives {
+          name
+          description
+          locations
+          args {
+            ...InputValue
+          }
+        }
+      }
+    }
+
+    fragment FullType on __Type {
+      kind
+      name
+      description
+      fields(includeDeprecated: true) {
+        name
+        description
+        args {
+          ...InputValue
+        }
+        type {
+          ...TypeRef
+        }
+        isDeprecated
+        deprecationReason
+      }
+      inputFields {
+        ...InputValue
+      }
+      interfaces {
+        ...TypeRef
+      }
+      enumValues(includeDeprecated: true) {
+        name
+        description
+        isDeprecated
+        deprecationReason
+      }
+      possibleTypes {
+        ...TypeRef
+      }
+    }
+
+    fragment InputValue on __InputValue {
+      name
+      description
+      type { ...TypeRef }
+      defaultValue
+    }
+
+    fragment TypeRef on __Type {
+      kind
+      name
+      ofType {
+        kind
+        name
+        ofType {
+          kind
+          name
+          ofType {
+            kind
+            name
+            ofType {
+              kind
+              name
+              ofType {
+                kind
+                name
+                ofType {
+                  kind
+                  name
+                  ofType {
+                    kind
+                    name
+                  }
+                }
+              }
+            }
+          }
+        }
+      }
+    }"#;
+
+/// A query selecting the friends of the hero's friends, `depth` levels deep
+///
+/// The response grows exponentially with the depth, which makes this a
+/// benchmark of the executor rather than the parser or validator.
+pub fn nested_friends(depth: usize) -> String {
+    let mut query = String::from("query NestedFriends { hero { name ");
+
+    for _ in 0..depth {
+        query.push_str("friends { name ");
+    }
+    for _ in 0..depth {
+        query.push_str("} ");
+    }
+
+    query.push_str("} }");
+    query
+}
+
+/// A document with `count` operations using the same fragment
+///
+/// Documents like this, e.g. the persisted queries of a whole application,
+/// are dominated by the cost of parsing and validating them.
+pub fn many_operations(count: usize) -> String {
+    let mut document = String::new();
+
+    for i in 0..count {
+        document.push_str(&format!(
+            "query Operation{} {{ human(id: \"{}\") {{ ...HumanFields }} hero {{ id name }} }}\n",
+            i, 1000 + i % 5));
+    }
+
+    document.push_str("fragment HumanFields on Human { id name homePlanet friends { name appearsIn } }\n");
+    document
+}
+
+/// Create the schema the fixtures are written for
+pub fn schema() -> Schema {
+    RootNode::new(Database::new(), EmptyMutation::<Database>::new())
+}
+
+/// Parse a document
+pub fn parse(source: &str) -> Result<Document, GraphQLError> {
+    parse_document_source(source).map_err(GraphQLError::ParseError)
+}
+
+/// Validate a parsed document against the schema
+pub fn validate(schema: &Schema, document: &Document) -> Vec<RuleError> {
+    let mut ctx = ValidatorContext::new(&schema.schema, document);
+    visit_all_rules(&mut ctx, document);
+    ctx.into_errors()
+}
+
+/// Execute a parsed and validated document
+pub fn execute<'a>(
+    schema: &Schema,
+    document: &Document,
+    operation_name: Option<&str>,
+    variables: &Variables,
+    database: &Database,
+)
+    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
+{
+    execute_validated_query(document, operation_name, schema, Cow::Borrowed(variables), database)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixtures_are_valid_and_execute_without_errors() {
+        let schema = schema();
+        let database = Database::new();
+
+        let many = many_operations(10);
+        let nested = nested_friends(3);
+        let documents = [HERO_NAME, HERO_FRIENDS, FRAGMENTS, VARIABLES, INTROSPECTION, &nested, &many];
+
+        for source in &documents {
+            let document = parse(source).unwrap();
+            assert_eq!(validate(&schema, &document), vec![]);
+
+            let operation_name = if *source == many { Some("Operation7") } else { None };
+            let (_, errors) = execute(&schema, &document, operation_name, &Variables::new(), &database).unwrap();
+            assert!(errors.is_empty());
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use ast::{Definition, Document, OperationType, Selection};
+use parser::parse_document_source;
+use prepared::select_operation;
+use schema::model::SchemaType;
+use validation::{ValidatorContext, visit_all_rules};
+use value::{DefaultScalarValue, ScalarValue, Value};
+
+// Distinct introspection queries are few: the ones of GraphiQL, the
+// playground and codegen tools
+const INTROSPECTION_CACHE_CAPACITY: usize = 8;
+
+/// A thread-safe cache of parsed and validated documents
+///
+/// Parsing and validating a document often takes longer than executing it.
+/// Servers answering the same queries over and over again can attach a cache
+/// to their schema, and `execute` (and with it `http::GraphQLRequest::execute`
+/// and all integrations) will then only do that work the first time it sees
+/// a query:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use juniper::{DocumentCache, EmptyMutation, RootNode, Variables};
+///
+/// struct Query;
+///
+/// graphql_object!(Query: () |&self| {
+///     field answer() -> i32 { 42 }
+/// });
+///
+/// # fn main() {
+/// let root_node = RootNode::new(Query, EmptyMutation::<()>::new())
+///     .with_document_cache(DocumentCache::new(256));
+///
+/// for _ in 0..3 {
+///     juniper::execute("{ answer }", None, &root_node, &Variables::new(), &()).unwrap();
+/// }
+/// assert_eq!(root_node.document_cache().unwrap().len(), 1);
+/// # }
+/// ```
+///
+/// Documents are looked up by their exact text, so queries differing only in
+/// whitespace are cached separately. Once the cache is full, the least
+/// recently used document is evicted. Documents that fail to parse or
+/// validate are never cached.
+pub struct DocumentCache<S = DefaultScalarValue> {
+    capacity: usize,
+    state: Mutex<CacheState<S>>,
+}
+
+struct CacheState<S> {
+    entries: HashMap<u64, CacheEntry<S>>,
+    clock: u64,
+}
+
+struct CacheEntry<S> {
+    document: Arc<CachedDocument<S>>,
+    last_used: u64,
+}
+
+#[doc(hidden)]
+pub struct CachedDocument<S> {
+    // Borrows from `source`, which is why it's declared, and dropped, first
+    document: Document<'static, S>,
+    source: Box<str>,
+}
+
+/// The results of introspection queries, see `RootNode::with_introspection_cache`
+#[doc(hidden)]
+pub struct IntrospectionCache<S> {
+    results: Mutex<Vec<(String, Option<String>, Value<S>)>>,
+}
+
+impl<S> DocumentCache<S> where S: ScalarValue {
+    /// Create a cache holding at most `capacity` documents
+    pub fn new(capacity: usize) -> DocumentCache<S> {
+        DocumentCache {
+            capacity: capacity,
+            state: Mutex::new(CacheState {
+                entries: HashMap::new(),
+                clock: 0,
+            }),
+        }
+    }
+
+    /// The maximum number of documents in the cache
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of documents in the cache
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Is the cache empty?
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all documents from the cache
+    pub fn clear(&self) {
+        self.lock().entries.clear();
+    }
+
+    /// Look up a document, parsing and validating it against the schema if
+    /// it isn't cached yet
+    ///
+    /// Returns `None` if the document doesn't parse or validate.
+    #[doc(hidden)]
+    pub fn get(&self, source: &str, schema: &SchemaType<S>) -> Option<Arc<CachedDocument<S>>> {
+        let key = hash(source);
+
+        {
+            let mut state = self.lock();
+            state.clock += 1;
+            let now = state.clock;
+
+            if let Some(entry) = state.entries.get_mut(&key) {
+                if &*entry.document.source == source {
+                    entry.last_used = now;
+                    return Some(entry.document.clone());
+                }
+            }
+        }
+
+        // Don't hold the lock while parsing, other queries may be served from
+        // the cache in the meantime
+        let document = match CachedDocument::new(source, schema) {
+            Some(document) => Arc::new(document),
+            None => return None,
+        };
+
+        if self.capacity > 0 {
+            let mut state = self.lock();
+            state.clock += 1;
+            let now = state.clock;
+
+            if state.entries.len() >= self.capacity && !state.entries.contains_key(&key) {
+                let oldest = state.entries.iter()
+                    .min_by_key(|&(_, entry)| entry.last_used)
+                    .map(|(key, _)| *key);
+                if let Some(oldest) = oldest {
+                    state.entries.remove(&oldest);
+                }
+            }
+
+            state.entries.insert(key, CacheEntry {
+                document: document.clone(),
+                last_used: now,
+            });
+        }
+
+        Some(document)
+    }
+
+    fn lock<'c>(&'c self) -> MutexGuard<'c, CacheState<S>> {
+        // The state is never left half-updated, so a panic elsewhere doesn't
+        // make it unusable
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl<S> CachedDocument<S> where S: ScalarValue {
+    fn new(source: &str, schema: &SchemaType<S>) -> Option<CachedDocument<S>> {
+        let source: Box<str> = source.into();
+
+        // The document borrows from the heap allocation of `source`, which
+        // doesn't move when `source` does, and lives as long as the document
+        // since both are only dropped together
+        let static_source: &'static str = unsafe { &*(&*source as *const str) };
+        let document = match parse_document_source(static_source) {
+            Ok(document) => document,
+            Err(_) => return None,
+        };
+
+        {
+            let mut ctx = ValidatorContext::new(schema, &document);
+            visit_all_rules(&mut ctx, &document);
+
+            if !ctx.into_errors().is_empty() {
+                return None;
+            }
+        }
+
+        Some(CachedDocument {
+            document: document,
+            source: source,
+        })
+    }
+
+    /// The parsed document, which can't outlive the cached document
+    pub fn document<'d>(&'d self) -> &'d Document<'d, S> {
+        &self.document
+    }
+}
+
+impl<S> IntrospectionCache<S> where S: ScalarValue {
+    #[doc(hidden)]
+    pub fn new() -> IntrospectionCache<S> {
+        IntrospectionCache {
+            results: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Does the result of the operation only depend on the schema?
+    ///
+    /// That's the case for queries selecting nothing but `__schema`, `__type`
+    /// and `__typename` at the top level, without any variables.
+    #[doc(hidden)]
+    pub fn is_cacheable(document: &Document<S>, operation_name: Option<&str>) -> bool {
+        let op = match select_operation(document, operation_name).map(|i| &document[i]) {
+            Ok(&Definition::Operation(ref op)) => &op.item,
+            _ => return false,
+        };
+
+        op.operation_type == OperationType::Query
+            && op.variable_definitions.as_ref().map_or(true, |defs| defs.item.items.is_empty())
+            && op.selection_set.iter().all(|selection| match *selection {
+                Selection::Field(ref field) =>
+                    ["__schema", "__type", "__typename"].contains(&field.item.name.item),
+                _ => false,
+            })
+    }
+
+    #[doc(hidden)]
+    pub fn get(&self, source: &str, operation_name: Option<&str>) -> Option<Value<S>> {
+        let results = self.results.lock().unwrap_or_else(|err| err.into_inner());
+
+        results.iter()
+            .find(|&&(ref s, ref op, _)| s == source && op.as_ref().map(String::as_str) == operation_name)
+            .map(|&(_, _, ref value)| value.clone())
+    }
+
+    #[doc(hidden)]
+    pub fn insert(&self, source: &str, operation_name: Option<&str>, value: Value<S>) {
+        let mut results = self.results.lock().unwrap_or_else(|err| err.into_inner());
+
+        if results.len() >= INTROSPECTION_CACHE_CAPACITY {
+            results.remove(0);
+        }
+        results.push((source.to_owned(), operation_name.map(str::to_owned), value));
+    }
+}
+
+fn hash(source: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use executor::Variables;
+    use http::GraphQLRequest;
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::DocumentCache;
+
+    #[test]
+    fn caches_valid_documents() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let cache = DocumentCache::new(2);
+
+        let first = cache.get("{ hero { name } }", &schema.schema).unwrap();
+        let second = cache.get("{ hero { name } }", &schema.schema).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(first.document().len(), 1);
+
+        assert!(cache.get("{ hero { name }", &schema.schema).is_none());
+        assert!(cache.get("{ hero { nope } }", &schema.schema).is_none());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_documents() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let cache = DocumentCache::new(2);
+
+        let a = cache.get("{ hero { id } }", &schema.schema).unwrap();
+        cache.get("{ hero { name } }", &schema.schema).unwrap();
+        cache.get("{ hero { id } }", &schema.schema).unwrap();
+        cache.get("{ human(id: \"1000\") { name } }", &schema.schema).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        assert!(Arc::ptr_eq(&a, &cache.get("{ hero { id } }", &schema.schema).unwrap()));
+        assert_eq!(cache.len(), 2);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn is_consulted_by_http_requests() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new())
+            .with_document_cache(DocumentCache::new(16));
+        let database = Database::new();
+
+        let request = GraphQLRequest::new("{ hero { name } }".to_owned(), None, None);
+        let uncached = ::execute("{ hero { name } }", None, &schema, &Variables::new(), &database).unwrap();
+
+        for _ in 0..2 {
+            let response = request.execute(&schema, &database);
+            assert!(response.is_ok());
+            assert_eq!(*response.data().unwrap(), uncached.0);
+        }
+        assert_eq!(schema.document_cache().unwrap().len(), 1);
+
+        let invalid = GraphQLRequest::new("{ hero { nope } }".to_owned(), None, None);
+        assert!(!invalid.execute(&schema, &database).is_ok());
+        assert_eq!(schema.document_cache().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn caches_introspection_results() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new())
+            .with_introspection_cache();
+        let database = Database::new();
+        let query = "query IntrospectionQuery { __schema { queryType { name } } }";
+
+        let first = ::execute(query, None, &schema, &Variables::new(), &database).unwrap();
+        let cache = schema.introspection_cache.as_ref().unwrap();
+        assert_eq!(cache.get(query, None), Some(first.0.clone()));
+        assert_eq!(::execute(query, None, &schema, &Variables::new(), &database).unwrap(), first);
+
+        let uncacheable = [
+            "{ __schema { queryType { name } } hero { name } }",
+            "query Q($name: String!) { __type(name: $name) { name } }",
+        ];
+        for query in &uncacheable {
+            let mut variables = Variables::new();
+            variables.insert("name".to_owned(), ::InputValue::string("Droid"));
+
+            ::execute(query, None, &schema, &variables, &database).unwrap();
+            assert_eq!(cache.get(query, None), None);
+        }
+    }
+}
@@ -0,0 +1,269 @@
+//! Reporting which fields and arguments of a schema queries use
+//!
+//! Deprecating a field is easier when it's known which clients still use it.
+//! Given the queries clients send, e.g. their persisted queries, a
+//! `FieldCoverage` counts the documents that use every field and argument of
+//! the schema, and lists those that no document uses:
+//!
+//! ```rust
+//! #[macro_use] extern crate juniper;
+//! use juniper::{EmptyMutation, RootNode};
+//! use juniper::coverage::FieldCoverage;
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field hero(episode: Option<i32>) -> &str { "R2-D2" }
+//!     field villain() -> &str { "Vader" }
+//! });
+//!
+//! # fn main() {
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+//! let mut coverage = FieldCoverage::new(&schema);
+//!
+//! coverage.add_document(&schema, "{ hero }").unwrap();
+//! coverage.add_document(&schema, "query Hero { hero }").unwrap();
+//!
+//! assert_eq!(coverage.field_usage("Query", "hero").map(|usage| usage.documents), Some(2));
+//! assert_eq!(coverage.unused_fields(), vec![("Query", "villain")]);
+//! assert_eq!(coverage.unused_arguments(), vec![("Query", "hero", "episode")]);
+//! # }
+//! ```
+//!
+//! Documents are parsed and validated like queries before they're counted.
+//! Fields are counted on the type they're selected on, so a field selected on
+//! an interface counts for the interface, not for the objects implementing it.
+//! Introspection fields aren't counted.
+
+use std::collections::{BTreeMap, HashSet};
+
+use ast::{Directive, Field, InputValue};
+use parser::{parse_document_source, Spanning};
+use schema::meta::MetaType;
+use schema::model::RootNode;
+use types::base::GraphQLType;
+use validation::{visit, visit_all_rules, ValidatorContext, Visitor};
+use value::ScalarValue;
+
+use GraphQLError;
+
+/// Counts the documents using each field and argument of a schema
+#[derive(Debug, Clone, Default)]
+pub struct FieldCoverage {
+    documents: usize,
+    fields: BTreeMap<(String, String), FieldUsage>,
+}
+
+/// The usage of a single field
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldUsage {
+    /// The number of documents selecting the field
+    pub documents: usize,
+    /// The number of documents passing each argument of the field
+    pub arguments: BTreeMap<String, usize>,
+}
+
+impl FieldCoverage {
+    /// Construct a report covering the fields of the schema, none of them used
+    /// yet
+    pub fn new<QueryT, MutationT, S>(root_node: &RootNode<QueryT, MutationT, S>) -> FieldCoverage
+        where QueryT: GraphQLType<S>,
+              MutationT: GraphQLType<S>,
+              S: ScalarValue,
+    {
+        let mut fields = BTreeMap::new();
+
+        for meta_type in root_node.schema.concrete_type_list() {
+            let (type_name, type_fields) = match *meta_type {
+                MetaType::Object(ref meta) => (&meta.name, &meta.fields),
+                MetaType::Interface(ref meta) => (&meta.name, &meta.fields),
+                _ => continue,
+            };
+
+            if type_name.starts_with("__") || &**type_name == "_EmptyMutation" {
+                continue;
+            }
+
+            for field in type_fields.iter().filter(|f| !f.name.starts_with("__")) {
+                let arguments = field.arguments.as_ref()
+                    .map(|args| args.iter().map(|arg| (arg.name.to_string(), 0)).collect())
+                    .unwrap_or_default();
+
+                fields.insert(
+                    (type_name.to_string(), field.name.to_string()),
+                    FieldUsage { documents: 0, arguments: arguments });
+            }
+        }
+
+        FieldCoverage {
+            documents: 0,
+            fields: fields,
+        }
+    }
+
+    /// Count the fields and arguments a document uses
+    ///
+    /// Documents that don't parse or validate against the schema aren't
+    /// counted, and their errors are returned.
+    pub fn add_document<'a, QueryT, MutationT, S>(
+        &mut self,
+        root_node: &RootNode<QueryT, MutationT, S>,
+        document_source: &'a str,
+    )
+        -> Result<(), GraphQLError<'a>>
+        where QueryT: GraphQLType<S>,
+              MutationT: GraphQLType<S>,
+              S: ScalarValue,
+    {
+        let document = try!(parse_document_source(document_source));
+
+        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        visit_all_rules(&mut ctx, &document);
+
+        let errors = ctx.into_errors();
+        if !errors.is_empty() {
+            return Err(GraphQLError::ValidationError(errors));
+        }
+
+        let mut visitor = CoverageVisitor {
+            fields: Vec::new(),
+            directive_depth: 0,
+            used_fields: HashSet::new(),
+            used_arguments: HashSet::new(),
+        };
+        visit(&mut visitor, &mut ValidatorContext::new(&root_node.schema, &document), &document);
+
+        self.documents += 1;
+
+        for key in visitor.used_fields {
+            if let Some(usage) = self.fields.get_mut(&key) {
+                usage.documents += 1;
+            }
+        }
+
+        for (key, arg_name) in visitor.used_arguments {
+            if let Some(count) = self.fields.get_mut(&key).and_then(|usage| usage.arguments.get_mut(&arg_name)) {
+                *count += 1;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The number of documents added
+    pub fn documents(&self) -> usize {
+        self.documents
+    }
+
+    /// The usage of a field of a type
+    pub fn field_usage(&self, type_name: &str, field_name: &str) -> Option<&FieldUsage> {
+        self.fields.get(&(type_name.to_owned(), field_name.to_owned()))
+    }
+
+    /// The type and field names of the fields no document selects, in order
+    pub fn unused_fields(&self) -> Vec<(&str, &str)> {
+        self.fields.iter()
+            .filter(|&(_, usage)| usage.documents == 0)
+            .map(|(&(ref type_name, ref field_name), _)| (type_name.as_str(), field_name.as_str()))
+            .collect()
+    }
+
+    /// The type, field and argument names of the arguments no document
+    /// passes, in order
+    ///
+    /// This includes the arguments of unused fields.
+    pub fn unused_arguments(&self) -> Vec<(&str, &str, &str)> {
+        self.fields.iter()
+            .flat_map(|(&(ref type_name, ref field_name), usage)| {
+                usage.arguments.iter()
+                    .filter(|&(_, count)| *count == 0)
+                    .map(move |(arg_name, _)| (type_name.as_str(), field_name.as_str(), arg_name.as_str()))
+            })
+            .collect()
+    }
+}
+
+// Collects the fields and arguments used by a single document, so documents
+// using a field more than once count once
+struct CoverageVisitor {
+    fields: Vec<(String, String)>,
+    directive_depth: usize,
+    used_fields: HashSet<(String, String)>,
+    used_arguments: HashSet<((String, String), String)>,
+}
+
+impl<'a, S> Visitor<'a, S> for CoverageVisitor where S: ScalarValue {
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
+        let type_name = ctx.parent_type().and_then(|t| t.name()).unwrap_or_default();
+        let key = (type_name.to_owned(), field.item.name.item.to_owned());
+
+        self.used_fields.insert(key.clone());
+        self.fields.push(key);
+    }
+
+    fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
+        self.fields.pop();
+    }
+
+    fn enter_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
+        self.directive_depth += 1;
+    }
+
+    fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
+        self.directive_depth -= 1;
+    }
+
+    fn enter_argument(&mut self, _: &mut ValidatorContext<'a, S>, &(ref name, _): &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
+        if self.directive_depth > 0 {
+            return;
+        }
+
+        if let Some(field) = self.fields.last() {
+            self.used_arguments.insert((field.clone(), name.item.to_owned()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::FieldCoverage;
+
+    #[test]
+    fn counts_documents_using_fields_and_arguments() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let mut coverage = FieldCoverage::new(&schema);
+
+        coverage.add_document(&schema, r#"
+            { hero { name friends { name } } human(id: "1000") { ...HumanFields } }
+            fragment HumanFields on Human { homePlanet name @include(if: true) }
+        "#).unwrap();
+        coverage.add_document(&schema, "{ hero(episode: EMPIRE) { name } __schema { types { name } } }").unwrap();
+        assert!(coverage.add_document(&schema, "{ nope }").is_err());
+
+        assert_eq!(coverage.documents(), 2);
+
+        let hero = coverage.field_usage("Query", "hero").unwrap();
+        assert_eq!(hero.documents, 2);
+        assert_eq!(hero.arguments.get("episode"), Some(&1));
+        assert_eq!(coverage.field_usage("Character", "name").map(|u| u.documents), Some(2));
+        assert_eq!(coverage.field_usage("Human", "name").map(|u| u.documents), Some(1));
+        assert_eq!(coverage.field_usage("Human", "homePlanet").map(|u| u.documents), Some(1));
+        assert_eq!(coverage.field_usage("Droid", "name").map(|u| u.documents), Some(0));
+        assert!(coverage.field_usage("__Schema", "types").is_none());
+
+        let unused = coverage.unused_fields();
+        assert!(unused.contains(&("Query", "droid")));
+        assert!(unused.contains(&("Character", "id")));
+        assert!(!unused.contains(&("Query", "human")));
+        assert!(!unused.iter().any(|&(type_name, _)| type_name.starts_with("__")));
+
+        let unused_args = coverage.unused_arguments();
+        assert!(unused_args.contains(&("Query", "droid", "id")));
+        assert!(!unused_args.contains(&("Query", "human", "id")));
+        assert!(!unused_args.contains(&("Query", "hero", "episode")));
+    }
+}
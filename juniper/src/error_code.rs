@@ -0,0 +1,82 @@
+use std::fmt;
+
+/// The codes of the errors Juniper itself reports
+///
+/// Every error that doesn't come from a resolver has one of these in the
+/// `code` field of its `extensions`, so clients can tell errors apart without
+/// matching on their messages, which may change between versions:
+///
+/// ```json
+/// {
+///   "errors": [{
+///     "message": "Unknown field \"nope\" on type \"Query\"",
+///     "locations": [{ "line": 1, "column": 3 }],
+///     "extensions": { "code": "GRAPHQL_VALIDATION_FAILED" }
+///   }]
+/// }
+/// ```
+///
+/// The codes follow the ones used by other GraphQL servers where there is an
+/// equivalent. Resolvers can attach them to their own errors with
+/// `FieldError::with_code`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GraphQLErrorCode {
+    /// The query isn't syntactically valid GraphQL
+    GraphQLParseFailed,
+    /// The query doesn't validate against the schema
+    GraphQLValidationFailed,
+    /// The values of variables or arguments don't fit their types
+    BadUserInput,
+    /// The operation to execute can't be determined, e.g. because the
+    /// request doesn't name it but the query has several
+    OperationResolutionFailure,
+    /// The HTTP request is malformed, e.g. it doesn't contain a query
+    BadRequest,
+    /// The request has to be authenticated, see `http::ContextError::unauthorized`
+    Unauthenticated,
+    /// The request isn't allowed, see `http::ContextError::forbidden`
+    Forbidden,
+    /// The request body is larger than the limit, see `http::RequestLimits`
+    BodyTooLarge,
+    /// The query is longer than the limit, see `http::RequestLimits`
+    QueryStringTooLong,
+    /// The request has more variables than the limit, see
+    /// `http::RequestLimits`
+    TooManyVariables,
+    /// The response exceeds the budget of the schema, see
+    /// `RootNode::with_response_budget`
+    ResponseTooLarge,
+    /// Executing the request took longer than the timeout, see
+    /// `ExecutionOptions::with_timeout`
+    ExecutionTimedOut,
+    /// The server failed in a way that isn't the client's fault, e.g. a
+    /// resolver returned an `Int` out of range
+    InternalServerError,
+}
+
+impl GraphQLErrorCode {
+    /// The code as it appears in responses
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            GraphQLErrorCode::GraphQLParseFailed => "GRAPHQL_PARSE_FAILED",
+            GraphQLErrorCode::GraphQLValidationFailed => "GRAPHQL_VALIDATION_FAILED",
+            GraphQLErrorCode::BadUserInput => "BAD_USER_INPUT",
+            GraphQLErrorCode::OperationResolutionFailure => "OPERATION_RESOLUTION_FAILURE",
+            GraphQLErrorCode::BadRequest => "BAD_REQUEST",
+            GraphQLErrorCode::Unauthenticated => "UNAUTHENTICATED",
+            GraphQLErrorCode::Forbidden => "FORBIDDEN",
+            GraphQLErrorCode::BodyTooLarge => "BODY_TOO_LARGE",
+            GraphQLErrorCode::QueryStringTooLong => "QUERY_STRING_TOO_LONG",
+            GraphQLErrorCode::TooManyVariables => "TOO_MANY_VARIABLES",
+            GraphQLErrorCode::ResponseTooLarge => "RESPONSE_TOO_LARGE",
+            GraphQLErrorCode::ExecutionTimedOut => "EXECUTION_TIMED_OUT",
+            GraphQLErrorCode::InternalServerError => "INTERNAL_SERVER_ERROR",
+        }
+    }
+}
+
+impl fmt::Display for GraphQLErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,109 +1,384 @@
 use std::borrow::Cow;
-use std::collections::HashMap;
-use std::sync::RwLock;
+use std::cmp;
+use std::fmt;
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::sync::{Arc, RwLock};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::ser::{self, SerializeMap};
 
 use ::GraphQLError;
-use ast::{InputValue, ToInputValue, Document, Selection, Fragment, Definition, Type, FromInputValue, OperationType};
-use value::Value;
-use parser::SourcePosition;
+use error_code::GraphQLErrorCode;
+use ast::{InputValue, ToInputValue, Document, Selection, Fragment, Definition, Type, FromInputValue, OperationType, Operation, VariableDefinitions};
+use value::{Value, Object, ScalarValue, DefaultScalarValue};
+use parser::{SourcePosition, Spanning};
 
 use schema::meta::{MetaType, ScalarMeta, ListMeta, NullableMeta,
                    ObjectMeta, EnumMeta, InterfaceMeta, UnionMeta,
                    InputObjectMeta, PlaceholderMeta, Field, Argument,
-                   EnumValue};
+                   EnumValue, Name};
 use schema::model::{RootNode, SchemaType};
+use instrumentation::{instrument_field, Instruments};
 
-use types::base::GraphQLType;
+use types::base::{Arguments, GraphQLType, StreamedObject};
 
 /// A type registry used to build schemas
 ///
 /// The registry gathers metadata for all types in a schema. It provides
 /// convenience methods to convert types implementing the `GraphQLType` trait
 /// into `Type` instances and automatically registers them.
-pub struct Registry<'r> {
+pub struct Registry<'r, S = DefaultScalarValue> {
     /// Currently registered types
-    pub types: HashMap<String, MetaType<'r>>,
+    pub types: HashMap<String, MetaType<'r, S>>,
 }
 
 #[derive(Clone)]
 pub enum FieldPath<'a> {
     Root(SourcePosition),
     Field(&'a str, SourcePosition, &'a FieldPath<'a>),
+    Index(usize, &'a FieldPath<'a>),
 }
 
 /// Query execution engine
 ///
 /// The executor helps drive the query execution in a schema. It keeps track
 /// of the current field stack, context, variables, and errors.
-pub struct Executor<'a, CtxT> where CtxT: 'a {
-    fragments: &'a HashMap<&'a str, &'a Fragment<'a>>,
-    variables: &'a Variables,
-    current_selection_set: Option<&'a [Selection<'a>]>,
-    schema: &'a SchemaType<'a>,
+pub struct Executor<'a, CtxT, S = DefaultScalarValue> where CtxT: 'a, S: 'a {
+    fragments: &'a HashMap<&'a str, &'a Fragment<'a, S>>,
+    variables: &'a Variables<S>,
+    current_selection_set: Option<&'a [Selection<'a, S>]>,
+    schema: &'a SchemaType<'a, S>,
     context: &'a CtxT,
-    errors: &'a RwLock<Vec<ExecutionError>>,
+    errors: &'a RwLock<Vec<ExecutionError<S>>>,
+    response_names: &'a RwLock<HashSet<Arc<str>>>,
+    budget: &'a ResponseBudget,
+    deadline: &'a Deadline,
+    list_sink: Option<&'a (ListSink<S> + 'a)>,
+    instruments: &'a Instruments<S>,
     field_path: FieldPath<'a>,
 }
 
+/// Receives the items of a list as they are resolved, instead of the whole
+/// list at once
+///
+/// See `serialize_validated_query`.
+#[doc(hidden)]
+pub trait ListSink<S> {
+    /// Start receiving the items of a list
+    ///
+    /// Returns `false` if the sink already received a list, the items of
+    /// lists nested in it have to be resolved as usual.
+    fn begin(&self, len: Option<usize>) -> bool;
+
+    /// Receive the next item, returns `false` to stop resolving items
+    fn push(&self, item: Value<S>) -> bool;
+}
+
+/// Allocations of the executor that can be reused from one request to the
+/// next
+///
+/// Executing a query sets up a few maps: the variables with their default
+/// values added, and the names of the fields in the response. Servers can
+/// keep a scratch per thread or connection and pass it to
+/// `execute_with_scratch` (or `http::GraphQLRequest::execute_with_scratch`),
+/// so these maps keep their capacity across requests instead of being
+/// allocated anew:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use std::cell::RefCell;
+/// use juniper::{EmptyMutation, ExecutionScratch, RootNode, Variables};
+///
+/// struct Query;
+///
+/// graphql_object!(Query: () |&self| {
+///     field answer() -> i32 { 42 }
+/// });
+///
+/// thread_local! {
+///     static SCRATCH: RefCell<ExecutionScratch> = RefCell::new(ExecutionScratch::new());
+/// }
+///
+/// # fn main() {
+/// let root_node = RootNode::new(Query, EmptyMutation::<()>::new());
+///
+/// for _ in 0..3 {
+///     SCRATCH.with(|scratch| {
+///         let mut scratch = scratch.borrow_mut();
+///         juniper::execute_with_scratch(
+///             "{ answer }", None, &root_node, &Variables::new(), &(), &mut scratch).unwrap();
+///     });
+/// }
+/// # }
+/// ```
+///
+/// Errors are handed to the caller with the response, so their allocation
+/// can't be kept.
+pub struct ExecutionScratch<S = DefaultScalarValue> where S: 'static {
+    variables: Variables<S>,
+    response_names: RwLock<HashSet<Arc<str>>>,
+}
+
+// The approximate size of the response built so far, and the limit set with
+// `RootNode::with_response_budget`
+struct ResponseBudget {
+    limit: Option<usize>,
+    used: AtomicUsize,
+    exceeded: AtomicBool,
+}
+
+// The time by which fields have to be resolved, see
+// `ExecutionOptions::with_timeout`
+struct Deadline {
+    timeout: Option<Duration>,
+    at: Option<Instant>,
+    passed: AtomicBool,
+}
+
 /// Error type for errors that occur during query execution
 ///
 /// All execution errors contain the source position in the query of the field
 /// that failed to resolve. It also contains the field stack.
-#[derive(Debug, PartialOrd, Ord, PartialEq, Eq)]
-pub struct ExecutionError {
+#[derive(Debug, PartialEq)]
+pub struct ExecutionError<S = DefaultScalarValue> {
     location: SourcePosition,
-    path: Vec<String>,
+    path: Vec<ErrorPathSegment>,
+    error: FieldError<S>,
+}
+
+/// A step in the `path` of an `ExecutionError`
+///
+/// Serialized as a string for fields and as a number for list items.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ErrorPathSegment {
+    /// A field, by its response name
+    Field(String),
+    /// An item of a list, by its index
+    Index(usize),
+}
+
+/// Error type for errors that occur during field resolution
+///
+/// Field errors are represented by a human-readable error message and an
+/// optional `Value` structure containing additional information, which is
+/// returned as the `extensions` of the error in the response.
+///
+/// Any type implementing `Display` can be converted into a `FieldError`,
+/// so the `?` operator can be used in resolvers returning `FieldResult`:
+///
+/// ```rust
+/// # use std::str::FromStr;
+/// # use juniper::FieldResult;
+/// fn parse(s: &str) -> FieldResult<i32> {
+///     Ok(i32::from_str(s)?)
+/// }
+/// # fn main() { assert_eq!(parse("12"), Ok(12)); assert!(parse("x").is_err()); }
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct FieldError<S = DefaultScalarValue> {
     message: String,
+    extensions: Value<S>,
+    location: Option<SourcePosition>,
+}
+
+impl<T: fmt::Display, S> From<T> for FieldError<S> where S: ScalarValue {
+    fn from(e: T) -> FieldError<S> {
+        FieldError {
+            message: format!("{}", e),
+            extensions: Value::null(),
+            location: None,
+        }
+    }
+}
+
+impl<S> FieldError<S> where S: ScalarValue {
+    /// Construct a new error with additional data
+    ///
+    /// The `extensions` are added to the error in the response, unless they
+    /// are `null`:
+    ///
+    /// ```rust
+    /// # use juniper::{FieldError, Value};
+    /// # fn main() {
+    /// let err: FieldError = FieldError::new(
+    ///     "Could not open connection to the database",
+    ///     Value::object(vec![
+    ///         ("internal_error", Value::string("Connection refused")),
+    ///     ].into_iter().collect()));
+    /// # }
+    /// ```
+    pub fn new<T: fmt::Display>(e: T, extensions: Value<S>) -> FieldError<S> {
+        FieldError {
+            message: format!("{}", e),
+            extensions: extensions,
+            location: None,
+        }
+    }
+
+    /// Add a field to the extensions of the error, replacing any field of
+    /// the same name
+    ///
+    /// Extensions that aren't an object yet, e.g. `null`, are replaced by an
+    /// object:
+    ///
+    /// ```rust
+    /// # use juniper::{FieldError, Value};
+    /// # fn main() {
+    /// let err: FieldError = FieldError::from("Too many requests")
+    ///     .with_extension("code", Value::string("RATE_LIMITED"))
+    ///     .with_extension("retryAfter", Value::int(30));
+    ///
+    /// assert_eq!(err.extensions().get("retryAfter"), Some(&Value::int(30)));
+    /// # }
+    /// ```
+    pub fn with_extension<K>(mut self, name: K, value: Value<S>) -> FieldError<S> where K: Into<Arc<str>> {
+        if self.extensions.as_object_value().is_none() {
+            self.extensions = Value::object(Object::new());
+        }
+        if let Some(extensions) = self.extensions.as_mut_object_value() {
+            extensions.insert(name, value);
+        }
+        self
+    }
+
+    /// Set the `code` in the extensions of the error, see `GraphQLErrorCode`
+    pub fn with_code(self, code: GraphQLErrorCode) -> FieldError<S> {
+        self.with_extension("code", Value::string(code.as_str()))
+    }
+}
+
+impl<S> FieldError<S> {
+    /// The error message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Additional data about the error, or `null` if there is none
+    pub fn extensions(&self) -> &Value<S> {
+        &self.extensions
+    }
+
+    /// Report the error at a specific location in the query
+    ///
+    /// By default, errors are reported at the location of the field being
+    /// resolved. Use this to point at e.g. the argument that caused it
+    /// instead, see `Arguments::location`.
+    pub fn with_location(mut self, location: SourcePosition) -> FieldError<S> {
+        self.location = Some(location);
+        self
+    }
+}
+
+/// Conversion of resolver errors into field errors
+///
+/// Fields returning `Result<T, E>` are supported for any error type `E`
+/// implementing this trait. Implement it for your own error types to
+/// control the message and extensions that end up in the response.
+///
+/// With the `anyhow` and `failure` features, it's implemented for
+/// `anyhow::Error` and `failure::Error`. Their message becomes the message of
+/// the field error, and in debug builds the messages of their causes are added
+/// to its extensions as `causes`.
+pub trait IntoFieldError<S = DefaultScalarValue> {
+    /// Performs the conversion
+    fn into_field_error(self) -> FieldError<S>;
+}
+
+impl<S> IntoFieldError<S> for FieldError<S> {
+    fn into_field_error(self) -> FieldError<S> {
+        self
+    }
+}
+
+impl<S> IntoFieldError<S> for String where S: ScalarValue {
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::null())
+    }
+}
+
+impl<'a, S> IntoFieldError<S> for &'a str where S: ScalarValue {
+    fn into_field_error(self) -> FieldError<S> {
+        FieldError::new(self, Value::null())
+    }
 }
 
 /// The result of resolving the value of a field of type `T`
-pub type FieldResult<T> = Result<T, String>;
+pub type FieldResult<T, S = DefaultScalarValue> = Result<T, FieldError<S>>;
 
 /// The result of resolving an unspecified field
-pub type ExecutionResult = Result<Value, String>;
+pub type ExecutionResult<S = DefaultScalarValue> = Result<Value<S>, FieldError<S>>;
 
 /// The map of variables used for substitution during query execution
-pub type Variables = HashMap<String, InputValue>;
+pub type Variables<S = DefaultScalarValue> = HashMap<String, InputValue<S>>;
 
 #[doc(hidden)]
-pub trait IntoResolvable<'a, T: GraphQLType, C>: Sized {
+pub trait IntoResolvable<'a, T, C, S = DefaultScalarValue>: Sized
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+{
     #[doc(hidden)]
-    fn into(self, ctx: &'a C) -> FieldResult<Option<(&'a T::Context, T)>>;
+    fn into(self, ctx: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S>;
 }
 
-impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for T where T::Context: FromContext<C> {
-    fn into(self, ctx: &'a C) -> FieldResult<Option<(&'a T::Context, T)>> {
+impl<'a, T, C, S> IntoResolvable<'a, T, C, S> for T
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+          T::Context: FromContext<C>,
+{
+    fn into(self, ctx: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S> {
         Ok(Some((FromContext::from(ctx), self)))
     }
 }
 
-impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for FieldResult<T> where T::Context: FromContext<C> {
-    fn into(self, ctx: &'a C) -> FieldResult<Option<(&'a T::Context, T)>> {
+impl<'a, T, C, E, S> IntoResolvable<'a, T, C, S> for Result<T, E>
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+          T::Context: FromContext<C>,
+          E: IntoFieldError<S>,
+{
+    fn into(self, ctx: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S> {
         self.map(|v| Some((FromContext::from(ctx), v)))
+            .map_err(IntoFieldError::into_field_error)
     }
 }
 
-impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for (&'a T::Context, T) {
-    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>> {
+impl<'a, T, C, S> IntoResolvable<'a, T, C, S> for (&'a T::Context, T)
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+{
+    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S> {
         Ok(Some(self))
     }
 }
 
-impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for Option<(&'a T::Context, T)> {
-    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>> {
+impl<'a, T, C, S> IntoResolvable<'a, T, C, S> for Option<(&'a T::Context, T)>
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+{
+    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S> {
         Ok(self)
     }
 }
 
-impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for FieldResult<(&'a T::Context, T)> {
-    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>> {
-        self.map(Some)
+impl<'a, T, C, E, S> IntoResolvable<'a, T, C, S> for Result<(&'a T::Context, T), E>
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+          E: IntoFieldError<S>,
+{
+    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S> {
+        self.map(Some).map_err(IntoFieldError::into_field_error)
     }
 }
 
-impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for FieldResult<Option<(&'a T::Context, T)>> {
-    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>> {
-        self
+impl<'a, T, C, E, S> IntoResolvable<'a, T, C, S> for Result<Option<(&'a T::Context, T)>, E>
+    where T: GraphQLType<S>,
+          S: ScalarValue,
+          E: IntoFieldError<S>,
+{
+    fn into(self, _: &'a C) -> FieldResult<Option<(&'a T::Context, T)>, S> {
+        self.map_err(IntoFieldError::into_field_error)
     }
 }
 
@@ -115,6 +390,12 @@ impl<'a, T: GraphQLType, C> IntoResolvable<'a, T, C> for FieldResult<Option<(&'a
 /// improved. Implement this trait if you have contexts that can
 /// generally be converted between each other.
 ///
+/// The context is converted automatically whenever a field resolves to an
+/// object with a different context type, and when interfaces or unions
+/// resolve into their concrete types. A reusable type can therefore declare
+/// only the context it needs, and be used in any schema whose context
+/// implements `FromContext` for it.
+///
 /// The empty tuple `()` can be converted into from any context type,
 /// making it suitable for GraphQL that don't need _any_ context to
 /// work, e.g. scalars or enums.
@@ -142,11 +423,11 @@ impl<T> FromContext<T> for T where T: Context {
     }
 }
 
-impl<'a, CtxT> Executor<'a, CtxT> {
+impl<'a, CtxT, S> Executor<'a, CtxT, S> where S: ScalarValue {
     /// Resolve a single arbitrary value, mapping the context to a new type
-    pub fn resolve_with_ctx<NewCtxT, T: GraphQLType<Context=NewCtxT>>(
+    pub fn resolve_with_ctx<NewCtxT, T: GraphQLType<S, Context=NewCtxT>>(
         &self, info: &T::TypeInfo, value: &T
-    ) -> ExecutionResult
+    ) -> ExecutionResult<S>
         where NewCtxT: FromContext<CtxT>,
     {
         self.replaced_context(<NewCtxT as FromContext<CtxT>>::from(self.context))
@@ -154,14 +435,47 @@ impl<'a, CtxT> Executor<'a, CtxT> {
     }
 
     /// Resolve a single arbitrary value into an `ExecutionResult`
-    pub fn resolve<T: GraphQLType<Context=CtxT>>(&self, info: &T::TypeInfo, value: &T) -> ExecutionResult {
-        Ok(value.resolve(info, self.current_selection_set, self))
+    pub fn resolve<T: GraphQLType<S, Context=CtxT>>(&self, info: &T::TypeInfo, value: &T) -> ExecutionResult<S> {
+        // Once the response is too large, the rest of the query is skipped
+        if self.budget.is_exceeded() {
+            return Ok(Value::null());
+        }
+
+        let value = value.resolve(info, self.current_selection_set, self);
+
+        if let Some(limit) = self.budget.spend(shallow_size(&value)) {
+            let position = self.field_path.location().clone();
+            self.push_error(
+                FieldError::from(format!("Response exceeds the memory budget of {} bytes", limit))
+                    .with_code(GraphQLErrorCode::ResponseTooLarge)
+                    .with_extension("budget", Value::int(cmp::min(limit, i32::max_value() as usize) as i32)),
+                position);
+        }
+
+        Ok(value)
     }
 
     /// Resolve a single arbitrary value into a return value
     ///
     /// If the field fails to resolve, `null` will be returned.
-    pub fn resolve_into_value<T: GraphQLType<Context=CtxT>>(&self, info: &T::TypeInfo, value: &T) -> Value {
+    pub fn resolve_into_value<T: GraphQLType<S, Context=CtxT>>(&self, info: &T::TypeInfo, value: &T) -> Value<S> {
+        // Lists nested in the value of a field, like the items of its list,
+        // are never streamed
+        if self.list_sink.is_some() {
+            return self.without_list_sink().resolve_nullable_into_value(info, value);
+        }
+
+        self.resolve_nullable_into_value(info, value)
+    }
+
+    /// Resolve the value of a nullable field into a return value
+    ///
+    /// Unlike `resolve_into_value`, a list the field resolves to may still be
+    /// streamed.
+    #[doc(hidden)]
+    pub fn resolve_nullable_into_value<T>(&self, info: &T::TypeInfo, value: &T) -> Value<S>
+        where T: GraphQLType<S, Context=CtxT>,
+    {
         match self.resolve(info, value) {
             Ok(v) => v,
             Err(e) => {
@@ -172,11 +486,80 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         }
     }
 
+    /// Resolve the items of a list into a `Value::List`
+    ///
+    /// If the list is streamed, the items are serialized one by one instead
+    /// and the returned list is empty, see
+    /// `http::GraphQLRequest::execute_streaming`.
+    #[doc(hidden)]
+    pub fn resolve_into_list<'i, T, I>(&self, info: &T::TypeInfo, items: I) -> Value<S>
+        where T: GraphQLType<S, Context=CtxT> + 'i,
+              I: Iterator<Item=&'i T>,
+    {
+        if let Some(sink) = self.list_sink {
+            if sink.begin(items.size_hint().1) {
+                for (index, item) in items.enumerate() {
+                    if !sink.push(self.resolve_item_into_value(index, info, item)) {
+                        break;
+                    }
+                }
+
+                return Value::list(Vec::new());
+            }
+        }
+
+        Value::list(items.enumerate().map(|(index, item)| self.resolve_item_into_value(index, info, item)).collect())
+    }
+
+    /// Resolve the item of a list at the given index into a return value
+    ///
+    /// Errors of the item have the index in their path.
+    #[doc(hidden)]
+    pub fn resolve_item_into_value<T>(&self, index: usize, info: &T::TypeInfo, value: &T) -> Value<S>
+        where T: GraphQLType<S, Context=CtxT>,
+    {
+        let executor = Executor {
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: self.current_selection_set,
+            schema: self.schema,
+            context: self.context,
+            errors: self.errors,
+            response_names: self.response_names,
+            budget: self.budget,
+            deadline: self.deadline,
+            list_sink: None,
+            instruments: self.instruments,
+            field_path: FieldPath::Index(index, &self.field_path),
+        };
+
+        executor.resolve_into_value(info, value)
+    }
+
+    fn without_list_sink(&self) -> Executor<'a, CtxT, S> {
+        Executor {
+            fragments: self.fragments,
+            variables: self.variables,
+            current_selection_set: self.current_selection_set,
+            schema: self.schema,
+            context: self.context,
+            errors: self.errors,
+            response_names: self.response_names,
+            budget: self.budget,
+            deadline: self.deadline,
+            list_sink: None,
+            instruments: self.instruments,
+            field_path: self.field_path.clone(),
+        }
+    }
+
     /// Derive a new executor by replacing the context
     ///
     /// This can be used to connect different types, e.g. from different Rust
     /// libraries, that require different context types.
-    pub fn replaced_context<'b, NewCtxT>(&'b self, ctx: &'b NewCtxT) -> Executor<'b, NewCtxT> {
+    pub fn replaced_context<'b, NewCtxT>(&'b self, ctx: &'b NewCtxT) -> Executor<'b, NewCtxT, S> {
+        // Fields resolve their values with the context of the value's type,
+        // so the list sink of the field is kept
         Executor {
             fragments: self.fragments,
             variables: self.variables,
@@ -184,6 +567,11 @@ impl<'a, CtxT> Executor<'a, CtxT> {
             schema: self.schema,
             context: ctx,
             errors: self.errors,
+            response_names: self.response_names,
+            budget: self.budget,
+            deadline: self.deadline,
+            list_sink: self.list_sink,
+            instruments: self.instruments,
             field_path: self.field_path.clone(),
         }
     }
@@ -193,9 +581,24 @@ impl<'a, CtxT> Executor<'a, CtxT> {
         &self,
         field_name: Option<&'a str>,
         location: SourcePosition,
-        selection_set: Option<&'a [Selection]>,
+        selection_set: Option<&'a [Selection<S>]>,
     )
-        -> Executor<CtxT>
+        -> Executor<CtxT, S>
+    {
+        self.streaming_sub_executor(field_name, location, selection_set, None)
+    }
+
+    // Only the list a field resolves to directly is handed to `list_sink`,
+    // the executors of the fields of its items don't get one
+    #[doc(hidden)]
+    pub fn streaming_sub_executor<'b>(
+        &'b self,
+        field_name: Option<&'a str>,
+        location: SourcePosition,
+        selection_set: Option<&'a [Selection<S>]>,
+        list_sink: Option<&'b (ListSink<S> + 'b)>,
+    )
+        -> Executor<'b, CtxT, S>
     {
         Executor {
             fragments: self.fragments,
@@ -204,6 +607,11 @@ impl<'a, CtxT> Executor<'a, CtxT> {
             schema: self.schema,
             context: self.context,
             errors: self.errors,
+            response_names: self.response_names,
+            budget: self.budget,
+            deadline: self.deadline,
+            list_sink: list_sink,
+            instruments: self.instruments,
             field_path: match field_name {
                 Some(name) => FieldPath::Field(name, location, &self.field_path),
                 None => self.field_path.clone(),
@@ -220,97 +628,372 @@ impl<'a, CtxT> Executor<'a, CtxT> {
     }
 
     /// The currently executing schema
-    pub fn schema(&self) -> &'a SchemaType {
+    pub fn schema(&self) -> &'a SchemaType<S> {
         self.schema
     }
 
+    /// The source location _in the query_ of the field being resolved
+    pub fn location(&self) -> &SourcePosition {
+        self.field_path.location()
+    }
+
     #[doc(hidden)]
-    pub fn variables(&self) -> &'a Variables {
+    pub fn variables(&self) -> &'a Variables<S> {
         self.variables
     }
 
+    // Every object in the response that contains a given field shares a single
+    // allocation for its key, so large lists of objects don't duplicate them.
     #[doc(hidden)]
-    pub fn fragment_by_name(&self, name: &str) -> Option<&'a Fragment> {
+    pub fn response_name(&self, name: &str) -> Arc<str> {
+        if let Some(interned) = self.response_names.read().unwrap_or_else(|err| err.into_inner()).get(name) {
+            return interned.clone();
+        }
+
+        let interned: Arc<str> = From::from(name);
+        self.response_names.write().unwrap_or_else(|err| err.into_inner()).insert(interned.clone());
+        interned
+    }
+
+    #[doc(hidden)]
+    pub fn fragment_by_name(&self, name: &str) -> Option<&'a Fragment<'a, S>> {
         self.fragments.get(name).map(|f| *f)
     }
 
+    // Resolve the field of this executor, calling the instrumentation hooks
+    // of the schema around it
+    #[doc(hidden)]
+    pub fn instrument_field<F>(&self, parent_type_name: &str, field_name: &str, arguments: &Arguments<S>, resolve: F)
+        -> ExecutionResult<S>
+        where F: FnOnce() -> ExecutionResult<S>,
+    {
+        instrument_field(self.instruments, parent_type_name, field_name, arguments, &self.field_path, resolve)
+    }
+
+    // Whether the field of this executor can still be resolved, reports an
+    // error for the first field that can't
+    #[doc(hidden)]
+    pub fn check_deadline(&self) -> bool {
+        match self.deadline.check() {
+            Ok(()) => true,
+            Err(Some(timeout)) => {
+                let millis = timeout.as_secs() * 1000 + u64::from(timeout.subsec_nanos() / 1_000_000);
+                let position = self.field_path.location().clone();
+                self.push_error(
+                    FieldError::from(format!("Execution exceeded the timeout of {} ms", millis))
+                        .with_code(GraphQLErrorCode::ExecutionTimedOut),
+                    position);
+                false
+            },
+            Err(None) => false,
+        }
+    }
+
     /// Add an error to the execution engine
-    pub fn push_error(&self, error: String, location: SourcePosition) {
+    ///
+    /// If the error was given a location with `FieldError::with_location`,
+    /// that location takes precedence over the one passed here.
+    pub fn push_error(&self, mut error: FieldError<S>, location: SourcePosition) {
+        let location = error.location.take().unwrap_or(location);
         let mut path = Vec::new();
         self.field_path.construct_path(&mut path);
 
-        let mut errors = self.errors.write().unwrap();
+        let mut errors = self.errors.write().unwrap_or_else(|err| err.into_inner());
 
         errors.push(ExecutionError {
             location: location,
             path: path,
-            message: error,
+            error: error,
         });
     }
 }
 
 impl<'a> FieldPath<'a> {
-    fn construct_path(&self, acc: &mut Vec<String>) {
+    #[doc(hidden)]
+    pub fn construct_path(&self, acc: &mut Vec<ErrorPathSegment>) {
         match *self {
             FieldPath::Root(_) => (),
             FieldPath::Field(name, _, parent) => {
                 parent.construct_path(acc);
+                acc.push(ErrorPathSegment::Field(name.to_owned()));
+            }
+            FieldPath::Index(index, parent) => {
+                parent.construct_path(acc);
+                acc.push(ErrorPathSegment::Index(index));
+            }
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn construct_field_names(&self, acc: &mut Vec<String>) {
+        match *self {
+            FieldPath::Root(_) => (),
+            FieldPath::Field(name, _, parent) => {
+                parent.construct_field_names(acc);
                 acc.push(name.to_owned());
             }
+            FieldPath::Index(_, parent) => parent.construct_field_names(acc),
         }
     }
 
-    fn location(&self) -> &SourcePosition {
+    #[doc(hidden)]
+    pub fn location(&self) -> &SourcePosition {
         match *self {
             FieldPath::Root(ref pos) |
-            FieldPath::Field(_, ref pos, _) => pos
+            FieldPath::Field(_, ref pos, _) => pos,
+            FieldPath::Index(_, parent) => parent.location(),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn depth(&self) -> usize {
+        match *self {
+            FieldPath::Root(_) => 0,
+            FieldPath::Field(_, _, parent) => parent.depth() + 1,
+            FieldPath::Index(_, parent) => parent.depth(),
         }
     }
 }
 
-impl ExecutionError {
+impl<S> ExecutionError<S> where S: ScalarValue {
     #[doc(hidden)]
-    pub fn new(location: SourcePosition, path: &[&str], message: &str) -> ExecutionError {
+    pub fn new(location: SourcePosition, path: &[&str], message: &str) -> ExecutionError<S> {
         ExecutionError {
             location: location,
-            path: path.iter().map(|s| (*s).to_owned()).collect(),
-            message: message.to_owned(),
+            path: path.iter().map(|s| ErrorPathSegment::Field((*s).to_owned())).collect(),
+            error: FieldError::new(message, Value::null()),
         }
     }
 
+    /// Replace the message of the error, keeping its location, path and
+    /// extensions
+    pub fn with_message<T: fmt::Display>(mut self, message: T) -> ExecutionError<S> {
+        self.error.message = format!("{}", message);
+        self
+    }
+
+    /// Move the error to another location, e.g. the matching one in the
+    /// document the client registered, see `PreparedOperation::with_source_map`
+    pub fn with_location(mut self, location: SourcePosition) -> ExecutionError<S> {
+        self.location = location;
+        self
+    }
+
+    /// Add a field to the extensions of the error, see
+    /// `FieldError::with_extension`
+    pub fn with_extension<K>(mut self, name: K, value: Value<S>) -> ExecutionError<S> where K: Into<Arc<str>> {
+        self.error = self.error.with_extension(name, value);
+        self
+    }
+}
+
+impl<S> ExecutionError<S> {
     /// The error message
     pub fn message(&self) -> &str {
-        &self.message
+        self.error.message()
+    }
+
+    /// The underlying field error, including its extensions
+    pub fn error(&self) -> &FieldError<S> {
+        &self.error
     }
 
-    /// The source location _in the query_ of the field that failed to resolve
+    /// The source location _in the query_ of the field that failed to resolve,
+    /// or of the argument that failed to convert
     pub fn location(&self) -> &SourcePosition {
         &self.location
     }
 
-    /// The path of fields leading to the field that generated this error
-    pub fn path(&self) -> &[String] {
+    /// The path of fields leading to the field that generated this error,
+    /// including the indices of the list items on the way
+    pub fn path(&self) -> &[ErrorPathSegment] {
         &self.path
     }
+
+    /// Additional data about the error, or `null` if there is none
+    ///
+    /// These are serialized as the `extensions` of the error.
+    pub fn extensions(&self) -> &Value<S> {
+        self.error.extensions()
+    }
 }
 
-pub fn execute_validated_query<'a, QueryT, MutationT, CtxT>(
-    document: Document,
+pub fn execute_validated_query<'a, 'v, QueryT, MutationT, CtxT, S>(
+    document: &Document<S>,
     operation_name: Option<&str>,
-    root_node: &RootNode<QueryT, MutationT>,
-    variables: &Variables,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: Cow<'v, Variables<S>>,
     context: &CtxT
 )
-    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
-    where QueryT: GraphQLType<Context=CtxT>,
-          MutationT: GraphQLType<Context=CtxT>
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    execute_validated_query_with_scratch(
+        document, operation_name, root_node, variables, context, &mut ExecutionScratch::new())
+}
+
+/// Execute a validated query, reusing the allocations of `scratch`
+#[doc(hidden)]
+pub fn execute_validated_query_with_scratch<'a, 'v, QueryT, MutationT, CtxT, S>(
+    document: &Document<S>,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: Cow<'v, Variables<S>>,
+    context: &CtxT,
+    scratch: &mut ExecutionScratch<S>,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    let mut fragments = HashMap::new();
+    let mut buffer = mem::replace(&mut scratch.variables, Variables::new());
+    let mut value;
+
+    let errors = {
+        let (op, variables) = try!(prepare_operation(document, operation_name, variables, &mut fragments, &mut buffer));
+
+        let errors = RwLock::new(Vec::new());
+        let budget = ResponseBudget::new(root_node.options.response_budget());
+        let deadline = Deadline::new(root_node.options.timeout());
+
+        {
+            let executor = Executor {
+                fragments: &fragments,
+                variables: &variables,
+                current_selection_set: Some(&op.item.selection_set[..]),
+                schema: &root_node.schema,
+                context: context,
+                errors: &errors,
+                response_names: &scratch.response_names,
+                budget: &budget,
+                deadline: &deadline,
+                list_sink: None,
+                instruments: &root_node.instruments,
+                field_path: FieldPath::Root(op.start.clone()),
+            };
+
+            value = match op.item.operation_type {
+                OperationType::Query => executor.resolve_into_value(&*root_node.query_info, &root_node),
+                OperationType::Mutation => executor.resolve_into_value(&*root_node.mutation_info, &*root_node.mutation_type),
+            };
+        }
+
+        // Partial responses of aborted queries could be just as large, so
+        // there's no data at all
+        if budget.is_exceeded() {
+            value = Value::null();
+        }
+
+        errors
+    };
+
+    buffer.clear();
+    scratch.variables = buffer;
+    scratch.trim_response_names();
+
+    Ok((value, sorted_errors(errors, root_node)))
+}
+
+/// Execute a validated query, serializing the response while it's resolved
+///
+/// Lists returned by top-level fields are handed to the serializer item by
+/// item, instead of being collected into a `Value` first. Since errors are
+/// only known once all data is serialized, they follow the data. If the
+/// response budget is exceeded, the data serialized so far stays.
+///
+/// Fails with a serialization error if the operation can't be executed,
+/// callers are expected to check that beforehand.
+#[doc(hidden)]
+pub fn serialize_validated_query<'v, QueryT, MutationT, CtxT, S, Ser>(
+    document: &Document<S>,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: Cow<'v, Variables<S>>,
+    context: &CtxT,
+    request_id: Option<&str>,
+    serializer: Ser,
+)
+    -> Result<Ser::Ok, Ser::Error>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+          Ser: ser::Serializer,
+{
+    let mut fragments = HashMap::new();
+    let mut buffer = Variables::new();
+    let (op, variables) = match prepare_operation(document, operation_name, variables, &mut fragments, &mut buffer) {
+        Ok(prepared) => prepared,
+        Err(_) => return Err(ser::Error::custom("The operation to execute can't be determined")),
+    };
+
+    let errors = RwLock::new(Vec::new());
+    let budget = ResponseBudget::new(root_node.options.response_budget());
+    let deadline = Deadline::new(root_node.options.timeout());
+    let mut map = try!(serializer.serialize_map(None));
+
+    {
+        let executor = Executor {
+            fragments: &fragments,
+            variables: &variables,
+            current_selection_set: Some(&op.item.selection_set[..]),
+            schema: &root_node.schema,
+            context: context,
+            errors: &errors,
+            response_names: &RwLock::new(HashSet::new()),
+            budget: &budget,
+            deadline: &deadline,
+            list_sink: None,
+            instruments: &root_node.instruments,
+            field_path: FieldPath::Root(op.start.clone()),
+        };
+
+        try!(map.serialize_key("data"));
+        match op.item.operation_type {
+            OperationType::Query => try!(map.serialize_value(&StreamedObject::new(
+                root_node, &*root_node.query_info, &op.item.selection_set, &executor))),
+            OperationType::Mutation => try!(map.serialize_value(&StreamedObject::new(
+                &*root_node.mutation_type, &*root_node.mutation_info, &op.item.selection_set, &executor))),
+        }
+    }
+
+    let errors = label_errors(sorted_errors(errors, root_node), request_id, operation_name);
+    if !errors.is_empty() {
+        try!(map.serialize_key("errors"));
+        try!(map.serialize_value(&errors));
+    }
+
+    map.end()
+}
+
+// The operation to execute and its variables
+type PreparedOperation<'d, 'v, S> = (
+    &'d Spanning<Operation<'d, S>>,
+    Cow<'v, Variables<S>>,
+);
+
+// Find the operation to execute, add the fragments it may use to `fragments`,
+// and add the default values of variables. Borrowed variables are copied into
+// `buffer` if defaults have to be added.
+#[doc(hidden)]
+pub fn prepare_operation<'a, 'd, 'v, S>(
+    document: &'d Document<S>,
+    operation_name: Option<&str>,
+    variables: Cow<'v, Variables<S>>,
+    fragments: &mut HashMap<&'d str, &'d Fragment<'d, S>>,
+    buffer: &'v mut Variables<S>,
+)
+    -> Result<PreparedOperation<'d, 'v, S>, GraphQLError<'a>>
+    where S: ScalarValue,
 {
-    let mut fragments = vec![];
     let mut operation = None;
 
     for def in document {
-        match def {
-            Definition::Operation(op) => {
+        match *def {
+            Definition::Operation(ref op) => {
                 if operation_name.is_none() && operation.is_some() {
                     return Err(GraphQLError::MultipleOperationsProvided);
                 }
@@ -322,7 +1005,9 @@ pub fn execute_validated_query<'a, QueryT, MutationT, CtxT>(
                     operation = Some(op);
                 }
             }
-            Definition::Fragment(f) => fragments.push(f),
+            Definition::Fragment(ref f) => {
+                fragments.insert(f.item.name.item, &f.item);
+            },
         };
     }
 
@@ -331,54 +1016,196 @@ pub fn execute_validated_query<'a, QueryT, MutationT, CtxT>(
         None => return Err(GraphQLError::UnknownOperationName),
     };
 
-    let default_variable_values = op.item.variable_definitions
-        .map(|defs| defs.item.items.iter().filter_map(
-            |&(ref name, ref def)| def.default_value.as_ref().map(
-                |i| (name.item.to_owned(), i.item.clone())))
-             .collect::<HashMap<String, InputValue>>());
+    // Variables can be large, e.g. the input of a batch mutation, so they're
+    // only copied if a default value has to be added and the caller didn't
+    // hand them over
+    let mut variables = variables;
+    if let Some(ref defs) = op.item.variable_definitions {
+        let missing_default = defs.item.items.iter()
+            .any(|&(ref name, ref def)| def.default_value.is_some() && !variables.contains_key(name.item));
+
+        if missing_default {
+            variables = match variables {
+                Cow::Borrowed(borrowed) => {
+                    buffer.clear();
+                    buffer.extend(borrowed.iter().map(|(name, value)| (name.clone(), value.clone())));
+                    add_default_values(&defs.item, buffer);
+                    Cow::Borrowed(&*buffer)
+                }
+                Cow::Owned(mut owned) => {
+                    add_default_values(&defs.item, &mut owned);
+                    Cow::Owned(owned)
+                }
+            };
+        }
+    }
 
-    let errors = RwLock::new(Vec::new());
-    let value;
+    Ok((op, variables))
+}
 
-    {
-        let mut all_vars;
-        let mut final_vars = variables;
+fn add_default_values<S>(defs: &VariableDefinitions<S>, variables: &mut Variables<S>) where S: ScalarValue {
+    for &(ref name, ref def) in &defs.items {
+        if let Some(ref default) = def.default_value {
+            if !variables.contains_key(name.item) {
+                variables.insert(name.item.to_owned(), default.item.clone());
+            }
+        }
+    }
+}
+
+// The errors in the order of the query, passed through the error mapper of
+// the schema if it has one
+//
+// The order mustn't depend on the order fields happen to be resolved in, so
+// snapshots of responses stay stable. Errors are ordered by their location
+// first, then by their path, so the errors of the items of a list, which
+// share a location, come in the order of the items.
+fn sorted_errors<QueryT, MutationT, S>(
+    errors: RwLock<Vec<ExecutionError<S>>>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+)
+    -> Vec<ExecutionError<S>>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    let mut errors = errors.into_inner().unwrap_or_else(|err| err.into_inner());
+    // The sort is stable, so the errors of a single field stay in the order
+    // they were raised in
+    errors.sort_by(|a, b| (&a.location, &a.path).cmp(&(&b.location, &b.path)));
 
-        if let Some(defaults) = default_variable_values {
-            all_vars = variables.clone();
+    if let Some(max) = root_node.options.max_errors() {
+        errors.truncate(max);
+    }
 
-            for (name, value) in defaults {
-                all_vars.entry(name).or_insert(value);
+    match root_node.error_mapper {
+        Some(ref mapper) => errors.into_iter().map(|err| mapper(err)).collect(),
+        None => errors,
+    }
+}
+
+/// Add the ID of the request, and the name of the operation if there is one,
+/// to the extensions of the errors of an execution
+///
+/// Errors aren't changed if there's no request ID.
+#[doc(hidden)]
+pub fn label_errors<S>(errors: Vec<ExecutionError<S>>, request_id: Option<&str>, operation_name: Option<&str>)
+    -> Vec<ExecutionError<S>>
+    where S: ScalarValue,
+{
+    let request_id = match request_id {
+        Some(request_id) => request_id,
+        None => return errors,
+    };
+
+    errors.into_iter()
+        .map(|err| {
+            let err = err.with_extension("requestId", Value::string(request_id));
+            match operation_name {
+                Some(name) => err.with_extension("operationName", Value::string(name)),
+                None => err,
             }
+        })
+        .collect()
+}
 
-            final_vars = &all_vars;
+// Response names interned by earlier requests are kept, as the same queries
+// tend to come in again, but only up to this many
+const SCRATCH_RESPONSE_NAMES: usize = 1024;
+
+impl<S> ExecutionScratch<S> where S: ScalarValue {
+    /// Create an empty scratch, which doesn't allocate until it's used
+    pub fn new() -> ExecutionScratch<S> {
+        ExecutionScratch {
+            variables: Variables::new(),
+            response_names: RwLock::new(HashSet::new()),
         }
+    }
 
-        let executor = Executor {
-            fragments: &fragments.iter().map(|f| (f.item.name.item, &f.item)).collect(),
-            variables: final_vars,
-            current_selection_set: Some(&op.item.selection_set[..]),
-            schema: &root_node.schema,
-            context: context,
-            errors: &errors,
-            field_path: FieldPath::Root(op.start),
-        };
+    fn trim_response_names(&mut self) {
+        let names = self.response_names.get_mut().unwrap_or_else(|err| err.into_inner());
+        if names.len() > SCRATCH_RESPONSE_NAMES {
+            names.clear();
+        }
+    }
+}
 
-        value = match op.item.operation_type {
-            OperationType::Query => executor.resolve_into_value(&root_node.query_info, &root_node),
-            OperationType::Mutation => executor.resolve_into_value(&root_node.mutation_info, &root_node.mutation_type),
+impl<S> Default for ExecutionScratch<S> where S: ScalarValue {
+    fn default() -> ExecutionScratch<S> {
+        ExecutionScratch::new()
+    }
+}
+
+impl ResponseBudget {
+    fn new(limit: Option<usize>) -> ResponseBudget {
+        ResponseBudget {
+            limit: limit,
+            used: AtomicUsize::new(0),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.exceeded.load(Ordering::Relaxed)
+    }
+
+    // Returns the limit if this is what exceeded it
+    fn spend(&self, bytes: usize) -> Option<usize> {
+        let limit = match self.limit {
+            Some(limit) => limit,
+            None => return None,
         };
+
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if used > limit && !self.exceeded.swap(true, Ordering::Relaxed) {
+            Some(limit)
+        } else {
+            None
+        }
     }
+}
 
-    let mut errors = errors.into_inner().unwrap();
-    errors.sort();
+impl Deadline {
+    fn new(timeout: Option<Duration>) -> Deadline {
+        Deadline {
+            timeout: timeout,
+            at: timeout.map(|timeout| Instant::now() + timeout),
+            passed: AtomicBool::new(false),
+        }
+    }
+
+    // Returns the timeout if this is the first check after the deadline
+    fn check(&self) -> Result<(), Option<Duration>> {
+        let at = match self.at {
+            Some(at) => at,
+            None => return Ok(()),
+        };
+
+        if self.passed.load(Ordering::Relaxed) {
+            Err(None)
+        } else if Instant::now() < at {
+            Ok(())
+        } else if !self.passed.swap(true, Ordering::Relaxed) {
+            Err(self.timeout)
+        } else {
+            Err(None)
+        }
+    }
+}
 
-    Ok((value, errors))
+// The memory a resolved value takes up itself. The values in lists and objects
+// are resolved, and counted, separately.
+fn shallow_size<S>(value: &Value<S>) -> usize {
+    mem::size_of::<Value<S>>() + match *value {
+        Value::String(ref s) => s.len(),
+        Value::Object(ref o) => o.len() * mem::size_of::<Arc<str>>(),
+        _ => 0,
+    }
 }
 
-impl<'r> Registry<'r> {
+impl<'r, S> Registry<'r, S> where S: ScalarValue {
     /// Construct a new registry
-    pub fn new(types: HashMap<String, MetaType<'r>>) -> Registry<'r> {
+    pub fn new(types: HashMap<String, MetaType<'r, S>>) -> Registry<'r, S> {
         Registry {
             types: types,
         }
@@ -388,7 +1215,7 @@ impl<'r> Registry<'r> {
     ///
     /// If the registry hasn't seen a type with this name before, it will
     /// construct its metadata and store it.
-    pub fn get_type<T>(&mut self) -> Type<'r> where T: GraphQLType<TypeInfo=()> {
+    pub fn get_type<T>(&mut self) -> Type<'r> where T: GraphQLType<S, TypeInfo=()> {
         self.get_type_with_info::<T>(&())
     }
 
@@ -397,7 +1224,7 @@ impl<'r> Registry<'r> {
     ///
     /// If the registry hasn't seen a type with this name before, it will
     /// construct its metadata and store it.
-    pub fn get_type_with_info<T>(&mut self, info: &T::TypeInfo) -> Type<'r> where T: GraphQLType {
+    pub fn get_type_with_info<T>(&mut self, info: &T::TypeInfo) -> Type<'r> where T: GraphQLType<S> {
         if let Some(name) = T::name(info) {
             if !self.types.contains_key(&name.to_string()) {
                 self.insert_placeholder(&name, Type::NonNullNamed(Cow::Owned(name.to_string())));
@@ -412,15 +1239,15 @@ impl<'r> Registry<'r> {
     }
 
     /// Create a field with the provided name
-    pub fn field<T>(&mut self, name: &str) -> Field<'r> where T: GraphQLType<TypeInfo=()> {
+    pub fn field<T>(&mut self, name: &str) -> Field<'r, S> where T: GraphQLType<S, TypeInfo=()> {
         self.field_with_info::<T>(name, &())
     }
 
     /// Create a field with the provided name,
     /// by providing a type info object.
-    pub fn field_with_info<T>(&mut self, name: &str, info: &T::TypeInfo) -> Field<'r> where T: GraphQLType {
+    pub fn field_with_info<T>(&mut self, name: &str, info: &T::TypeInfo) -> Field<'r, S> where T: GraphQLType<S> {
         Field {
-            name: name.to_owned(),
+            name: Name::new(name),
             description: None,
             arguments: None,
             field_type: self.get_type_with_info::<T>(info),
@@ -429,18 +1256,18 @@ impl<'r> Registry<'r> {
     }
 
     #[doc(hidden)]
-    pub fn field_convert<'a, T: IntoResolvable<'a, I, C>, I, C>(&mut self, name: &str) -> Field<'r>
-        where I: GraphQLType<TypeInfo=()>
+    pub fn field_convert<'a, T: IntoResolvable<'a, I, C, S>, I, C>(&mut self, name: &str) -> Field<'r, S>
+        where I: GraphQLType<S, TypeInfo=()>
     {
         self.field_convert_with_info::<'a, T, I, C>(name, &())
     }
 
     #[doc(hidden)]
-    pub fn field_convert_with_info<'a, T: IntoResolvable<'a, I, C>, I, C>(&mut self, name: &str, info: &I::TypeInfo) -> Field<'r>
-        where I: GraphQLType
+    pub fn field_convert_with_info<'a, T: IntoResolvable<'a, I, C, S>, I, C>(&mut self, name: &str, info: &I::TypeInfo) -> Field<'r, S>
+        where I: GraphQLType<S>
     {
         Field {
-            name: name.to_owned(),
+            name: Name::new(name),
             description: None,
             arguments: None,
             field_type: self.get_type_with_info::<I>(info),
@@ -449,13 +1276,13 @@ impl<'r> Registry<'r> {
     }
 
     /// Create an argument with the provided name
-    pub fn arg<T>(&mut self, name: &str) -> Argument<'r> where T: GraphQLType<TypeInfo=()> + FromInputValue {
+    pub fn arg<T>(&mut self, name: &str) -> Argument<'r, S> where T: GraphQLType<S, TypeInfo=()> + FromInputValue<S> {
         self.arg_with_info::<T>(name, &())
     }
 
     /// Create an argument with the provided name,
     /// by providing a type info object.
-    pub fn arg_with_info<T>(&mut self, name: &str, info: &T::TypeInfo) -> Argument<'r> where T: GraphQLType + FromInputValue {
+    pub fn arg_with_info<T>(&mut self, name: &str, info: &T::TypeInfo) -> Argument<'r, S> where T: GraphQLType<S> + FromInputValue<S> {
         Argument::new(name, self.get_type_with_info::<T>(info))
     }
 
@@ -468,8 +1295,8 @@ impl<'r> Registry<'r> {
         name: &str,
         value: &T
     )
-        -> Argument<'r>
-        where T: GraphQLType<TypeInfo=()> + ToInputValue + FromInputValue
+        -> Argument<'r, S>
+        where T: GraphQLType<S, TypeInfo=()> + ToInputValue<S> + FromInputValue<S>
     {
         self.arg_with_default_with_info(name, value, &())
     }
@@ -485,13 +1312,34 @@ impl<'r> Registry<'r> {
         value: &T,
         info: &T::TypeInfo
     )
-        -> Argument<'r>
-        where T: GraphQLType + ToInputValue + FromInputValue
+        -> Argument<'r, S>
+        where T: GraphQLType<S> + ToInputValue<S> + FromInputValue<S>
     {
         Argument::new(name, self.get_type_with_info::<Option<T>>(info))
             .default_value(value.to())
     }
 
+    /// Get the input fields of an input object, to be merged into another
+    /// input object
+    ///
+    /// The input object itself is not registered in the schema.
+    pub fn flattened_args<T>(&mut self) -> Vec<Argument<'r, S>>
+        where T: GraphQLType<S, TypeInfo=()> + FromInputValue<S>
+    {
+        self.flattened_args_with_info::<T>(&())
+    }
+
+    /// Get the input fields of an input object, to be merged into another
+    /// input object, by providing a type info object.
+    pub fn flattened_args_with_info<T>(&mut self, info: &T::TypeInfo) -> Vec<Argument<'r, S>>
+        where T: GraphQLType<S> + FromInputValue<S>
+    {
+        match T::meta(info, self) {
+            MetaType::InputObject(InputObjectMeta { input_fields, .. }) => input_fields,
+            _ => panic!("Only input objects can be flattened"),
+        }
+    }
+
     fn insert_placeholder(&mut self, name: &str, of_type: Type<'r>) {
         if !self.types.contains_key(name) {
             self.types.insert(
@@ -503,21 +1351,21 @@ impl<'r> Registry<'r> {
     /// Create a scalar meta type
     ///
     /// This expects the type to implement `FromInputValue`.
-    pub fn build_scalar_type<T>(&mut self, info: &T::TypeInfo) -> ScalarMeta<'r>
-        where T: FromInputValue + GraphQLType
+    pub fn build_scalar_type<T>(&mut self, info: &T::TypeInfo) -> ScalarMeta<'r, S>
+        where T: FromInputValue<S> + GraphQLType<S>
     {
         let name = T::name(info).expect("Scalar types must be named. Implement name()");
         ScalarMeta::new::<T>(Cow::Owned(name.to_string()))
     }
 
     /// Create a list meta type
-    pub fn build_list_type<T: GraphQLType>(&mut self, info: &T::TypeInfo) -> ListMeta<'r> {
+    pub fn build_list_type<T: GraphQLType<S>>(&mut self, info: &T::TypeInfo) -> ListMeta<'r> {
         let of_type = self.get_type_with_info::<T>(info);
         ListMeta::new(of_type)
     }
 
     /// Create a nullable meta type
-    pub fn build_nullable_type<T: GraphQLType>(&mut self, info: &T::TypeInfo) -> NullableMeta<'r> {
+    pub fn build_nullable_type<T: GraphQLType<S>>(&mut self, info: &T::TypeInfo) -> NullableMeta<'r> {
         let of_type = self.get_type_with_info::<T>(info);
         NullableMeta::new(of_type)
     }
@@ -526,8 +1374,8 @@ impl<'r> Registry<'r> {
     ///
     /// To prevent infinite recursion by enforcing ordering, this returns a
     /// function that needs to be called with the list of fields on the object.
-    pub fn build_object_type<T>(&mut self, fields: &[Field<'r>]) -> ObjectMeta<'r>
-        where T: GraphQLType<TypeInfo=()>
+    pub fn build_object_type<T>(&mut self, fields: &[Field<'r, S>]) -> ObjectMeta<'r, S>
+        where T: GraphQLType<S, TypeInfo=()>
     {
         self.build_object_type_with_info::<T>(&(), fields)
     }
@@ -537,8 +1385,8 @@ impl<'r> Registry<'r> {
     ///
     /// To prevent infinite recursion by enforcing ordering, this returns a
     /// function that needs to be called with the list of fields on the object.
-    pub fn build_object_type_with_info<T>(&mut self, info: &T::TypeInfo, fields: &[Field<'r>]) -> ObjectMeta<'r>
-        where T: GraphQLType
+    pub fn build_object_type_with_info<T>(&mut self, info: &T::TypeInfo, fields: &[Field<'r, S>]) -> ObjectMeta<'r, S>
+        where T: GraphQLType<S>
     {
         let name = T::name(info).expect("Object types must be named. Implement name()");
 
@@ -548,16 +1396,16 @@ impl<'r> Registry<'r> {
     }
 
     /// Create an enum meta type
-    pub fn build_enum_type<T>(&mut self, values: &[EnumValue]) -> EnumMeta<'r>
-        where T: FromInputValue + GraphQLType<TypeInfo=()>
+    pub fn build_enum_type<T>(&mut self, values: &[EnumValue]) -> EnumMeta<'r, S>
+        where T: FromInputValue<S> + GraphQLType<S, TypeInfo=()>
     {
         self.build_enum_type_with_info::<T>(&(), values)
     }
 
     /// Create an enum meta type,
     /// by providing a type info object.
-    pub fn build_enum_type_with_info<T>(&mut self, info: &T::TypeInfo, values: &[EnumValue]) -> EnumMeta<'r>
-        where T: FromInputValue + GraphQLType
+    pub fn build_enum_type_with_info<T>(&mut self, info: &T::TypeInfo, values: &[EnumValue]) -> EnumMeta<'r, S>
+        where T: FromInputValue<S> + GraphQLType<S>
     {
         let name = T::name(info).expect("Enum types must be named. Implement name()");
 
@@ -565,16 +1413,16 @@ impl<'r> Registry<'r> {
     }
 
     /// Create an interface meta type builder
-    pub fn build_interface_type<T>(&mut self, fields: &[Field<'r>]) -> InterfaceMeta<'r>
-        where T: GraphQLType<TypeInfo=()>
+    pub fn build_interface_type<T>(&mut self, fields: &[Field<'r, S>]) -> InterfaceMeta<'r, S>
+        where T: GraphQLType<S, TypeInfo=()>
     {
         self.build_interface_type_with_info::<T>(&(), fields)
     }
 
     /// Create an interface meta type builder,
     /// by providing a type info object.
-    pub fn build_interface_type_with_info<T>(&mut self, info: &T::TypeInfo, fields: &[Field<'r>]) -> InterfaceMeta<'r>
-        where T: GraphQLType
+    pub fn build_interface_type_with_info<T>(&mut self, info: &T::TypeInfo, fields: &[Field<'r, S>]) -> InterfaceMeta<'r, S>
+        where T: GraphQLType<S>
     {
         let name = T::name(info).expect("Interface types must be named. Implement name()");
 
@@ -585,7 +1433,7 @@ impl<'r> Registry<'r> {
 
     /// Create a union meta type builder
     pub fn build_union_type<T>(&mut self, types: &[Type<'r>]) -> UnionMeta<'r>
-        where T: GraphQLType<TypeInfo=()>
+        where T: GraphQLType<S, TypeInfo=()>
     {
         self.build_union_type_with_info::<T>(&(), types)
     }
@@ -593,7 +1441,7 @@ impl<'r> Registry<'r> {
     /// Create a union meta type builder,
     /// by providing a type info object.
     pub fn build_union_type_with_info<T>(&mut self, info: &T::TypeInfo, types: &[Type<'r>]) -> UnionMeta<'r>
-        where T: GraphQLType
+        where T: GraphQLType<S>
     {
         let name = T::name(info).expect("Union types must be named. Implement name()");
 
@@ -601,16 +1449,16 @@ impl<'r> Registry<'r> {
     }
 
     /// Create an input object meta type builder
-    pub fn build_input_object_type<T>(&mut self, args: &[Argument<'r>]) -> InputObjectMeta<'r>
-        where T: FromInputValue + GraphQLType<TypeInfo=()>
+    pub fn build_input_object_type<T>(&mut self, args: &[Argument<'r, S>]) -> InputObjectMeta<'r, S>
+        where T: FromInputValue<S> + GraphQLType<S, TypeInfo=()>
     {
         self.build_input_object_type_with_info::<T>(&(), args)
     }
 
     /// Create an input object meta type builder,
     /// by providing a type info object.
-    pub fn build_input_object_type_with_info<T>(&mut self, info: &T::TypeInfo, args: &[Argument<'r>]) -> InputObjectMeta<'r>
-        where T: FromInputValue + GraphQLType
+    pub fn build_input_object_type_with_info<T>(&mut self, info: &T::TypeInfo, args: &[Argument<'r, S>]) -> InputObjectMeta<'r, S>
+        where T: FromInputValue<S> + GraphQLType<S>
     {
         let name = T::name(info).expect("Input object types must be named. Implement name()");
 
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use executor::Variables;
 use schema::model::RootNode;
 use types::scalars::EmptyMutation;
@@ -18,7 +18,7 @@ graphql_object!(TestType: () |&self| {
 });
 
 fn run_variable_query<F>(query: &str, vars: Variables, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let schema = RootNode::new(TestType, EmptyMutation::<()>::new());
 
@@ -35,7 +35,7 @@ fn run_variable_query<F>(query: &str, vars: Variables, f: F)
 }
 
 fn run_query<F>(query: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     run_variable_query(query, Variables::new(), f);
 }
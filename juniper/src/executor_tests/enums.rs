@@ -1,11 +1,12 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use ast::InputValue;
 use executor::Variables;
 use schema::model::RootNode;
 use ::GraphQLError::ValidationError;
 use validation::RuleError;
+use ::GraphQLErrorCode;
 use parser::SourcePosition;
 use types::scalars::EmptyMutation;
 
@@ -30,7 +31,7 @@ graphql_object!(TestType: () |&self| {
 });
 
 fn run_variable_query<F>(query: &str, vars: Variables, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let schema = RootNode::new(TestType, EmptyMutation::<()>::new());
 
@@ -47,7 +48,7 @@ fn run_variable_query<F>(query: &str, vars: Variables, f: F)
 }
 
 fn run_query<F>(query: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     run_variable_query(query, Variables::new(), f);
 }
@@ -123,7 +124,7 @@ fn does_not_accept_incorrect_enum_name_in_variables() {
         RuleError::new(
             r#"Variable "$color" got invalid value. Invalid value for enum "Color"."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -143,6 +144,6 @@ fn does_not_accept_incorrect_type_in_variables() {
         RuleError::new(
             r#"Variable "$color" got invalid value. Expected "Color", found not a string or enum."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
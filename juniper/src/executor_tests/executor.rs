@@ -149,19 +149,54 @@ mod merge_parallel_fragments {
             Value::object(vec![
                 ("a", Value::string("Apple")),
                 ("b", Value::string("Banana")),
-                ("c", Value::string("Cherry")),
                 ("deep", Value::object(vec![
                     ("b", Value::string("Banana")),
-                    ("c", Value::string("Cherry")),
                     ("deeper", Value::object(vec![
                         ("b", Value::string("Banana")),
                         ("c", Value::string("Cherry")),
                     ].into_iter().collect())),
+                    ("c", Value::string("Cherry")),
                 ].into_iter().collect())),
+                ("c", Value::string("Cherry")),
             ].into_iter().collect()));
     }
 }
 
+mod field_order {
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Type;
+
+    graphql_object!(Type: () |&self| {
+        field a() -> i32 { 1 }
+        field b() -> i32 { 2 }
+        field c() -> i32 { 3 }
+        field deep() -> Type { Type }
+    });
+
+    fn keys(value: &Value) -> Vec<&str> {
+        value.as_object_value().unwrap().iter().map(|&(ref k, _)| &**k).collect()
+    }
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(Type, EmptyMutation::<()>::new());
+        let doc = r"{ c, deep { b, a, c }, a, z: b, ... on Type { c, b } }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(keys(&result), vec!["c", "deep", "a", "z", "b"]);
+        assert_eq!(keys(result.as_object_value().unwrap().get("deep").unwrap()), vec!["b", "a", "c"]);
+    }
+}
+
 mod threads_context_correctly {
     use value::Value;
     use types::scalars::EmptyMutation;
@@ -233,14 +268,14 @@ mod dynamic_context_switching {
         }
 
         field item_res(&executor, key: i32) -> FieldResult<(&InnerContext, ItemRef)> {
-            executor.context().items.get(&key)
-                .ok_or(format!("Could not find key {}", key))
-                .map(|c| (c, ItemRef))
+            let c = executor.context().items.get(&key)
+                .ok_or(format!("Could not find key {}", key))?;
+            Ok((c, ItemRef))
         }
 
         field item_res_opt(&executor, key: i32) -> FieldResult<Option<(&InnerContext, ItemRef)>> {
             if key > 100 {
-                Err(format!("Key too large: {}", key))
+                Err(format!("Key too large: {}", key).into())
             } else {
                 Ok(executor.context().items.get(&key)
                    .map(|c| (c, ItemRef)))
@@ -417,7 +452,7 @@ mod nulls_out_errors {
 
     graphql_object!(Schema: () |&self| {
         field sync() -> FieldResult<&str> { Ok("sync") }
-        field sync_error() -> FieldResult<&str> { Err("Error for syncError".to_owned()) }
+        field sync_error() -> FieldResult<&str> { Err("Error for syncError".into()) }
     });
 
     #[test]
@@ -451,6 +486,269 @@ mod nulls_out_errors {
     }
 }
 
+mod field_errors {
+    use value::Value;
+    use schema::model::RootNode;
+    use executor::{ErrorPathSegment, FieldError, FieldResult, IntoFieldError};
+    use parser::SourcePosition;
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+
+    enum CustomError {
+        NotFound(i32),
+    }
+
+    impl IntoFieldError for CustomError {
+        fn into_field_error(self) -> FieldError {
+            match self {
+                CustomError::NotFound(id) => FieldError::new(
+                    "Not found",
+                    Value::object(vec![
+                        ("id", Value::int(id)),
+                    ].into_iter().collect())),
+            }
+        }
+    }
+
+    graphql_object!(Schema: () |&self| {
+        field parse(value: String) -> FieldResult<i32> {
+            Ok(value.parse::<i32>()?)
+        }
+
+        field custom(id: i32) -> Result<i32, CustomError> {
+            Err(CustomError::NotFound(id))
+        }
+
+        field plain() -> Result<i32, String> {
+            Err("Plain error".to_owned())
+        }
+    });
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r#"{ ok: parse(value: "12") failed: parse(value: "x") custom(id: 3) plain }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        println!("Result: {:?}", result);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("ok", Value::int(12)),
+                ("failed", Value::null()),
+                ("custom", Value::null()),
+                ("plain", Value::null()),
+            ].into_iter().collect()));
+
+        assert_eq!(errs.len(), 3);
+
+        assert_eq!(errs[0].location(), &SourcePosition::new(25, 0, 25));
+        assert_eq!(errs[0].path(), &[ErrorPathSegment::Field("failed".to_owned())]);
+        assert_eq!(errs[0].message(), "invalid digit found in string");
+        assert!(errs[0].error().extensions().is_null());
+
+        assert_eq!(errs[1].path(), &[ErrorPathSegment::Field("custom".to_owned())]);
+        assert_eq!(errs[1].message(), "Not found");
+        assert_eq!(
+            errs[1].error().extensions(),
+            &Value::object(vec![
+                ("id", Value::int(3)),
+            ].into_iter().collect()));
+
+        assert_eq!(errs[2].path(), &[ErrorPathSegment::Field("plain".to_owned())]);
+        assert_eq!(errs[2].message(), "Plain error");
+    }
+}
+
+mod error_order {
+    use value::Value;
+    use schema::model::RootNode;
+    use executor::{ErrorPathSegment, FieldError, FieldResult};
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+    struct Item(i32);
+
+    graphql_object!(Item: () |&self| {
+        field fail() -> FieldResult<i32> {
+            if self.0 % 5 == 0 {
+                Err(FieldError::from("Item failed").with_extension("id", Value::int(self.0)))
+            } else {
+                Ok(self.0)
+            }
+        }
+    });
+
+    graphql_object!(Schema: () |&self| {
+        field items() -> Vec<Item> { (0..11).rev().map(Item).collect() }
+        field fail(message: String) -> FieldResult<i32> { Err(message.into()) }
+    });
+
+    #[test]
+    fn sorts_errors_independently_of_resolution_order() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r#"{ b: fail(message: "b") items { fail } a: fail(message: "a") }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (_, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        let summary = errs.iter()
+            .map(|err| (err.path().to_vec(), err.extensions().get("id").and_then(Value::as_int_value)))
+            .collect::<Vec<_>>();
+
+        let item = |index| vec![
+            ErrorPathSegment::Field("items".to_owned()),
+            ErrorPathSegment::Index(index),
+            ErrorPathSegment::Field("fail".to_owned()),
+        ];
+
+        assert_eq!(
+            summary,
+            vec![
+                (vec![ErrorPathSegment::Field("b".to_owned())], None),
+                (item(0), Some(10)),
+                (item(5), Some(5)),
+                (item(10), Some(0)),
+                (vec![ErrorPathSegment::Field("a".to_owned())], None),
+            ]);
+    }
+}
+
+mod unsigned_ints {
+    use value::Value;
+    use ast::InputValue;
+    use executor::ErrorPathSegment;
+    use parser::SourcePosition;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+
+    graphql_object!(Schema: () |&self| {
+        field small() -> u16 { 65535 }
+        field medium(value: u32) -> u32 { value }
+        field large() -> usize { 3_000_000_000 }
+        field capped(value = (3_000_000_000): u32) -> u32 { value }
+    });
+
+    #[test]
+    fn test_in_range() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ small, medium(value: 12) }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("small", Value::int(65535)),
+                ("medium", Value::int(12)),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn test_negative_argument() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ medium(value: -1) }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("medium", Value::null()),
+            ].into_iter().collect()));
+
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].path(), &[ErrorPathSegment::Field("medium".to_owned())]);
+        assert_eq!(errs[0].message(), "Invalid value for argument \"value\"");
+        assert_eq!(errs[0].location(), &SourcePosition::new(16, 0, 16));
+        assert_eq!(errs[0].extensions().get("code"), Some(&Value::string("BAD_USER_INPUT")));
+    }
+
+    #[test]
+    fn test_negative_argument_from_variable() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"query q($v: Int!) { medium(value: $v) }";
+
+        let vars = vec![
+            ("v".to_owned(), InputValue::int(-1)),
+        ].into_iter().collect();
+
+        let (_, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].location(), &SourcePosition::new(34, 0, 34));
+    }
+
+    #[test]
+    fn test_out_of_range_result() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ large }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        println!("Result: {:?}", result);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("large", Value::null()),
+            ].into_iter().collect()));
+
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].path(), &[ErrorPathSegment::Field("large".to_owned())]);
+        assert_eq!(errs[0].message(), "Int cannot represent 3000000000");
+        assert_eq!(errs[0].extensions().get("code"), Some(&Value::string("INTERNAL_SERVER_ERROR")));
+    }
+
+    #[test]
+    fn test_out_of_range_default_value() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r#"{
+            __type(name: "Schema") { fields { name args { defaultValue } } }
+            capped
+        }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(result.pointer("/__type/fields/3/name"), Some(&Value::string("capped")));
+        assert_eq!(
+            result.pointer("/__type/fields/3/args"),
+            Some(&Value::list(vec![
+                Value::object(vec![
+                    ("defaultValue", Value::string("null")),
+                ].into_iter().collect()),
+            ])));
+
+        assert_eq!(result.get("capped"), Some(&Value::null()));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].message(), "Invalid value for argument \"value\"");
+    }
+}
+
 mod named_operations {
     use value::Value;
     use schema::model::RootNode;
@@ -546,3 +844,608 @@ mod named_operations {
         assert_eq!(err, GraphQLError::UnknownOperationName);
     }
 }
+
+mod shared_pointers {
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+    struct Inner(&'static str);
+
+    graphql_object!(Inner: () |&self| {
+        field value() -> &str { self.0 }
+    });
+
+    graphql_object!(Schema: () |&self| {
+        field arc() -> Arc<Inner> { Arc::new(Inner("arc")) }
+        field rc() -> Rc<Inner> { Rc::new(Inner("rc")) }
+        field arc_string() -> Arc<String> { Arc::new("shared".to_owned()) }
+        field echo(value: Arc<String>) -> Rc<String> { Rc::new((*value).clone()) }
+    });
+
+    #[test]
+    fn resolves_through_arc_and_rc() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r#"{ arc { value } rc { value } arcString echo(value: "hello") }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("arc", Value::object(vec![
+                    ("value", Value::string("arc")),
+                ].into_iter().collect())),
+                ("rc", Value::object(vec![
+                    ("value", Value::string("rc")),
+                ].into_iter().collect())),
+                ("arcString", Value::string("shared")),
+                ("echo", Value::string("hello")),
+            ].into_iter().collect()));
+    }
+}
+
+mod cow_values {
+    use std::borrow::Cow;
+
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Schema {
+        cached: String,
+        numbers: Vec<i32>,
+    }
+
+    graphql_object!(Schema: () |&self| {
+        field borrowed() -> Cow<str> { Cow::Borrowed(self.cached.as_str()) }
+        field owned() -> Cow<str> { Cow::Owned(self.cached.to_uppercase()) }
+        field numbers() -> Cow<[i32]> { Cow::Borrowed(&self.numbers[..]) }
+        field boxed() -> Cow<i32> { Cow::Owned(7) }
+        field echo(value: Cow<'static, str>) -> Cow<str> { value }
+    });
+
+    #[test]
+    fn resolves_borrowed_and_owned_cows() {
+        let schema = RootNode::new(
+            Schema { cached: "cached".to_owned(), numbers: vec![1, 2] },
+            EmptyMutation::<()>::new());
+        let doc = r#"{ borrowed owned numbers boxed echo(value: "hi") }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("borrowed", Value::string("cached")),
+                ("owned", Value::string("CACHED")),
+                ("numbers", Value::list(vec![Value::int(1), Value::int(2)])),
+                ("boxed", Value::int(7)),
+                ("echo", Value::string("hi")),
+            ].into_iter().collect()));
+    }
+}
+
+mod borrowed_values {
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Item {
+        name: String,
+    }
+
+    struct Schema {
+        name: String,
+        tags: Vec<String>,
+        items: Vec<Item>,
+    }
+
+    graphql_object!(Item: () |&self| {
+        field name() -> &str { &self.name }
+    });
+
+    graphql_object!(Schema: () |&self| {
+        field name() -> &str { &self.name }
+        field tags() -> &[String] { &self.tags }
+        field items() -> &[Item] { &self.items }
+        field first() -> Option<&Item> { self.items.first() }
+        field last() -> &Item { self.items.last().unwrap() }
+        field item_names() -> Vec<&str> { self.items.iter().map(|i| i.name.as_str()).collect() }
+        field first_tag() -> Option<&str> { self.tags.first().map(|t| t.as_str()) }
+    });
+
+    #[test]
+    fn resolves_borrowed_values() {
+        let schema = RootNode::new(
+            Schema {
+                name: "shop".to_owned(),
+                tags: vec!["a".to_owned(), "b".to_owned()],
+                items: vec![Item { name: "x".to_owned() }, Item { name: "y".to_owned() }],
+            },
+            EmptyMutation::<()>::new());
+        let doc = r#"{ name tags items { name } first { name } last { name } itemNames firstTag }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("name", Value::string("shop")),
+                ("tags", Value::list(vec![Value::string("a"), Value::string("b")])),
+                ("items", Value::list(vec![
+                    Value::object(vec![("name", Value::string("x"))].into_iter().collect()),
+                    Value::object(vec![("name", Value::string("y"))].into_iter().collect()),
+                ])),
+                ("first", Value::object(vec![("name", Value::string("x"))].into_iter().collect())),
+                ("last", Value::object(vec![("name", Value::string("y"))].into_iter().collect())),
+                ("itemNames", Value::list(vec![Value::string("x"), Value::string("y")])),
+                ("firstTag", Value::string("a")),
+            ].into_iter().collect()));
+    }
+}
+
+mod maps {
+    use std::collections::{HashMap, BTreeMap};
+
+    use value::Value;
+    use ast::InputValue;
+    use schema::model::RootNode;
+    use types::maps::{ObjectMap, KeyValueList};
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+
+    fn attributes() -> BTreeMap<String, i32> {
+        vec![
+            ("height".to_owned(), 180),
+            ("weight".to_owned(), 75),
+        ].into_iter().collect()
+    }
+
+    graphql_object!(Schema: () |&self| {
+        field object() -> ObjectMap<i32> { attributes().into() }
+        field entries() -> KeyValueList<i32> { attributes().into() }
+        field sum(values: ObjectMap<i32>) -> i32 {
+            values.entries().iter().map(|&(_, v)| v).sum()
+        }
+        field count(labels: ObjectMap<String>) -> i32 {
+            labels.entries().len() as i32
+        }
+        field groups() -> ObjectMap<Vec<i32>> {
+            vec![("even", vec![2, 4]), ("odd", vec![1])].into_iter().collect::<BTreeMap<_, _>>().into()
+        }
+    });
+
+    #[test]
+    fn resolves_map_as_scalar_and_entries() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ object entries { key value __typename } }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("object", Value::object(vec![
+                    ("height", Value::int(180)),
+                    ("weight", Value::int(75)),
+                ].into_iter().collect())),
+                ("entries", Value::list(vec![
+                    Value::object(vec![
+                        ("key", Value::string("height")),
+                        ("value", Value::int(180)),
+                        ("__typename", Value::string("IntEntry")),
+                    ].into_iter().collect()),
+                    Value::object(vec![
+                        ("key", Value::string("weight")),
+                        ("value", Value::int(75)),
+                        ("__typename", Value::string("IntEntry")),
+                    ].into_iter().collect()),
+                ])),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn accepts_map_arguments() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"query Sum($values: IntMap!) { literal: sum(values: {a: 1, b: 2}) variable: sum(values: $values) }";
+
+        let vars = vec![
+            ("values".to_owned(), InputValue::object(vec![
+                ("x", InputValue::int(10)),
+                ("y", InputValue::int(20)),
+            ].into_iter().collect::<HashMap<_, _>>())),
+        ].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("literal", Value::int(3)),
+                ("variable", Value::int(30)),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn names_maps_after_their_value_type() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r#"{
+            int: __type(name: "IntMap") { kind }
+            string: __type(name: "StringMap") { kind }
+            list: __type(name: "IntListMap") { kind }
+        }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("int", Value::object(vec![("kind", Value::string("SCALAR"))].into_iter().collect())),
+                ("string", Value::object(vec![("kind", Value::string("SCALAR"))].into_iter().collect())),
+                ("list", Value::object(vec![("kind", Value::string("SCALAR"))].into_iter().collect())),
+            ].into_iter().collect()));
+
+        assert!(::execute(r#"{ count(labels: {a: "x", b: "y"}) }"#, None, &schema, &vars, &()).is_ok());
+        assert!(::execute(r#"{ count(labels: {a: 1}) }"#, None, &schema, &vars, &()).is_err());
+    }
+}
+
+mod collections {
+    use std::collections::{HashSet, BTreeSet, VecDeque};
+
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+
+    graphql_object!(Schema: () |&self| {
+        field deque() -> VecDeque<i32> { vec![1, 2, 3].into_iter().collect() }
+        field hash_set() -> HashSet<i32> { vec![4].into_iter().collect() }
+        field btree_set() -> BTreeSet<&str> { vec!["b", "a"].into_iter().collect() }
+        field count(values: HashSet<String>) -> i32 { values.len() as i32 }
+        field sorted(values: BTreeSet<i32>) -> Vec<i32> { values.into_iter().collect() }
+        field reversed(values: VecDeque<i32>) -> Vec<i32> { values.into_iter().rev().collect() }
+    });
+
+    #[test]
+    fn resolves_std_collections_as_lists() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r#"{
+            deque hashSet btreeSet
+            count(values: ["a", "b", "a"])
+            sorted(values: [3, 1, 2, 1])
+            reversed(values: [1, 2, 3])
+        }"#;
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("deque", Value::list(vec![Value::int(1), Value::int(2), Value::int(3)])),
+                ("hashSet", Value::list(vec![Value::int(4)])),
+                ("btreeSet", Value::list(vec![Value::string("a"), Value::string("b")])),
+                ("count", Value::int(2)),
+                ("sorted", Value::list(vec![Value::int(1), Value::int(2), Value::int(3)])),
+                ("reversed", Value::list(vec![Value::int(3), Value::int(2), Value::int(1)])),
+            ].into_iter().collect()));
+    }
+}
+
+mod arrays {
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Schema;
+
+    graphql_object!(Schema: () |&self| {
+        field rgb() -> [i32; 3] { [255, 128, 0] }
+        field sum(point: [i32; 2]) -> i32 { point[0] + point[1] }
+    });
+
+    #[test]
+    fn resolves_arrays_as_lists() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ rgb sum(point: [3, 4]) }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("rgb", Value::list(vec![Value::int(255), Value::int(128), Value::int(0)])),
+                ("sum", Value::int(7)),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn rejects_lists_of_the_wrong_length() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ sum(point: [1, 2, 3]) }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("sum", Value::null()),
+            ].into_iter().collect()));
+
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].message(), "Invalid value for argument \"point\"");
+    }
+}
+
+mod shared_response_names {
+    use std::sync::Arc;
+
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Item(i32);
+
+    graphql_object!(Item: () |&self| {
+        field id() -> i32 { self.0 }
+    });
+
+    struct Schema;
+
+    graphql_object!(Schema: () |&self| {
+        field items() -> Vec<Item> { (0..3).map(Item).collect() }
+    });
+
+    #[test]
+    fn objects_share_keys() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"{ items { id } }";
+
+        let vars = vec![].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        let keys = result.get("items").and_then(Value::as_list_value).unwrap().iter()
+            .map(|item| item.as_object_value().unwrap().iter().next().unwrap().0.clone())
+            .collect::<Vec<_>>();
+
+        assert_eq!(keys.len(), 3);
+        assert_eq!(&*keys[0], "id");
+        assert!(keys.iter().all(|k| Arc::ptr_eq(k, &keys[0])));
+    }
+}
+
+mod custom_scalar_values {
+    use std::fmt;
+
+    use serde::{Serialize, Serializer};
+
+    use value::{Value, ScalarValue};
+    use ast::InputValue;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use parser::{ParseError, LexerError};
+    use ::GraphQLError;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct BigInt(i64);
+
+    impl fmt::Display for BigInt {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{}", self.0)
+        }
+    }
+
+    impl Serialize for BigInt {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_i64(self.0)
+        }
+    }
+
+    impl ScalarValue for BigInt {
+        fn from_int_literal(literal: &str) -> Option<BigInt> {
+            literal.parse().ok().map(BigInt)
+        }
+    }
+
+    struct Long(i64);
+
+    graphql_scalar!(Long where Scalar = BigInt {
+        resolve(&self) -> Value {
+            Value::scalar(BigInt(self.0))
+        }
+
+        from_input_value(v: &InputValue) -> Option<Long> {
+            match *v {
+                InputValue::Int(i) => Some(Long(i as i64)),
+                InputValue::Scalar(BigInt(i)) => Some(Long(i)),
+                _ => None,
+            }
+        }
+    });
+
+    struct Schema;
+
+    graphql_object!(Schema: () where Scalar = BigInt |&self| {
+        field double(value: Long) -> Long { Long(value.0 * 2) }
+        field small() -> i32 { 3 }
+    });
+
+    #[test]
+    fn accepts_and_resolves_custom_scalar_values() {
+        let schema = RootNode::new(Schema, EmptyMutation::<()>::new());
+        let doc = r"query Double($value: Long!) {
+            literal: double(value: 8589934592)
+            small: double(value: 2)
+            variable: double(value: $value)
+            int: small
+        }";
+
+        let vars = vec![
+            ("value".to_owned(), InputValue::scalar(BigInt(-4294967296))),
+        ].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("literal", Value::scalar(BigInt(17179869184))),
+                ("small", Value::scalar(BigInt(4))),
+                ("variable", Value::scalar(BigInt(-8589934592))),
+                ("int", Value::int(3)),
+            ].into_iter().collect()));
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Decimal(String);
+
+    impl fmt::Display for Decimal {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl Serialize for Decimal {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.0)
+        }
+    }
+
+    impl ScalarValue for Decimal {
+        fn from_float_literal(literal: &str) -> Option<Decimal> {
+            Some(Decimal(literal.to_owned()))
+        }
+
+        fn as_float(&self) -> Option<f64> {
+            self.0.parse().ok()
+        }
+    }
+
+    struct Amount(String);
+
+    graphql_scalar!(Amount where Scalar = Decimal {
+        resolve(&self) -> Value {
+            Value::scalar(Decimal(self.0.clone()))
+        }
+
+        from_input_value(v: &InputValue) -> Option<Amount> {
+            match *v {
+                InputValue::Int(i) => Some(Amount(i.to_string())),
+                InputValue::Scalar(Decimal(ref d)) => Some(Amount(d.clone())),
+                _ => None,
+            }
+        }
+    });
+
+    struct DecimalSchema;
+
+    graphql_object!(DecimalSchema: () where Scalar = Decimal |&self| {
+        field echo(value: Amount) -> Amount { value }
+        field half(value: f64) -> f64 { value / 2.0 }
+    });
+
+    #[test]
+    fn keeps_the_digits_of_float_literals() {
+        let schema = RootNode::new(DecimalSchema, EmptyMutation::<()>::new());
+        let doc = r"query Echo($value: Amount!) {
+            literal: echo(value: 0.100000000000000000001)
+            int: echo(value: 2)
+            variable: echo(value: $value)
+            half(value: 1.5)
+        }";
+
+        let vars = vec![
+            ("value".to_owned(), InputValue::scalar(Decimal("12.50".to_owned()))),
+        ].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("literal", Value::scalar(Decimal("0.100000000000000000001".to_owned()))),
+                ("int", Value::scalar(Decimal("2".to_owned()))),
+                ("variable", Value::scalar(Decimal("12.50".to_owned()))),
+                ("half", Value::float(0.75)),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn rejects_oversized_literals_by_default() {
+        struct DefaultSchema;
+
+        graphql_object!(DefaultSchema: () |&self| {
+            field echo(value: i32) -> i32 { value }
+        });
+
+        let schema = RootNode::new(DefaultSchema, EmptyMutation::<()>::new());
+        let vars = vec![].into_iter().collect();
+
+        let err = ::execute("{ echo(value: 8589934592) }", None, &schema, &vars, &())
+            .unwrap_err();
+
+        match err {
+            GraphQLError::ParseError(e) =>
+                assert_eq!(e.item, ParseError::LexerError(LexerError::InvalidNumber)),
+            _ => panic!("Expected a parse error"),
+        }
+    }
+}
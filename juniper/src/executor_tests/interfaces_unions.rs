@@ -221,3 +221,248 @@ mod union {
             ].into_iter().collect()));
     }
 }
+
+
+
+
+mod narrowed_context {
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use executor::{Context, FromContext};
+
+    struct AppContext {
+        sounds: SoundContext,
+    }
+
+    struct SoundContext {
+        woof: String,
+    }
+
+    impl Context for AppContext {}
+    impl Context for SoundContext {}
+
+    impl FromContext<AppContext> for SoundContext {
+        fn from(ctx: &AppContext) -> &SoundContext {
+            &ctx.sounds
+        }
+    }
+
+    trait Pet {
+        fn name(&self) -> &str;
+
+        fn as_dog(&self) -> Option<&Dog> { None }
+        fn as_cat(&self) -> Option<&Cat> { None }
+    }
+
+    graphql_interface!(<'a> &'a Pet: AppContext as "Pet" |&self| {
+        field name() -> &str { self.name() }
+
+        instance_resolvers: |&_| {
+            &Dog => self.as_dog(),
+            &Cat => self.as_cat(),
+        }
+    });
+
+    struct Dog {
+        name: String,
+    }
+
+    impl Pet for Dog {
+        fn name(&self) -> &str { &self.name }
+        fn as_dog(&self) -> Option<&Dog> { Some(self) }
+    }
+
+    graphql_object!(Dog: SoundContext |&self| {
+        field name() -> &str { &self.name }
+        field sound(&executor) -> &str { &executor.context().woof }
+
+        interfaces: [&Pet]
+    });
+
+    struct Cat {
+        name: String,
+    }
+
+    impl Pet for Cat {
+        fn name(&self) -> &str { &self.name }
+        fn as_cat(&self) -> Option<&Cat> { Some(self) }
+    }
+
+    graphql_object!(Cat: () |&self| {
+        field name() -> &str { &self.name }
+
+        interfaces: [&Pet]
+    });
+
+    struct Schema {
+        pets: Vec<Box<Pet>>,
+    }
+
+    graphql_object!(Schema: AppContext |&self| {
+        field pets() -> Vec<&Pet> {
+            self.pets.iter().map(|p| p.as_ref()).collect()
+        }
+    });
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(
+            Schema {
+                pets: vec![
+                    Box::new(Dog { name: "Odie".to_owned() }),
+                    Box::new(Cat { name: "Garfield".to_owned() }),
+                ],
+            },
+            EmptyMutation::<AppContext>::new());
+        let doc = r"
+          {
+            pets {
+              name
+              ... on Dog {
+                sound
+              }
+            }
+          }";
+
+        let vars = vec![
+        ].into_iter().collect();
+
+        let ctx = AppContext { sounds: SoundContext { woof: "Woof!".to_owned() } };
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &ctx)
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        println!("Result: {:?}", result);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("pets", Value::list(vec![
+                    Value::object(vec![
+                        ("name", Value::string("Odie")),
+                        ("sound", Value::string("Woof!")),
+                    ].into_iter().collect()),
+                    Value::object(vec![
+                        ("name", Value::string("Garfield")),
+                    ].into_iter().collect()),
+                ])),
+            ].into_iter().collect()));
+    }
+}
+
+
+
+
+mod trait_objects {
+    use value::Value;
+    use schema::model::RootNode;
+    use types::base::DynGraphQLType;
+    use types::scalars::EmptyMutation;
+
+    trait Pet: DynGraphQLType<()> {
+        fn name(&self) -> &str;
+    }
+
+    graphql_interface!(Box<Pet>: () as "Pet" |&self| {
+        field name() -> &str { self.name() }
+
+        instance_types: [Dog, Cat]
+    });
+
+    struct Dog {
+        name: String,
+        woofs: bool,
+    }
+
+    impl Pet for Dog {
+        fn name(&self) -> &str { &self.name }
+    }
+
+    graphql_object!(Dog: () |&self| {
+        field name() -> &str { &self.name }
+        field woofs() -> bool { self.woofs }
+
+        interfaces: [Box<Pet>]
+    });
+
+    struct Cat {
+        name: String,
+        meows: bool,
+    }
+
+    impl Pet for Cat {
+        fn name(&self) -> &str { &self.name }
+    }
+
+    graphql_object!(Cat: () |&self| {
+        field name() -> &str { &self.name }
+        field meows() -> bool { self.meows }
+
+        interfaces: [Box<Pet>]
+    });
+
+    struct Schema {
+        pets: Vec<Box<Pet>>,
+    }
+
+    graphql_object!(Schema: () |&self| {
+        field pets() -> &Vec<Box<Pet>> {
+            &self.pets
+        }
+    });
+
+    #[test]
+    fn test() {
+        let schema = RootNode::new(
+            Schema {
+                pets: vec![
+                    Box::new(Dog { name: "Odie".to_owned(), woofs: true }),
+                    Box::new(Cat { name: "Garfield".to_owned(), meows: false }),
+                ],
+            },
+            EmptyMutation::<()>::new());
+        let doc = r"
+          {
+            pets {
+              __typename
+              name
+              ... on Dog {
+                woofs
+              }
+              ... on Cat {
+                meows
+              }
+            }
+          }";
+
+        let vars = vec![
+        ].into_iter().collect();
+
+        let (result, errs) = ::execute(doc, None, &schema, &vars, &())
+            .expect("Execution failed");
+
+        assert_eq!(errs, []);
+
+        println!("Result: {:?}", result);
+
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("pets", Value::list(vec![
+                    Value::object(vec![
+                        ("__typename", Value::string("Dog")),
+                        ("name", Value::string("Odie")),
+                        ("woofs", Value::boolean(true)),
+                    ].into_iter().collect()),
+                    Value::object(vec![
+                        ("__typename", Value::string("Cat")),
+                        ("name", Value::string("Garfield")),
+                        ("meows", Value::boolean(false)),
+                    ].into_iter().collect()),
+                ])),
+            ].into_iter().collect()));
+    }
+}
@@ -1,13 +1,15 @@
 use std::collections::HashMap;
 
-use value::Value;
+use value::{Value, Object};
 use ast::InputValue;
 use executor::Variables;
 use schema::model::RootNode;
 use ::GraphQLError::ValidationError;
 use validation::RuleError;
+use ::GraphQLErrorCode;
 use parser::SourcePosition;
 use types::scalars::EmptyMutation;
+use types::nullable::Nullable;
 
 #[derive(Debug)]
 struct TestComplexScalar;
@@ -64,6 +66,14 @@ graphql_input_object!(
     }
 );
 
+graphql_input_object!(
+    #[derive(Debug)]
+    struct PatchInputObject {
+        a: Nullable<String>,
+        b: Nullable<i32>,
+    }
+);
+
 graphql_object!(TestType: () |&self| {
     field field_with_object_input(input: Option<TestInputObject>) -> String {
         format!("{:?}", input)
@@ -109,6 +119,14 @@ graphql_object!(TestType: () |&self| {
         format!("a: {:?}", arg.a)
     }
 
+    field field_with_nullable_input(input: Nullable<String>) -> String {
+        format!("{:?}", input)
+    }
+
+    field patch_input(arg: PatchInputObject) -> String {
+        format!("a: {:?}, b: {:?}", arg.a, arg.b)
+    }
+
     field integer_input(value: i32) -> String {
         format!("value: {}", value)
     }
@@ -119,7 +137,7 @@ graphql_object!(TestType: () |&self| {
 });
 
 fn run_variable_query<F>(query: &str, vars: Variables, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     let schema = RootNode::new(TestType, EmptyMutation::<()>::new());
 
@@ -136,7 +154,7 @@ fn run_variable_query<F>(query: &str, vars: Variables, f: F)
 }
 
 fn run_query<F>(query: &str, f: F)
-    where F: Fn(&HashMap<String, Value>) -> ()
+    where F: Fn(&Object) -> ()
 {
     run_variable_query(query, Variables::new(), f);
 }
@@ -247,7 +265,7 @@ fn variable_error_on_nested_non_null() {
         RuleError::new(
             r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -267,7 +285,7 @@ fn variable_error_on_incorrect_type() {
         RuleError::new(
             r#"Variable "$input" got invalid value. Expected "TestInputObject", found not an object."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -290,7 +308,7 @@ fn variable_error_on_omit_non_null() {
         RuleError::new(
             r#"Variable "$input" got invalid value. In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -314,11 +332,11 @@ fn variable_multiple_errors_with_nesting() {
         RuleError::new(
             r#"Variable "$input" got invalid value. In field "na": In field "c": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
         RuleError::new(
             r#"Variable "$input" got invalid value. In field "nb": Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -343,7 +361,7 @@ fn variable_error_on_additional_field() {
         RuleError::new(
             r#"Variable "$input" got invalid value. In field "extra": Unknown field."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -434,7 +452,7 @@ fn does_not_allow_non_nullable_input_to_be_omitted_in_variable() {
         RuleError::new(
             r#"Variable "$value" of required type "String!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -454,7 +472,7 @@ fn does_not_allow_non_nullable_input_to_be_set_to_null_in_variable() {
         RuleError::new(
             r#"Variable "$value" of required type "String!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -547,7 +565,7 @@ fn does_not_allow_non_null_lists_to_be_null() {
         RuleError::new(
             r#"Variable "$input" of required type "[String]!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -634,7 +652,7 @@ fn does_not_allow_lists_of_non_null_to_contain_null() {
         RuleError::new(
             r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -658,7 +676,7 @@ fn does_not_allow_non_null_lists_of_non_null_to_contain_null() {
         RuleError::new(
             r#"Variable "$input" got invalid value. In element #1: Expected "String!", found null."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -678,7 +696,7 @@ fn does_not_allow_non_null_lists_of_non_null_to_be_null() {
         RuleError::new(
             r#"Variable "$input" of required type "[String!]!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -766,6 +784,28 @@ fn default_argument_when_nullable_variable_not_provided() {
         });
 }
 
+#[test]
+fn variable_defaults_alongside_provided_variables() {
+    let schema = RootNode::new(TestType, EmptyMutation::<()>::new());
+    let vars: Variables = vec![
+        ("list".to_owned(), InputValue::list(vec![InputValue::string("A")])),
+    ].into_iter().collect();
+
+    let (result, errs) = ::execute(
+        r#"query q($list: [String], $input: String = "Default") { list(input: $list) fieldWithNullableStringInput(input: $input) }"#,
+        None, &schema, &vars, &())
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result.as_object_value().unwrap().get("fieldWithNullableStringInput"),
+        Some(&Value::string(r#"Some("Default")"#)));
+    assert_eq!(
+        result.as_object_value().unwrap().get("list"),
+        Some(&Value::string(r#"Some([Some("A")])"#)));
+    assert_eq!(vars.len(), 1);
+}
+
 #[test]
 fn default_argument_when_nullable_variable_set_to_null() {
     run_variable_query(
@@ -887,7 +927,7 @@ fn does_not_allow_missing_variable_for_required_field() {
         RuleError::new(
             r#"Variable "$var" of required type "Int!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -907,7 +947,7 @@ fn does_not_allow_null_variable_for_required_field() {
         RuleError::new(
             r#"Variable "$var" of required type "Int!" was not provided."#,
             &[SourcePosition::new(8, 0, 8)],
-        ),
+        ).with_code(GraphQLErrorCode::BadUserInput),
     ]));
 }
 
@@ -999,7 +1039,7 @@ mod integers {
             RuleError::new(
                 r#"Variable "$var" got invalid value. Expected "Int"."#,
                 &[SourcePosition::new(8, 0, 8)],
-            ),
+            ).with_code(GraphQLErrorCode::BadUserInput),
         ]));
     }
 
@@ -1019,7 +1059,7 @@ mod integers {
             RuleError::new(
                 r#"Variable "$var" got invalid value. Expected "Int"."#,
                 &[SourcePosition::new(8, 0, 8)],
-            ),
+            ).with_code(GraphQLErrorCode::BadUserInput),
         ]));
     }
 }
@@ -1072,7 +1112,92 @@ mod floats {
             RuleError::new(
                 r#"Variable "$var" got invalid value. Expected "Float"."#,
                 &[SourcePosition::new(8, 0, 8)],
-            ),
+            ).with_code(GraphQLErrorCode::BadUserInput),
         ]));
     }
 }
+
+#[test]
+fn nullable_input_implicit_null_when_omitted() {
+    run_query(
+        r#"{ fieldWithNullableInput }"#,
+        |result| {
+            assert_eq!(
+                result.get("fieldWithNullableInput"),
+                Some(&Value::string(r#"ImplicitNull"#)));
+        });
+}
+
+#[test]
+fn nullable_input_implicit_null_when_variable_not_provided() {
+    run_query(
+        r#"query q($value: String) { fieldWithNullableInput(input: $value) }"#,
+        |result| {
+            assert_eq!(
+                result.get("fieldWithNullableInput"),
+                Some(&Value::string(r#"ImplicitNull"#)));
+        });
+}
+
+#[test]
+fn nullable_input_explicit_null() {
+    run_query(
+        r#"{ fieldWithNullableInput(input: null) }"#,
+        |result| {
+            assert_eq!(
+                result.get("fieldWithNullableInput"),
+                Some(&Value::string(r#"ExplicitNull"#)));
+        });
+}
+
+#[test]
+fn nullable_input_explicit_null_in_variable() {
+    run_variable_query(
+        r#"query q($value: String) { fieldWithNullableInput(input: $value) }"#,
+        vec![
+            ("value".to_owned(), InputValue::null()),
+        ].into_iter().collect(),
+        |result| {
+            assert_eq!(
+                result.get("fieldWithNullableInput"),
+                Some(&Value::string(r#"ExplicitNull"#)));
+        });
+}
+
+#[test]
+fn nullable_input_set_to_value() {
+    run_query(
+        r#"{ fieldWithNullableInput(input: "a") }"#,
+        |result| {
+            assert_eq!(
+                result.get("fieldWithNullableInput"),
+                Some(&Value::string(r#"Some("a")"#)));
+        });
+}
+
+#[test]
+fn nullable_input_object_fields() {
+    run_query(
+        r#"{ patchInput(arg: {a: null}) }"#,
+        |result| {
+            assert_eq!(
+                result.get("patchInput"),
+                Some(&Value::string(r#"a: ExplicitNull, b: ImplicitNull"#)));
+        });
+}
+
+#[test]
+fn nullable_input_object_fields_in_variable() {
+    run_variable_query(
+        r#"query q($input: PatchInputObject!) { patchInput(arg: $input) }"#,
+        vec![
+            ("input".to_owned(), InputValue::object(vec![
+                ("b", InputValue::int(1)),
+            ].into_iter().collect())),
+        ].into_iter().collect(),
+        |result| {
+            assert_eq!(
+                result.get("patchInput"),
+                Some(&Value::string(r#"a: ImplicitNull, b: Some(1)"#)));
+        });
+}
@@ -0,0 +1,248 @@
+// `Arbitrary` implementations for the syntax tree
+//
+// Names are picked from small sets, so that generated documents refer to the
+// same fields, types, variables and fragments often enough for the validator
+// to get past its first checks. Generated trees are nested at most
+// `MAX_DEPTH` levels deep, and print as documents that parse.
+
+use std::borrow::Cow;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use ast::{Arguments, Definition, Directive, Field, Fragment, FragmentSpread, InlineFragment, InputValue,
+          Operation, OperationType, Selection, Type, VariableDefinition, VariableDefinitions};
+use parser::Spanning;
+
+const MAX_DEPTH: usize = 4;
+
+const NAMES: &[&str] = &["id", "name", "hero", "human", "droid", "friends", "episode", "first", "a", "b"];
+const TYPE_NAMES: &[&str] = &["Int", "Float", "String", "Boolean", "ID", "Episode", "Character", "Human"];
+const ENUM_VALUES: &[&str] = &["NEW_HOPE", "EMPIRE", "JEDI"];
+const DIRECTIVE_NAMES: &[&str] = &["skip", "include", "deprecated", "a"];
+
+impl<'a, S> Arbitrary<'a> for Definition<'a, S> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Definition<'a, S>> {
+        if try!(u.ratio(1, 4)) {
+            Ok(Definition::Fragment(Spanning::unlocated(Fragment {
+                name: Spanning::unlocated(try!(name(u))),
+                type_condition: Spanning::unlocated(*try!(u.choose(TYPE_NAMES))),
+                directives: try!(directives(u, 0)),
+                selection_set: try!(selection_set(u, 0)),
+            })))
+        } else {
+            Ok(Definition::Operation(Spanning::unlocated(Operation {
+                operation_type: if try!(u.ratio(1, 4)) { OperationType::Mutation } else { OperationType::Query },
+                name: if try!(u.arbitrary()) { Some(Spanning::unlocated(try!(name(u)))) } else { None },
+                variable_definitions: try!(variable_definitions(u)),
+                directives: try!(directives(u, 0)),
+                selection_set: try!(selection_set(u, 0)),
+            })))
+        }
+    }
+}
+
+impl<'a, S> Arbitrary<'a> for InputValue<S> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<InputValue<S>> {
+        input_value(u, 0, true)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Type<'a> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Type<'a>> {
+        type_literal(u, 0)
+    }
+}
+
+fn name(u: &mut Unstructured) -> Result<&'static str> {
+    u.choose(NAMES).cloned()
+}
+
+fn type_literal<'a>(u: &mut Unstructured, depth: usize) -> Result<Type<'a>> {
+    let kind = if depth < MAX_DEPTH { try!(u.int_in_range(0..=3)) } else { try!(u.int_in_range(0..=1)) };
+    let type_name = *try!(u.choose(TYPE_NAMES));
+
+    Ok(match kind {
+        0 => Type::Named(Cow::Borrowed(type_name)),
+        1 => Type::NonNullNamed(Cow::Borrowed(type_name)),
+        2 => Type::List(Box::new(try!(type_literal(u, depth + 1)))),
+        _ => Type::NonNullList(Box::new(try!(type_literal(u, depth + 1)))),
+    })
+}
+
+// Variables aren't allowed in default values
+fn input_value<S>(u: &mut Unstructured, depth: usize, variables: bool) -> Result<InputValue<S>> {
+    let kind = if depth < MAX_DEPTH { try!(u.int_in_range(0..=8)) } else { try!(u.int_in_range(0..=6)) };
+
+    Ok(match kind {
+        0 => InputValue::Null,
+        1 => InputValue::Int(try!(u.arbitrary())),
+        2 => {
+            let f: f64 = try!(u.arbitrary());
+            InputValue::Float(if f.is_finite() { f } else { 0.5 })
+        },
+        3 => InputValue::String(try!(u.arbitrary())),
+        4 => InputValue::Boolean(try!(u.arbitrary())),
+        5 => InputValue::Enum(try!(u.choose(ENUM_VALUES)).to_string()),
+        6 if variables => InputValue::Variable(try!(name(u)).to_owned()),
+        6 => InputValue::Null,
+        7 => {
+            let len = try!(u.int_in_range(0..=3));
+            let mut items = Vec::with_capacity(len);
+            for _ in 0..len {
+                items.push(Spanning::unlocated(try!(input_value(u, depth + 1, variables))));
+            }
+            InputValue::List(items)
+        },
+        _ => {
+            let len = try!(u.int_in_range(0..=3));
+            let mut fields = Vec::with_capacity(len);
+            for _ in 0..len {
+                fields.push((
+                    Spanning::unlocated(try!(name(u)).to_owned()),
+                    Spanning::unlocated(try!(input_value(u, depth + 1, variables)))));
+            }
+            InputValue::Object(fields)
+        },
+    })
+}
+
+fn variable_definitions<'a, S>(u: &mut Unstructured) -> Result<Option<Spanning<VariableDefinitions<'a, S>>>> {
+    let len = try!(u.int_in_range(0..=2));
+    if len == 0 {
+        return Ok(None);
+    }
+
+    let mut items = Vec::with_capacity(len);
+    for _ in 0..len {
+        let default_value = if try!(u.arbitrary()) {
+            Some(Spanning::unlocated(try!(input_value(u, 0, false))))
+        } else {
+            None
+        };
+
+        items.push((
+            Spanning::unlocated(try!(name(u))),
+            VariableDefinition {
+                var_type: Spanning::unlocated(try!(type_literal(u, 0))),
+                default_value: default_value,
+            }));
+    }
+
+    Ok(Some(Spanning::unlocated(VariableDefinitions { items: items })))
+}
+
+fn arguments<'a, S>(u: &mut Unstructured) -> Result<Option<Spanning<Arguments<'a, S>>>> {
+    let len = try!(u.int_in_range(0..=2));
+    if len == 0 {
+        return Ok(None);
+    }
+
+    let mut items = Vec::with_capacity(len);
+    for _ in 0..len {
+        items.push((Spanning::unlocated(try!(name(u))), Spanning::unlocated(try!(input_value(u, 0, true)))));
+    }
+
+    Ok(Some(Spanning::unlocated(Arguments { items: items })))
+}
+
+fn directives<'a, S>(u: &mut Unstructured, depth: usize) -> Result<Option<Vec<Spanning<Directive<'a, S>>>>> {
+    if depth >= MAX_DEPTH || try!(u.ratio(3, 4)) {
+        return Ok(None);
+    }
+
+    let len = try!(u.int_in_range(1..=2));
+    let mut items = Vec::with_capacity(len);
+    for _ in 0..len {
+        items.push(Spanning::unlocated(Directive {
+            name: Spanning::unlocated(*try!(u.choose(DIRECTIVE_NAMES))),
+            arguments: try!(arguments(u)),
+        }));
+    }
+
+    Ok(Some(items))
+}
+
+fn selection_set<'a, S>(u: &mut Unstructured, depth: usize) -> Result<Vec<Selection<'a, S>>> {
+    let len = try!(u.int_in_range(1..=3));
+    let mut selections = Vec::with_capacity(len);
+
+    for _ in 0..len {
+        let kind = if depth < MAX_DEPTH { try!(u.int_in_range(0..=5)) } else { 0 };
+
+        selections.push(match kind {
+            4 => Selection::FragmentSpread(Spanning::unlocated(FragmentSpread {
+                name: Spanning::unlocated(try!(name(u))),
+                directives: try!(directives(u, depth)),
+            })),
+            5 => Selection::InlineFragment(Spanning::unlocated(InlineFragment {
+                type_condition: if try!(u.arbitrary()) {
+                    Some(Spanning::unlocated(*try!(u.choose(TYPE_NAMES))))
+                } else {
+                    None
+                },
+                directives: try!(directives(u, depth)),
+                selection_set: try!(selection_set(u, depth + 1)),
+            })),
+            _ => Selection::Field(Spanning::unlocated(Field {
+                alias: if try!(u.ratio(1, 4)) { Some(Spanning::unlocated(try!(name(u)))) } else { None },
+                name: Spanning::unlocated(try!(name(u))),
+                arguments: try!(arguments(u)),
+                directives: try!(directives(u, depth)),
+                selection_set: if depth < MAX_DEPTH && try!(u.arbitrary()) {
+                    Some(try!(selection_set(u, depth + 1)))
+                } else {
+                    None
+                },
+            })),
+        });
+    }
+
+    Ok(selections)
+}
+
+#[cfg(test)]
+mod tests {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+    use value::DefaultScalarValue;
+
+    use fuzz::{parse, parse_and_validate, print_document, Document, Limits};
+
+    // Deterministic pseudo-random input, so failures can be reproduced
+    fn bytes(seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        (0..512).map(|_| {
+            state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+            (state >> 33) as u8
+        }).collect()
+    }
+
+    #[test]
+    fn generated_documents_round_trip() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let limits = Limits::default();
+
+        for seed in 0..2000 {
+            let data = bytes(seed);
+            let document: Document<DefaultScalarValue> = match Arbitrary::arbitrary(&mut Unstructured::new(&data)) {
+                Ok(document) => document,
+                Err(_) => continue,
+            };
+            if document.is_empty() {
+                continue;
+            }
+
+            let printed = print_document(&document);
+            let parsed = parse::<DefaultScalarValue>(&printed, &limits)
+                .unwrap_or_else(|err| panic!("{} does not parse: {}", printed, err));
+
+            assert_eq!(print_document(&parsed), printed);
+
+            // Validating must not panic, whether the document is valid or not
+            let _ = parse_and_validate(&schema, &printed, &limits);
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Entry points for fuzzing the parser and validator
+//!
+//! `parse` and `parse_and_validate` reject documents that are too long or
+//! nested too deeply before parsing them, so arbitrary input can't exhaust the
+//! stack of the recursive parser and validator. They return an error for
+//! every input they can't handle, and a panic in them is a bug:
+//!
+//! ```rust
+//! use juniper::DefaultScalarValue;
+//! use juniper::fuzz::{self, Limits};
+//!
+//! let limits = Limits::default();
+//! let deep = format!("{}{}", "{ a ".repeat(1000), "}".repeat(1000));
+//!
+//! assert!(fuzz::parse::<DefaultScalarValue>(&deep, &limits).is_err());
+//!
+//! let document = fuzz::parse::<DefaultScalarValue>("query Q($id: ID!) { node(id: $id) { id } }", &limits)
+//!     .unwrap();
+//! assert_eq!(fuzz::print_document(&document), "query Q($id: ID!) { node(id: $id) { id } }");
+//! ```
+//!
+//! `print_document` prints a document so it parses back into the same
+//! document, which makes round trips a property to test. With the
+//! `arbitrary` feature, `Definition`s (and so `Document`s), `InputValue`s and
+//! `Type`s implement `arbitrary::Arbitrary`, for fuzzers and property tests
+//! to generate syntax trees rather than text.
+
+use std::error::Error;
+use std::fmt;
+
+use parser::{parse_document_source, Lexer, Token};
+use schema::model::RootNode;
+use types::base::GraphQLType;
+use validation::{visit_all_rules, ValidatorContext};
+use value::ScalarValue;
+
+use GraphQLError;
+
+pub use ast::{Definition, Document};
+
+#[cfg(feature="arbitrary")] mod generate;
+
+/// The limits inputs are checked against before they're parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum length of a document in bytes
+    pub max_length: usize,
+    /// The maximum nesting of braces, brackets and parentheses
+    pub max_depth: usize,
+}
+
+/// Error parsing or validating a document with `Limits`
+#[derive(Debug, PartialEq)]
+pub enum BoundedError<'a> {
+    /// The document is longer than the given number of bytes
+    TooLong(usize),
+    /// The document is nested deeper than the given depth
+    TooDeep(usize),
+    /// The document doesn't parse, or doesn't validate against the schema
+    Invalid(GraphQLError<'a>),
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            max_length: 64 * 1024,
+            max_depth: 64,
+        }
+    }
+}
+
+/// Parse a document, if it's within the limits
+pub fn parse<'a, S>(source: &'a str, limits: &Limits) -> Result<Document<'a, S>, BoundedError<'a>>
+    where S: ScalarValue
+{
+    try!(check_limits(source, limits));
+
+    parse_document_source(source).map_err(|err| BoundedError::Invalid(GraphQLError::ParseError(err)))
+}
+
+/// Parse a document, if it's within the limits, and validate it against the
+/// schema
+pub fn parse_and_validate<'a, QueryT, MutationT, S>(
+    root_node: &RootNode<QueryT, MutationT, S>,
+    source: &'a str,
+    limits: &Limits,
+)
+    -> Result<Document<'a, S>, BoundedError<'a>>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    let document = try!(parse(source, limits));
+
+    let errors = {
+        let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+        visit_all_rules(&mut ctx, &document);
+        ctx.into_errors()
+    };
+
+    if !errors.is_empty() {
+        return Err(BoundedError::Invalid(GraphQLError::ValidationError(errors)));
+    }
+
+    Ok(document)
+}
+
+/// Print a document on a single line
+pub fn print_document<S>(document: &Document<S>) -> String where S: ScalarValue {
+    document.iter().map(|def| def.to_string()).collect::<Vec<_>>().join(" ")
+}
+
+fn check_limits<'a>(source: &str, limits: &Limits) -> Result<(), BoundedError<'a>> {
+    if source.len() > limits.max_length {
+        return Err(BoundedError::TooLong(limits.max_length));
+    }
+
+    let mut depth = 0usize;
+
+    // Lexer errors are left to the parser to report
+    for token in Lexer::new(source) {
+        match token.map(|t| t.item) {
+            Ok(Token::CurlyOpen) | Ok(Token::BracketOpen) | Ok(Token::ParenOpen) => {
+                depth += 1;
+                if depth > limits.max_depth {
+                    return Err(BoundedError::TooDeep(limits.max_depth));
+                }
+            },
+            Ok(Token::CurlyClose) | Ok(Token::BracketClose) | Ok(Token::ParenClose) => {
+                depth = depth.saturating_sub(1);
+            },
+            Ok(Token::EndOfFile) | Err(_) => break,
+            Ok(_) => {},
+        }
+    }
+
+    Ok(())
+}
+
+impl<'a> fmt::Display for BoundedError<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BoundedError::TooLong(max) => write!(f, "The document is longer than {} bytes", max),
+            BoundedError::TooDeep(max) => write!(f, "The document is nested deeper than {} levels", max),
+            BoundedError::Invalid(GraphQLError::ParseError(ref err)) => fmt::Display::fmt(&err.item, f),
+            BoundedError::Invalid(GraphQLError::ValidationError(ref errs)) => {
+                let messages = errs.iter().map(|err| err.message()).collect::<Vec<_>>();
+                f.write_str(&messages.join("\n"))
+            },
+            BoundedError::Invalid(ref err) => write!(f, "{:?}", err),
+        }
+    }
+}
+
+impl<'a> Error for BoundedError<'a> {
+    fn description(&self) -> &str {
+        match *self {
+            BoundedError::TooLong(_) => "Document too long",
+            BoundedError::TooDeep(_) => "Document nested too deeply",
+            BoundedError::Invalid(_) => "Invalid document",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+    use value::DefaultScalarValue;
+
+    use super::{parse, parse_and_validate, print_document, BoundedError, Limits};
+
+    #[test]
+    fn rejects_documents_exceeding_the_limits() {
+        let limits = Limits { max_length: 40, max_depth: 3 };
+
+        assert_eq!(
+            parse::<DefaultScalarValue>("{ a { b { c } } }", &limits).map(|_| ()),
+            Ok(()));
+        assert_eq!(
+            parse::<DefaultScalarValue>("{ a { b { c { d } } } }", &limits).map(|_| ()),
+            Err(BoundedError::TooDeep(3)));
+        assert_eq!(
+            parse::<DefaultScalarValue>("{ a(x: [[[1]]]) }", &limits).map(|_| ()),
+            Err(BoundedError::TooDeep(3)));
+        assert_eq!(
+            parse::<DefaultScalarValue>(&format!("{{ {} }}", "a ".repeat(20)), &limits).map(|_| ()),
+            Err(BoundedError::TooLong(40)));
+        assert!(match parse::<DefaultScalarValue>("{ a", &limits) {
+            Err(BoundedError::Invalid(_)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn validates_against_the_schema() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let limits = Limits::default();
+
+        assert!(parse_and_validate(&schema, "{ hero { name } }", &limits).is_ok());
+        assert!(match parse_and_validate(&schema, "{ hero { nope } }", &limits) {
+            Err(BoundedError::Invalid(_)) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn prints_documents_that_parse_back() {
+        let source = r#"
+            query Hero($episode: Episode = JEDI, $first: [Int!]!) @dir(a: "q\"uo\\te\n") {
+                hero(episode: $episode) { ...Name friends @skip(if: false) { id } }
+                h: human(id: "1000", x: {a: 1.0, b: [null, true]}) { ... on Human { homePlanet } ... @include(if: true) { id } }
+            }
+            mutation { a }
+            fragment Name on Character { name }
+        "#;
+
+        let printed = print_document(&parse::<DefaultScalarValue>(source, &Limits::default()).unwrap());
+        assert_eq!(
+            printed,
+            "query Hero($episode: Episode = JEDI, $first: [Int!]!) @dir(a: \"q\\\"uo\\\\te\\n\") { \
+             hero(episode: $episode) { ...Name friends @skip(if: false) { id } } \
+             h: human(id: \"1000\", x: {a: 1.0, b: [null, true]}) { ... on Human { homePlanet } ... @include(if: true) { id } } } \
+             mutation { a } \
+             fragment Name on Character { name }");
+        assert_eq!(print_document(&parse::<DefaultScalarValue>(&printed, &Limits::default()).unwrap()), printed);
+    }
+}
@@ -2,7 +2,113 @@
 
 /// Generate the HTML source to show a GraphiQL interface
 pub fn graphiql_source(graphql_endpoint_url: &str) -> String {
-    let stylesheet_source = r#"
+    GraphiQLSource::new(graphql_endpoint_url).render()
+}
+
+/// Builder for a customized GraphiQL interface
+///
+/// ```rust
+/// use juniper::graphiql::GraphiQLSource;
+///
+/// let html = GraphiQLSource::new("/graphql")
+///     .version("0.11.2")
+///     .header("Authorization", "Bearer 123")
+///     .default_query("{ hero { name } }")
+///     .render();
+///
+/// assert!(html.contains("graphiql/0.11.2/graphiql.js"));
+/// ```
+pub struct GraphiQLSource {
+    graphql_url: String,
+    version: String,
+    headers: Vec<(String, String)>,
+    default_query: Option<String>,
+    assets_url: Option<String>,
+    subscriptions_url: Option<String>,
+    nonce: Option<String>,
+}
+
+impl GraphiQLSource {
+    /// Start building a GraphiQL interface targeting the specified URL
+    pub fn new(graphql_endpoint_url: &str) -> GraphiQLSource {
+        GraphiQLSource {
+            graphql_url: graphql_endpoint_url.to_owned(),
+            version: "0.10.2".to_owned(),
+            headers: Vec::new(),
+            default_query: None,
+            assets_url: None,
+            subscriptions_url: None,
+            nonce: None,
+        }
+    }
+
+    /// Load the given version of GraphiQL from the CDN instead of the default
+    pub fn version(mut self, version: &str) -> GraphiQLSource {
+        self.version = version.to_owned();
+        self
+    }
+
+    /// Send an additional header with every request, e.g. for authentication
+    pub fn header(mut self, name: &str, value: &str) -> GraphiQLSource {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// Show the given query when the editor is opened for the first time
+    pub fn default_query(mut self, query: &str) -> GraphiQLSource {
+        self.default_query = Some(query.to_owned());
+        self
+    }
+
+    /// Send subscriptions to the given WebSocket URL
+    ///
+    /// Subscription operations are sent with the `graphql-ws` protocol of
+    /// subscriptions-transport-ws, which the `http::websocket` module
+    /// implements. All other operations still go to the GraphQL endpoint.
+    pub fn subscriptions_url(mut self, url: &str) -> GraphiQLSource {
+        self.subscriptions_url = Some(url.to_owned());
+        self
+    }
+
+    /// Load the scripts and stylesheets from the given URL instead of the CDN
+    ///
+    /// The URL is the prefix of the files `graphiql.css`, `graphiql.js`,
+    /// `fetch.js`, `react.js`, and `react-dom.js`, and with a subscriptions URL
+    /// also `subscriptions-transport-ws.js` and
+    /// `graphiql-subscriptions-fetcher.js`. They can be served from the
+    /// `assets` module when the `graphiql-embedded` feature is enabled. This
+    /// overrides the version.
+    pub fn assets_url(mut self, url: &str) -> GraphiQLSource {
+        self.assets_url = Some(url.trim_end_matches('/').to_owned());
+        self
+    }
+
+    /// Mark the inline scripts and styles with a Content Security Policy nonce
+    ///
+    /// This lets the page be served with a policy like `script-src 'nonce-...'`
+    /// that forbids other inline scripts. The page doesn't use inline event
+    /// handlers, so nothing else needs `'unsafe-inline'`. Use a new random
+    /// nonce for every response.
+    pub fn nonce(mut self, nonce: &str) -> GraphiQLSource {
+        self.nonce = Some(nonce.to_owned());
+        self
+    }
+
+    fn asset_url(&self, cdn_url: &str, name: &str) -> String {
+        match self.assets_url {
+            Some(ref prefix) => format!("{}/{}", prefix, name),
+            None => cdn_url.to_owned(),
+        }
+    }
+
+    fn cdnjs_url(&self, path: &str) -> String {
+        let name = path.rsplit('/').next().unwrap_or(path);
+        self.asset_url(&format!("//cdnjs.cloudflare.com/ajax/libs/{}", path), name)
+    }
+
+    /// Generate the HTML source
+    pub fn render(&self) -> String {
+        let stylesheet_source = r#"
     <style>
         html, body, #app {
             height: 100%;
@@ -12,56 +118,246 @@ pub fn graphiql_source(graphql_endpoint_url: &str) -> String {
         }
     </style>
     "#;
-    let fetcher_source = r#"
+
+        let extra_headers: String = self.headers.iter()
+            .map(|&(ref name, ref value)| format!(
+                "\n                    {}: {},", js_string(name), js_string(value)))
+            .collect();
+        let default_query = match self.default_query {
+            Some(ref query) => js_string(query),
+            None => "undefined".to_owned(),
+        };
+
+        let (subscriptions_scripts, fetcher) = match self.subscriptions_url {
+            Some(ref url) => (
+                format!(
+                    concat!(
+                        "\n    <script src=\"{}\"></script>",
+                        "\n    <script src=\"{}\"></script>"),
+                    self.asset_url(
+                        "//unpkg.com/subscriptions-transport-ws@0.8.3/browser/client.js",
+                        "subscriptions-transport-ws.js"),
+                    self.asset_url(
+                        "//unpkg.com/graphiql-subscriptions-fetcher@0.0.2/browser/client.js",
+                        "graphiql-subscriptions-fetcher.js")),
+                format!(
+                    concat!(
+                        "window.GraphiQLSubscriptionsFetcher.graphQLFetcher(",
+                        "new window.SubscriptionsTransportWs.SubscriptionClient({}, {{ reconnect: true }}), ",
+                        "graphQLFetcher)"),
+                    js_string(url)),
+            ),
+            None => (String::new(), "graphQLFetcher".to_owned()),
+        };
+
+        let fetcher_source = format!(r#"
     <script>
-        function graphQLFetcher(params) {
-            return fetch(GRAPHQL_URL, {
+        function graphQLFetcher(params) {{
+            return fetch(GRAPHQL_URL, {{
                 method: 'post',
-                headers: {
+                headers: {{
                     'Accept': 'application/json',
-                    'Content-Type': 'application/json',
-                },
+                    'Content-Type': 'application/json',{extra_headers}
+                }},
                 body: JSON.stringify(params)
-            }).then(function (response) {
+            }}).then(function (response) {{
                 return response.text();
-            }).then(function (body) {
-                try {
+            }}).then(function (body) {{
+                try {{
                     return JSON.parse(body);
-                } catch (error) {
+                }} catch (error) {{
                     return body;
-                }
-            });
-        }
+                }}
+            }});
+        }}
         ReactDOM.render(
-            React.createElement(GraphiQL, {
-                fetcher: graphQLFetcher,
-            }),
+            React.createElement(GraphiQL, {{
+                fetcher: {fetcher},
+                defaultQuery: {default_query},
+            }}),
             document.querySelector('#app'));
     </script>
-    "#;
+    "#,
+            extra_headers = extra_headers,
+            fetcher = fetcher,
+            default_query = default_query);
 
-    format!(r#"
+        let html = format!(r#"
 <!DOCTYPE html>
 <html>
 <head>
     <title>GraphQL</title>
     {stylesheet_source}
-    <link rel="stylesheet" type="text/css" href="//cdnjs.cloudflare.com/ajax/libs/graphiql/0.10.2/graphiql.css">
+    <link rel="stylesheet" type="text/css" href="{graphiql_css}">
 </head>
 <body>
     <div id="app"></div>
 
-    <script src="//cdnjs.cloudflare.com/ajax/libs/fetch/2.0.3/fetch.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react-dom.js"></script>
-    <script src="//cdnjs.cloudflare.com/ajax/libs/graphiql/0.10.2/graphiql.js"></script>
+    <script src="{fetch_js}"></script>
+    <script src="{react_js}"></script>
+    <script src="{react_dom_js}"></script>
+    <script src="{graphiql_js}"></script>{subscriptions_scripts}
     <script>var GRAPHQL_URL = '{graphql_url}';</script>
     {fetcher_source}
 </body>
 </html>
 "#,
-        graphql_url = graphql_endpoint_url,
-        stylesheet_source = stylesheet_source,
-        fetcher_source = fetcher_source)
+            graphql_url = self.graphql_url,
+            graphiql_css = self.cdnjs_url(&format!("graphiql/{}/graphiql.css", self.version)),
+            fetch_js = self.cdnjs_url("fetch/2.0.3/fetch.js"),
+            react_js = self.cdnjs_url("react/15.6.0/react.js"),
+            react_dom_js = self.cdnjs_url("react/15.6.0/react-dom.js"),
+            graphiql_js = self.cdnjs_url(&format!("graphiql/{}/graphiql.js", self.version)),
+            subscriptions_scripts = subscriptions_scripts,
+            stylesheet_source = stylesheet_source,
+            fetcher_source = fetcher_source);
+
+        match self.nonce {
+            Some(ref nonce) => add_nonce(&html, nonce),
+            None => html,
+        }
+    }
+}
+
+// Adds the nonce attribute to every script and style element of a page
+#[doc(hidden)]
+pub fn add_nonce(html: &str, nonce: &str) -> String {
+    let nonce: String = nonce.chars()
+        .map(|c| match c {
+            '"' => "&quot;".to_owned(),
+            '&' => "&amp;".to_owned(),
+            '<' => "&lt;".to_owned(),
+            '>' => "&gt;".to_owned(),
+            c => c.to_string(),
+        })
+        .collect();
+
+    html
+        .replace("<script", &format!("<script nonce=\"{}\"", nonce))
+        .replace("<style", &format!("<style nonce=\"{}\"", nonce))
+}
+
+/// The GraphiQL scripts and stylesheets, for deployments without access to the
+/// CDN
+///
+/// Requires the `graphiql-embedded` feature. Serve the files under some URL,
+/// and pass it to `GraphiQLSource::assets_url`.
+#[cfg(feature="graphiql-embedded")]
+pub mod assets {
+    /// An embedded file
+    pub struct Asset {
+        /// The value of the `Content-Type` header
+        pub content_type: &'static str,
+        /// The contents of the file
+        pub content: &'static [u8],
+    }
+
+    /// Look up an embedded file by its name, e.g. `graphiql.js`
+    pub fn get(name: &str) -> Option<Asset> {
+        let (content_type, content): (_, &'static [u8]) = match name {
+            "graphiql.css" => ("text/css", include_bytes!("../assets/graphiql/graphiql.css")),
+            "graphiql.js" => ("application/javascript", include_bytes!("../assets/graphiql/graphiql.js")),
+            "fetch.js" => ("application/javascript", include_bytes!("../assets/graphiql/fetch.js")),
+            "react.js" => ("application/javascript", include_bytes!("../assets/graphiql/react.js")),
+            "react-dom.js" => ("application/javascript", include_bytes!("../assets/graphiql/react-dom.js")),
+            "subscriptions-transport-ws.js" =>
+                ("application/javascript", include_bytes!("../assets/graphiql/subscriptions-transport-ws.js")),
+            "graphiql-subscriptions-fetcher.js" =>
+                ("application/javascript", include_bytes!("../assets/graphiql/graphiql-subscriptions-fetcher.js")),
+            _ => return None,
+        };
+
+        Some(Asset {
+            content_type: content_type,
+            content: content,
+        })
+    }
+}
+
+// Quote a string for inclusion in a script element. `<` is escaped so the
+// string can't end the element.
+fn js_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '<' => quoted.push_str("\\u003c"),
+            '\u{2028}' => quoted.push_str("\\u2028"),
+            '\u{2029}' => quoted.push_str("\\u2029"),
+            c => quoted.push(c),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{graphiql_source, GraphiQLSource};
+
+    #[test]
+    fn default_source() {
+        let html = graphiql_source("/graphql");
+
+        assert!(html.contains("var GRAPHQL_URL = '/graphql';"));
+        assert!(html.contains("graphiql/0.10.2/graphiql.css"));
+        assert!(html.contains("defaultQuery: undefined,"));
+        assert!(html.contains(r#"<script src="//cdnjs.cloudflare.com/ajax/libs/react/15.6.0/react.js">"#));
+    }
+
+    #[test]
+    fn assets_can_be_served_locally() {
+        let html = GraphiQLSource::new("/graphql").assets_url("/graphiql/").render();
+
+        assert!(html.contains(r#"href="/graphiql/graphiql.css""#));
+        assert!(html.contains(r#"<script src="/graphiql/react-dom.js">"#));
+        assert!(!html.contains("cdnjs"));
+    }
+
+    #[cfg(feature="graphiql-embedded")]
+    #[test]
+    fn assets_are_embedded() {
+        let asset = super::assets::get("graphiql.js").expect("graphiql.js should be embedded");
+
+        assert_eq!(asset.content_type, "application/javascript");
+        assert!(!asset.content.is_empty());
+        assert!(super::assets::get("nope.js").is_none());
+    }
+
+    #[test]
+    fn subscriptions_use_a_websocket_fetcher() {
+        let html = graphiql_source("/graphql");
+        assert!(html.contains("fetcher: graphQLFetcher,"));
+        assert!(!html.contains("subscriptions-transport-ws"));
+
+        let html = GraphiQLSource::new("/graphql").subscriptions_url("ws://localhost/subscriptions").render();
+        assert!(html.contains(r#"<script src="//unpkg.com/subscriptions-transport-ws@0.8.3/browser/client.js">"#));
+        assert!(html.contains(r#"SubscriptionClient("ws://localhost/subscriptions", { reconnect: true })"#));
+    }
+
+    #[test]
+    fn scripts_and_styles_carry_the_nonce() {
+        let html = GraphiQLSource::new("/graphql").nonce("abc\"123").render();
+
+        assert_eq!(html.matches("<script").count(), html.matches(r#"<script nonce="abc&quot;123""#).count());
+        assert!(html.contains(r#"<style nonce="abc&quot;123">"#));
+        assert!(!graphiql_source("/graphql").contains("nonce"));
+    }
+
+    #[test]
+    fn headers_and_default_query_are_escaped() {
+        let html = GraphiQLSource::new("/graphql")
+            .header("Authorization", "Bearer \"x\"")
+            .default_query("# </script>\n{ hero }")
+            .render();
 
-}
\ No newline at end of file
+        assert!(html.contains(r#""Authorization": "Bearer \"x\"","#));
+        assert!(html.contains(r##"defaultQuery: "# \u003c/script>\n{ hero }","##));
+    }
+}
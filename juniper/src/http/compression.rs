@@ -0,0 +1,264 @@
+//! Compressing responses. Requires the `compression` feature enabled.
+//!
+//! Introspection queries and long lists compress very well, so integrations
+//! can encode their responses with gzip or deflate when the client's
+//! `Accept-Encoding` header allows it. Small responses are sent as they are,
+//! since compressing them costs more than it saves:
+//!
+//! ```rust,ignore
+//! let compression = Compression::new().with_min_bytes(1024);
+//! let response = compression.compress(handler_response, Some("gzip, deflate;q=0.5"));
+//! ```
+//!
+//! Responses of endpoints with compression enabled always carry
+//! `Vary: Accept-Encoding`, so caches keep the encodings apart.
+
+use std::io::{self, Write};
+
+use flate2::Compression as Level;
+use flate2::write::{GzEncoder, ZlibEncoder};
+
+use http::handler::HandlerResponse;
+
+/// The encoding of a response body
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Encoding {
+    /// Not compressed
+    Identity,
+    /// `gzip`
+    Gzip,
+    /// `deflate`, which is the zlib format in HTTP
+    Deflate,
+}
+
+/// When and how to compress responses
+#[derive(Clone, Copy, Debug)]
+pub struct Compression {
+    min_bytes: usize,
+}
+
+/// A response with an encoded body
+#[derive(Debug)]
+pub struct EncodedResponse {
+    status: u16,
+    encoding: Encoding,
+    headers: Vec<(&'static str, &'static str)>,
+    body: Vec<u8>,
+}
+
+impl Encoding {
+    /// Pick the encoding of the response from the `Accept-Encoding` header
+    ///
+    /// gzip is preferred over deflate if the client accepts both equally.
+    /// Clients that don't send the header get the body as it is.
+    pub fn negotiate(accept_encoding: Option<&str>) -> Encoding {
+        let accept_encoding = match accept_encoding {
+            Some(accept_encoding) => accept_encoding,
+            None => return Encoding::Identity,
+        };
+
+        let gzip = quality(accept_encoding, Encoding::Gzip);
+        let deflate = quality(accept_encoding, Encoding::Deflate);
+
+        if gzip > 0.0 && gzip >= deflate {
+            Encoding::Gzip
+        } else if deflate > 0.0 {
+            Encoding::Deflate
+        } else {
+            Encoding::Identity
+        }
+    }
+
+    /// The value of the `Content-Encoding` header, if any
+    pub fn content_encoding(&self) -> Option<&'static str> {
+        match *self {
+            Encoding::Identity => None,
+            Encoding::Gzip => Some("gzip"),
+            Encoding::Deflate => Some("deflate"),
+        }
+    }
+
+    /// Encode a body
+    pub fn encode(&self, body: &[u8]) -> io::Result<Vec<u8>> {
+        match *self {
+            Encoding::Identity => Ok(body.to_vec()),
+            Encoding::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), Level::default());
+                try!(encoder.write_all(body));
+                encoder.finish()
+            },
+            Encoding::Deflate => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Level::default());
+                try!(encoder.write_all(body));
+                encoder.finish()
+            },
+        }
+    }
+}
+
+impl Compression {
+    /// Compress responses of at least 1 KiB
+    pub fn new() -> Compression {
+        Compression {
+            min_bytes: 1024,
+        }
+    }
+
+    /// Only compress responses of at least the given size
+    pub fn with_min_bytes(mut self, min_bytes: usize) -> Compression {
+        self.min_bytes = min_bytes;
+        self
+    }
+
+    /// The encoding of a body of the given length
+    pub fn choose(&self, accept_encoding: Option<&str>, body_len: usize) -> Encoding {
+        if body_len < self.min_bytes {
+            Encoding::Identity
+        } else {
+            Encoding::negotiate(accept_encoding)
+        }
+    }
+
+    /// Encode a response for a client sending the given `Accept-Encoding`
+    /// header
+    ///
+    /// If the body fails to encode, it's sent as it is.
+    pub fn compress(&self, response: HandlerResponse, accept_encoding: Option<&str>) -> EncodedResponse {
+        let encoding = self.choose(accept_encoding, response.body().len());
+        let status = response.status();
+
+        let mut headers = response.headers().to_vec();
+
+        let encoded = match encoding {
+            Encoding::Identity => None,
+            encoding => encoding.encode(response.body().as_bytes()).ok(),
+        };
+        let (encoding, body) = match encoded {
+            Some(body) => (encoding, body),
+            None => (Encoding::Identity, response.into_body().into_bytes()),
+        };
+
+        headers.push(("Vary", "Accept-Encoding"));
+        if let Some(content_encoding) = encoding.content_encoding() {
+            headers.push(("Content-Encoding", content_encoding));
+        }
+
+        EncodedResponse {
+            status: status,
+            encoding: encoding,
+            headers: headers,
+            body: body,
+        }
+    }
+}
+
+impl Default for Compression {
+    fn default() -> Compression {
+        Compression::new()
+    }
+}
+
+impl EncodedResponse {
+    /// The HTTP status
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The encoding of the body
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// The headers of the response, including `Content-Type`, `Vary` and
+    /// `Content-Encoding`
+    pub fn headers(&self) -> &[(&'static str, &'static str)] {
+        &self.headers
+    }
+
+    /// The encoded body
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Take the encoded body
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+// The quality of the most specific coding matching the encoding, or zero if
+// none matches
+fn quality(accept_encoding: &str, encoding: Encoding) -> f32 {
+    let mut best: Option<(u8, f32)> = None;
+
+    for coding in accept_encoding.split(',') {
+        let mut params = coding.split(';');
+        let coding = params.next().unwrap_or("").trim().to_ascii_lowercase();
+
+        let specificity = match &*coding {
+            c if Some(c) == encoding.content_encoding() => 1,
+            "x-gzip" if encoding == Encoding::Gzip => 1,
+            "*" => 0,
+            _ => continue,
+        };
+
+        let q = params
+            .filter_map(|param| {
+                let mut parts = param.splitn(2, '=');
+                match (parts.next().map(str::trim), parts.next()) {
+                    (Some("q"), Some(value)) => value.trim().parse::<f32>().ok(),
+                    _ => None,
+                }
+            })
+            .next()
+            .unwrap_or(1.0);
+
+        if best.map_or(true, |(s, _)| specificity > s) {
+            best = Some((specificity, q));
+        }
+    }
+
+    best.map_or(0.0, |(_, q)| q)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use http::handler::HandlerResponse;
+    use super::{Compression, Encoding};
+
+    #[test]
+    fn negotiates_encoding() {
+        assert_eq!(Encoding::negotiate(None), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(Some("")), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(Some("gzip, deflate, br")), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate(Some("deflate, gzip;q=0.5")), Encoding::Deflate);
+        assert_eq!(Encoding::negotiate(Some("X-GZIP")), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate(Some("*")), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate(Some("*, gzip;q=0")), Encoding::Deflate);
+        assert_eq!(Encoding::negotiate(Some("br, identity")), Encoding::Identity);
+    }
+
+    #[test]
+    fn compresses_large_responses() {
+        let body = format!(r#"{{"data":{{"items":[{}]}}}}"#, vec!["1"; 1000].join(","));
+        let compression = Compression::new();
+
+        let small = compression.compress(HandlerResponse::new(200, "application/json", "{}".to_owned()), Some("gzip"));
+        assert_eq!(small.encoding(), Encoding::Identity);
+        assert_eq!(small.headers(), &[("Content-Type", "application/json"), ("Vary", "Accept-Encoding")]);
+        assert_eq!(small.body(), b"{}");
+
+        let large = compression.compress(HandlerResponse::new(200, "application/json", body.clone()), Some("gzip"));
+        assert_eq!(large.encoding(), Encoding::Gzip);
+        assert_eq!(large.headers()[2], ("Content-Encoding", "gzip"));
+        assert!(large.body().len() < body.len() / 10);
+
+        let mut decoded = String::new();
+        GzDecoder::new(large.body()).read_to_string(&mut decoded).unwrap();
+        assert_eq!(decoded, body);
+    }
+}
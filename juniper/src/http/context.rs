@@ -0,0 +1,148 @@
+//! Building a context for every request
+//!
+//! Contexts often depend on the request: the user is taken from an
+//! authorization header, or a database connection is checked out for the
+//! duration of the request. A `ContextFactory` builds the context from the
+//! `RequestMetadata` the integrations collect, and can refuse to, in which
+//! case the request is answered with the `ContextError` instead of being
+//! executed:
+//!
+//! ```rust
+//! use juniper::http::context::{ContextError, ContextFactory, RequestMetadata};
+//!
+//! struct User {
+//!     name: String,
+//! }
+//!
+//! let factory = |request: &RequestMetadata| {
+//!     match request.header("authorization") {
+//!         Some("Bearer secret") => Ok(User { name: "admin".to_owned() }),
+//!         _ => Err(ContextError::unauthorized("Missing or invalid token")),
+//!     }
+//! };
+//!
+//! let request = RequestMetadata::new("POST", None)
+//!     .with_header("Authorization", "Bearer secret");
+//! assert_eq!(factory.create(&request).ok().unwrap().name, "admin");
+//!
+//! let err = factory.create(&RequestMetadata::new("POST", None)).err().unwrap();
+//! assert_eq!(err.status(), 401);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+/// What a `ContextFactory` gets to know about a request
+#[derive(Clone, Debug)]
+pub struct RequestMetadata {
+    method: String,
+    query_string: Option<String>,
+    headers: Vec<(String, String)>,
+}
+
+/// Builds the context of each request
+///
+/// This is implemented for closures taking the `RequestMetadata`.
+pub trait ContextFactory<CtxT>: Send + Sync {
+    /// Build the context for a request, or refuse to execute it
+    fn create(&self, request: &RequestMetadata) -> Result<CtxT, ContextError>;
+}
+
+/// The reason a `ContextFactory` refused to build a context
+///
+/// The request is answered with a GraphQL response containing only the error
+/// message, with the given HTTP status.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContextError {
+    status: u16,
+    message: String,
+}
+
+impl RequestMetadata {
+    /// Describe a request with the given method and URL query string
+    pub fn new(method: &str, query_string: Option<&str>) -> RequestMetadata {
+        RequestMetadata {
+            method: method.to_owned(),
+            query_string: query_string.map(str::to_owned),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Add a header
+    pub fn with_header(mut self, name: &str, value: &str) -> RequestMetadata {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    /// The method of the request, e.g. `"POST"`
+    pub fn method(&self) -> &str {
+        &self.method
+    }
+
+    /// The URL query string of the request, the part after the `?`
+    pub fn query_string(&self) -> Option<&str> {
+        self.query_string.as_ref().map(String::as_str)
+    }
+
+    /// Look up the first value of a header, ignoring the case of its name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|&&(ref key, _)| key.eq_ignore_ascii_case(name))
+            .map(|&(_, ref value)| value.as_str())
+    }
+
+    /// All headers, in the order they were added
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+}
+
+impl<F, CtxT> ContextFactory<CtxT> for F
+    where F: Fn(&RequestMetadata) -> Result<CtxT, ContextError> + Send + Sync
+{
+    fn create(&self, request: &RequestMetadata) -> Result<CtxT, ContextError> {
+        self(request)
+    }
+}
+
+impl ContextError {
+    /// Refuse a request with the given HTTP status
+    pub fn new(status: u16, message: &str) -> ContextError {
+        ContextError {
+            status: status,
+            message: message.to_owned(),
+        }
+    }
+
+    /// Refuse a request that isn't authenticated, with a 401 status
+    pub fn unauthorized(message: &str) -> ContextError {
+        ContextError::new(401, message)
+    }
+
+    /// Refuse a request that isn't allowed, with a 403 status
+    pub fn forbidden(message: &str) -> ContextError {
+        ContextError::new(403, message)
+    }
+
+    /// The HTTP status of the response
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The message of the error in the response
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for ContextError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
@@ -0,0 +1,217 @@
+//! Cross-origin resource sharing for GraphQL endpoints
+//!
+//! Browsers only let pages talk to a GraphQL endpoint on another origin if its
+//! responses carry the right `Access-Control-*` headers, and ask for
+//! permission with a preflight `OPTIONS` request before sending POST requests
+//! with a JSON body. `Cors` describes what is allowed, and builds the headers
+//! of both kinds of responses for the integrations:
+//!
+//! ```rust
+//! use juniper::http::cors::Cors;
+//!
+//! let cors = Cors::new()
+//!     .allow_origin("https://example.com")
+//!     .allow_header("Authorization");
+//!
+//! let preflight = cors.preflight(
+//!     Some("https://example.com"),
+//!     Some("POST"),
+//!     Some("content-type, authorization"));
+//! assert_eq!(preflight.status(), 204);
+//!
+//! let refused = cors.preflight(Some("https://evil.example"), Some("POST"), None);
+//! assert_eq!(refused.status(), 403);
+//! ```
+
+use http::over_http::{self, ALLOWED_METHODS};
+
+/// What cross-origin requests are allowed
+#[derive(Clone, Debug)]
+pub struct Cors {
+    origins: Option<Vec<String>>,
+    headers: Vec<String>,
+    max_age: Option<u32>,
+    credentials: bool,
+}
+
+/// The response to a preflight request
+#[derive(Clone, Debug)]
+pub struct PreflightResponse {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+}
+
+impl Cors {
+    /// Allow GET and POST requests with a `Content-Type` header from any origin
+    pub fn new() -> Cors {
+        Cors {
+            origins: None,
+            headers: vec!["Content-Type".to_owned()],
+            max_age: None,
+            credentials: false,
+        }
+    }
+
+    /// Only allow requests from the given origins, e.g.
+    /// `"https://example.com"`
+    ///
+    /// Call this once for every allowed origin.
+    pub fn allow_origin(mut self, origin: &str) -> Cors {
+        self.origins.get_or_insert_with(Vec::new).push(origin.to_owned());
+        self
+    }
+
+    /// Allow requests to carry the given header, e.g. `"Authorization"`
+    pub fn allow_header(mut self, header: &str) -> Cors {
+        self.headers.push(header.to_owned());
+        self
+    }
+
+    /// Let browsers cache the answer to a preflight request for the given
+    /// number of seconds
+    pub fn max_age(mut self, seconds: u32) -> Cors {
+        self.max_age = Some(seconds);
+        self
+    }
+
+    /// Allow requests with cookies or HTTP authentication
+    ///
+    /// The origin of the request is always sent back instead of `*` then, as
+    /// browsers require.
+    pub fn allow_credentials(mut self) -> Cors {
+        self.credentials = true;
+        self
+    }
+
+    /// Are requests from the given origin allowed?
+    pub fn is_allowed_origin(&self, origin: &str) -> bool {
+        match self.origins {
+            Some(ref origins) => origins.iter().any(|allowed| allowed == origin),
+            None => true,
+        }
+    }
+
+    /// The headers to add to the response to a request from the given origin
+    ///
+    /// Requests without an `Origin` header, or from an origin that isn't
+    /// allowed, get none.
+    pub fn headers(&self, origin: Option<&str>) -> Vec<(&'static str, String)> {
+        let origin = match origin {
+            Some(origin) if self.is_allowed_origin(origin) => origin,
+            _ => return Vec::new(),
+        };
+
+        let mut headers = Vec::new();
+        if self.origins.is_none() && !self.credentials {
+            headers.push(("Access-Control-Allow-Origin", "*".to_owned()));
+        } else {
+            headers.push(("Access-Control-Allow-Origin", origin.to_owned()));
+            headers.push(("Vary", "Origin".to_owned()));
+        }
+        if self.credentials {
+            headers.push(("Access-Control-Allow-Credentials", "true".to_owned()));
+        }
+
+        headers
+    }
+
+    /// Answer a preflight request
+    ///
+    /// The arguments are the values of the request's `Origin`,
+    /// `Access-Control-Request-Method` and `Access-Control-Request-Headers`
+    /// headers. Requests that aren't allowed get a 403 status without any
+    /// CORS headers, so the browser won't send the actual request.
+    pub fn preflight(
+        &self,
+        origin: Option<&str>,
+        request_method: Option<&str>,
+        request_headers: Option<&str>,
+    )
+        -> PreflightResponse
+    {
+        let method_allowed = request_method.map_or(false, over_http::is_allowed_method);
+        let headers_allowed = request_headers.map_or(true, |headers| headers
+            .split(',')
+            .map(str::trim)
+            .filter(|header| !header.is_empty())
+            .all(|header| self.headers.iter().any(|allowed| allowed.eq_ignore_ascii_case(header))));
+
+        let mut headers = self.headers(origin);
+        if headers.is_empty() || !method_allowed || !headers_allowed {
+            return PreflightResponse {
+                status: 403,
+                headers: Vec::new(),
+            };
+        }
+
+        headers.push(("Access-Control-Allow-Methods", ALLOWED_METHODS.to_owned()));
+        headers.push(("Access-Control-Allow-Headers", self.headers.join(", ")));
+        if let Some(max_age) = self.max_age {
+            headers.push(("Access-Control-Max-Age", max_age.to_string()));
+        }
+
+        PreflightResponse {
+            status: 204,
+            headers: headers,
+        }
+    }
+}
+
+impl Default for Cors {
+    fn default() -> Cors {
+        Cors::new()
+    }
+}
+
+impl PreflightResponse {
+    /// The HTTP status, 204 if the request is allowed and 403 otherwise
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The headers of the response
+    pub fn headers(&self) -> &[(&'static str, String)] {
+        &self.headers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cors;
+
+    fn header<'a>(headers: &'a [(&'static str, String)], name: &str) -> Option<&'a str> {
+        headers.iter().find(|&&(key, _)| key == name).map(|&(_, ref value)| value.as_str())
+    }
+
+    #[test]
+    fn adds_headers_to_allowed_origins() {
+        let any = Cors::new();
+        assert_eq!(any.headers(Some("https://a.example")), vec![("Access-Control-Allow-Origin", "*".to_owned())]);
+        assert!(any.headers(None).is_empty());
+
+        let listed = Cors::new().allow_origin("https://a.example").allow_credentials();
+        let headers = listed.headers(Some("https://a.example"));
+        assert_eq!(header(&headers, "Access-Control-Allow-Origin"), Some("https://a.example"));
+        assert_eq!(header(&headers, "Vary"), Some("Origin"));
+        assert_eq!(header(&headers, "Access-Control-Allow-Credentials"), Some("true"));
+        assert!(listed.headers(Some("https://b.example")).is_empty());
+    }
+
+    #[test]
+    fn answers_preflight_requests() {
+        let cors = Cors::new().allow_header("Authorization").max_age(600);
+
+        let allowed = cors.preflight(Some("https://a.example"), Some("POST"), Some("Content-Type, authorization"));
+        assert_eq!(allowed.status(), 204);
+        assert_eq!(header(allowed.headers(), "Access-Control-Allow-Methods"), Some("GET, POST"));
+        assert_eq!(header(allowed.headers(), "Access-Control-Allow-Headers"), Some("Content-Type, Authorization"));
+        assert_eq!(header(allowed.headers(), "Access-Control-Max-Age"), Some("600"));
+
+        let put = cors.preflight(Some("https://a.example"), Some("PUT"), None);
+        assert_eq!(put.status(), 403);
+        assert!(put.headers().is_empty());
+
+        assert_eq!(cors.preflight(Some("https://a.example"), Some("POST"), Some("X-Custom")).status(), 403);
+        assert_eq!(cors.preflight(None, Some("POST"), None).status(), 403);
+    }
+}
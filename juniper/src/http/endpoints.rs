@@ -0,0 +1,80 @@
+//! Operational endpoints to mount next to the GraphQL endpoint
+//!
+//! These produce the same responses regardless of the web framework: a
+//! liveness check for load balancers and orchestrators, and the schema in the
+//! GraphQL schema definition language for tooling.
+
+use ::{GraphQLType, RootNode};
+
+/// A response that integrations send as it is
+pub struct EndpointResponse {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl EndpointResponse {
+    /// The HTTP status
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The value of the `Content-Type` header
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+
+    /// The body of the response
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Take the body of the response
+    pub fn into_body(self) -> String {
+        self.body
+    }
+}
+
+/// The response to a liveness check, `{"status":"ok"}`
+pub fn health_response() -> EndpointResponse {
+    EndpointResponse {
+        status: 200,
+        content_type: "application/json",
+        body: r#"{"status":"ok"}"#.to_owned(),
+    }
+}
+
+/// The response to a request for the schema, in the GraphQL schema definition
+/// language
+pub fn schema_response<QueryT, MutationT>(root_node: &RootNode<QueryT, MutationT>) -> EndpointResponse
+    where QueryT: GraphQLType,
+          MutationT: GraphQLType,
+{
+    EndpointResponse {
+        status: 200,
+        content_type: "text/plain; charset=utf-8",
+        body: root_node.as_schema_language(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::{health_response, schema_response};
+
+    #[test]
+    fn endpoint_responses() {
+        let health = health_response();
+        assert_eq!(health.status(), 200);
+        assert_eq!(health.content_type(), "application/json");
+        assert_eq!(health.body(), r#"{"status":"ok"}"#);
+
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let response = schema_response(&schema);
+        assert_eq!(response.content_type(), "text/plain; charset=utf-8");
+        assert!(response.into_body().starts_with("schema {\n  query: Query\n}\n"));
+    }
+}
@@ -0,0 +1,633 @@
+//! Framework-agnostic handling of GraphQL requests
+//!
+//! `GraphQLHandler` holds everything integrations have in common: which
+//! methods are allowed, content negotiation, the size limits, parsing GET,
+//! JSON, `application/graphql` and multipart requests, and choosing the status
+//! of the response. Requests that can't be executed are answered with a
+//! GraphQL response containing only the error, see `HandlerResponse::error`.
+//! An integration only has to describe the incoming request with
+//! `RequestParts` and send the `HandlerResponse` it gets back:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! use juniper::{RootNode, EmptyMutation};
+//! use juniper::http::handler::{GraphQLHandler, RequestParts};
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field hello() -> &str { "world" }
+//! });
+//!
+//! # fn main() {
+//! let root_node = RootNode::new(Query, EmptyMutation::<()>::new());
+//! let handler = GraphQLHandler::new();
+//!
+//! let request = RequestParts::new("POST", None)
+//!     .with_content_type(Some("application/graphql"));
+//! let body = &b"{ hello }"[..];
+//!
+//! let response = match handler.parse(&request, body) {
+//!     Ok(parsed) => handler.execute(&parsed, &root_node, &()),
+//!     Err(response) => response,
+//! };
+//!
+//! assert_eq!(response.status(), 200);
+//! assert_eq!(response.body(), "{\n  \"data\": {\n    \"hello\": \"world\"\n  }\n}");
+//! # }
+//! ```
+
+use std::fmt;
+use std::io::Read;
+use std::sync::Arc;
+
+use serde_json;
+
+use ::{GraphQLErrorCode, GraphQLType, RootNode};
+use http::{GraphQLRequest, GraphQLBatchRequest, GraphQLBatchResponse, JsonFormat};
+use http::context::{ContextError, ContextFactory, RequestMetadata};
+use http::limits::{RequestLimits, LimitError, ReadBodyError};
+use http::multipart::{self, UploadedFiles};
+use http::over_http::{self, MediaType};
+use http::recording::Recorder;
+
+/// The parts of an HTTP request that `GraphQLHandler` looks at, besides the
+/// body
+#[derive(Clone, Copy, Debug)]
+pub struct RequestParts<'a> {
+    method: &'a str,
+    query_string: Option<&'a str>,
+    content_type: Option<&'a str>,
+    accept: Option<&'a str>,
+}
+
+/// Configurable handling of GraphQL requests, shared by the integrations
+#[derive(Clone, Debug, Default)]
+pub struct GraphQLHandler {
+    json_format: JsonFormat,
+    strict_parsing: bool,
+    over_http: bool,
+    limits: RequestLimits,
+    recorder: Option<Arc<Recorder>>,
+}
+
+/// A request that was parsed successfully, ready to be executed
+pub struct ParsedRequest {
+    request: GraphQLBatchRequest,
+    uploads: UploadedFiles,
+    media_type: Option<MediaType>,
+}
+
+/// A response that integrations send as it is
+#[derive(Debug)]
+pub struct HandlerResponse {
+    status: u16,
+    headers: Vec<(&'static str, &'static str)>,
+    body: String,
+}
+
+impl<'a> RequestParts<'a> {
+    /// Describe a request with the given method and URL query string, the
+    /// part after the `?`
+    pub fn new(method: &'a str, query_string: Option<&'a str>) -> RequestParts<'a> {
+        RequestParts {
+            method: method,
+            query_string: query_string,
+            content_type: None,
+            accept: None,
+        }
+    }
+
+    /// Set the value of the `Content-Type` header
+    pub fn with_content_type(mut self, content_type: Option<&'a str>) -> RequestParts<'a> {
+        self.content_type = content_type;
+        self
+    }
+
+    /// Set the value of the `Accept` header
+    pub fn with_accept(mut self, accept: Option<&'a str>) -> RequestParts<'a> {
+        self.accept = accept;
+        self
+    }
+
+    fn media_type(&self) -> Option<&'a str> {
+        self.content_type.map(|content_type| content_type.split(';').next().unwrap_or("").trim())
+    }
+}
+
+impl GraphQLHandler {
+    /// Construct a handler with the default settings
+    pub fn new() -> GraphQLHandler {
+        GraphQLHandler::default()
+    }
+
+    /// Set the layout of the JSON responses, see `JsonFormat`
+    pub fn with_json_format(mut self, json_format: JsonFormat) -> GraphQLHandler {
+        self.json_format = json_format;
+        self
+    }
+
+    /// Reject JSON request bodies with unknown keys or values of the wrong
+    /// type, see `GraphQLBatchRequest::from_json_strict`
+    pub fn with_strict_parsing(mut self) -> GraphQLHandler {
+        self.strict_parsing = true;
+        self
+    }
+
+    /// Follow the GraphQL over HTTP specification, see `over_http`
+    pub fn with_graphql_over_http(mut self) -> GraphQLHandler {
+        self.over_http = true;
+        self
+    }
+
+    /// Limit the size of incoming requests, see `limits`
+    pub fn with_limits(mut self, limits: RequestLimits) -> GraphQLHandler {
+        self.limits = limits;
+        self
+    }
+
+    /// Record every request the handler executes with its response, see
+    /// `recording`
+    pub fn with_recorder(mut self, recorder: Arc<Recorder>) -> GraphQLHandler {
+        self.recorder = Some(recorder);
+        self
+    }
+
+    /// The limits on the size of incoming requests
+    pub fn limits(&self) -> &RequestLimits {
+        &self.limits
+    }
+
+    /// Parse a request, reading the body of POST requests
+    ///
+    /// Requests that can't be executed are answered with the returned error
+    /// response: 400 for malformed requests, 405 for other methods than GET
+    /// and POST, 406 if the response media type can't be negotiated, and the
+    /// status of the `LimitError` for requests exceeding the limits.
+    pub fn parse<R>(&self, parts: &RequestParts, body: R) -> Result<ParsedRequest, HandlerResponse>
+        where R: Read
+    {
+        let method = parts.method.to_ascii_uppercase();
+
+        if !over_http::is_allowed_method(&method) {
+            let mut response = HandlerResponse::error(405, "Only GET and POST requests are allowed");
+            response.headers.push(("Allow", over_http::ALLOWED_METHODS));
+            return Err(response);
+        }
+
+        let media_type = if self.over_http {
+            match MediaType::negotiate(parts.accept) {
+                Some(media_type) => Some(media_type),
+                None => return Err(HandlerResponse::error(
+                    406, "The response can only be sent as application/graphql-response+json or application/json")),
+            }
+        } else {
+            None
+        };
+
+        let (request, uploads) = if method == "GET" {
+            (try!(self.parse_get(parts)), UploadedFiles::new())
+        } else {
+            try!(self.parse_post(parts, body))
+        };
+
+        try!(self.limits.check_variables(&request).map_err(HandlerResponse::from));
+
+        Ok(ParsedRequest {
+            request: request,
+            uploads: uploads,
+            media_type: media_type,
+        })
+    }
+
+    /// Serialize a response as the given media type
+    ///
+    /// Pass the `media_type` of the parsed request. Without a media type, the
+    /// response is `application/json` with the status from
+    /// `GraphQLBatchResponse::status_code`.
+    pub fn respond(&self, media_type: Option<MediaType>, response: &GraphQLBatchResponse) -> HandlerResponse {
+        let (status, content_type) = match media_type {
+            Some(media_type) => (media_type.status(response), media_type.as_str()),
+            None => (response.status_code(), "application/json"),
+        };
+
+        match response.to_json(self.json_format) {
+            Ok(body) => HandlerResponse::new(status, content_type, body),
+            Err(err) => HandlerResponse::serialization_error(&err),
+        }
+    }
+
+    /// Execute a parsed request and serialize the response
+    pub fn execute<CtxT, QueryT, MutationT>(
+        &self,
+        parsed: &ParsedRequest,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+    )
+        -> HandlerResponse
+        where QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+    {
+        let response = parsed.request.execute(root_node, context);
+
+        if let Some(ref recorder) = self.recorder {
+            recorder.record_batch(&parsed.request, &response);
+        }
+
+        self.respond(parsed.media_type, &response)
+    }
+
+    /// Build the context with a `ContextFactory` and execute a parsed request
+    ///
+    /// If the factory refuses to build a context, the request isn't executed
+    /// and the response contains the error of the factory.
+    pub fn execute_with_context_factory<F, CtxT, QueryT, MutationT>(
+        &self,
+        parsed: &ParsedRequest,
+        root_node: &RootNode<QueryT, MutationT>,
+        context_factory: &F,
+        metadata: &RequestMetadata,
+    )
+        -> HandlerResponse
+        where F: ContextFactory<CtxT> + ?Sized,
+              QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+    {
+        match context_factory.create(metadata) {
+            Ok(context) => self.execute(parsed, root_node, &context),
+            Err(err) => err.into(),
+        }
+    }
+
+    fn parse_get(&self, parts: &RequestParts) -> Result<GraphQLBatchRequest, HandlerResponse> {
+        let query_string = parts.query_string.unwrap_or("");
+        try!(self.limits.check_query_string(query_string).map_err(HandlerResponse::from));
+
+        GraphQLRequest::from_url_query(query_string)
+            .map(GraphQLBatchRequest::from)
+            .map_err(bad_request)
+    }
+
+    fn parse_post<R>(&self, parts: &RequestParts, body: R)
+        -> Result<(GraphQLBatchRequest, UploadedFiles), HandlerResponse>
+        where R: Read
+    {
+        let body = match self.limits.read_body(body) {
+            Ok(body) => body,
+            Err(ReadBodyError::Limit(err)) => return Err(err.into()),
+            Err(ReadBodyError::Io(err)) =>
+                return Err(HandlerResponse::error(500, &err.to_string())),
+        };
+
+        if let Some(content_type) = parts.content_type {
+            if multipart::is_multipart(content_type) {
+                return multipart::parse_request(content_type, &body).map_err(bad_request);
+            }
+        }
+
+        let body = try!(String::from_utf8(body).map_err(bad_request));
+
+        let request = if parts.media_type().map_or(false, |t| t.eq_ignore_ascii_case("application/graphql")) {
+            GraphQLRequest::from_graphql_body(body).into()
+        } else if self.strict_parsing {
+            try!(GraphQLBatchRequest::from_json_strict(&body).map_err(bad_request))
+        } else {
+            try!(serde_json::from_str::<GraphQLBatchRequest>(&body).map_err(bad_request))
+        };
+
+        Ok((request, UploadedFiles::new()))
+    }
+}
+
+impl ParsedRequest {
+    /// The parsed request
+    pub fn request(&self) -> &GraphQLBatchRequest {
+        &self.request
+    }
+
+    /// Identify the request in the errors of its execution, e.g. with the
+    /// value of an `X-Request-Id` header, see `GraphQLRequest::with_request_id`
+    pub fn with_request_id<T: Into<String>>(self, request_id: T) -> ParsedRequest {
+        ParsedRequest {
+            request: self.request.with_request_id(request_id),
+            uploads: self.uploads,
+            media_type: self.media_type,
+        }
+    }
+
+    /// Take the files uploaded along with a multipart request
+    ///
+    /// Requests that weren't multipart have no files.
+    pub fn take_uploads(&mut self) -> UploadedFiles {
+        ::std::mem::replace(&mut self.uploads, UploadedFiles::new())
+    }
+
+    /// The negotiated media type of the response, if the handler follows the
+    /// GraphQL over HTTP specification
+    pub fn media_type(&self) -> Option<MediaType> {
+        self.media_type
+    }
+}
+
+impl From<GraphQLBatchRequest> for ParsedRequest {
+    /// Wrap a request that was parsed elsewhere, e.g. by the framework's own
+    /// form handling
+    fn from(request: GraphQLBatchRequest) -> ParsedRequest {
+        ParsedRequest {
+            request: request,
+            uploads: UploadedFiles::new(),
+            media_type: None,
+        }
+    }
+}
+
+impl HandlerResponse {
+    /// Construct a response with the given status, `Content-Type` and body
+    pub fn new(status: u16, content_type: &'static str, body: String) -> HandlerResponse {
+        HandlerResponse {
+            status: status,
+            headers: vec![("Content-Type", content_type)],
+            body: body,
+        }
+    }
+
+    /// A GraphQL response containing only an error with the given message, like
+    /// the ones for requests exceeding the limits
+    ///
+    /// The code of the error is derived from the status: `Unauthenticated`
+    /// for 401, `Forbidden` for 403, `InternalServerError` for 5xx, and
+    /// `BadRequest` otherwise.
+    pub fn error(status: u16, message: &str) -> HandlerResponse {
+        let code = match status {
+            401 => GraphQLErrorCode::Unauthenticated,
+            403 => GraphQLErrorCode::Forbidden,
+            500..=599 => GraphQLErrorCode::InternalServerError,
+            _ => GraphQLErrorCode::BadRequest,
+        };
+
+        let mut extensions = serde_json::Map::new();
+        extensions.insert("code".to_owned(), serde_json::Value::String(code.as_str().to_owned()));
+
+        let mut error = serde_json::Map::new();
+        error.insert("message".to_owned(), serde_json::Value::String(message.to_owned()));
+        error.insert("extensions".to_owned(), serde_json::Value::Object(extensions));
+
+        let mut document = serde_json::Map::new();
+        document.insert("errors".to_owned(), serde_json::Value::Array(vec![serde_json::Value::Object(error)]));
+
+        HandlerResponse::new(status, "application/json", serde_json::Value::Object(document).to_string())
+    }
+
+    /// A page of HTML, such as GraphiQL or Playground
+    pub fn html(body: String) -> HandlerResponse {
+        HandlerResponse::new(200, "text/html; charset=utf-8", body)
+    }
+
+    /// The HTTP status
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The headers of the response, including `Content-Type`
+    pub fn headers(&self) -> &[(&'static str, &'static str)] {
+        &self.headers
+    }
+
+    /// The value of the `Content-Type` header
+    pub fn content_type(&self) -> &'static str {
+        self.headers[0].1
+    }
+
+    /// The body of the response
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    /// Take the body of the response
+    pub fn into_body(self) -> String {
+        self.body
+    }
+}
+
+impl HandlerResponse {
+    /// The response for a response that failed to serialize, e.g. because a
+    /// custom scalar can't be represented as JSON
+    ///
+    /// Request handlers shouldn't panic on such responses, and take the worker
+    /// serving them down with them.
+    pub fn serialization_error<E>(err: &E) -> HandlerResponse where E: fmt::Display {
+        HandlerResponse::error(500, &format!("Could not serialize the response: {}", err))
+    }
+}
+
+impl From<LimitError> for HandlerResponse {
+    fn from(err: LimitError) -> HandlerResponse {
+        match serde_json::to_string(&err) {
+            Ok(body) => HandlerResponse::new(err.status(), "application/json", body),
+            Err(_) => HandlerResponse::error(err.status(), &err.to_string()),
+        }
+    }
+}
+
+impl From<ContextError> for HandlerResponse {
+    fn from(err: ContextError) -> HandlerResponse {
+        HandlerResponse::error(err.status(), err.message())
+    }
+}
+
+fn bad_request<E>(err: E) -> HandlerResponse where E: fmt::Display {
+    HandlerResponse::error(400, &err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ::http::tests as http_tests;
+    use http::context::{ContextError, RequestMetadata};
+    use http::limits::RequestLimits;
+    use http::recording::Recorder;
+    use http::websocket::{Connection, MessageSink, Protocol};
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::{GraphQLHandler, RequestParts, HandlerResponse};
+
+    const MAX_BODY_BYTES: usize = 64 * 1024;
+
+    struct TestHandlerIntegration {
+        handler: GraphQLHandler,
+    }
+
+    struct TestSocket(Vec<String>);
+
+    impl MessageSink for TestSocket {
+        fn send(&mut self, message: String) {
+            self.0.push(message);
+        }
+
+        fn close(&mut self, code: u16, reason: &str) {
+            panic!("Socket closed with {}: {}", code, reason);
+        }
+    }
+
+    impl TestHandlerIntegration {
+        fn request(&self, parts: RequestParts, body: &str) -> HandlerResponse {
+            let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+            match self.handler.parse(&parts, body.as_bytes()) {
+                Ok(parsed) => self.handler.execute(&parsed, &schema, &Database::new()),
+                Err(response) => response,
+            }
+        }
+    }
+
+    impl http_tests::HTTPIntegration for TestHandlerIntegration {
+        fn get(&self, url: &str) -> http_tests::TestResponse {
+            let query_string = url.splitn(2, '?').nth(1);
+            make_test_response(self.request(RequestParts::new("GET", query_string), ""))
+        }
+
+        fn post(&self, _: &str, body: &str) -> http_tests::TestResponse {
+            let parts = RequestParts::new("POST", None).with_content_type(Some("application/json"));
+            make_test_response(self.request(parts, body))
+        }
+
+        fn post_graphql(&self, _: &str, body: &str) -> http_tests::TestResponse {
+            let parts = RequestParts::new("POST", None).with_content_type(Some("application/graphql"));
+            make_test_response(self.request(parts, body))
+        }
+
+        fn post_with_content_type(&self, _: &str, content_type: &str, body: &str) -> http_tests::TestResponse {
+            let parts = RequestParts::new("POST", None).with_content_type(Some(content_type));
+            make_test_response(self.request(parts, body))
+        }
+
+        fn features(&self) -> http_tests::IntegrationFeatures {
+            http_tests::IntegrationFeatures {
+                uploads: true,
+                max_body_bytes: Some(MAX_BODY_BYTES),
+                websockets: true,
+            }
+        }
+
+        fn websocket(&self, messages: &[&str]) -> Vec<String> {
+            let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+            let mut socket = TestSocket(Vec::new());
+
+            Connection::new(Protocol::GraphQLTransportWs)
+                .serve(messages, &schema, &Database::new(), &mut socket);
+
+            socket.0
+        }
+    }
+
+    fn make_test_response(response: HandlerResponse) -> http_tests::TestResponse {
+        http_tests::TestResponse {
+            status_code: response.status() as i32,
+            content_type: response.content_type().to_owned(),
+            body: Some(response.into_body()),
+        }
+    }
+
+    #[test]
+    fn test_handler_integration() {
+        let integration = TestHandlerIntegration {
+            handler: GraphQLHandler::new()
+                .with_limits(RequestLimits::new().with_max_body_bytes(MAX_BODY_BYTES)),
+        };
+
+        http_tests::run_http_test_suite(&integration);
+    }
+
+    #[test]
+    fn rejects_requests_before_executing_them() {
+        let integration = TestHandlerIntegration {
+            handler: GraphQLHandler::new()
+                .with_graphql_over_http()
+                .with_limits(RequestLimits::new().with_max_body_bytes(8)),
+        };
+
+        let put = integration.request(RequestParts::new("PUT", None), "");
+        assert_eq!(put.status(), 405);
+        assert_eq!(put.headers()[1], ("Allow", "GET, POST"));
+
+        let html = integration.request(RequestParts::new("GET", Some("query={a}")).with_accept(Some("text/html")), "");
+        assert_eq!(html.status(), 406);
+
+        let large = integration.request(RequestParts::new("POST", None), r#"{"query": "{hero{name}}"}"#);
+        assert_eq!(large.status(), 413);
+        assert_eq!(large.content_type(), "application/json");
+
+        let invalid = integration.request(RequestParts::new("GET", Some("operationName=A")), "");
+        assert_eq!(invalid.status(), 400);
+        assert_eq!(invalid.content_type(), "application/json");
+        assert_eq!(invalid.body(), r#"{"errors":[{"extensions":{"code":"BAD_REQUEST"},"message":"No query provided"}]}"#);
+    }
+
+    #[test]
+    fn negotiates_graphql_response_json() {
+        let integration = TestHandlerIntegration {
+            handler: GraphQLHandler::new().with_graphql_over_http(),
+        };
+
+        let response = integration.request(RequestParts::new("GET", Some("query={nope}")), "");
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.content_type(), "application/json");
+
+        let parts = RequestParts::new("GET", Some("query={nope}"))
+            .with_accept(Some("application/graphql-response+json"));
+        let response = integration.request(parts, "");
+        assert_eq!(response.status(), 400);
+        assert_eq!(response.content_type(), "application/graphql-response+json");
+    }
+
+    #[test]
+    fn records_executed_requests() {
+        let recorder = Arc::new(Recorder::new());
+        let integration = TestHandlerIntegration {
+            handler: GraphQLHandler::new().with_recorder(recorder.clone()),
+        };
+
+        integration.request(
+            RequestParts::new("POST", None).with_content_type(Some("application/json")),
+            r#"[{"query": "{ hero { name } }"}, {"query": "query Q { nope }", "operationName": "Q"}]"#);
+        integration.request(RequestParts::new("PUT", None), "");
+
+        let recordings = recorder.recordings();
+        assert_eq!(recordings.len(), 2);
+        assert_eq!(recordings[0].query, "{ hero { name } }");
+        assert_eq!(recordings[1].operation_name, Some("Q".to_owned()));
+        assert!(recordings[1].response.get("errors").is_some());
+    }
+
+    #[test]
+    fn refuses_requests_the_context_factory_rejects() {
+        let handler = GraphQLHandler::new();
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let factory = |request: &RequestMetadata| match request.header("authorization") {
+            Some(_) => Ok(Database::new()),
+            None => Err(ContextError::unauthorized("Not logged in")),
+        };
+
+        let parsed = handler.parse(&RequestParts::new("GET", Some("query={hero{name}}")), &b""[..]).ok().unwrap();
+
+        let refused = handler.execute_with_context_factory(&parsed, &schema, &factory, &RequestMetadata::new("GET", None));
+        assert_eq!(refused.status(), 401);
+        assert_eq!(refused.body(), r#"{"errors":[{"extensions":{"code":"UNAUTHENTICATED"},"message":"Not logged in"}]}"#);
+
+        let metadata = RequestMetadata::new("GET", None).with_header("Authorization", "Bearer token");
+        let executed = handler.execute_with_context_factory(&parsed, &schema, &factory, &metadata);
+        assert_eq!(executed.status(), 200);
+    }
+
+    #[test]
+    fn reports_serialization_failures_as_server_errors() {
+        let response = HandlerResponse::serialization_error(&"key must be a string");
+        assert_eq!(response.status(), 500);
+        assert_eq!(
+            response.body(),
+            r#"{"errors":[{"extensions":{"code":"INTERNAL_SERVER_ERROR"},"message":"Could not serialize the response: key must be a string"}]}"#);
+    }
+}
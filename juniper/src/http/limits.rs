@@ -0,0 +1,315 @@
+//! Limits on the size of incoming requests
+//!
+//! The limits are checked before the request is parsed any further, so
+//! oversized requests are rejected cheaply regardless of what the web
+//! framework allows by default:
+//!
+//! ```rust
+//! use juniper::http::limits::RequestLimits;
+//!
+//! let limits = RequestLimits::new()
+//!     .with_max_body_bytes(64 * 1024)
+//!     .with_max_variables(100);
+//!
+//! let err = limits.check_body_length(100 * 1024).unwrap_err();
+//! assert_eq!(err.status(), 413);
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Read};
+
+use serde::ser;
+use serde::ser::SerializeMap;
+
+use ::GraphQLErrorCode;
+use http::{GraphQLRequest, GraphQLBatchRequest};
+
+/// Configurable limits on the size of requests
+///
+/// All limits are disabled by default.
+#[derive(Clone, Debug, Default)]
+pub struct RequestLimits {
+    max_body_bytes: Option<usize>,
+    max_query_string_length: Option<usize>,
+    max_variables: Option<usize>,
+}
+
+impl RequestLimits {
+    /// Construct a set of limits where nothing is limited
+    pub fn new() -> RequestLimits {
+        RequestLimits::default()
+    }
+
+    /// Limit the size of POST bodies in bytes
+    pub fn with_max_body_bytes(mut self, max: usize) -> RequestLimits {
+        self.max_body_bytes = Some(max);
+        self
+    }
+
+    /// Limit the length of the URL query string of GET requests in bytes
+    pub fn with_max_query_string_length(mut self, max: usize) -> RequestLimits {
+        self.max_query_string_length = Some(max);
+        self
+    }
+
+    /// Limit the number of variables of each request
+    pub fn with_max_variables(mut self, max: usize) -> RequestLimits {
+        self.max_variables = Some(max);
+        self
+    }
+
+    /// Check the length of a body, e.g. from its `Content-Length` header
+    pub fn check_body_length(&self, length: usize) -> Result<(), LimitError> {
+        match self.max_body_bytes {
+            Some(max) if length > max => Err(LimitError::BodyTooLarge(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Read a body, failing as soon as it exceeds the limit
+    ///
+    /// Unlike `check_body_length`, this doesn't trust the client to announce
+    /// the size of the body.
+    pub fn read_body<R: Read>(&self, body: R) -> Result<Vec<u8>, ReadBodyError> {
+        let mut content = Vec::new();
+
+        match self.max_body_bytes {
+            Some(max) => {
+                try!(body.take((max as u64).saturating_add(1)).read_to_end(&mut content).map_err(ReadBodyError::Io));
+                try!(self.check_body_length(content.len()).map_err(ReadBodyError::Limit));
+            },
+            None => {
+                let mut body = body;
+                try!(body.read_to_end(&mut content).map_err(ReadBodyError::Io));
+            },
+        }
+
+        Ok(content)
+    }
+
+    /// Check the URL query string of a GET request
+    pub fn check_query_string(&self, query_string: &str) -> Result<(), LimitError> {
+        match self.max_query_string_length {
+            Some(max) if query_string.len() > max => Err(LimitError::QueryStringTooLong(max)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check the number of variables of every request in a batch
+    pub fn check_variables(&self, request: &GraphQLBatchRequest) -> Result<(), LimitError> {
+        let max = match self.max_variables {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+
+        let too_many = |request: &GraphQLRequest| request.variables.as_ref()
+            .and_then(|variables| variables.to_object_value())
+            .map_or(false, |variables| variables.len() > max);
+
+        let exceeded = match *request {
+            GraphQLBatchRequest::Single(ref request) => too_many(request),
+            GraphQLBatchRequest::Batch(ref requests) => requests.iter().any(too_many),
+        };
+
+        if exceeded {
+            Err(LimitError::TooManyVariables(max))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A request exceeding one of the `RequestLimits`
+///
+/// This serializes to a GraphQL response containing only the error, to be
+/// sent with the status from `status`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitError {
+    /// The body is larger than the given number of bytes
+    BodyTooLarge(usize),
+    /// The URL query string is longer than the given number of bytes
+    QueryStringTooLong(usize),
+    /// A request has more than the given number of variables
+    TooManyVariables(usize),
+}
+
+impl LimitError {
+    /// The HTTP status of the response: 413 for bodies that are too large,
+    /// and 400 otherwise
+    pub fn status(&self) -> u16 {
+        match *self {
+            LimitError::BodyTooLarge(_) => 413,
+            LimitError::QueryStringTooLong(_) | LimitError::TooManyVariables(_) => 400,
+        }
+    }
+
+    /// The code of the error in the response
+    pub fn code(&self) -> GraphQLErrorCode {
+        match *self {
+            LimitError::BodyTooLarge(_) => GraphQLErrorCode::BodyTooLarge,
+            LimitError::QueryStringTooLong(_) => GraphQLErrorCode::QueryStringTooLong,
+            LimitError::TooManyVariables(_) => GraphQLErrorCode::TooManyVariables,
+        }
+    }
+
+    fn limit(&self) -> usize {
+        match *self {
+            LimitError::BodyTooLarge(max) |
+            LimitError::QueryStringTooLong(max) |
+            LimitError::TooManyVariables(max) => max,
+        }
+    }
+}
+
+impl fmt::Display for LimitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            LimitError::BodyTooLarge(max) =>
+                write!(f, "Request body must not be larger than {} bytes", max),
+            LimitError::QueryStringTooLong(max) =>
+                write!(f, "Query string must not be longer than {} bytes", max),
+            LimitError::TooManyVariables(max) =>
+                write!(f, "Request must not have more than {} variables", max),
+        }
+    }
+}
+
+impl Error for LimitError {
+    fn description(&self) -> &str {
+        match *self {
+            LimitError::BodyTooLarge(_) => "Request body too large",
+            LimitError::QueryStringTooLong(_) => "Query string too long",
+            LimitError::TooManyVariables(_) => "Too many variables",
+        }
+    }
+}
+
+struct LimitErrorEntry<'a>(&'a LimitError);
+
+struct LimitErrorExtensions<'a>(&'a LimitError);
+
+impl ser::Serialize for LimitError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let mut map = try!(serializer.serialize_map(Some(1)));
+        try!(map.serialize_key("errors"));
+        try!(map.serialize_value(&[LimitErrorEntry(self)]));
+        map.end()
+    }
+}
+
+impl<'a> ser::Serialize for LimitErrorEntry<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let mut map = try!(serializer.serialize_map(Some(2)));
+        try!(map.serialize_key("message"));
+        try!(map.serialize_value(&self.0.to_string()));
+        try!(map.serialize_key("extensions"));
+        try!(map.serialize_value(&LimitErrorExtensions(self.0)));
+        map.end()
+    }
+}
+
+impl<'a> ser::Serialize for LimitErrorExtensions<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let mut map = try!(serializer.serialize_map(Some(2)));
+        try!(map.serialize_key("code"));
+        try!(map.serialize_value(&self.0.code()));
+        try!(map.serialize_key("limit"));
+        try!(map.serialize_value(&self.0.limit()));
+        map.end()
+    }
+}
+
+/// Error reading a body with `RequestLimits::read_body`
+#[derive(Debug)]
+pub enum ReadBodyError {
+    /// The body exceeds the limit
+    Limit(LimitError),
+    /// Reading the body failed
+    Io(io::Error),
+}
+
+impl fmt::Display for ReadBodyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadBodyError::Limit(ref err) => fmt::Display::fmt(err, f),
+            ReadBodyError::Io(ref err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for ReadBodyError {
+    fn description(&self) -> &str {
+        match *self {
+            ReadBodyError::Limit(_) => "Request body exceeds a limit",
+            ReadBodyError::Io(_) => "Failed to read the request body",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ReadBodyError::Limit(ref err) => Some(err),
+            ReadBodyError::Io(ref err) => Some(err),
+        }
+    }
+}
+
+#[cfg(all(test, feature="serde_json"))]
+mod tests {
+    use serde_json;
+
+    use http::{GraphQLRequest, GraphQLBatchRequest};
+    use super::{RequestLimits, LimitError, ReadBodyError};
+
+    #[test]
+    fn limits_are_disabled_by_default() {
+        let limits = RequestLimits::new();
+
+        assert_eq!(limits.read_body(&b"{}"[..]).unwrap(), b"{}");
+        assert_eq!(limits.check_query_string(&"a".repeat(10000)), Ok(()));
+    }
+
+    #[test]
+    fn limits_body_size() {
+        let limits = RequestLimits::new().with_max_body_bytes(4);
+
+        assert_eq!(limits.read_body(&b"1234"[..]).unwrap(), b"1234");
+        match limits.read_body(&b"12345"[..]) {
+            Err(ReadBodyError::Limit(LimitError::BodyTooLarge(4))) => (),
+            _ => panic!("Expected the body to be too large"),
+        }
+
+        let unlimited = RequestLimits::new().with_max_body_bytes(usize::max_value());
+        assert_eq!(unlimited.read_body(&b"12345"[..]).unwrap(), b"12345");
+    }
+
+    #[test]
+    fn limits_variables() {
+        let limits = RequestLimits::new().with_max_variables(1);
+
+        let request = |variables: &str| GraphQLRequest::new(
+            "{a}".to_owned(), None, Some(serde_json::from_str(variables).unwrap()));
+
+        let ok = GraphQLBatchRequest::Batch(vec![request(r#"{"a": 1}"#)]);
+        assert_eq!(limits.check_variables(&ok), Ok(()));
+
+        let too_many = GraphQLBatchRequest::Batch(vec![request(r#"{"a": 1}"#), request(r#"{"a": 1, "b": 2}"#)]);
+        assert_eq!(limits.check_variables(&too_many), Err(LimitError::TooManyVariables(1)));
+    }
+
+    #[test]
+    fn serializes_to_graphql_error() {
+        let err = LimitError::QueryStringTooLong(10);
+
+        assert_eq!(err.status(), 400);
+        assert_eq!(
+            serde_json::to_string(&err).unwrap(),
+            r#"{"errors":[{"message":"Query string must not be longer than 10 bytes","extensions":{"code":"QUERY_STRING_TOO_LONG","limit":10}}]}"#);
+    }
+}
@@ -0,0 +1,2019 @@
+//! Utilities for building HTTP endpoints in a library-agnostic manner
+
+#[cfg(feature="serde_json")] use std::error::Error;
+use std::borrow::Cow;
+use std::fmt;
+use std::slice;
+
+use serde::{de, ser};
+use serde::ser::SerializeMap;
+
+use ::{ExecutionOptions, GraphQLError, Value, Object, Variables, GraphQLType, RootNode};
+use ast::{Document, InputValue};
+use executor::{label_errors, serialize_validated_query, ExecutionError, ExecutionScratch};
+use options::check_operation;
+use prepared::select_operation;
+
+#[cfg(feature="compression")] pub mod compression;
+pub mod context;
+pub mod cors;
+pub mod endpoints;
+#[cfg(feature="serde_json")] pub mod handler;
+pub mod limits;
+#[cfg(feature="serde_json")] pub mod multipart;
+pub mod over_http;
+pub mod playground;
+#[cfg(feature="serde_json")] pub mod recording;
+#[cfg(feature="serde_json")] pub mod sse;
+#[cfg(feature="serde_json")] pub mod websocket;
+
+/// The expected structure of the decoded JSON document for either POST or GET requests.
+///
+/// For POST, you can use Serde to deserialize the incoming JSON data directly
+/// into this struct - it derives Deserialize for exactly this reason.
+///
+/// For GET, use `from_url_query` to parse the "query", "operationName", and
+/// "variables" parameters out of the URL query string.
+///
+/// POST requests with the `application/graphql` content type carry the query
+/// itself as the body, see `from_graphql_body`.
+#[derive(Deserialize)]
+pub struct GraphQLRequest {
+    query: String,
+    #[serde(rename = "operationName")]
+    operation_name: Option<String>,
+    variables: Option<InputValue>,
+    #[serde(skip)]
+    request_id: Option<String>,
+}
+
+impl GraphQLRequest {
+    fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_ref().map(|oper_name| &**oper_name)
+    }
+
+    fn variables(&self) -> Variables {
+        variables_of(self.variables.as_ref())
+    }
+
+    /// Construct a new GraphQL request from parts
+    pub fn new(query: String, operation_name: Option<String>, variables: Option<InputValue>) -> GraphQLRequest {
+        GraphQLRequest {
+            query: query,
+            operation_name: operation_name,
+            variables: variables,
+            request_id: None,
+        }
+    }
+
+    /// Identify the request in the errors of its execution
+    ///
+    /// The ID is added as `requestId` to the `extensions` of every execution
+    /// error, along with the name of the operation as `operationName` if the
+    /// request names one, so errors collected from the logs of several
+    /// servers can be traced back to the request that caused them:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate juniper;
+    /// use juniper::{EmptyMutation, FieldResult, RootNode, Value};
+    /// use juniper::http::GraphQLRequest;
+    ///
+    /// struct Query;
+    ///
+    /// graphql_object!(Query: () |&self| {
+    ///     field fail() -> FieldResult<i32> { Err("Out of order")? }
+    /// });
+    ///
+    /// # fn main() {
+    /// let root_node = RootNode::new(Query, EmptyMutation::<()>::new());
+    /// let request = GraphQLRequest::new("query Q { fail }".to_owned(), Some("Q".to_owned()), None)
+    ///     .with_request_id("7f3a");
+    ///
+    /// let response = request.execute(&root_node, &());
+    /// let extensions = response.errors()[0].extensions();
+    /// assert_eq!(extensions.get("requestId"), Some(&Value::string("7f3a")));
+    /// assert_eq!(extensions.get("operationName"), Some(&Value::string("Q")));
+    /// # }
+    /// ```
+    ///
+    /// Errors that prevent the request from being executed, e.g. validation
+    /// errors, aren't labeled.
+    pub fn with_request_id<T: Into<String>>(mut self, request_id: T) -> GraphQLRequest {
+        self.request_id = Some(request_id.into());
+        self
+    }
+
+    /// The ID of the request, see `with_request_id`
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_ref().map(String::as_str)
+    }
+
+    /// Construct a request from the body of an `application/graphql` POST
+    ///
+    /// The whole body is the query document, without an operation name or
+    /// variables.
+    pub fn from_graphql_body(body: String) -> GraphQLRequest {
+        GraphQLRequest::new(body, None, None)
+    }
+
+    /// Execute a GraphQL request using the specified schema and context
+    ///
+    /// This is a simple wrapper around the `execute` function exposed at the
+    /// top level of this crate, so it uses the document cache of the schema if
+    /// it has one.
+    pub fn execute<'a, CtxT, QueryT, MutationT>(
+        &'a self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+    )
+        -> GraphQLResponse<'a>
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        self.execute_with_scratch(root_node, context, &mut ExecutionScratch::new())
+    }
+
+    /// Execute a GraphQL request with the given options instead of the ones of
+    /// the schema, see `ExecutionOptions`
+    pub fn execute_with_options<'a, CtxT, QueryT, MutationT>(
+        &'a self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+        options: &ExecutionOptions,
+    )
+        -> GraphQLResponse<'a>
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        self.execute(&root_node.clone().with_execution_options(options.clone()), context)
+    }
+
+    /// Execute a GraphQL request, reusing the allocations of an
+    /// `ExecutionScratch`
+    ///
+    /// Integrations can keep a scratch per thread or connection, see
+    /// `ExecutionScratch`.
+    pub fn execute_with_scratch<'a, CtxT, QueryT, MutationT>(
+        &'a self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+        scratch: &mut ExecutionScratch,
+    )
+        -> GraphQLResponse<'a>
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        let result = ::execute_document(
+            &self.query,
+            self.operation_name(),
+            root_node,
+            Cow::Owned(self.variables()),
+            context,
+            scratch,
+        );
+
+        GraphQLResponse::from_result(result.map(|(value, errors)| {
+            (value, label_errors(errors, self.request_id(), self.operation_name()))
+        }))
+    }
+
+    /// Execute a GraphQL request while its response is serialized
+    ///
+    /// `execute` builds the whole response in memory before it's serialized,
+    /// which takes several times the size of the serialized response. This
+    /// parses and validates the request right away, but only executes it
+    /// when the returned response is serialized, and hands the items of lists
+    /// returned by top-level fields to the serializer as soon as they're
+    /// resolved:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate juniper;
+    /// # extern crate serde_json;
+    /// use juniper::{EmptyMutation, RootNode};
+    /// use juniper::http::GraphQLRequest;
+    ///
+    /// struct Query;
+    ///
+    /// graphql_object!(Query: () |&self| {
+    ///     field squares(count: i32) -> Vec<i32> { (0..count).map(|i| i * i).collect() }
+    /// });
+    ///
+    /// # fn main() {
+    /// let root_node = RootNode::new(Query, EmptyMutation::<()>::new());
+    /// let request = GraphQLRequest::new("{ squares(count: 4) }".to_owned(), None, None);
+    ///
+    /// let response = request.execute_streaming(&root_node, &());
+    /// assert!(response.is_ok());
+    ///
+    /// let mut body = Vec::new();
+    /// serde_json::to_writer(&mut body, &response).unwrap();
+    /// assert_eq!(body, br#"{"data":{"squares":[0,1,4,9]}}"#.to_vec());
+    /// # }
+    /// ```
+    ///
+    /// Lists nested in other values are still collected, as are the values of
+    /// fields selected more than once. Since the errors are only known once
+    /// the data is serialized, they follow the data in the response, and data
+    /// that was serialized before the response budget was exceeded is kept.
+    /// The document cache isn't used.
+    pub fn execute_streaming<'a, CtxT, QueryT, MutationT>(
+        &'a self,
+        root_node: &'a RootNode<'a, QueryT, MutationT>,
+        context: &'a CtxT,
+    )
+        -> StreamingGraphQLResponse<'a, CtxT, QueryT, MutationT>
+        where QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+    {
+        let variables = self.variables();
+        let document = ::parse_and_validate(&self.query, root_node, &variables)
+            .and_then(|document| {
+                try!(select_operation(&document, self.operation_name()));
+                try!(check_operation(&document, self.operation_name(), &root_node.options));
+                Ok(document)
+            });
+
+        StreamingGraphQLResponse {
+            document: document,
+            operation_name: self.operation_name(),
+            request_id: self.request_id(),
+            variables: variables,
+            root_node: root_node,
+            context: context,
+        }
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl GraphQLRequest {
+    /// Construct a request from the parameters of a GET request
+    ///
+    /// The parameters are taken as they appear in the URL, i.e. still
+    /// URL-encoded. The variables, if present, must be a JSON object or
+    /// `null`.
+    pub fn from_get_params(query: &str, operation_name: Option<&str>, variables: Option<&str>)
+        -> Result<GraphQLRequest, GetRequestError>
+    {
+        let query = try!(url_decode_param("query", query));
+        let operation_name = match operation_name {
+            Some(name) => Some(try!(url_decode_param("operationName", name))),
+            None => None,
+        };
+        let variables = match variables {
+            Some(variables) => {
+                let variables = try!(
+                    ::serde_json::from_str::<InputValue>(&try!(url_decode_param("variables", variables)))
+                        .map_err(GetRequestError::Variables));
+
+                if variables.is_null() {
+                    None
+                } else if variables.to_object_value().is_none() {
+                    return Err(GetRequestError::VariablesNotObject);
+                } else {
+                    Some(variables)
+                }
+            },
+            None => None,
+        };
+
+        Ok(GraphQLRequest::new(query, operation_name, variables))
+    }
+
+    /// Construct a request from URL-encoded key/value pairs
+    ///
+    /// Only the `query`, `operationName`, and `variables` keys are used, and
+    /// each of them may appear at most once. The operation name is also
+    /// accepted as `operation_name`. Other keys are ignored.
+    pub fn from_query_params<'a, I>(params: I) -> Result<GraphQLRequest, GetRequestError>
+        where I: IntoIterator<Item=(&'a str, &'a str)>
+    {
+        let mut query = None;
+        let mut operation_name = None;
+        let mut variables = None;
+
+        for (key, value) in params {
+            let key = try!(url_decode_param(key, key));
+            let slot = match &*key {
+                "query" => &mut query,
+                "operationName" | "operation_name" => &mut operation_name,
+                "variables" => &mut variables,
+                _ => continue,
+            };
+
+            if slot.is_some() {
+                return Err(GetRequestError::DuplicateParameter(key));
+            }
+            *slot = Some(value);
+        }
+
+        match query {
+            Some(query) => GraphQLRequest::from_get_params(query, operation_name, variables),
+            None => Err(GetRequestError::MissingQuery),
+        }
+    }
+
+    /// Construct a request from the query string of a GET request's URL
+    ///
+    /// The query string is everything after the `?`, e.g.
+    /// `query=%7Bhero%7Bname%7D%7D&operationName=Hero`.
+    pub fn from_url_query(query_string: &str) -> Result<GraphQLRequest, GetRequestError> {
+        GraphQLRequest::from_query_params(query_string
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| match pair.find('=') {
+                Some(pos) => (&pair[..pos], &pair[pos + 1..]),
+                None => (pair, ""),
+            }))
+    }
+}
+
+/// Error building a `GraphQLRequest` from the parameters of a GET request
+#[cfg(feature="serde_json")]
+#[derive(Debug)]
+pub enum GetRequestError {
+    /// The `query` parameter is missing
+    MissingQuery,
+    /// A parameter was given more than once
+    DuplicateParameter(String),
+    /// The named parameter is not valid UTF-8 after URL-decoding
+    InvalidEncoding(String),
+    /// The `variables` parameter is not a valid JSON document
+    Variables(::serde_json::Error),
+    /// The `variables` parameter is valid JSON, but not an object
+    VariablesNotObject,
+}
+
+#[cfg(feature="serde_json")]
+impl fmt::Display for GetRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GetRequestError::MissingQuery => write!(f, "No query provided"),
+            GetRequestError::DuplicateParameter(ref name) =>
+                write!(f, "Parameter \"{}\" must not occur more than once", name),
+            GetRequestError::InvalidEncoding(ref name) =>
+                write!(f, "Parameter \"{}\" is not valid UTF-8 after URL-decoding", name),
+            GetRequestError::Variables(ref err) =>
+                write!(f, "Parameter \"variables\" is not a valid JSON document: {}", err),
+            GetRequestError::VariablesNotObject =>
+                write!(f, "Parameter \"variables\" must be a JSON object"),
+        }
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl Error for GetRequestError {
+    fn description(&self) -> &str {
+        match *self {
+            GetRequestError::MissingQuery => "No query provided",
+            GetRequestError::DuplicateParameter(_) => "Duplicate URL query parameter",
+            GetRequestError::InvalidEncoding(_) => "Invalid URL encoding",
+            GetRequestError::Variables(_) | GetRequestError::VariablesNotObject => "Invalid variables",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            GetRequestError::Variables(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+// Decodes `application/x-www-form-urlencoded` text. Malformed escapes are
+// kept as they are, like browsers do.
+#[cfg(feature="serde_json")]
+fn url_decode_param(name: &str, s: &str) -> Result<String, GetRequestError> {
+    fn hex_value(b: u8) -> Option<u8> {
+        (b as char).to_digit(16).map(|d| d as u8)
+    }
+
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                match (hex_value(bytes[i + 1]), hex_value(bytes[i + 2])) {
+                    (Some(hi), Some(lo)) => {
+                        decoded.push(hi * 16 + lo);
+                        i += 2;
+                    },
+                    _ => decoded.push(b'%'),
+                }
+            },
+            b => decoded.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8(decoded).map_err(|_| GetRequestError::InvalidEncoding(name.to_owned()))
+}
+
+/// Simple wrapper around the result from executing a GraphQL query
+///
+/// This struct implements Serialize, so you can simply serialize this
+/// to JSON and send it over the wire. Use the `is_ok` method to determine
+/// whether to send a 200 or 400 HTTP status code.
+///
+/// The executed data and errors can be inspected and modified before the
+/// response is serialized, e.g. by middleware, which can also add entries to
+/// the `extensions` of the response.
+pub struct GraphQLResponse<'a> {
+    result: Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>,
+    extensions: Object,
+}
+
+impl<'a> GraphQLResponse<'a> {
+    /// Construct a response from the result of the top-level `execute` function
+    pub fn from_result(result: Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>) -> GraphQLResponse<'a> {
+        GraphQLResponse {
+            result: result,
+            extensions: Object::new(),
+        }
+    }
+
+    /// Was the request successful or not?
+    ///
+    /// Note that there still might be errors in the response even though it's
+    /// considered OK. This is by design in GraphQL.
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// The data produced by executing the query
+    ///
+    /// Returns `None` if the request failed before execution, e.g. because
+    /// the query didn't validate.
+    pub fn data(&self) -> Option<&Value> {
+        self.result.as_ref().ok().map(|&(ref data, _)| data)
+    }
+
+    /// Mutable access to the data produced by executing the query
+    pub fn data_mut(&mut self) -> Option<&mut Value> {
+        self.result.as_mut().ok().map(|&mut (ref mut data, _)| data)
+    }
+
+    /// The field errors that occurred during execution
+    ///
+    /// This is empty if the request failed before execution, see `error`.
+    pub fn errors(&self) -> &[ExecutionError] {
+        match self.result {
+            Ok((_, ref errors)) => errors,
+            Err(_) => &[],
+        }
+    }
+
+    /// The error that prevented the query from being executed at all
+    pub fn error(&self) -> Option<&GraphQLError<'a>> {
+        self.result.as_ref().err()
+    }
+
+    /// Entries added to the `extensions` of the response
+    ///
+    /// The `extensions` key is only serialized if there is at least one
+    /// entry.
+    pub fn extensions(&self) -> &Object {
+        &self.extensions
+    }
+
+    /// Mutable access to the `extensions` of the response, to add entries
+    /// such as tracing or cost information
+    pub fn extensions_mut(&mut self) -> &mut Object {
+        &mut self.extensions
+    }
+
+    /// Unwrap the result of executing the query
+    ///
+    /// Any extensions are discarded.
+    pub fn into_result(self) -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>> {
+        self.result
+    }
+}
+
+/// Either a single GraphQL request or a batch of them
+///
+/// Clients like Apollo's batching link send several requests at once as a
+/// JSON array of request objects. Deserializing into this type accepts both
+/// forms, and executing a batch produces an array of responses in the same
+/// order.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum GraphQLBatchRequest {
+    /// A single request, sent as a JSON object
+    Single(GraphQLRequest),
+    /// Several requests, sent as a JSON array
+    Batch(Vec<GraphQLRequest>),
+}
+
+impl GraphQLBatchRequest {
+    /// Was this sent as a batch, i.e. as a JSON array?
+    pub fn is_batch(&self) -> bool {
+        match *self {
+            GraphQLBatchRequest::Single(_) => false,
+            GraphQLBatchRequest::Batch(_) => true,
+        }
+    }
+
+    /// Identify all requests in the batch with the same ID, see
+    /// `GraphQLRequest::with_request_id`
+    pub fn with_request_id<T: Into<String>>(self, request_id: T) -> GraphQLBatchRequest {
+        let request_id = request_id.into();
+
+        match self {
+            GraphQLBatchRequest::Single(request) =>
+                GraphQLBatchRequest::Single(request.with_request_id(request_id)),
+            GraphQLBatchRequest::Batch(requests) =>
+                GraphQLBatchRequest::Batch(requests.into_iter()
+                    .map(|request| request.with_request_id(request_id.clone()))
+                    .collect()),
+        }
+    }
+
+    /// The individual requests
+    ///
+    /// A single request is returned as a slice of one element.
+    pub fn requests(&self) -> &[GraphQLRequest] {
+        match *self {
+            GraphQLBatchRequest::Single(ref request) => slice::from_ref(request),
+            GraphQLBatchRequest::Batch(ref requests) => requests,
+        }
+    }
+
+    /// Execute every request in the batch using the specified schema and context
+    pub fn execute<'a, CtxT, QueryT, MutationT>(
+        &'a self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+    )
+        -> GraphQLBatchResponse<'a>
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        match *self {
+            GraphQLBatchRequest::Single(ref request) =>
+                GraphQLBatchResponse::Single(request.execute(root_node, context)),
+            GraphQLBatchRequest::Batch(ref requests) =>
+                GraphQLBatchResponse::Batch(requests.iter()
+                    .map(|request| request.execute(root_node, context))
+                    .collect()),
+        }
+    }
+}
+
+impl From<GraphQLRequest> for GraphQLBatchRequest {
+    fn from(request: GraphQLRequest) -> GraphQLBatchRequest {
+        GraphQLBatchRequest::Single(request)
+    }
+}
+
+/// A GraphQL request borrowing its query from the request body
+///
+/// Deserializing a `GraphQLRequest` copies the query out of the body, which
+/// for large documents or batches means holding everything twice. This type
+/// borrows the query and operation name from the buffer it's deserialized
+/// from instead, e.g. with `serde_json::from_slice`, and only copies them if
+/// they contain escape sequences. Variables are always copied.
+///
+/// ```rust
+/// # extern crate juniper;
+/// # extern crate serde_json;
+/// use std::borrow::Cow;
+/// use juniper::http::GraphQLRequestRef;
+///
+/// # fn main() {
+/// let body = br#"{"query": "{ hero { name } }", "operationName": null}"#.to_vec();
+/// let request: GraphQLRequestRef = serde_json::from_slice(&body).unwrap();
+///
+/// assert!(match *request.query() { Cow::Borrowed(_) => true, Cow::Owned(_) => false });
+/// # }
+/// ```
+#[derive(Deserialize)]
+pub struct GraphQLRequestRef<'a> {
+    #[serde(borrow)]
+    query: BorrowedStr<'a>,
+    #[serde(rename = "operationName", borrow, default)]
+    operation_name: Option<BorrowedStr<'a>>,
+    variables: Option<InputValue>,
+}
+
+/// Either a single borrowed GraphQL request or a batch of them, see
+/// `GraphQLRequestRef` and `GraphQLBatchRequest`
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum GraphQLBatchRequestRef<'a> {
+    /// A single request, sent as a JSON object
+    #[serde(borrow)]
+    Single(GraphQLRequestRef<'a>),
+    /// Several requests, sent as a JSON array
+    #[serde(borrow)]
+    Batch(Vec<GraphQLRequestRef<'a>>),
+}
+
+// A string that's borrowed from the input whenever the deserializer allows
+struct BorrowedStr<'a>(Cow<'a, str>);
+
+impl<'de: 'a, 'a> de::Deserialize<'de> for BorrowedStr<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<BorrowedStr<'a>, D::Error>
+        where D: de::Deserializer<'de>,
+    {
+        struct BorrowedStrVisitor;
+
+        impl<'de> de::Visitor<'de> for BorrowedStrVisitor {
+            type Value = BorrowedStr<'de>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a string")
+            }
+
+            fn visit_borrowed_str<E>(self, value: &'de str) -> Result<BorrowedStr<'de>, E> {
+                Ok(BorrowedStr(Cow::Borrowed(value)))
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<BorrowedStr<'de>, E> {
+                Ok(BorrowedStr(Cow::Owned(value.to_owned())))
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<BorrowedStr<'de>, E> {
+                Ok(BorrowedStr(Cow::Owned(value)))
+            }
+        }
+
+        deserializer.deserialize_str(BorrowedStrVisitor)
+    }
+}
+
+impl<'a> GraphQLRequestRef<'a> {
+    /// The query document
+    pub fn query(&self) -> &Cow<'a, str> {
+        &self.query.0
+    }
+
+    /// The name of the operation to execute, if given
+    pub fn operation_name(&self) -> Option<&str> {
+        self.operation_name.as_ref().map(|name| &*name.0)
+    }
+
+    /// Execute the request using the specified schema and context, see
+    /// `GraphQLRequest::execute`
+    pub fn execute<'b, CtxT, QueryT, MutationT>(
+        &'b self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+    )
+        -> GraphQLResponse<'b>
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        GraphQLResponse::from_result(::execute_document(
+            &self.query.0,
+            self.operation_name(),
+            root_node,
+            Cow::Owned(variables_of(self.variables.as_ref())),
+            context,
+            &mut ExecutionScratch::new(),
+        ))
+    }
+
+    /// Copy the request out of the buffer it borrows from
+    pub fn into_owned(self) -> GraphQLRequest {
+        GraphQLRequest::new(
+            self.query.0.into_owned(),
+            self.operation_name.map(|name| name.0.into_owned()),
+            self.variables)
+    }
+}
+
+impl<'a> GraphQLBatchRequestRef<'a> {
+    /// Was this sent as a batch, i.e. as a JSON array?
+    pub fn is_batch(&self) -> bool {
+        match *self {
+            GraphQLBatchRequestRef::Single(_) => false,
+            GraphQLBatchRequestRef::Batch(_) => true,
+        }
+    }
+
+    /// The individual requests
+    ///
+    /// A single request is returned as a slice of one element.
+    pub fn requests(&self) -> &[GraphQLRequestRef<'a>] {
+        match *self {
+            GraphQLBatchRequestRef::Single(ref request) => slice::from_ref(request),
+            GraphQLBatchRequestRef::Batch(ref requests) => requests,
+        }
+    }
+
+    /// Execute every request in the batch using the specified schema and context
+    pub fn execute<'b, CtxT, QueryT, MutationT>(
+        &'b self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+    )
+        -> GraphQLBatchResponse<'b>
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        match *self {
+            GraphQLBatchRequestRef::Single(ref request) =>
+                GraphQLBatchResponse::Single(request.execute(root_node, context)),
+            GraphQLBatchRequestRef::Batch(ref requests) =>
+                GraphQLBatchResponse::Batch(requests.iter()
+                    .map(|request| request.execute(root_node, context))
+                    .collect()),
+        }
+    }
+
+    /// Copy the requests out of the buffer they borrow from
+    pub fn into_owned(self) -> GraphQLBatchRequest {
+        match self {
+            GraphQLBatchRequestRef::Single(request) =>
+                GraphQLBatchRequest::Single(request.into_owned()),
+            GraphQLBatchRequestRef::Batch(requests) =>
+                GraphQLBatchRequest::Batch(requests.into_iter().map(GraphQLRequestRef::into_owned).collect()),
+        }
+    }
+}
+
+// The variables of a request, which are sent as an object
+fn variables_of(variables: Option<&InputValue>) -> Variables {
+    variables.and_then(|iv| {
+        iv.to_object_value().map(|o| {
+            o.into_iter().map(|(k, v)| (k.to_owned(), v.clone())).collect()
+        })
+    }).unwrap_or_default()
+}
+
+#[cfg(feature="serde_json")]
+impl GraphQLBatchRequest {
+    /// Parse a JSON request body, rejecting anything but well-formed requests
+    ///
+    /// Deserializing with Serde ignores unknown keys, so a misspelled
+    /// `"qeury"` only shows up as a missing query. This instead rejects
+    /// unknown keys and values of the wrong type, reporting where in the
+    /// document they are, e.g. `$[1].variables`.
+    pub fn from_json_strict(body: &str) -> Result<GraphQLBatchRequest, StrictRequestError> {
+        match try!(::serde_json::from_str(body).map_err(StrictRequestError::Json)) {
+            ::serde_json::Value::Array(requests) => Ok(GraphQLBatchRequest::Batch(try!(
+                requests.into_iter()
+                    .enumerate()
+                    .map(|(i, request)| strict_request(request, format!("$[{}]", i)))
+                    .collect::<Result<_, _>>()))),
+            request => strict_request(request, "$".to_owned()).map(GraphQLBatchRequest::Single),
+        }
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl GraphQLRequest {
+    /// Parse a JSON request body containing a single request, see
+    /// `GraphQLBatchRequest::from_json_strict`
+    pub fn from_json_strict(body: &str) -> Result<GraphQLRequest, StrictRequestError> {
+        strict_request(
+            try!(::serde_json::from_str(body).map_err(StrictRequestError::Json)),
+            "$".to_owned())
+    }
+}
+
+#[cfg(feature="serde_json")]
+fn strict_request(request: ::serde_json::Value, path: String) -> Result<GraphQLRequest, StrictRequestError> {
+    use serde_json::Value as Json;
+
+    let fields = match request {
+        Json::Object(fields) => fields,
+        _ => return Err(StrictRequestError::InvalidType { path: path, expected: "an object" }),
+    };
+
+    let mut query = None;
+    let mut operation_name = None;
+    let mut variables = None;
+
+    for (key, value) in fields {
+        let field_path = format!("{}.{}", path, key);
+
+        match (&*key, value) {
+            ("query", Json::String(s)) => query = Some(s),
+            ("query", _) =>
+                return Err(StrictRequestError::InvalidType { path: field_path, expected: "a string" }),
+            ("operationName", Json::String(s)) => operation_name = Some(s),
+            ("operationName", Json::Null) => (),
+            ("operationName", _) =>
+                return Err(StrictRequestError::InvalidType { path: field_path, expected: "a string or null" }),
+            ("variables", value @ Json::Object(_)) => variables = Some(InputValue::from(value)),
+            ("variables", Json::Null) => (),
+            ("variables", _) =>
+                return Err(StrictRequestError::InvalidType { path: field_path, expected: "an object or null" }),
+            _ => return Err(StrictRequestError::UnknownKey(field_path)),
+        }
+    }
+
+    match query {
+        Some(query) => Ok(GraphQLRequest::new(query, operation_name, variables)),
+        None => Err(StrictRequestError::MissingQuery(path)),
+    }
+}
+
+/// Error parsing a request body with `from_json_strict`
+///
+/// Paths point into the JSON document, with `$` being the document itself.
+#[cfg(feature="serde_json")]
+#[derive(Debug)]
+pub enum StrictRequestError {
+    /// The body is not valid JSON
+    Json(::serde_json::Error),
+    /// A request contains a key other than `query`, `operationName`, and
+    /// `variables`
+    UnknownKey(String),
+    /// A request doesn't contain a query
+    MissingQuery(String),
+    /// A value doesn't have the expected type
+    InvalidType {
+        /// Where the value is in the document
+        path: String,
+        /// A description of the expected type
+        expected: &'static str,
+    },
+}
+
+#[cfg(feature="serde_json")]
+impl fmt::Display for StrictRequestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            StrictRequestError::Json(ref err) => write!(f, "Invalid JSON: {}", err),
+            StrictRequestError::UnknownKey(ref path) => write!(f, "Unknown key at {}", path),
+            StrictRequestError::MissingQuery(ref path) => write!(f, "No query provided at {}", path),
+            StrictRequestError::InvalidType { ref path, expected } =>
+                write!(f, "Expected {} at {}", expected, path),
+        }
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl Error for StrictRequestError {
+    fn description(&self) -> &str {
+        match *self {
+            StrictRequestError::Json(_) => "Invalid JSON",
+            StrictRequestError::UnknownKey(_) => "Unknown request key",
+            StrictRequestError::MissingQuery(_) => "No query provided",
+            StrictRequestError::InvalidType { .. } => "Invalid request value",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            StrictRequestError::Json(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// The result of executing a `GraphQLBatchRequest`
+///
+/// A single request serializes to a single response object, and a batch to an
+/// array of them.
+pub enum GraphQLBatchResponse<'a> {
+    /// The response to a single request
+    Single(GraphQLResponse<'a>),
+    /// The responses to a batch of requests
+    Batch(Vec<GraphQLResponse<'a>>),
+}
+
+impl<'a> GraphQLBatchResponse<'a> {
+    /// Were all requests successful?
+    ///
+    /// As with `GraphQLResponse::is_ok`, the responses might still contain
+    /// field errors.
+    pub fn is_ok(&self) -> bool {
+        match *self {
+            GraphQLBatchResponse::Single(ref response) => response.is_ok(),
+            GraphQLBatchResponse::Batch(ref responses) => responses.iter().all(GraphQLResponse::is_ok),
+        }
+    }
+
+    /// The HTTP status integrations respond with
+    ///
+    /// This is 200 if every request in the batch could be executed, and 400 if
+    /// any of them failed e.g. validation. Field errors don't affect the
+    /// status.
+    pub fn status_code(&self) -> u16 {
+        if self.is_ok() { 200 } else { 400 }
+    }
+}
+
+impl<'a> GraphQLBatchResponse<'a> {
+    /// The individual responses, in the order of the requests
+    ///
+    /// A single response is returned as a slice of one element.
+    pub fn responses(&self) -> &[GraphQLResponse<'a>] {
+        match *self {
+            GraphQLBatchResponse::Single(ref response) => slice::from_ref(response),
+            GraphQLBatchResponse::Batch(ref responses) => responses,
+        }
+    }
+
+    /// Mutable access to the individual responses
+    pub fn responses_mut(&mut self) -> &mut [GraphQLResponse<'a>] {
+        match *self {
+            GraphQLBatchResponse::Single(ref mut response) => slice::from_mut(response),
+            GraphQLBatchResponse::Batch(ref mut responses) => responses,
+        }
+    }
+}
+
+impl<'a> ser::Serialize for GraphQLBatchResponse<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        match *self {
+            GraphQLBatchResponse::Single(ref response) => response.serialize(serializer),
+            GraphQLBatchResponse::Batch(ref responses) => responses.serialize(serializer),
+        }
+    }
+}
+
+/// A response that executes its request while it's serialized
+///
+/// See `GraphQLRequest::execute_streaming`. Serializing the response more than
+/// once executes the request again.
+pub struct StreamingGraphQLResponse<'a, CtxT: 'a, QueryT: 'a, MutationT: 'a>
+    where QueryT: GraphQLType<Context=CtxT>,
+          MutationT: GraphQLType<Context=CtxT>,
+{
+    document: Result<Document<'a>, GraphQLError<'a>>,
+    operation_name: Option<&'a str>,
+    request_id: Option<&'a str>,
+    variables: Variables,
+    root_node: &'a RootNode<'a, QueryT, MutationT>,
+    context: &'a CtxT,
+}
+
+impl<'a, CtxT, QueryT, MutationT> StreamingGraphQLResponse<'a, CtxT, QueryT, MutationT>
+    where QueryT: GraphQLType<Context=CtxT>,
+          MutationT: GraphQLType<Context=CtxT>,
+{
+    /// Can the request be executed?
+    ///
+    /// Field errors that occur during execution are only known after the
+    /// response has been serialized.
+    pub fn is_ok(&self) -> bool {
+        self.document.is_ok()
+    }
+
+    /// The error that prevents the request from being executed, if any
+    pub fn error(&self) -> Option<&GraphQLError<'a>> {
+        self.document.as_ref().err()
+    }
+}
+
+impl<'a, CtxT, QueryT, MutationT> ser::Serialize for StreamingGraphQLResponse<'a, CtxT, QueryT, MutationT>
+    where QueryT: GraphQLType<Context=CtxT>,
+          MutationT: GraphQLType<Context=CtxT>,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        match self.document {
+            Ok(ref document) => serialize_validated_query(
+                document,
+                self.operation_name,
+                self.root_node,
+                Cow::Borrowed(&self.variables),
+                self.context,
+                self.request_id,
+                serializer),
+            Err(ref err) => {
+                let mut map = try!(serializer.serialize_map(Some(1)));
+                try!(map.serialize_key("errors"));
+                try!(map.serialize_value(err));
+                map.end()
+            },
+        }
+    }
+}
+
+/// Layout of the JSON produced by `GraphQLResponse::to_json`
+#[cfg(feature="serde_json")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonFormat {
+    /// Indented output, suitable for reading in a browser or GraphiQL
+    Pretty,
+    /// Output without any whitespace between tokens
+    Compact,
+    /// Compact output with object keys sorted and floats always written with
+    /// a fraction or an exponent
+    ///
+    /// Equal responses always serialize to the same bytes in this format,
+    /// which makes it suitable for cache keys and signatures.
+    Canonical,
+}
+
+#[cfg(feature="serde_json")]
+impl Default for JsonFormat {
+    fn default() -> JsonFormat {
+        JsonFormat::Pretty
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl<'a> GraphQLResponse<'a> {
+    /// Encode the response as JSON in the given format
+    pub fn to_json(&self, format: JsonFormat) -> Result<String, ::serde_json::Error> {
+        to_json(self, format)
+    }
+}
+
+#[cfg(feature="serde_json")]
+impl<'a> GraphQLBatchResponse<'a> {
+    /// Encode the response as JSON in the given format
+    pub fn to_json(&self, format: JsonFormat) -> Result<String, ::serde_json::Error> {
+        to_json(self, format)
+    }
+}
+
+#[cfg(feature="serde_json")]
+fn to_json<T>(value: &T, format: JsonFormat) -> Result<String, ::serde_json::Error> where T: ser::Serialize {
+    match format {
+        JsonFormat::Pretty => ::serde_json::to_string_pretty(value),
+        JsonFormat::Compact => ::serde_json::to_string(value),
+        JsonFormat::Canonical => {
+            let json = try!(::serde_json::to_value(value));
+            let mut out = String::new();
+            try!(write_canonical_json(&json, &mut out));
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature="serde_json")]
+fn write_canonical_json(json: &::serde_json::Value, out: &mut String) -> Result<(), ::serde_json::Error> {
+    use serde_json::Value as Json;
+
+    match *json {
+        Json::Null => out.push_str("null"),
+        Json::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+        Json::Number(ref n) => match n.as_f64() {
+            Some(f) if n.is_f64() => out.push_str(&format!("{:?}", f)),
+            _ => out.push_str(&n.to_string()),
+        },
+        Json::String(ref s) => out.push_str(&try!(::serde_json::to_string(s))),
+        Json::Array(ref l) => {
+            out.push('[');
+            for (i, v) in l.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                try!(write_canonical_json(v, out));
+            }
+            out.push(']');
+        },
+        Json::Object(ref o) => {
+            let mut fields = o.iter().collect::<Vec<_>>();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+
+            out.push('{');
+            for (i, (k, v)) in fields.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&try!(::serde_json::to_string(k)));
+                out.push(':');
+                try!(write_canonical_json(v, out));
+            }
+            out.push('}');
+        },
+    }
+
+    Ok(())
+}
+
+#[cfg(feature="msgpack")]
+impl GraphQLRequest {
+    /// Decode a request from MessagePack
+    pub fn from_msgpack(bytes: &[u8]) -> Result<GraphQLRequest, ::rmp_serde::decode::Error> {
+        ::rmp_serde::from_slice(bytes)
+    }
+}
+
+#[cfg(feature="msgpack")]
+impl<'a> GraphQLResponse<'a> {
+    /// Encode the response as MessagePack
+    ///
+    /// Objects are encoded as maps keyed by field name, mirroring the JSON
+    /// representation.
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, ::rmp_serde::encode::Error> {
+        ::rmp_serde::to_vec_named(self)
+    }
+}
+
+#[cfg(feature="cbor")]
+impl GraphQLRequest {
+    /// Decode a request from CBOR
+    pub fn from_cbor(bytes: &[u8]) -> Result<GraphQLRequest, ::serde_cbor::Error> {
+        ::serde_cbor::from_slice(bytes)
+    }
+}
+
+#[cfg(feature="cbor")]
+impl<'a> GraphQLResponse<'a> {
+    /// Encode the response as CBOR
+    pub fn to_cbor(&self) -> Result<Vec<u8>, ::serde_cbor::Error> {
+        ::serde_cbor::to_vec(self)
+    }
+}
+
+impl<'a> ser::Serialize for GraphQLResponse<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let extensions_len = if self.extensions.is_empty() { 0 } else { 1 };
+
+        let mut map = match self.result {
+            Ok((ref res, ref err)) => {
+                let len = if err.is_empty() { 1 } else { 2 };
+                let mut map = try!(serializer.serialize_map(Some(len + extensions_len)));
+
+                try!(map.serialize_key("data"));
+                try!(map.serialize_value(res));
+
+                if !err.is_empty() {
+                    try!(map.serialize_key("errors"));
+                    try!(map.serialize_value(err));
+                }
+
+                map
+            },
+            Err(ref err) => {
+                let mut map = try!(serializer.serialize_map(Some(1 + extensions_len)));
+                try!(map.serialize_key("errors"));
+                try!(map.serialize_value(err));
+                map
+            },
+        };
+
+        if !self.extensions.is_empty() {
+            try!(map.serialize_key("extensions"));
+            try!(map.serialize_value(&self.extensions));
+        }
+
+        map.end()
+    }
+}
+
+#[cfg(all(test, feature="msgpack"))]
+mod msgpack_tests {
+    use std::collections::BTreeMap;
+
+    use rmp_serde;
+
+    use super::GraphQLRequest;
+    use ast::InputValue;
+    use value::Value;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Root;
+
+    graphql_object!(Root: () |&self| {
+        field double(value: i32) -> i32 { value * 2 }
+    });
+
+    #[test]
+    fn round_trips_requests_and_responses() {
+        let mut body: BTreeMap<&str, InputValue> = BTreeMap::new();
+        body.insert("query", InputValue::string("query D($v: Int!) { double(value: $v) }"));
+        body.insert("operationName", InputValue::string("D"));
+        body.insert("variables", InputValue::object(vec![("v", InputValue::int(21))].into_iter().collect()));
+
+        let request = GraphQLRequest::from_msgpack(&rmp_serde::to_vec_named(&body).unwrap()).unwrap();
+        assert_eq!(request.operation_name(), Some("D"));
+
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+        let bytes = request.execute(&schema, &()).to_msgpack().unwrap();
+
+        assert_eq!(
+            rmp_serde::from_slice::<Value>(&bytes).unwrap(),
+            Value::object(vec![
+                ("data", Value::object(vec![("double", Value::int(42))].into_iter().collect())),
+            ].into_iter().collect()));
+    }
+}
+
+#[cfg(all(test, feature="serde_json"))]
+mod get_tests {
+    use ast::InputValue;
+    use super::{GraphQLRequest, GetRequestError};
+
+    #[test]
+    fn parses_url_encoded_parameters() {
+        let request = GraphQLRequest::from_url_query(
+            "query=query+Q(%24id%3A+String)+%7Bhuman(id%3A+%24id)%7Bname%7D%7D&operationName=Q&variables=%7B%22id%22%3A%221000%22%7D&extra")
+            .unwrap();
+
+        assert_eq!(request.query, "query Q($id: String) {human(id: $id){name}}");
+        assert_eq!(request.operation_name(), Some("Q"));
+        assert_eq!(
+            request.variables().get("id"),
+            Some(&InputValue::string("1000")));
+    }
+
+    #[test]
+    fn accepts_snake_case_operation_name_and_unencoded_variables() {
+        let request = GraphQLRequest::from_url_query(
+            r#"query=query+Q($id:+String){human(id:+$id){name}}&operation_name=Q&variables={"id":"1000"}"#)
+            .unwrap();
+
+        assert_eq!(request.operation_name(), Some("Q"));
+        assert_eq!(
+            request.variables().get("id"),
+            Some(&InputValue::string("1000")));
+
+        let request = GraphQLRequest::from_url_query("query=%7Ba%7D&variables=null").unwrap();
+        assert!(request.variables.is_none());
+    }
+
+    #[test]
+    fn keeps_malformed_escapes() {
+        let request = GraphQLRequest::from_get_params("{a}%2", None, None).unwrap();
+
+        assert_eq!(request.query, "{a}%2");
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        match GraphQLRequest::from_url_query("operationName=Q") {
+            Err(GetRequestError::MissingQuery) => (),
+            _ => panic!("Expected a missing query error"),
+        }
+
+        match GraphQLRequest::from_url_query("query=%7Ba%7D&query=%7Bb%7D") {
+            Err(GetRequestError::DuplicateParameter(ref name)) if name == "query" => (),
+            _ => panic!("Expected a duplicate parameter error"),
+        }
+
+        match GraphQLRequest::from_url_query("query=%FF") {
+            Err(GetRequestError::InvalidEncoding(ref name)) if name == "query" => (),
+            _ => panic!("Expected an encoding error"),
+        }
+
+        match GraphQLRequest::from_url_query("query=%7Ba%7D&variables=%7B") {
+            Err(GetRequestError::Variables(_)) => (),
+            _ => panic!("Expected a variables error"),
+        }
+
+        match GraphQLRequest::from_url_query("query=%7Ba%7D&variables=%5B1%5D") {
+            Err(ref err @ GetRequestError::VariablesNotObject) =>
+                assert_eq!(err.to_string(), "Parameter \"variables\" must be a JSON object"),
+            _ => panic!("Expected a variables error"),
+        }
+
+        match GraphQLRequest::from_url_query("query=%7Ba%7D&operationName=A&operation_name=B") {
+            Err(GetRequestError::DuplicateParameter(ref name)) if name == "operation_name" => (),
+            _ => panic!("Expected a duplicate parameter error"),
+        }
+    }
+}
+
+#[cfg(all(test, feature="serde_json"))]
+mod strict_tests {
+    use ast::InputValue;
+    use super::{GraphQLRequest, GraphQLBatchRequest, StrictRequestError};
+
+    #[test]
+    fn accepts_valid_requests() {
+        let request = GraphQLRequest::from_json_strict(
+            r#"{"query": "{a}", "operationName": null, "variables": {"id": 1}}"#).unwrap();
+
+        assert_eq!(request.query, "{a}");
+        assert_eq!(request.operation_name(), None);
+        assert_eq!(request.variables().get("id"), Some(&InputValue::int(1)));
+
+        match GraphQLBatchRequest::from_json_strict(r#"[{"query": "{a}"}, {"query": "{b}"}]"#) {
+            Ok(GraphQLBatchRequest::Batch(ref requests)) if requests.len() == 2 => (),
+            _ => panic!("Expected a batch of two requests"),
+        }
+    }
+
+    #[test]
+    fn reports_paths_of_invalid_values() {
+        match GraphQLRequest::from_json_strict(r#"{"qeury": "{a}"}"#) {
+            Err(StrictRequestError::UnknownKey(ref path)) if path == "$.qeury" => (),
+            _ => panic!("Expected an unknown key error"),
+        }
+
+        match GraphQLRequest::from_json_strict(r#"{"operationName": "A"}"#) {
+            Err(StrictRequestError::MissingQuery(ref path)) if path == "$" => (),
+            _ => panic!("Expected a missing query error"),
+        }
+
+        match GraphQLBatchRequest::from_json_strict(r#"[{"query": "{a}"}, {"query": "{b}", "variables": []}]"#) {
+            Err(err @ StrictRequestError::InvalidType { .. }) =>
+                assert_eq!(err.to_string(), "Expected an object or null at $[1].variables"),
+            _ => panic!("Expected an invalid type error"),
+        }
+
+        match GraphQLRequest::from_json_strict(r#"{"query": 1}"#) {
+            Err(err) => assert_eq!(err.to_string(), "Expected a string at $.query"),
+            _ => panic!("Expected an invalid type error"),
+        }
+
+        match GraphQLRequest::from_json_strict("{") {
+            Err(StrictRequestError::Json(_)) => (),
+            _ => panic!("Expected a JSON error"),
+        }
+    }
+}
+
+#[cfg(all(test, feature="serde_json"))]
+mod json_tests {
+    use std::borrow::Cow;
+    use std::cell::RefCell;
+    use std::collections::BTreeMap;
+    use std::io;
+
+    use serde_json;
+
+    use super::{GraphQLRequest, GraphQLBatchRequest, GraphQLBatchRequestRef, JsonFormat};
+    use executor::{Context, FieldResult};
+    use value::Value;
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::maps::{ObjectMap, KeyValueList};
+    use types::scalars::EmptyMutation;
+
+    struct Root;
+
+    graphql_object!(Root: () |&self| {
+        field zebra() -> f64 { 2.0 }
+        field apple() -> &str { "a\"b" }
+        field list() -> Vec<i32> { vec![1, 2] }
+        field limited() -> ::FieldResult<i32> {
+            Err(::FieldError::from("Slow down").with_extension("retryAfter", Value::int(30)))
+        }
+    });
+
+    fn response(format: JsonFormat) -> String {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+        let request = GraphQLRequest::new("{ zebra apple list }".to_owned(), None, None);
+
+        request.execute(&schema, &()).to_json(format).unwrap()
+    }
+
+    #[test]
+    fn borrowed_requests_borrow_from_the_body() {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+        let body = br#"[{"query": "query A { zebra }", "operationName": "A"}, {"query": "{ apple \u0020}"}]"#.to_vec();
+
+        let batch: GraphQLBatchRequestRef = serde_json::from_slice(&body).unwrap();
+        assert!(batch.is_batch());
+
+        let requests = batch.requests();
+        assert!(match *requests[0].query() { Cow::Borrowed("query A { zebra }") => true, _ => false });
+        assert_eq!(requests[0].operation_name(), Some("A"));
+        assert!(match *requests[1].query() { Cow::Owned(ref query) => query == "{ apple  }", _ => false });
+
+        assert_eq!(
+            batch.execute(&schema, &()).to_json(JsonFormat::Compact).unwrap(),
+            r#"[{"data":{"zebra":2.0}},{"data":{"apple":"a\"b"}}]"#);
+
+        match batch.into_owned() {
+            GraphQLBatchRequest::Batch(ref requests) => assert_eq!(requests[1].query, "{ apple  }"),
+            _ => panic!("Expected a batch"),
+        }
+    }
+
+    #[test]
+    fn streamed_responses_match_executed_ones() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let database = Database::new();
+
+        let queries = [
+            "{ hero { name friends { name appearsIn } } }",
+            "query A { hero { id } } query B { luke: human(id: \"1000\") { ...Names } }
+             fragment Names on Human { name friends { name } }",
+            "{ hero { id } hero { name } ... on Query { droid(id: \"2001\") { name } } }",
+            "{ hero { nope } }",
+        ];
+
+        for query in &queries {
+            let operation_name = if query.starts_with("query A") { Some("B".to_owned()) } else { None };
+            let request = GraphQLRequest::new(query.to_string(), operation_name, None);
+
+            let streamed = request.execute_streaming(&schema, &database);
+            let executed = request.execute(&schema, &database);
+            assert_eq!(streamed.is_ok(), executed.is_ok());
+            assert_eq!(
+                serde_json::to_value(&streamed).unwrap(),
+                serde_json::to_value(&executed).unwrap());
+        }
+    }
+
+    #[test]
+    fn streamed_nested_lists_match_executed_ones() {
+        struct Query;
+        struct Item;
+
+        graphql_object!(Query: () |&self| {
+            field grid() -> Vec<Vec<i32>> { vec![vec![1, 2], vec![], vec![3]] }
+            field maybe() -> Option<Vec<Option<Vec<i32>>>> { Some(vec![Some(vec![1]), None]) }
+            field items() -> Vec<Item> { vec![Item, Item] }
+            field groups() -> ObjectMap<Vec<i32>> {
+                let mut groups = BTreeMap::new();
+                groups.insert("a", vec![1, 2]);
+                groups.insert("b", vec![3]);
+                ObjectMap::from(groups)
+            }
+            field pairs() -> KeyValueList<Item> {
+                let mut pairs = BTreeMap::new();
+                pairs.insert("a", Item);
+                KeyValueList::from(pairs)
+            }
+        });
+
+        graphql_object!(Item: () |&self| {
+            field tags() -> Vec<&str> { vec!["x", "y"] }
+        });
+
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+        let request = GraphQLRequest::new(
+            "{ grid maybe items { tags } groups pairs { key value { tags } } }".to_owned(), None, None);
+
+        let streamed = serde_json::to_value(&request.execute_streaming(&schema, &())).unwrap();
+        assert_eq!(streamed, serde_json::to_value(&request.execute(&schema, &())).unwrap());
+        assert_eq!(streamed["data"]["groups"].to_string(), r#"{"a":[1,2],"b":[3]}"#);
+        assert_eq!(streamed["data"]["grid"].to_string(), "[[1,2],[],[3]]");
+    }
+
+    #[test]
+    fn error_paths_have_numeric_list_indices() {
+        struct Query;
+        struct Item;
+
+        graphql_object!(Query: () |&self| {
+            field items() -> Vec<Item> { vec![Item, Item] }
+        });
+
+        graphql_object!(Item: () |&self| {
+            field fail() -> FieldResult<i32> { Err("Failed")? }
+        });
+
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+        let request = GraphQLRequest::new("{ items { fail } }".to_owned(), None, None);
+
+        let response = serde_json::to_value(&request.execute(&schema, &())).unwrap();
+        assert_eq!(response["errors"][1]["path"].to_string(), r#"["items",1,"fail"]"#);
+    }
+
+    #[test]
+    fn streamed_lists_are_serialized_item_by_item() {
+        struct Output(RefCell<Vec<u8>>);
+
+        impl Context for Output {}
+
+        impl<'a> io::Write for &'a Output {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.0.borrow_mut().write(buf)
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        struct Report;
+        struct Row;
+
+        graphql_object!(Report: Output |&self| {
+            field rows(count: i32) -> Vec<Row> { (0..count).map(|_| Row).collect() }
+        });
+
+        graphql_object!(Row: Output |&self| {
+            field written(&executor) -> i32 { executor.context().0.borrow().len() as i32 }
+        });
+
+        let schema = RootNode::new(Report, EmptyMutation::<Output>::new());
+        let request = GraphQLRequest::new("{ rows(count: 3) { written } }".to_owned(), None, None);
+        let output = Output(RefCell::new(Vec::new()));
+
+        serde_json::to_writer(&output, &request.execute_streaming(&schema, &output)).unwrap();
+        assert_eq!(
+            String::from_utf8(output.0.into_inner()).unwrap(),
+            r#"{"data":{"rows":[{"written":17},{"written":31},{"written":46}]}}"#);
+    }
+
+    #[test]
+    fn compact_output_has_no_whitespace() {
+        assert_eq!(
+            response(JsonFormat::Compact),
+            r#"{"data":{"zebra":2.0,"apple":"a\"b","list":[1,2]}}"#);
+    }
+
+    #[test]
+    fn pretty_output_is_indented() {
+        assert!(response(JsonFormat::Pretty).starts_with("{\n  \"data\": {\n"));
+    }
+
+    #[test]
+    fn canonical_output_sorts_keys() {
+        assert_eq!(
+            response(JsonFormat::Canonical),
+            r#"{"data":{"apple":"a\"b","list":[1,2],"zebra":2.0}}"#);
+    }
+
+    #[test]
+    fn responses_can_be_modified_before_serialization() {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+
+        let request = GraphQLRequest::new("{ apple nope }".to_owned(), None, None);
+        let response = request.execute(&schema, &());
+        assert!(!response.is_ok());
+        assert!(response.data().is_none());
+        assert!(response.errors().is_empty());
+        assert!(response.error().is_some());
+
+        let request = GraphQLRequest::new("{ apple list }".to_owned(), None, None);
+        let mut response = request.execute(&schema, &());
+        assert!(response.is_ok());
+        assert!(response.errors().is_empty());
+
+        if let Some(&mut Value::Object(ref mut data)) = response.data_mut() {
+            data.insert("apple", Value::string("redacted"));
+        }
+
+        assert_eq!(
+            response.to_json(JsonFormat::Compact).unwrap(),
+            r#"{"data":{"apple":"redacted","list":[1,2]}}"#);
+    }
+
+    #[test]
+    fn extensions_are_serialized_after_data_and_errors() {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+
+        let request = GraphQLRequest::new("{ list }".to_owned(), None, None);
+        let mut response = request.execute(&schema, &());
+        response.extensions_mut().insert("cost", Value::int(1));
+
+        assert_eq!(
+            response.to_json(JsonFormat::Compact).unwrap(),
+            r#"{"data":{"list":[1,2]},"extensions":{"cost":1}}"#);
+
+        let request = GraphQLRequest::new("{ nope }".to_owned(), None, None);
+        let mut response = request.execute(&schema, &());
+        response.extensions_mut().insert("traceId", Value::string("abc"));
+
+        let json = response.to_json(JsonFormat::Compact).unwrap();
+        assert!(json.starts_with(r#"{"errors":[{"#));
+        assert!(json.ends_with(r#"],"extensions":{"traceId":"abc"}}"#));
+    }
+
+    #[test]
+    fn errors_are_serialized_as_objects_with_extensions() {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+
+        let request = GraphQLRequest::new("{ limited }".to_owned(), None, None);
+        assert_eq!(
+            request.execute(&schema, &()).to_json(JsonFormat::Compact).unwrap(),
+            concat!(
+                r#"{"data":{"limited":null},"errors":[{"message":"Slow down","locations":[{"line":1,"column":3}],"#,
+                r#""path":["limited"],"extensions":{"retryAfter":30}}]}"#));
+
+        let request = GraphQLRequest::new("{ zebra".to_owned(), None, None);
+        assert_eq!(
+            request.execute(&schema, &()).to_json(JsonFormat::Compact).unwrap(),
+            concat!(
+                r#"{"errors":[{"message":"Unexpected end of input","locations":[{"line":1,"column":8}],"#,
+                r#""extensions":{"code":"GRAPHQL_PARSE_FAILED"}}]}"#));
+
+        let request = GraphQLRequest::new("query A { zebra } query B { list }".to_owned(), None, None);
+        assert_eq!(
+            request.execute(&schema, &()).to_json(JsonFormat::Compact).unwrap(),
+            concat!(
+                r#"{"errors":[{"message":"Must provide operation name if query contains multiple operations","#,
+                r#""extensions":{"code":"OPERATION_RESOLUTION_FAILURE"}}]}"#));
+    }
+
+    #[test]
+    fn errors_are_labeled_with_the_request_id() {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+
+        let batch: GraphQLBatchRequest = serde_json::from_str(
+            r#"[{"query": "query A { limited }", "operationName": "A"}, {"query": "{ limited }"}]"#).unwrap();
+        let batch = batch.with_request_id("r1");
+        assert_eq!(batch.requests()[0].request_id(), Some("r1"));
+        assert_eq!(
+            serde_json::to_string(&batch.execute(&schema, &())).unwrap(),
+            concat!(
+                r#"[{"data":{"limited":null},"errors":[{"message":"Slow down","locations":[{"line":1,"column":11}],"#,
+                r#""path":["limited"],"extensions":{"retryAfter":30,"requestId":"r1","operationName":"A"}}]},"#,
+                r#"{"data":{"limited":null},"errors":[{"message":"Slow down","locations":[{"line":1,"column":3}],"#,
+                r#""path":["limited"],"extensions":{"retryAfter":30,"requestId":"r1"}}]}]"#));
+
+        let request = GraphQLRequest::new("{ limited }".to_owned(), None, None).with_request_id("r2");
+        let streamed = serde_json::to_string(&request.execute_streaming(&schema, &())).unwrap();
+        assert!(streamed.ends_with(r#""extensions":{"retryAfter":30,"requestId":"r2"}}]}"#));
+    }
+
+    #[test]
+    fn batched_requests_produce_an_array() {
+        let schema = RootNode::new(Root, EmptyMutation::<()>::new());
+
+        let single: GraphQLBatchRequest = serde_json::from_str(r#"{"query": "{ zebra }"}"#).unwrap();
+        assert_eq!(
+            single.execute(&schema, &()).to_json(JsonFormat::Compact).unwrap(),
+            r#"{"data":{"zebra":2.0}}"#);
+        assert!(!single.is_batch());
+        assert_eq!(single.execute(&schema, &()).status_code(), 200);
+
+        let batch: GraphQLBatchRequest = serde_json::from_str(
+            r#"[{"query": "{ zebra }"}, {"query": "{ list }"}, {"query": "{ nope }"}]"#).unwrap();
+        let response = batch.execute(&schema, &());
+
+        assert!(batch.is_batch());
+        assert_eq!(batch.requests().len(), 3);
+        assert!(!response.is_ok());
+        assert_eq!(response.status_code(), 400);
+        assert_eq!(
+            response.to_json(JsonFormat::Compact).unwrap(),
+            concat!(
+                r#"[{"data":{"zebra":2.0}},{"data":{"list":[1,2]}},"#,
+                r#"{"errors":[{"message":"Unknown field \"nope\" on type \"Root\"","locations":[{"line":1,"column":3}],"#,
+                r#""extensions":{"code":"GRAPHQL_VALIDATION_FAILED"}}]}]"#));
+    }
+}
+
+#[cfg(all(test, feature="serde_json"))]
+pub mod tests {
+    use serde_json::Value as Json;
+    use serde_json;
+
+    pub struct TestResponse {
+        pub status_code: i32,
+        pub body: Option<String>,
+        pub content_type: String,
+    }
+
+    pub trait HTTPIntegration {
+        fn get(&self, url: &str) -> TestResponse;
+        fn post(&self, url: &str, body: &str) -> TestResponse;
+        fn post_graphql(&self, url: &str, body: &str) -> TestResponse;
+        fn post_with_content_type(&self, url: &str, content_type: &str, body: &str) -> TestResponse;
+
+        /// The optional features to test, none by default
+        fn features(&self) -> IntegrationFeatures {
+            IntegrationFeatures::default()
+        }
+
+        /// Send messages over a `graphql-transport-ws` WebSocket and collect
+        /// the messages sent back, only called if `features().websockets` is
+        /// set
+        fn websocket(&self, _messages: &[&str]) -> Vec<String> {
+            panic!("The integration doesn't serve WebSockets")
+        }
+    }
+
+    /// The optional features an integration supports, whose sections of the
+    /// suite are skipped otherwise
+    #[derive(Default)]
+    pub struct IntegrationFeatures {
+        /// Multipart requests with file uploads
+        pub uploads: bool,
+        /// The body size limit the integration is set up with
+        pub max_body_bytes: Option<usize>,
+        /// Subscriptions over a WebSocket, see `HTTPIntegration::websocket`
+        pub websockets: bool,
+    }
+
+    pub fn run_http_test_suite<T: HTTPIntegration>(integration: &T) {
+        println!("Running HTTP Test suite for integration");
+
+        println!("  - test_simple_get");
+        test_simple_get(integration);
+
+        println!("  - test_encoded_get");
+        test_encoded_get(integration);
+
+        println!("  - test_get_with_variables");
+        test_get_with_variables(integration);
+
+        println!("  - test_simple_post");
+        test_simple_post(integration);
+
+        println!("  - test_batched_post");
+        test_batched_post(integration);
+
+        println!("  - test_graphql_post");
+        test_graphql_post(integration);
+
+        println!("  - test_get_with_operation_name");
+        test_get_with_operation_name(integration);
+
+        println!("  - test_invalid_get");
+        test_invalid_get(integration);
+
+        println!("  - test_invalid_post");
+        test_invalid_post(integration);
+
+        println!("  - test_batched_post_with_error");
+        test_batched_post_with_error(integration);
+
+        println!("  - test_post_with_operation_name");
+        test_post_with_operation_name(integration);
+
+        println!("  - test_post_without_query");
+        test_post_without_query(integration);
+
+        println!("  - test_post_with_wrong_content_type");
+        test_post_with_wrong_content_type(integration);
+
+        println!("  - test_get_with_invalid_variables");
+        test_get_with_invalid_variables(integration);
+
+        let features = integration.features();
+
+        if features.uploads {
+            println!("  - test_multipart_post");
+            test_multipart_post(integration);
+        }
+
+        if let Some(max_body_bytes) = features.max_body_bytes {
+            println!("  - test_huge_post");
+            test_huge_post(integration, max_body_bytes);
+        }
+
+        if features.websockets {
+            println!("  - test_websocket");
+            test_websocket(integration);
+        }
+    }
+
+    fn unwrap_json_response(response: &TestResponse) -> Json {
+        serde_json::from_str::<Json>(
+            response.body.as_ref().expect("No data returned from request")
+        ).expect("Could not parse JSON object")
+    }
+
+    fn test_simple_get<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get("/?query={hero{name}}");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type.as_str(), "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_encoded_get<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get(
+            "/?query=query%20{%20%20%20human(id:%20\"1000\")%20{%20%20%20%20%20id,%20%20%20%20%20name,%20%20%20%20%20appearsIn,%20%20%20%20%20homePlanet%20%20%20}%20}");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type.as_str(), "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{
+                    "data": {
+                        "human": {
+                            "appearsIn": [
+                                "NEW_HOPE",
+                                "EMPIRE",
+                                "JEDI"
+                                ],
+                                "homePlanet": "Tatooine",
+                                "name": "Luke Skywalker",
+                                "id": "1000"
+                            }
+                        }
+                    }"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_get_with_variables<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get(
+            "/?query=query($id:%20String!)%20{%20%20%20human(id:%20$id)%20{%20%20%20%20%20id,%20%20%20%20%20name,%20%20%20%20%20appearsIn,%20%20%20%20%20homePlanet%20%20%20}%20}&variables={%20%20%20\"id\":%20%20\"1000\"%20}");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{
+                    "data": {
+                        "human": {
+                            "appearsIn": [
+                                "NEW_HOPE",
+                                "EMPIRE",
+                                "JEDI"
+                                ],
+                                "homePlanet": "Tatooine",
+                                "name": "Luke Skywalker",
+                                "id": "1000"
+                            }
+                        }
+                    }"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_simple_post<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post(
+            "/",
+            r#"{"query": "{hero{name}}"}"#);
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_batched_post<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post(
+            "/",
+            r#"[{"query": "{hero{name}}"}, {"query": "{hero{name}}"}]"#);
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(
+                r#"[{"data": {"hero": {"name": "R2-D2"}}}, {"data": {"hero": {"name": "R2-D2"}}}]"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_graphql_post<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post_graphql("/", "{hero{name}}");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_get_with_operation_name<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get(
+            "/?query=query%20A%20{hero{name}}%20query%20B%20{hero{id}}&operationName=B");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"id": "2001"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_invalid_get<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get("/?operationName=A");
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"errors": [{"message": "No query provided", "extensions": {"code": "BAD_REQUEST"}}]}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_invalid_post<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post("/", r#"{"query": "#);
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+        assert!(unwrap_json_response(&response)["errors"][0]["message"].is_string());
+    }
+
+    fn test_batched_post_with_error<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post(
+            "/",
+            r#"[{"query": "{hero{name}}"}, {"query": "{hero{nope}}"}]"#);
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+
+        let json = unwrap_json_response(&response);
+        assert_eq!(
+            json[0],
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+        assert!(json[1]["errors"][0]["message"].is_string());
+    }
+
+    fn test_post_with_operation_name<T: HTTPIntegration>(integration: &T) {
+        let response = integration.post(
+            "/",
+            r#"{"query": "query A {hero{name}} query B {hero{id}}", "operationName": "B"}"#);
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"id": "2001"}}}"#)
+                .expect("Invalid JSON constant in test"));
+
+        let response = integration.post(
+            "/",
+            r#"{"query": "query A {hero{name}}", "operationName": "B"}"#);
+
+        assert_eq!(response.status_code, 400);
+        assert!(!unwrap_json_response(&response)["errors"].is_null());
+    }
+
+    fn test_post_without_query<T: HTTPIntegration>(integration: &T) {
+        for body in &[r#"{"variables": {}}"#, "42", r#"{"query": 42}"#] {
+            let response = integration.post("/", body);
+
+            assert_eq!(response.status_code, 400);
+            assert_eq!(response.content_type, "application/json");
+            assert!(unwrap_json_response(&response)["errors"][0]["message"].is_string());
+        }
+    }
+
+    fn test_post_with_wrong_content_type<T: HTTPIntegration>(integration: &T) {
+        // Integrations either fail to parse the body as JSON, or don't route
+        // the request at all
+        let response = integration.post_with_content_type("/", "text/plain", "{hero{name}}");
+
+        assert!(response.status_code >= 400 && response.status_code < 500);
+    }
+
+    fn test_get_with_invalid_variables<T: HTTPIntegration>(integration: &T) {
+        let response = integration.get("/?query={hero{name}}&variables=[1]");
+
+        assert_eq!(response.status_code, 400);
+        assert_eq!(response.content_type, "application/json");
+        assert!(unwrap_json_response(&response)["errors"][0]["message"].is_string());
+
+        let response = integration.get("/?query={hero{name}}&variables=null");
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_multipart_post<T: HTTPIntegration>(integration: &T) {
+        let body = [
+            "--boundary",
+            r#"Content-Disposition: form-data; name="operations""#,
+            "",
+            r#"{"query": "{hero{name}}", "variables": {"file": null}}"#,
+            "--boundary",
+            r#"Content-Disposition: form-data; name="map""#,
+            "",
+            r#"{"0": ["variables.file"]}"#,
+            "--boundary",
+            r#"Content-Disposition: form-data; name="0"; filename="a.txt""#,
+            "Content-Type: text/plain",
+            "",
+            "contents",
+            "--boundary--",
+            "",
+        ].join("\r\n");
+
+        let response = integration.post_with_content_type("/", "multipart/form-data; boundary=boundary", &body);
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(response.content_type, "application/json");
+
+        assert_eq!(
+            unwrap_json_response(&response),
+            serde_json::from_str::<Json>(r#"{"data": {"hero": {"name": "R2-D2"}}}"#)
+                .expect("Invalid JSON constant in test"));
+    }
+
+    fn test_huge_post<T: HTTPIntegration>(integration: &T, max_body_bytes: usize) {
+        let padding = " ".repeat(max_body_bytes);
+        let response = integration.post("/", &format!(r#"{{"query": "{{hero{{name}}}}{}"}}"#, padding));
+
+        assert_eq!(response.status_code, 413);
+        assert_eq!(response.content_type, "application/json");
+    }
+
+    fn test_websocket<T: HTTPIntegration>(integration: &T) {
+        let messages = integration.websocket(&[
+            r#"{"type": "connection_init"}"#,
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "{hero{name}}"}}"#,
+        ]);
+
+        let messages = messages.iter()
+            .map(|message| serde_json::from_str::<Json>(message).expect("Could not parse JSON message"))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            messages,
+            serde_json::from_str::<Vec<Json>>(r#"[
+                {"type": "connection_ack"},
+                {"type": "next", "id": "1", "payload": {"data": {"hero": {"name": "R2-D2"}}}},
+                {"type": "complete", "id": "1"}
+            ]"#).expect("Invalid JSON constant in test"));
+    }
+}
@@ -0,0 +1,465 @@
+//! Parsing of GraphQL multipart requests, used to upload files
+//!
+//! This implements the [GraphQL multipart request
+//! spec](https://github.com/jaydenseric/graphql-multipart-request-spec). A
+//! `multipart/form-data` body contains an `operations` field with the JSON
+//! request (or batch of requests), a `map` field saying which variables refer
+//! to which file, and one field per file.
+//!
+//! Every variable referring to a file is replaced by a placeholder that
+//! converts into an `Upload`. The file itself ends up in the `UploadedFiles`
+//! returned alongside the request, which should be made available to
+//! resolvers, e.g. through the context:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! # use juniper::Upload;
+//! # use juniper::http::multipart::UploadedFiles;
+//! struct Context {
+//!     uploads: UploadedFiles,
+//! }
+//!
+//! impl juniper::Context for Context {}
+//!
+//! struct Mutation;
+//!
+//! graphql_object!(Mutation: Context |&self| {
+//!     field upload_size(&executor, file: Upload) -> Option<i32> {
+//!         executor.context().uploads.get(&file).map(|f| f.content().len() as i32)
+//!     }
+//! });
+//! # fn main() {}
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use serde_json::{self, Value as Json};
+
+use http::GraphQLBatchRequest;
+use types::upload::Upload;
+
+/// A file sent as part of a multipart request
+#[derive(Debug)]
+pub struct UploadedFile {
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: Vec<u8>,
+}
+
+impl UploadedFile {
+    /// The name of the file on the client, if it was provided
+    pub fn filename(&self) -> Option<&str> {
+        self.filename.as_ref().map(|s| s.as_str())
+    }
+
+    /// The media type of the file, if it was provided
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_ref().map(|s| s.as_str())
+    }
+
+    /// The contents of the file
+    pub fn content(&self) -> &[u8] {
+        &self.content
+    }
+
+    /// Take ownership of the contents of the file
+    pub fn into_content(self) -> Vec<u8> {
+        self.content
+    }
+}
+
+/// The files sent as part of a multipart request, by field name
+#[derive(Debug, Default)]
+pub struct UploadedFiles {
+    files: HashMap<String, UploadedFile>,
+}
+
+impl UploadedFiles {
+    /// An empty set of files, for requests that weren't multipart
+    pub fn new() -> UploadedFiles {
+        UploadedFiles::default()
+    }
+
+    /// Look up the file an `Upload` argument refers to
+    pub fn get(&self, upload: &Upload) -> Option<&UploadedFile> {
+        self.files.get(upload.key())
+    }
+
+    /// Remove the file an `Upload` argument refers to, taking ownership of it
+    pub fn take(&mut self, upload: &Upload) -> Option<UploadedFile> {
+        self.files.remove(upload.key())
+    }
+
+    /// The number of files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Were there no files in the request?
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+}
+
+/// Error parsing a multipart request
+#[derive(Debug)]
+pub enum MultipartError {
+    /// The content type is not `multipart/form-data` with a boundary
+    InvalidContentType,
+    /// The body is not valid `multipart/form-data`
+    Malformed,
+    /// A required field, `operations` or `map`, is missing
+    MissingField(&'static str),
+    /// The `operations` or `map` field is not valid JSON of the right shape
+    Json(serde_json::Error),
+    /// The `map` refers to a file that wasn't sent
+    MissingFile(String),
+    /// The `map` contains a path that doesn't exist in the operations
+    InvalidPath(String),
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MultipartError::InvalidContentType => write!(f, "Expected multipart/form-data with a boundary"),
+            MultipartError::Malformed => write!(f, "Malformed multipart body"),
+            MultipartError::MissingField(name) => write!(f, "Missing multipart field \"{}\"", name),
+            MultipartError::Json(ref err) => write!(f, "Invalid JSON in multipart field: {}", err),
+            MultipartError::MissingFile(ref key) => write!(f, "Missing file \"{}\"", key),
+            MultipartError::InvalidPath(ref path) => write!(f, "Invalid file path \"{}\"", path),
+        }
+    }
+}
+
+impl Error for MultipartError {
+    fn description(&self) -> &str {
+        match *self {
+            MultipartError::InvalidContentType => "Invalid content type",
+            MultipartError::Malformed => "Malformed multipart body",
+            MultipartError::MissingField(_) => "Missing multipart field",
+            MultipartError::Json(_) => "Invalid JSON in multipart field",
+            MultipartError::MissingFile(_) => "Missing file",
+            MultipartError::InvalidPath(_) => "Invalid file path",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            MultipartError::Json(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Is this the content type of a multipart request?
+pub fn is_multipart(content_type: &str) -> bool {
+    media_type(content_type).eq_ignore_ascii_case("multipart/form-data")
+}
+
+/// Parse the body of a multipart request
+///
+/// `content_type` is the value of the request's `Content-Type` header, which
+/// contains the boundary between the fields.
+pub fn parse_request(content_type: &str, body: &[u8])
+    -> Result<(GraphQLBatchRequest, UploadedFiles), MultipartError>
+{
+    if !is_multipart(content_type) {
+        return Err(MultipartError::InvalidContentType);
+    }
+    let boundary = try!(header_params(content_type).into_iter()
+        .find(|&(ref name, _)| name.eq_ignore_ascii_case("boundary"))
+        .map(|(_, value)| value)
+        .ok_or(MultipartError::InvalidContentType));
+
+    let mut operations = None;
+    let mut map = None;
+    let mut files = HashMap::new();
+
+    for part in try!(split_parts(body, &boundary)) {
+        let part = try!(parse_part(part));
+
+        match (part.name.as_str(), part.filename.is_some()) {
+            ("operations", false) => operations = Some(try!(parse_json(&part.content))),
+            ("map", false) => map = Some(try!(parse_json(&part.content))),
+            _ => {
+                files.insert(part.name, UploadedFile {
+                    filename: part.filename,
+                    content_type: part.content_type,
+                    content: part.content,
+                });
+            },
+        }
+    }
+
+    let mut operations = try!(operations.ok_or(MultipartError::MissingField("operations")));
+    let map: HashMap<String, Vec<String>> = try!(
+        serde_json::from_value(try!(map.ok_or(MultipartError::MissingField("map"))))
+            .map_err(MultipartError::Json));
+
+    for (key, paths) in map {
+        if !files.contains_key(&key) {
+            return Err(MultipartError::MissingFile(key));
+        }
+
+        for path in paths {
+            match lookup_mut(&mut operations, &path) {
+                Some(value) => *value = Json::String(Upload::placeholder(&key)),
+                None => return Err(MultipartError::InvalidPath(path)),
+            }
+        }
+    }
+
+    let request = try!(serde_json::from_value(operations).map_err(MultipartError::Json));
+
+    Ok((request, UploadedFiles { files: files }))
+}
+
+struct Part {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    content: Vec<u8>,
+}
+
+fn split_parts<'a>(body: &'a [u8], boundary: &str) -> Result<Vec<&'a [u8]>, MultipartError> {
+    let delimiter = format!("\r\n--{}", boundary).into_bytes();
+
+    // The first delimiter isn't preceded by a line break if there's no preamble
+    let mut rest = if body.starts_with(&delimiter[2..]) {
+        &body[delimiter.len() - 2..]
+    } else {
+        match find(body, &delimiter) {
+            Some(pos) => &body[pos + delimiter.len()..],
+            None => return Err(MultipartError::Malformed),
+        }
+    };
+
+    let mut parts = Vec::new();
+
+    loop {
+        if rest.starts_with(b"--") {
+            return Ok(parts);
+        }
+        if !rest.starts_with(b"\r\n") {
+            return Err(MultipartError::Malformed);
+        }
+
+        let end = try!(find(rest, &delimiter).ok_or(MultipartError::Malformed));
+        parts.push(&rest[2..end]);
+        rest = &rest[end + delimiter.len()..];
+    }
+}
+
+fn parse_part(part: &[u8]) -> Result<Part, MultipartError> {
+    let header_end = try!(find(part, b"\r\n\r\n").ok_or(MultipartError::Malformed));
+    let headers = try!(str::from_utf8(&part[..header_end]).map_err(|_| MultipartError::Malformed));
+
+    let mut name = None;
+    let mut filename = None;
+    let mut content_type = None;
+
+    for line in headers.split("\r\n") {
+        let colon = try!(line.find(':').ok_or(MultipartError::Malformed));
+        let (header, value) = (line[..colon].trim(), line[colon + 1..].trim());
+
+        if header.eq_ignore_ascii_case("content-disposition") {
+            for (param, value) in header_params(value) {
+                if param.eq_ignore_ascii_case("name") {
+                    name = Some(value);
+                } else if param.eq_ignore_ascii_case("filename") {
+                    filename = Some(value);
+                }
+            }
+        } else if header.eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.to_owned());
+        }
+    }
+
+    Ok(Part {
+        name: try!(name.ok_or(MultipartError::Malformed)),
+        filename: filename,
+        content_type: content_type,
+        content: part[header_end + 4..].to_vec(),
+    })
+}
+
+fn parse_json(content: &[u8]) -> Result<Json, MultipartError> {
+    serde_json::from_slice(content).map_err(MultipartError::Json)
+}
+
+// Paths are dot-separated object keys and list indices, e.g.
+// `variables.files.0`, or `0.variables.file` for a batch.
+fn lookup_mut<'a>(value: &'a mut Json, path: &str) -> Option<&'a mut Json> {
+    path.split('.').fold(Some(value), |value, segment| match value {
+        Some(&mut Json::Object(ref mut o)) => o.get_mut(segment),
+        Some(&mut Json::Array(ref mut l)) => segment.parse::<usize>().ok().and_then(move |i| l.get_mut(i)),
+        _ => None,
+    })
+}
+
+fn media_type(header: &str) -> &str {
+    header.split(';').next().unwrap_or("").trim()
+}
+
+// Splits the `; name=value` parameters following a header's main value,
+// removing quotes around values.
+fn header_params(header: &str) -> Vec<(String, String)> {
+    let mut params = Vec::new();
+    let mut chars = header.chars().skip_while(|&c| c != ';').peekable();
+
+    while chars.next().is_some() {
+        let name = chars.by_ref()
+            .take_while(|&c| c != '=')
+            .collect::<String>();
+        while chars.peek() == Some(&' ') {
+            chars.next();
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => value.extend(chars.next()),
+                    c => value.push(c),
+                }
+            }
+            while chars.peek().map_or(false, |&c| c != ';') {
+                chars.next();
+            }
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c == ';' {
+                    break;
+                }
+                value.push(c);
+                chars.next();
+            }
+        }
+
+        params.push((name.trim().to_owned(), value.trim().to_owned()));
+    }
+
+    params
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::Context;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use types::upload::Upload;
+
+    use super::{parse_request, MultipartError, UploadedFiles};
+
+    const CONTENT_TYPE: &'static str = "multipart/form-data; boundary=\"XyZ\"";
+
+    fn body(operations: &str, map: &str) -> Vec<u8> {
+        format!(
+            concat!(
+                "preamble\r\n",
+                "--XyZ\r\n",
+                "Content-Disposition: form-data; name=\"operations\"\r\n\r\n",
+                "{}\r\n",
+                "--XyZ\r\n",
+                "Content-Disposition: form-data; name=\"map\"\r\n\r\n",
+                "{}\r\n",
+                "--XyZ\r\n",
+                "Content-Disposition: form-data; name=\"0\"; filename=\"a; b.txt\"\r\n",
+                "Content-Type: text/plain\r\n\r\n",
+                "Hello,\r\nworld\r\n",
+                "--XyZ\r\n",
+                "Content-Disposition: form-data; name=\"1\"; filename=\"empty\"\r\n\r\n",
+                "\r\n",
+                "--XyZ--\r\n"),
+            operations, map).into_bytes()
+    }
+
+    struct Database {
+        uploads: UploadedFiles,
+    }
+
+    impl Context for Database {}
+
+    struct Query;
+
+    graphql_object!(Query: Database |&self| {
+        field sizes(&executor, files: Vec<Upload>) -> Vec<Option<i32>> {
+            files.iter()
+                .map(|f| executor.context().uploads.get(f).map(|f| f.content().len() as i32))
+                .collect()
+        }
+    });
+
+    #[test]
+    fn test_parse_request() {
+        let (_, uploads) = parse_request(
+            CONTENT_TYPE,
+            &body(
+                r#"{"query": "query($files: [Upload!]!) { sizes(files: $files) }", "variables": {"files": [null, null]}}"#,
+                r#"{"0": ["variables.files.0"], "1": ["variables.files.1"]}"#))
+            .expect("Valid multipart request");
+
+        assert_eq!(uploads.len(), 2);
+
+        let file = uploads.files.get("0").unwrap();
+        assert_eq!(file.filename(), Some("a; b.txt"));
+        assert_eq!(file.content_type(), Some("text/plain"));
+        assert_eq!(file.content(), b"Hello,\r\nworld");
+
+        let empty = uploads.files.get("1").unwrap();
+        assert_eq!(empty.content_type(), None);
+        assert_eq!(empty.content(), b"");
+    }
+
+    #[test]
+    fn test_execute_with_uploads() {
+        let (request, uploads) = parse_request(
+            CONTENT_TYPE,
+            &body(
+                r#"[{"query": "query($files: [Upload!]!) { sizes(files: $files) }", "variables": {"files": [null, null]}}]"#,
+                r#"{"0": ["0.variables.files.0"], "1": ["0.variables.files.1"]}"#))
+            .expect("Valid multipart request");
+
+        let schema = RootNode::new(Query, EmptyMutation::<Database>::new());
+        let context = Database { uploads: uploads };
+
+        let response = request.execute(&schema, &context);
+        assert!(response.is_ok());
+
+        assert_eq!(
+            ::serde_json::to_string(&response).unwrap(),
+            r#"[{"data":{"sizes":[13,0]}}]"#);
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        match parse_request("multipart/form-data", b"") {
+            Err(MultipartError::InvalidContentType) => (),
+            r => panic!("Unexpected result {:?}", r.map(|_| ())),
+        }
+
+        match parse_request(CONTENT_TYPE, b"--XyZ\r\nContent-Disposition: form-data; name=\"map\"\r\n\r\n{}") {
+            Err(MultipartError::Malformed) => (),
+            r => panic!("Unexpected result {:?}", r.map(|_| ())),
+        }
+
+        match parse_request(CONTENT_TYPE, &body(r#"{"query": "{ a }"}"#, r#"{"2": ["variables.file"]}"#)) {
+            Err(MultipartError::MissingFile(ref key)) if key == "2" => (),
+            r => panic!("Unexpected result {:?}", r.map(|_| ())),
+        }
+
+        match parse_request(CONTENT_TYPE, &body(r#"{"query": "{ a }"}"#, r#"{"0": ["variables.file"]}"#)) {
+            Err(MultipartError::InvalidPath(ref path)) if path == "variables.file" => (),
+            r => panic!("Unexpected result {:?}", r.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! The [GraphQL over HTTP](https://github.com/graphql/graphql-over-http)
+//! specification
+//!
+//! Integrations following the specification answer with the
+//! `application/graphql-response+json` media type when the client accepts it.
+//! With that media type, requests that can't be executed at all, e.g. because
+//! the document is invalid, get a 400 status, while responses containing
+//! field errors are still successful. With plain `application/json`, every
+//! executed request gets a 200 status.
+//!
+//! ```rust
+//! use juniper::http::over_http::MediaType;
+//!
+//! let media_type = MediaType::negotiate(Some("application/json;q=0.9, */*;q=0.8"));
+//! assert_eq!(media_type, Some(MediaType::Json));
+//!
+//! assert_eq!(MediaType::negotiate(Some("text/html")), None);
+//! ```
+
+use http::GraphQLBatchResponse;
+
+/// The methods a GraphQL endpoint accepts, for the `Allow` header of 405
+/// responses
+pub const ALLOWED_METHODS: &'static str = "GET, POST";
+
+/// Does a GraphQL endpoint accept requests with the given method?
+pub fn is_allowed_method(method: &str) -> bool {
+    method == "GET" || method == "POST"
+}
+
+/// The media type of a response
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MediaType {
+    /// `application/graphql-response+json`
+    GraphQLResponseJson,
+    /// `application/json`
+    Json,
+}
+
+impl MediaType {
+    /// The value of the `Content-Type` header
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            MediaType::GraphQLResponseJson => "application/graphql-response+json",
+            MediaType::Json => "application/json",
+        }
+    }
+
+    /// Pick the media type of the response from the `Accept` header
+    ///
+    /// Clients that don't send the header get `application/json`. Returns
+    /// `None` if the client accepts neither media type, which should be
+    /// answered with a 406 status.
+    pub fn negotiate(accept: Option<&str>) -> Option<MediaType> {
+        let accept = match accept {
+            Some(accept) => accept,
+            None => return Some(MediaType::Json),
+        };
+
+        let preferred = quality(accept, MediaType::GraphQLResponseJson);
+        let json = quality(accept, MediaType::Json);
+
+        if preferred > 0.0 && preferred >= json {
+            Some(MediaType::GraphQLResponseJson)
+        } else if json > 0.0 {
+            Some(MediaType::Json)
+        } else {
+            None
+        }
+    }
+
+    /// The HTTP status of a response sent as this media type
+    pub fn status(&self, response: &GraphQLBatchResponse) -> u16 {
+        match *self {
+            MediaType::GraphQLResponseJson => response.status_code(),
+            MediaType::Json => 200,
+        }
+    }
+}
+
+// The quality of the most specific media range matching the media type, or
+// zero if none matches
+fn quality(accept: &str, media_type: MediaType) -> f32 {
+    let mut best: Option<(u8, f32)> = None;
+
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let range = params.next().unwrap_or("").trim().to_ascii_lowercase();
+
+        // Both media types are `application/...`
+        let specificity = match &*range {
+            r if r == media_type.as_str() => 2,
+            "application/*" => 1,
+            "*/*" => 0,
+            _ => continue,
+        };
+
+        let q = params
+            .filter_map(|param| {
+                let mut parts = param.splitn(2, '=');
+                match (parts.next().map(str::trim), parts.next()) {
+                    (Some("q"), Some(value)) => value.trim().parse::<f32>().ok(),
+                    _ => None,
+                }
+            })
+            .next()
+            .unwrap_or(1.0);
+
+        if best.map_or(true, |(s, _)| specificity > s) {
+            best = Some((specificity, q));
+        }
+    }
+
+    best.map_or(0.0, |(_, q)| q)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MediaType;
+
+    #[test]
+    fn negotiates_media_type() {
+        assert_eq!(MediaType::negotiate(None), Some(MediaType::Json));
+        assert_eq!(MediaType::negotiate(Some("*/*")), Some(MediaType::GraphQLResponseJson));
+        assert_eq!(
+            MediaType::negotiate(Some("application/graphql-response+json, application/json;q=0.9")),
+            Some(MediaType::GraphQLResponseJson));
+        assert_eq!(
+            MediaType::negotiate(Some("application/json, application/graphql-response+json;q=0.5")),
+            Some(MediaType::Json));
+        assert_eq!(
+            MediaType::negotiate(Some("Application/JSON; charset=utf-8")),
+            Some(MediaType::Json));
+        assert_eq!(
+            MediaType::negotiate(Some("application/*, application/graphql-response+json;q=0")),
+            Some(MediaType::Json));
+        assert_eq!(MediaType::negotiate(Some("text/html, */*;q=0")), None);
+    }
+}
@@ -0,0 +1,86 @@
+//! Utility module to generate a GraphQL Playground interface
+
+/// Generate the HTML source to show a GraphQL Playground interface
+///
+/// The subscriptions endpoint is the URL of a WebSocket endpoint, see the
+/// `websocket` module. Without one, Playground only sends queries and
+/// mutations to `graphql_endpoint_url`.
+pub fn playground_source(graphql_endpoint_url: &str, subscriptions_endpoint_url: Option<&str>) -> String {
+    let source = PlaygroundSource::new(graphql_endpoint_url);
+
+    match subscriptions_endpoint_url {
+        Some(url) => source.subscriptions_url(url).render(),
+        None => source.render(),
+    }
+}
+
+/// Builder for a customized GraphQL Playground interface
+pub struct PlaygroundSource {
+    graphql_url: String,
+    subscriptions_url: Option<String>,
+    nonce: Option<String>,
+}
+
+impl PlaygroundSource {
+    /// Start building a Playground interface targeting the specified URL
+    pub fn new(graphql_endpoint_url: &str) -> PlaygroundSource {
+        PlaygroundSource {
+            graphql_url: graphql_endpoint_url.to_owned(),
+            subscriptions_url: None,
+            nonce: None,
+        }
+    }
+
+    /// Send subscriptions to the given WebSocket URL
+    pub fn subscriptions_url(mut self, url: &str) -> PlaygroundSource {
+        self.subscriptions_url = Some(url.to_owned());
+        self
+    }
+
+    /// Mark the inline script with a Content Security Policy nonce, see
+    /// `GraphiQLSource::nonce`
+    pub fn nonce(mut self, nonce: &str) -> PlaygroundSource {
+        self.nonce = Some(nonce.to_owned());
+        self
+    }
+
+    /// Generate the HTML source
+    pub fn render(&self) -> String {
+        let subscriptions_endpoint = match self.subscriptions_url {
+            Some(ref url) => format!("'{}'", url),
+            None => "null".to_owned(),
+        };
+
+        let html = format!(r#"
+<!DOCTYPE html>
+<html>
+<head>
+    <meta charset="utf-8">
+    <meta name="viewport" content="user-scalable=no, initial-scale=1.0, minimum-scale=1.0, maximum-scale=1.0, minimal-ui">
+    <title>GraphQL Playground</title>
+    <link rel="stylesheet" href="//cdn.jsdelivr.net/npm/graphql-playground-react/build/static/css/index.css">
+    <link rel="shortcut icon" href="//cdn.jsdelivr.net/npm/graphql-playground-react/build/favicon.png">
+    <script src="//cdn.jsdelivr.net/npm/graphql-playground-react/build/static/js/middleware.js"></script>
+</head>
+<body>
+    <div id="root"></div>
+    <script>
+        window.addEventListener('load', function (event) {{
+            GraphQLPlayground.init(document.getElementById('root'), {{
+                endpoint: '{graphql_url}',
+                subscriptionEndpoint: {subscriptions_endpoint},
+            }});
+        }});
+    </script>
+</body>
+</html>
+"#,
+            graphql_url = self.graphql_url,
+            subscriptions_endpoint = subscriptions_endpoint);
+
+        match self.nonce {
+            Some(ref nonce) => ::graphiql::add_nonce(&html, nonce),
+            None => html,
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! Recording requests and replaying them against a schema
+//!
+//! A `Recorder` keeps the requests a server executes together with the
+//! responses it sent. Replaying the recordings against a changed schema shows
+//! where its responses differ, e.g. after refactoring resolvers:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! use juniper::{EmptyMutation, RootNode};
+//! use juniper::http::GraphQLRequest;
+//! use juniper::http::recording::{replay, Recorder};
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field greeting() -> &str { "Hello" }
+//! });
+//!
+//! struct Refactored;
+//!
+//! graphql_object!(Refactored: () as "Query" |&self| {
+//!     field greeting() -> &str { "Hello!" }
+//! });
+//!
+//! # fn main() {
+//! let recorder = Recorder::new();
+//! let request = GraphQLRequest::new("{ greeting }".to_owned(), None, None);
+//! recorder.execute(&request, &RootNode::new(Query, EmptyMutation::<()>::new()), &());
+//!
+//! let recordings = recorder.take();
+//! assert!(replay(&recordings, &RootNode::new(Query, EmptyMutation::<()>::new()), &()).is_empty());
+//!
+//! let mismatches = replay(&recordings, &RootNode::new(Refactored, EmptyMutation::<()>::new()), &());
+//! assert_eq!(mismatches[0].differences[0].to_string(), r#"/data/greeting: expected "Hello", found "Hello!""#);
+//! # }
+//! ```
+//!
+//! `GraphQLHandler::with_recorder` records every request a handler executes.
+//! `Recording`s serialize to and from JSON, so they can be collected on a
+//! server and replayed in tests. Requests that depend on the context, such as
+//! the current user, have to be replayed with an equivalent context.
+
+use std::fmt;
+use std::sync::Mutex;
+
+use serde_json;
+
+use ::{GraphQLType, RootNode};
+use ast::InputValue;
+use http::{GraphQLBatchRequest, GraphQLBatchResponse, GraphQLRequest, GraphQLResponse};
+
+/// A request and the response it was answered with
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Recording {
+    /// The query document of the request
+    pub query: String,
+    /// The name of the operation to execute, if the request names one
+    #[serde(rename = "operationName")]
+    pub operation_name: Option<String>,
+    /// The variables of the request
+    pub variables: Option<InputValue>,
+    /// The response, as it was serialized
+    pub response: serde_json::Value,
+}
+
+/// Collects the requests it's given with their responses
+#[derive(Debug, Default)]
+pub struct Recorder {
+    recordings: Mutex<Vec<Recording>>,
+}
+
+/// A recording whose replayed response differs from the recorded one
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    /// The index of the recording in the replayed slice
+    pub index: usize,
+    /// Where the responses differ, in order
+    pub differences: Vec<Difference>,
+}
+
+/// A value that differs between the recorded and replayed response
+#[derive(Clone, Debug, PartialEq)]
+pub struct Difference {
+    /// The JSON pointer to the value in the response, e.g. `/data/hero/name`
+    pub path: String,
+    /// The recorded value, or `None` if it's only in the replayed response
+    pub expected: Option<serde_json::Value>,
+    /// The replayed value, or `None` if it's only in the recorded response
+    pub actual: Option<serde_json::Value>,
+}
+
+impl Recorder {
+    /// Construct a recorder without any recordings
+    pub fn new() -> Recorder {
+        Recorder::default()
+    }
+
+    /// Record a request with the response it was answered with
+    ///
+    /// Responses that can't be serialized aren't recorded.
+    pub fn record(&self, request: &GraphQLRequest, response: &GraphQLResponse) {
+        let response = match serde_json::to_value(response) {
+            Ok(response) => response,
+            Err(_) => return,
+        };
+
+        self.lock().push(Recording {
+            query: request.query.clone(),
+            operation_name: request.operation_name.clone(),
+            variables: request.variables.clone(),
+            response: response,
+        });
+    }
+
+    /// Record every request of a batch with its response
+    pub fn record_batch(&self, request: &GraphQLBatchRequest, response: &GraphQLBatchResponse) {
+        for (request, response) in request.requests().iter().zip(response.responses()) {
+            self.record(request, response);
+        }
+    }
+
+    /// Execute a request and record it with its response
+    pub fn execute<'a, CtxT, QueryT, MutationT>(
+        &self,
+        request: &'a GraphQLRequest,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+    )
+        -> GraphQLResponse<'a>
+        where QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+    {
+        let response = request.execute(root_node, context);
+        self.record(request, &response);
+        response
+    }
+
+    /// The recordings so far, in the order they were recorded
+    pub fn recordings(&self) -> Vec<Recording> {
+        self.lock().clone()
+    }
+
+    /// Take the recordings so far, leaving the recorder empty
+    pub fn take(&self) -> Vec<Recording> {
+        ::std::mem::replace(&mut *self.lock(), Vec::new())
+    }
+
+    fn lock<'r>(&'r self) -> ::std::sync::MutexGuard<'r, Vec<Recording>> {
+        self.recordings.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+impl Recording {
+    /// The request that was recorded
+    pub fn request(&self) -> GraphQLRequest {
+        GraphQLRequest::new(self.query.clone(), self.operation_name.clone(), self.variables.clone())
+    }
+}
+
+/// Execute recorded requests again, and compare their responses to the
+/// recorded ones
+///
+/// Returns the recordings whose responses differ, with the differences.
+pub fn replay<CtxT, QueryT, MutationT>(
+    recordings: &[Recording],
+    root_node: &RootNode<QueryT, MutationT>,
+    context: &CtxT,
+)
+    -> Vec<Mismatch>
+    where QueryT: GraphQLType<Context=CtxT>,
+          MutationT: GraphQLType<Context=CtxT>,
+{
+    recordings.iter().enumerate()
+        .filter_map(|(index, recording)| {
+            let request = recording.request();
+            let response = request.execute(root_node, context);
+
+            let mut differences = Vec::new();
+            match serde_json::to_value(&response) {
+                Ok(actual) => diff(&mut differences, String::new(), Some(&recording.response), Some(&actual)),
+                Err(_) => diff(&mut differences, String::new(), Some(&recording.response), None),
+            }
+
+            if differences.is_empty() {
+                None
+            } else {
+                Some(Mismatch { index: index, differences: differences })
+            }
+        })
+        .collect()
+}
+
+fn diff(
+    differences: &mut Vec<Difference>,
+    path: String,
+    expected: Option<&serde_json::Value>,
+    actual: Option<&serde_json::Value>,
+) {
+    use serde_json::Value::{Array, Object};
+
+    match (expected, actual) {
+        (Some(&Object(ref expected)), Some(&Object(ref actual))) => {
+            for (key, value) in expected {
+                diff(differences, child_path(&path, key), Some(value), actual.get(key));
+            }
+
+            for (key, value) in actual.iter().filter(|&(key, _)| !expected.contains_key(key)) {
+                diff(differences, child_path(&path, key), None, Some(value));
+            }
+        },
+        (Some(&Array(ref expected)), Some(&Array(ref actual))) => {
+            for i in 0..expected.len().max(actual.len()) {
+                diff(differences, child_path(&path, &i.to_string()), expected.get(i), actual.get(i));
+            }
+        },
+        (expected, actual) if expected != actual => {
+            differences.push(Difference {
+                path: if path.is_empty() { "/".to_owned() } else { path },
+                expected: expected.cloned(),
+                actual: actual.cloned(),
+            });
+        },
+        _ => {},
+    }
+}
+
+// Escaped as in RFC 6901
+fn child_path(path: &str, key: &str) -> String {
+    format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"))
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.expected, &self.actual) {
+            (&Some(ref expected), &Some(ref actual)) =>
+                write!(f, "{}: expected {}, found {}", self.path, expected, actual),
+            (&Some(ref expected), &None) => write!(f, "{}: expected {}, found nothing", self.path, expected),
+            (&None, &Some(ref actual)) => write!(f, "{}: expected nothing, found {}", self.path, actual),
+            (&None, &None) => write!(f, "{}", self.path),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use http::GraphQLRequest;
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::{replay, Difference, Recorder, Recording};
+
+    fn json(source: &str) -> serde_json::Value {
+        serde_json::from_str(source).unwrap()
+    }
+
+    #[test]
+    fn replays_recordings_and_reports_differences() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let recorder = Recorder::new();
+
+        let request = GraphQLRequest::new(
+            "query Hero { hero { name friends { name } } }".to_owned(), Some("Hero".to_owned()), None);
+        recorder.execute(&request, &schema, &Database::new());
+        recorder.execute(&GraphQLRequest::new("{ nope }".to_owned(), None, None), &schema, &Database::new());
+
+        let recordings = recorder.recordings();
+        assert_eq!(recordings.len(), 2);
+        assert_eq!(recordings[0].operation_name, Some("Hero".to_owned()));
+        assert!(replay(&recordings, &schema, &Database::new()).is_empty());
+
+        let serialized = serde_json::to_string(&recordings[0]).unwrap();
+        let mut changed: Recording = serde_json::from_str(&serialized).unwrap();
+        changed.response["data"]["hero"]["name"] = json(r#""R2""#);
+        changed.response["data"]["hero"]["friends"].as_array_mut().unwrap().pop();
+        changed.response["data"]["hero"]["friends/"] = json("null");
+
+        let mismatches = replay(&[recordings[1].clone(), changed], &schema, &Database::new());
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].index, 1);
+        assert_eq!(mismatches[0].differences, vec![
+            Difference {
+                path: "/data/hero/friends/2".to_owned(),
+                expected: None,
+                actual: Some(json(r#"{"name": "Leia Organa"}"#)),
+            },
+            Difference {
+                path: "/data/hero/friends~1".to_owned(),
+                expected: Some(json("null")),
+                actual: None,
+            },
+            Difference {
+                path: "/data/hero/name".to_owned(),
+                expected: Some(json(r#""R2""#)),
+                actual: Some(json(r#""R2-D2""#)),
+            },
+        ]);
+
+        assert!(recorder.take().len() == 2 && recorder.recordings().is_empty());
+    }
+}
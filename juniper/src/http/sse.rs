@@ -0,0 +1,158 @@
+//! Server-sent events, for clients streaming results over plain HTTP
+//!
+//! An `EventStream` turns the payloads of an operation into chunks of a
+//! `text/event-stream` body, following the distinct connections mode of the
+//! [GraphQL over SSE](https://github.com/enisdenjo/graphql-sse) protocol: every
+//! payload is sent as a `next` event, and the stream ends with a `complete`
+//! event. The payloads can be anything serializable, e.g. `GraphQLResponse`s or
+//! incremental delivery patches.
+//!
+//! Each chunk can be written to the response as soon as it is produced, which
+//! works with any library that can stream a response body:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! # use juniper::{RootNode, EmptyMutation};
+//! # use juniper::http::GraphQLRequest;
+//! # use juniper::http::sse::{self, EventStream};
+//! # struct Query;
+//! # graphql_object!(Query: () |&self| {
+//! #     field hello() -> &str { "world" }
+//! # });
+//! # fn main() {
+//! # let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+//! let request = GraphQLRequest::new("{ hello }".to_owned(), None, None);
+//! let response = request.execute(&schema, &());
+//!
+//! let body: String = EventStream::new(Some(response)).collect();
+//!
+//! assert_eq!(sse::CONTENT_TYPE, "text/event-stream");
+//! assert_eq!(
+//!     body,
+//!     "event: next\ndata: {\"data\":{\"hello\":\"world\"}}\n\nevent: complete\ndata: \n\n");
+//! # }
+//! ```
+
+use serde::ser::Serialize;
+use serde_json;
+
+use ::GraphQLErrorCode;
+
+/// The media type of an event stream response
+pub const CONTENT_TYPE: &'static str = "text/event-stream";
+
+/// A comment line, sent periodically to keep idle connections open
+pub const KEEP_ALIVE: &'static str = ":\n\n";
+
+/// Format a single event
+///
+/// Every line of `data` is sent in a `data:` field of its own, so the client
+/// receives it unchanged.
+pub fn event(name: &str, data: &str) -> String {
+    let mut event = format!("event: {}\n", name);
+
+    for line in data.split('\n') {
+        let line = if line.ends_with('\r') { &line[..line.len() - 1] } else { line };
+        event.push_str("data: ");
+        event.push_str(line);
+        event.push('\n');
+    }
+
+    event.push('\n');
+    event
+}
+
+/// The events of a stream of payloads, as chunks of the response body
+pub struct EventStream<I> {
+    payloads: I,
+    completed: bool,
+}
+
+impl<I> EventStream<I> where I: Iterator, I::Item: Serialize {
+    /// Stream the given payloads, ending with a `complete` event
+    pub fn new<T>(payloads: T) -> EventStream<I> where T: IntoIterator<IntoIter=I, Item=I::Item> {
+        EventStream {
+            payloads: payloads.into_iter(),
+            completed: false,
+        }
+    }
+}
+
+impl<I> Iterator for EventStream<I> where I: Iterator, I::Item: Serialize {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.completed {
+            return None;
+        }
+
+        match self.payloads.next() {
+            Some(payload) => Some(match serde_json::to_string(&payload) {
+                Ok(data) => event("next", &data),
+                Err(err) => event("next", &error_payload(&err.to_string())),
+            }),
+            None => {
+                self.completed = true;
+                Some(event("complete", ""))
+            },
+        }
+    }
+}
+
+fn error_payload(message: &str) -> String {
+    let code = serde_json::Value::String(GraphQLErrorCode::InternalServerError.as_str().to_owned());
+    let mut error = json_object("message", serde_json::Value::String(message.to_owned()));
+    if let Some(error) = error.as_object_mut() {
+        error.insert("extensions".to_owned(), json_object("code", code));
+    }
+    let errors = json_object("errors", serde_json::Value::Array(vec![error]));
+    errors.to_string()
+}
+
+fn json_object(key: &str, value: serde_json::Value) -> serde_json::Value {
+    let mut object = serde_json::Map::new();
+    object.insert(key.to_owned(), value);
+    serde_json::Value::Object(object)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use value::Value;
+
+    use super::{event, EventStream};
+
+    #[test]
+    fn splits_multiline_data() {
+        assert_eq!(event("next", "a\r\nb\nc"), "event: next\ndata: a\ndata: b\ndata: c\n\n");
+    }
+
+    #[test]
+    fn streams_payloads() {
+        let payloads: Vec<Value> = vec![
+            Value::object(vec![("a", Value::int(1))].into_iter().collect()),
+            Value::list(vec![Value::string("x\ny")]),
+        ];
+
+        let chunks: Vec<String> = EventStream::new(payloads).collect();
+
+        assert_eq!(chunks, vec![
+            "event: next\ndata: {\"a\":1}\n\n",
+            "event: next\ndata: [\"x\\ny\"]\n\n",
+            "event: complete\ndata: \n\n",
+        ]);
+    }
+
+    #[test]
+    fn reports_unserializable_payloads() {
+        let mut payload = BTreeMap::new();
+        payload.insert(vec![1], 2);
+
+        let chunks: Vec<String> = EventStream::new(vec![payload]).collect();
+
+        assert!(chunks[0].starts_with(
+            "event: next\ndata: {\"errors\":[{\"extensions\":{\"code\":\"INTERNAL_SERVER_ERROR\"},\"message\":"));
+        assert_eq!(chunks[1], "event: complete\ndata: \n\n");
+    }
+}
@@ -0,0 +1,723 @@
+//! The GraphQL over WebSocket protocols, independent of any WebSocket library
+//!
+//! Two protocols are supported, selected by the subprotocol the client asks
+//! for during the handshake:
+//!
+//! * `graphql-transport-ws`, from the
+//!   [graphql-ws](https://github.com/enisdenjo/graphql-ws) library, and
+//! * `graphql-ws`, the older protocol of
+//!   [subscriptions-transport-ws](https://github.com/apollographql/subscriptions-transport-ws).
+//!
+//! A `Connection` holds the state of one socket. Integrations feed it every
+//! text message they receive, and it answers through a `MessageSink` wrapping
+//! the socket:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! # use juniper::{RootNode, EmptyMutation};
+//! # use juniper::http::websocket::{Connection, MessageSink, Protocol};
+//! struct Socket {
+//!     sent: Vec<String>,
+//! }
+//!
+//! impl MessageSink for Socket {
+//!     fn send(&mut self, message: String) {
+//!         self.sent.push(message);
+//!     }
+//!
+//!     fn close(&mut self, _code: u16, _reason: &str) {}
+//! }
+//!
+//! # struct Query;
+//! # graphql_object!(Query: () |&self| {
+//! #     field hello() -> &str { "world" }
+//! # });
+//! # fn main() {
+//! # let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+//! let protocol = Protocol::from_subprotocol("graphql-transport-ws").unwrap();
+//! let mut connection = Connection::new(protocol);
+//! let mut socket = Socket { sent: Vec::new() };
+//!
+//! connection.serve(
+//!     vec![
+//!         r#"{"type": "connection_init"}"#,
+//!         r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hello }"}}"#,
+//!     ],
+//!     &schema, &(), &mut socket);
+//!
+//! assert_eq!(socket.sent, vec![
+//!     r#"{"type":"connection_ack"}"#,
+//!     r#"{"id":"1","payload":{"data":{"hello":"world"}},"type":"next"}"#,
+//!     r#"{"id":"1","type":"complete"}"#,
+//! ]);
+//! # }
+//! ```
+//!
+//! Juniper doesn't execute subscription operations yet, so every operation
+//! produces a single result: a `next` (or `data`) message followed by
+//! `complete`. Documents containing a `subscription` are answered with an
+//! `error` message.
+
+use serde_json::{self, Value as Json, Map};
+
+use ::{GraphQLErrorCode, GraphQLType, RootNode};
+use ast::InputValue;
+use http::{GraphQLRequest, GraphQLResponse};
+
+/// The WebSocket subprotocol spoken by a client
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Protocol {
+    /// The `graphql-transport-ws` protocol of the graphql-ws library
+    GraphQLTransportWs,
+    /// The `graphql-ws` protocol of subscriptions-transport-ws
+    SubscriptionsTransportWs,
+}
+
+impl Protocol {
+    /// Look up the protocol for a `Sec-WebSocket-Protocol` value
+    pub fn from_subprotocol(name: &str) -> Option<Protocol> {
+        match name.trim() {
+            "graphql-transport-ws" => Some(Protocol::GraphQLTransportWs),
+            "graphql-ws" => Some(Protocol::SubscriptionsTransportWs),
+            _ => None,
+        }
+    }
+
+    /// Pick the first supported protocol in a comma separated
+    /// `Sec-WebSocket-Protocol` header
+    pub fn negotiate(header: &str) -> Option<Protocol> {
+        header.split(',').filter_map(Protocol::from_subprotocol).next()
+    }
+
+    /// The subprotocol name to send back in the handshake response
+    pub fn subprotocol(&self) -> &'static str {
+        match *self {
+            Protocol::GraphQLTransportWs => "graphql-transport-ws",
+            Protocol::SubscriptionsTransportWs => "graphql-ws",
+        }
+    }
+
+    fn next_message(&self) -> &'static str {
+        match *self {
+            Protocol::GraphQLTransportWs => "next",
+            Protocol::SubscriptionsTransportWs => "data",
+        }
+    }
+}
+
+/// The outgoing half of a WebSocket, implemented by each integration
+pub trait MessageSink {
+    /// Send a text message
+    fn send(&mut self, message: String);
+
+    /// Close the socket with the given close code and reason
+    fn close(&mut self, code: u16, reason: &str);
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum State {
+    AwaitingInit,
+    Ready,
+    Closed,
+}
+
+// A message of either protocol, as sent by the client
+struct ClientMessage {
+    kind: String,
+    id: Option<String>,
+    payload: Option<Json>,
+}
+
+/// The protocol state of a single WebSocket connection
+pub struct Connection {
+    protocol: Protocol,
+    state: State,
+    init_payload: Option<InputValue>,
+}
+
+impl Connection {
+    /// Start a connection that has just completed the handshake
+    pub fn new(protocol: Protocol) -> Connection {
+        Connection {
+            protocol: protocol,
+            state: State::AwaitingInit,
+            init_payload: None,
+        }
+    }
+
+    /// The protocol spoken on this connection
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Has the client sent `connection_init`?
+    pub fn is_initialized(&self) -> bool {
+        self.state == State::Ready
+    }
+
+    /// Has the connection been closed, either by the client or because of a
+    /// protocol error?
+    pub fn is_closed(&self) -> bool {
+        self.state == State::Closed
+    }
+
+    /// The payload of the `connection_init` message
+    ///
+    /// Clients commonly put authentication tokens here, which makes this the
+    /// place to look when building the context for the connection.
+    pub fn init_payload(&self) -> Option<&InputValue> {
+        self.init_payload.as_ref()
+    }
+
+    /// Handle every message of a stream until it ends or the connection is
+    /// closed
+    pub fn serve<I, M, CtxT, QueryT, MutationT, S>(
+        &mut self,
+        messages: I,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+        sink: &mut S,
+    )
+        where I: IntoIterator<Item=M>,
+              M: AsRef<str>,
+              QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+              S: MessageSink,
+    {
+        for message in messages {
+            if self.is_closed() {
+                break;
+            }
+
+            self.handle_message(message.as_ref(), root_node, context, sink);
+        }
+    }
+
+    /// Handle every message of a stream, building the context from the
+    /// payload of `connection_init`
+    ///
+    /// This is where integrations authenticate the connection: the context
+    /// factory is called when the client sends `connection_init`, before the
+    /// connection is acknowledged. Returning an error closes the connection
+    /// with the 4403 close code and the error as the reason, without
+    /// acknowledging it.
+    pub fn serve_with_context_factory<I, M, F, CtxT, QueryT, MutationT, S>(
+        &mut self,
+        messages: I,
+        root_node: &RootNode<QueryT, MutationT>,
+        context_factory: F,
+        sink: &mut S,
+    )
+        where I: IntoIterator<Item=M>,
+              M: AsRef<str>,
+              F: FnOnce(Option<&InputValue>) -> Result<CtxT, String>,
+              QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+              S: MessageSink,
+    {
+        let mut context_factory = Some(context_factory);
+        let mut context = None;
+
+        for message in messages {
+            if self.is_closed() {
+                break;
+            }
+
+            let message = match self.parse(message.as_ref(), sink) {
+                Some(message) => message,
+                None => break,
+            };
+
+            // A second `connection_init` is rejected like any other
+            if message.kind == "connection_init" {
+                if let Some(context_factory) = context_factory.take() {
+                    self.init_with_context_factory(message.payload, context_factory, &mut context, sink);
+                    continue;
+                }
+            }
+
+            self.route(message, root_node, context.as_ref(), sink);
+        }
+    }
+
+    /// Handle a single text message sent by the client
+    pub fn handle_message<CtxT, QueryT, MutationT, S>(
+        &mut self,
+        message: &str,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+        sink: &mut S,
+    )
+        where QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+              S: MessageSink,
+    {
+        if let Some(message) = self.parse(message, sink) {
+            self.route(message, root_node, Some(context), sink);
+        }
+    }
+
+    /// Handle a single text message sent by the client, building the context
+    /// from the payload of `connection_init`
+    ///
+    /// This is `serve_with_context_factory` for integrations that receive
+    /// messages one at a time. The context is kept in `context` between
+    /// messages, and the factory is only called for `connection_init`.
+    pub fn handle_message_with_context_factory<F, CtxT, QueryT, MutationT, S>(
+        &mut self,
+        message: &str,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &mut Option<CtxT>,
+        context_factory: F,
+        sink: &mut S,
+    )
+        where F: FnOnce(Option<&InputValue>) -> Result<CtxT, String>,
+              QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+              S: MessageSink,
+    {
+        let message = match self.parse(message, sink) {
+            Some(message) => message,
+            None => return,
+        };
+
+        if message.kind == "connection_init" && context.is_none() {
+            self.init_with_context_factory(message.payload, context_factory, context, sink);
+        } else {
+            self.route(message, root_node, context.as_ref(), sink);
+        }
+    }
+
+    // Fails the connection and returns `None` if the message isn't valid
+    fn parse<S>(&mut self, message: &str, sink: &mut S) -> Option<ClientMessage> where S: MessageSink {
+        if self.is_closed() {
+            return None;
+        }
+
+        let mut message = match serde_json::from_str::<Json>(message) {
+            Ok(Json::Object(message)) => message,
+            _ => {
+                self.fail(4400, "Invalid message received", sink);
+                return None;
+            },
+        };
+
+        let kind = match message.remove("type") {
+            Some(Json::String(kind)) => kind,
+            _ => {
+                self.fail(4400, "Invalid message received", sink);
+                return None;
+            },
+        };
+        let id = match message.remove("id") {
+            Some(Json::String(id)) => Some(id),
+            None | Some(Json::Null) => None,
+            Some(_) => {
+                self.fail(4400, "Invalid message received", sink);
+                return None;
+            },
+        };
+
+        Some(ClientMessage {
+            kind: kind,
+            id: id,
+            payload: message.remove("payload"),
+        })
+    }
+
+    // Without a context, operations are rejected like before `connection_init`
+    fn route<CtxT, QueryT, MutationT, S>(
+        &mut self,
+        message: ClientMessage,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: Option<&CtxT>,
+        sink: &mut S,
+    )
+        where QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+              S: MessageSink,
+    {
+        let ClientMessage { kind, id, payload } = message;
+
+        match (self.protocol, &*kind) {
+            (_, "connection_init") => if self.accept(payload, sink) {
+                self.acknowledge(sink);
+            },
+            (Protocol::GraphQLTransportWs, "ping") => {
+                let mut pong = Map::new();
+                if let Some(payload) = payload {
+                    pong.insert("payload".to_owned(), payload);
+                }
+                send(sink, "pong", None, pong);
+            },
+            (Protocol::GraphQLTransportWs, "pong") => (),
+            (Protocol::GraphQLTransportWs, "subscribe") |
+            (Protocol::SubscriptionsTransportWs, "start") => {
+                match id {
+                    Some(id) => self.start(&id, payload, root_node, context, sink),
+                    None => self.fail(4400, "Operation without an id", sink),
+                }
+            },
+            // Every operation has completed by the time it could be stopped
+            (Protocol::GraphQLTransportWs, "complete") |
+            (Protocol::SubscriptionsTransportWs, "stop") => (),
+            (Protocol::SubscriptionsTransportWs, "connection_terminate") => {
+                self.state = State::Closed;
+                sink.close(1000, "Normal closure");
+            },
+            _ => self.fail(4400, "Invalid message received", sink),
+        }
+    }
+
+    /// Send a keep-alive message, to be called periodically by integrations
+    /// that want to keep idle connections open
+    pub fn keep_alive<S>(&self, sink: &mut S) where S: MessageSink {
+        if self.state != State::Ready {
+            return;
+        }
+
+        match self.protocol {
+            Protocol::GraphQLTransportWs => send(sink, "ping", None, Map::new()),
+            Protocol::SubscriptionsTransportWs => send(sink, "ka", None, Map::new()),
+        }
+    }
+
+    // Returns whether the connection may be acknowledged
+    fn accept<S>(&mut self, payload: Option<Json>, sink: &mut S) -> bool where S: MessageSink {
+        if self.state == State::Ready {
+            self.fail(4429, "Too many initialisation requests", sink);
+            return false;
+        }
+
+        self.init_payload = match payload {
+            None | Some(Json::Null) => None,
+            Some(payload) => Some(InputValue::from(payload)),
+        };
+        self.state = State::Ready;
+        true
+    }
+
+    fn init_with_context_factory<F, CtxT, S>(
+        &mut self,
+        payload: Option<Json>,
+        context_factory: F,
+        context: &mut Option<CtxT>,
+        sink: &mut S,
+    )
+        where F: FnOnce(Option<&InputValue>) -> Result<CtxT, String>,
+              S: MessageSink,
+    {
+        if !self.accept(payload, sink) {
+            return;
+        }
+
+        match context_factory(self.init_payload()) {
+            Ok(new_context) => {
+                *context = Some(new_context);
+                self.acknowledge(sink);
+            },
+            Err(reason) => self.fail(4403, &reason, sink),
+        }
+    }
+
+    fn acknowledge<S>(&self, sink: &mut S) where S: MessageSink {
+        send(sink, "connection_ack", None, Map::new());
+
+        if self.protocol == Protocol::SubscriptionsTransportWs {
+            send(sink, "ka", None, Map::new());
+        }
+    }
+
+    fn start<CtxT, QueryT, MutationT, S>(
+        &mut self,
+        id: &str,
+        payload: Option<Json>,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: Option<&CtxT>,
+        sink: &mut S,
+    )
+        where QueryT: GraphQLType<Context=CtxT>,
+              MutationT: GraphQLType<Context=CtxT>,
+              S: MessageSink,
+    {
+        let context = match context {
+            Some(context) if self.state == State::Ready => context,
+            _ => return self.fail(4401, "Unauthorized", sink),
+        };
+
+        let request: GraphQLRequest = match payload.map(serde_json::from_value) {
+            Some(Ok(request)) => request,
+            _ => return self.fail(4400, "Invalid operation payload", sink),
+        };
+
+        let response = request.execute(root_node, context);
+
+        match response_payload(&response) {
+            Ok(result) => {
+                let kind = if response.error().is_some() {
+                    "error"
+                } else {
+                    self.protocol.next_message()
+                };
+                send(sink, kind, Some(id), payload_field(result));
+            },
+            Err(err) => send(
+                sink, "error", Some(id), payload_field(errors(&err, GraphQLErrorCode::InternalServerError))),
+        }
+
+        // The graphql-transport-ws protocol ends an operation with its error
+        if response.error().is_none() || self.protocol == Protocol::SubscriptionsTransportWs {
+            send(sink, "complete", Some(id), Map::new());
+        }
+    }
+
+    fn fail<S>(&mut self, code: u16, reason: &str, sink: &mut S) where S: MessageSink {
+        if self.protocol == Protocol::SubscriptionsTransportWs {
+            let mut payload = Map::new();
+            payload.insert("message".to_owned(), Json::String(reason.to_owned()));
+            send(sink, "connection_error", None, payload_field(Json::Object(payload)));
+        }
+
+        self.state = State::Closed;
+        sink.close(code, reason);
+    }
+}
+
+// Results are sent as the usual `{"data": ..., "errors": ...}` response, while
+// request errors become the list of errors the protocols expect.
+fn response_payload(response: &GraphQLResponse) -> Result<Json, serde_json::Error> {
+    if let Some(err) = response.error() {
+        let err = try!(serde_json::to_value(err));
+        return Ok(match err {
+            Json::String(message) => errors(&message, GraphQLErrorCode::OperationResolutionFailure),
+            err => err,
+        });
+    }
+
+    serde_json::to_value(response)
+}
+
+fn errors<T: ToString + ?Sized>(message: &T, code: GraphQLErrorCode) -> Json {
+    let mut extensions = Map::new();
+    extensions.insert("code".to_owned(), Json::String(code.as_str().to_owned()));
+
+    let mut error = Map::new();
+    error.insert("message".to_owned(), Json::String(message.to_string()));
+    error.insert("extensions".to_owned(), Json::Object(extensions));
+    Json::Array(vec![Json::Object(error)])
+}
+
+fn payload_field(payload: Json) -> Map<String, Json> {
+    let mut fields = Map::new();
+    fields.insert("payload".to_owned(), payload);
+    fields
+}
+
+fn send<S>(sink: &mut S, kind: &str, id: Option<&str>, mut fields: Map<String, Json>)
+    where S: MessageSink,
+{
+    fields.insert("type".to_owned(), Json::String(kind.to_owned()));
+    if let Some(id) = id {
+        fields.insert("id".to_owned(), Json::String(id.to_owned()));
+    }
+
+    sink.send(Json::Object(fields).to_string());
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{self, Value as Json};
+
+    use ast::InputValue;
+    use executor::Context;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    use super::{Connection, MessageSink, Protocol};
+
+    #[derive(Default)]
+    struct Socket {
+        sent: Vec<Json>,
+        closed: Option<u16>,
+    }
+
+    impl MessageSink for Socket {
+        fn send(&mut self, message: String) {
+            self.sent.push(serde_json::from_str(&message).unwrap());
+        }
+
+        fn close(&mut self, code: u16, _reason: &str) {
+            self.closed = Some(code);
+        }
+    }
+
+    struct Database;
+
+    impl Context for Database {}
+
+    struct Query;
+
+    graphql_object!(Query: Database |&self| {
+        field hello() -> &str { "world" }
+    });
+
+    fn run(protocol: Protocol, messages: &[&str]) -> (Connection, Socket) {
+        let schema = RootNode::new(Query, EmptyMutation::<Database>::new());
+        let mut connection = Connection::new(protocol);
+        let mut socket = Socket::default();
+
+        connection.serve(messages, &schema, &Database, &mut socket);
+
+        (connection, socket)
+    }
+
+    fn json(s: &str) -> Json {
+        serde_json::from_str(s).unwrap()
+    }
+
+    fn authenticate(payload: Option<&InputValue>) -> Result<Database, String> {
+        let token = payload
+            .and_then(|payload| payload.to_object_value())
+            .and_then(|payload| payload.get("token").and_then(|token| token.as_string_value()));
+
+        match token {
+            Some("abc") => Ok(Database),
+            _ => Err("Invalid token".to_owned()),
+        }
+    }
+
+    #[test]
+    fn builds_context_from_init_payload() {
+        let schema = RootNode::new(Query, EmptyMutation::<Database>::new());
+
+        let mut connection = Connection::new(Protocol::GraphQLTransportWs);
+        let mut socket = Socket::default();
+        connection.serve_with_context_factory(&[
+            r#"{"type": "connection_init", "payload": {"token": "abc"}}"#,
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hello }"}}"#,
+        ], &schema, authenticate, &mut socket);
+
+        assert_eq!(socket.sent[0], json(r#"{"type": "connection_ack"}"#));
+        assert_eq!(socket.sent[1], json(r#"{"type": "next", "id": "1", "payload": {"data": {"hello": "world"}}}"#));
+        assert_eq!(socket.closed, None);
+
+        let mut connection = Connection::new(Protocol::GraphQLTransportWs);
+        let mut socket = Socket::default();
+        connection.serve_with_context_factory(&[
+            r#"{"type": "connection_init", "payload": {"token": "xyz"}}"#,
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hello }"}}"#,
+        ], &schema, authenticate, &mut socket);
+
+        assert!(socket.sent.is_empty());
+        assert_eq!(socket.closed, Some(4403));
+
+        let mut connection = Connection::new(Protocol::SubscriptionsTransportWs);
+        let mut socket = Socket::default();
+        connection.serve_with_context_factory(&[
+            r#"{"type": "connection_init", "payload": {"token": "xyz"}}"#,
+        ], &schema, authenticate, &mut socket);
+
+        assert_eq!(
+            socket.sent,
+            vec![json(r#"{"type": "connection_error", "payload": {"message": "Invalid token"}}"#)]);
+        assert_eq!(socket.closed, Some(4403));
+    }
+
+    #[test]
+    fn builds_context_one_message_at_a_time() {
+        let schema = RootNode::new(Query, EmptyMutation::<Database>::new());
+        let mut connection = Connection::new(Protocol::GraphQLTransportWs);
+        let mut context = None;
+        let mut socket = Socket::default();
+
+        for message in &[
+            r#"{"type": "connection_init", "payload": {"token": "abc"}}"#,
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hello }"}}"#,
+            r#"{"type": "connection_init", "payload": {"token": "abc"}}"#,
+        ] {
+            connection.handle_message_with_context_factory(message, &schema, &mut context, authenticate, &mut socket);
+        }
+
+        assert!(context.is_some());
+        assert_eq!(socket.sent, vec![
+            json(r#"{"type": "connection_ack"}"#),
+            json(r#"{"type": "next", "id": "1", "payload": {"data": {"hello": "world"}}}"#),
+            json(r#"{"type": "complete", "id": "1"}"#),
+        ]);
+        assert_eq!(socket.closed, Some(4429));
+    }
+
+    #[test]
+    fn negotiates_protocol() {
+        assert_eq!(Protocol::negotiate("foo, graphql-ws"), Some(Protocol::SubscriptionsTransportWs));
+        assert_eq!(Protocol::negotiate("graphql-transport-ws"), Some(Protocol::GraphQLTransportWs));
+        assert_eq!(Protocol::negotiate("foo"), None);
+    }
+
+    #[test]
+    fn graphql_transport_ws_operations() {
+        let (connection, socket) = run(Protocol::GraphQLTransportWs, &[
+            r#"{"type": "connection_init", "payload": {"token": "abc"}}"#,
+            r#"{"type": "ping"}"#,
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hello }"}}"#,
+            r#"{"type": "subscribe", "id": "2", "payload": {"query": "{ nope }"}}"#,
+        ]);
+
+        assert_eq!(socket.sent, vec![
+            json(r#"{"type": "connection_ack"}"#),
+            json(r#"{"type": "pong"}"#),
+            json(r#"{"type": "next", "id": "1", "payload": {"data": {"hello": "world"}}}"#),
+            json(r#"{"type": "complete", "id": "1"}"#),
+            json(r#"{"type": "error", "id": "2", "payload": [{
+                "message": "Unknown field \"nope\" on type \"Query\"",
+                "locations": [{"line": 1, "column": 3}],
+                "extensions": {"code": "GRAPHQL_VALIDATION_FAILED"}
+            }]}"#),
+        ]);
+        assert_eq!(socket.closed, None);
+        assert_eq!(
+            connection.init_payload().and_then(|p| p.to_object_value()).and_then(|o| o.get("token").cloned()),
+            Some(&InputValue::string("abc")));
+    }
+
+    #[test]
+    fn subscriptions_transport_ws_operations() {
+        let (connection, socket) = run(Protocol::SubscriptionsTransportWs, &[
+            r#"{"type": "connection_init"}"#,
+            r#"{"type": "start", "id": "1", "payload": {"query": "{ hello }"}}"#,
+            r#"{"type": "stop", "id": "1"}"#,
+            r#"{"type": "connection_terminate"}"#,
+            r#"{"type": "start", "id": "2", "payload": {"query": "{ hello }"}}"#,
+        ]);
+
+        assert_eq!(socket.sent, vec![
+            json(r#"{"type": "connection_ack"}"#),
+            json(r#"{"type": "ka"}"#),
+            json(r#"{"type": "data", "id": "1", "payload": {"data": {"hello": "world"}}}"#),
+            json(r#"{"type": "complete", "id": "1"}"#),
+        ]);
+        assert_eq!(socket.closed, Some(1000));
+        assert!(connection.is_closed());
+    }
+
+    #[test]
+    fn closes_on_protocol_errors() {
+        let (_, socket) = run(Protocol::GraphQLTransportWs, &[
+            r#"{"type": "subscribe", "id": "1", "payload": {"query": "{ hello }"}}"#,
+        ]);
+        assert!(socket.sent.is_empty());
+        assert_eq!(socket.closed, Some(4401));
+
+        let (_, socket) = run(Protocol::GraphQLTransportWs, &[
+            r#"{"type": "connection_init"}"#,
+            r#"{"type": "connection_init"}"#,
+        ]);
+        assert_eq!(socket.closed, Some(4429));
+
+        let (_, socket) = run(Protocol::SubscriptionsTransportWs, &["not json"]);
+        assert_eq!(socket.sent, vec![
+            json(r#"{"type": "connection_error", "payload": {"message": "Invalid message received"}}"#),
+        ]);
+        assert_eq!(socket.closed, Some(4400));
+    }
+}
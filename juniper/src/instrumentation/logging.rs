@@ -0,0 +1,170 @@
+// Logging the events of requests with the `log` crate
+
+use std::fmt;
+use std::time::Duration;
+
+use log::Level;
+
+use executor::{ExecutionError, FieldError};
+use instrumentation::{FieldInfo, Instrumentation, Rejection, RequestInfo};
+use value::ScalarValue;
+
+use GraphQLError;
+
+/// Logs requests and failed fields with the `log` crate
+///
+/// Enabled with the `log` feature. Records are logged with the `juniper`
+/// target by default:
+///
+/// * `debug` when a request starts
+/// * `warn` when a request can't be executed, with why
+/// * `info` when a request ends, with its duration and number of errors
+/// * `warn` when a field fails, with the error and how long it took
+/// * `trace` when any other field is resolved, with how long it took
+///
+/// Applications logging with `slog` can forward the records with
+/// `slog-stdlog`.
+#[derive(Clone, Debug)]
+pub struct LogInstrumentation {
+    target: &'static str,
+}
+
+impl LogInstrumentation {
+    /// Construct an instrumentation logging with the `juniper` target
+    pub fn new() -> LogInstrumentation {
+        LogInstrumentation::default()
+    }
+
+    /// Log with the given target instead of `juniper`
+    pub fn with_target(mut self, target: &'static str) -> LogInstrumentation {
+        self.target = target;
+        self
+    }
+}
+
+impl Default for LogInstrumentation {
+    fn default() -> LogInstrumentation {
+        LogInstrumentation {
+            target: "juniper",
+        }
+    }
+}
+
+impl<S> Instrumentation<S> for LogInstrumentation where S: ScalarValue {
+    fn request_start(&self, request: &RequestInfo) {
+        log!(target: self.target, Level::Debug, "Executing {}", Operation(request));
+    }
+
+    fn request_invalid(&self, request: &RequestInfo, error: &GraphQLError) {
+        log!(target: self.target, Level::Warn, "Rejected {}: {}", Operation(request), Rejection(error));
+    }
+
+    fn request_end(&self, request: &RequestInfo, duration: Duration, errors: &[ExecutionError<S>]) {
+        log!(
+            target: self.target, Level::Info,
+            "Executed {} in {} with {} error(s)",
+            Operation(request), Millis(duration), errors.len());
+    }
+
+    fn field_end(&self, field: &FieldInfo<S>, duration: Duration, error: Option<&FieldError<S>>) {
+        match error {
+            Some(error) => log!(
+                target: self.target, Level::Warn,
+                "Field {}.{} at {} failed after {}: {}",
+                field.parent_type_name(), field.field_name(), field.path().join("."), Millis(duration),
+                error.message()),
+            None => if log_enabled!(target: self.target, Level::Trace) {
+                log!(
+                    target: self.target, Level::Trace,
+                    "Resolved field {}.{} at {} in {}",
+                    field.parent_type_name(), field.field_name(), field.path().join("."), Millis(duration));
+            },
+        }
+    }
+}
+
+struct Operation<'a, 'r: 'a>(&'a RequestInfo<'r>);
+
+struct Millis(Duration);
+
+impl<'a, 'r> fmt::Display for Operation<'a, 'r> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.operation_name() {
+            Some(name) => write!(f, "operation {}", name),
+            None => f.write_str("anonymous operation"),
+        }
+    }
+}
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let millis = self.0.as_secs() as f64 * 1000.0 + f64::from(self.0.subsec_nanos()) / 1_000_000.0;
+        write!(f, "{:.3} ms", millis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use log::{self, Log, Metadata, Record};
+
+    use executor::{FieldResult, Variables};
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    use super::LogInstrumentation;
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field answer() -> i32 { 42 }
+        field fail() -> FieldResult<i32> { Err("Out of order")? }
+    });
+
+    // Requests are executed on the thread of the test, and only records of the
+    // test target are kept, as the tests in this crate share the global logger
+    struct TestLogger;
+
+    static LOGGER: TestLogger = TestLogger;
+
+    thread_local!(static RECORDS: RefCell<Vec<String>> = RefCell::new(Vec::new()));
+
+    impl Log for TestLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.target() == "juniper-test"
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                let message = format!("{} {}", record.level(), record.args());
+                RECORDS.with(|r| r.borrow_mut().push(message.split(" in ").next().unwrap().to_owned()));
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    #[test]
+    fn logs_requests_and_failed_fields() {
+        let _ = log::set_logger(&LOGGER);
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new())
+            .with_instrumentation(LogInstrumentation::new().with_target("juniper-test"));
+
+        ::execute("query Q { answer fail }", Some("Q"), &schema, &Variables::new(), &()).unwrap();
+        assert!(::execute("{ nope }", None, &schema, &Variables::new(), &()).is_err());
+
+        let records = RECORDS.with(|r| r.borrow().clone());
+        assert_eq!(records.len(), 7);
+        assert_eq!(records[0], "DEBUG Executing operation Q");
+        assert_eq!(records[1], "TRACE Resolved field Query.answer at answer");
+        assert!(records[2].starts_with("WARN Field Query.fail at fail failed after "));
+        assert!(records[2].ends_with(" ms: Out of order"));
+        assert_eq!(records[3], "INFO Executed operation Q");
+        assert_eq!(records[4], "DEBUG Executing anonymous operation");
+        assert_eq!(records[5], r#"WARN Rejected anonymous operation: Unknown field "nope" on type "Query""#);
+        assert_eq!(records[6], "INFO Executed anonymous operation");
+    }
+}
@@ -0,0 +1,304 @@
+// Recording the events of requests with the `metrics` crate
+
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use error_code::GraphQLErrorCode;
+use executor::{ExecutionError, FieldError};
+use instrumentation::{FieldInfo, Instrumentation, RequestInfo};
+use value::ScalarValue;
+
+use GraphQLError;
+
+const ANONYMOUS: &str = "anonymous";
+const OTHER_OPERATION: &str = "other";
+const OTHER_CODE: &str = "OTHER";
+const NO_CODE: &str = "NONE";
+
+/// Records the number, errors and latencies of requests with the `metrics`
+/// crate
+///
+/// Enabled with the `metrics` feature. The metrics go to the recorder
+/// installed for `metrics`, e.g. the Prometheus exporter of
+/// `metrics-exporter-prometheus`:
+///
+/// * `graphql_requests_total`, a counter labelled with `operation`
+/// * `graphql_errors_total`, a counter labelled with `operation` and `code`,
+///   incremented for every error of a request
+/// * `graphql_request_duration_seconds`, a histogram labelled with
+///   `operation`
+/// * `graphql_field_duration_seconds`, a histogram labelled with `type` and
+///   `field`, if enabled with `with_field_latencies`
+///
+/// The `operation` label is the operation name of the request, or
+/// `anonymous`. As clients pick operation names, only the first 100 distinct
+/// names become labels, and the requests of any further names are labelled
+/// `other`. The same goes for the first 20 error codes, further ones are
+/// labelled `OTHER`, while errors without a code are labelled `NONE`.
+#[derive(Debug)]
+pub struct MetricsInstrumentation {
+    field_latencies: bool,
+    operation_names: LabelValues,
+    error_codes: LabelValues,
+}
+
+// The values a label takes, so that they can be capped
+#[derive(Debug)]
+struct LabelValues {
+    values: Mutex<HashSet<String>>,
+    max: usize,
+}
+
+impl MetricsInstrumentation {
+    /// Construct an instrumentation recording requests, but not fields
+    pub fn new() -> MetricsInstrumentation {
+        MetricsInstrumentation::default()
+    }
+
+    /// Record the latencies of fields too
+    ///
+    /// Fields are labelled with their type and name rather than their path,
+    /// so the number of labels is bounded by the schema.
+    pub fn with_field_latencies(mut self, field_latencies: bool) -> MetricsInstrumentation {
+        self.field_latencies = field_latencies;
+        self
+    }
+
+    /// Label at most `max` distinct operation names, 100 by default
+    pub fn with_max_operation_names(mut self, max: usize) -> MetricsInstrumentation {
+        self.operation_names.max = max;
+        self
+    }
+
+    /// Only label the given operation names, and label all others `other`
+    pub fn with_operation_names<I, T>(mut self, names: I) -> MetricsInstrumentation
+        where I: IntoIterator<Item = T>,
+              T: Into<String>,
+    {
+        self.operation_names = LabelValues::only(names.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Label at most `max` distinct error codes, 20 by default
+    pub fn with_max_error_codes(mut self, max: usize) -> MetricsInstrumentation {
+        self.error_codes.max = max;
+        self
+    }
+
+    fn operation_label(&self, request: &RequestInfo) -> String {
+        match request.operation_name() {
+            Some(name) => self.operation_names.label(name, OTHER_OPERATION),
+            None => ANONYMOUS.to_owned(),
+        }
+    }
+
+    fn record_error(&self, operation: &str, code: Option<&str>) {
+        let code = match code {
+            Some(code) => self.error_codes.label(code, OTHER_CODE),
+            None => NO_CODE.to_owned(),
+        };
+
+        counter!("graphql_errors_total", "operation" => operation.to_owned(), "code" => code).increment(1);
+    }
+}
+
+impl Default for MetricsInstrumentation {
+    fn default() -> MetricsInstrumentation {
+        MetricsInstrumentation {
+            field_latencies: false,
+            operation_names: LabelValues::up_to(100),
+            error_codes: LabelValues::up_to(20),
+        }
+    }
+}
+
+impl<S> Instrumentation<S> for MetricsInstrumentation where S: ScalarValue {
+    fn request_invalid(&self, request: &RequestInfo, error: &GraphQLError) {
+        let operation = self.operation_label(request);
+
+        match *error {
+            GraphQLError::ParseError(_) =>
+                self.record_error(&operation, Some(GraphQLErrorCode::GraphQLParseFailed.as_str())),
+            GraphQLError::ValidationError(ref errs) => for err in errs {
+                self.record_error(&operation, Some(err.code().as_str()));
+            },
+            GraphQLError::NoOperationProvided |
+            GraphQLError::MultipleOperationsProvided |
+            GraphQLError::UnknownOperationName =>
+                self.record_error(&operation, Some(GraphQLErrorCode::OperationResolutionFailure.as_str())),
+        }
+    }
+
+    fn request_end(&self, request: &RequestInfo, duration: Duration, errors: &[ExecutionError<S>]) {
+        let operation = self.operation_label(request);
+
+        for err in errors {
+            self.record_error(&operation, err.error().extensions().get("code").and_then(|c| c.as_string_value()));
+        }
+
+        counter!("graphql_requests_total", "operation" => operation.clone()).increment(1);
+        histogram!("graphql_request_duration_seconds", "operation" => operation).record(seconds(duration));
+    }
+
+    fn field_end(&self, field: &FieldInfo<S>, duration: Duration, _: Option<&FieldError<S>>) {
+        if self.field_latencies {
+            histogram!(
+                "graphql_field_duration_seconds",
+                "type" => field.parent_type_name().to_owned(),
+                "field" => field.field_name().to_owned())
+                .record(seconds(duration));
+        }
+    }
+}
+
+impl LabelValues {
+    fn up_to(max: usize) -> LabelValues {
+        LabelValues {
+            values: Mutex::new(HashSet::new()),
+            max: max,
+        }
+    }
+
+    fn only(values: HashSet<String>) -> LabelValues {
+        LabelValues {
+            max: values.len(),
+            values: Mutex::new(values),
+        }
+    }
+
+    // The value itself if it's known or there's room for it, `other` if not
+    fn label(&self, value: &str, other: &str) -> String {
+        let mut values = self.values.lock().unwrap_or_else(|err| err.into_inner());
+
+        if values.contains(value) {
+            value.to_owned()
+        } else if values.len() < self.max {
+            values.insert(value.to_owned());
+            value.to_owned()
+        } else {
+            other.to_owned()
+        }
+    }
+}
+
+fn seconds(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1_000_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use metrics;
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use executor::{FieldError, FieldResult, Variables};
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use value::Value;
+    use GraphQLErrorCode;
+
+    use super::MetricsInstrumentation;
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field answer() -> i32 { 42 }
+        field fail() -> FieldResult<i32> { Err("Out of order")? }
+        field deny() -> FieldResult<i32> {
+            Err(FieldError::new("Denied", Value::null()).with_code(GraphQLErrorCode::Forbidden))
+        }
+    });
+
+    // The metrics as `name{labels} value`, with the number of samples of
+    // histograms as their value
+    fn metrics(instrumentation: MetricsInstrumentation, queries: &[(&str, Option<&str>)]) -> Vec<String> {
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new()).with_instrumentation(instrumentation);
+
+        metrics::with_local_recorder(&recorder, || for &(query, operation_name) in queries {
+            let _ = ::execute(query, operation_name, &schema, &Variables::new(), &());
+        });
+
+        let mut metrics = snapshotter.snapshot().into_vec().into_iter()
+            .map(|(key, _, _, value)| {
+                let labels = key.key().labels()
+                    .map(|l| format!("{}={}", l.key(), l.value()))
+                    .collect::<Vec<_>>();
+                let value = match value {
+                    DebugValue::Counter(count) => count as usize,
+                    DebugValue::Histogram(samples) => samples.len(),
+                    DebugValue::Gauge(_) => 0,
+                };
+                format!("{}{{{}}} {}", key.key().name(), labels.join(","), value)
+            })
+            .collect::<Vec<_>>();
+        metrics.sort();
+        metrics
+    }
+
+    #[test]
+    fn records_requests_and_errors() {
+        assert_eq!(
+            metrics(MetricsInstrumentation::new(), &[
+                ("query A { answer }", Some("A")),
+                ("query A { answer fail deny }", Some("A")),
+                ("{ fail }", None),
+                ("{ nope, neither }", None),
+                ("{ answer", None),
+            ]),
+            vec![
+                "graphql_errors_total{operation=A,code=FORBIDDEN} 1",
+                "graphql_errors_total{operation=A,code=NONE} 1",
+                "graphql_errors_total{operation=anonymous,code=GRAPHQL_PARSE_FAILED} 1",
+                "graphql_errors_total{operation=anonymous,code=GRAPHQL_VALIDATION_FAILED} 2",
+                "graphql_errors_total{operation=anonymous,code=NONE} 1",
+                "graphql_request_duration_seconds{operation=A} 2",
+                "graphql_request_duration_seconds{operation=anonymous} 3",
+                "graphql_requests_total{operation=A} 2",
+                "graphql_requests_total{operation=anonymous} 3",
+            ]);
+    }
+
+    #[test]
+    fn caps_labels() {
+        let queries = &[
+            ("query A { answer }", Some("A")),
+            ("query B { answer }", Some("B")),
+            ("query C { answer fail deny }", Some("C")),
+        ];
+
+        assert_eq!(
+            metrics(MetricsInstrumentation::new().with_max_operation_names(1).with_max_error_codes(0), queries),
+            vec![
+                "graphql_errors_total{operation=other,code=NONE} 1",
+                "graphql_errors_total{operation=other,code=OTHER} 1",
+                "graphql_request_duration_seconds{operation=A} 1",
+                "graphql_request_duration_seconds{operation=other} 2",
+                "graphql_requests_total{operation=A} 1",
+                "graphql_requests_total{operation=other} 2",
+            ]);
+
+        assert_eq!(
+            metrics(MetricsInstrumentation::new().with_operation_names(vec!["B"]), &queries[..2]),
+            vec![
+                "graphql_request_duration_seconds{operation=B} 1",
+                "graphql_request_duration_seconds{operation=other} 1",
+                "graphql_requests_total{operation=B} 1",
+                "graphql_requests_total{operation=other} 1",
+            ]);
+    }
+
+    #[test]
+    fn records_field_latencies_if_enabled() {
+        assert_eq!(
+            metrics(MetricsInstrumentation::new().with_field_latencies(true), &[("{ answer fail }", None)]),
+            vec![
+                "graphql_errors_total{operation=anonymous,code=NONE} 1",
+                "graphql_field_duration_seconds{type=Query,field=answer} 1",
+                "graphql_field_duration_seconds{type=Query,field=fail} 1",
+                "graphql_request_duration_seconds{operation=anonymous} 1",
+                "graphql_requests_total{operation=anonymous} 1",
+            ]);
+    }
+}
@@ -0,0 +1,391 @@
+//! Hooks into the execution of requests
+//!
+//! An `Instrumentation` is told when a request starts and ends, when it can't
+//! be executed, and when each of its fields is resolved, with how long that
+//! took. Logging, metrics and tracing can be built on these hooks without
+//! wrapping every resolver. Instruments are added to a schema with
+//! `RootNode::with_instrumentation`:
+//!
+//! ```rust
+//! # #[macro_use] extern crate juniper;
+//! use std::sync::Mutex;
+//! use std::time::Duration;
+//! use juniper::{EmptyMutation, FieldError, RootNode, Variables};
+//! use juniper::instrumentation::{FieldInfo, Instrumentation};
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field answer() -> i32 { 42 }
+//! });
+//!
+//! #[derive(Default)]
+//! struct SlowFields(Mutex<Vec<String>>);
+//!
+//! impl Instrumentation for SlowFields {
+//!     fn field_end(&self, field: &FieldInfo, duration: Duration, _: Option<&FieldError>) {
+//!         if duration > Duration::from_millis(100) {
+//!             self.0.lock().unwrap().push(field.path().join("."));
+//!         }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let root_node = RootNode::new(Query, EmptyMutation::<()>::new())
+//!     .with_instrumentation(SlowFields::default());
+//!
+//! juniper::execute("{ answer }", None, &root_node, &Variables::new(), &()).unwrap();
+//! # }
+//! ```
+//!
+//! The request hooks are called for requests executed with `juniper::execute`
+//! and `execute_with_scratch`, and so by `http::GraphQLRequest::execute` and
+//! the integrations. The field hooks are called however a query is executed.
+//! Fields are resolved on the thread that executes the request, and the hooks
+//! of a field are called in between those of the field it's nested in.
+//! `__typename` isn't resolved, and so isn't instrumented.
+//!
+//! With the `log` feature, `LogInstrumentation` logs requests, their
+//! durations and the fields that fail with the `log` crate. With the
+//! `metrics` feature, `MetricsInstrumentation` records the number, errors and
+//! latencies of requests with the `metrics` crate. With the `tracing`
+//! feature, `TracingInstrumentation` opens spans for requests and fields with
+//! the `tracing` crate.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use executor::{ExecutionError, FieldError, FieldPath};
+use parser::SourcePosition;
+use types::base::Arguments;
+use value::{DefaultScalarValue, ScalarValue, Value};
+
+use GraphQLError;
+
+#[cfg(feature="log")] mod logging;
+#[cfg(feature="metrics")] mod metrics;
+#[cfg(feature="tracing")] mod tracing;
+
+#[cfg(feature="log")] pub use self::logging::LogInstrumentation;
+#[cfg(feature="metrics")] pub use self::metrics::MetricsInstrumentation;
+#[cfg(feature="tracing")] pub use self::tracing::TracingInstrumentation;
+
+/// Receives the events of executing requests, see the module documentation
+///
+/// Every hook does nothing by default, so implementations only implement the
+/// ones they need.
+pub trait Instrumentation<S = DefaultScalarValue>: Send + Sync {
+    /// A request is about to be parsed, validated and executed
+    fn request_start(&self, _: &RequestInfo) {}
+
+    /// A request can't be executed, because it doesn't parse or validate, or
+    /// it doesn't name an operation of the document
+    ///
+    /// `request_end` is called right after.
+    fn request_invalid(&self, _: &RequestInfo, _: &GraphQLError) {}
+
+    /// A request is done, with the errors of its execution
+    fn request_end(&self, _: &RequestInfo, _: Duration, _: &[ExecutionError<S>]) {}
+
+    /// A field is about to be resolved
+    fn field_start(&self, _: &FieldInfo<S>) {}
+
+    /// A field is resolved, with the error it failed with, if any
+    ///
+    /// The duration includes resolving the fields selected on it.
+    fn field_end(&self, _: &FieldInfo<S>, _: Duration, _: Option<&FieldError<S>>) {}
+}
+
+impl<S, T> Instrumentation<S> for Arc<T> where T: Instrumentation<S> + ?Sized {
+    fn request_start(&self, request: &RequestInfo) {
+        (**self).request_start(request)
+    }
+
+    fn request_invalid(&self, request: &RequestInfo, error: &GraphQLError) {
+        (**self).request_invalid(request, error)
+    }
+
+    fn request_end(&self, request: &RequestInfo, duration: Duration, errors: &[ExecutionError<S>]) {
+        (**self).request_end(request, duration, errors)
+    }
+
+    fn field_start(&self, field: &FieldInfo<S>) {
+        (**self).field_start(field)
+    }
+
+    fn field_end(&self, field: &FieldInfo<S>, duration: Duration, error: Option<&FieldError<S>>) {
+        (**self).field_end(field, duration, error)
+    }
+}
+
+/// The request an instrumentation hook is called for
+#[derive(Clone, Copy, Debug)]
+pub struct RequestInfo<'r> {
+    query: &'r str,
+    operation_name: Option<&'r str>,
+}
+
+/// The field an instrumentation hook is called for
+pub struct FieldInfo<'r, S: 'r = DefaultScalarValue> {
+    parent_type_name: &'r str,
+    field_name: &'r str,
+    arguments: &'r Arguments<'r, S>,
+    path: &'r FieldPath<'r>,
+}
+
+impl<'r> RequestInfo<'r> {
+    /// The query document of the request
+    pub fn query(&self) -> &'r str {
+        self.query
+    }
+
+    /// The name of the operation to execute, if the request names one
+    pub fn operation_name(&self) -> Option<&'r str> {
+        self.operation_name
+    }
+}
+
+impl<'r, S> FieldInfo<'r, S> {
+    /// The name of the type the field belongs to
+    pub fn parent_type_name(&self) -> &'r str {
+        self.parent_type_name
+    }
+
+    /// The name of the field in the schema
+    pub fn field_name(&self) -> &'r str {
+        self.field_name
+    }
+
+    /// The arguments the field is resolved with, including default values
+    pub fn arguments(&self) -> &'r Arguments<'r, S> {
+        self.arguments
+    }
+
+    /// The location of the field in the query
+    pub fn location(&self) -> &'r SourcePosition {
+        self.path.location()
+    }
+
+    /// The response names of the field and the fields it's nested in, as in
+    /// the `path` of errors but without the indices of list items
+    pub fn path(&self) -> Vec<String> {
+        let mut path = Vec::new();
+        self.path.construct_field_names(&mut path);
+        path
+    }
+
+    /// How deeply the field is nested, `1` for the fields of the root type
+    pub fn depth(&self) -> usize {
+        self.path.depth()
+    }
+}
+
+impl<'r, S> fmt::Debug for FieldInfo<'r, S> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FieldInfo")
+            .field("parent_type_name", &self.parent_type_name)
+            .field("field_name", &self.field_name)
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+// Describes why a request can't be executed
+#[cfg(any(feature="log", feature="tracing"))]
+struct Rejection<'a, 'e: 'a>(&'a GraphQLError<'e>);
+
+#[cfg(any(feature="log", feature="tracing"))]
+impl<'a, 'e> fmt::Display for Rejection<'a, 'e> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self.0 {
+            GraphQLError::ParseError(ref err) => fmt::Display::fmt(&err.item, f),
+            GraphQLError::ValidationError(ref errs) => {
+                let messages = errs.iter().map(|err| err.message()).collect::<Vec<_>>();
+                f.write_str(&messages.join("; "))
+            },
+            GraphQLError::NoOperationProvided => f.write_str("No operation provided"),
+            GraphQLError::MultipleOperationsProvided => f.write_str("Multiple operations provided"),
+            GraphQLError::UnknownOperationName => f.write_str("Unknown operation name"),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub type Instruments<S> = [Arc<Instrumentation<S>>];
+
+// Call the request hooks around executing a request
+#[doc(hidden)]
+pub fn instrument_request<'a, S, F>(
+    instruments: &Instruments<S>,
+    query: &str,
+    operation_name: Option<&str>,
+    execute: F,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where S: ScalarValue,
+          F: FnOnce() -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>,
+{
+    if instruments.is_empty() {
+        return execute();
+    }
+
+    let request = RequestInfo {
+        query: query,
+        operation_name: operation_name,
+    };
+
+    for instrument in instruments {
+        instrument.request_start(&request);
+    }
+
+    let start = Instant::now();
+    let result = execute();
+    let duration = start.elapsed();
+
+    match result {
+        Ok((_, ref errors)) => for instrument in instruments {
+            instrument.request_end(&request, duration, errors);
+        },
+        Err(ref err) => for instrument in instruments {
+            instrument.request_invalid(&request, err);
+            instrument.request_end(&request, duration, &[]);
+        },
+    }
+
+    result
+}
+
+// Call the field hooks around resolving a field
+#[doc(hidden)]
+pub fn instrument_field<S, F>(
+    instruments: &Instruments<S>,
+    parent_type_name: &str,
+    field_name: &str,
+    arguments: &Arguments<S>,
+    path: &FieldPath,
+    resolve: F,
+)
+    -> Result<Value<S>, FieldError<S>>
+    where S: ScalarValue,
+          F: FnOnce() -> Result<Value<S>, FieldError<S>>,
+{
+    if instruments.is_empty() {
+        return resolve();
+    }
+
+    let field = FieldInfo {
+        parent_type_name: parent_type_name,
+        field_name: field_name,
+        arguments: arguments,
+        path: path,
+    };
+
+    for instrument in instruments {
+        instrument.field_start(&field);
+    }
+
+    let start = Instant::now();
+    let result = resolve();
+    let duration = start.elapsed();
+
+    for instrument in instruments {
+        instrument.field_end(&field, duration, result.as_ref().err());
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use executor::{ExecutionError, FieldError, FieldResult, Variables};
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use GraphQLError;
+
+    use super::{FieldInfo, Instrumentation, RequestInfo};
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field hero(name: String) -> Hero { Hero }
+        field fail() -> FieldResult<i32> { Err("Failed")? }
+    });
+
+    struct Hero;
+
+    graphql_object!(Hero: () |&self| {
+        field friends() -> Vec<Hero> { vec![Hero, Hero] }
+        field name() -> &str { "R2-D2" }
+    });
+
+    #[derive(Default)]
+    struct Events(Mutex<Vec<String>>);
+
+    impl Events {
+        fn push(&self, event: String) {
+            self.0.lock().unwrap().push(event);
+        }
+    }
+
+    impl Instrumentation for Events {
+        fn request_start(&self, request: &RequestInfo) {
+            self.push(format!("start {:?}", request.operation_name()));
+        }
+
+        fn request_invalid(&self, _: &RequestInfo, _: &GraphQLError) {
+            self.push("invalid".to_owned());
+        }
+
+        fn request_end(&self, _: &RequestInfo, _: Duration, errors: &[ExecutionError]) {
+            self.push(format!("end {}", errors.len()));
+        }
+
+        fn field_start(&self, field: &FieldInfo) {
+            let name = field.arguments().get::<String>("name").map(|n| format!("({})", n)).unwrap_or_default();
+            self.push(format!("> {}.{}{} {}", field.parent_type_name(), field.field_name(), name, field.depth()));
+        }
+
+        fn field_end(&self, field: &FieldInfo, _: Duration, error: Option<&FieldError>) {
+            self.push(format!("< {} {:?}", field.path().join("."), error.map(|e| e.message())));
+        }
+    }
+
+    fn events(query: &str) -> Vec<String> {
+        let events = Arc::new(Events::default());
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new()).with_instrumentation(events.clone());
+
+        let _ = ::execute(query, None, &schema, &Variables::new(), &());
+
+        let events = events.0.lock().unwrap();
+        events.clone()
+    }
+
+    #[test]
+    fn calls_hooks_in_order() {
+        assert_eq!(
+            events(r#"query Q { h: hero(name: "R2") { __typename friends { name } } fail }"#),
+            vec![
+                "start None",
+                "> Query.hero(R2) 1",
+                "> Hero.friends 2",
+                "> Hero.name 3",
+                "< h.friends.name None",
+                "> Hero.name 3",
+                "< h.friends.name None",
+                "< h.friends None",
+                "< h None",
+                "> Query.fail 1",
+                "< fail Some(\"Failed\")",
+                "end 1",
+            ]);
+    }
+
+    #[test]
+    fn reports_invalid_requests() {
+        assert_eq!(events("{ nope }"), vec!["start None", "invalid", "end 0"]);
+    }
+}
@@ -0,0 +1,335 @@
+// Opening spans for requests and fields with the `tracing` crate
+
+use std::cell::RefCell;
+use std::fmt;
+use std::time::Duration;
+
+use tracing::field::{display, Empty};
+use tracing::span::EnteredSpan;
+
+use executor::{ExecutionError, FieldError};
+use instrumentation::{FieldInfo, Instrumentation, Rejection, RequestInfo};
+use value::ScalarValue;
+
+use GraphQLError;
+
+// The spans entered by the hooks, innermost last
+thread_local!(static SPANS: RefCell<Vec<EnteredSpan>> = RefCell::new(Vec::new()));
+
+/// Opens a span for every request and resolved field with the `tracing`
+/// crate
+///
+/// Enabled with the `tracing` feature. The span of a request is entered
+/// while it executes, so it's nested in whatever span is current, e.g. the
+/// one of an HTTP request, and the spans of fields are nested in the span of
+/// the request and the fields they're selected on. With
+/// `tracing-opentelemetry`, requests appear in distributed traces this way.
+///
+/// Spans are named `graphql.request` and `graphql.field`, and have an
+/// `otel.name` with the operation name, or the type and name of the field.
+/// Requests record `graphql.operation.name` and the number of
+/// `graphql.errors`, and fields their `graphql.field.path`, the
+/// `graphql.field.arguments` that are allowed by `with_arguments`, and the
+/// `error` they failed with. Both record their `duration_ms`, and set
+/// `otel.status_code` to `ERROR` if they failed.
+///
+/// Spans are entered on the thread the request is executed on. If a resolver
+/// panics, the spans of the request are left entered on that thread.
+#[derive(Clone, Debug)]
+pub struct TracingInstrumentation {
+    max_field_depth: usize,
+    arguments: Vec<String>,
+}
+
+impl TracingInstrumentation {
+    /// Construct an instrumentation opening spans for every field, without
+    /// arguments
+    pub fn new() -> TracingInstrumentation {
+        TracingInstrumentation::default()
+    }
+
+    /// Only open spans for fields nested at most `depth` levels deep
+    ///
+    /// The fields of the root type are at depth `1`, so `0` doesn't open
+    /// spans for any fields.
+    pub fn with_max_field_depth(mut self, depth: usize) -> TracingInstrumentation {
+        self.max_field_depth = depth;
+        self
+    }
+
+    /// Record the arguments with the given names
+    ///
+    /// Arguments are recorded with the values of variables, so arguments
+    /// that may be sensitive shouldn't be allowed.
+    pub fn with_arguments<I, T>(mut self, names: I) -> TracingInstrumentation
+        where I: IntoIterator<Item = T>,
+              T: Into<String>,
+    {
+        self.arguments = names.into_iter().map(Into::into).collect();
+        self.arguments.sort();
+        self.arguments.dedup();
+        self
+    }
+
+    fn traces<S>(&self, field: &FieldInfo<S>) -> bool {
+        field.depth() <= self.max_field_depth
+    }
+}
+
+impl Default for TracingInstrumentation {
+    fn default() -> TracingInstrumentation {
+        TracingInstrumentation {
+            max_field_depth: usize::max_value(),
+            arguments: Vec::new(),
+        }
+    }
+}
+
+impl<S> Instrumentation<S> for TracingInstrumentation where S: ScalarValue {
+    fn request_start(&self, request: &RequestInfo) {
+        let span = info_span!(
+            "graphql.request",
+            otel.name = request.operation_name().unwrap_or("anonymous"),
+            graphql.operation.name = request.operation_name(),
+            graphql.errors = Empty,
+            duration_ms = Empty,
+            error = Empty,
+            otel.status_code = Empty);
+
+        push(span.entered());
+    }
+
+    fn request_invalid(&self, _: &RequestInfo, error: &GraphQLError) {
+        SPANS.with(|spans| if let Some(span) = spans.borrow().last() {
+            span.record("error", &display(Rejection(error)));
+            span.record("otel.status_code", &"ERROR");
+        });
+    }
+
+    fn request_end(&self, _: &RequestInfo, duration: Duration, errors: &[ExecutionError<S>]) {
+        if let Some(span) = pop() {
+            span.record("graphql.errors", &(errors.len() as u64));
+            span.record("duration_ms", &millis(duration));
+        }
+    }
+
+    fn field_start(&self, field: &FieldInfo<S>) {
+        if !self.traces(field) {
+            return;
+        }
+
+        let span = info_span!(
+            "graphql.field",
+            otel.name = Empty,
+            graphql.field.path = Empty,
+            graphql.field.arguments = Empty,
+            duration_ms = Empty,
+            error = Empty,
+            otel.status_code = Empty);
+
+        if !span.is_disabled() {
+            span.record("otel.name", &display(format_args!("{}.{}", field.parent_type_name(), field.field_name())));
+            span.record("graphql.field.path", &display(field.path().join(".")));
+
+            let arguments = AllowedArguments(&self.arguments, field);
+            if !arguments.is_empty() {
+                span.record("graphql.field.arguments", &display(arguments));
+            }
+        }
+
+        push(span.entered());
+    }
+
+    fn field_end(&self, field: &FieldInfo<S>, duration: Duration, error: Option<&FieldError<S>>) {
+        if !self.traces(field) {
+            return;
+        }
+
+        if let Some(span) = pop() {
+            span.record("duration_ms", &millis(duration));
+            if let Some(error) = error {
+                span.record("error", &error.message());
+                span.record("otel.status_code", &"ERROR");
+            }
+        }
+    }
+}
+
+// The allowed arguments a field is resolved with, as `name: value, ...`
+struct AllowedArguments<'a, 'r: 'a, S: 'r>(&'a [String], &'a FieldInfo<'r, S>);
+
+impl<'a, 'r, S> AllowedArguments<'a, 'r, S> where S: ScalarValue {
+    fn is_empty(&self) -> bool {
+        !self.0.iter().any(|name| self.1.arguments().get_input_value(name).is_some())
+    }
+}
+
+impl<'a, 'r, S> fmt::Display for AllowedArguments<'a, 'r, S> where S: ScalarValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let arguments = self.0.iter()
+            .filter_map(|name| self.1.arguments().get_input_value(name).map(|value| (name, value)));
+
+        for (i, (name, value)) in arguments.enumerate() {
+            if i > 0 {
+                try!(f.write_str(", "));
+            }
+            try!(write!(f, "{}: {}", name, value));
+        }
+
+        Ok(())
+    }
+}
+
+fn push(span: EnteredSpan) {
+    SPANS.with(|spans| spans.borrow_mut().push(span));
+}
+
+fn pop() -> Option<EnteredSpan> {
+    SPANS.with(|spans| spans.borrow_mut().pop())
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs() as f64 * 1000.0 + f64::from(duration.subsec_nanos()) / 1_000_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fmt;
+    use std::sync::{Arc, Mutex};
+
+    use tracing::{self, Event, Id, Metadata, Subscriber};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Record};
+
+    use executor::{FieldResult, Variables};
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    use super::TracingInstrumentation;
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field hero(id: String, secret = ("".to_owned()): String) -> Hero { Hero }
+    });
+
+    struct Hero;
+
+    graphql_object!(Hero: () |&self| {
+        field name() -> &str { "R2-D2" }
+        field friends() -> FieldResult<Vec<Hero>> { Err("No friends")? }
+    });
+
+    // The spans opened, as the name of their parent, their own name and the
+    // fields recorded for them other than the duration
+    #[derive(Clone, Default)]
+    struct Spans(Arc<Mutex<Vec<(Option<usize>, &'static str, BTreeMap<String, String>)>>>);
+
+    // Entered spans are tracked per thread by real subscribers, the tests
+    // execute requests on a single thread
+    #[derive(Clone, Default)]
+    struct TestSubscriber {
+        spans: Spans,
+        entered: Arc<Mutex<Vec<usize>>>,
+    }
+
+    struct Fields<'a>(&'a mut BTreeMap<String, String>);
+
+    impl<'a> Visit for Fields<'a> {
+        fn record_debug(&mut self, field: &Field, value: &fmt::Debug) {
+            if field.name() != "duration_ms" {
+                self.0.insert(field.name().to_owned(), format!("{:?}", value));
+            }
+        }
+    }
+
+    impl Subscriber for TestSubscriber {
+        fn enabled(&self, _: &Metadata) -> bool {
+            true
+        }
+
+        fn new_span(&self, attributes: &Attributes) -> Id {
+            let mut fields = BTreeMap::new();
+            attributes.record(&mut Fields(&mut fields));
+
+            let mut spans = self.spans.0.lock().unwrap();
+            let parent = self.entered.lock().unwrap().last().cloned();
+            spans.push((parent, attributes.metadata().name(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, id: &Id, record: &Record) {
+            let mut spans = self.spans.0.lock().unwrap();
+            record.record(&mut Fields(&mut spans[id.into_u64() as usize - 1].2));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event) {}
+
+        fn enter(&self, id: &Id) {
+            self.entered.lock().unwrap().push(id.into_u64() as usize - 1);
+        }
+
+        fn exit(&self, _: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+    }
+
+    fn spans(instrumentation: TracingInstrumentation, query: &str, operation_name: Option<&str>) -> Vec<String> {
+        let subscriber = TestSubscriber::default();
+        let spans = subscriber.spans.clone();
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new()).with_instrumentation(instrumentation);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let _ = ::execute(query, operation_name, &schema, &Variables::new(), &());
+        });
+
+        let spans = spans.0.lock().unwrap();
+        spans.iter()
+            .map(|&(parent, name, ref fields)| {
+                let fields = fields.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>();
+                let parent = parent.map_or("", |i| spans[i].1);
+                format!("{} > {} {}", parent, name, fields.join(" "))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn opens_nested_spans() {
+        assert_eq!(
+            spans(
+                TracingInstrumentation::new().with_arguments(vec!["id"]),
+                r#"query Q { hero(id: "2001", secret: "s3cr3t") { name friends { name } } }"#,
+                Some("Q")),
+            vec![
+                " > graphql.request graphql.errors=1 graphql.operation.name=\"Q\" otel.name=\"Q\"",
+                "graphql.request > graphql.field graphql.field.arguments=id: \"2001\" graphql.field.path=hero \
+                 otel.name=Query.hero",
+                "graphql.field > graphql.field graphql.field.path=hero.name otel.name=Hero.name",
+                "graphql.field > graphql.field error=\"No friends\" graphql.field.path=hero.friends \
+                 otel.name=Hero.friends otel.status_code=\"ERROR\"",
+            ]);
+    }
+
+    #[test]
+    fn limits_the_depth_of_field_spans() {
+        assert_eq!(
+            spans(TracingInstrumentation::new().with_max_field_depth(1), r#"{ hero(id: "2001") { name } }"#, None),
+            vec![
+                " > graphql.request graphql.errors=0 otel.name=\"anonymous\"",
+                "graphql.request > graphql.field graphql.field.path=hero otel.name=Query.hero",
+            ]);
+    }
+
+    #[test]
+    fn records_invalid_requests() {
+        assert_eq!(
+            spans(TracingInstrumentation::new(), "{ nope }", None),
+            vec![
+                " > graphql.request error=Unknown field \"nope\" on type \"Query\" graphql.errors=0 \
+                 otel.name=\"anonymous\" otel.status_code=\"ERROR\"",
+            ]);
+    }
+}
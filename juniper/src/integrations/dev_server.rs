@@ -0,0 +1,190 @@
+//! A standalone server for examples and prototypes. Requires the `dev-server`
+//! feature enabled.
+//!
+//! `DevServer` serves a schema over hyper without any routing or setup: GraphQL
+//! requests at `/graphql`, and GraphiQL at `/`. Use one of the framework
+//! integrations for anything more involved.
+//!
+//! ```rust,ignore
+//! let root_node = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+//!
+//! DevServer::new(root_node, |_: &RequestMetadata| Ok(Database::new()))
+//!     .run(&([127, 0, 0, 1], 8080).into())
+//!     .unwrap();
+//! ```
+//!
+//! Tests can bind to port 0 and spawn the server on their own runtime instead:
+//!
+//! ```rust,ignore
+//! let (addr, server) = DevServer::new(root_node, context_factory)
+//!     .serve(&([127, 0, 0, 1], 0).into())
+//!     .unwrap();
+//! runtime.spawn(server.map_err(|_| ()));
+//! ```
+
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use futures::{future, Future};
+use hyper::{self, Body, Request, Response, Server, StatusCode};
+use hyper::service::Service;
+
+use ::{GraphQLType, RootNode};
+use http::context::ContextFactory;
+use http::limits::RequestLimits;
+use integrations::hyper_handlers::{self, GraphQLService, ResponseFuture};
+
+/// The future of a running `DevServer`
+pub type ServerFuture = Box<Future<Item=(), Error=hyper::Error> + Send>;
+
+/// Server for a GraphQL schema, with GraphiQL
+pub struct DevServer<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    service: GraphQLService<CtxFactory, Query, Mutation, CtxT>,
+    graphql_path: String,
+    graphiql_path: Option<String>,
+}
+
+struct DevService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    service: GraphQLService<CtxFactory, Query, Mutation, CtxT>,
+    graphql_path: Arc<String>,
+    graphiql_path: Option<Arc<String>>,
+}
+
+impl<CtxFactory, Query, Mutation, CtxT>
+    DevServer<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    /// Set up a server for the given schema
+    ///
+    /// The context factory builds the context of every request, see
+    /// `http::context`.
+    pub fn new(root_node: RootNode<'static, Query, Mutation>, context_factory: CtxFactory) -> Self {
+        DevServer {
+            service: GraphQLService::new(context_factory, root_node),
+            graphql_path: "/graphql".to_owned(),
+            graphiql_path: Some("/".to_owned()),
+        }
+    }
+
+    /// Serve GraphQL requests at the given path
+    pub fn graphql_path(mut self, path: &str) -> Self {
+        self.graphql_path = path.to_owned();
+        self
+    }
+
+    /// Serve GraphiQL at the given path
+    pub fn graphiql_path(mut self, path: &str) -> Self {
+        self.graphiql_path = Some(path.to_owned());
+        self
+    }
+
+    /// Don't serve GraphiQL
+    pub fn without_graphiql(mut self) -> Self {
+        self.graphiql_path = None;
+        self
+    }
+
+    /// Limit the size of incoming requests, see `http::limits`
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.service = self.service.with_limits(limits);
+        self
+    }
+
+    /// Bind to the given address
+    ///
+    /// Returns the address the server is bound to, which tells the port when
+    /// binding to port 0, and the server to spawn on a hyper runtime.
+    pub fn serve(self, addr: &SocketAddr) -> Result<(SocketAddr, ServerFuture), hyper::Error> {
+        let service = DevService {
+            service: self.service,
+            graphql_path: Arc::new(self.graphql_path),
+            graphiql_path: self.graphiql_path.map(Arc::new),
+        };
+
+        let server = try!(Server::try_bind(addr))
+            .serve(move || Ok::<_, hyper::Error>(service.clone()));
+        let local_addr = server.local_addr();
+
+        Ok((local_addr, Box::new(server)))
+    }
+
+    /// Bind to the given address and serve requests until the process exits
+    pub fn run(self, addr: &SocketAddr) -> Result<(), hyper::Error> {
+        let (_, server) = try!(self.serve(addr));
+        hyper::rt::run(server.map_err(|err| eprintln!("server error: {}", err)));
+
+        Ok(())
+    }
+}
+
+impl<CtxFactory, Query, Mutation, CtxT>
+    Clone
+    for DevService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    fn clone(&self) -> Self {
+        DevService {
+            service: self.service.clone(),
+            graphql_path: self.graphql_path.clone(),
+            graphiql_path: self.graphiql_path.clone(),
+        }
+    }
+}
+
+impl<CtxFactory, Query, Mutation, CtxT>
+    Service
+    for DevService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    type ReqBody = Body;
+    type ResBody = Body;
+    type Error = hyper::Error;
+    type Future = ResponseFuture;
+
+    fn call(&mut self, req: Request<Body>) -> ResponseFuture {
+        if req.uri().path() == self.graphql_path.as_str() {
+            return self.service.call(req);
+        }
+
+        let response = match self.graphiql_path {
+            Some(ref path) if req.uri().path() == path.as_str() =>
+                hyper_handlers::graphiql(&self.graphql_path),
+            _ => {
+                let mut response = Response::new(Body::empty());
+                *response.status_mut() = StatusCode::NOT_FOUND;
+                response
+            },
+        };
+
+        Box::new(future::ok(response))
+    }
+}
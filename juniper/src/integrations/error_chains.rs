@@ -0,0 +1,74 @@
+use executor::{FieldError, IntoFieldError};
+use value::{ScalarValue, Value};
+
+// The messages of the causes often contain internals like file paths or SQL,
+// so they're only added to the extensions in debug builds
+fn with_causes<S, I>(message: String, causes: I) -> FieldError<S>
+    where S: ScalarValue,
+          I: Iterator<Item=String>,
+{
+    let error = FieldError::from(message);
+    if !cfg!(debug_assertions) {
+        return error;
+    }
+
+    let causes = causes.map(Value::string).collect::<Vec<_>>();
+    if causes.is_empty() {
+        error
+    } else {
+        error.with_extension("causes", Value::list(causes))
+    }
+}
+
+#[cfg(feature="anyhow")]
+impl<S> IntoFieldError<S> for ::anyhow::Error where S: ScalarValue {
+    fn into_field_error(self) -> FieldError<S> {
+        with_causes(self.to_string(), self.chain().skip(1).map(|cause| cause.to_string()))
+    }
+}
+
+#[cfg(feature="failure")]
+impl<S> IntoFieldError<S> for ::failure::Error where S: ScalarValue {
+    fn into_field_error(self) -> FieldError<S> {
+        with_causes(self.to_string(), self.iter_causes().map(|cause| cause.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::{FieldError, IntoFieldError};
+    use value::Value;
+
+    #[cfg(feature="anyhow")]
+    #[test]
+    fn anyhow_errors_keep_their_causes() {
+        use anyhow::Context;
+
+        let err = Err::<(), _>(::anyhow::anyhow!("connection refused"))
+            .context("could not load user")
+            .unwrap_err();
+        let err: FieldError = err.into_field_error();
+
+        assert_eq!(err.message(), "could not load user");
+        assert_eq!(
+            err.extensions().get("causes"),
+            Some(&Value::list(vec![Value::string("connection refused")])));
+    }
+
+    #[cfg(feature="failure")]
+    #[test]
+    fn failure_errors_keep_their_causes() {
+        use failure::{err_msg, ResultExt};
+
+        let err: ::failure::Error = Err::<(), _>(err_msg("connection refused"))
+            .context("could not load user")
+            .unwrap_err()
+            .into();
+        let err: FieldError = err.into_field_error();
+
+        assert_eq!(err.message(), "could not load user");
+        assert_eq!(
+            err.extensions().get("causes"),
+            Some(&Value::list(vec![Value::string("connection refused")])));
+    }
+}
@@ -0,0 +1,229 @@
+//! Optional handlers for [hyper](https://hyper.rs). Requires the `hyper-handlers` feature enabled.
+//!
+//! This is for servers that don't need a full web framework: `GraphQLService`
+//! answers every request it receives, so it can be handed to hyper directly
+//! or called from a hand-written router.
+//!
+//! ```rust,ignore
+//! let root_node = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+//! let service = GraphQLService::new(|_: &RequestMetadata| Ok(Database::new()), root_node);
+//!
+//! let server = Server::bind(&addr)
+//!     .serve(move || Ok::<_, hyper::Error>(service.clone()))
+//!     .map_err(|err| eprintln!("server error: {}", err));
+//!
+//! hyper::rt::run(server);
+//! ```
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use futures::{future, Future, Stream};
+use hyper::{Body, Request, Response, StatusCode};
+use hyper::header::{self, HeaderValue};
+use hyper::service::Service;
+
+use ::{GraphQLType, RootNode, SwappableRootNode};
+use ::http;
+use graphiql::graphiql_source;
+use http::context::{ContextFactory, RequestMetadata};
+use http::handler::{GraphQLHandler, RequestParts, HandlerResponse};
+use http::limits::RequestLimits;
+use http::playground::playground_source;
+
+/// The future of a response, as returned by `GraphQLService`
+pub type ResponseFuture = Box<Future<Item=Response<Body>, Error=::hyper::Error> + Send>;
+
+/// Service that executes GraphQL queries in the given schema
+///
+/// The service responds to GET requests and POST requests only. In GET
+/// requests, the query should be supplied in the `query` URL parameter, e.g.
+/// `http://localhost:3000/graphql?query={hero{name}}`.
+///
+/// POST requests support both queries and variables. POST a JSON document
+/// containing the field `"query"` and optionally `"variables"`, or a JSON
+/// array of such documents to execute them as a batch. Alternatively, POST the
+/// query itself with the `application/graphql` content type.
+///
+/// The service is cheap to clone, so a clone can be handed out for every
+/// connection.
+pub struct GraphQLService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    context_factory: Arc<CtxFactory>,
+    root_node: Arc<SwappableRootNode<'static, Query, Mutation>>,
+    handler: GraphQLHandler,
+    phantom: PhantomData<fn() -> CtxT>,
+}
+
+impl<CtxFactory, Query, Mutation, CtxT>
+    GraphQLService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    /// Build a new GraphQL service
+    ///
+    /// The context factory will receive the metadata of the request, before
+    /// its body is read, and is expected to construct a context object for the
+    /// given schema. Requests it refuses are answered with its error, see
+    /// `http::context`.
+    pub fn new(context_factory: CtxFactory, root_node: RootNode<'static, Query, Mutation>) -> Self {
+        GraphQLService::from_swappable(context_factory, Arc::new(SwappableRootNode::new(root_node)))
+    }
+
+    /// Build a new GraphQL service for a root node that may be replaced while
+    /// the server is running
+    ///
+    /// Every request is executed with the root node that is current when its
+    /// body has been read, see `SwappableRootNode`.
+    pub fn from_swappable(
+        context_factory: CtxFactory,
+        root_node: Arc<SwappableRootNode<'static, Query, Mutation>>,
+    )
+        -> Self
+    {
+        GraphQLService {
+            context_factory: Arc::new(context_factory),
+            root_node: root_node,
+            handler: GraphQLHandler::new(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Set the layout of the JSON responses, see `http::JsonFormat`
+    pub fn with_json_format(mut self, json_format: http::JsonFormat) -> Self {
+        self.handler = self.handler.with_json_format(json_format);
+        self
+    }
+
+    /// Limit the size of incoming requests, see `http::limits`
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.handler = self.handler.with_limits(limits);
+        self
+    }
+}
+
+impl<CtxFactory, Query, Mutation, CtxT>
+    Clone
+    for GraphQLService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    fn clone(&self) -> Self {
+        GraphQLService {
+            context_factory: self.context_factory.clone(),
+            root_node: self.root_node.clone(),
+            handler: self.handler.clone(),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<CtxFactory, Query, Mutation, CtxT>
+    Service
+    for GraphQLService<CtxFactory, Query, Mutation, CtxT>
+    where CtxFactory: ContextFactory<CtxT> + 'static,
+          CtxT: Send + 'static,
+          Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync
+{
+    type ReqBody = Body;
+    type ResBody = Body;
+    type Error = ::hyper::Error;
+    type Future = ResponseFuture;
+
+    fn call(&mut self, req: Request<Body>) -> ResponseFuture {
+        let declared_length = header_str(&req, header::CONTENT_LENGTH)
+            .and_then(|value| value.parse::<usize>().ok());
+
+        if let Some(length) = declared_length {
+            if let Err(err) = self.handler.limits().check_body_length(length) {
+                return Box::new(future::ok(hyper_response(err.into())));
+            }
+        }
+
+        let method = req.method().as_str().to_owned();
+        let query_string = req.uri().query().map(str::to_owned);
+        let content_type = header_str(&req, header::CONTENT_TYPE).map(str::to_owned);
+        let accept = header_str(&req, header::ACCEPT).map(str::to_owned);
+
+        let context = match self.context_factory.create(&request_metadata(&req)) {
+            Ok(context) => context,
+            Err(err) => return Box::new(future::ok(hyper_response(err.into()))),
+        };
+        let service = self.clone();
+
+        Box::new(req.into_body().concat2().map(move |body| {
+            let parts = RequestParts::new(&method, query_string.as_ref().map(String::as_str))
+                .with_content_type(content_type.as_ref().map(String::as_str))
+                .with_accept(accept.as_ref().map(String::as_str));
+
+            let response = match service.handler.parse(&parts, &body[..]) {
+                Ok(parsed) => service.handler.execute(&parsed, &service.root_node.load(), &context),
+                Err(response) => response,
+            };
+
+            hyper_response(response)
+        }))
+    }
+}
+
+/// Build a response rendering GraphiQL - a graphical query editor interface
+///
+/// The URL should point to where the `GraphQLService` is served, e.g.
+/// `"/graphql"`.
+pub fn graphiql(graphql_url: &str) -> Response<Body> {
+    html_response(graphiql_source(graphql_url))
+}
+
+/// Build a response rendering GraphQL Playground - an alternative query
+/// editor interface
+pub fn playground(graphql_url: &str) -> Response<Body> {
+    html_response(playground_source(graphql_url, None))
+}
+
+/// Describe a hyper request for a `ContextFactory`
+///
+/// Headers whose values aren't visible ASCII are left out.
+pub fn request_metadata(req: &Request<Body>) -> RequestMetadata {
+    req.headers().iter().fold(
+        RequestMetadata::new(req.method().as_str(), req.uri().query()),
+        |metadata, (name, value)| match value.to_str() {
+            Ok(value) => metadata.with_header(name.as_str(), value),
+            Err(_) => metadata,
+        })
+}
+
+fn header_str(req: &Request<Body>, name: header::HeaderName) -> Option<&str> {
+    req.headers().get(name).and_then(|value| value.to_str().ok())
+}
+
+fn html_response(body: String) -> Response<Body> {
+    hyper_response(HandlerResponse::html(body))
+}
+
+fn hyper_response(response: HandlerResponse) -> Response<Body> {
+    let mut hyper_response = Response::new(Body::empty());
+    *hyper_response.status_mut() = StatusCode::from_u16(response.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    for &(name, value) in response.headers() {
+        hyper_response.headers_mut().insert(name, HeaderValue::from_static(value));
+    }
+    *hyper_response.body_mut() = Body::from(response.into_body());
+
+    hyper_response
+}
@@ -5,20 +5,26 @@
 
 use iron::prelude::*;
 use iron::middleware::Handler;
+use iron::headers::Headers;
+use iron::method::Method;
 use iron::mime::Mime;
 use iron::status;
-use iron::method;
-use urlencoded::{UrlEncodedQuery, UrlDecodingError};
+use iron::typemap;
 
-use std::io::Read;
-use std::error::Error;
-use std::fmt;
+use std::str;
+use std::sync::Arc;
 
-use serde_json;
-use serde_json::error::Error as SerdeError;
-
-use ::{InputValue, GraphQLType, RootNode};
+use ::{GraphQLType, RootNode, SwappableRootNode};
 use ::http;
+use graphiql::GraphiQLSource;
+#[cfg(feature="compression")] use http::compression::{Compression, EncodedResponse};
+use http::context::{ContextError, ContextFactory, RequestMetadata};
+use http::cors::Cors;
+use http::endpoints;
+use http::handler::{RequestParts, HandlerResponse};
+use http::playground::PlaygroundSource;
+use http::multipart::UploadedFiles;
+use http::limits::RequestLimits;
 
 /// Handler that executes GraphQL queries in the given schema
 ///
@@ -29,9 +35,14 @@ use ::http;
 /// POST requests support both queries and variables. POST a JSON document to
 /// this endpoint containing the field `"query"` and optionally `"variables"`.
 /// The variables should be a JSON object containing the variable to value
-/// mapping.
+/// mapping. A JSON array of such documents is executed as a batch, and
+/// answered with an array of responses. Alternatively, POST the query itself
+/// with the `application/graphql` content type.
+///
+/// Files uploaded with a multipart request are stored in the request's
+/// extensions under the `Uploads` key before the context factory is called.
 pub struct GraphQLHandler<'a, CtxFactory, Query, Mutation, CtxT>
-    where CtxFactory: Fn(&mut Request) -> CtxT + Send + Sync + 'static,
+    where CtxFactory: IronContextFactory<CtxT>,
           CtxT: 'static,
           Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
           Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
@@ -39,48 +50,86 @@ pub struct GraphQLHandler<'a, CtxFactory, Query, Mutation, CtxT>
           Mutation::TypeInfo: Send + Sync
 {
     context_factory: CtxFactory,
-    root_node: RootNode<'a, Query, Mutation>,
+    root_node: Arc<SwappableRootNode<'a, Query, Mutation>>,
+    handler: http::handler::GraphQLHandler,
+    response_hook: Option<Box<Fn(&Request, &mut http::GraphQLBatchResponse) + Send + Sync>>,
+    cors: Option<Cors>,
+    #[cfg(feature="compression")]
+    compression: Option<Compression>,
 }
 
-/// Handler that renders GraphiQL - a graphical query editor interface
-pub struct GraphiQLHandler {
-    graphql_url: String,
+/// Builds the context of each request from the Iron request
+///
+/// This is implemented for closures taking the Iron request and returning the
+/// context, and for any framework-independent `ContextFactory` wrapped in
+/// `SharedContextFactory`.
+pub trait IronContextFactory<CtxT>: Send + Sync + 'static {
+    /// Build the context for a request, or refuse to execute it
+    fn create(&self, req: &mut Request) -> Result<CtxT, ContextError>;
 }
 
+/// Use a framework-independent `ContextFactory` with `GraphQLHandler`
+///
+/// ```rust,ignore
+/// let context_factory = SharedContextFactory(|request: &RequestMetadata| {
+///     match request.header("authorization") {
+///         Some(token) => Ok(Context::for_token(token)),
+///         None => Err(ContextError::unauthorized("Missing token")),
+///     }
+/// });
+/// let handler = GraphQLHandler::new(context_factory, Query, Mutation);
+/// ```
+pub struct SharedContextFactory<F>(pub F);
+
+/// Key for the files uploaded with a multipart request
+///
+/// The handler stores them in the Iron request's extensions, where the context
+/// factory can take them to make them available to resolvers:
+///
+/// ```rust,ignore
+/// fn context_factory(req: &mut Request) -> Context {
+///     Context {
+///         uploads: req.extensions.remove::<Uploads>().unwrap_or_default(),
+///     }
+/// }
+/// ```
+pub struct Uploads;
+
+impl typemap::Key for Uploads {
+    type Value = UploadedFiles;
+}
 
-fn get_single_value<T>(mut values: Vec<T>) -> IronResult<T> {
-    if values.len() == 1 {
-        Ok(values.remove(0))
-    }
-    else {
-        Err(GraphQLIronError::InvalidData("Duplicate URL query parameter").into())
-    }
+/// Handler that renders GraphiQL - a graphical query editor interface
+pub struct GraphiQLHandler {
+    source: GraphiQLSource,
 }
 
-fn parse_url_param(params: Option<Vec<String>>) -> IronResult<Option<String>> {
-    if let Some(values) = params {
-        get_single_value(values).map(Some)
-    }
-    else {
-        Ok(None)
-    }
+/// Handler that serves the embedded GraphiQL scripts and stylesheets
+///
+/// Requires the `graphiql-embedded` feature. The file is looked up by the last
+/// segment of the request path, so mount the handler under the URL given to
+/// `GraphiQLSource::assets_url`.
+#[cfg(feature="graphiql-embedded")]
+pub struct GraphiQLAssetsHandler;
+
+/// Handler that answers liveness checks, see `http::endpoints::health_response`
+pub struct HealthHandler;
+
+/// Handler that serves the schema in the GraphQL schema definition language
+pub struct SchemaHandler {
+    schema_language: String,
 }
 
-fn parse_variable_param(params: Option<Vec<String>>) -> IronResult<Option<InputValue>> {
-    if let Some(values) = params {
-        Ok(serde_json::from_str::<InputValue>(get_single_value(values)?.as_ref())
-            .map(Some)
-            .map_err(GraphQLIronError::Serde)?)
-    }
-    else {
-        Ok(None)
-    }
+/// Handler that renders GraphQL Playground - an alternative query editor
+/// interface
+pub struct PlaygroundHandler {
+    source: PlaygroundSource,
 }
 
 
 impl<'a, CtxFactory, Query, Mutation, CtxT>
     GraphQLHandler<'a, CtxFactory, Query, Mutation, CtxT>
-    where CtxFactory: Fn(&mut Request) -> CtxT + Send + Sync + 'static,
+    where CtxFactory: IronContextFactory<CtxT>,
           CtxT: 'static,
           Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
           Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
@@ -92,54 +141,175 @@ impl<'a, CtxFactory, Query, Mutation, CtxT>
     /// The context factory will receive the Iron request object and is
     /// expected to construct a context object for the given schema. This can
     /// be used to construct e.g. database connections or similar data that
-    /// the schema needs to execute the query.
+    /// the schema needs to execute the query. Requests the factory refuses
+    /// are answered with its `ContextError`, see `IronContextFactory`.
     pub fn new(context_factory: CtxFactory, query: Query, mutation: Mutation) -> Self
         where Query: GraphQLType<TypeInfo=()>,
               Mutation: GraphQLType<TypeInfo=()>
     {
-        GraphQLHandler {
-            context_factory: context_factory,
-            root_node: RootNode::new(query, mutation),
-        }
+        GraphQLHandler::from_swappable(
+            context_factory,
+            Arc::new(SwappableRootNode::new(RootNode::new(query, mutation))))
     }
 
     /// Build a new GraphQL handler, providing type info objects
     pub fn new_with_info(context_factory: CtxFactory, query: Query, mutation: Mutation, query_info: Query::TypeInfo, mutation_info: Mutation::TypeInfo) -> Self {
+        GraphQLHandler::from_swappable(
+            context_factory,
+            Arc::new(SwappableRootNode::new(RootNode::new_with_info(query, mutation, query_info, mutation_info))))
+    }
+
+    /// Build a new GraphQL handler for a root node that may be replaced while
+    /// the server is running
+    ///
+    /// Keep a clone of the `Arc` to swap in a new root node later, see
+    /// `SwappableRootNode`.
+    pub fn from_swappable(context_factory: CtxFactory, root_node: Arc<SwappableRootNode<'a, Query, Mutation>>) -> Self {
         GraphQLHandler {
             context_factory: context_factory,
-            root_node: RootNode::new_with_info(query, mutation, query_info, mutation_info),
+            root_node: root_node,
+            handler: http::handler::GraphQLHandler::new(),
+            response_hook: None,
+            cors: None,
+            #[cfg(feature="compression")]
+            compression: None,
         }
     }
 
-    fn handle_get(&self, req: &mut Request) -> IronResult<http::GraphQLRequest> {
-        let url_query_string = req.get_mut::<UrlEncodedQuery>()
-            .map_err(|e| GraphQLIronError::Url(e))?;
-    
-        let input_query = parse_url_param(url_query_string.remove("query"))?
-            .ok_or_else(|| GraphQLIronError::InvalidData("No query provided"))?;
-        let operation_name = parse_url_param(url_query_string.remove("operationName"))?;
-        let variables = parse_variable_param(url_query_string.remove("variables"))?;
+    /// Set the layout of the JSON responses
+    ///
+    /// Responses are pretty-printed by default. Use `JsonFormat::Compact` to
+    /// save bandwidth, or `JsonFormat::Canonical` if responses are cached or
+    /// signed.
+    pub fn with_json_format(mut self, json_format: http::JsonFormat) -> Self {
+        self.handler = self.handler.with_json_format(json_format);
+        self
+    }
+
+    /// Reject JSON request bodies with unknown keys or values of the wrong type
+    ///
+    /// The error message says where in the body the problem is, see
+    /// `http::GraphQLBatchRequest::from_json_strict`.
+    pub fn with_strict_parsing(mut self) -> Self {
+        self.handler = self.handler.with_strict_parsing();
+        self
+    }
 
-        Ok(http::GraphQLRequest::new(input_query, operation_name, variables))
+    /// Follow the GraphQL over HTTP specification
+    ///
+    /// The response media type is negotiated from the `Accept` header, see
+    /// `http::over_http`. Clients accepting
+    /// `application/graphql-response+json` get a 400 status only for requests
+    /// that couldn't be executed, and others always get a 200 status.
+    /// Requests with other methods than GET and POST are answered with a 405
+    /// status and an `Allow` header.
+    pub fn with_graphql_over_http(mut self) -> Self {
+        self.handler = self.handler.with_graphql_over_http();
+        self
     }
 
-    fn handle_post(&self, req: &mut Request) -> IronResult<http::GraphQLRequest> {
-        let mut request_payload = String::new();
-        itry!(req.body.read_to_string(&mut request_payload));
-        
-        Ok(serde_json::from_str::<http::GraphQLRequest>(request_payload.as_str())
-            .map_err(|err| GraphQLIronError::Serde(err))?)
+    /// Limit the size of incoming requests
+    ///
+    /// Requests exceeding the limits are rejected before they are parsed, with
+    /// a 413 status for bodies that are too large and 400 otherwise.
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.handler = self.handler.with_limits(limits);
+        self
     }
 
-    fn execute(&self, context: &CtxT, request: http::GraphQLRequest) -> IronResult<Response> {
-        let response = request.execute(
-            &self.root_node,
-            context,
-        );
-        let content_type = "application/json".parse::<Mime>().unwrap();
-        let json = serde_json::to_string_pretty(&response).unwrap();
-        let status = if response.is_ok() { status::Ok } else { status::BadRequest };
-        Ok(Response::with((content_type, status, json)))
+    /// Run a function on every response before it is serialized
+    ///
+    /// This can be used to inspect the data and errors, or to add entries to
+    /// the `extensions` of the responses:
+    ///
+    /// ```rust,ignore
+    /// let handler = GraphQLHandler::new(context_factory, Query, Mutation)
+    ///     .with_response_hook(|_, response| {
+    ///         for response in response.responses_mut() {
+    ///             response.extensions_mut().insert("server", Value::string("iron"));
+    ///         }
+    ///     });
+    /// ```
+    pub fn with_response_hook<F>(mut self, hook: F) -> Self
+        where F: Fn(&Request, &mut http::GraphQLBatchResponse) + Send + Sync + 'static
+    {
+        self.response_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Answer cross-origin requests, including preflight `OPTIONS` requests,
+    /// as allowed by the given configuration, see `http::cors`
+    pub fn with_cors(mut self, cors: Cors) -> Self {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Compress responses for clients accepting gzip or deflate, see
+    /// `http::compression`
+    ///
+    /// Requires the `compression` feature.
+    #[cfg(feature="compression")]
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    fn execute_request(&self, req: &mut Request) -> Response {
+        let parsed = {
+            let parts = RequestParts::new(req.method.as_ref(), req.url.query())
+                .with_content_type(raw_header(&req.headers, "Content-Type"))
+                .with_accept(raw_header(&req.headers, "Accept"));
+
+            self.handler.parse(&parts, &mut req.body)
+        };
+
+        let mut parsed = match parsed {
+            Ok(parsed) => parsed,
+            Err(response) => return handler_response(response),
+        };
+        req.extensions.insert::<Uploads>(parsed.take_uploads());
+
+        let context = match self.context_factory.create(req) {
+            Ok(context) => context,
+            Err(err) => return handler_response(err.into()),
+        };
+
+        let mut response = parsed.request().execute(&self.root_node.load(), &context);
+        if let Some(ref hook) = self.response_hook {
+            hook(req, &mut response);
+        }
+
+        self.send(req, self.handler.respond(parsed.media_type(), &response))
+    }
+
+    #[cfg(feature="compression")]
+    fn send(&self, req: &Request, response: HandlerResponse) -> Response {
+        match self.compression {
+            Some(ref compression) => encoded_response(
+                compression.compress(response, raw_header(&req.headers, "Accept-Encoding"))),
+            None => handler_response(response),
+        }
+    }
+
+    #[cfg(not(feature="compression"))]
+    fn send(&self, _: &Request, response: HandlerResponse) -> Response {
+        handler_response(response)
+    }
+}
+
+impl<F, CtxT> IronContextFactory<CtxT> for F
+    where F: Fn(&mut Request) -> CtxT + Send + Sync + 'static
+{
+    fn create(&self, req: &mut Request) -> Result<CtxT, ContextError> {
+        Ok(self(req))
+    }
+}
+
+impl<F, CtxT> IronContextFactory<CtxT> for SharedContextFactory<F>
+    where F: ContextFactory<CtxT> + 'static
+{
+    fn create(&self, req: &mut Request) -> Result<CtxT, ContextError> {
+        self.0.create(&request_metadata(req))
     }
 }
 
@@ -149,8 +319,13 @@ impl GraphiQLHandler {
     /// The provided URL should point to the URL of the attached `GraphQLHandler`. It can be
     /// relative, so a common value could be `"/graphql"`.
     pub fn new(graphql_url: &str) -> GraphiQLHandler {
+        GraphiQLHandler::from_source(GraphiQLSource::new(graphql_url))
+    }
+
+    /// Build a GraphiQL handler rendering a customized interface
+    pub fn from_source(source: GraphiQLSource) -> GraphiQLHandler {
         GraphiQLHandler {
-            graphql_url: graphql_url.to_owned(),
+            source: source,
         }
     }
 }
@@ -158,92 +333,189 @@ impl GraphiQLHandler {
 impl<'a, CtxFactory, Query, Mutation, CtxT>
     Handler
     for GraphQLHandler<'a, CtxFactory, Query, Mutation, CtxT>
-    where CtxFactory: Fn(&mut Request) -> CtxT + Send + Sync + 'static,
+    where CtxFactory: IronContextFactory<CtxT>,
           CtxT: 'static,
           Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
           Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static, 'a: 'static,
           Query::TypeInfo: Send + Sync,
           Mutation::TypeInfo: Send + Sync
 {
-    fn handle(&self, mut req: &mut Request) -> IronResult<Response> {
-        let context = (self.context_factory)(req);
-
-        let graphql_request = match req.method {
-            method::Get => self.handle_get(&mut req)?,
-            method::Post => self.handle_post(&mut req)?,
-            _ => return Ok(Response::with((status::MethodNotAllowed)))
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
+        let cors = match self.cors {
+            Some(ref cors) => cors,
+            None => return Ok(self.execute_request(req)),
         };
 
-        self.execute(&context, graphql_request)
+        let origin = raw_header(&req.headers, "Origin").map(str::to_owned);
+        let origin = origin.as_ref().map(String::as_str);
+
+        if req.method == Method::Options {
+            let preflight = cors.preflight(
+                origin,
+                raw_header(&req.headers, "Access-Control-Request-Method"),
+                raw_header(&req.headers, "Access-Control-Request-Headers"));
+
+            let mut response = Response::with(status::Status::from_u16(preflight.status()));
+            add_headers(&mut response, preflight.headers());
+            return Ok(response);
+        }
+
+        let mut response = self.execute_request(req);
+        add_headers(&mut response, &cors.headers(origin));
+        Ok(response)
     }
 }
 
-impl Handler for GraphiQLHandler {
+impl PlaygroundHandler {
+    /// Build a new GraphQL Playground handler targeting the specified URL.
+    ///
+    /// As with `GraphiQLHandler`, the URL should point to the attached
+    /// `GraphQLHandler`.
+    pub fn new(graphql_url: &str) -> PlaygroundHandler {
+        PlaygroundHandler::from_source(PlaygroundSource::new(graphql_url))
+    }
+
+    /// Build a Playground handler rendering a customized interface
+    pub fn from_source(source: PlaygroundSource) -> PlaygroundHandler {
+        PlaygroundHandler {
+            source: source,
+        }
+    }
+
+    /// Set the URL of the WebSocket endpoint used for subscriptions
+    pub fn with_subscriptions_url(self, subscriptions_url: &str) -> PlaygroundHandler {
+        PlaygroundHandler::from_source(self.source.subscriptions_url(subscriptions_url))
+    }
+}
+
+impl SchemaHandler {
+    /// Build a handler serving the schema of the given root node
+    pub fn new<Query, Mutation>(root_node: &RootNode<Query, Mutation>) -> SchemaHandler
+        where Query: GraphQLType,
+              Mutation: GraphQLType,
+    {
+        SchemaHandler {
+            schema_language: endpoints::schema_response(root_node).into_body(),
+        }
+    }
+}
+
+impl Handler for HealthHandler {
     fn handle(&self, _: &mut Request) -> IronResult<Response> {
-        let content_type = "text/html".parse::<Mime>().unwrap();
+        Ok(endpoint_response(endpoints::health_response()))
+    }
+}
+
+impl Handler for SchemaHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        let content_type = "text/plain; charset=utf-8".parse::<Mime>().unwrap();
 
-        Ok(Response::with((
-            content_type,
-            status::Ok,
-            ::graphiql::graphiql_source(&self.graphql_url),
-        )))
+        Ok(Response::with((content_type, status::Ok, self.schema_language.clone())))
     }
 }
 
-#[derive(Debug)]
-enum GraphQLIronError {
-    Serde(SerdeError),
-    Url(UrlDecodingError),
-    InvalidData(&'static str),
+fn endpoint_response(response: endpoints::EndpointResponse) -> Response {
+    let content_type = response.content_type().parse::<Mime>().unwrap();
+    let status = status::Status::from_u16(response.status());
+
+    Response::with((content_type, status, response.into_body()))
 }
 
-impl fmt::Display for GraphQLIronError {
-    fn fmt(&self, mut f: &mut fmt::Formatter) -> fmt::Result {
-        match *self {
-            GraphQLIronError::Serde(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::Url(ref err) => fmt::Display::fmt(err, &mut f),
-            GraphQLIronError::InvalidData(ref err) => fmt::Display::fmt(err, &mut f),
+impl Handler for GraphiQLHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        Ok(handler_response(HandlerResponse::html(self.source.render())))
+    }
+}
+
+#[cfg(feature="graphiql-embedded")]
+impl Handler for GraphiQLAssetsHandler {
+    fn handle(&self, req: &mut Request) -> IronResult<Response> {
+        use iron::mime::{TopLevel, SubLevel};
+
+        let asset = req.url.path().last().and_then(|name| ::graphiql::assets::get(name));
+
+        match asset {
+            Some(asset) => {
+                let content_type = match asset.content_type {
+                    "text/css" => Mime(TopLevel::Text, SubLevel::Css, vec![]),
+                    "application/javascript" => Mime(TopLevel::Application, SubLevel::Javascript, vec![]),
+                    _ => Mime(TopLevel::Application, SubLevel::OctetStream, vec![]),
+                };
+
+                Ok(Response::with((content_type, status::Ok, asset.content.to_vec())))
+            },
+            None => Ok(Response::with(status::NotFound)),
         }
     }
 }
 
-impl Error for GraphQLIronError {
-    fn description(&self) -> &str {
-       match *self {
-           GraphQLIronError::Serde(ref err) => err.description(),
-           GraphQLIronError::Url(ref err) => err.description(),
-           GraphQLIronError::InvalidData(ref err) => err,
-       }
+impl Handler for PlaygroundHandler {
+    fn handle(&self, _: &mut Request) -> IronResult<Response> {
+        Ok(handler_response(HandlerResponse::html(self.source.render())))
+    }
+}
+
+fn handler_response(response: HandlerResponse) -> Response {
+    let mut iron_response = Response::with((status::Status::from_u16(response.status()), response.body().to_owned()));
+    for &(name, value) in response.headers() {
+        iron_response.headers.set_raw(name, vec![value.as_bytes().to_vec()]);
     }
 
-    fn cause(&self) -> Option<&Error> {
-        match *self {
-            GraphQLIronError::Serde(ref err) => Some(err),
-            GraphQLIronError::Url(ref err) => Some(err),
-            GraphQLIronError::InvalidData(_) => None,
-        }
+    iron_response
+}
+
+/// Describe an Iron request for a `ContextFactory`
+pub fn request_metadata(req: &Request) -> RequestMetadata {
+    req.headers.iter().fold(
+        RequestMetadata::new(req.method.as_ref(), req.url.query()),
+        |metadata, header| metadata.with_header(header.name(), &header.value_string()))
+}
+
+#[cfg(feature="compression")]
+fn encoded_response(response: EncodedResponse) -> Response {
+    let mut iron_response = Response::with(status::Status::from_u16(response.status()));
+    for &(name, value) in response.headers() {
+        iron_response.headers.set_raw(name, vec![value.as_bytes().to_vec()]);
     }
+    iron_response.body = Some(Box::new(response.into_body()));
+
+    iron_response
 }
 
-impl From<GraphQLIronError> for IronError {
-    fn from(err: GraphQLIronError) -> IronError {
-        let message = format!("{}", err);
-        IronError::new(err, (status::BadRequest, message))
+// Add headers to a response, appending to an existing `Vary` header
+fn add_headers(response: &mut Response, headers: &[(&'static str, String)]) {
+    for &(name, ref value) in headers {
+        let mut value = value.clone().into_bytes();
+        if name == "Vary" {
+            if let Some(existing) = response.headers.get_raw(name).and_then(|values| values.first()) {
+                value = [&existing[..], b", ", &value[..]].concat();
+            }
+        }
+        response.headers.set_raw(name, vec![value]);
     }
 }
 
+fn raw_header<'a>(headers: &'a Headers, name: &str) -> Option<&'a str> {
+    headers.get_raw(name)
+        .and_then(|values| values.first())
+        .and_then(|value| str::from_utf8(value).ok())
+}
+
 
 #[cfg(test)]
 mod tests {
     use iron::prelude::*;
     use iron_test::{request, response};
-    use iron::{Handler, Headers};
+    use iron::{status, Handler, Headers};
+    use iron::headers::{Accept, Allow, ContentType};
 
     use ::tests::model::Database;
     use ::http::tests as http_tests;
+    use http::context::{ContextError, RequestMetadata};
+    use http::cors::Cors;
     use types::scalars::EmptyMutation;
 
-    use super::GraphQLHandler;
+    use super::{GraphQLHandler, GraphiQLHandler, PlaygroundHandler, SharedContextFactory};
 
     struct TestIronIntegration;
 
@@ -265,6 +537,37 @@ mod tests {
                 &make_handler(),
             ))
         }
+
+        fn post_graphql(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            let mut headers = Headers::new();
+            headers.set(ContentType("application/graphql".parse().unwrap()));
+
+            make_test_response(request::post(
+                &("http://localhost:3000".to_owned() + url),
+                headers,
+                body,
+                &make_handler(),
+            ))
+        }
+
+        fn post_with_content_type(&self, url: &str, content_type: &str, body: &str) -> http_tests::TestResponse {
+            let mut headers = Headers::new();
+            headers.set_raw("Content-Type", vec![content_type.as_bytes().to_vec()]);
+
+            make_test_response(request::post(
+                &("http://localhost:3000".to_owned() + url),
+                headers,
+                body,
+                &make_handler(),
+            ))
+        }
+
+        fn features(&self) -> http_tests::IntegrationFeatures {
+            http_tests::IntegrationFeatures {
+                uploads: true,
+                .. http_tests::IntegrationFeatures::default()
+            }
+        }
     }
 
     #[test]
@@ -274,6 +577,74 @@ mod tests {
         http_tests::run_http_test_suite(&integration);
     }
 
+    #[test]
+    fn test_graphiql_and_playground_handlers() {
+        let handlers: Vec<Box<Handler>> = vec![
+            Box::new(GraphiQLHandler::new("/graphql")),
+            Box::new(PlaygroundHandler::new("/graphql")),
+        ];
+
+        for handler in handlers {
+            let response = make_test_response(request::get(
+                "http://localhost:3000/graphiql",
+                Headers::new(),
+                &handler,
+            ));
+
+            assert_eq!(response.status_code, 200);
+            assert_eq!(response.content_type, "text/html; charset=utf-8");
+            assert!(response.body.unwrap().contains("'/graphql'"));
+        }
+    }
+
+    #[test]
+    fn test_shared_context_factory() {
+        let handler = GraphQLHandler::new(
+            SharedContextFactory(|request: &RequestMetadata| match request.header("authorization") {
+                Some(_) => Ok(Database::new()),
+                None => Err(ContextError::unauthorized("Not logged in")),
+            }),
+            Database::new(),
+            EmptyMutation::<Database>::new(),
+        );
+
+        let refused = make_test_response(request::get(
+            "http://localhost:3000/?query={hero{name}}",
+            Headers::new(),
+            &handler,
+        ));
+        assert_eq!(refused.status_code, 401);
+        assert_eq!(refused.body.unwrap(), r#"{"errors":[{"extensions":{"code":"UNAUTHENTICATED"},"message":"Not logged in"}]}"#);
+
+        let mut headers = Headers::new();
+        headers.set_raw("Authorization", vec![b"Bearer token".to_vec()]);
+        let executed = make_test_response(request::get(
+            "http://localhost:3000/?query={hero{name}}",
+            headers,
+            &handler,
+        ));
+        assert_eq!(executed.status_code, 200);
+    }
+
+    #[test]
+    fn test_cors() {
+        let handler = GraphQLHandler::new(context_factory, Database::new(), EmptyMutation::<Database>::new())
+            .with_cors(Cors::new().allow_origin("https://a.example"));
+
+        let mut headers = Headers::new();
+        headers.set_raw("Origin", vec![b"https://a.example".to_vec()]);
+        headers.set_raw("Access-Control-Request-Method", vec![b"POST".to_vec()]);
+        let preflight = request::options("http://localhost:3000/", headers, &handler).unwrap();
+        assert_eq!(preflight.status, Some(status::NoContent));
+        assert_eq!(preflight.headers.get_raw("Access-Control-Allow-Methods"), Some(&[b"GET, POST".to_vec()][..]));
+
+        let mut headers = Headers::new();
+        headers.set_raw("Origin", vec![b"https://a.example".to_vec()]);
+        let response = request::get("http://localhost:3000/?query={hero{name}}", headers, &handler).unwrap();
+        assert_eq!(response.status, Some(status::Ok));
+        assert_eq!(response.headers.get_raw("Access-Control-Allow-Origin"), Some(&[b"https://a.example".to_vec()][..]));
+    }
+
     fn context_factory(_: &mut Request) -> Database {
         Database::new()
     }
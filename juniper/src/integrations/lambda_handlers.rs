@@ -0,0 +1,302 @@
+//! Optional adapter for [AWS Lambda](https://aws.amazon.com/lambda/) functions
+//! behind an API Gateway proxy integration. Requires the `lambda-handlers`
+//! feature enabled.
+//!
+//! The adapter doesn't depend on a Lambda runtime. Decode the event the
+//! runtime hands over with `ProxyRequest::from_json`, and return the
+//! serialized `ProxyResponse`:
+//!
+//! ```rust,ignore
+//! fn handler(event: &str) -> String {
+//!     let request = ProxyRequest::from_json(event).unwrap();
+//!     let response = execute(&request, &root_node, &Database::new());
+//!
+//!     serde_json::to_string(&response).unwrap()
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use serde::ser;
+use serde::ser::SerializeMap;
+use serde_json;
+
+use ::{GraphQLType, RootNode};
+use ast::InputValue;
+use http::{GraphQLRequest, GraphQLBatchRequest, JsonFormat};
+use http::handler::HandlerResponse;
+
+/// The parts of an API Gateway proxy event that are needed to execute a
+/// GraphQL request
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyRequest {
+    http_method: String,
+    #[serde(default)]
+    headers: Option<HashMap<String, String>>,
+    #[serde(default)]
+    query_string_parameters: Option<HashMap<String, String>>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    is_base64_encoded: bool,
+}
+
+/// The response to return from the Lambda function
+///
+/// This serializes to the structure API Gateway expects from proxy
+/// integrations, with the GraphQL response as the body.
+#[derive(Debug)]
+pub struct ProxyResponse {
+    status_code: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl ProxyRequest {
+    /// Decode a proxy event
+    pub fn from_json(event: &str) -> Result<ProxyRequest, serde_json::Error> {
+        serde_json::from_str(event)
+    }
+
+    /// The HTTP method of the request
+    pub fn http_method(&self) -> &str {
+        &self.http_method
+    }
+
+    /// Look up a header, ignoring the case of its name
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.as_ref().and_then(|headers| headers.iter()
+            .find(|&(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str()))
+    }
+
+    // API Gateway has already decoded the URL query parameters, so they can't
+    // go through `GraphQLRequest::from_query_params`
+    fn parse_get(&self) -> Result<GraphQLRequest, String> {
+        let param = |name: &str| self.query_string_parameters.as_ref()
+            .and_then(|params| params.get(name));
+
+        let query = match param("query") {
+            Some(query) => query.clone(),
+            None => return Err("No query provided".to_owned()),
+        };
+        let variables = match param("variables") {
+            Some(variables) => Some(try!(serde_json::from_str::<InputValue>(variables)
+                .map_err(|err| format!("Invalid variables: {}", err)))),
+            None => None,
+        };
+
+        Ok(GraphQLRequest::new(query, param("operationName").cloned(), variables))
+    }
+
+    fn parse_post(&self) -> Result<GraphQLBatchRequest, String> {
+        let body = match self.body {
+            Some(ref body) if self.is_base64_encoded => try!(
+                decode_base64(body)
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .ok_or_else(|| "Invalid base64 encoded body".to_owned())),
+            Some(ref body) => body.clone(),
+            None => String::new(),
+        };
+
+        let is_graphql = self.header("content-type")
+            .map_or(false, |value| value.split(';').next().unwrap_or("").trim() == "application/graphql");
+
+        if is_graphql {
+            Ok(GraphQLRequest::from_graphql_body(body).into())
+        } else {
+            serde_json::from_str::<GraphQLBatchRequest>(&body).map_err(|err| err.to_string())
+        }
+    }
+}
+
+impl ProxyResponse {
+    /// The HTTP status
+    pub fn status_code(&self) -> u16 {
+        self.status_code
+    }
+
+    /// The body of the response
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+
+    fn error(status_code: u16, message: String) -> ProxyResponse {
+        ProxyResponse {
+            status_code: status_code,
+            content_type: "text/plain; charset=utf-8",
+            body: message,
+        }
+    }
+}
+
+impl ser::Serialize for ProxyResponse {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type", self.content_type);
+        if self.status_code == 405 {
+            headers.insert("Allow", "GET, POST");
+        }
+
+        let mut map = try!(serializer.serialize_map(Some(4)));
+        try!(map.serialize_key("statusCode"));
+        try!(map.serialize_value(&self.status_code));
+        try!(map.serialize_key("headers"));
+        try!(map.serialize_value(&headers));
+        try!(map.serialize_key("body"));
+        try!(map.serialize_value(&self.body));
+        try!(map.serialize_key("isBase64Encoded"));
+        try!(map.serialize_value(&false));
+        map.end()
+    }
+}
+
+/// Execute the GraphQL request of a proxy event
+///
+/// GET requests carry the query in the `query` parameter, POST requests a JSON
+/// document or a batch of them, or the query itself with the
+/// `application/graphql` content type. Malformed requests are answered with a
+/// 400 status, and other methods with a 405 status.
+pub fn execute<CtxT, QueryT, MutationT>(
+    request: &ProxyRequest,
+    root_node: &RootNode<QueryT, MutationT>,
+    context: &CtxT,
+)
+    -> ProxyResponse
+    where QueryT: GraphQLType<Context=CtxT>,
+          MutationT: GraphQLType<Context=CtxT>,
+{
+    let graphql_request = match &*request.http_method.to_ascii_uppercase() {
+        "GET" => request.parse_get().map(GraphQLBatchRequest::from),
+        "POST" => request.parse_post(),
+        _ => return ProxyResponse::error(405, String::new()),
+    };
+
+    let graphql_request = match graphql_request {
+        Ok(graphql_request) => graphql_request,
+        Err(message) => return ProxyResponse::error(400, message),
+    };
+
+    let response = graphql_request.execute(root_node, context);
+
+    match response.to_json(JsonFormat::Compact) {
+        Ok(body) => ProxyResponse {
+            status_code: response.status_code(),
+            content_type: "application/json",
+            body: body,
+        },
+        Err(err) => {
+            let response = HandlerResponse::serialization_error(&err);
+            ProxyResponse {
+                status_code: response.status(),
+                content_type: "application/json",
+                body: response.into_body(),
+            }
+        },
+    }
+}
+
+fn decode_base64(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        if c >= b'A' && c <= b'Z' {
+            Some((c - b'A') as u32)
+        } else if c >= b'a' && c <= b'z' {
+            Some((c - b'a') as u32 + 26)
+        } else if c >= b'0' && c <= b'9' {
+            Some((c - b'0') as u32 + 52)
+        } else if c == b'+' {
+            Some(62)
+        } else if c == b'/' {
+            Some(63)
+        } else {
+            None
+        }
+    }
+
+    let input = input.trim_right_matches('=').as_bytes();
+    let mut decoded = Vec::with_capacity(input.len() * 3 / 4);
+    let mut buffer = 0u32;
+    let mut bits = 0;
+
+    for &c in input {
+        buffer = match value(c) {
+            Some(v) => (buffer << 6) | v,
+            None => return None,
+        };
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(decoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json;
+
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::{execute, ProxyRequest};
+
+    fn run(event: &str) -> serde_json::Value {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let request = ProxyRequest::from_json(event).unwrap();
+
+        serde_json::to_value(&execute(&request, &schema, &Database::new())).unwrap()
+    }
+
+    #[test]
+    fn executes_get_request() {
+        let response = run(r#"{
+            "httpMethod": "GET",
+            "headers": null,
+            "queryStringParameters": {"query": "{ hero { name } }"},
+            "body": null,
+            "isBase64Encoded": false
+        }"#);
+
+        assert_eq!(response["statusCode"], 200);
+        assert_eq!(response["headers"]["Content-Type"], "application/json");
+        assert_eq!(response["body"], r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+        assert_eq!(response["isBase64Encoded"], false);
+    }
+
+    #[test]
+    fn executes_post_request() {
+        let json = run(r#"{
+            "httpMethod": "POST",
+            "headers": {"Content-Type": "application/json"},
+            "body": "{\"query\": \"{ hero { name } }\"}"
+        }"#);
+        assert_eq!(json["body"], r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+
+        // "{ hero { name } }"
+        let base64 = run(r#"{
+            "httpMethod": "POST",
+            "headers": {"content-type": "application/graphql"},
+            "body": "eyBoZXJvIHsgbmFtZSB9IH0=",
+            "isBase64Encoded": true
+        }"#);
+        assert_eq!(base64["body"], r#"{"data":{"hero":{"name":"R2-D2"}}}"#);
+    }
+
+    #[test]
+    fn rejects_invalid_requests() {
+        let missing_query = run(r#"{"httpMethod": "GET", "queryStringParameters": {}}"#);
+        assert_eq!(missing_query["statusCode"], 400);
+        assert_eq!(missing_query["body"], "No query provided");
+
+        let put = run(r#"{"httpMethod": "PUT", "body": "{}"}"#);
+        assert_eq!(put["statusCode"], 405);
+        assert_eq!(put["headers"]["Allow"], "GET, POST");
+    }
+}
@@ -1,3 +1,9 @@
+#[cfg(feature="dev-server")] pub mod dev_server;
+#[cfg(any(feature="anyhow", feature="failure"))] mod error_chains;
+#[cfg(feature="hyper-handlers")] pub mod hyper_handlers;
 #[cfg(feature="iron-handlers")] pub mod iron_handlers;
+#[cfg(feature="lambda-handlers")] pub mod lambda_handlers;
 #[cfg(feature="rocket-handlers")] pub mod rocket_handlers;
 pub mod serde;
+#[cfg(feature="tower-handlers")] pub mod tower_handlers;
+#[cfg(feature="serde_json")] mod serde_json;
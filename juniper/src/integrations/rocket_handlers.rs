@@ -5,43 +5,357 @@
 //! in handler functions in the Rocket framework.
 //!
 //! See the [rocket-server.rs](https://github.com/mhallin/juniper/blob/master/examples/rocket-server.rs)
-//! example for how to use these tools.
+//! example for how to use these tools. For a basic server, `GraphQLMount`
+//! sets up the routes and state in one go.
 
-use std::io::{Cursor, Read};
-use std::error::Error;
+use std::io::Cursor;
+use std::sync::Arc;
 
-use serde_json;
-
-use rocket::Request;
-use rocket::request::{FromForm, FormItems, FromFormValue};
+use rocket::{Request, State, Rocket, Route};
+use rocket::request::{self, FromForm, FormItems, FromRequest};
 use rocket::data::{FromData, Outcome as FromDataOutcome};
+use rocket::handler::Outcome as HandlerOutcome;
 use rocket::response::{Responder, Response, content};
-use rocket::http::{ContentType, Status};
+use rocket::http::{ContentType, Method, Status};
 use rocket::Data;
 use rocket::Outcome::{Forward, Failure, Success};
 
-use ::InputValue;
 use ::http;
+use graphiql::GraphiQLSource;
+#[cfg(feature="compression")] use http::compression::{Compression, EncodedResponse};
+use http::context::{ContextFactory, RequestMetadata};
+use http::cors::{Cors, PreflightResponse};
+use http::handler::{GraphQLHandler, RequestParts, ParsedRequest, HandlerResponse};
+use http::multipart::{self, UploadedFiles};
+use http::limits::RequestLimits;
+use http::over_http::MediaType;
 
 use types::base::GraphQLType;
-use schema::model::RootNode;
+use schema::model::{RootNode, SwappableRootNode};
 
 /// Simple wrapper around an incoming GraphQL request
 ///
 /// See the `http` module for more information. This type can be constructed
 /// automatically from both GET and POST routes by implementing the `FromForm`
-/// and `FromData` traits.
-pub struct GraphQLRequest(http::GraphQLRequest);
+/// and `FromData` traits. POST bodies may also contain a batch of requests as
+/// a JSON array, the query itself with the `application/graphql` content
+/// type, or a multipart request with file uploads.
+///
+/// POST bodies are parsed by the `juniper::http::handler::GraphQLHandler` the
+/// application manages, if any. Otherwise, a handler with the default
+/// settings is used, limited by the managed
+/// `juniper::http::limits::RequestLimits` if there are any. Manage either to
+/// limit the size of the body, which is unbounded by default:
+///
+/// ```rust,ignore
+/// rocket::ignite()
+///     .manage(RequestLimits::new().with_max_body_bytes(64 * 1024))
+/// ```
+///
+/// Requests that can't be parsed fail with a `GraphQLResponse` containing the
+/// error as JSON, with the status the `http::handler` module picks. Take a
+/// `Result` in the route to send it to the client, instead of Rocket's
+/// catcher for the status:
+///
+/// ```rust,ignore
+/// #[post("/graphql", data="<request>")]
+/// fn post_graphql_handler(
+///     context: State<Database>,
+///     request: Result<GraphQLRequest, GraphQLResponse>,
+///     schema: State<Schema>,
+/// ) -> GraphQLResponse {
+///     match request {
+///         Ok(request) => request.execute(&schema, &context),
+///         Err(response) => response,
+///     }
+/// }
+/// ```
+pub struct GraphQLRequest(ParsedRequest, GraphQLHandler);
 
 /// Simple wrapper around the result of executing a GraphQL query
-pub struct GraphQLResponse(Status, String);
+#[derive(Debug)]
+pub struct GraphQLResponse(HandlerResponse);
+
+/// The value of the request's `Accept-Encoding` header, for
+/// `GraphQLResponse::compress`
+///
+/// This request guard never fails.
+pub struct AcceptEncoding(pub Option<String>);
+
+/// The CORS headers of a request, for answering cross-origin requests in
+/// custom routes
+///
+/// This request guard never fails:
+///
+/// ```rust,ignore
+/// #[route(OPTIONS, "/graphql")]
+/// fn preflight(cors_request: CorsRequest, cors: State<Cors>) -> PreflightResponse {
+///     cors_request.preflight(&cors)
+/// }
+///
+/// #[post("/graphql", data="<request>")]
+/// fn post_graphql_handler(
+///     context: State<Database>,
+///     request: GraphQLRequest,
+///     schema: State<Schema>,
+///     cors_request: CorsRequest,
+///     cors: State<Cors>,
+/// ) -> CorsResponse<GraphQLResponse> {
+///     cors_request.respond(&cors, request.execute(&schema, &context))
+/// }
+/// ```
+pub struct CorsRequest {
+    origin: Option<String>,
+    method: Option<String>,
+    headers: Option<String>,
+}
+
+/// A response carrying the CORS headers for the request it answers
+pub struct CorsResponse<R>(R, Vec<(&'static str, String)>);
 
 /// Generate an HTML page containing GraphiQL
 pub fn graphiql_source(graphql_endpoint_url: &str) -> content::HTML<String> {
     content::HTML(::graphiql::graphiql_source(graphql_endpoint_url))
 }
 
+/// Look up one of the embedded GraphiQL scripts and stylesheets by its name
+///
+/// Requires the `graphiql-embedded` feature. Return it from a route like
+/// `/graphiql/<name>`, and pass `/graphiql` to `GraphiQLSource::assets_url`.
+#[cfg(feature="graphiql-embedded")]
+pub fn graphiql_asset(name: &str) -> Option<content::Content<&'static [u8]>> {
+    ::graphiql::assets::get(name).map(|asset| {
+        let content_type = if asset.content_type == "text/css" {
+            ContentType::CSS
+        } else {
+            ContentType::JavaScript
+        };
+
+        content::Content(content_type, asset.content)
+    })
+}
+
+/// Generate an HTML page containing GraphQL Playground
+pub fn playground_source(graphql_endpoint_url: &str, subscriptions_endpoint_url: Option<&str>)
+    -> content::HTML<String>
+{
+    content::HTML(http::playground::playground_source(graphql_endpoint_url, subscriptions_endpoint_url))
+}
+
+/// The routes and state of a basic GraphQL server
+///
+/// This mounts GET and POST routes executing requests at one path, and
+/// optionally GraphiQL at another, and manages everything they need:
+///
+/// ```rust,ignore
+/// let root_node = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+///
+/// GraphQLMount::new(root_node, |_: &RequestMetadata| Ok(Database::new()))
+///     .path("/graphql")
+///     .graphiql_path("/")
+///     .mount(rocket::ignite())
+///     .launch();
+/// ```
+///
+/// The state is looked up by the types of the schema and context, so each
+/// combination of them can only be mounted once. Rocket 0.2 has no fairings,
+/// so this is a builder consuming the `Rocket` instance instead.
+pub struct GraphQLMount<Query, Mutation, CtxT>
+    where Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync,
+          CtxT: 'static,
+{
+    root_node: Arc<SwappableRootNode<'static, Query, Mutation>>,
+    context_factory: Box<ContextFactory<CtxT>>,
+    handler: GraphQLHandler,
+    path: String,
+    graphiql_path: Option<String>,
+    cors: Option<Cors>,
+    #[cfg(feature="compression")]
+    compression: Option<Compression>,
+}
+
+impl<Query, Mutation, CtxT> GraphQLMount<Query, Mutation, CtxT>
+    where Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync,
+          CtxT: 'static,
+{
+    /// Set up a server for the given schema
+    ///
+    /// The context factory receives the metadata of every request and
+    /// constructs the context for executing it, or refuses to, see
+    /// `http::context`. Requests are served at `/graphql`, and GraphiQL at
+    /// `/graphiql`.
+    pub fn new<F>(root_node: RootNode<'static, Query, Mutation>, context_factory: F) -> Self
+        where F: ContextFactory<CtxT> + 'static
+    {
+        GraphQLMount::from_swappable(Arc::new(SwappableRootNode::new(root_node)), context_factory)
+    }
+
+    /// Set up a server for a root node that may be replaced while the server
+    /// is running, see `SwappableRootNode`
+    pub fn from_swappable<F>(root_node: Arc<SwappableRootNode<'static, Query, Mutation>>, context_factory: F) -> Self
+        where F: ContextFactory<CtxT> + 'static
+    {
+        GraphQLMount {
+            root_node: root_node,
+            context_factory: Box::new(context_factory),
+            handler: GraphQLHandler::new(),
+            path: "/graphql".to_owned(),
+            graphiql_path: Some("/graphiql".to_owned()),
+            cors: None,
+            #[cfg(feature="compression")]
+            compression: None,
+        }
+    }
+
+    /// Serve GraphQL requests at the given path
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = path.to_owned();
+        self
+    }
+
+    /// Serve GraphiQL at the given path
+    pub fn graphiql_path(mut self, path: &str) -> Self {
+        self.graphiql_path = Some(path.to_owned());
+        self
+    }
+
+    /// Don't serve GraphiQL
+    pub fn without_graphiql(mut self) -> Self {
+        self.graphiql_path = None;
+        self
+    }
+
+    /// Configure how requests are handled, e.g. to limit their size
+    pub fn handler(mut self, handler: GraphQLHandler) -> Self {
+        self.handler = handler;
+        self
+    }
+
+    /// Answer cross-origin requests, including preflight `OPTIONS` requests,
+    /// as allowed by the given configuration, see `http::cors`
+    pub fn cors(mut self, cors: Cors) -> Self {
+        self.cors = Some(cors);
+        self
+    }
+
+    /// Compress responses for clients accepting gzip or deflate, see
+    /// `http::compression`
+    ///
+    /// Requires the `compression` feature.
+    #[cfg(feature="compression")]
+    pub fn compression(mut self, compression: Compression) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    #[cfg(feature="compression")]
+    fn send<'r>(&self, request: &Request, response: HandlerResponse) -> HandlerOutcome<'r> {
+        let cors_headers = self.cors_headers(request);
+        match self.compression {
+            Some(ref compression) => HandlerOutcome::of(CorsResponse(
+                compression.compress(response, request.headers().get_one("Accept-Encoding")), cors_headers)),
+            None => HandlerOutcome::of(CorsResponse(GraphQLResponse(response), cors_headers)),
+        }
+    }
+
+    #[cfg(not(feature="compression"))]
+    fn send<'r>(&self, request: &Request, response: HandlerResponse) -> HandlerOutcome<'r> {
+        HandlerOutcome::of(CorsResponse(GraphQLResponse(response), self.cors_headers(request)))
+    }
+
+    fn cors_headers(&self, request: &Request) -> Vec<(&'static str, String)> {
+        match self.cors {
+            Some(ref cors) => cors.headers(request.headers().get_one("Origin")),
+            None => Vec::new(),
+        }
+    }
+
+    /// Mount the routes and manage their state
+    pub fn mount(self, rocket: Rocket) -> Rocket {
+        let mut graphql_routes = vec![
+            Route::new(Method::Get, "/", execute_route::<Query, Mutation, CtxT>),
+            Route::new(Method::Post, "/", execute_route::<Query, Mutation, CtxT>),
+        ];
+        if self.cors.is_some() {
+            graphql_routes.push(Route::new(Method::Options, "/", preflight_route::<Query, Mutation, CtxT>));
+        }
+
+        let mut rocket = rocket.mount(&self.path, graphql_routes);
+        if let Some(ref graphiql_path) = self.graphiql_path {
+            rocket = rocket.mount(graphiql_path, vec![
+                Route::new(Method::Get, "/", graphiql_route::<Query, Mutation, CtxT>),
+            ]);
+        }
+
+        rocket.manage(self)
+    }
+}
+
+fn execute_route<'r, Query, Mutation, CtxT>(request: &'r Request, data: Data) -> HandlerOutcome<'r>
+    where Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync,
+          CtxT: 'static,
+{
+    let mount = match request.guard::<State<GraphQLMount<Query, Mutation, CtxT>>>().succeeded() {
+        Some(mount) => mount,
+        None => return HandlerOutcome::failure(Status::InternalServerError),
+    };
+
+    let response = match mount.handler.parse(&request_parts(request), data.open()) {
+        Ok(parsed) => mount.handler.execute_with_context_factory(
+            &parsed, &mount.root_node.load(), &*mount.context_factory, &request_metadata(request)),
+        Err(response) => response,
+    };
+
+    mount.send(request, response)
+}
+
+fn preflight_route<'r, Query, Mutation, CtxT>(request: &'r Request, _: Data) -> HandlerOutcome<'r>
+    where Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync,
+          CtxT: 'static,
+{
+    let mount = request.guard::<State<GraphQLMount<Query, Mutation, CtxT>>>().succeeded();
+    match mount.as_ref().and_then(|mount| mount.cors.as_ref()) {
+        Some(cors) => HandlerOutcome::of(CorsRequest::from(request).preflight(cors)),
+        None => HandlerOutcome::failure(Status::InternalServerError),
+    }
+}
+
+fn graphiql_route<'r, Query, Mutation, CtxT>(request: &'r Request, _: Data) -> HandlerOutcome<'r>
+    where Query: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Mutation: GraphQLType<Context=CtxT> + Send + Sync + 'static,
+          Query::TypeInfo: Send + Sync,
+          Mutation::TypeInfo: Send + Sync,
+          CtxT: 'static,
+{
+    match request.guard::<State<GraphQLMount<Query, Mutation, CtxT>>>().succeeded() {
+        Some(mount) => HandlerOutcome::of(GraphQLResponse(HandlerResponse::html(
+            GraphiQLSource::new(&mount.path).render()))),
+        None => HandlerOutcome::failure(Status::InternalServerError),
+    }
+}
+
 impl GraphQLRequest {
+    /// Take the files uploaded along with a multipart request
+    ///
+    /// Pass them to the resolvers through the context, so they can look up
+    /// their `Upload` arguments. Requests that weren't multipart have no
+    /// files.
+    pub fn take_uploads(&mut self) -> UploadedFiles {
+        self.0.take_uploads()
+    }
+
     /// Execute an incoming GraphQL query
     pub fn execute<CtxT, QueryT, MutationT>(
         &self,
@@ -52,96 +366,264 @@ impl GraphQLRequest {
         where QueryT: GraphQLType<Context=CtxT>,
             MutationT: GraphQLType<Context=CtxT>,
     {
-        let response = self.0.execute(root_node, context);
-        let status = if response.is_ok() { Status::Ok } else { Status::BadRequest };
-        let json = serde_json::to_string_pretty(&response).unwrap();
+        self.execute_with_json_format(root_node, context, http::JsonFormat::default())
+    }
 
-        GraphQLResponse(status, json)
+    /// Execute an incoming GraphQL query, rendering the response in the given
+    /// JSON format
+    pub fn execute_with_json_format<CtxT, QueryT, MutationT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT>,
+        context: &CtxT,
+        json_format: http::JsonFormat,
+    )
+        -> GraphQLResponse
+        where QueryT: GraphQLType<Context=CtxT>,
+            MutationT: GraphQLType<Context=CtxT>,
+    {
+        GraphQLResponse(self.1.clone().with_json_format(json_format).execute(&self.0, root_node, context))
+    }
+
+    /// The underlying request of the `http` module
+    ///
+    /// Execute it directly to inspect or modify the response before it is
+    /// serialized with `GraphQLResponse::from_http`.
+    pub fn as_http(&self) -> &http::GraphQLBatchRequest {
+        self.0.request()
     }
 }
 
-impl<'f> FromForm<'f> for GraphQLRequest {
-    type Error = String;
-
-    fn from_form_items(form_items: &mut FormItems<'f>) -> Result<Self, String> {
-        let mut query = None;
-        let mut operation_name = None;
-        let mut variables = None;
-
-        for (key, value) in form_items {
-            match key {
-                "query" => {
-                    if query.is_some() {
-                        return Err("Query parameter must not occur more than once".to_owned());
-                    }
-                    else {
-                        query = Some(String::from_form_value(value)?);
-                    }
-                }
-                "operation_name" => {
-                    if operation_name.is_some() {
-                        return Err("Operation name parameter must not occur more than once".to_owned());
-                    }
-                    else {
-                        operation_name = Some(String::from_form_value(value)?);
-                    }
-                }
-                "variables" => {
-                    if variables.is_some() {
-                        return Err("Variables parameter must not occur more than once".to_owned());
-                    }
-                    else {
-                        variables = Some(serde_json::from_str::<InputValue>(&String::from_form_value(value)?)
-                            .map_err(|err| err.description().to_owned())?);
-                    }
-                }
-                _ => {}
-            }
-        }
+impl GraphQLResponse {
+    /// Serialize a response of the `http` module
+    ///
+    /// The status is 200 if all requests were successful, and 400 otherwise.
+    pub fn from_http(response: &http::GraphQLBatchResponse, json_format: http::JsonFormat) -> GraphQLResponse {
+        GraphQLResponse(GraphQLHandler::new().with_json_format(json_format).respond(None, response))
+    }
 
-        if let Some(query) = query {
-            Ok(GraphQLRequest(http::GraphQLRequest::new(
-                query,
-                operation_name,
-                variables
-            )))
+    /// Serialize a response of the `http` module following the GraphQL over
+    /// HTTP specification
+    ///
+    /// The media type is negotiated from the value of the request's `Accept`
+    /// header, see `http::over_http`. The response has a 406 status if the
+    /// client accepts neither `application/graphql-response+json` nor
+    /// `application/json`.
+    pub fn from_http_negotiated(
+        response: &http::GraphQLBatchResponse,
+        accept: Option<&str>,
+        json_format: http::JsonFormat,
+    )
+        -> GraphQLResponse
+    {
+        match MediaType::negotiate(accept) {
+            Some(media_type) => GraphQLResponse(
+                GraphQLHandler::new().with_json_format(json_format).respond(Some(media_type), response)),
+            None => GraphQLResponse(HandlerResponse::error(
+                406, "The response can only be sent as application/graphql-response+json or application/json")),
         }
-        else {
-            Err("Query parameter missing".to_owned())
+    }
+}
+
+#[cfg(feature="compression")]
+impl GraphQLResponse {
+    /// Encode the response for a client accepting the given encodings, see
+    /// `http::compression`
+    ///
+    /// ```rust,ignore
+    /// #[post("/graphql", data="<request>")]
+    /// fn post_graphql_handler(
+    ///     context: State<Database>,
+    ///     request: GraphQLRequest,
+    ///     schema: State<Schema>,
+    ///     accept_encoding: AcceptEncoding,
+    /// ) -> EncodedResponse {
+    ///     request.execute(&schema, &context).compress(&Compression::new(), &accept_encoding)
+    /// }
+    /// ```
+    ///
+    /// Requires the `compression` feature.
+    pub fn compress(self, compression: &Compression, accept_encoding: &AcceptEncoding) -> EncodedResponse {
+        compression.compress(self.0, accept_encoding.0.as_ref().map(String::as_str))
+    }
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for AcceptEncoding {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<AcceptEncoding, ()> {
+        Success(AcceptEncoding(request.headers().get_one("Accept-Encoding").map(str::to_owned)))
+    }
+}
+
+impl CorsRequest {
+    /// Answer the request as a preflight request
+    pub fn preflight(&self, cors: &Cors) -> PreflightResponse {
+        cors.preflight(
+            self.origin.as_ref().map(String::as_str),
+            self.method.as_ref().map(String::as_str),
+            self.headers.as_ref().map(String::as_str))
+    }
+
+    /// Add the CORS headers for the request to a response
+    pub fn respond<R>(&self, cors: &Cors, response: R) -> CorsResponse<R> {
+        CorsResponse(response, cors.headers(self.origin.as_ref().map(String::as_str)))
+    }
+}
+
+impl<'a, 'r> From<&'a Request<'r>> for CorsRequest {
+    fn from(request: &'a Request<'r>) -> CorsRequest {
+        let header = |name: &str| request.headers().get_one(name).map(str::to_owned);
+
+        CorsRequest {
+            origin: header("Origin"),
+            method: header("Access-Control-Request-Method"),
+            headers: header("Access-Control-Request-Headers"),
         }
     }
 }
 
+impl<'a, 'r> FromRequest<'a, 'r> for CorsRequest {
+    type Error = ();
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<CorsRequest, ()> {
+        Success(CorsRequest::from(request))
+    }
+}
+
+impl<'f> FromForm<'f> for GraphQLRequest {
+    type Error = GraphQLResponse;
+
+    fn from_form_items(form_items: &mut FormItems<'f>) -> Result<Self, GraphQLResponse> {
+        http::GraphQLRequest::from_query_params(form_items)
+            .map(|request| GraphQLRequest(http::GraphQLBatchRequest::from(request).into(), GraphQLHandler::new()))
+            .map_err(|err| GraphQLResponse(HandlerResponse::error(400, &err.to_string())))
+    }
+}
+
 impl FromData for GraphQLRequest {
-    type Error = String;
+    type Error = GraphQLResponse;
+
+    fn from_data(request: &Request, data: Data) -> FromDataOutcome<Self, GraphQLResponse> {
+        let is_json = request.content_type().map_or(false, |ct| ct.is_json());
+        let is_graphql = request.content_type().map_or(false, |ct| is_graphql_content_type(&ct));
+
+        let is_multipart = request.content_type().map_or(false, |ct| multipart::is_multipart(&ct.to_string()));
 
-    fn from_data(request: &Request, data: Data) -> FromDataOutcome<Self, String> {
-        if !request.content_type().map_or(false, |ct| ct.is_json()) {
+        if !is_json && !is_graphql && !is_multipart {
             return Forward(data);
         }
 
-        let mut body = String::new();
-        if let Err(e) = data.open().read_to_string(&mut body) {
-            return Failure((Status::InternalServerError, format!("{:?}", e)));
+        let handler = request.guard::<State<GraphQLHandler>>().succeeded()
+            .map_or_else(
+                || {
+                    let limits = request.guard::<State<RequestLimits>>().succeeded()
+                        .map_or_else(RequestLimits::new, |limits| limits.inner().clone());
+                    GraphQLHandler::new().with_limits(limits)
+                },
+                |handler| handler.inner().clone());
+
+        match handler.parse(&request_parts(request), data.open()) {
+            Ok(parsed) => Success(GraphQLRequest(parsed, handler)),
+            Err(response) => Failure((Status::raw(response.status()), GraphQLResponse(response))),
+        }
+    }
+}
+
+/// Describe a Rocket request for a `ContextFactory`
+pub fn request_metadata(request: &Request) -> RequestMetadata {
+    request.headers().iter().fold(
+        RequestMetadata::new(request.method().as_str(), request.uri().query()),
+        |metadata, header| metadata.with_header(header.name(), header.value()))
+}
+
+fn request_parts<'r>(request: &'r Request) -> RequestParts<'r> {
+    RequestParts::new(request.method().as_str(), request.uri().query())
+        .with_content_type(request.headers().get_one("Content-Type"))
+        .with_accept(request.headers().get_one("Accept"))
+}
+
+fn is_graphql_content_type(content_type: &ContentType) -> bool {
+    let content_type = content_type.to_string();
+    let media_type = content_type.split(';').next().unwrap_or("");
+
+    media_type.trim().eq_ignore_ascii_case("application/graphql")
+}
+
+impl<'r> Responder<'r> for GraphQLResponse {
+    fn respond(self) -> Result<Response<'r>, Status> {
+        let GraphQLResponse(response) = self;
+
+        let mut builder = Response::build();
+        for &(name, value) in response.headers() {
+            builder.raw_header(name, value);
+        }
+
+        Ok(builder
+            .status(Status::raw(response.status()))
+            .sized_body(Cursor::new(response.into_body()))
+            .finalize())
+    }
+}
+
+impl<'r, R> Responder<'r> for CorsResponse<R> where R: Responder<'r> {
+    fn respond(self) -> Result<Response<'r>, Status> {
+        let CorsResponse(responder, headers) = self;
+
+        let mut response = try!(responder.respond());
+        for (name, value) in headers {
+            // Keep the `Vary: Accept-Encoding` of compressed responses
+            let existing = response.headers().get_one(name).map(str::to_owned);
+            let value = match existing {
+                Some(existing) if name == "Vary" => format!("{}, {}", existing, value),
+                _ => value,
+            };
+            response.set_raw_header(name, value);
         }
 
-        match serde_json::from_str(&body) {
-            Ok(value) => Success(GraphQLRequest(value)),
-            Err(failure) => return Failure(
-                (Status::BadRequest, format!("{}", failure)),
-            ),
+        Ok(response)
+    }
+}
+
+impl<'r> Responder<'r> for PreflightResponse {
+    fn respond(self) -> Result<Response<'r>, Status> {
+        let mut builder = Response::build();
+        for &(name, ref value) in self.headers() {
+            builder.raw_header(name, value.clone());
         }
+
+        Ok(builder
+            .status(Status::raw(self.status()))
+            .finalize())
     }
 }
 
-impl<'r> Responder<'r> for GraphQLResponse {
+/// Encoded responses of `http::compression` can be returned from routes as
+/// they are
+#[cfg(feature="compression")]
+impl<'r> Responder<'r> for EncodedResponse {
+    fn respond(self) -> Result<Response<'r>, Status> {
+        let mut builder = Response::build();
+        for &(name, value) in self.headers() {
+            builder.raw_header(name, value);
+        }
+
+        Ok(builder
+            .status(Status::raw(self.status()))
+            .sized_body(Cursor::new(self.into_body()))
+            .finalize())
+    }
+}
+
+/// The responses of `http::endpoints` can be returned from routes as they are
+impl<'r> Responder<'r> for http::endpoints::EndpointResponse {
     fn respond(self) -> Result<Response<'r>, Status> {
-        let GraphQLResponse(status, body) = self;
+        let status = Status::raw(self.status());
+        let content_type = self.content_type();
 
         Ok(Response::build()
-            .header(ContentType::new("application", "json"))
+            .raw_header("Content-Type", content_type)
             .status(status)
-            .sized_body(Cursor::new(body))
+            .sized_body(Cursor::new(self.into_body()))
             .finalize())
     }
 }
@@ -150,7 +632,7 @@ impl<'r> Responder<'r> for GraphQLResponse {
 mod tests {
     use rocket;
     use rocket::Rocket;
-    use rocket::http::{ContentType, Method};
+    use rocket::http::{ContentType, Header, Method};
     use rocket::State;
     use rocket::testing::MockRequest;
 
@@ -164,19 +646,25 @@ mod tests {
     #[get("/?<request>")]
     fn get_graphql_handler(
         context: State<Database>,
-        request: super::GraphQLRequest,
+        request: Result<super::GraphQLRequest, super::GraphQLResponse>,
         schema: State<Schema>,
     ) -> super::GraphQLResponse {
-        request.execute(&schema, &context)
+        match request {
+            Ok(request) => request.execute(&schema, &context),
+            Err(response) => response,
+        }
     }
 
     #[post("/", data="<request>")]
     fn post_graphql_handler(
         context: State<Database>,
-        request: super::GraphQLRequest,
+        request: Result<super::GraphQLRequest, super::GraphQLResponse>,
         schema: State<Schema>,
     ) -> super::GraphQLResponse {
-        request.execute(&schema, &context)
+        match request {
+            Ok(request) => request.execute(&schema, &context),
+            Err(response) => response,
+        }
     }
 
     struct TestRocketIntegration {
@@ -199,6 +687,31 @@ mod tests {
                     url,
                 ).header(ContentType::JSON).body(body))
         }
+
+        fn post_graphql(&self, url: &str, body: &str) -> http_tests::TestResponse {
+            make_test_response(
+                &self.rocket,
+                MockRequest::new(
+                    Method::Post,
+                    url,
+                ).header(ContentType::new("application", "graphql")).body(body))
+        }
+
+        fn post_with_content_type(&self, url: &str, content_type: &str, body: &str) -> http_tests::TestResponse {
+            make_test_response(
+                &self.rocket,
+                MockRequest::new(
+                    Method::Post,
+                    url,
+                ).header(Header::new("Content-Type", content_type.to_owned())).body(body))
+        }
+
+        fn features(&self) -> http_tests::IntegrationFeatures {
+            http_tests::IntegrationFeatures {
+                uploads: true,
+                .. http_tests::IntegrationFeatures::default()
+            }
+        }
     }
 
     #[test]
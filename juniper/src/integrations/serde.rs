@@ -2,19 +2,22 @@ use serde::{de, ser};
 use serde::ser::SerializeMap;
 use std::fmt;
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use ::{GraphQLError, Value};
+use ::{GraphQLError, GraphQLErrorCode, Value, Object, ScalarValue};
 use ast::InputValue;
-use executor::ExecutionError;
+use executor::{ExecutionError, ErrorPathSegment};
 use parser::{ParseError, Spanning, SourcePosition};
 use validation::RuleError;
 
 
-impl ser::Serialize for ExecutionError {
+impl<T> ser::Serialize for ExecutionError<T> where T: ScalarValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: ser::Serializer,
     {
-        let mut map = try!(serializer.serialize_map(Some(3)));
+        let extensions = self.extensions();
+        let len = if extensions.is_null() { 3 } else { 4 };
+        let mut map = try!(serializer.serialize_map(Some(len)));
 
         try!(map.serialize_key("message"));
         try!(map.serialize_value(self.message()));
@@ -26,10 +29,26 @@ impl ser::Serialize for ExecutionError {
         try!(map.serialize_key("path"));
         try!(map.serialize_value(self.path()));
 
+        if !extensions.is_null() {
+            try!(map.serialize_key("extensions"));
+            try!(map.serialize_value(extensions));
+        }
+
         map.end()
     }
 }
 
+impl ser::Serialize for ErrorPathSegment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        match *self {
+            ErrorPathSegment::Field(ref name) => serializer.serialize_str(name),
+            ErrorPathSegment::Index(index) => serializer.serialize_u64(index as u64),
+        }
+    }
+}
+
 impl<'a> ser::Serialize for GraphQLError<'a> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: ser::Serializer,
@@ -38,80 +57,131 @@ impl<'a> ser::Serialize for GraphQLError<'a> {
             GraphQLError::ParseError(ref err) => vec![err].serialize(serializer),
             GraphQLError::ValidationError(ref errs) => errs.serialize(serializer),
             GraphQLError::NoOperationProvided => {
-                serializer.serialize_str("Must provide an operation")
+                vec![MessageError("Must provide an operation")].serialize(serializer)
             },
             GraphQLError::MultipleOperationsProvided => {
-                serializer.serialize_str("Must provide operation name if query contains multiple operations")
+                vec![MessageError("Must provide operation name if query contains multiple operations")]
+                    .serialize(serializer)
             },
             GraphQLError::UnknownOperationName => {
-                serializer.serialize_str("Unknown operation")
+                vec![MessageError("Unknown operation")].serialize(serializer)
             },
         }
     }
 }
 
-impl<'de> de::Deserialize<'de> for InputValue {
-    fn deserialize<D>(deserializer: D) -> Result<InputValue, D::Error>
+// An error without a location in the query
+struct MessageError(&'static str);
+
+// The extensions of errors that only have a code
+struct CodeExtensions(GraphQLErrorCode);
+
+impl ser::Serialize for MessageError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let mut map = try!(serializer.serialize_map(Some(2)));
+
+        try!(map.serialize_key("message"));
+        try!(map.serialize_value(self.0));
+
+        try!(map.serialize_key("extensions"));
+        try!(map.serialize_value(&CodeExtensions(GraphQLErrorCode::OperationResolutionFailure)));
+
+        map.end()
+    }
+}
+
+impl ser::Serialize for CodeExtensions {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        let mut map = try!(serializer.serialize_map(Some(1)));
+
+        try!(map.serialize_key("code"));
+        try!(map.serialize_value(&self.0));
+
+        map.end()
+    }
+}
+
+impl ser::Serialize for GraphQLErrorCode {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, S> de::Deserialize<'de> for InputValue<S> where S: ScalarValue {
+    fn deserialize<D>(deserializer: D) -> Result<InputValue<S>, D::Error>
         where D: de::Deserializer<'de>,
     {
-        struct InputValueVisitor;
+        struct InputValueVisitor<S>(PhantomData<S>);
 
-        impl<'de> de::Visitor<'de> for InputValueVisitor {
-            type Value = InputValue;
+        impl<'de, S> de::Visitor<'de> for InputValueVisitor<S> where S: ScalarValue {
+            type Value = InputValue<S>;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
                 formatter.write_str("a valid input value")
             }
 
-            fn visit_bool<E>(self, value: bool) -> Result<InputValue, E> {
+            fn visit_bool<E>(self, value: bool) -> Result<InputValue<S>, E> {
                 Ok(InputValue::boolean(value))
             }
 
-            fn visit_i64<E>(self, value: i64) -> Result<InputValue, E>
+            fn visit_i64<E>(self, value: i64) -> Result<InputValue<S>, E>
                 where E: de::Error,
             {
                 if value >= i32::min_value() as i64 && value <= i32::max_value() as i64 {
                     Ok(InputValue::int(value as i32))
                 }
                 else {
-                    Err(E::custom(format!("integer out of range")))
+                    S::from_int_literal(&value.to_string())
+                        .map(InputValue::scalar)
+                        .ok_or_else(|| E::custom(format!("integer out of range")))
                 }
             }
 
-            fn visit_u64<E>(self, value: u64) -> Result<InputValue, E>
+            fn visit_u64<E>(self, value: u64) -> Result<InputValue<S>, E>
                 where E: de::Error,
             {
                 if value <= i32::max_value() as u64 {
                     self.visit_i64(value as i64)
                 }
                 else {
-                    Err(E::custom(format!("integer out of range")))
+                    S::from_int_literal(&value.to_string())
+                        .map(InputValue::scalar)
+                        .ok_or_else(|| E::custom(format!("integer out of range")))
                 }
             }
 
-            fn visit_f64<E>(self, value: f64) -> Result<InputValue, E> {
-                Ok(InputValue::float(value))
+            fn visit_f64<E>(self, value: f64) -> Result<InputValue<S>, E> {
+                Ok(match S::from_float_literal(&value.to_string()) {
+                    Some(s) => InputValue::scalar(s),
+                    None => InputValue::float(value),
+                })
             }
 
-            fn visit_str<E>(self, value: &str) -> Result<InputValue, E>
+            fn visit_str<E>(self, value: &str) -> Result<InputValue<S>, E>
                 where E: de::Error,
             {
                 self.visit_string(value.into())
             }
 
-            fn visit_string<E>(self, value: String) -> Result<InputValue, E> {
+            fn visit_string<E>(self, value: String) -> Result<InputValue<S>, E> {
                 Ok(InputValue::string(value))
             }
 
-            fn visit_none<E>(self) -> Result<InputValue, E> {
+            fn visit_none<E>(self) -> Result<InputValue<S>, E> {
                 Ok(InputValue::null())
             }
 
-            fn visit_unit<E>(self) -> Result<InputValue, E> {
+            fn visit_unit<E>(self) -> Result<InputValue<S>, E> {
                 Ok(InputValue::null())
             }
 
-            fn visit_seq<V>(self, mut visitor: V) -> Result<InputValue, V::Error>
+            fn visit_seq<V>(self, mut visitor: V) -> Result<InputValue<S>, V::Error>
                 where V: de::SeqAccess<'de>,
             {
                 let mut values = Vec::new();
@@ -123,10 +193,10 @@ impl<'de> de::Deserialize<'de> for InputValue {
                 Ok(InputValue::list(values))
             }
 
-            fn visit_map<V>(self, mut visitor: V) -> Result<InputValue, V::Error>
+            fn visit_map<V>(self, mut visitor: V) -> Result<InputValue<S>, V::Error>
                 where V: de::MapAccess<'de>,
             {
-                let mut values: HashMap<String, InputValue> = HashMap::new();
+                let mut values: HashMap<String, InputValue<S>> = HashMap::new();
 
                 while let Some((key, value)) = try!(visitor.next_entry()) {
                     values.insert(key, value);
@@ -136,13 +206,110 @@ impl<'de> de::Deserialize<'de> for InputValue {
             }
         }
 
-        deserializer.deserialize_any(InputValueVisitor)
+        deserializer.deserialize_any(InputValueVisitor(PhantomData))
     }
 }
 
-impl ser::Serialize for InputValue {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where S: ser::Serializer,
+impl<'de, S> de::Deserialize<'de> for Value<S> where S: ScalarValue {
+    fn deserialize<D>(deserializer: D) -> Result<Value<S>, D::Error>
+        where D: de::Deserializer<'de>,
+    {
+        struct ValueVisitor<S>(PhantomData<S>);
+
+        impl<'de, S> de::Visitor<'de> for ValueVisitor<S> where S: ScalarValue {
+            type Value = Value<S>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a valid value")
+            }
+
+            fn visit_bool<E>(self, value: bool) -> Result<Value<S>, E> {
+                Ok(Value::boolean(value))
+            }
+
+            fn visit_i64<E>(self, value: i64) -> Result<Value<S>, E>
+                where E: de::Error,
+            {
+                if value >= i32::min_value() as i64 && value <= i32::max_value() as i64 {
+                    Ok(Value::int(value as i32))
+                }
+                else {
+                    S::from_int_literal(&value.to_string())
+                        .map(Value::scalar)
+                        .ok_or_else(|| E::custom(format!("integer out of range")))
+                }
+            }
+
+            fn visit_u64<E>(self, value: u64) -> Result<Value<S>, E>
+                where E: de::Error,
+            {
+                if value <= i32::max_value() as u64 {
+                    self.visit_i64(value as i64)
+                }
+                else {
+                    S::from_int_literal(&value.to_string())
+                        .map(Value::scalar)
+                        .ok_or_else(|| E::custom(format!("integer out of range")))
+                }
+            }
+
+            fn visit_f64<E>(self, value: f64) -> Result<Value<S>, E> {
+                Ok(match S::from_float_literal(&value.to_string()) {
+                    Some(s) => Value::scalar(s),
+                    None => Value::float(value),
+                })
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Value<S>, E>
+                where E: de::Error,
+            {
+                self.visit_string(value.into())
+            }
+
+            fn visit_string<E>(self, value: String) -> Result<Value<S>, E> {
+                Ok(Value::String(value))
+            }
+
+            fn visit_none<E>(self) -> Result<Value<S>, E> {
+                Ok(Value::null())
+            }
+
+            fn visit_unit<E>(self) -> Result<Value<S>, E> {
+                Ok(Value::null())
+            }
+
+            fn visit_seq<V>(self, mut visitor: V) -> Result<Value<S>, V::Error>
+                where V: de::SeqAccess<'de>,
+            {
+                let mut values = Vec::new();
+
+                while let Some(el) = try!(visitor.next_element()) {
+                    values.push(el);
+                }
+
+                Ok(Value::list(values))
+            }
+
+            fn visit_map<V>(self, mut visitor: V) -> Result<Value<S>, V::Error>
+                where V: de::MapAccess<'de>,
+            {
+                let mut object = Object::new();
+
+                while let Some((key, value)) = try!(visitor.next_entry::<String, Value<S>>()) {
+                    object.insert(key, value);
+                }
+
+                Ok(Value::object(object))
+            }
+        }
+
+        deserializer.deserialize_any(ValueVisitor(PhantomData))
+    }
+}
+
+impl<S> ser::Serialize for InputValue<S> where S: ScalarValue {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+        where Ser: ser::Serializer,
     {
         match *self {
             InputValue::Null | InputValue::Variable(_) => serializer.serialize_unit(),
@@ -150,6 +317,7 @@ impl ser::Serialize for InputValue {
             InputValue::Float(v) => serializer.serialize_f64(v),
             InputValue::String(ref v) | InputValue::Enum(ref v) => serializer.serialize_str(v),
             InputValue::Boolean(v) => serializer.serialize_bool(v),
+            InputValue::Scalar(ref v) => v.serialize(serializer),
             InputValue::List(ref v) => {
                 v.iter().map(|x| x.item.clone()).collect::<Vec<_>>().serialize(serializer)
             },
@@ -167,7 +335,7 @@ impl ser::Serialize for RuleError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: ser::Serializer,
     {
-        let mut map = try!(serializer.serialize_map(Some(2)));
+        let mut map = try!(serializer.serialize_map(Some(3)));
 
         try!(map.serialize_key("message"));
         try!(map.serialize_value(self.message()));
@@ -175,6 +343,9 @@ impl ser::Serialize for RuleError {
         try!(map.serialize_key("locations"));
         try!(map.serialize_value(self.locations()));
 
+        try!(map.serialize_key("extensions"));
+        try!(map.serialize_value(&CodeExtensions(self.code())));
+
         map.end()
     }
 }
@@ -201,28 +372,27 @@ impl<'a> ser::Serialize for Spanning<ParseError<'a>> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where S: ser::Serializer,
     {
-        let mut map = try!(serializer.serialize_map(Some(2)));
+        let mut map = try!(serializer.serialize_map(Some(3)));
 
         let message = format!("{}", self.item);
         try!(map.serialize_key("message"));
         try!(map.serialize_value(&message));
 
-        let mut location = HashMap::new();
-        location.insert("line".to_owned(), self.start.line() + 1);
-        location.insert("column".to_owned(), self.start.column() + 1);
-
-        let locations = vec![location];
+        let locations = vec![&self.start];
 
         try!(map.serialize_key("locations"));
         try!(map.serialize_value(&locations));
 
+        try!(map.serialize_key("extensions"));
+        try!(map.serialize_value(&CodeExtensions(GraphQLErrorCode::GraphQLParseFailed)));
+
         map.end()
     }
 }
 
-impl ser::Serialize for Value {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-        where S: ser::Serializer,
+impl<S> ser::Serialize for Value<S> where S: ScalarValue {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+        where Ser: ser::Serializer,
     {
         match *self {
             Value::Null => serializer.serialize_unit(),
@@ -230,8 +400,24 @@ impl ser::Serialize for Value {
             Value::Float(v) => serializer.serialize_f64(v),
             Value::String(ref v) => serializer.serialize_str(v),
             Value::Boolean(v) => serializer.serialize_bool(v),
+            Value::Scalar(ref v) => v.serialize(serializer),
             Value::List(ref v) => v.serialize(serializer),
             Value::Object(ref v) => v.serialize(serializer),
         }
     }
 }
+
+impl<S> ser::Serialize for Object<S> where S: ScalarValue {
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+        where Ser: ser::Serializer,
+    {
+        let mut map = try!(serializer.serialize_map(Some(self.len())));
+
+        for &(ref k, ref v) in self.iter() {
+            try!(map.serialize_key(&**k));
+            try!(map.serialize_value(v));
+        }
+
+        map.end()
+    }
+}
@@ -0,0 +1,237 @@
+use std::convert::TryFrom;
+
+use serde::ser::Error as SerError;
+use serde_json::{self, Value as Json, Number, Error};
+
+use ast::InputValue;
+use parser::Spanning;
+use value::{Value, ScalarValue};
+
+// Integers that don't fit in an `Int` and all floats are offered to the
+// schema's scalar representation first, and only become a (possibly lossy)
+// `Float` if it rejects them.
+enum JsonNumber<S> {
+    Int(i32),
+    Float(f64),
+    Scalar(S),
+}
+
+fn convert_number<S>(n: &Number) -> JsonNumber<S> where S: ScalarValue {
+    if let Some(i) = n.as_i64() {
+        if i >= i32::min_value() as i64 && i <= i32::max_value() as i64 {
+            return JsonNumber::Int(i as i32);
+        }
+    }
+
+    if n.is_i64() || n.is_u64() {
+        if let Some(s) = S::from_int_literal(&n.to_string()) {
+            return JsonNumber::Scalar(s);
+        }
+    }
+
+    if n.is_f64() {
+        if let Some(s) = S::from_float_literal(&n.to_string()) {
+            return JsonNumber::Scalar(s);
+        }
+    }
+
+    JsonNumber::Float(n.as_f64().expect("JSON numbers can always be read as floats"))
+}
+
+fn convert_float(f: f64) -> Result<Json, Error> {
+    Number::from_f64(f)
+        .map(Json::Number)
+        .ok_or_else(|| Error::custom(format!("{} can't be represented in JSON", f)))
+}
+
+impl<S> From<Json> for Value<S> where S: ScalarValue {
+    fn from(json: Json) -> Value<S> {
+        match json {
+            Json::Null => Value::null(),
+            Json::Bool(b) => Value::boolean(b),
+            Json::Number(ref n) => match convert_number(n) {
+                JsonNumber::Int(i) => Value::int(i),
+                JsonNumber::Float(f) => Value::float(f),
+                JsonNumber::Scalar(s) => Value::scalar(s),
+            },
+            Json::String(s) => Value::String(s),
+            Json::Array(l) => Value::list(l.into_iter().map(Value::from).collect()),
+            Json::Object(o) => Value::object(o.into_iter().map(|(k, v)| (k, Value::from(v))).collect()),
+        }
+    }
+}
+
+impl<S> From<Json> for InputValue<S> where S: ScalarValue {
+    fn from(json: Json) -> InputValue<S> {
+        match json {
+            Json::Null => InputValue::null(),
+            Json::Bool(b) => InputValue::boolean(b),
+            Json::Number(ref n) => match convert_number(n) {
+                JsonNumber::Int(i) => InputValue::int(i),
+                JsonNumber::Float(f) => InputValue::float(f),
+                JsonNumber::Scalar(s) => InputValue::scalar(s),
+            },
+            Json::String(s) => InputValue::String(s),
+            Json::Array(l) => InputValue::list(l.into_iter().map(InputValue::from).collect()),
+            Json::Object(o) => InputValue::parsed_object(
+                o.into_iter()
+                    .map(|(k, v)| (Spanning::unlocated(k), Spanning::unlocated(InputValue::from(v))))
+                    .collect()),
+        }
+    }
+}
+
+/// The conversion fails for non-finite floats, which JSON can't represent, and
+/// for custom scalars that can't be serialized.
+impl<S> TryFrom<Value<S>> for Json where S: ScalarValue {
+    type Error = Error;
+
+    fn try_from(value: Value<S>) -> Result<Json, Error> {
+        Ok(match value {
+            Value::Null => Json::Null,
+            Value::Int(i) => Json::from(i),
+            Value::Float(f) => try!(convert_float(f)),
+            Value::String(s) => Json::String(s),
+            Value::Boolean(b) => Json::Bool(b),
+            Value::List(l) => Json::Array(try!(
+                l.into_iter().map(Json::try_from).collect::<Result<_, _>>())),
+            Value::Object(o) => Json::Object(try!(
+                o.into_iter()
+                    .map(|(k, v)| Json::try_from(v).map(|v| (k.to_string(), v)))
+                    .collect::<Result<_, _>>())),
+            Value::Scalar(s) => try!(serde_json::to_value(&s)),
+        })
+    }
+}
+
+/// Enum values become strings. The conversion fails for unresolved variables,
+/// non-finite floats, and custom scalars that can't be serialized.
+impl<S> TryFrom<InputValue<S>> for Json where S: ScalarValue {
+    type Error = Error;
+
+    fn try_from(value: InputValue<S>) -> Result<Json, Error> {
+        Ok(match value {
+            InputValue::Null => Json::Null,
+            InputValue::Int(i) => Json::from(i),
+            InputValue::Float(f) => try!(convert_float(f)),
+            InputValue::String(s) | InputValue::Enum(s) => Json::String(s),
+            InputValue::Boolean(b) => Json::Bool(b),
+            InputValue::Variable(v) => {
+                return Err(Error::custom(format!("Unresolved variable ${}", v)));
+            },
+            InputValue::List(l) => Json::Array(try!(
+                l.into_iter().map(|v| Json::try_from(v.item)).collect::<Result<_, _>>())),
+            InputValue::Object(o) => Json::Object(try!(
+                o.into_iter()
+                    .map(|(k, v)| Json::try_from(v.item).map(|v| (k.item, v)))
+                    .collect::<Result<_, _>>())),
+            InputValue::Scalar(s) => try!(serde_json::to_value(&s)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::f64;
+    use std::fmt;
+
+    use serde::{Serialize, Serializer};
+    use serde_json::{self, Value as Json};
+
+    use ast::InputValue;
+    use value::{Value, ScalarValue};
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Decimal(String);
+
+    impl fmt::Display for Decimal {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl Serialize for Decimal {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(&self.0)
+        }
+    }
+
+    impl ScalarValue for Decimal {
+        fn from_float_literal(literal: &str) -> Option<Decimal> {
+            Some(Decimal(literal.to_owned()))
+        }
+    }
+
+    #[test]
+    fn test_json_into_value() {
+        let json: Json = serde_json::from_str(
+            r#"{"b": [1, 2.5, "x", true, null], "a": {"big": 3000000000}}"#).unwrap();
+
+        let value: Value = Value::from(json);
+
+        assert_eq!(
+            value,
+            Value::object(vec![
+                ("a", Value::object(vec![
+                    ("big", Value::float(3000000000.0)),
+                ].into_iter().collect())),
+                ("b", Value::list(vec![
+                    Value::int(1),
+                    Value::float(2.5),
+                    Value::string("x"),
+                    Value::boolean(true),
+                    Value::null(),
+                ])),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn test_json_into_input_value() {
+        let json: Json = serde_json::from_str(r#"{"list": [1, null]}"#).unwrap();
+        let iv: InputValue = InputValue::from(json);
+
+        assert_eq!(iv.to_string(), "{list: [1, null]}");
+    }
+
+    #[test]
+    fn test_floats_are_offered_to_the_scalar_value() {
+        let json: Json = serde_json::from_str(r#"[0.25, 1]"#).unwrap();
+
+        assert_eq!(
+            InputValue::<Decimal>::from(json),
+            InputValue::list(vec![InputValue::scalar(Decimal("0.25".to_owned())), InputValue::int(1)]));
+
+        assert_eq!(
+            serde_json::from_str::<InputValue<Decimal>>(r#"[0.25, 1]"#).unwrap(),
+            InputValue::list(vec![InputValue::scalar(Decimal("0.25".to_owned())), InputValue::int(1)]));
+    }
+
+    #[test]
+    fn test_value_into_json() {
+        let value: Value = Value::object(vec![
+            ("name", Value::string("R2-D2")),
+            ("ratio", Value::float(0.5)),
+            ("friends", Value::list(vec![Value::int(1), Value::null()])),
+        ].into_iter().collect());
+
+        assert_eq!(
+            Json::try_from(value).unwrap(),
+            serde_json::from_str::<Json>(r#"{"name": "R2-D2", "ratio": 0.5, "friends": [1, null]}"#).unwrap());
+
+        let nan: Value = Value::float(f64::NAN);
+        assert!(Json::try_from(nan).is_err());
+    }
+
+    #[test]
+    fn test_input_value_into_json() {
+        let iv: InputValue = InputValue::list(vec![InputValue::enum_value("EMPIRE"), InputValue::int(2)]);
+
+        assert_eq!(
+            Json::try_from(iv).unwrap(),
+            serde_json::from_str::<Json>(r#"["EMPIRE", 2]"#).unwrap());
+
+        let variable: InputValue = InputValue::variable("id");
+        assert!(Json::try_from(variable).is_err());
+    }
+}
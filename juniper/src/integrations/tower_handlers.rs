@@ -0,0 +1,171 @@
+//! Optional [tower](https://github.com/tower-rs/tower) service. Requires the `tower-handlers` feature enabled.
+//!
+//! `GraphQLService` takes requests with a buffered body, so it works with any
+//! server or middleware stack built on the `http` crate's types. Timeouts,
+//! retries, metrics and so on are added by wrapping it in tower layers.
+
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+
+use futures::{future, Async, Poll};
+use http_crate::{header, HeaderValue, Request, Response, StatusCode};
+use tower_service::Service;
+
+use ::{GraphQLType, RootNode, SwappableRootNode};
+use ::http;
+use http::context::{ContextFactory, RequestMetadata};
+use http::handler::{GraphQLHandler, RequestParts, HandlerResponse};
+use http::limits::RequestLimits;
+
+/// Service that executes GraphQL queries in the given schema
+///
+/// The service responds to GET requests and POST requests only, like the
+/// other integrations: GET requests carry the query in the `query` URL
+/// parameter, POST requests a JSON document or a batch of them, or the query
+/// itself with the `application/graphql` content type.
+///
+/// The body of the request has to be read completely before the service is
+/// called. Every outcome, including malformed requests, is a response, so the
+/// service never fails.
+pub struct GraphQLService<Query, Mutation, CtxT, CtxFactory>
+    where Query: GraphQLType<Context=CtxT> + 'static,
+          Mutation: GraphQLType<Context=CtxT> + 'static,
+{
+    context_factory: Arc<CtxFactory>,
+    root_node: Arc<SwappableRootNode<'static, Query, Mutation>>,
+    handler: GraphQLHandler,
+}
+
+/// The error of `GraphQLService`, which can't occur
+#[derive(Debug)]
+pub enum GraphQLServiceError {}
+
+impl<Query, Mutation, CtxT, CtxFactory>
+    GraphQLService<Query, Mutation, CtxT, CtxFactory>
+    where Query: GraphQLType<Context=CtxT> + 'static,
+          Mutation: GraphQLType<Context=CtxT> + 'static,
+{
+    /// Build a new GraphQL service
+    ///
+    /// The context factory will receive the metadata of the request and is
+    /// expected to construct a context object for the given schema, or refuse
+    /// to, see `http::context`.
+    pub fn new(context_factory: CtxFactory, root_node: RootNode<'static, Query, Mutation>) -> Self {
+        GraphQLService::from_swappable(context_factory, Arc::new(SwappableRootNode::new(root_node)))
+    }
+
+    /// Build a new GraphQL service for a root node that may be replaced while
+    /// the server is running, see `SwappableRootNode`
+    pub fn from_swappable(
+        context_factory: CtxFactory,
+        root_node: Arc<SwappableRootNode<'static, Query, Mutation>>,
+    )
+        -> Self
+    {
+        GraphQLService {
+            context_factory: Arc::new(context_factory),
+            root_node: root_node,
+            handler: GraphQLHandler::new(),
+        }
+    }
+
+    /// Set the layout of the JSON responses, see `http::JsonFormat`
+    pub fn with_json_format(mut self, json_format: http::JsonFormat) -> Self {
+        self.handler = self.handler.with_json_format(json_format);
+        self
+    }
+
+    /// Limit the size of incoming requests, see `http::limits`
+    pub fn with_limits(mut self, limits: RequestLimits) -> Self {
+        self.handler = self.handler.with_limits(limits);
+        self
+    }
+}
+
+impl<Query, Mutation, CtxT, CtxFactory>
+    Clone
+    for GraphQLService<Query, Mutation, CtxT, CtxFactory>
+    where Query: GraphQLType<Context=CtxT> + 'static,
+          Mutation: GraphQLType<Context=CtxT> + 'static,
+{
+    fn clone(&self) -> Self {
+        GraphQLService {
+            context_factory: self.context_factory.clone(),
+            root_node: self.root_node.clone(),
+            handler: self.handler.clone(),
+        }
+    }
+}
+
+impl<Query, Mutation, CtxT, CtxFactory, B>
+    Service<Request<B>>
+    for GraphQLService<Query, Mutation, CtxT, CtxFactory>
+    where Query: GraphQLType<Context=CtxT> + 'static,
+          Mutation: GraphQLType<Context=CtxT> + 'static,
+          CtxFactory: ContextFactory<CtxT>,
+          B: AsRef<[u8]>,
+{
+    type Response = Response<String>;
+    type Error = GraphQLServiceError;
+    type Future = future::FutureResult<Response<String>, GraphQLServiceError>;
+
+    fn poll_ready(&mut self) -> Poll<(), GraphQLServiceError> {
+        Ok(Async::Ready(()))
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let response = {
+            let parts = RequestParts::new(req.method().as_str(), req.uri().query())
+                .with_content_type(header_str(&req, header::CONTENT_TYPE))
+                .with_accept(header_str(&req, header::ACCEPT));
+
+            match self.handler.parse(&parts, req.body().as_ref()) {
+                Ok(parsed) => self.handler.execute_with_context_factory(
+                    &parsed, &self.root_node.load(), &*self.context_factory, &request_metadata(&req)),
+                Err(response) => response,
+            }
+        };
+
+        future::ok(http_response(response))
+    }
+}
+
+impl fmt::Display for GraphQLServiceError {
+    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+impl Error for GraphQLServiceError {
+    fn description(&self) -> &str {
+        match *self {}
+    }
+}
+
+/// Describe a request for a `ContextFactory`
+///
+/// Headers whose values aren't visible ASCII are left out.
+pub fn request_metadata<B>(req: &Request<B>) -> RequestMetadata {
+    req.headers().iter().fold(
+        RequestMetadata::new(req.method().as_str(), req.uri().query()),
+        |metadata, (name, value)| match value.to_str() {
+            Ok(value) => metadata.with_header(name.as_str(), value),
+            Err(_) => metadata,
+        })
+}
+
+fn header_str<B>(req: &Request<B>, name: header::HeaderName) -> Option<&str> {
+    req.headers().get(name).and_then(|value| value.to_str().ok())
+}
+
+fn http_response(response: HandlerResponse) -> Response<String> {
+    let mut http_response = Response::new(String::new());
+    *http_response.status_mut() = StatusCode::from_u16(response.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    for &(name, value) in response.headers() {
+        http_response.headers_mut().insert(name, HeaderValue::from_static(value));
+    }
+    *http_response.body_mut() = response.into_body();
+
+    http_response
+}
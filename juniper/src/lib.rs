@@ -59,11 +59,11 @@ graphql_object!(User: Database |&self| {
         &self.name
     }
 
-    // FieldResult<T> is an alias for Result<T, String> - simply return
-    // a string from this method and it will be correctly inserted into
-    // the execution response.
+    // FieldResult<T> is an alias for Result<T, FieldError> - any error
+    // implementing Display can be converted into a FieldError, and will be
+    // correctly inserted into the execution response.
     field secret() -> FieldResult<&String> {
-        Err("Can't touch this".to_owned())
+        Err("Can't touch this".into())
     }
 
     // Field accessors can optionally take an "executor" as their first
@@ -195,13 +195,29 @@ built-in [GraphiQL][6] handler included.
 #[cfg(feature="rocket-handlers")] extern crate rocket;
 
 #[cfg(feature="nightly")] extern crate test;
-#[cfg(feature="iron-handlers")] #[macro_use(itry)] extern crate iron;
-#[cfg(feature="iron-handlers")] extern crate urlencoded;
+#[cfg(any(feature="hyper-handlers", feature="tower-handlers"))] extern crate futures;
+#[cfg(feature="hyper-handlers")] extern crate hyper;
+#[cfg(feature="tower-handlers")] extern crate http as http_crate;
+#[cfg(feature="tower-handlers")] extern crate tower_service;
+#[cfg(feature="iron-handlers")] extern crate iron;
 #[cfg(test)] extern crate iron_test;
 extern crate serde;
 #[macro_use] extern crate serde_derive;
 
-#[cfg(any(feature="iron-handlers", feature="rocket-handlers"))] extern crate serde_json;
+#[cfg(feature="serde_json")] extern crate serde_json;
+#[cfg(feature="compression")] extern crate flate2;
+#[cfg(feature="msgpack")] extern crate rmp_serde;
+#[cfg(feature="cbor")] extern crate serde_cbor;
+#[cfg(feature="anyhow")] extern crate anyhow;
+#[cfg(feature="failure")] extern crate failure;
+#[cfg(feature="relay")] extern crate base64;
+#[cfg(feature="relay-signed-cursors")] extern crate hmac;
+#[cfg(feature="relay-signed-cursors")] extern crate sha2;
+#[cfg(feature="arbitrary")] extern crate arbitrary;
+#[cfg(feature="log")] #[macro_use] extern crate log;
+#[cfg(feature="metrics")] #[macro_use(counter, histogram)] extern crate metrics;
+#[cfg(all(test, feature="metrics"))] extern crate metrics_util;
+#[cfg(feature="tracing")] #[macro_use(info_span)] extern crate tracing;
 
 use std::borrow::Cow;
 
@@ -214,36 +230,63 @@ mod schema;
 mod validation;
 mod executor;
 mod integrations;
+mod prepared;
+mod cache;
+mod error_code;
+mod options;
+pub mod coverage;
+pub mod fuzz;
 pub mod graphiql;
 pub mod http;
+pub mod instrumentation;
+pub mod mock;
+#[cfg(feature="relay")] pub mod relay;
+#[cfg(feature="testing")] pub mod testing;
 #[macro_use] mod result_ext;
 
 #[cfg(all(test, not(feature="expose-test-schema")))] mod tests;
 #[cfg(feature="expose-test-schema")] pub mod tests;
+#[cfg(all(test, not(feature="expose-test-schema")))] mod bench;
+#[cfg(feature="expose-test-schema")] pub mod bench;
 
 #[cfg(test)] mod executor_tests;
 
 use parser::{parse_document_source, ParseError, Spanning};
 use validation::{ValidatorContext, visit_all_rules, validate_input_values};
-use executor::execute_validated_query;
+use executor::execute_validated_query_with_scratch;
+use ast::Document;
+use cache::IntrospectionCache;
+use instrumentation::instrument_request;
+use options::check_operation;
 
 pub use ast::{ToInputValue, FromInputValue, InputValue, Type, Selection};
-pub use value::Value;
-pub use types::base::{Arguments, GraphQLType, TypeKind};
+pub use value::{Value, Object, ObjectIterMut, PathSegment, ScalarValue, DefaultScalarValue};
+pub use types::base::{Arguments, GraphQLType, DynGraphQLType, TypeKind};
 pub use executor::{
-    Executor, ExecutionError, Registry,
+    Executor, ExecutionError, ErrorPathSegment, ExecutionScratch, Registry,
     Context, FromContext, IntoResolvable,
-    FieldResult, ExecutionResult, Variables,
+    FieldError, FieldResult, IntoFieldError, ExecutionResult, Variables,
 };
 pub use validation::RuleError;
-pub use types::scalars::{EmptyMutation, ID};
-pub use schema::model::RootNode;
+pub use types::scalars::{EmptyMutation, EmptySubscription, ID};
+pub use types::maps::{ObjectMap, KeyValueList};
+pub use types::nullable::Nullable;
+pub use types::upload::Upload;
+pub use schema::model::{RootNode, SwappableRootNode};
+pub use prepared::PreparedOperation;
+pub use cache::DocumentCache;
+pub use error_code::GraphQLErrorCode;
+pub use options::ExecutionOptions;
 pub use result_ext::ResultExt;
 
 pub use schema::meta;
 
+#[cfg(feature="dev-server")] pub use integrations::dev_server;
+#[cfg(feature="hyper-handlers")] pub use integrations::hyper_handlers;
 #[cfg(feature="iron-handlers")] pub use integrations::iron_handlers;
+#[cfg(feature="lambda-handlers")] pub use integrations::lambda_handlers;
 #[cfg(feature="rocket-handlers")] pub use integrations::rocket_handlers;
+#[cfg(feature="tower-handlers")] pub use integrations::tower_handlers;
 
 /// An error that prevented query execution
 #[derive(Debug, PartialEq)]
@@ -257,16 +300,130 @@ pub enum GraphQLError<'a> {
 }
 
 /// Execute a query in a provided schema
-pub fn execute<'a, CtxT, QueryT, MutationT>(
+///
+/// If the schema has a `DocumentCache`, the query is only parsed and validated
+/// the first time it's executed.
+pub fn execute<'a, CtxT, QueryT, MutationT, S>(
     document_source: &'a str,
     operation_name: Option<&str>,
-    root_node: &RootNode<QueryT, MutationT>,
-    variables: &Variables,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: &Variables<S>,
     context: &CtxT,
 )
-    -> Result<(Value, Vec<ExecutionError>), GraphQLError<'a>>
-    where QueryT: GraphQLType<Context=CtxT>,
-          MutationT: GraphQLType<Context=CtxT>,
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    execute_document(
+        document_source, operation_name, root_node, Cow::Borrowed(variables), context,
+        &mut ExecutionScratch::new())
+}
+
+/// Execute a query in a provided schema with the given options instead of the
+/// ones of the schema
+///
+/// Otherwise the same as `execute`, see `ExecutionOptions`.
+pub fn execute_with_options<'a, CtxT, QueryT, MutationT, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: &Variables<S>,
+    context: &CtxT,
+    options: &ExecutionOptions,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    let root_node = root_node.clone().with_execution_options(options.clone());
+
+    execute(document_source, operation_name, &root_node, variables, context)
+}
+
+/// Execute a query in a provided schema, reusing the allocations of an
+/// `ExecutionScratch`
+///
+/// Otherwise the same as `execute`.
+pub fn execute_with_scratch<'a, CtxT, QueryT, MutationT, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: &Variables<S>,
+    context: &CtxT,
+    scratch: &mut ExecutionScratch<S>,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    execute_document(document_source, operation_name, root_node, Cow::Borrowed(variables), context, scratch)
+}
+
+// `execute`, taking the variables by value if the caller can spare them, so
+// the executor doesn't need to copy them to add default values
+fn execute_document<'a, 'v, CtxT, QueryT, MutationT, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: Cow<'v, Variables<S>>,
+    context: &CtxT,
+    scratch: &mut ExecutionScratch<S>,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    instrument_request(&root_node.instruments, document_source, operation_name, || {
+        execute_uninstrumented_document(document_source, operation_name, root_node, variables, context, scratch)
+    })
+}
+
+fn execute_uninstrumented_document<'a, 'v, CtxT, QueryT, MutationT, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: Cow<'v, Variables<S>>,
+    context: &CtxT,
+    scratch: &mut ExecutionScratch<S>,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    if let Some(ref cache) = root_node.document_cache {
+        // Documents that fail to parse or validate aren't cached, and are
+        // handled below to report why
+        if let Some(cached) = cache.get(document_source, &root_node.schema) {
+            let errors = validate_input_values(&variables, cached.document(), &root_node.schema);
+            if !errors.is_empty() {
+                return Err(GraphQLError::ValidationError(errors));
+            }
+
+            return execute_valid_document(
+                document_source, cached.document(), operation_name, root_node, variables, context, scratch);
+        }
+    }
+
+    let document = try!(parse_and_validate(document_source, root_node, &variables));
+
+    execute_valid_document(document_source, &document, operation_name, root_node, variables, context, scratch)
+}
+
+// Parse a document, and validate it and the variables against the schema
+fn parse_and_validate<'a, QueryT, MutationT, S>(
+    document_source: &'a str,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: &Variables<S>,
+)
+    -> Result<Document<'a, S>, GraphQLError<'a>>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
 {
     let document = try!(parse_document_source(document_source));
 
@@ -288,7 +445,45 @@ pub fn execute<'a, CtxT, QueryT, MutationT>(
         }
     }
 
-    execute_validated_query(document, operation_name, root_node, variables, context)
+    Ok(document)
+}
+
+// Execute a validated document, answering introspection queries from the
+// introspection cache of the schema if it has one, once the operation passed
+// the checks of the execution options
+fn execute_valid_document<'a, 'v, CtxT, QueryT, MutationT, S>(
+    document_source: &str,
+    document: &Document<S>,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: Cow<'v, Variables<S>>,
+    context: &CtxT,
+    scratch: &mut ExecutionScratch<S>,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    try!(check_operation(document, operation_name, &root_node.options));
+
+    let cache = match root_node.introspection_cache {
+        Some(ref cache) if IntrospectionCache::is_cacheable(document, operation_name) => cache,
+        _ => return execute_validated_query_with_scratch(
+            document, operation_name, root_node, variables, context, scratch),
+    };
+
+    if let Some(value) = cache.get(document_source, operation_name) {
+        return Ok((value, Vec::new()));
+    }
+
+    let (value, errors) = try!(execute_validated_query_with_scratch(
+        document, operation_name, root_node, variables, context, scratch));
+    if errors.is_empty() {
+        cache.insert(document_source, operation_name, value.clone());
+    }
+
+    Ok((value, errors))
 }
 
 impl<'a> From<Spanning<ParseError<'a>>> for GraphQLError<'a> {
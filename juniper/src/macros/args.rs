@@ -5,6 +5,19 @@ macro_rules! __graphql__args {
     ( @as_expr, $e:expr) => { $e };
     ( @as_pattern, $p:pat) => { $p };
 
+    ( @invalid_arg_error, $args:ident, $name:ident ) => {
+        {
+            let name = $crate::to_camel_case(stringify!($name));
+            let error = $crate::FieldError::from(format!("Invalid value for argument \"{}\"", name))
+                .with_code($crate::GraphQLErrorCode::BadUserInput);
+
+            match $args.location(&name) {
+                Some(location) => $crate::FieldError::with_location(error, location.clone()),
+                None => error,
+            }
+        }
+    };
+
     ( @assign_arg_vars, $args:ident, $executorvar:ident, , $($rest:tt)* ) => {
         __graphql__args!(@assign_arg_vars, $args, $executorvar, $($rest)*);
     };
@@ -28,7 +41,7 @@ macro_rules! __graphql__args {
     ) => {
         let $name: $ty = $args
             .get(&$crate::to_camel_case(stringify!($name)))
-            .expect("Argument missing - validation must have failed");
+            .ok_or_else(|| __graphql__args!(@invalid_arg_error, $args, $name))?;
         __graphql__args!(@assign_arg_vars, $args, $executorvar, $($rest)*);
     };
 
@@ -39,7 +52,7 @@ macro_rules! __graphql__args {
     ) => {
         let $name: $ty = $args
             .get(&$crate::to_camel_case(stringify!($name)))
-            .expect("Argument missing - validation must have failed");
+            .ok_or_else(|| __graphql__args!(@invalid_arg_error, $args, $name))?;
     };
 
     ( @apply_args, $reg:expr, $base:expr, $info:expr, ( ) ) => {
@@ -59,7 +59,7 @@ macro_rules! graphql_enum {
         ( $name:path, $outname:tt, $descr:tt ),
         [ $( ( $eval:tt, $ename:tt, $edescr:tt, $edepr:tt ) , )* ]
     ) => {
-        impl $crate::GraphQLType for $name {
+        impl<__S> $crate::GraphQLType<__S> for $name where __S: $crate::ScalarValue {
             type Context = ();
             type TypeInfo = ();
 
@@ -67,7 +67,7 @@ macro_rules! graphql_enum {
                 Some(graphql_enum!(@as_expr, $outname))
             }
 
-            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r, __S>) -> $crate::meta::MetaType<'r, __S> {
                 graphql_enum!(
                     @maybe_apply, $descr, description,
                     registry.build_enum_type_with_info::<$name>(info, &[
@@ -84,7 +84,7 @@ macro_rules! graphql_enum {
                     .into_meta()
             }
 
-            fn resolve(&self, _: &(), _: Option<&[$crate::Selection]>, _: &$crate::Executor<Self::Context>) -> $crate::Value {
+            fn resolve(&self, _: &(), _: Option<&[$crate::Selection<__S>]>, _: &$crate::Executor<Self::Context, __S>) -> $crate::Value<__S> {
                 match *self {
                     $(
                         graphql_enum!(@as_pattern, $eval) =>
@@ -93,8 +93,8 @@ macro_rules! graphql_enum {
             }
         }
 
-        impl $crate::FromInputValue for $name {
-            fn from(v: &$crate::InputValue) -> Option<$name> {
+        impl<__S> $crate::FromInputValue<__S> for $name where __S: $crate::ScalarValue {
+            fn from(v: &$crate::InputValue<__S>) -> Option<$name> {
                 match v.as_enum_value().or_else(|| v.as_string_value()) {
                     $(
                         Some(graphql_enum!(@as_pattern, $ename))
@@ -104,8 +104,8 @@ macro_rules! graphql_enum {
             }
         }
 
-        impl $crate::ToInputValue for $name {
-            fn to(&self) -> $crate::InputValue {
+        impl<__S> $crate::ToInputValue<__S> for $name where __S: $crate::ScalarValue {
+            fn to(&self) -> $crate::InputValue<__S> {
                 match *self {
                     $(
                         graphql_enum!(@as_pattern, $eval) =>
@@ -59,25 +59,29 @@ macro_rules! __graphql__build_field_matches {
         __graphql__build_field_matches!($resolveargs, $acc, $( $rest )*);
     };
 
+    ( $resolveargs:tt, $acc:tt, instance_types : $value:tt $( $rest:tt )*) => {
+        __graphql__build_field_matches!($resolveargs, $acc, $( $rest )*);
+    };
+
     ( $resolveargs:tt, $acc:tt, , $( $rest:tt )*) => {
         __graphql__build_field_matches!($resolveargs, $acc, $( $rest )*);
     };
 
     (
-        ($outname:tt, $selfvar:ident, $fieldvar:ident, $argsvar:ident, $executorvar:ident),
+        ($outname:tt, $selfvar:ident, $fieldvar:ident, $argsvar:ident, $executorvar:ident, $scalar:ty),
         ( $( ( $name:ident; ( $($args:tt)* ); $t:ty; $body:block ) )* ),
     ) => {
         $(
             if $fieldvar == &$crate::to_camel_case(stringify!($name)) {
+                __graphql__args!(
+                    @assign_arg_vars,
+                    $argsvar, $executorvar, $($args)*
+                );
                 let result: $t = (||{
-                    __graphql__args!(
-                        @assign_arg_vars,
-                        $argsvar, $executorvar, $($args)*
-                    );
                     $body
                 })();
 
-                return ($crate::IntoResolvable::into(result, $executorvar.context())).and_then(
+                return ($crate::IntoResolvable::<_, _, $scalar>::into(result, $executorvar.context())).and_then(
                     |res| match res {
                         Some((ctx, r)) => $executorvar.replaced_context(ctx).resolve_with_ctx(&(), &r),
                         None => Ok($crate::Value::null()),
@@ -65,12 +65,12 @@ macro_rules! graphql_input_object {
         Some($name {
             $( $field_name: {
                 let n = $crate::to_camel_case(stringify!($field_name));
-                let v: Option<&&$crate::InputValue> = $var.get(&n[..]);
+                let v: Option<&&$crate::InputValue<__S>> = $var.get(&n[..]);
 
                 match v {
                     $( Some(&&$crate::InputValue::Null) | None if true => $default, )*
                         Some(v) => $crate::FromInputValue::from(v).unwrap(),
-                        _ => $crate::FromInputValue::from(&$crate::InputValue::null()).unwrap()
+                        _ => $crate::FromInputValue::from_implicit_null().unwrap()
                 }
             } ),*
         })
@@ -219,11 +219,25 @@ macro_rules! graphql_input_object {
         ( $meta:tt, $pubmod:tt, $name:tt, $outname:tt, $fields:tt, $descr:tt ),
     ) => {
         graphql_input_object!(@generate_struct_fields, $meta, $pubmod, $name, $fields);
+        graphql_input_object!(@generate_impls, $name, $outname, $descr, $fields);
+    };
 
-        impl $crate::FromInputValue for $name {
-            fn from(value: &$crate::InputValue) -> Option<$name> {
+    // Generate the FromInputValue, ToInputValue, and GraphQLType impls, all
+    // generic over the scalar value type as far as the field types allow
+    (
+        @generate_impls,
+        $name:tt, $outname:tt, $descr:tt,
+        ( $($field_name:ident $(= $default:tt)* : $field_type:ty $(as $descr_:tt)* $(,)* ),* )
+    ) => {
+        impl<__S> $crate::FromInputValue<__S> for $name
+            where __S: $crate::ScalarValue,
+                  $( $field_type: $crate::FromInputValue<__S>, )*
+        {
+            fn from(value: &$crate::InputValue<__S>) -> Option<$name> {
                 if let Some(obj) = value.to_object_value() {
-                    graphql_input_object!(@generate_from_input_value, $name, obj, $fields)
+                    graphql_input_object!(
+                        @generate_from_input_value, $name, obj,
+                        ( $($field_name $(= $default)* : $field_type),* ))
                 }
                 else {
                    None
@@ -231,13 +245,22 @@ macro_rules! graphql_input_object {
             }
         }
 
-        impl $crate::ToInputValue for $name {
-            fn to(&self) -> $crate::InputValue {
-                graphql_input_object!(@generate_to_input_value, $name, self, $fields)
+        impl<__S> $crate::ToInputValue<__S> for $name
+            where __S: $crate::ScalarValue,
+                  $( $field_type: $crate::ToInputValue<__S>, )*
+        {
+            fn to(&self) -> $crate::InputValue<__S> {
+                graphql_input_object!(
+                    @generate_to_input_value, $name, self,
+                    ( $($field_name $(= $default)* : $field_type),* ))
             }
         }
 
-        impl $crate::GraphQLType for $name {
+        impl<__S> $crate::GraphQLType<__S> for $name
+            where __S: $crate::ScalarValue,
+                  $( $field_type: $crate::GraphQLType<__S, TypeInfo=()>
+                      + $crate::FromInputValue<__S> + $crate::ToInputValue<__S>, )*
+        {
             type Context = ();
             type TypeInfo = ();
 
@@ -245,8 +268,10 @@ macro_rules! graphql_input_object {
                 Some($outname)
             }
 
-            fn meta<'r>(_: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
-                let fields = graphql_input_object!(@generate_meta_fields, registry, $fields);
+            fn meta<'r>(_: &(), registry: &mut $crate::Registry<'r, __S>) -> $crate::meta::MetaType<'r, __S> {
+                let fields = graphql_input_object!(
+                    @generate_meta_fields, registry,
+                    ( $($field_name $(= $default)* : $field_type $(as $descr_)*),* ));
                 graphql_input_object!(
                     @maybe_apply, $descr, description,
                     registry.build_input_object_type::<$name>(fields)).into_meta()
@@ -13,8 +13,8 @@ type name as well as downcast the general type to the actual concrete type.
 ## Syntax
 
 See the documentation for [`graphql_object!`][1] on the general item and type
-syntax. `graphql_interface!` requires an additional `instance_resolvers` item,
-and does _not_ support the `interfaces` item.
+syntax. `graphql_interface!` requires an additional `instance_resolvers` or
+`instance_types` item, and does _not_ support the `interfaces` item.
 
 `instance_resolvers` is a match like structure used to resolve the concrete
 instance type of the interface. It starts with a context argument and continues
@@ -34,6 +34,26 @@ in order - the first one returning `Some` will be the determined type name. When
 resolving fragment type conditions, only the corresponding match arm will be
 executed.
 
+### Trait objects
+
+If the interface is exposed through a pointer to a trait object, e.g.
+`Box<Pet>` or `&'a Pet`, the concrete type can be determined through dynamic
+dispatch instead. Make [`DynGraphQLType`][2] a supertrait of the trait, and
+replace `instance_resolvers` with an `instance_types` item listing all types
+that implement it, so they are registered in the schema:
+
+```rust,ignore
+trait Pet: DynGraphQLType<Database> {
+    fn name(&self) -> &str;
+}
+
+graphql_interface!(Box<Pet>: Database as "Pet" |&self| {
+    field name() -> &str { self.name() }
+
+    instance_types: [Dog, Cat]
+});
+```
+
 ## Example
 
 A simplified extract from the StarWars schema example shows how to use the
@@ -49,6 +69,8 @@ struct Database {
     droids: HashMap<String, Droid>,
 }
 
+impl juniper::Context for Database {}
+
 trait Character {
     fn id(&self) -> &str;
 }
@@ -83,6 +105,7 @@ graphql_interface!(<'a> &'a Character: Database as "Character" |&self| {
 ```
 
 [1]: macro.graphql_object!.html
+[2]: trait.DynGraphQLType.html
 
 */
 #[macro_export]
@@ -186,40 +209,77 @@ macro_rules! graphql_interface {
             graphql_interface!(@gather_meta, ($reg, $acc, $info, $descr), $( $rest )*)
     };
 
+    // instance_types: [...]
+    (
+        @ gather_meta,
+        ($reg:expr, $acc:expr, $info:expr, $descr:expr),
+        instance_types : [ $( $srctype:ty ),* $(,)* ] $( $rest:tt )*
+    ) => {
+        $(
+            let _ = $reg.get_type::<$srctype>();
+        )*
+
+            graphql_interface!(@gather_meta, ($reg, $acc, $info, $descr), $( $rest )*)
+    };
+
     // instance_resolvers: | <ctxtvar> | [...]
     (
         @ concrete_type_name,
-        ($outname:tt, $ctxtarg:ident, $ctxttype:ty),
+        ($outname:tt, $ctxtarg:ident, $ctxttype:ty, $scalar:ty, $mainself:ident),
         instance_resolvers : | $ctxtvar:pat | { $( $srctype:ty => $resolver:expr ),* $(,)* } $( $rest:tt )*
     ) => {
         let $ctxtvar = &$ctxtarg;
 
         $(
             if ($resolver as Option<$srctype>).is_some() {
-                return (<$srctype as $crate::GraphQLType>::name(&())).unwrap().to_owned();
+                return (<$srctype as $crate::GraphQLType<$scalar>>::name(&())).unwrap().to_owned();
             }
         )*
 
             panic!("Concrete type not handled by instance resolvers on {}", $outname);
     };
 
+    // instance_types: [...]
+    (
+        @ concrete_type_name,
+        ($outname:tt, $ctxtarg:ident, $ctxttype:ty, $scalar:ty, $mainself:ident),
+        instance_types : [ $( $srctype:ty ),* $(,)* ] $( $rest:tt )*
+    ) => {
+        return $crate::DynGraphQLType::<$ctxttype, $scalar>::dyn_type_name(&**$mainself);
+    };
+
     // instance_resolvers: | <ctxtvar> |
     (
         @ resolve_into_type,
-        ($outname:tt, $typenamearg:ident, $execarg:ident, $ctxttype:ty),
+        ($outname:tt, $typenamearg:ident, $execarg:ident, $ctxttype:ty, $scalar:ty, $mainself:ident),
         instance_resolvers : | $ctxtvar:pat | { $( $srctype:ty => $resolver:expr ),* $(,)* } $( $rest:tt )*
     ) => {
         let $ctxtvar = &$execarg.context();
 
         $(
-            if $typenamearg == (<$srctype as $crate::GraphQLType>::name(&())).unwrap() {
-                return $execarg.resolve(&(), &$resolver);
+            if $typenamearg == (<$srctype as $crate::GraphQLType<$scalar>>::name(&())).unwrap() {
+                return $execarg.resolve_with_ctx(&(), &$resolver);
             }
         )*
 
             panic!("Concrete type not handled by instance resolvers on {}", $outname);
     };
 
+    // instance_types: [...]
+    (
+        @ resolve_into_type,
+        ($outname:tt, $typenamearg:ident, $execarg:ident, $ctxttype:ty, $scalar:ty, $mainself:ident),
+        instance_types : [ $( $srctype:ty ),* $(,)* ] $( $rest:tt )*
+    ) => {
+        let instance = &**$mainself;
+
+        if $typenamearg == $crate::DynGraphQLType::<$ctxttype, $scalar>::dyn_type_name(instance) {
+            return $crate::DynGraphQLType::<$ctxttype, $scalar>::dyn_resolve(instance, $execarg);
+        }
+
+        return Ok($crate::Value::null());
+    };
+
     ( @ $mfn:ident, $args:tt, $first:tt $($rest:tt)* ) => {
         graphql_interface!(@ $mfn, $args, $($rest)*);
     };
@@ -227,11 +287,14 @@ macro_rules! graphql_interface {
     ( @ $mfn:ident, $buildargs:tt, ) => {};
 
     (
-        ( $($lifetime:tt),* ) $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        ( $($lifetime:tt),* ) $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty
+        | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
-        graphql_interface!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
+        graphql_interface!(@as_item, impl<$($lifetime),*> $crate::GraphQLType<$scalar> for $name
+            where $scalar: $crate::ScalarValue
+        {
             type Context = $ctxt;
             type TypeInfo = ();
 
@@ -241,7 +304,7 @@ macro_rules! graphql_interface {
 
             #[allow(unused_assignments)]
             #[allow(unused_mut)]
-            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r, $scalar>) -> $crate::meta::MetaType<'r, $scalar> {
                 let mut fields = Vec::new();
                 let mut description = None;
                 graphql_interface!(@ gather_meta, (registry, fields, info, description), $($items)*);
@@ -256,9 +319,9 @@ macro_rules! graphql_interface {
 
             #[allow(unused_variables)]
             #[allow(unused_mut)]
-            fn resolve_field(&$mainself, info: &(), field: &str, args: &$crate::Arguments, mut executor: &$crate::Executor<Self::Context>) -> $crate::ExecutionResult {
+            fn resolve_field(&$mainself, info: &(), field: &str, args: &$crate::Arguments<$scalar>, mut executor: &$crate::Executor<Self::Context, $scalar>) -> $crate::ExecutionResult<$scalar> {
                 __graphql__build_field_matches!(
-                    ($outname, $mainself, field, args, executor),
+                    ($outname, $mainself, field, args, executor, $scalar),
                     (),
                     $($items)*);
             }
@@ -266,7 +329,7 @@ macro_rules! graphql_interface {
             fn concrete_type_name(&$mainself, context: &Self::Context) -> String {
                 graphql_interface!(
                     @ concrete_type_name,
-                    ($outname, context, $ctxt),
+                    ($outname, context, $ctxt, $scalar, $mainself),
                     $($items)*);
             }
 
@@ -274,26 +337,47 @@ macro_rules! graphql_interface {
                 &$mainself,
                 _: &(),
                 type_name: &str,
-                _: Option<&[$crate::Selection]>,
-                executor: &$crate::Executor<Self::Context>,
+                _: Option<&[$crate::Selection<$scalar>]>,
+                executor: &$crate::Executor<Self::Context, $scalar>,
             )
-                -> $crate::ExecutionResult
+                -> $crate::ExecutionResult<$scalar>
             {
                 graphql_interface!(
                     @ resolve_into_type,
-                    ($outname, type_name, executor, $ctxt),
+                    ($outname, type_name, executor, $ctxt, $scalar, $mainself),
                     $($items)*);
             }
         });
     };
 
+    (
+        <$($lifetime:tt),*> $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty
+        | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_interface!(
+            ($($lifetime),*) $name : $ctxt as $outname where Scalar = $scalar
+            | &$mainself | { $( $items )* });
+    };
+
     (
         <$($lifetime:tt),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
         graphql_interface!(
-            ($($lifetime),*) $name : $ctxt as $outname | &$mainself | { $( $items )* });
+            ($($lifetime),*) $name : $ctxt as $outname where Scalar = $crate::DefaultScalarValue
+            | &$mainself | { $( $items )* });
+    };
+
+    (
+        $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_interface!(
+            () $name : $ctxt as $outname where Scalar = $scalar | &$mainself | { $( $items )* });
     };
 
     (
@@ -301,7 +385,19 @@ macro_rules! graphql_interface {
             $( $items:tt )*
         }
     ) => {
-        graphql_interface!(() $name : $ctxt as $outname | &$mainself | { $( $items )* });
+        graphql_interface!(
+            () $name : $ctxt as $outname where Scalar = $crate::DefaultScalarValue
+            | &$mainself | { $( $items )* });
+    };
+
+    (
+        $name:ty : $ctxt:ty where Scalar = $scalar:ty | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_interface!(
+            () $name : $ctxt as (stringify!($name)) where Scalar = $scalar
+            | &$mainself | { $( $items )* });
     };
 
     (
@@ -309,6 +405,8 @@ macro_rules! graphql_interface {
             $( $items:tt )*
         }
     ) => {
-        graphql_interface!(() $name : $ctxt as (stringify!($name)) | &$mainself | { $( $items )* });
+        graphql_interface!(
+            () $name : $ctxt as (stringify!($name)) where Scalar = $crate::DefaultScalarValue
+            | &$mainself | { $( $items )* });
     };
 }
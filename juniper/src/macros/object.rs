@@ -136,7 +136,7 @@ graphql_object!(User: () |&self| {
     }
 
     field name() -> FieldResult<&String> {
-        Err("Does not have a name".to_owned())
+        Err("Does not have a name".into())
     }
 });
 
@@ -154,6 +154,16 @@ ExposedType: ContextType as "ExposedName" |&self| { items... }
 <Generics> ExposedType: ContextType as "ExposedName" |&self| { items... }
 ```
 
+Objects are exposed in schemas using `DefaultScalarValue`. To expose the
+object in a schema with a custom scalar value representation, name it with a
+`where Scalar = ...` clause before the `|&self|` part. The scalar type can
+also be one of the generics, in which case the object works with all of them:
+
+```text
+ExposedType: ContextType as "ExposedName" where Scalar = MyScalarValue |&self| { items... }
+<S> ExposedType: ContextType as "ExposedName" where Scalar = S |&self| { items... }
+```
+
 ## Items
 
 Each item within the brackets of the top level declaration has its own syntax.
@@ -358,9 +368,11 @@ macro_rules! graphql_object {
 
     (
         ( $($lifetime:tt)* );
-        $name:ty; $ctxt:ty; $outname:expr; $mainself:ident; $($items:tt)*
+        $name:ty; $ctxt:ty; $outname:expr; $mainself:ident; $scalar:ty; $($items:tt)*
     ) => {
-        graphql_object!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
+        graphql_object!(@as_item, impl<$($lifetime)*> $crate::GraphQLType<$scalar> for $name
+            where $scalar: $crate::ScalarValue
+        {
             type Context = $ctxt;
             type TypeInfo = ();
 
@@ -370,7 +382,7 @@ macro_rules! graphql_object {
 
             #[allow(unused_assignments)]
             #[allow(unused_mut)]
-            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r, $scalar>) -> $crate::meta::MetaType<'r, $scalar> {
                 let mut fields = Vec::new();
                 let mut description = None;
                 let mut interfaces: Option<Vec<$crate::Type>> = None;
@@ -401,26 +413,46 @@ macro_rules! graphql_object {
                 &$mainself,
                 info: &(),
                 field: &str,
-                args: &$crate::Arguments,
-                executor: &$crate::Executor<Self::Context>
+                args: &$crate::Arguments<$scalar>,
+                executor: &$crate::Executor<Self::Context, $scalar>
             )
-                -> $crate::ExecutionResult
+                -> $crate::ExecutionResult<$scalar>
             {
                 __graphql__build_field_matches!(
-                    ($outname, $mainself, field, args, executor),
+                    ($outname, $mainself, field, args, executor, $scalar),
                     (),
                     $($items)*);
             }
         });
     };
 
+    (
+        <$( $lifetime:tt ),*> $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty
+        | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_object!(
+            ( $($lifetime),* ); $name; $ctxt; $outname; $mainself; $scalar; $( $items )*);
+    };
+
     (
         <$( $lifetime:tt ),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
         graphql_object!(
-            ( $($lifetime),* ); $name; $ctxt; $outname; $mainself; $( $items )*);
+            ( $($lifetime),* ); $name; $ctxt; $outname; $mainself; $crate::DefaultScalarValue;
+            $( $items )*);
+    };
+
+    (
+        $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_object!(
+            ( ); $name; $ctxt; $outname; $mainself; $scalar; $( $items )*);
     };
 
     (
@@ -429,7 +461,16 @@ macro_rules! graphql_object {
         }
     ) => {
         graphql_object!(
-            ( ); $name; $ctxt; $outname; $mainself; $( $items )*);
+            ( ); $name; $ctxt; $outname; $mainself; $crate::DefaultScalarValue; $( $items )*);
+    };
+
+    (
+        $name:ty : $ctxt:ty where Scalar = $scalar:ty | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_object!(
+            ( ); $name; $ctxt; (stringify!($name)); $mainself; $scalar; $( $items )*);
     };
 
     (
@@ -438,6 +479,7 @@ macro_rules! graphql_object {
         }
     ) => {
         graphql_object!(
-            ( ); $name; $ctxt; (stringify!($name)); $mainself; $( $items )*);
+            ( ); $name; $ctxt; (stringify!($name)); $mainself; $crate::DefaultScalarValue;
+            $( $items )*);
     };
 }
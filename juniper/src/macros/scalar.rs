@@ -35,6 +35,56 @@ In addition to implementing `GraphQLType` for the type in question,
 `FromInputValue` and `ToInputValue` is also implemented. This makes the type
 usable as arguments and default values.
 
+The implementations are generic over the `ScalarValue` representation, so
+the scalar can be used in any schema. If the scalar needs to inspect a
+specific representation, e.g. to accept the `Scalar` variant of
+`InputValue`, name it with `where Scalar = ...`:
+
+```rust
+# #[macro_use] extern crate juniper;
+# extern crate serde;
+# use std::fmt;
+# use juniper::{Value, ScalarValue, InputValue};
+#[derive(Clone, Debug, PartialEq)]
+struct BigInt(i64);
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl serde::Serialize for BigInt {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_i64(self.0)
+    }
+}
+
+impl ScalarValue for BigInt {
+    fn from_int_literal(literal: &str) -> Option<BigInt> {
+        literal.parse().ok().map(BigInt)
+    }
+}
+
+struct Long(i64);
+
+graphql_scalar!(Long where Scalar = BigInt {
+    resolve(&self) -> Value {
+        Value::scalar(BigInt(self.0))
+    }
+
+    from_input_value(v: &InputValue) -> Option<Long> {
+        match *v {
+            InputValue::Int(i) => Some(Long(i as i64)),
+            InputValue::Scalar(BigInt(i)) => Some(Long(i)),
+            _ => None,
+        }
+    }
+});
+
+# fn main() { }
+```
+
 */
 #[macro_export]
 macro_rules! graphql_scalar {
@@ -57,13 +107,15 @@ macro_rules! graphql_scalar {
     // and body for the from() method on FromInputValue.
     (
         @generate,
-        ( $name:ty, $outname:tt, $descr:tt ),
+        ( $name:ty, $outname:tt, $descr:tt, ( $( $generic:ident ),* ), $scalar:ty ),
         (
             ( $resolve_selfvar:ident, $resolve_body:block ),
             ( $fiv_arg:ident, $fiv_result:ty, $fiv_body:block )
         )
     ) => {
-        impl $crate::GraphQLType for $name {
+        impl<$( $generic ),*> $crate::GraphQLType<$scalar> for $name
+            where $scalar: $crate::ScalarValue
+        {
             type Context = ();
             type TypeInfo = ();
 
@@ -71,7 +123,7 @@ macro_rules! graphql_scalar {
                 Some(graphql_scalar!( @as_expr, $outname ))
             }
 
-            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+            fn meta<'r>(info: &(), registry: &mut $crate::Registry<'r, $scalar>) -> $crate::meta::MetaType<'r, $scalar> {
                 graphql_scalar!(
                     @maybe_apply, $descr, description,
                     registry.build_scalar_type::<Self>(info))
@@ -81,20 +133,25 @@ macro_rules! graphql_scalar {
             fn resolve(
                 &$resolve_selfvar,
                 _: &(),
-                _: Option<&[$crate::Selection]>,
-                _: &$crate::Executor<Self::Context>) -> $crate::Value {
+                _: Option<&[$crate::Selection<$scalar>]>,
+                _: &$crate::Executor<Self::Context, $scalar>) -> $crate::Value<$scalar> {
                 $resolve_body
             }
         }
 
-        impl $crate::ToInputValue for $name {
-            fn to(&$resolve_selfvar) -> $crate::InputValue {
-                $crate::ToInputValue::to(&$resolve_body)
+        impl<$( $generic ),*> $crate::ToInputValue<$scalar> for $name
+            where $scalar: $crate::ScalarValue
+        {
+            fn to(&$resolve_selfvar) -> $crate::InputValue<$scalar> {
+                let v: $crate::Value<$scalar> = $resolve_body;
+                $crate::ToInputValue::to(&v)
             }
         }
 
-        impl $crate::FromInputValue for $name {
-            fn from($fiv_arg: &$crate::InputValue) -> $fiv_result {
+        impl<$( $generic ),*> $crate::FromInputValue<$scalar> for $name
+            where $scalar: $crate::ScalarValue
+        {
+            fn from($fiv_arg: &$crate::InputValue<$scalar>) -> $fiv_result {
                 $fiv_body
             }
         }
@@ -132,22 +189,34 @@ macro_rules! graphql_scalar {
     // description: <description>
     (
         @parse,
-        ( $name:ty, $outname:tt, $_ignored:tt ),
+        ( $name:ty, $outname:tt, $_ignored:tt, $generics:tt, $scalar:ty ),
         $acc:tt,
         description: $descr:tt $($rest:tt)*
     ) => {
-        graphql_scalar!( @parse, ( $name, $outname, $descr ), $acc, $($rest)* );
+        graphql_scalar!( @parse, ( $name, $outname, $descr, $generics, $scalar ), $acc, $($rest)* );
+    };
+
+    // Entry point:
+    // RustName as "GraphQLName" where Scalar = ScalarValueType { ... }
+    ( $name:ty as $outname:tt where Scalar = $scalar:ty { $( $items:tt )* }) => {
+        graphql_scalar!( @parse, ( $name, $outname, None, (), $scalar ), ( None, None ), $($items)* );
+    };
+
+    // Entry point:
+    // RustName where Scalar = ScalarValueType { ... }
+    ( $name:ty where Scalar = $scalar:ty { $( $items:tt )* }) => {
+        graphql_scalar!( @parse, ( $name, (stringify!($name)), None, (), $scalar ), ( None, None ), $($items)* );
     };
 
     // Entry point:
     // RustName as "GraphQLName" { ... }
     ( $name:ty as $outname:tt { $( $items:tt )* }) => {
-        graphql_scalar!( @parse, ( $name, $outname, None ), ( None, None ), $($items)* );
+        graphql_scalar!( @parse, ( $name, $outname, None, (__S), __S ), ( None, None ), $($items)* );
     };
 
     // Entry point
     // RustName { ... }
     ( $name:ty { $( $items:tt )* }) => {
-        graphql_scalar!( @parse, ( $name, (stringify!($name)), None ), ( None, None ), $($items)* );
+        graphql_scalar!( @parse, ( $name, (stringify!($name)), None, (__S), __S ), ( None, None ), $($items)* );
     };
 }
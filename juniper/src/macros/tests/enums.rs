@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use executor::Variables;
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 use types::scalars::EmptyMutation;
 
@@ -68,7 +68,7 @@ graphql_object!(Root: () |&self| {
     field enum_deprecation() -> EnumDeprecation { EnumDeprecation::Foo }
 });
 
-fn run_type_info_query<F>(doc: &str, f: F) where F: Fn((&HashMap<String, Value>, &Vec<Value>)) -> () {
+fn run_type_info_query<F>(doc: &str, f: F) where F: Fn((&Object, &Vec<Value>)) -> () {
     let schema = RootNode::new(Root {}, EmptyMutation::<()>::new());
 
     let (result, errs) = ::execute(doc, None, &schema, &Variables::new(), &())
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use ast::{InputValue, FromInputValue};
 use executor::Variables;
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 use types::scalars::EmptyMutation;
 
@@ -103,7 +103,7 @@ graphql_object!(Root: () |&self| {
     }
 });
 
-fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&HashMap<String, Value>, &Vec<Value>) -> () {
+fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&Object, &Vec<Value>) -> () {
     let schema = RootNode::new(Root {}, EmptyMutation::<()>::new());
 
     let (result, errs) = ::execute(doc, None, &schema, &Variables::new(), &())
@@ -178,7 +178,7 @@ fn default_name_introspection() {
 
 #[test]
 fn default_name_input_value() {
-    let iv = InputValue::object(vec![
+    let iv: InputValue = InputValue::object(vec![
         ("fieldOne", InputValue::string("number one")),
         ("fieldTwo", InputValue::string("number two")),
     ].into_iter().collect());
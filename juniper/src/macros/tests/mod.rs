@@ -6,6 +6,7 @@ mod field;
 mod object;
 mod interface;
 mod union;
+mod value;
 
 
 // This asserts that the input objects defined public actually became public
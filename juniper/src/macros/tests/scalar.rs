@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use executor::Variables;
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 use types::scalars::EmptyMutation;
 
@@ -70,7 +70,7 @@ graphql_object!(Root: () |&self| {
     field scalar_description() -> ScalarDescription { ScalarDescription(0) }
 });
 
-fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&HashMap<String, Value>) -> () {
+fn run_type_info_query<F>(doc: &str, f: F) where F: Fn(&Object) -> () {
     let schema = RootNode::new(Root {}, EmptyMutation::<()>::new());
 
     let (result, errs) = ::execute(doc, None, &schema, &Variables::new(), &())
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use ast::InputValue;
-use value::Value;
+use value::{Value, Object};
 use schema::model::RootNode;
 use types::scalars::EmptyMutation;
 
@@ -97,7 +97,7 @@ graphql_object!(<'a> Root: () as "Root" |&self| {
 
 
 fn run_type_info_query<F>(type_name: &str, f: F)
-    where F: Fn(&HashMap<String, Value>, &Vec<Value>) -> ()
+    where F: Fn(&Object, &Vec<Value>) -> ()
 {
     let doc = r#"
     query ($typeName: String!) {
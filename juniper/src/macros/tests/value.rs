@@ -0,0 +1,101 @@
+use ast::InputValue;
+use value::Value;
+
+#[test]
+fn test_value_leaves() {
+    let name = "R2-D2".to_owned();
+    let missing: Option<i32> = None;
+
+    let cases: Vec<(Value, Value)> = vec![
+        (graphql_value!(null), Value::null()),
+        (graphql_value!(1), Value::int(1)),
+        (graphql_value!(-1), Value::int(-1)),
+        (graphql_value!(1.5), Value::float(1.5)),
+        (graphql_value!(true), Value::boolean(true)),
+        (graphql_value!("abc"), Value::string("abc")),
+        (graphql_value!(name), Value::string("R2-D2")),
+        (graphql_value!(missing), Value::null()),
+        (graphql_value!(Some(3)), Value::int(3)),
+    ];
+
+    for (actual, expected) in cases {
+        assert_eq!(actual, expected);
+    }
+}
+
+#[test]
+fn test_value_lists() {
+    let v: Value = graphql_value!([]);
+    assert_eq!(v, Value::list(vec![]));
+
+    let v: Value = graphql_value!([1, -2, 1 + 2, null, [true], { "a": 1 },]);
+    assert_eq!(
+        v,
+        Value::list(vec![
+            Value::int(1),
+            Value::int(-2),
+            Value::int(3),
+            Value::null(),
+            Value::list(vec![Value::boolean(true)]),
+            Value::object(vec![("a", Value::int(1))].into_iter().collect()),
+        ]));
+}
+
+#[test]
+fn test_value_objects() {
+    let key = "dynamic";
+    let friends = vec!["Luke", "Leia"];
+
+    let v: Value = graphql_value!({});
+    assert_eq!(v.as_object_value().map(|o| o.is_empty()), Some(true));
+
+    let v: Value = graphql_value!({
+        "hero": {
+            "name": "R2-D2",
+            "height": -0.96,
+            "friends": [friends[0], friends[1]],
+            "droid": true,
+            "home": null
+        },
+        (key): 1,
+    });
+    assert_eq!(
+        v,
+        Value::object(vec![
+            ("hero", Value::object(vec![
+                ("name", Value::string("R2-D2")),
+                ("height", Value::float(-0.96)),
+                ("friends", Value::list(vec![Value::string("Luke"), Value::string("Leia")])),
+                ("droid", Value::boolean(true)),
+                ("home", Value::null()),
+            ].into_iter().collect())),
+            ("dynamic", Value::int(1)),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn test_value_objects_keep_order() {
+    let v: Value = graphql_value!({ "b": 1, "a": 2 });
+    let keys: Vec<&str> = v.as_object_value().unwrap().iter().map(|&(ref k, _)| &**k).collect();
+
+    assert_eq!(keys, vec!["b", "a"]);
+}
+
+#[test]
+fn test_input_value() {
+    let iv: InputValue = graphql_input_value!({
+        "episode": (InputValue::enum_value("EMPIRE")),
+        "first": @count,
+        "names": ["Luke", null],
+        "nested": { "x": -1.5 },
+    });
+
+    assert_eq!(
+        iv,
+        InputValue::parsed_object(vec![
+            ("episode", InputValue::enum_value("EMPIRE")),
+            ("first", InputValue::variable("count")),
+            ("names", InputValue::list(vec![InputValue::string("Luke"), InputValue::null()])),
+            ("nested", InputValue::object(vec![("x", InputValue::float(-1.5))].into_iter().collect())),
+        ].into_iter().map(|(k, v)| (::parser::Spanning::unlocated(k.to_owned()), ::parser::Spanning::unlocated(v))).collect()));
+}
@@ -55,14 +55,14 @@ macro_rules! graphql_union {
     // instance_resolvers: | <ctxtvar> | [...]
     (
         @ concrete_type_name,
-        ($outname:tt, $ctxtarg:ident, $ctxttype:ty),
+        ($outname:tt, $ctxtarg:ident, $ctxttype:ty, $scalar:ty),
         instance_resolvers: | $ctxtvar:pat | { $( $srctype:ty => $resolver:expr ),* $(,)* } $( $rest:tt )*
     ) => {
         let $ctxtvar = &$ctxtarg;
 
         $(
             if let Some(_) = $resolver as Option<$srctype> {
-                return (<$srctype as $crate::GraphQLType>::name(&())).unwrap().to_owned();
+                return (<$srctype as $crate::GraphQLType<$scalar>>::name(&())).unwrap().to_owned();
             }
         )*
 
@@ -73,14 +73,14 @@ macro_rules! graphql_union {
     // instance_resolvers: | <ctxtvar> | [...]
     (
         @ resolve_into_type,
-        ($outname:tt, $typenamearg:ident, $execarg:ident, $ctxttype:ty),
+        ($outname:tt, $typenamearg:ident, $execarg:ident, $ctxttype:ty, $scalar:ty),
         instance_resolvers: | $ctxtvar:pat | { $( $srctype:ty => $resolver:expr ),* $(,)* } $( $rest:tt )*
     ) => {
         let $ctxtvar = &$execarg.context();
 
         $(
-            if $typenamearg == (<$srctype as $crate::GraphQLType>::name(&())).unwrap().to_owned() {
-                return $execarg.resolve(&(), &$resolver);
+            if $typenamearg == (<$srctype as $crate::GraphQLType<$scalar>>::name(&())).unwrap().to_owned() {
+                return $execarg.resolve_with_ctx(&(), &$resolver);
             }
         )*
 
@@ -101,11 +101,14 @@ macro_rules! graphql_union {
     ( @ $mfn:ident, $args:tt, ) => {};
 
     (
-        ( $($lifetime:tt),* ) $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
+        ( $($lifetime:tt),* ) $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty
+        | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
-        graphql_union!(@as_item, impl<$($lifetime)*> $crate::GraphQLType for $name {
+        graphql_union!(@as_item, impl<$($lifetime),*> $crate::GraphQLType<$scalar> for $name
+            where $scalar: $crate::ScalarValue
+        {
             type Context = $ctxt;
             type TypeInfo = ();
 
@@ -115,7 +118,7 @@ macro_rules! graphql_union {
 
             #[allow(unused_assignments)]
             #[allow(unused_mut)]
-            fn meta<'r>(_: &(), registry: &mut $crate::Registry<'r>) -> $crate::meta::MetaType<'r> {
+            fn meta<'r>(_: &(), registry: &mut $crate::Registry<'r, $scalar>) -> $crate::meta::MetaType<'r, $scalar> {
                 let mut types;
                 let mut description = None;
                 graphql_union!(@ gather_meta, (registry, types, description), $($items)*);
@@ -131,7 +134,7 @@ macro_rules! graphql_union {
             fn concrete_type_name(&$mainself, context: &Self::Context) -> String {
                 graphql_union!(
                     @ concrete_type_name,
-                    ($outname, context, $ctxt),
+                    ($outname, context, $ctxt, $scalar),
                     $($items)*);
             }
 
@@ -139,26 +142,47 @@ macro_rules! graphql_union {
                 &$mainself,
                 _: &(),
                 type_name: &str,
-                _: Option<&[$crate::Selection]>,
-                executor: &$crate::Executor<Self::Context>,
+                _: Option<&[$crate::Selection<$scalar>]>,
+                executor: &$crate::Executor<Self::Context, $scalar>,
             )
-                -> $crate::ExecutionResult
+                -> $crate::ExecutionResult<$scalar>
             {
                 graphql_union!(
                     @ resolve_into_type,
-                    ($outname, type_name, executor, $ctxt),
+                    ($outname, type_name, executor, $ctxt, $scalar),
                     $($items)*);
             }
         });
     };
 
+    (
+        <$($lifetime:tt),*> $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty
+        | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_union!(
+            ($($lifetime),*) $name : $ctxt as $outname where Scalar = $scalar
+            | &$mainself | { $( $items )* });
+    };
+
     (
         <$($lifetime:tt),*> $name:ty : $ctxt:ty as $outname:tt | &$mainself:ident | {
             $( $items:tt )*
         }
     ) => {
         graphql_union!(
-            ($($lifetime),*) $name : $ctxt as $outname | &$mainself | { $( $items )* });
+            ($($lifetime),*) $name : $ctxt as $outname where Scalar = $crate::DefaultScalarValue
+            | &$mainself | { $( $items )* });
+    };
+
+    (
+        $name:ty : $ctxt:ty as $outname:tt where Scalar = $scalar:ty | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_union!(
+            () $name : $ctxt as $outname where Scalar = $scalar | &$mainself | { $( $items )* });
     };
 
     (
@@ -166,7 +190,19 @@ macro_rules! graphql_union {
             $( $items:tt )*
         }
     ) => {
-        graphql_union!(() $name : $ctxt as $outname | &$mainself | { $( $items )* });
+        graphql_union!(
+            () $name : $ctxt as $outname where Scalar = $crate::DefaultScalarValue
+            | &$mainself | { $( $items )* });
+    };
+
+    (
+        $name:ty : $ctxt:ty where Scalar = $scalar:ty | &$mainself:ident | {
+            $( $items:tt )*
+        }
+    ) => {
+        graphql_union!(
+            () $name : $ctxt as (stringify!($name)) where Scalar = $scalar
+            | &$mainself | { $( $items )* });
     };
 
     (
@@ -174,6 +210,8 @@ macro_rules! graphql_union {
             $( $items:tt )*
         }
     ) => {
-        graphql_union!(() $name : $ctxt as (stringify!($name)) | &$mainself | { $( $items )* });
+        graphql_union!(
+            () $name : $ctxt as (stringify!($name)) where Scalar = $crate::DefaultScalarValue
+            | &$mainself | { $( $items )* });
     };
 }
@@ -0,0 +1,175 @@
+/**
+Construct a `Value` from a JSON-like literal
+
+Objects and lists can be nested, `null` is the null value, and any other
+expression is converted with `Value::from`, which accepts strings, `i32`,
+`f64`, `bool`, `Option`s of those, and other `Value`s. Object keys are string
+literals or parenthesized expressions evaluating to a `String` or `&str`.
+Fields keep the order they were written in.
+
+```rust
+# #[macro_use] extern crate juniper;
+# use juniper::Value;
+# fn main() {
+let name = "R2-D2";
+
+let value: Value = graphql_value!({
+    "hero": {
+        "name": name,
+        "height": 0.96,
+        "friends": [{ "name": "Luke Skywalker" }, { "name": null }],
+    }
+});
+
+assert_eq!(
+    value.pointer("/hero/friends/0/name").and_then(Value::as_string_value),
+    Some("Luke Skywalker"));
+# }
+```
+*/
+#[macro_export]
+macro_rules! graphql_value {
+    ( $($tree:tt)+ ) => {
+        __graphql__value!(value $($tree)+)
+    };
+}
+
+/**
+Construct an `InputValue` from a JSON-like literal
+
+This accepts the same syntax as `graphql_value!`, and additionally `@name` for
+a reference to the variable `$name`. Enum values can be included with an
+expression such as `InputValue::enum_value("NEW_HOPE")`.
+
+```rust
+# #[macro_use] extern crate juniper;
+# use juniper::InputValue;
+# fn main() {
+let filter: InputValue = graphql_input_value!({
+    "episode": (InputValue::enum_value("EMPIRE")),
+    "first": @count,
+    "names": ["Luke", "Leia"],
+});
+
+assert_eq!(filter.to_string(), r#"{episode: EMPIRE, first: $count, names: ["Luke", "Leia"]}"#);
+# }
+```
+*/
+#[macro_export]
+macro_rules! graphql_input_value {
+    ( $($tree:tt)+ ) => {
+        __graphql__value!(input $($tree)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __graphql__value {
+    // Constructors for the two kinds of value trees
+    ( @null value ) => { $crate::Value::null() };
+    ( @null input ) => { $crate::InputValue::null() };
+
+    ( @leaf value $e:expr ) => { $crate::Value::from($e) };
+    ( @leaf input $e:expr ) => { $crate::InputValue::from($e) };
+
+    ( @list value [ $($elem:expr),* ] ) => {
+        $crate::Value::list(vec![ $($elem),* ])
+    };
+    ( @list input [ $($elem:expr),* ] ) => {
+        $crate::InputValue::list(vec![ $($elem),* ])
+    };
+
+    ( @object value [ $( ($key:expr, $value:expr) ),* ] ) => {
+        {
+            #[allow(unused_mut)]
+            let mut object = $crate::Object::new();
+            $( object.insert($key, $value); )*
+            $crate::Value::object(object)
+        }
+    };
+    ( @object input [ $( ($key:expr, $value:expr) ),* ] ) => {
+        $crate::InputValue::parsed_object(vec![ $( (
+            $crate::parser::Spanning::unlocated($key),
+            $crate::parser::Spanning::unlocated($value),
+        ) ),* ])
+    };
+
+    // List elements, syntax case:
+    // [ <value>, <value>, ... ]
+    ( @elems $kind:ident [ $($done:expr),* ] ) => {
+        __graphql__value!(@list $kind [ $($done),* ])
+    };
+    ( @elems $kind:ident [ $($done:expr),* ] $elem:tt , $($rest:tt)* ) => {
+        __graphql__value!(@elems $kind [ $($done,)* __graphql__value!($kind $elem) ] $($rest)*)
+    };
+    ( @elems $kind:ident [ $($done:expr),* ] $elem:tt ) => {
+        __graphql__value!(@elems $kind [ $($done,)* __graphql__value!($kind $elem) ])
+    };
+    ( @elems $kind:ident [ $($done:expr),* ] $($rest:tt)+ ) => {
+        __graphql__value!(@elem $kind [ $($done),* ] () $($rest)+)
+    };
+
+    // Elements spanning several tokens are collected up to the next comma
+    ( @elem $kind:ident [ $($done:expr),* ] ( $($cur:tt)+ ) , $($rest:tt)* ) => {
+        __graphql__value!(@elems $kind [ $($done,)* __graphql__value!($kind $($cur)+) ] $($rest)*)
+    };
+    ( @elem $kind:ident [ $($done:expr),* ] ( $($cur:tt)+ ) ) => {
+        __graphql__value!(@elems $kind [ $($done,)* __graphql__value!($kind $($cur)+) ])
+    };
+    ( @elem $kind:ident [ $($done:expr),* ] ( $($cur:tt)* ) $next:tt $($rest:tt)* ) => {
+        __graphql__value!(@elem $kind [ $($done),* ] ( $($cur)* $next ) $($rest)*)
+    };
+
+    // Object fields, syntax case:
+    // { <key>: <value>, <key>: <value>, ... }
+    ( @fields $kind:ident [ $($done:tt),* ] ) => {
+        __graphql__value!(@object $kind [ $($done),* ])
+    };
+    ( @fields $kind:ident [ $($done:tt),* ] $key:tt : $value:tt , $($rest:tt)* ) => {
+        __graphql__value!(
+            @fields $kind
+            [ $($done,)* (::std::string::String::from($key), __graphql__value!($kind $value)) ]
+            $($rest)*)
+    };
+    ( @fields $kind:ident [ $($done:tt),* ] $key:tt : $value:tt ) => {
+        __graphql__value!(
+            @fields $kind
+            [ $($done,)* (::std::string::String::from($key), __graphql__value!($kind $value)) ])
+    };
+    ( @fields $kind:ident [ $($done:tt),* ] $key:tt : $($rest:tt)+ ) => {
+        __graphql__value!(@field $kind [ $($done),* ] $key () $($rest)+)
+    };
+
+    // Field values spanning several tokens are collected up to the next comma
+    ( @field $kind:ident [ $($done:tt),* ] $key:tt ( $($cur:tt)+ ) , $($rest:tt)* ) => {
+        __graphql__value!(
+            @fields $kind
+            [ $($done,)* (::std::string::String::from($key), __graphql__value!($kind $($cur)+)) ]
+            $($rest)*)
+    };
+    ( @field $kind:ident [ $($done:tt),* ] $key:tt ( $($cur:tt)+ ) ) => {
+        __graphql__value!(
+            @fields $kind
+            [ $($done,)* (::std::string::String::from($key), __graphql__value!($kind $($cur)+)) ])
+    };
+    ( @field $kind:ident [ $($done:tt),* ] $key:tt ( $($cur:tt)* ) $next:tt $($rest:tt)* ) => {
+        __graphql__value!(@field $kind [ $($done),* ] $key ( $($cur)* $next ) $($rest)*)
+    };
+
+    // Values
+    ( $kind:ident null ) => {
+        __graphql__value!(@null $kind)
+    };
+    ( $kind:ident [ $($elems:tt)* ] ) => {
+        __graphql__value!(@elems $kind [] $($elems)*)
+    };
+    ( $kind:ident { $($fields:tt)* } ) => {
+        __graphql__value!(@fields $kind [] $($fields)*)
+    };
+    ( input @ $var:ident ) => {
+        $crate::InputValue::variable(stringify!($var))
+    };
+    ( $kind:ident $e:expr ) => {
+        __graphql__value!(@leaf $kind $e)
+    };
+}
@@ -0,0 +1,322 @@
+//! Executing queries with placeholder data
+//!
+//! `mock::execute` takes the same arguments as `juniper::execute`, and parses
+//! and validates the query the same way, but never calls a resolver. Every
+//! field is answered with placeholder data of its type instead, so clients can
+//! be developed against a schema whose resolvers don't exist yet:
+//!
+//! ```rust
+//! #[macro_use] extern crate juniper;
+//! use juniper::{EmptyMutation, RootNode, Variables};
+//!
+//! struct Ship;
+//!
+//! graphql_object!(Ship: () |&self| {
+//!     field name() -> String { unimplemented!() }
+//!     field crew() -> Vec<i32> { unimplemented!() }
+//! });
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field ship(id: String) -> Option<Ship> { unimplemented!() }
+//! });
+//!
+//! # fn main() {
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+//! let query = r#"{ ship(id: "1") { name crew } }"#;
+//!
+//! let (first, errors) = juniper::mock::execute(query, None, &schema, &Variables::new(), &()).unwrap();
+//! let (second, _) = juniper::mock::execute(query, None, &schema, &Variables::new(), &()).unwrap();
+//!
+//! assert!(errors.is_empty());
+//! assert_eq!(first, second);
+//! assert_eq!(first.pointer("/ship/crew").and_then(|v| v.as_list_value()).map(Vec::len), Some(2));
+//! # }
+//! ```
+//!
+//! The data is deterministic: it only depends on the type and name of each
+//! field, and on those of the fields it's nested in, so the same query always
+//! returns the same result. Nullable fields are never `null`, and lists have
+//! `LIST_LENGTH` items. Fields of interfaces and unions resolve to one of
+//! their possible types, and fragments are applied as they would be to that
+//! type.
+//!
+//! Custom scalars are mocked as strings. Queries that select nothing but
+//! `__schema`, `__type` and `__typename` are executed normally, with the given
+//! context, so introspection works; the introspection fields of other queries
+//! are `null`.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use ast::{Fragment, OperationType, Selection, Type};
+use cache::IntrospectionCache;
+use executor::{prepare_operation, ExecutionError, ExecutionScratch, Variables};
+use parser::Spanning;
+use schema::meta::MetaType;
+use schema::model::{RootNode, SchemaType};
+use types::base::{is_excluded, merge_key_into, GraphQLType};
+use value::{Object, ScalarValue, Value};
+
+use {execute_valid_document, parse_and_validate, GraphQLError};
+
+/// The number of items of every mocked list
+pub const LIST_LENGTH: usize = 2;
+
+// FNV-1a, which unlike `DefaultHasher` is guaranteed to stay the same
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Execute a query with placeholder data instead of calling resolvers
+///
+/// Otherwise the same as `juniper::execute`, see the module documentation.
+pub fn execute<'a, CtxT, QueryT, MutationT, S>(
+    document_source: &'a str,
+    operation_name: Option<&str>,
+    root_node: &RootNode<QueryT, MutationT, S>,
+    variables: &Variables<S>,
+    context: &CtxT,
+)
+    -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    let document = try!(parse_and_validate(document_source, root_node, variables));
+
+    if IntrospectionCache::is_cacheable(&document, operation_name) {
+        return execute_valid_document(
+            document_source, &document, operation_name, root_node, Cow::Borrowed(variables), context,
+            &mut ExecutionScratch::new());
+    }
+
+    let mut fragments = HashMap::new();
+    let mut buffer = Variables::new();
+    let (op, variables) = try!(prepare_operation(
+        &document, operation_name, Cow::Borrowed(variables), &mut fragments, &mut buffer));
+
+    let root_type = match op.item.operation_type {
+        OperationType::Query => root_node.schema.concrete_query_type(),
+        OperationType::Mutation => root_node.schema.concrete_mutation_type()
+            .expect("No mutation type found"),
+    };
+
+    let mocker = Mocker {
+        schema: &root_node.schema,
+        fragments: &fragments,
+        variables: &variables,
+    };
+
+    let mut result = Object::new();
+    mocker.mock_selection_set(root_type, &op.item.selection_set, FNV_OFFSET_BASIS, &mut result);
+
+    Ok((Value::object(result), vec![]))
+}
+
+struct Mocker<'m, S: 'm> {
+    schema: &'m SchemaType<'m, S>,
+    fragments: &'m HashMap<&'m str, &'m Fragment<'m, S>>,
+    variables: &'m Variables<S>,
+}
+
+impl<'m, S> Mocker<'m, S> where S: ScalarValue {
+    fn mock_selection_set(&self, object_type: &MetaType<S>, selection_set: &[Selection<S>], seed: u64, result: &mut Object<S>) {
+        let type_name = object_type.name().expect("Mocking an unnamed object type");
+
+        for selection in selection_set {
+            match *selection {
+                Selection::Field(Spanning { item: ref f, .. }) => {
+                    if is_excluded(&f.directives, self.variables) {
+                        continue;
+                    }
+
+                    let response_name = f.alias.as_ref().unwrap_or(&f.name).item;
+
+                    let value = match object_type.field_by_name(f.name.item) {
+                        _ if f.name.item == "__typename" => Value::string(type_name),
+                        Some(field) if !f.name.item.starts_with("__") => self.mock_value(
+                            &field.field_type,
+                            f.name.item,
+                            f.selection_set.as_ref().map_or(&[][..], |s| &s[..]),
+                            hash(hash(seed, type_name), f.name.item)),
+                        _ => Value::null(),
+                    };
+
+                    merge_key_into(result, Arc::from(response_name), value);
+                },
+                Selection::FragmentSpread(Spanning { item: ref spread, .. }) => {
+                    if is_excluded(&spread.directives, self.variables) {
+                        continue;
+                    }
+
+                    let fragment = self.fragments.get(spread.name.item).expect("Fragment could not be found");
+
+                    if self.schema.is_named_subtype(type_name, fragment.type_condition.item) {
+                        self.mock_selection_set(object_type, &fragment.selection_set, seed, result);
+                    }
+                },
+                Selection::InlineFragment(Spanning { item: ref fragment, .. }) => {
+                    if is_excluded(&fragment.directives, self.variables) {
+                        continue;
+                    }
+
+                    let applies = fragment.type_condition.as_ref()
+                        .map_or(true, |cond| self.schema.is_named_subtype(type_name, cond.item));
+
+                    if applies {
+                        self.mock_selection_set(object_type, &fragment.selection_set, seed, result);
+                    }
+                },
+            }
+        }
+    }
+
+    fn mock_value(&self, field_type: &Type, field_name: &str, selection_set: &[Selection<S>], seed: u64) -> Value<S> {
+        let type_name = match *field_type {
+            Type::List(ref inner) | Type::NonNullList(ref inner) => {
+                return Value::list((0..LIST_LENGTH)
+                    .map(|i| self.mock_value(inner, field_name, selection_set, hash(seed, &i.to_string())))
+                    .collect());
+            },
+            Type::Named(ref name) | Type::NonNullNamed(ref name) => name,
+        };
+
+        let meta_type = self.schema.concrete_type_by_name(type_name).expect("Type not found in schema");
+
+        match *meta_type {
+            MetaType::Scalar(_) => mock_scalar(type_name, field_name, seed),
+            MetaType::Enum(ref meta) => match pick(&meta.values, seed) {
+                Some(value) => Value::string(&value.name),
+                None => Value::null(),
+            },
+            MetaType::Object(_) => self.mock_object(meta_type, selection_set, seed),
+            MetaType::Interface(_) | MetaType::Union(_) => {
+                let mut possible_types = self.schema.possible_types(meta_type);
+                possible_types.sort_by_key(|t| t.name());
+
+                match pick(&possible_types, seed) {
+                    Some(object_type) => self.mock_object(object_type, selection_set, seed),
+                    None => Value::null(),
+                }
+            },
+            _ => Value::null(),
+        }
+    }
+
+    fn mock_object(&self, object_type: &MetaType<S>, selection_set: &[Selection<S>], seed: u64) -> Value<S> {
+        let mut object = Object::new();
+        self.mock_selection_set(object_type, selection_set, seed, &mut object);
+        Value::object(object)
+    }
+}
+
+fn mock_scalar<S: ScalarValue>(type_name: &str, field_name: &str, seed: u64) -> Value<S> {
+    match type_name {
+        "Int" => Value::int((seed % 100) as i32),
+        "Float" => Value::float((seed % 10_000) as f64 / 100.0),
+        "Boolean" => Value::boolean(seed % 2 == 0),
+        "ID" => Value::string((seed % 100_000).to_string()),
+        _ => Value::string(format!("{} {}", field_name, seed % 100)),
+    }
+}
+
+fn pick<T>(items: &[T], seed: u64) -> Option<&T> {
+    if items.is_empty() {
+        None
+    } else {
+        items.get((seed % items.len() as u64) as usize)
+    }
+}
+
+fn hash(seed: u64, data: &str) -> u64 {
+    data.bytes().fold(seed, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::Variables;
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+    use value::Value;
+
+    use super::{execute, LIST_LENGTH};
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field count() -> i32 { panic!("Resolvers are never called") }
+    });
+
+    fn mock(query: &str) -> Value {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let (result, errs) = execute(query, None, &schema, &Variables::new(), &Database::new()).unwrap();
+        assert_eq!(errs, []);
+        result
+    }
+
+    #[test]
+    fn mocks_deterministic_data_of_the_type_of_fields() {
+        let query = r#"{
+            hero {
+                __typename
+                id
+                name
+                appearsIn
+                friends { name }
+                ... on Human { homePlanet }
+                ... on Droid { primaryFunction }
+            }
+        }"#;
+        let result = mock(query);
+        assert_eq!(result, mock(query));
+
+        let hero = result.get("hero").unwrap();
+        let type_name = hero.get("__typename").and_then(|v| v.as_string_value()).unwrap();
+        assert!(type_name == "Human" || type_name == "Droid");
+        assert_eq!(hero.get("homePlanet").is_some(), type_name == "Human");
+        assert_eq!(hero.get("primaryFunction").is_some(), type_name == "Droid");
+        assert!(hero.get("id").and_then(|v| v.as_string_value()).is_some());
+
+        let appears_in = hero.get("appearsIn").and_then(|v| v.as_list_value()).unwrap();
+        assert_eq!(appears_in.len(), LIST_LENGTH);
+        for episode in appears_in {
+            assert!(["NEW_HOPE", "EMPIRE", "JEDI"].contains(&episode.as_string_value().unwrap()));
+        }
+
+        let friends = hero.get("friends").and_then(|v| v.as_list_value()).unwrap();
+        assert_eq!(friends.len(), LIST_LENGTH);
+        assert!(friends[0].get("name").and_then(|v| v.as_string_value()).unwrap().starts_with("name "));
+        assert!(friends[0] != friends[1]);
+    }
+
+    #[test]
+    fn never_calls_resolvers() {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+
+        let (result, errs) = execute("{ count alias: count }", None, &schema, &Variables::new(), &()).unwrap();
+        assert_eq!(errs, []);
+        assert_eq!(result.get("count"), result.get("alias"));
+        assert!(result.get("count").and_then(|v| v.as_int_value()).is_some());
+    }
+
+    #[test]
+    fn executes_introspection_queries() {
+        assert_eq!(
+            mock("{ __schema { queryType { name } } }"),
+            Value::object(vec![
+                ("__schema", Value::object(vec![
+                    ("queryType", Value::object(vec![("name", Value::string("Query"))].into_iter().collect())),
+                ].into_iter().collect())),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn rejects_invalid_queries() {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+
+        assert!(execute("{ nope }", None, &schema, &Variables::new(), &()).is_err());
+    }
+}
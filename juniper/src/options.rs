@@ -0,0 +1,403 @@
+use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use ast::{Definition, Document, Fragment, Selection};
+use parser::SourcePosition;
+use prepared::select_operation;
+use validation::RuleError;
+
+use GraphQLError;
+
+/// The limits and switches that affect how requests are executed
+///
+/// Set them for every request executed with a schema with
+/// `RootNode::with_execution_options`, which applies to `execute`, the `http`
+/// module and the integrations alike, or for a single request with
+/// `execute_with_options` or `http::GraphQLRequest::execute_with_options`:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use std::time::Duration;
+/// use juniper::{EmptyMutation, ExecutionOptions, RootNode, Variables};
+///
+/// struct Query;
+///
+/// graphql_object!(Query: () |&self| {
+///     field answer() -> i32 { 42 }
+/// });
+///
+/// # fn main() {
+/// let options = ExecutionOptions::new()
+///     .with_max_depth(10)
+///     .with_max_complexity(1000)
+///     .with_timeout(Duration::from_secs(5))
+///     .with_introspection(false);
+///
+/// let root_node = RootNode::new(Query, EmptyMutation::<()>::new()).with_execution_options(options);
+///
+/// assert!(juniper::execute("{ answer }", None, &root_node, &Variables::new(), &()).is_ok());
+/// assert!(juniper::execute("{ __schema { types { name } } }", None, &root_node, &Variables::new(), &()).is_err());
+/// # }
+/// ```
+///
+/// By default nothing is limited, and introspection is enabled. Operations
+/// that are nested too deeply, select too many fields or use introspection
+/// when it's disabled fail with a `GraphQLError::ValidationError`, before any
+/// field is resolved. Fields are resolved on the thread that executes the
+/// request, so there's no parallelism to configure.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExecutionOptions {
+    max_depth: Option<usize>,
+    max_complexity: Option<usize>,
+    max_errors: Option<usize>,
+    timeout: Option<Duration>,
+    response_budget: Option<usize>,
+    introspection: bool,
+}
+
+impl ExecutionOptions {
+    /// Construct options where nothing is limited
+    pub fn new() -> ExecutionOptions {
+        ExecutionOptions::default()
+    }
+
+    /// Reject operations with fields nested more than `depth` levels deep
+    ///
+    /// The fields of the root type are at depth `1`. Fields selected through
+    /// fragments count where the fragments are spread.
+    pub fn with_max_depth(mut self, depth: usize) -> ExecutionOptions {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Reject operations that select more than `complexity` fields
+    ///
+    /// Every field in the operation counts once, including the fields of the
+    /// fragments it spreads, however long the lists they resolve to are.
+    pub fn with_max_complexity(mut self, complexity: usize) -> ExecutionOptions {
+        self.max_complexity = Some(complexity);
+        self
+    }
+
+    /// Only report the first `max` errors of an execution
+    ///
+    /// Errors are ordered by their location in the query, further errors are
+    /// dropped from the response.
+    pub fn with_max_errors(mut self, max: usize) -> ExecutionOptions {
+        self.max_errors = Some(max);
+        self
+    }
+
+    /// Stop resolving fields once executing an operation took longer than
+    /// `timeout`
+    ///
+    /// The time it takes to parse and validate the document isn't included.
+    /// A resolver that's already running isn't interrupted, but no further
+    /// fields are resolved once it returns. They are `null` in the response,
+    /// and a single error with an `EXECUTION_TIMED_OUT` code in its
+    /// extensions is reported.
+    pub fn with_timeout(mut self, timeout: Duration) -> ExecutionOptions {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Abort operations whose response grows beyond roughly the given number
+    /// of bytes, see `RootNode::with_response_budget`
+    pub fn with_response_budget(mut self, bytes: usize) -> ExecutionOptions {
+        self.response_budget = Some(bytes);
+        self
+    }
+
+    /// Allow or reject queries selecting `__schema` or `__type`
+    ///
+    /// `__typename` can be selected either way.
+    pub fn with_introspection(mut self, introspection: bool) -> ExecutionOptions {
+        self.introspection = introspection;
+        self
+    }
+
+    /// The maximum depth of operations, if it's limited
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// The maximum number of fields of operations, if it's limited
+    pub fn max_complexity(&self) -> Option<usize> {
+        self.max_complexity
+    }
+
+    /// The maximum number of errors reported, if it's limited
+    pub fn max_errors(&self) -> Option<usize> {
+        self.max_errors
+    }
+
+    /// The time executing an operation may take, if it's limited
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// The approximate maximum size of responses in bytes, if it's limited
+    pub fn response_budget(&self) -> Option<usize> {
+        self.response_budget
+    }
+
+    /// Whether introspection queries are allowed
+    pub fn introspection(&self) -> bool {
+        self.introspection
+    }
+}
+
+impl Default for ExecutionOptions {
+    fn default() -> ExecutionOptions {
+        ExecutionOptions {
+            max_depth: None,
+            max_complexity: None,
+            max_errors: None,
+            timeout: None,
+            response_budget: None,
+            introspection: true,
+        }
+    }
+}
+
+// The depth and number of fields of a selection set
+#[derive(Clone, Copy, Default)]
+struct Cost {
+    depth: usize,
+    complexity: usize,
+}
+
+// Check the operation a validated document executes against the limits of
+// the options. Operations that can't be determined are left to the executor
+// to report.
+#[doc(hidden)]
+pub fn check_operation<'a, S>(document: &Document<S>, operation_name: Option<&str>, options: &ExecutionOptions)
+    -> Result<(), GraphQLError<'a>>
+{
+    if options.max_depth.is_none() && options.max_complexity.is_none() && options.introspection {
+        return Ok(());
+    }
+
+    let operation = match select_operation(document, operation_name).map(|i| &document[i]) {
+        Ok(&Definition::Operation(ref operation)) => operation,
+        _ => return Ok(()),
+    };
+
+    let fragments = document.iter()
+        .filter_map(|def| match *def {
+            Definition::Fragment(ref f) => Some((f.item.name.item, &f.item)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let mut errors = Vec::new();
+
+    let cost = selection_set_cost(&operation.item.selection_set, &fragments, &mut HashMap::new());
+    match options.max_depth {
+        Some(max) if cost.depth > max => errors.push(RuleError::new(
+            &format!("The operation is nested {} levels deep, more than the limit of {}", cost.depth, max),
+            &[operation.start.clone()])),
+        _ => {},
+    }
+    match options.max_complexity {
+        Some(max) if cost.complexity > max => errors.push(RuleError::new(
+            &format!("The operation selects {} fields, more than the limit of {}", cost.complexity, max),
+            &[operation.start.clone()])),
+        _ => {},
+    }
+
+    if !options.introspection {
+        let mut positions = Vec::new();
+        find_introspection(&operation.item.selection_set, &fragments, &mut HashSet::new(), &mut positions);
+        errors.extend(positions.into_iter().map(|pos| RuleError::new("Introspection is disabled", &[pos])));
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(GraphQLError::ValidationError(errors))
+    }
+}
+
+// Fragments are only walked once, so documents spreading fragments many times
+// don't take exponential time to check. Validation rules out cycles, the cost
+// inserted before walking a fragment only keeps a cycle from recursing.
+fn selection_set_cost<'d, S>(
+    selection_set: &'d [Selection<'d, S>],
+    fragments: &HashMap<&'d str, &'d Fragment<'d, S>>,
+    costs: &mut HashMap<&'d str, Cost>,
+)
+    -> Cost
+{
+    let mut cost = Cost::default();
+
+    for selection in selection_set {
+        let selection_cost = match *selection {
+            Selection::Field(ref field) => {
+                let nested = field.item.selection_set.as_ref()
+                    .map(|s| selection_set_cost(s, fragments, costs))
+                    .unwrap_or_default();
+                Cost {
+                    depth: nested.depth + 1,
+                    complexity: nested.complexity.saturating_add(1),
+                }
+            },
+            Selection::InlineFragment(ref fragment) =>
+                selection_set_cost(&fragment.item.selection_set, fragments, costs),
+            Selection::FragmentSpread(ref spread) => {
+                let name = spread.item.name.item;
+                match (costs.get(name).cloned(), fragments.get(name)) {
+                    (Some(cost), _) => cost,
+                    (None, Some(fragment)) => {
+                        costs.insert(name, Cost::default());
+                        let cost = selection_set_cost(&fragment.selection_set, fragments, costs);
+                        costs.insert(name, cost);
+                        cost
+                    },
+                    (None, None) => Cost::default(),
+                }
+            },
+        };
+
+        cost.depth = cmp::max(cost.depth, selection_cost.depth);
+        cost.complexity = cost.complexity.saturating_add(selection_cost.complexity);
+    }
+
+    cost
+}
+
+// The positions of `__schema` and `__type` fields selected on the root type
+fn find_introspection<'d, S>(
+    selection_set: &'d [Selection<'d, S>],
+    fragments: &HashMap<&'d str, &'d Fragment<'d, S>>,
+    visited: &mut HashSet<&'d str>,
+    positions: &mut Vec<SourcePosition>,
+) {
+    for selection in selection_set {
+        match *selection {
+            Selection::Field(ref field) => if field.item.name.item == "__schema" || field.item.name.item == "__type" {
+                positions.push(field.start.clone());
+            },
+            Selection::InlineFragment(ref fragment) =>
+                find_introspection(&fragment.item.selection_set, fragments, visited, positions),
+            Selection::FragmentSpread(ref spread) => {
+                let name = spread.item.name.item;
+                if visited.insert(name) {
+                    if let Some(fragment) = fragments.get(name) {
+                        find_introspection(&fragment.selection_set, fragments, visited, positions);
+                    }
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use executor::{ErrorPathSegment, FieldResult, Variables};
+    use parser::parse_document_source;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use value::{DefaultScalarValue, Value};
+    use {GraphQLError, GraphQLErrorCode};
+
+    use super::{check_operation, ExecutionOptions};
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field hero() -> Hero { Hero }
+        field fail(n: i32) -> FieldResult<i32> { Err(format!("Failed {}", n))? }
+        field slow() -> i32 {
+            ::std::thread::sleep(Duration::from_millis(20));
+            1
+        }
+    });
+
+    struct Hero;
+
+    graphql_object!(Hero: () |&self| {
+        field name() -> &str { "R2-D2" }
+        field friends() -> Vec<Hero> { vec![Hero, Hero] }
+    });
+
+    fn messages(options: ExecutionOptions, query: &str) -> Result<Vec<String>, Vec<String>> {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new()).with_execution_options(options);
+
+        match ::execute(query, None, &schema, &Variables::new(), &()) {
+            Ok((_, errors)) => Ok(errors.iter().map(|e| e.error().message().to_owned()).collect()),
+            Err(GraphQLError::ValidationError(errors)) => Err(errors.iter().map(|e| e.message().to_owned()).collect()),
+            Err(err) => panic!("Unexpected error {:?}", err),
+        }
+    }
+
+    #[test]
+    fn limits_depth_and_complexity() {
+        let query = "{ hero { ...Friends friends { ...Friends } } } fragment Friends on Hero { friends { name } }";
+        let options = ExecutionOptions::new().with_max_depth(4).with_max_complexity(6);
+
+        assert_eq!(messages(options.clone(), query), Ok(vec![]));
+        assert_eq!(
+            messages(options.clone().with_max_depth(3).with_max_complexity(5), query),
+            Err(vec![
+                "The operation is nested 4 levels deep, more than the limit of 3".to_owned(),
+                "The operation selects 6 fields, more than the limit of 5".to_owned(),
+            ]));
+    }
+
+    #[test]
+    fn checks_fragments_spread_many_times_quickly() {
+        let mut query = "{ hero { ...F63 } } fragment F0 on Hero { name }".to_owned();
+        for i in 1..64 {
+            query.push_str(&format!(" fragment F{} on Hero {{ friends {{ ...F{} ...F{} }} }}", i, i - 1, i - 1));
+        }
+        let document = parse_document_source::<DefaultScalarValue>(&query).unwrap();
+
+        let options = ExecutionOptions::new().with_max_depth(100).with_max_complexity(1000);
+        match check_operation(&document, None, &options) {
+            Err(GraphQLError::ValidationError(errors)) => {
+                assert_eq!(errors.len(), 1);
+                assert!(errors[0].message().ends_with("fields, more than the limit of 1000"));
+            },
+            result => panic!("Unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn disables_introspection() {
+        let options = ExecutionOptions::new().with_introspection(false);
+
+        assert_eq!(messages(options.clone(), "{ hero { __typename } }"), Ok(vec![]));
+        assert_eq!(
+            messages(options, "{ ... on Query { __schema { queryType { name } } } ...T } fragment T on Query { __type(name: \"Hero\") { name } }"),
+            Err(vec!["Introspection is disabled".to_owned(), "Introspection is disabled".to_owned()]));
+    }
+
+    #[test]
+    fn caps_errors() {
+        assert_eq!(
+            messages(ExecutionOptions::new().with_max_errors(2), "{ a: fail(n: 1) b: fail(n: 2) c: fail(n: 3) }"),
+            Ok(vec!["Failed 1".to_owned(), "Failed 2".to_owned()]));
+    }
+
+    #[test]
+    fn stops_resolving_fields_after_the_timeout() {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new())
+            .with_execution_options(ExecutionOptions::new().with_timeout(Duration::from_millis(10)));
+
+        let (value, errors) = ::execute("{ a: slow b: slow c: slow }", None, &schema, &Variables::new(), &()).unwrap();
+
+        assert_eq!(value, Value::object(vec![
+            ("a", Value::int(1)),
+            ("b", Value::null()),
+            ("c", Value::null()),
+        ].into_iter().collect()));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error().message(), "Execution exceeded the timeout of 10 ms");
+        assert_eq!(errors[0].path(), &[ErrorPathSegment::Field("b".to_owned())]);
+        assert_eq!(errors[0].extensions().get("code"), Some(&Value::string(GraphQLErrorCode::ExecutionTimedOut.as_str())));
+    }
+}
@@ -7,15 +7,20 @@ use ast::{Definition, Document, OperationType,
 
 use parser::{Lexer, Parser, Spanning, UnlocatedParseResult, OptionParseResult, ParseResult, ParseError, Token};
 use parser::value::parse_value_literal;
+use value::ScalarValue;
 
 #[doc(hidden)]
-pub fn parse_document_source(s: &str) -> UnlocatedParseResult<Document> {
+pub fn parse_document_source<S>(s: &str) -> UnlocatedParseResult<Document<S>>
+    where S: ScalarValue
+{
     let mut lexer = Lexer::new(s);
     let mut parser = try!(Parser::new(&mut lexer).map_err(|s| s.map(ParseError::LexerError)));
     parse_document(&mut parser)
 }
 
-fn parse_document<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Document<'a>> {
+fn parse_document<'a, S>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Document<'a, S>>
+    where S: ScalarValue
+{
     let mut defs = Vec::new();
 
     loop {
@@ -27,7 +32,9 @@ fn parse_document<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Docum
     }
 }
 
-fn parse_definition<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Definition<'a>> {
+fn parse_definition<'a, S>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Definition<'a, S>>
+    where S: ScalarValue
+{
     match parser.peek().item {
         Token::CurlyOpen | Token::Name("query") | Token::Name("mutation") =>
             Ok(Definition::Operation(try!(parse_operation_definition(parser)))),
@@ -37,7 +44,9 @@ fn parse_definition<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Def
     }
 }
 
-fn parse_operation_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Operation<'a>> {
+fn parse_operation_definition<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, Operation<'a, S>>
+    where S: ScalarValue
+{
     if parser.peek().item == Token::CurlyOpen {
         let selection_set = try!(parse_selection_set(parser));
 
@@ -76,7 +85,9 @@ fn parse_operation_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Op
     }
 }
 
-fn parse_fragment_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Fragment<'a>> {
+fn parse_fragment_definition<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, Fragment<'a, S>>
+    where S: ScalarValue
+{
     let Spanning { start: start_pos, .. } = try!(parser.expect(&Token::Name("fragment")));
     let name = match parser.expect_name() {
         Ok(n) => if n.item == "on" {
@@ -104,7 +115,9 @@ fn parse_fragment_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Fra
         }))
 }
 
-fn parse_optional_selection_set<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Selection<'a>>> {
+fn parse_optional_selection_set<'a, S>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Selection<'a, S>>>
+    where S: ScalarValue
+{
     if parser.peek().item == Token::CurlyOpen {
         Ok(Some(try!(parse_selection_set(parser))))
     }
@@ -113,21 +126,27 @@ fn parse_optional_selection_set<'a>(parser: &mut Parser<'a>) -> OptionParseResul
     }
 }
 
-fn parse_selection_set<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Vec<Selection<'a>>> {
+fn parse_selection_set<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, Vec<Selection<'a, S>>>
+    where S: ScalarValue
+{
     parser.unlocated_delimited_nonempty_list(
         &Token::CurlyOpen,
         parse_selection,
         &Token::CurlyClose)
 }
 
-fn parse_selection<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Selection<'a>> {
+fn parse_selection<'a, S>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Selection<'a, S>>
+    where S: ScalarValue
+{
     match parser.peek().item {
         Token::Ellipsis => parse_fragment(parser),
         _ => parse_field(parser).map(Selection::Field),
     }
 }
 
-fn parse_fragment<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Selection<'a>> {
+fn parse_fragment<'a, S>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Selection<'a, S>>
+    where S: ScalarValue
+{
     let Spanning { start: ref start_pos, .. } = try!(parser.expect(&Token::Ellipsis));
 
     match parser.peek().item {
@@ -191,7 +210,9 @@ fn parse_fragment<'a>(parser: &mut Parser<'a>) -> UnlocatedParseResult<'a, Selec
     }
 }
 
-fn parse_field<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Field<'a>> {
+fn parse_field<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, Field<'a, S>>
+    where S: ScalarValue
+{
     let mut alias = Some(try!(parser.expect_name()));
 
     let name = if try!(parser.skip(&Token::Colon)).is_some() {
@@ -221,7 +242,9 @@ fn parse_field<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Field<'a>> {
         }))
 }
 
-fn parse_arguments<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Arguments<'a>> {
+fn parse_arguments<'a, S>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Arguments<'a, S>>
+    where S: ScalarValue
+{
     if parser.peek().item != Token::ParenOpen {
         Ok(None)
     } else {
@@ -233,7 +256,9 @@ fn parse_arguments<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Argumen
     }
 }
 
-fn parse_argument<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, (Spanning<&'a str>, Spanning<InputValue>)> {
+fn parse_argument<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, (Spanning<&'a str>, Spanning<InputValue<S>>)>
+    where S: ScalarValue
+{
     let name = try!(parser.expect_name());
     try!(parser.expect(&Token::Colon));
     let value = try!(parse_value_literal(parser, false));
@@ -252,7 +277,9 @@ fn parse_operation_type<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Operatio
     }
 }
 
-fn parse_variable_definitions<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, VariableDefinitions<'a>> {
+fn parse_variable_definitions<'a, S>(parser: &mut Parser<'a>) -> OptionParseResult<'a, VariableDefinitions<'a, S>>
+    where S: ScalarValue
+{
     if parser.peek().item != Token::ParenOpen {
         Ok(None)
     }
@@ -265,7 +292,9 @@ fn parse_variable_definitions<'a>(parser: &mut Parser<'a>) -> OptionParseResult<
     }
 }
 
-fn parse_variable_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, (Spanning<&'a str>, VariableDefinition<'a>)> {
+fn parse_variable_definition<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, (Spanning<&'a str>, VariableDefinition<'a, S>)>
+    where S: ScalarValue
+{
     let Spanning { start: start_pos, .. } = try!(parser.expect(&Token::Dollar));
     let var_name = try!(parser.expect_name());
     try!(parser.expect(&Token::Colon));
@@ -294,7 +323,9 @@ fn parse_variable_definition<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, (Sp
         )))
 }
 
-fn parse_directives<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Spanning<Directive<'a>>>> {
+fn parse_directives<'a, S>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Spanning<Directive<'a, S>>>>
+    where S: ScalarValue
+{
     if parser.peek().item != Token::At {
         Ok(None)
     }
@@ -308,7 +339,9 @@ fn parse_directives<'a>(parser: &mut Parser<'a>) -> OptionParseResult<'a, Vec<Sp
     }
 }
 
-fn parse_directive<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, Directive<'a>> {
+fn parse_directive<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, Directive<'a, S>>
+    where S: ScalarValue
+{
     let Spanning { start: start_pos, .. } = try!(parser.expect(&Token::At));
     let name = try!(parser.expect_name());
     let arguments = try!(parse_arguments(parser));
@@ -22,7 +22,10 @@ pub struct Lexer<'a> {
 pub enum Token<'a> {
     Name(&'a str),
     Int(i32),
-    Float(f64),
+    /// An integer literal that doesn't fit in an `Int`, as written in the source
+    BigInt(&'a str),
+    /// A float literal, as written in the source
+    Float(&'a str),
     String(String),
     ExclamationMark,
     Dollar,
@@ -276,9 +279,11 @@ impl<'a> Lexer<'a> {
     }
 
     fn scan_escaped_unicode(&mut self, start_pos: &SourcePosition) -> Result<char, Spanning<LexerError>> {
-        let (start_idx, _) = try!(self.peek_char().ok_or(
+        let (start_idx, start_ch) = try!(self.peek_char().ok_or(
             Spanning::zero_width(&self.position, LexerError::UnterminatedString)));
-        let mut end_idx = start_idx;
+        // Exclusive, and on a character boundary even if the escape contains
+        // multi-byte characters
+        let mut end_idx = start_idx + start_ch.len_utf8();
         let mut len = 0;
 
         for _ in 0..4 {
@@ -289,11 +294,11 @@ impl<'a> Lexer<'a> {
                 break;
             }
 
-            end_idx = idx;
+            end_idx = idx + ch.len_utf8();
             len += 1;
         }
 
-        let escape = &self.source[start_idx..end_idx+1];
+        let escape = &self.source[start_idx..end_idx];
 
         if len != 4 {
             return Err(Spanning::zero_width(
@@ -314,54 +319,51 @@ impl<'a> Lexer<'a> {
 
     fn scan_number(&mut self) -> LexerResult<'a> {
         let start_pos = self.position.clone();
-        let int_part = try!(self.scan_integer_part());
-        let mut frac_part = None;
-        let mut exp_part = None;
+        let start_idx = self.byte_index();
+        let int_part = match self.scan_integer_part() {
+            Ok(i) => Some(i),
+            Err(Spanning { item: LexerError::InvalidNumber, .. }) => None,
+            Err(e) => return Err(e),
+        };
+        let mut is_float = false;
 
         if let Some((_, '.')) = self.peek_char() {
             self.next_char();
 
-            frac_part = Some(try!(self.scan_digits()));
+            try!(self.skip_digits());
+            is_float = true;
         }
 
         if let Some((_, ch)) = self.peek_char() {
             if ch == 'e' || ch == 'E' {
                 self.next_char();
 
-                let mut is_negative = false;
-
                 if let Some((_, ch)) = self.peek_char() {
-                    if ch == '-' {
-                        self.next_char();
-                        is_negative = true;
-                    }
-                    else if ch == '+' {
+                    if ch == '-' || ch == '+' {
                         self.next_char();
                     }
                 }
-                exp_part = Some(if is_negative { -1 } else { 1 } * try!(self.scan_digits()));
+
+                try!(self.skip_digits());
+                is_float = true;
             }
         }
 
-        let mantissa = frac_part.map(|f| f as f64).map(|frac|
-            if frac > 0f64 {
-                frac / 10f64.powf(frac.log10().floor() + 1f64)
-            }
-            else {
-                0f64
-            }).map(|m| if int_part < 0 { -m } else { m });
+        let literal = &self.source[start_idx..self.byte_index()];
 
-        let exp = exp_part.map(|e| e as f64).map(|e| 10f64.powf(e));
+        // Floats are parsed as a whole rather than from their parts, so long
+        // fractions and leading zeros in them don't lose precision
+        let token = match int_part {
+            _ if is_float => {
+                try!(literal.parse::<f64>().map_err(|_|
+                    Spanning::zero_width(&start_pos, LexerError::InvalidNumber)));
+                Token::Float(literal)
+            },
+            Some(i) => Token::Int(i),
+            None => Token::BigInt(literal),
+        };
 
-        Ok(Spanning::start_end(
-            &start_pos,
-            &self.position,
-            match (mantissa, exp) {
-                (None, None) => Token::Int(int_part),
-                (None, Some(exp)) => Token::Float((int_part as f64) * exp),
-                (Some(mantissa), None) => Token::Float((int_part as f64) + mantissa),
-                (Some(mantissa), Some(exp)) => Token::Float(((int_part as f64) + mantissa) * exp),
-            }))
+        Ok(Spanning::start_end(&start_pos, &self.position, token))
     }
 
     fn scan_integer_part(&mut self) -> Result<i32, Spanning<LexerError>> {
@@ -396,26 +398,35 @@ impl<'a> Lexer<'a> {
 
     fn scan_digits(&mut self) -> Result<i32, Spanning<LexerError>> {
         let start_pos = self.position.clone();
+        let digits = try!(self.skip_digits());
+
+        i32::from_str_radix(digits, 10)
+            .map_err(|_| Spanning::zero_width(&start_pos, LexerError::InvalidNumber))
+    }
+
+    fn skip_digits(&mut self) -> Result<&'a str, Spanning<LexerError>> {
         let (start_idx, ch) = try!(self.peek_char().ok_or(
             Spanning::zero_width(&self.position, LexerError::UnexpectedEndOfFile)));
-        let mut end_idx = start_idx;
 
         if !ch.is_digit(10) {
             return Err(Spanning::zero_width(&self.position, LexerError::UnexpectedCharacter(ch)));
         }
 
-        while let Some((idx, ch)) = self.peek_char() {
+        while let Some((_, ch)) = self.peek_char() {
             if !ch.is_digit(10) {
                 break;
             }
-            else {
-                self.next_char();
-                end_idx = idx;
-            }
+
+            self.next_char();
         }
 
-        i32::from_str_radix(&self.source[start_idx..end_idx+1], 10)
-            .map_err(|_| Spanning::zero_width(&start_pos, LexerError::InvalidNumber))
+        Ok(&self.source[start_idx..self.byte_index()])
+    }
+
+    // The byte index of the next character, unlike `position.index()` which
+    // counts characters
+    fn byte_index(&mut self) -> usize {
+        self.peek_char().map_or(self.length, |(idx, _)| idx)
     }
 }
 
@@ -471,6 +482,7 @@ impl<'a> fmt::Display for Token<'a> {
         match *self {
             Token::Name(name) => write!(f, "{}", name),
             Token::Int(i) => write!(f, "{}", i),
+            Token::BigInt(s) => write!(f, "{}", s),
             Token::Float(v) => write!(f, "{}", v),
             Token::String(ref s) => write!(f, "\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\"")),
             Token::ExclamationMark => write!(f, "!"),
@@ -1,6 +1,7 @@
 use ast::{Definition, Operation, Document, OperationType, Field, Selection, InputValue, Arguments};
 use parser::{Spanning, SourcePosition, ParseError, Token};
 use parser::document::parse_document_source;
+use value::DefaultScalarValue;
 
 fn parse_document(s: &str) -> Document {
     parse_document_source(s)
@@ -8,7 +9,7 @@ fn parse_document(s: &str) -> Document {
 }
 
 fn parse_document_error<'a>(s: &'a str) -> Spanning<ParseError<'a>> {
-    match parse_document_source(s) {
+    match parse_document_source::<DefaultScalarValue>(s) {
         Ok(doc) => panic!("*No* parse error on input {:#?} =>\n{:#?}", s, doc),
         Err(err) => err,
     }
@@ -259,6 +259,18 @@ fn string_errors() {
             &SourcePosition::new(6, 0, 6),
             LexerError::UnknownEscapeSequence("\\uXXXF".to_owned())));
 
+    assert_eq!(
+        tokenize_error("\"bad \\u00\u{e9} esc\""),
+        Spanning::zero_width(
+            &SourcePosition::new(6, 0, 6),
+            LexerError::UnknownEscapeSequence("\\u00\u{e9}".to_owned())));
+
+    assert_eq!(
+        tokenize_error("\"bad \\u\u{e9}\""),
+        Spanning::zero_width(
+            &SourcePosition::new(6, 0, 6),
+            LexerError::UnknownEscapeSequence("\\u\u{e9}".to_owned())));
+
     assert_eq!(
         tokenize_error(r#""unterminated in string \""#),
         Spanning::zero_width(
@@ -280,7 +292,8 @@ fn numbers() {
         assert_eq!(parsed.end, end);
 
         match parsed.item {
-            Token::Float(actual) => {
+            Token::Float(literal) => {
+                let actual: f64 = literal.parse().unwrap();
                 let relative_error = ((expected - actual) / actual).abs();
                 assert!(
                     relative_error.abs() < 0.001,
@@ -330,6 +343,13 @@ fn numbers() {
             &SourcePosition::new(1, 0, 1),
             Token::Int(0)));
 
+    assert_eq!(
+        tokenize_single("-8589934592"),
+        Spanning::start_end(
+            &SourcePosition::new(0, 0, 0),
+            &SourcePosition::new(11, 0, 11),
+            Token::BigInt("-8589934592")));
+
     assert_float_token_eq(
         "-4.123",
         SourcePosition::new(0, 0, 0),
@@ -395,6 +415,30 @@ fn numbers() {
         SourcePosition::new(0, 0, 0),
         SourcePosition::new(9, 0, 9),
         -1.123e45);
+
+    assert_float_token_eq(
+        "1.05",
+        SourcePosition::new(0, 0, 0),
+        SourcePosition::new(4, 0, 4),
+        1.05);
+
+    assert_float_token_eq(
+        "-0.5",
+        SourcePosition::new(0, 0, 0),
+        SourcePosition::new(4, 0, 4),
+        -0.5);
+
+    assert_float_token_eq(
+        "-5.170093336921576e-297",
+        SourcePosition::new(0, 0, 0),
+        SourcePosition::new(23, 0, 23),
+        -5.170093336921576e-297);
+
+    assert_float_token_eq(
+        "12345678901.5",
+        SourcePosition::new(0, 0, 0),
+        SourcePosition::new(13, 0, 13),
+        12345678901.5);
 }
 
 #[test]
@@ -570,7 +614,7 @@ fn display() {
     );
 
     assert_eq!(
-        format!("{}", Token::Float(4.5)),
+        format!("{}", Token::Float("4.5")),
         "4.5"
     );
 
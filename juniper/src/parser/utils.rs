@@ -1,4 +1,3 @@
-use std::fmt;
 use std::hash::{Hash, Hasher};
 
 /// A reference to a line and column in an input source file
@@ -15,7 +14,7 @@ pub struct SourcePosition {
 /// character pointed by the `start` field and ending just before the `end`
 /// marker.
 #[derive(Debug)]
-pub struct Spanning<T: fmt::Debug> {
+pub struct Spanning<T> {
     /// The wrapped item
     pub item: T,
 
@@ -28,7 +27,7 @@ pub struct Spanning<T: fmt::Debug> {
     pub end: SourcePosition,
 }
 
-impl<T: fmt::Debug> Spanning<T> {
+impl<T> Spanning<T> {
     #[doc(hidden)]
     pub fn zero_width(pos: &SourcePosition, item: T) -> Spanning<T> {
         Spanning {
@@ -83,7 +82,7 @@ impl<T: fmt::Debug> Spanning<T> {
     }
 
     /// Modify the contents of the spanned item
-    pub fn map<O: fmt::Debug, F: Fn(T) -> O>(self, f: F) -> Spanning<O> {
+    pub fn map<O, F: FnOnce(T) -> O>(self, f: F) -> Spanning<O> {
         Spanning {
             item: f(self.item),
             start: self.start.clone(),
@@ -92,7 +91,7 @@ impl<T: fmt::Debug> Spanning<T> {
     }
 }
 
-impl<T> Clone for Spanning<T> where T: Clone + fmt::Debug {
+impl<T> Clone for Spanning<T> where T: Clone {
     fn clone(&self) -> Self {
         Spanning {
             start: self.start.clone(),
@@ -102,15 +101,15 @@ impl<T> Clone for Spanning<T> where T: Clone + fmt::Debug {
     }
 }
 
-impl<T> PartialEq for Spanning<T> where T: PartialEq + fmt::Debug {
+impl<T> PartialEq for Spanning<T> where T: PartialEq {
     fn eq(&self, other: &Self) -> bool {
         self.start == other.start && self.end == other.end && self.item == other.item
     }
 }
 
-impl<T> Eq for Spanning<T> where T: Eq + fmt::Debug {}
+impl<T> Eq for Spanning<T> where T: Eq {}
 
-impl<T> Hash for Spanning<T> where T: Hash + fmt::Debug {
+impl<T> Hash for Spanning<T> where T: Hash {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.start.hash(state);
         self.end.hash(state);
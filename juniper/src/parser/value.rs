@@ -1,16 +1,28 @@
 use ast::InputValue;
+use value::ScalarValue;
 
-use parser::{Parser, ParseResult, ParseError, Token, Spanning};
+use parser::{Parser, ParseResult, ParseError, Token, Spanning, LexerError};
 
-pub fn parse_value_literal<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, InputValue> {
+pub fn parse_value_literal<'a, S>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, InputValue<S>>
+    where S: ScalarValue
+{
     match *parser.peek() {
         Spanning { item: Token::BracketOpen, .. } => parse_list_literal(parser, is_const),
         Spanning { item: Token::CurlyOpen, .. } => parse_object_literal(parser, is_const),
         Spanning { item: Token::Dollar, .. } if !is_const => parse_variable_literal(parser),
         Spanning { item: Token::Int(i), .. } =>
             Ok(parser.next()?.map(|_| InputValue::int(i))),
-        Spanning { item: Token::Float(f), .. } =>
-            Ok(parser.next()?.map(|_| InputValue::float(f))),
+        Spanning { item: Token::BigInt(literal), .. } => match S::from_int_literal(literal) {
+            Some(s) => Ok(parser.next()?.map(|_| InputValue::scalar(s))),
+            None => Err(parser.next()?.map(|_| ParseError::LexerError(LexerError::InvalidNumber))),
+        },
+        Spanning { item: Token::Float(literal), .. } => match S::from_float_literal(literal) {
+            Some(s) => Ok(parser.next()?.map(|_| InputValue::scalar(s))),
+            None => match literal.parse() {
+                Ok(f) => Ok(parser.next()?.map(|_| InputValue::float(f))),
+                Err(_) => Err(parser.next()?.map(|_| ParseError::LexerError(LexerError::InvalidNumber))),
+            },
+        },
         Spanning { item: Token::String(_), .. } =>
             Ok(parser.next()?.map(|t|
                 if let Token::String(s) = t {
@@ -31,7 +43,9 @@ pub fn parse_value_literal<'a>(parser: &mut Parser<'a>, is_const: bool) -> Parse
     }
 }
 
-fn parse_list_literal<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, InputValue> {
+fn parse_list_literal<'a, S>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, InputValue<S>>
+    where S: ScalarValue
+{
     Ok(try!(parser.delimited_list(
             &Token::BracketOpen,
             |p| parse_value_literal(p, is_const),
@@ -39,7 +53,9 @@ fn parse_list_literal<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseResul
         )).map(InputValue::parsed_list))
 }
 
-fn parse_object_literal<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, InputValue> {
+fn parse_object_literal<'a, S>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, InputValue<S>>
+    where S: ScalarValue
+{
     Ok(try!(parser.delimited_list(
             &Token::CurlyOpen,
             |p| parse_object_field(p, is_const),
@@ -47,7 +63,9 @@ fn parse_object_literal<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseRes
         )).map(|items| InputValue::parsed_object(items.into_iter().map(|s| s.item).collect())))
 }
 
-fn parse_object_field<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, (Spanning<String>, Spanning<InputValue>)> {
+fn parse_object_field<'a, S>(parser: &mut Parser<'a>, is_const: bool) -> ParseResult<'a, (Spanning<String>, Spanning<InputValue<S>>)>
+    where S: ScalarValue
+{
     let key = try!(parser.expect_name());
 
     try!(parser.expect(&Token::Colon));
@@ -60,7 +78,9 @@ fn parse_object_field<'a>(parser: &mut Parser<'a>, is_const: bool) -> ParseResul
         (key.map(|s| s.to_owned()), value)))
 }
 
-fn parse_variable_literal<'a>(parser: &mut Parser<'a>) -> ParseResult<'a, InputValue> {
+fn parse_variable_literal<'a, S>(parser: &mut Parser<'a>) -> ParseResult<'a, InputValue<S>>
+    where S: ScalarValue
+{
     let Spanning { start: start_pos, .. } = try!(parser.expect(&Token::Dollar));
     let Spanning { item: name, end: end_pos, ..} = try!(parser.expect_name());
 
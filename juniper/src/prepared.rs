@@ -0,0 +1,326 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use ast::{Definition, Document, OperationType, Type};
+use executor::{execute_validated_query, ExecutionError, Variables};
+use options::check_operation;
+use parser::{parse_document_source, SourcePosition};
+use schema::model::RootNode;
+use types::base::GraphQLType;
+use validation::{ValidatorContext, visit_all_rules, validate_input_values};
+use value::{DefaultScalarValue, ScalarValue, Value};
+
+use GraphQLError;
+
+/// A query that has been parsed and validated, ready to be executed
+///
+/// `execute` parses and validates the document on every call. Servers that
+/// execute the same documents over and over again, e.g. persisted queries,
+/// can prepare them once instead, and only check the variables of each
+/// execution:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use juniper::{EmptyMutation, PreparedOperation, RootNode, Value, Variables};
+///
+/// struct Query;
+///
+/// graphql_object!(Query: () |&self| {
+///     field greet(name: String) -> String { format!("Hello, {}!", name) }
+/// });
+///
+/// # fn main() {
+/// let root_node = RootNode::new(Query, EmptyMutation::<()>::new());
+/// let operation = PreparedOperation::new(
+///     &root_node,
+///     "query Greet($name: String!) { greet(name: $name) }",
+///     None,
+/// ).unwrap();
+///
+/// for name in &["Alice", "Bob"] {
+///     let mut variables = Variables::new();
+///     variables.insert("name".to_owned(), juniper::InputValue::string(name));
+///
+///     let (value, errors) = operation.execute(&root_node, &variables, &()).unwrap();
+///     assert!(errors.is_empty());
+///     assert_eq!(value.get("greet"), Some(&Value::string(format!("Hello, {}!", name))));
+/// }
+/// # }
+/// ```
+///
+/// The operation must be executed with the schema it was prepared for.
+///
+/// When the document was registered ahead of time, and the client only sends
+/// its identifier, the locations of errors refer to a document the client
+/// never saw. `with_document_id` adds the identifier to the `extensions` of
+/// every execution error, so clients can resolve the locations against the
+/// document they registered:
+///
+/// ```json
+/// {
+///   "message": "Not found",
+///   "locations": [{ "line": 1, "column": 30 }],
+///   "extensions": { "documentId": "ecf4edb46db40b5132295c0291d62fb65d6759a9" }
+/// }
+/// ```
+///
+/// If the stored document isn't the registered one verbatim, e.g. because it
+/// was minified, `with_source_map` translates the locations back.
+pub struct PreparedOperation<'a, S = DefaultScalarValue> {
+    document: Document<'a, S>,
+    operation_name: Option<String>,
+    operation: usize,
+    document_id: Option<String>,
+    source_map: Option<Arc<SourceMap>>,
+}
+
+/// Translates a position in a prepared document to the matching one in the
+/// document the client registered, see `PreparedOperation::with_source_map`
+pub type SourceMap = Fn(&SourcePosition) -> SourcePosition + Send + Sync;
+
+impl<'a, S> PreparedOperation<'a, S> where S: ScalarValue {
+    /// Parse and validate a document, and select the operation to execute
+    ///
+    /// The operation name may only be omitted if the document contains a
+    /// single operation.
+    pub fn new<QueryT, MutationT>(
+        root_node: &RootNode<QueryT, MutationT, S>,
+        document_source: &'a str,
+        operation_name: Option<&str>,
+    )
+        -> Result<PreparedOperation<'a, S>, GraphQLError<'a>>
+        where QueryT: GraphQLType<S>,
+              MutationT: GraphQLType<S>,
+    {
+        let document = try!(parse_document_source(document_source));
+
+        {
+            let mut ctx = ValidatorContext::new(&root_node.schema, &document);
+            visit_all_rules(&mut ctx, &document);
+
+            let errors = ctx.into_errors();
+            if !errors.is_empty() {
+                return Err(GraphQLError::ValidationError(errors));
+            }
+        }
+
+        let operation = try!(select_operation(&document, operation_name));
+
+        Ok(PreparedOperation {
+            document: document,
+            operation_name: operation_name.map(str::to_owned),
+            operation: operation,
+            document_id: None,
+            source_map: None,
+        })
+    }
+
+    /// Identify the document in the errors of every execution
+    ///
+    /// The identifier is added as `documentId` to the `extensions` of the
+    /// errors.
+    pub fn with_document_id<T: Into<String>>(mut self, document_id: T) -> PreparedOperation<'a, S> {
+        self.document_id = Some(document_id.into());
+        self
+    }
+
+    /// Translate the locations of execution errors before returning them
+    pub fn with_source_map<F>(mut self, source_map: F) -> PreparedOperation<'a, S>
+        where F: Fn(&SourcePosition) -> SourcePosition + Send + Sync + 'static
+    {
+        self.source_map = Some(Arc::new(source_map));
+        self
+    }
+
+    /// The identifier of the document, if it has one
+    pub fn document_id(&self) -> Option<&str> {
+        self.document_id.as_ref().map(String::as_str)
+    }
+
+    /// The name of the selected operation, if it has one
+    pub fn operation_name(&self) -> Option<&'a str> {
+        match self.document[self.operation] {
+            Definition::Operation(ref op) => op.item.name.as_ref().map(|name| name.item),
+            Definition::Fragment(_) => unreachable!(),
+        }
+    }
+
+    /// Is the selected operation a mutation?
+    pub fn is_mutation(&self) -> bool {
+        match self.document[self.operation] {
+            Definition::Operation(ref op) => op.item.operation_type == OperationType::Mutation,
+            Definition::Fragment(_) => unreachable!(),
+        }
+    }
+
+    /// The names and types of the variables the selected operation defines
+    pub fn variables(&self) -> Vec<(&'a str, &Type<'a>)> {
+        match self.document[self.operation] {
+            Definition::Operation(ref op) => op.item.variable_definitions.as_ref()
+                .map(|defs| defs.item.items.iter()
+                    .map(|&(ref name, ref def)| (name.item, &def.var_type.item))
+                    .collect())
+                .unwrap_or_default(),
+            Definition::Fragment(_) => unreachable!(),
+        }
+    }
+
+    /// Execute the operation with the given variables
+    ///
+    /// The variables are checked against the definitions of the operation
+    /// first, so this fails with a `GraphQLError::ValidationError` if they
+    /// don't fit.
+    pub fn execute<CtxT, QueryT, MutationT>(
+        &self,
+        root_node: &RootNode<QueryT, MutationT, S>,
+        variables: &Variables<S>,
+        context: &CtxT,
+    )
+        -> Result<(Value<S>, Vec<ExecutionError<S>>), GraphQLError<'a>>
+        where QueryT: GraphQLType<S, Context=CtxT>,
+              MutationT: GraphQLType<S, Context=CtxT>,
+    {
+        let errors = validate_input_values(variables, &self.document, &root_node.schema);
+        if !errors.is_empty() {
+            return Err(GraphQLError::ValidationError(errors));
+        }
+
+        try!(check_operation(&self.document, self.operation_name.as_ref().map(String::as_str), &root_node.options));
+
+        let (value, errors) = try!(execute_validated_query(
+            &self.document,
+            self.operation_name.as_ref().map(String::as_str),
+            root_node,
+            Cow::Borrowed(variables),
+            context));
+
+        if self.document_id.is_none() && self.source_map.is_none() {
+            return Ok((value, errors));
+        }
+
+        let errors = errors.into_iter()
+            .map(|error| {
+                let error = match self.source_map {
+                    Some(ref source_map) => {
+                        let location = source_map(error.location());
+                        error.with_location(location)
+                    },
+                    None => error,
+                };
+
+                match self.document_id {
+                    Some(ref id) => error.with_extension("documentId", Value::string(id)),
+                    None => error,
+                }
+            })
+            .collect();
+
+        Ok((value, errors))
+    }
+}
+
+// The index of the operation `execute_validated_query` will pick
+pub fn select_operation<'a, S>(document: &Document<S>, operation_name: Option<&str>)
+    -> Result<usize, GraphQLError<'a>>
+{
+    let mut operation = None;
+
+    for (i, def) in document.iter().enumerate() {
+        if let Definition::Operation(ref op) = *def {
+            if operation_name.is_none() && operation.is_some() {
+                return Err(GraphQLError::MultipleOperationsProvided);
+            }
+
+            if operation_name.is_none() || op.item.name.as_ref().map(|s| s.item) == operation_name {
+                operation = Some(i);
+            }
+        }
+    }
+
+    operation.ok_or(GraphQLError::UnknownOperationName)
+}
+
+#[cfg(test)]
+mod tests {
+    use ast::InputValue;
+    use executor::Variables;
+    use parser::SourcePosition;
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+    use value::Value;
+
+    use GraphQLError;
+    use super::PreparedOperation;
+
+    #[test]
+    fn executes_prepared_operation_repeatedly() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+        let operation = PreparedOperation::new(
+            &schema,
+            "query A { hero { name } } query B($id: String!) { human(id: $id) { name } }",
+            Some("B"),
+        ).unwrap();
+
+        assert_eq!(operation.operation_name(), Some("B"));
+        assert!(!operation.is_mutation());
+        assert_eq!(operation.variables().iter().map(|&(name, _)| name).collect::<Vec<_>>(), vec!["id"]);
+
+        for &(id, name) in &[("1000", "Luke Skywalker"), ("1003", "Leia Organa")] {
+            let mut variables = Variables::new();
+            variables.insert("id".to_owned(), InputValue::string(id));
+
+            let (value, errors) = operation.execute(&schema, &variables, &Database::new()).unwrap();
+            assert!(errors.is_empty());
+            assert_eq!(
+                value,
+                Value::object(vec![
+                    ("human", Value::object(vec![("name", Value::string(name))].into_iter().collect())),
+                ].into_iter().collect()));
+        }
+
+        match operation.execute(&schema, &Variables::new(), &Database::new()) {
+            Err(GraphQLError::ValidationError(ref errors)) => assert_eq!(errors.len(), 1),
+            _ => panic!("Expected missing variables to be rejected"),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_documents() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+        assert!(match PreparedOperation::new(&schema, "{ hero { name }", None) {
+            Err(GraphQLError::ParseError(_)) => true,
+            _ => false,
+        });
+        assert!(match PreparedOperation::new(&schema, "{ hero { nope } }", None) {
+            Err(GraphQLError::ValidationError(_)) => true,
+            _ => false,
+        });
+        assert!(match PreparedOperation::new(&schema, "query A { hero { name } } query B { hero { id } }", None) {
+            Err(GraphQLError::MultipleOperationsProvided) => true,
+            _ => false,
+        });
+        assert!(match PreparedOperation::new(&schema, "query A { hero { name } }", Some("B")) {
+            Err(GraphQLError::UnknownOperationName) => true,
+            _ => false,
+        });
+    }
+
+    #[test]
+    fn identifies_the_document_in_errors() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new())
+            .with_response_budget(0);
+        let operation = PreparedOperation::new(&schema, "{hero{name}}", None).unwrap()
+            .with_document_id("abc123")
+            .with_source_map(|pos: &SourcePosition| SourcePosition::new(pos.index() + 10, pos.line() + 2, pos.column()));
+
+        assert_eq!(operation.document_id(), Some("abc123"));
+
+        let (_, errors) = operation.execute(&schema, &Variables::new(), &Database::new()).unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].location(), &SourcePosition::new(16, 2, 6));
+        assert_eq!(errors[0].extensions().get("documentId"), Some(&Value::string("abc123")));
+        assert_eq!(errors[0].extensions().get("code"), Some(&Value::string("RESPONSE_TOO_LARGE")));
+    }
+}
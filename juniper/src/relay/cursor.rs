@@ -0,0 +1,250 @@
+use std::error::Error;
+use std::fmt;
+
+use base64;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+
+#[cfg(feature="relay-signed-cursors")] use hmac::{Hmac, Mac};
+#[cfg(feature="relay-signed-cursors")] use sha2::Sha256;
+
+use executor::{FieldError, IntoFieldError};
+use value::ScalarValue;
+
+use GraphQLErrorCode;
+
+#[cfg(feature="relay-signed-cursors")]
+const SIGNATURE_LEN: usize = 32;
+
+/// Encodes and decodes opaque pagination cursors
+///
+/// A cursor is any serializable value, e.g. the sort key of the last item of a
+/// page, encoded as base64 of its JSON representation. Resolvers decode the
+/// cursors they receive back into the same type:
+///
+/// ```rust
+/// # #[macro_use] extern crate serde_derive;
+/// # extern crate juniper;
+/// use juniper::relay::CursorCodec;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct PostCursor {
+///     created_at: i64,
+///     id: i32,
+/// }
+///
+/// # fn main() {
+/// let codec = CursorCodec::new();
+/// let cursor = codec.encode(&PostCursor { created_at: 1514764800, id: 7 }).unwrap();
+///
+/// assert_eq!(codec.decode::<PostCursor>(&cursor).unwrap(), PostCursor { created_at: 1514764800, id: 7 });
+/// assert!(codec.decode::<PostCursor>("bm9wZQ==").is_err());
+/// # }
+/// ```
+///
+/// Cursors are opaque, but not secret: clients can decode them, and craft
+/// their own. With the `relay-signed-cursors` feature, `with_signing_key`
+/// adds an HMAC-SHA256 signature to every cursor, and cursors that weren't
+/// signed with the same key are rejected.
+///
+/// Errors decoding a cursor can be returned from resolvers directly, and are
+/// reported as `BAD_USER_INPUT`.
+#[derive(Clone, Default)]
+pub struct CursorCodec {
+    #[cfg(feature="relay-signed-cursors")]
+    key: Option<Vec<u8>>,
+}
+
+/// Error encoding or decoding a cursor
+#[derive(Debug)]
+pub enum CursorError {
+    /// The value can't be serialized as JSON
+    Serialize(serde_json::Error),
+    /// The cursor isn't valid base64
+    InvalidEncoding,
+    /// The cursor wasn't signed with the key of the codec
+    InvalidSignature,
+    /// The cursor doesn't decode to a value of the expected type
+    InvalidContent(serde_json::Error),
+}
+
+impl CursorCodec {
+    /// Construct a codec that doesn't sign cursors
+    pub fn new() -> CursorCodec {
+        CursorCodec::default()
+    }
+
+    /// Sign cursors with the given key, and reject cursors that weren't
+    #[cfg(feature="relay-signed-cursors")]
+    pub fn with_signing_key(mut self, key: &[u8]) -> CursorCodec {
+        self.key = Some(key.to_vec());
+        self
+    }
+
+    /// Encode a value as an opaque cursor
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<String, CursorError> {
+        let json = try!(serde_json::to_vec(value).map_err(CursorError::Serialize));
+
+        Ok(base64::encode(&self.sign(json)))
+    }
+
+    /// Decode a cursor back into the value it was encoded from
+    pub fn decode<T: DeserializeOwned>(&self, cursor: &str) -> Result<T, CursorError> {
+        let bytes = try!(base64::decode(cursor).map_err(|_| CursorError::InvalidEncoding));
+        let json = try!(self.verify(bytes));
+
+        serde_json::from_slice(&json).map_err(CursorError::InvalidContent)
+    }
+
+    #[cfg(feature="relay-signed-cursors")]
+    fn sign(&self, json: Vec<u8>) -> Vec<u8> {
+        match self.mac() {
+            Some(mut mac) => {
+                mac.input(&json);
+                let mut signed = mac.result().code().to_vec();
+                signed.extend(json);
+                signed
+            },
+            None => json,
+        }
+    }
+
+    #[cfg(not(feature="relay-signed-cursors"))]
+    fn sign(&self, json: Vec<u8>) -> Vec<u8> {
+        json
+    }
+
+    #[cfg(feature="relay-signed-cursors")]
+    fn verify(&self, bytes: Vec<u8>) -> Result<Vec<u8>, CursorError> {
+        let mut mac = match self.mac() {
+            Some(mac) => mac,
+            None => return Ok(bytes),
+        };
+
+        if bytes.len() < SIGNATURE_LEN {
+            return Err(CursorError::InvalidSignature);
+        }
+
+        let (signature, json) = bytes.split_at(SIGNATURE_LEN);
+        mac.input(json);
+        try!(mac.verify(signature).map_err(|_| CursorError::InvalidSignature));
+
+        Ok(json.to_vec())
+    }
+
+    #[cfg(not(feature="relay-signed-cursors"))]
+    fn verify(&self, bytes: Vec<u8>) -> Result<Vec<u8>, CursorError> {
+        Ok(bytes)
+    }
+
+    #[cfg(feature="relay-signed-cursors")]
+    fn mac(&self) -> Option<Hmac<Sha256>> {
+        self.key.as_ref().map(|key| Hmac::new_varkey(key).expect("HMAC accepts keys of any length"))
+    }
+}
+
+impl fmt::Debug for CursorCodec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Never print the key
+        f.write_str("CursorCodec")
+    }
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CursorError::Serialize(ref err) => write!(f, "Could not encode the cursor: {}", err),
+            CursorError::InvalidEncoding => write!(f, "The cursor is not valid"),
+            CursorError::InvalidSignature => write!(f, "The cursor is not valid"),
+            CursorError::InvalidContent(_) => write!(f, "The cursor is not valid"),
+        }
+    }
+}
+
+impl Error for CursorError {
+    fn description(&self) -> &str {
+        match *self {
+            CursorError::Serialize(_) => "Could not encode the cursor",
+            CursorError::InvalidEncoding => "Invalid cursor encoding",
+            CursorError::InvalidSignature => "Invalid cursor signature",
+            CursorError::InvalidContent(_) => "Invalid cursor content",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            CursorError::Serialize(ref err) | CursorError::InvalidContent(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<S> IntoFieldError<S> for CursorError where S: ScalarValue {
+    fn into_field_error(self) -> FieldError<S> {
+        let code = match self {
+            CursorError::Serialize(_) => GraphQLErrorCode::InternalServerError,
+            _ => GraphQLErrorCode::BadUserInput,
+        };
+
+        FieldError::from(self).with_code(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::{FieldError, IntoFieldError};
+    use value::Value;
+
+    use super::{CursorCodec, CursorError};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Cursor {
+        id: i32,
+    }
+
+    #[test]
+    fn round_trips_cursors() {
+        let codec = CursorCodec::new();
+        let cursor = codec.encode(&Cursor { id: 3 }).unwrap();
+
+        assert_eq!(cursor, "eyJpZCI6M30=");
+        assert_eq!(codec.decode::<Cursor>(&cursor).unwrap(), Cursor { id: 3 });
+    }
+
+    #[test]
+    fn rejects_invalid_cursors() {
+        let codec = CursorCodec::new();
+
+        assert!(match codec.decode::<Cursor>("not base64!") {
+            Err(CursorError::InvalidEncoding) => true,
+            _ => false,
+        });
+        assert!(match codec.decode::<Cursor>("eyJuYW1lIjoiYSJ9") {
+            Err(CursorError::InvalidContent(_)) => true,
+            _ => false,
+        });
+
+        let err: FieldError = codec.decode::<Cursor>("not base64!").unwrap_err().into_field_error();
+        assert_eq!(err.message(), "The cursor is not valid");
+        assert_eq!(err.extensions().get("code"), Some(&Value::string("BAD_USER_INPUT")));
+    }
+
+    #[cfg(feature="relay-signed-cursors")]
+    #[test]
+    fn rejects_cursors_signed_with_other_keys() {
+        let codec = CursorCodec::new().with_signing_key(b"secret");
+        let cursor = codec.encode(&Cursor { id: 3 }).unwrap();
+        assert_eq!(codec.decode::<Cursor>(&cursor).unwrap(), Cursor { id: 3 });
+
+        let forged = CursorCodec::new().with_signing_key(b"guess").encode(&Cursor { id: 4 }).unwrap();
+        let unsigned = CursorCodec::new().encode(&Cursor { id: 4 }).unwrap();
+
+        for cursor in &[forged, unsigned] {
+            assert!(match codec.decode::<Cursor>(cursor) {
+                Err(CursorError::InvalidSignature) => true,
+                _ => false,
+            });
+        }
+    }
+}
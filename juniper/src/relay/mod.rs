@@ -0,0 +1,17 @@
+//! Helpers for the conventions of [Relay][1]-compliant schemas
+//!
+//! These are enabled with the `relay` feature. Signing cursors additionally
+//! requires the `relay-signed-cursors` feature.
+//!
+//! [1]: https://facebook.github.io/relay/docs/en/graphql-server-specification.html
+
+mod cursor;
+#[macro_use] mod mutation;
+mod node;
+mod pagination;
+mod typed_id;
+
+pub use self::cursor::{CursorCodec, CursorError};
+pub use self::node::{GlobalId, GlobalIdError, Node, NodeFetcher, NodeFetchers};
+pub use self::pagination::{KeysetPage, OffsetPage, PaginationArgs, PaginationError};
+pub use self::typed_id::TypedId;
@@ -0,0 +1,194 @@
+/**
+Define the input and payload types of a Relay mutation
+
+[Relay mutations][1] take a single `input` argument, and return a payload
+object. Both carry a `clientMutationId` that clients use to match payloads to
+mutations. This macro generates both types from their fields, adding the
+`client_mutation_id` field to each:
+
+```rust
+# #[macro_use] extern crate juniper;
+use juniper::{FieldResult, RootNode, Variables};
+
+struct Ship { name: String }
+
+graphql_object!(Ship: () |&self| {
+    field name() -> &str { &self.name }
+});
+
+graphql_relay_mutation!(
+    /// The input of `introduceShip`
+    input IntroduceShipInput {
+        ship_name: String as "The name of the new ship",
+    }
+
+    /// The payload of `introduceShip`
+    payload IntroduceShipPayload: () {
+        ship: Ship as "The new ship",
+    }
+);
+
+struct Mutation;
+
+graphql_object!(Mutation: () |&self| {
+    field introduce_ship(input: IntroduceShipInput) -> FieldResult<IntroduceShipPayload> {
+        Ok(IntroduceShipPayload {
+            client_mutation_id: input.client_mutation_id,
+            ship: Ship { name: input.ship_name },
+        })
+    }
+});
+# struct Query;
+# graphql_object!(Query: () |&self| { field ok() -> bool { true } });
+
+# fn main() {
+let schema = RootNode::new(Query, Mutation);
+let (result, errors) = juniper::execute(
+    r#"mutation {
+        introduceShip(input: { shipName: "B-Wing", clientMutationId: "1" }) {
+            clientMutationId
+            ship { name }
+        }
+    }"#,
+    None, &schema, &Variables::new(), &()).unwrap();
+
+assert!(errors.is_empty());
+# }
+```
+
+Both structs are public, as are their fields. The payload is an object with
+the given context type, and can hold any type that can be returned from a
+field. The inputs are converted from the `input` argument like the fields
+of `graphql_input_object!`.
+
+[1]: https://facebook.github.io/relay/graphql/mutations.htm
+*/
+#[macro_export]
+macro_rules! graphql_relay_mutation {
+    (
+        $(#[$input_meta:meta])*
+        input $input:ident {
+            $( $input_field:ident : $input_type:ty $(as $input_descr:tt)* ),* $(,)*
+        }
+
+        $(#[$payload_meta:meta])*
+        payload $payload:ident : $ctxt:ty {
+            $( $payload_field:ident : $payload_type:ty $(as $payload_descr:tt)* ),* $(,)*
+        }
+    ) => {
+        $(#[$input_meta])*
+        pub struct $input {
+            /// Identifies the mutation, and is returned as it is in its payload
+            pub client_mutation_id: Option<String>,
+            $( pub $input_field: $input_type, )*
+        }
+
+        graphql_input_object!(
+            @generate_impls, $input, (stringify!($input)), None,
+            (
+                client_mutation_id: Option<String> as "Identifies the mutation in its payload",
+                $( $input_field : $input_type $(as $input_descr)* ),*
+            ));
+
+        $(#[$payload_meta])*
+        pub struct $payload {
+            /// The `client_mutation_id` of the input
+            pub client_mutation_id: Option<String>,
+            $( pub $payload_field: $payload_type, )*
+        }
+
+        graphql_object!($payload: $ctxt |&self| {
+            field client_mutation_id() -> Option<&str> as "The clientMutationId of the input" {
+                self.client_mutation_id.as_ref().map(|id| id.as_str())
+            }
+
+            $(
+                field $payload_field() -> &$payload_type $(as $payload_descr)* {
+                    &self.$payload_field
+                }
+            )*
+        });
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::{FieldResult, Variables};
+    use schema::model::RootNode;
+    use value::Value;
+
+    pub struct Database;
+
+    impl ::Context for Database {}
+
+    pub struct Ship {
+        name: String,
+    }
+
+    graphql_object!(Ship: Database |&self| {
+        field name() -> &str { &self.name }
+    });
+
+    graphql_relay_mutation!(
+        input IntroduceShipInput {
+            faction_id: String,
+            ship_name: String as "The name of the new ship",
+        }
+
+        payload IntroduceShipPayload: Database {
+            ship: Ship,
+            faction_id: Option<String> as "The faction the ship joined",
+        }
+    );
+
+    struct Query;
+
+    graphql_object!(Query: Database |&self| {
+        field ok() -> bool { true }
+    });
+
+    struct Mutation;
+
+    graphql_object!(Mutation: Database |&self| {
+        field introduce_ship(input: IntroduceShipInput) -> FieldResult<IntroduceShipPayload> {
+            Ok(IntroduceShipPayload {
+                client_mutation_id: input.client_mutation_id,
+                ship: Ship { name: input.ship_name },
+                faction_id: Some(input.faction_id),
+            })
+        }
+    });
+
+    #[test]
+    fn generates_input_and_payload_types() {
+        let schema = RootNode::new(Query, Mutation);
+        let doc = r#"mutation {
+            withId: introduceShip(input: { factionId: "1", shipName: "B-Wing", clientMutationId: "abc" }) {
+                clientMutationId
+                ship { name }
+                factionId
+            }
+            withoutId: introduceShip(input: { factionId: "2", shipName: "X-Wing" }) {
+                clientMutationId
+            }
+        }"#;
+
+        let (result, errs) = ::execute(doc, None, &schema, &Variables::new(), &Database).unwrap();
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("withId", Value::object(vec![
+                    ("clientMutationId", Value::string("abc")),
+                    ("ship", Value::object(vec![("name", Value::string("B-Wing"))].into_iter().collect())),
+                    ("factionId", Value::string("1")),
+                ].into_iter().collect())),
+                ("withoutId", Value::object(vec![
+                    ("clientMutationId", Value::null()),
+                ].into_iter().collect())),
+            ].into_iter().collect()));
+
+        let input_type = schema.schema.concrete_type_by_name("IntroduceShipInput").unwrap();
+        assert!(input_type.input_field_by_name("clientMutationId").is_some());
+    }
+}
@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::str;
+
+use base64;
+
+use executor::{FieldError, FieldResult};
+use types::base::DynGraphQLType;
+use types::scalars::ID;
+use value::DefaultScalarValue;
+
+use GraphQLErrorCode;
+
+/// An object that can be refetched by its global ID
+///
+/// This is the `Node` interface of the [Relay global object identification
+/// specification][1]. Implement the trait for every object type that can be
+/// fetched through `node(id: ID!)`, and list `Box<Node<CtxT>>` in the
+/// `interfaces` of the object:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use juniper::ID;
+/// use juniper::relay::{GlobalId, Node};
+///
+/// struct Database;
+/// impl juniper::Context for Database {}
+///
+/// struct User { id: String }
+///
+/// impl Node<Database> for User {
+///     fn global_id(&self) -> ID {
+///         GlobalId::new("User", &self.id).encode()
+///     }
+/// }
+///
+/// graphql_object!(User: Database |&self| {
+///     field id() -> ID { self.global_id() }
+///
+///     interfaces: [Box<Node<Database>>]
+/// });
+/// # fn main() { }
+/// ```
+///
+/// The object types must be reachable from the root of the schema some other
+/// way too, e.g. through a field returning them, for the schema to know them.
+///
+/// [1]: https://facebook.github.io/relay/graphql/objectidentification.htm
+pub trait Node<CtxT>: DynGraphQLType<CtxT> {
+    /// The global ID of the object, see `GlobalId`
+    fn global_id(&self) -> ID;
+}
+
+graphql_interface!(<CtxT> Box<Node<CtxT>>: CtxT as "Node" where Scalar = DefaultScalarValue |&self| {
+    description: "An object with a global ID"
+
+    field id() -> ID as "The global ID of the object" {
+        self.global_id()
+    }
+
+    instance_types: []
+});
+
+/// The type name and ID that make up an opaque global ID
+///
+/// Global IDs are the base64 encoding of `TypeName:id`, so they are unique
+/// across all types, and `node(id: ID!)` can tell which type to fetch.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct GlobalId {
+    type_name: String,
+    id: String,
+}
+
+/// Error decoding a `GlobalId`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GlobalIdError {
+    /// The ID isn't valid base64, or doesn't decode to UTF-8 text
+    InvalidEncoding,
+    /// The decoded ID doesn't contain a type name
+    MissingTypeName,
+    /// The ID is of another type than expected, see `TypedId`
+    UnexpectedType {
+        /// The name of the expected type
+        expected: String,
+        /// The name of the type of the ID
+        found: String,
+    },
+}
+
+/// Fetches objects by their global ID, to implement `node(id: ID!)`
+///
+/// A fetcher is registered for every type name. The root query type holds
+/// on to the fetchers, and dispatches to them from its `node` field:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// # use juniper::ID;
+/// # use juniper::relay::{GlobalId, Node};
+/// # struct Database;
+/// # impl juniper::Context for Database {}
+/// # struct User { id: String }
+/// # impl Node<Database> for User {
+/// #     fn global_id(&self) -> ID { GlobalId::new("User", &self.id).encode() }
+/// # }
+/// # graphql_object!(User: Database |&self| {
+/// #     field id() -> ID { self.global_id() }
+/// #     interfaces: [Box<Node<Database>>]
+/// # });
+/// use juniper::FieldResult;
+/// use juniper::relay::NodeFetchers;
+///
+/// struct Query {
+///     nodes: NodeFetchers<Database>,
+/// }
+///
+/// graphql_object!(Query: Database |&self| {
+///     field node(&executor, id: ID) -> FieldResult<Option<Box<Node<Database>>>> {
+///         self.nodes.fetch(&id, executor.context())
+///     }
+///
+///     field user() -> User { User { id: "1".to_owned() } }
+/// });
+///
+/// # fn main() {
+/// let query = Query {
+///     nodes: NodeFetchers::new()
+///         .with_fetcher("User", |id, _: &Database| Ok(Some(Box::new(User { id: id.to_owned() })))),
+/// };
+/// # }
+/// ```
+///
+/// IDs of types without a fetcher resolve to `null`, as do IDs the fetcher
+/// doesn't find, while IDs that can't be decoded are a `BAD_USER_INPUT` error.
+pub struct NodeFetchers<CtxT> {
+    fetchers: HashMap<String, Box<NodeFetcher<CtxT>>>,
+}
+
+/// Fetches an object of one type by the ID it was encoded from
+pub type NodeFetcher<CtxT> = Fn(&str, &CtxT) -> FieldResult<Option<Box<Node<CtxT>>>> + Send + Sync;
+
+impl GlobalId {
+    /// Construct the global ID of an object of the given type
+    pub fn new<T: Into<String>, I: Into<String>>(type_name: T, id: I) -> GlobalId {
+        GlobalId {
+            type_name: type_name.into(),
+            id: id.into(),
+        }
+    }
+
+    /// Decode an opaque global ID
+    pub fn decode(id: &str) -> Result<GlobalId, GlobalIdError> {
+        let bytes = try!(base64::decode(id).map_err(|_| GlobalIdError::InvalidEncoding));
+        let decoded = try!(str::from_utf8(&bytes).map_err(|_| GlobalIdError::InvalidEncoding));
+
+        match decoded.find(':') {
+            Some(pos) if pos > 0 => Ok(GlobalId::new(&decoded[..pos], &decoded[pos + 1..])),
+            _ => Err(GlobalIdError::MissingTypeName),
+        }
+    }
+
+    /// Encode the ID as opaque base64
+    pub fn encode(&self) -> ID {
+        ID::from(base64::encode(format!("{}:{}", self.type_name, self.id).as_bytes()))
+    }
+
+    /// The name of the GraphQL type of the object
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// The ID of the object among those of its type
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+impl fmt::Display for GlobalIdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GlobalIdError::InvalidEncoding => write!(f, "The ID is not a valid global ID"),
+            GlobalIdError::MissingTypeName => write!(f, "The global ID does not contain a type name"),
+            GlobalIdError::UnexpectedType { ref expected, ref found } =>
+                write!(f, "Expected the ID of a {}, found the ID of a {}", expected, found),
+        }
+    }
+}
+
+impl Error for GlobalIdError {
+    fn description(&self) -> &str {
+        match *self {
+            GlobalIdError::InvalidEncoding => "Invalid global ID encoding",
+            GlobalIdError::MissingTypeName => "Missing type name in global ID",
+            GlobalIdError::UnexpectedType { .. } => "Global ID of an unexpected type",
+        }
+    }
+}
+
+impl<CtxT> NodeFetchers<CtxT> {
+    /// Construct an empty set of fetchers
+    pub fn new() -> NodeFetchers<CtxT> {
+        NodeFetchers {
+            fetchers: HashMap::new(),
+        }
+    }
+
+    /// Fetch the objects of a type with the given function
+    ///
+    /// The function is called with the ID of the object among those of its
+    /// type, i.e. the ID the global ID was constructed from.
+    pub fn with_fetcher<F>(mut self, type_name: &str, fetcher: F) -> NodeFetchers<CtxT>
+        where F: Fn(&str, &CtxT) -> FieldResult<Option<Box<Node<CtxT>>>> + Send + Sync + 'static
+    {
+        self.fetchers.insert(type_name.to_owned(), Box::new(fetcher));
+        self
+    }
+
+    /// Fetch the object with the given global ID
+    pub fn fetch(&self, id: &ID, context: &CtxT) -> FieldResult<Option<Box<Node<CtxT>>>> {
+        let id = try!(GlobalId::decode(id).map_err(|err| {
+            FieldError::from(err).with_code(GraphQLErrorCode::BadUserInput)
+        }));
+
+        match self.fetchers.get(id.type_name()) {
+            Some(fetcher) => fetcher(id.id(), context),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<CtxT> Default for NodeFetchers<CtxT> {
+    fn default() -> NodeFetchers<CtxT> {
+        NodeFetchers::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::{FieldResult, Variables};
+    use schema::model::RootNode;
+    use types::scalars::{EmptyMutation, ID};
+    use value::Value;
+
+    use super::{GlobalId, GlobalIdError, Node, NodeFetchers};
+
+    struct Database;
+
+    impl ::Context for Database {}
+
+    struct User {
+        id: String,
+    }
+
+    impl Node<Database> for User {
+        fn global_id(&self) -> ID {
+            GlobalId::new("User", &self.id).encode()
+        }
+    }
+
+    graphql_object!(User: Database |&self| {
+        field id() -> ID { self.global_id() }
+        field name() -> String { format!("User {}", self.id) }
+
+        interfaces: [Box<Node<Database>>]
+    });
+
+    struct Query {
+        nodes: NodeFetchers<Database>,
+    }
+
+    graphql_object!(Query: Database |&self| {
+        field node(&executor, id: ID) -> FieldResult<Option<Box<Node<Database>>>> {
+            self.nodes.fetch(&id, executor.context())
+        }
+
+        field user() -> User { User { id: "1".to_owned() } }
+    });
+
+    #[test]
+    fn encodes_and_decodes_global_ids() {
+        let id = GlobalId::new("User", "a:b");
+        let encoded = id.encode();
+
+        assert_eq!(&*encoded, "VXNlcjphOmI=");
+        assert_eq!(GlobalId::decode(&encoded), Ok(id));
+        assert_eq!(GlobalId::decode("not base64!"), Err(GlobalIdError::InvalidEncoding));
+        assert_eq!(GlobalId::decode("VXNlcg=="), Err(GlobalIdError::MissingTypeName));
+    }
+
+    #[test]
+    fn fetches_nodes_by_global_id() {
+        let schema = RootNode::new(
+            Query {
+                nodes: NodeFetchers::new().with_fetcher("User", |id, _: &Database| {
+                    Ok(if id == "404" { None } else { Some(Box::new(User { id: id.to_owned() })) })
+                }),
+            },
+            EmptyMutation::<Database>::new());
+        let doc = r#"{
+            user: node(id: "VXNlcjo0Mg==") { __typename id ... on User { name } }
+            missing: node(id: "VXNlcjo0MDQ=") { id }
+            unknown: node(id: "UG9zdDox") { id }
+        }"#;
+
+        let (result, errs) = ::execute(doc, None, &schema, &Variables::new(), &Database).unwrap();
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("user", Value::object(vec![
+                    ("__typename", Value::string("User")),
+                    ("id", Value::string("VXNlcjo0Mg==")),
+                    ("name", Value::string("User 42")),
+                ].into_iter().collect())),
+                ("missing", Value::null()),
+                ("unknown", Value::null()),
+            ].into_iter().collect()));
+
+        let (_, errs) = ::execute(r#"{ node(id: "nope") { id } }"#, None, &schema, &Variables::new(), &Database)
+            .unwrap();
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].message(), "The ID is not a valid global ID");
+        assert_eq!(errs[0].extensions().get("code"), Some(&Value::string("BAD_USER_INPUT")));
+    }
+}
@@ -0,0 +1,286 @@
+use std::cmp;
+use std::error::Error;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+
+use executor::{FieldError, IntoFieldError};
+use value::ScalarValue;
+
+use GraphQLErrorCode;
+
+use super::cursor::{CursorCodec, CursorError};
+
+/// The `first`, `after`, `last` and `before` arguments of a Relay connection
+/// field
+///
+/// The arguments are checked before they're turned into the parameters of a
+/// query: `first` and `last` can't be negative, can't exceed the maximum page
+/// size, and can't both be given. Without either of them, a page has the
+/// maximum size.
+///
+/// Pages can be fetched by offset, with cursors encoding the offset of an
+/// item:
+///
+/// ```rust
+/// # extern crate juniper;
+/// use juniper::relay::{CursorCodec, PaginationArgs};
+///
+/// # fn main() {
+/// let codec = CursorCodec::new();
+/// let after = codec.encode(&9).unwrap();
+///
+/// let args = PaginationArgs::new(Some(5), Some(after), None, None);
+/// let page = args.to_offset(&codec, 100, || 50).unwrap();
+///
+/// assert_eq!((page.offset, page.limit), (10, 5));
+/// assert_eq!(page.cursor(&codec, 0).unwrap(), codec.encode(&10).unwrap());
+/// # }
+/// ```
+///
+/// or by key, with cursors encoding the sort key of an item, see `to_keyset`.
+///
+/// Errors can be returned from resolvers directly, and are reported as
+/// `BAD_USER_INPUT`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PaginationArgs {
+    /// The number of items after `after` to return
+    pub first: Option<i32>,
+    /// The cursor of the item to start after
+    pub after: Option<String>,
+    /// The number of items before `before` to return
+    pub last: Option<i32>,
+    /// The cursor of the item to end before
+    pub before: Option<String>,
+}
+
+/// The range of items of a page fetched by offset
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OffsetPage {
+    /// The offset of the first item of the page
+    pub offset: usize,
+    /// The number of items in the page
+    pub limit: usize,
+}
+
+/// The bounds of a page fetched by key
+///
+/// Fetch at most `limit` items with keys between `after` and `before`, in
+/// ascending order of their keys, unless `backward` is set. Backward pages
+/// are the last items before `before`, so they're fetched in descending order
+/// and reversed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeysetPage<K> {
+    /// The key to start after, exclusively
+    pub after: Option<K>,
+    /// The key to end before, exclusively
+    pub before: Option<K>,
+    /// The maximum number of items in the page
+    pub limit: usize,
+    /// Is this page counted from `before` rather than from `after`?
+    pub backward: bool,
+}
+
+/// Error validating the arguments of a connection field
+#[derive(Debug)]
+pub enum PaginationError {
+    /// The named argument is negative
+    Negative(&'static str),
+    /// The named argument exceeds the maximum page size
+    TooLarge(&'static str, usize),
+    /// Both `first` and `last` were given
+    FirstAndLast,
+    /// A cursor couldn't be decoded
+    Cursor(CursorError),
+}
+
+impl PaginationArgs {
+    /// Collect the arguments of a connection field
+    pub fn new(first: Option<i32>, after: Option<String>, last: Option<i32>, before: Option<String>)
+        -> PaginationArgs
+    {
+        PaginationArgs {
+            first: first,
+            after: after,
+            last: last,
+            before: before,
+        }
+    }
+
+    /// The page of items to fetch by offset, with cursors encoding offsets
+    ///
+    /// `count` returns the total number of items. It's only called for pages
+    /// counted from the end without a `before` cursor.
+    pub fn to_offset<F>(&self, codec: &CursorCodec, max_page_size: usize, count: F)
+        -> Result<OffsetPage, PaginationError>
+        where F: FnOnce() -> usize
+    {
+        let (size, backward) = try!(self.page_size(max_page_size));
+        let after: Option<usize> = try!(decode(codec, self.after.as_ref()));
+        let before: Option<usize> = try!(decode(codec, self.before.as_ref()));
+
+        let start = after.map_or(0, |after| after + 1);
+
+        let (offset, end) = if backward {
+            let end = before.unwrap_or_else(count);
+            (cmp::max(start, end.saturating_sub(size)), end)
+        } else {
+            (start, before.map_or(start + size, |before| cmp::min(before, start + size)))
+        };
+
+        Ok(OffsetPage {
+            offset: offset,
+            limit: end.saturating_sub(offset),
+        })
+    }
+
+    /// The page of items to fetch by key, with cursors encoding keys
+    pub fn to_keyset<K>(&self, codec: &CursorCodec, max_page_size: usize)
+        -> Result<KeysetPage<K>, PaginationError>
+        where K: DeserializeOwned
+    {
+        let (size, backward) = try!(self.page_size(max_page_size));
+
+        Ok(KeysetPage {
+            after: try!(decode(codec, self.after.as_ref())),
+            before: try!(decode(codec, self.before.as_ref())),
+            limit: size,
+            backward: backward,
+        })
+    }
+
+    // The size of the page, and whether it's counted from the end
+    fn page_size(&self, max_page_size: usize) -> Result<(usize, bool), PaginationError> {
+        match (self.first, self.last) {
+            (Some(_), Some(_)) => Err(PaginationError::FirstAndLast),
+            (Some(first), None) => check_size("first", first, max_page_size).map(|size| (size, false)),
+            (None, Some(last)) => check_size("last", last, max_page_size).map(|size| (size, true)),
+            (None, None) => Ok((max_page_size, false)),
+        }
+    }
+}
+
+impl OffsetPage {
+    /// The cursor of the item at `index` in the page
+    pub fn cursor(&self, codec: &CursorCodec, index: usize) -> Result<String, CursorError> {
+        codec.encode(&(self.offset + index))
+    }
+}
+
+fn check_size(name: &'static str, size: i32, max_page_size: usize) -> Result<usize, PaginationError> {
+    if size < 0 {
+        Err(PaginationError::Negative(name))
+    } else if size as usize > max_page_size {
+        Err(PaginationError::TooLarge(name, max_page_size))
+    } else {
+        Ok(size as usize)
+    }
+}
+
+fn decode<K: DeserializeOwned>(codec: &CursorCodec, cursor: Option<&String>) -> Result<Option<K>, PaginationError> {
+    match cursor {
+        Some(cursor) => codec.decode(cursor).map(Some).map_err(PaginationError::Cursor),
+        None => Ok(None),
+    }
+}
+
+impl fmt::Display for PaginationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PaginationError::Negative(name) => write!(f, "\"{}\" must not be negative", name),
+            PaginationError::TooLarge(name, max) => write!(f, "\"{}\" must not exceed {}", name, max),
+            PaginationError::FirstAndLast => write!(f, "\"first\" and \"last\" must not be given together"),
+            PaginationError::Cursor(ref err) => fmt::Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for PaginationError {
+    fn description(&self) -> &str {
+        match *self {
+            PaginationError::Negative(_) => "Negative page size",
+            PaginationError::TooLarge(_, _) => "Page size too large",
+            PaginationError::FirstAndLast => "Both first and last given",
+            PaginationError::Cursor(_) => "Invalid cursor",
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            PaginationError::Cursor(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl<S> IntoFieldError<S> for PaginationError where S: ScalarValue {
+    fn into_field_error(self) -> FieldError<S> {
+        match self {
+            PaginationError::Cursor(err) => err.into_field_error(),
+            err => FieldError::from(err).with_code(GraphQLErrorCode::BadUserInput),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::{FieldError, IntoFieldError};
+    use relay::CursorCodec;
+    use value::Value;
+
+    use super::{KeysetPage, OffsetPage, PaginationArgs, PaginationError};
+
+    fn offset_page(first: Option<i32>, after: Option<usize>, last: Option<i32>, before: Option<usize>)
+        -> Result<OffsetPage, PaginationError>
+    {
+        let codec = CursorCodec::new();
+        let cursor = |offset: Option<usize>| offset.map(|offset| codec.encode(&offset).unwrap());
+
+        PaginationArgs::new(first, cursor(after), last, cursor(before)).to_offset(&codec, 20, || 50)
+    }
+
+    #[test]
+    fn converts_to_offsets() {
+        let page = |offset, limit| OffsetPage { offset: offset, limit: limit };
+
+        assert_eq!(offset_page(None, None, None, None).unwrap(), page(0, 20));
+        assert_eq!(offset_page(Some(5), None, None, None).unwrap(), page(0, 5));
+        assert_eq!(offset_page(Some(5), Some(9), None, None).unwrap(), page(10, 5));
+        assert_eq!(offset_page(Some(5), Some(9), None, Some(12)).unwrap(), page(10, 2));
+        assert_eq!(offset_page(None, None, Some(5), None).unwrap(), page(45, 5));
+        assert_eq!(offset_page(None, None, Some(5), Some(3)).unwrap(), page(0, 3));
+        assert_eq!(offset_page(None, Some(1), Some(5), Some(4)).unwrap(), page(2, 2));
+        assert_eq!(offset_page(Some(0), None, None, None).unwrap(), page(0, 0));
+    }
+
+    #[test]
+    fn converts_to_keysets() {
+        let codec = CursorCodec::new();
+        let args = PaginationArgs::new(None, None, Some(3), Some(codec.encode(&"2018-01-01").unwrap()));
+
+        assert_eq!(
+            args.to_keyset::<String>(&codec, 10).unwrap(),
+            KeysetPage {
+                after: None,
+                before: Some("2018-01-01".to_owned()),
+                limit: 3,
+                backward: true,
+            });
+    }
+
+    #[test]
+    fn rejects_invalid_arguments() {
+        let message = |result: Result<OffsetPage, PaginationError>| {
+            let err: FieldError = result.unwrap_err().into_field_error();
+            assert_eq!(err.extensions().get("code"), Some(&Value::string("BAD_USER_INPUT")));
+            err.message().to_owned()
+        };
+
+        assert_eq!(message(offset_page(Some(-1), None, None, None)), "\"first\" must not be negative");
+        assert_eq!(message(offset_page(None, None, Some(21), None)), "\"last\" must not exceed 20");
+        assert_eq!(message(offset_page(Some(1), None, Some(1), None)), "\"first\" and \"last\" must not be given together");
+
+        let args = PaginationArgs::new(Some(1), Some("nope".to_owned()), None, None);
+        assert_eq!(message(args.to_offset(&CursorCodec::new(), 20, || 50)), "The cursor is not valid");
+    }
+}
@@ -0,0 +1,242 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+
+use ast::{FromInputValue, InputValue, Selection, ToInputValue};
+use executor::{Executor, Registry};
+use schema::meta::MetaType;
+use types::base::GraphQLType;
+use types::scalars::ID;
+use value::{ScalarValue, Value};
+
+use super::node::{GlobalId, GlobalIdError};
+
+/// A global ID of an object of type `T`
+///
+/// Resolvers taking a plain `ID` argument have to check the type of the
+/// object it refers to themselves. A `TypedId<T>` argument only accepts
+/// global IDs of objects of type `T`, so e.g. a `Post` ID can't be passed
+/// where a `User` ID is expected:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use juniper::relay::TypedId;
+///
+/// struct User { id: String }
+///
+/// graphql_object!(User: () |&self| {
+///     field id() -> TypedId<User> { TypedId::new(self.id.clone()) }
+/// });
+///
+/// struct Query;
+///
+/// graphql_object!(Query: () |&self| {
+///     field user(id: TypedId<User>) -> User {
+///         User { id: id.into_inner() }
+///     }
+/// });
+/// # fn main() { }
+/// ```
+///
+/// In the schema, it's an `ID`. It's encoded the same way as `GlobalId`, and
+/// arguments of other types are reported as invalid values.
+pub struct TypedId<T> {
+    id: String,
+    phantom: PhantomData<T>,
+}
+
+impl<T> TypedId<T> {
+    /// Construct the ID of an object of type `T`
+    pub fn new<I: Into<String>>(id: I) -> TypedId<T> {
+        TypedId {
+            id: id.into(),
+            phantom: PhantomData,
+        }
+    }
+
+    /// The ID of the object among those of its type
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Take the ID of the object among those of its type
+    pub fn into_inner(self) -> String {
+        self.id
+    }
+}
+
+impl<T> TypedId<T> where T: GraphQLType<TypeInfo=()> {
+    /// Encode the ID as an opaque global ID
+    pub fn encode(&self) -> ID {
+        encode::<T, _>(&self.id)
+    }
+
+    /// Decode an opaque global ID, checking that it's of type `T`
+    pub fn decode(id: &str) -> Result<TypedId<T>, GlobalIdError> {
+        decode::<T, _>(id)
+    }
+}
+
+fn type_name<T, S>() -> &'static str
+    where T: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
+{
+    T::name(&()).expect("Typed IDs must refer to named types")
+}
+
+fn encode<T, S>(id: &str) -> ID
+    where T: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
+{
+    GlobalId::new(type_name::<T, S>(), id).encode()
+}
+
+fn decode<T, S>(id: &str) -> Result<TypedId<T>, GlobalIdError>
+    where T: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
+{
+    let id = try!(GlobalId::decode(id));
+    let expected = type_name::<T, S>();
+
+    if id.type_name() != expected {
+        return Err(GlobalIdError::UnexpectedType {
+            expected: expected.to_owned(),
+            found: id.type_name().to_owned(),
+        });
+    }
+
+    Ok(TypedId::new(id.id()))
+}
+
+impl<T, S> GraphQLType<S> for TypedId<T>
+    where T: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
+{
+    type Context = ();
+    type TypeInfo = ();
+
+    fn name(_: &()) -> Option<&str> {
+        Some("ID")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_scalar_type::<ID>(&()).into_meta()
+    }
+
+    fn resolve(&self, _: &(), _: Option<&[Selection<S>]>, _: &Executor<Self::Context, S>) -> Value<S> {
+        Value::string(&*encode::<T, S>(&self.id))
+    }
+}
+
+impl<T, S> FromInputValue<S> for TypedId<T>
+    where T: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<TypedId<T>> {
+        match *v {
+            InputValue::String(ref s) => decode::<T, S>(s).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl<T, S> ToInputValue<S> for TypedId<T>
+    where T: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::string(&*encode::<T, S>(&self.id))
+    }
+}
+
+// Implemented by hand, so `T` doesn't have to implement these traits too
+
+impl<T> Clone for TypedId<T> {
+    fn clone(&self) -> TypedId<T> {
+        TypedId::new(self.id.clone())
+    }
+}
+
+impl<T> fmt::Debug for TypedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("TypedId").field(&self.id).finish()
+    }
+}
+
+impl<T> PartialEq for TypedId<T> {
+    fn eq(&self, other: &TypedId<T>) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for TypedId<T> {}
+
+impl<T> Hash for TypedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::Variables;
+    use relay::{GlobalId, GlobalIdError};
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+    use value::Value;
+
+    use super::TypedId;
+
+    struct User;
+    struct Post;
+
+    graphql_object!(User: () |&self| {
+        field id() -> TypedId<User> { TypedId::new("1") }
+    });
+
+    graphql_object!(Post: () |&self| {
+        field id() -> TypedId<Post> { TypedId::new("1") }
+    });
+
+    struct Query;
+
+    graphql_object!(Query: () |&self| {
+        field user(id: TypedId<User>) -> Option<User> {
+            if id.id() == "1" { Some(User) } else { None }
+        }
+
+        field post() -> Post { Post }
+    });
+
+    #[test]
+    fn checks_the_type_of_ids() {
+        let user_id = TypedId::<User>::new("1").encode();
+        let post_id = TypedId::<Post>::new("1").encode();
+
+        assert_eq!(user_id, GlobalId::new("User", "1").encode());
+        assert_eq!(TypedId::<User>::decode(&user_id), Ok(TypedId::new("1")));
+        assert_eq!(
+            TypedId::<User>::decode(&post_id),
+            Err(GlobalIdError::UnexpectedType { expected: "User".to_owned(), found: "Post".to_owned() }));
+    }
+
+    #[test]
+    fn rejects_arguments_of_other_types() {
+        let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+
+        let query = format!(r#"{{ user(id: "{}") {{ id }} }}"#, &*TypedId::<User>::new("1").encode());
+        let (result, errs) = ::execute(&query, None, &schema, &Variables::new(), &()).unwrap();
+        assert_eq!(errs, []);
+        assert_eq!(
+            result,
+            Value::object(vec![
+                ("user", Value::object(vec![("id", Value::string("VXNlcjox"))].into_iter().collect())),
+            ].into_iter().collect()));
+
+        let query = format!(r#"{{ user(id: "{}") {{ id }} }}"#, &*TypedId::<Post>::new("1").encode());
+        let (result, errs) = ::execute(&query, None, &schema, &Variables::new(), &()).unwrap();
+        assert_eq!(result, Value::object(vec![("user", Value::null())].into_iter().collect()));
+        assert_eq!(errs.len(), 1);
+        assert_eq!(errs[0].message(), "Invalid value for argument \"id\"");
+    }
+}
@@ -1,13 +1,16 @@
 use std::fmt;
 use std::result::Result;
 
+use executor::{FieldError, FieldResult};
+use value::ScalarValue;
+
 /**
 Helper trait to produce `FieldResult`s
 
-`FieldResult` only have strings as errors as that's what's going out
-in the GraphQL response. As such, all errors must be manually
-converted to strings. Importing the `ResultExt` macro and using its
-only method `to_field_err` can help with that:
+Errors of any type implementing `Display` can be converted into a
+`FieldError`, which makes the `?` operator work in resolvers returning a
+`FieldResult`. Where an explicit conversion is more convenient, importing
+the `ResultExt` trait and using its only method `to_field_err` can help:
 
 ```rust
 use std::str::FromStr;
@@ -41,13 +44,13 @@ fn sample_fn(s: &str) -> FieldResult<i32> {
 
  */
 pub trait ResultExt<T, E: fmt::Display> {
-    /// Convert the error to a string by using it's `Display` implementation
-    fn to_field_err(self) -> Result<T, String>;
+    /// Convert the error to a `FieldError` by using it's `Display` implementation
+    fn to_field_err<S>(self) -> FieldResult<T, S> where S: ScalarValue;
 }
 
 impl<T, E: fmt::Display> ResultExt<T, E> for Result<T, E> {
-    fn to_field_err(self) -> Result<T, String> {
-        self.map_err(|e| format!("{}", e))
+    fn to_field_err<S>(self) -> FieldResult<T, S> where S: ScalarValue {
+        self.map_err(FieldError::from)
     }
 }
 
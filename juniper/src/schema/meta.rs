@@ -1,19 +1,23 @@
 //! Types used to describe a GraphQL schema
 
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fmt;
 
 use ast::{InputValue, FromInputValue, Type};
 use types::base::TypeKind;
+use value::{ScalarValue, DefaultScalarValue};
+
+pub use schema::name::Name;
 
 /// Scalar type metadata
-pub struct ScalarMeta<'a> {
+pub struct ScalarMeta<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub try_parse_fn: Box<Fn(&InputValue) -> bool + Send + Sync>,
+    pub try_parse_fn: Box<Fn(&InputValue<S>) -> bool + Send + Sync>,
 }
 
 /// List type metadata
@@ -32,19 +36,21 @@ pub struct NullableMeta<'a> {
 
 /// Object type metadata
 #[derive(Debug)]
-pub struct ObjectMeta<'a> {
+pub struct ObjectMeta<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub fields: Vec<Field<'a>>,
+    pub fields: Vec<Field<'a, S>>,
+    #[doc(hidden)]
+    pub interface_names: Vec<Name>,
     #[doc(hidden)]
-    pub interface_names: Vec<String>,
+    pub field_indices: HashMap<Name, usize>,
 }
 
 /// Enum type metadata
-pub struct EnumMeta<'a> {
+pub struct EnumMeta<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
@@ -52,18 +58,20 @@ pub struct EnumMeta<'a> {
     #[doc(hidden)]
     pub values: Vec<EnumValue>,
     #[doc(hidden)]
-    pub try_parse_fn: Box<Fn(&InputValue) -> bool + Send + Sync>,
+    pub try_parse_fn: Box<Fn(&InputValue<S>) -> bool + Send + Sync>,
 }
 
 /// Interface type metadata
 #[derive(Debug)]
-pub struct InterfaceMeta<'a> {
+pub struct InterfaceMeta<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub fields: Vec<Field<'a>>,
+    pub fields: Vec<Field<'a, S>>,
+    #[doc(hidden)]
+    pub field_indices: HashMap<Name, usize>,
 }
 
 /// Union type metadata
@@ -74,19 +82,21 @@ pub struct UnionMeta<'a> {
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub of_type_names: Vec<String>,
+    pub of_type_names: Vec<Name>,
 }
 
 /// Input object metadata
-pub struct InputObjectMeta<'a> {
+pub struct InputObjectMeta<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
     pub name: Cow<'a, str>,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub input_fields: Vec<Argument<'a>>,
+    pub input_fields: Vec<Argument<'a, S>>,
+    #[doc(hidden)]
+    pub field_indices: HashMap<Name, usize>,
     #[doc(hidden)]
-    pub try_parse_fn: Box<Fn(&InputValue) -> bool + Send + Sync>,
+    pub try_parse_fn: Box<Fn(&InputValue<S>) -> bool + Send + Sync>,
 }
 
 /// A placeholder for not-yet-registered types
@@ -101,36 +111,36 @@ pub struct PlaceholderMeta<'a> {
 
 /// Generic type metadata
 #[derive(Debug)]
-pub enum MetaType<'a> {
+pub enum MetaType<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
-    Scalar(ScalarMeta<'a>),
+    Scalar(ScalarMeta<'a, S>),
     #[doc(hidden)]
     List(ListMeta<'a>),
     #[doc(hidden)]
     Nullable(NullableMeta<'a>),
     #[doc(hidden)]
-    Object(ObjectMeta<'a>),
+    Object(ObjectMeta<'a, S>),
     #[doc(hidden)]
-    Enum(EnumMeta<'a>),
+    Enum(EnumMeta<'a, S>),
     #[doc(hidden)]
-    Interface(InterfaceMeta<'a>),
+    Interface(InterfaceMeta<'a, S>),
     #[doc(hidden)]
     Union(UnionMeta<'a>),
     #[doc(hidden)]
-    InputObject(InputObjectMeta<'a>),
+    InputObject(InputObjectMeta<'a, S>),
     #[doc(hidden)]
     Placeholder(PlaceholderMeta<'a>),
 }
 
 /// Metadata for a field
 #[derive(Debug, Clone)]
-pub struct Field<'a> {
+pub struct Field<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
-    pub name: String,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
-    pub arguments: Option<Vec<Argument<'a>>>,
+    pub arguments: Option<Vec<Argument<'a, S>>>,
     #[doc(hidden)]
     pub field_type: Type<'a>,
     #[doc(hidden)]
@@ -139,15 +149,15 @@ pub struct Field<'a> {
 
 /// Metadata for an argument to a field
 #[derive(Debug, Clone)]
-pub struct Argument<'a> {
+pub struct Argument<'a, S = DefaultScalarValue> {
     #[doc(hidden)]
-    pub name: String,
+    pub name: Name,
     #[doc(hidden)]
     pub description: Option<String>,
     #[doc(hidden)]
     pub arg_type: Type<'a>,
     #[doc(hidden)]
-    pub default_value: Option<InputValue>,
+    pub default_value: Option<InputValue<S>>,
 }
 
 /// Metadata for a single value in an enum
@@ -156,7 +166,7 @@ pub struct EnumValue {
     /// The name of the enum value
     ///
     /// This is the string literal representation of the enum in responses.
-    pub name: String,
+    pub name: Name,
     /// The optional description of the enum value.
     ///
     /// Note: this is not the description of the enum itself; it's the
@@ -168,7 +178,7 @@ pub struct EnumValue {
     pub deprecation_reason: Option<String>,
 }
 
-impl<'a> MetaType<'a> {
+impl<'a, S> MetaType<'a, S> where S: ScalarValue {
     /// Access the name of the type, if applicable
     ///
     /// Lists, non-null wrappers, and placeholders don't have names.
@@ -222,11 +232,11 @@ impl<'a> MetaType<'a> {
     /// Access a field's meta data given its name
     ///
     /// Only objects and interfaces have fields. This method always returns `None` for other types.
-    pub fn field_by_name(&self, name: &str) -> Option<&Field> {
+    pub fn field_by_name(&self, name: &str) -> Option<&Field<S>> {
         match *self {
-            MetaType::Object(ObjectMeta { ref fields, .. }) |
-            MetaType::Interface(InterfaceMeta { ref fields, .. }) =>
-                fields.iter().find(|f| f.name == name),
+            MetaType::Object(ObjectMeta { ref fields, ref field_indices, .. }) |
+            MetaType::Interface(InterfaceMeta { ref fields, ref field_indices, .. }) =>
+                lookup(fields, field_indices, name, |f| &f.name),
             _ => None,
         }
     }
@@ -234,14 +244,31 @@ impl<'a> MetaType<'a> {
     /// Access an input field's meta data given its name
     ///
     /// Only input objects have input fields. This method always returns `None` for other types.
-    pub fn input_field_by_name(&self, name: &str) -> Option<&Argument> {
+    pub fn input_field_by_name(&self, name: &str) -> Option<&Argument<S>> {
         match *self {
-            MetaType::InputObject(InputObjectMeta { ref input_fields, .. }) =>
-                input_fields.iter().find(|f| f.name == name),
+            MetaType::InputObject(InputObjectMeta { ref input_fields, ref field_indices, .. }) =>
+                lookup(input_fields, field_indices, name, |f| &f.name),
             _ => None,
         }
     }
 
+    /// Build the tables `field_by_name` and `input_field_by_name` look fields
+    /// up in
+    ///
+    /// This is done for all types once the schema is complete, so wide types
+    /// don't have to be searched field by field.
+    #[doc(hidden)]
+    pub fn index_fields(&mut self) {
+        match *self {
+            MetaType::Object(ObjectMeta { ref fields, ref mut field_indices, .. }) |
+            MetaType::Interface(InterfaceMeta { ref fields, ref mut field_indices, .. }) =>
+                *field_indices = index(fields, |f| &f.name),
+            MetaType::InputObject(InputObjectMeta { ref input_fields, ref mut field_indices, .. }) =>
+                *field_indices = index(input_fields, |f| &f.name),
+            _ => (),
+        }
+    }
+
     /// Construct a `Type` literal instance based on the metadata
     pub fn as_type(&self) -> Type<'a> {
         match *self {
@@ -270,7 +297,7 @@ impl<'a> MetaType<'a> {
     /// `true` if it can be parsed as the provided type.
     ///
     /// Only scalars, enums, and input objects have parse functions.
-    pub fn input_value_parse_fn(&self) -> Option<&Box<Fn(&InputValue) -> bool + Send + Sync>> {
+    pub fn input_value_parse_fn(&self) -> Option<&Box<Fn(&InputValue<S>) -> bool + Send + Sync>> {
         match *self {
             MetaType::Scalar(ScalarMeta { ref try_parse_fn, .. }) |
             MetaType::Enum(EnumMeta { ref try_parse_fn, .. }) |
@@ -327,27 +354,27 @@ impl<'a> MetaType<'a> {
     }
 }
 
-impl<'a> ScalarMeta<'a> {
+impl<'a, S> ScalarMeta<'a, S> where S: ScalarValue {
     /// Build a new scalar type metadata with the specified name
-    pub fn new<T: FromInputValue>(name: Cow<'a, str>) -> ScalarMeta<'a> {
+    pub fn new<T: FromInputValue<S>>(name: Cow<'a, str>) -> ScalarMeta<'a, S> {
         ScalarMeta {
             name: name,
             description: None,
             try_parse_fn: Box::new(
-                |v: &InputValue| <T as FromInputValue>::from(v).is_some()),
+                |v: &InputValue<S>| <T as FromInputValue<S>>::from(v).is_some()),
         }
     }
 
     /// Set the description for the given scalar type
     ///
     /// If a description already was set prior to calling this method, it will be overwritten.
-    pub fn description(mut self, description: &str) -> ScalarMeta<'a> {
+    pub fn description(mut self, description: &str) -> ScalarMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
 
     /// Wrap the scalar in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Scalar(self)
     }
 }
@@ -361,7 +388,7 @@ impl<'a> ListMeta<'a> {
     }
 
     /// Wrap the list in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta<S>(self) -> MetaType<'a, S> {
         MetaType::List(self)
     }
 }
@@ -375,26 +402,27 @@ impl<'a> NullableMeta<'a> {
     }
 
     /// Wrap the nullable type in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta<S>(self) -> MetaType<'a, S> {
         MetaType::Nullable(self)
     }
 }
 
-impl<'a> ObjectMeta<'a> {
+impl<'a, S> ObjectMeta<'a, S> where S: ScalarValue {
     /// Build a new object type with the specified name and fields
-    pub fn new(name: Cow<'a, str>, fields: &[Field<'a>]) -> ObjectMeta<'a> {
+    pub fn new(name: Cow<'a, str>, fields: &[Field<'a, S>]) -> ObjectMeta<'a, S> {
         ObjectMeta {
             name: name,
             description: None,
             fields: fields.to_vec(),
             interface_names: vec![],
+            field_indices: HashMap::new(),
         }
     }
 
     /// Set the description for the object
     ///
     /// If a description was provided prior to calling this method, it will be overwritten.
-    pub fn description(mut self, description: &str) -> ObjectMeta<'a> {
+    pub fn description(mut self, description: &str) -> ObjectMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
@@ -403,64 +431,65 @@ impl<'a> ObjectMeta<'a> {
     ///
     /// If a list of interfaces already was provided prior to calling this method, they will be
     /// overwritten.
-    pub fn interfaces(mut self, interfaces: &[Type<'a>]) -> ObjectMeta<'a> {
+    pub fn interfaces(mut self, interfaces: &[Type<'a>]) -> ObjectMeta<'a, S> {
         self.interface_names = interfaces.iter()
-            .map(|t| t.innermost_name().to_owned()).collect();
+            .map(|t| Name::new(t.innermost_name())).collect();
         self
     }
 
     /// Wrap this object type in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Object(self)
     }
 }
 
-impl<'a> EnumMeta<'a> {
+impl<'a, S> EnumMeta<'a, S> where S: ScalarValue {
     /// Build a new enum type with the specified name and possible values
-    pub fn new<T: FromInputValue>(name: Cow<'a, str>, values: &[EnumValue]) -> EnumMeta<'a> {
+    pub fn new<T: FromInputValue<S>>(name: Cow<'a, str>, values: &[EnumValue]) -> EnumMeta<'a, S> {
         EnumMeta {
             name: name,
             description: None,
             values: values.to_vec(),
             try_parse_fn: Box::new(
-                |v: &InputValue| <T as FromInputValue>::from(v).is_some()),
+                |v: &InputValue<S>| <T as FromInputValue<S>>::from(v).is_some()),
         }
     }
 
     /// Set the description of the type
     ///
     /// If a description was provided prior to calling this method, it will be overwritten
-    pub fn description(mut self, description: &str) -> EnumMeta<'a> {
+    pub fn description(mut self, description: &str) -> EnumMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
 
     /// Wrap this enum type in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Enum(self)
     }
 }
 
-impl<'a> InterfaceMeta<'a> {
+impl<'a, S> InterfaceMeta<'a, S> where S: ScalarValue {
     /// Build a new interface type with the specified name and fields
-    pub fn new(name: Cow<'a, str>, fields: &[Field<'a>]) -> InterfaceMeta<'a> {
+    pub fn new(name: Cow<'a, str>, fields: &[Field<'a, S>]) -> InterfaceMeta<'a, S> {
         InterfaceMeta {
             name: name,
             description: None,
             fields: fields.to_vec(),
+            field_indices: HashMap::new(),
         }
     }
 
     /// Set the description of the type
     ///
     /// If a description was provided prior to calling this method, it will be overwritten.
-    pub fn description(mut self, description: &str) -> InterfaceMeta<'a> {
+    pub fn description(mut self, description: &str) -> InterfaceMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
 
     /// Wrap this interface type in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::Interface(self)
     }
 }
@@ -472,7 +501,7 @@ impl<'a> UnionMeta<'a> {
             name: name,
             description: None,
             of_type_names: of_types.iter()
-                .map(|t| t.innermost_name().to_owned()).collect(),
+                .map(|t| Name::new(t.innermost_name())).collect(),
         }
     }
 
@@ -485,42 +514,43 @@ impl<'a> UnionMeta<'a> {
     }
 
     /// Wrap this union type in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta<S>(self) -> MetaType<'a, S> {
         MetaType::Union(self)
     }
 }
 
-impl<'a> InputObjectMeta<'a> {
+impl<'a, S> InputObjectMeta<'a, S> where S: ScalarValue {
     /// Build a new input type with the specified name and input fields
-    pub fn new<T: FromInputValue>(name: Cow<'a, str>, input_fields: &[Argument<'a>]) -> InputObjectMeta<'a> {
+    pub fn new<T: FromInputValue<S>>(name: Cow<'a, str>, input_fields: &[Argument<'a, S>]) -> InputObjectMeta<'a, S> {
         InputObjectMeta {
             name: name,
             description: None,
             input_fields: input_fields.to_vec(),
+            field_indices: HashMap::new(),
             try_parse_fn: Box::new(
-                |v: &InputValue| <T as FromInputValue>::from(v).is_some()),
+                |v: &InputValue<S>| <T as FromInputValue<S>>::from(v).is_some()),
         }
     }
 
     /// Set the description of the type
     ///
     /// If a description was provided prior to calling this method, it will be overwritten.
-    pub fn description(mut self, description: &str) -> InputObjectMeta<'a> {
+    pub fn description(mut self, description: &str) -> InputObjectMeta<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
 
     /// Wrap this union type in a generic meta type
-    pub fn into_meta(self) -> MetaType<'a> {
+    pub fn into_meta(self) -> MetaType<'a, S> {
         MetaType::InputObject(self)
     }
 }
 
-impl<'a> Field<'a> {
+impl<'a, S> Field<'a, S> where S: ScalarValue {
     /// Set the description of the field
     ///
     /// This overwrites the description if any was previously set.
-    pub fn description(mut self, description: &str) -> Field<'a> {
+    pub fn description(mut self, description: &str) -> Field<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
@@ -528,7 +558,7 @@ impl<'a> Field<'a> {
     /// Add an argument to the field
     ///
     /// Arguments are unordered and can't contain duplicates by name.
-    pub fn argument(mut self, argument: Argument<'a>) -> Field<'a> {
+    pub fn argument(mut self, argument: Argument<'a, S>) -> Field<'a, S> {
         match self.arguments {
             None => { self.arguments = Some(vec![argument]); }
             Some(ref mut args) => { args.push(argument); }
@@ -540,17 +570,17 @@ impl<'a> Field<'a> {
     /// Set the deprecation reason
     ///
     /// This overwrites the deprecation reason if any was previously set.
-    pub fn deprecated(mut self, reason: &str) -> Field<'a> {
+    pub fn deprecated(mut self, reason: &str) -> Field<'a, S> {
         self.deprecation_reason = Some(reason.to_owned());
         self
     }
 }
 
-impl<'a> Argument<'a> {
+impl<'a, S> Argument<'a, S> where S: ScalarValue {
     #[doc(hidden)]
-    pub fn new(name: &str, arg_type: Type<'a>) -> Argument<'a> {
+    pub fn new(name: &str, arg_type: Type<'a>) -> Argument<'a, S> {
         Argument {
-            name: name.to_owned(),
+            name: Name::new(name),
             description: None,
             arg_type: arg_type,
             default_value: None
@@ -560,7 +590,7 @@ impl<'a> Argument<'a> {
     /// Set the description of the argument
     ///
     /// This overwrites the description if any was previously set.
-    pub fn description(mut self, description: &str) -> Argument<'a> {
+    pub fn description(mut self, description: &str) -> Argument<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
@@ -568,7 +598,7 @@ impl<'a> Argument<'a> {
     /// Set the default value of the argument
     ///
     /// This overwrites the description if any was previously set.
-    pub fn default_value(mut self, default_value: InputValue) -> Argument<'a> {
+    pub fn default_value(mut self, default_value: InputValue<S>) -> Argument<'a, S> {
         self.default_value = Some(default_value);
         self
     }
@@ -578,7 +608,7 @@ impl EnumValue {
     /// Construct a new enum value with the provided name
     pub fn new(name: &str) -> EnumValue {
         EnumValue {
-            name: name.to_owned(),
+            name: Name::new(name),
             description: None,
             deprecation_reason: None,
         }
@@ -601,7 +631,7 @@ impl EnumValue {
     }
 }
 
-impl<'a> fmt::Debug for ScalarMeta<'a> {
+impl<'a, S> fmt::Debug for ScalarMeta<'a, S> where S: fmt::Debug {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("ScalarMeta")
             .field("name", &self.name)
@@ -610,7 +640,7 @@ impl<'a> fmt::Debug for ScalarMeta<'a> {
     }
 }
 
-impl<'a> fmt::Debug for EnumMeta<'a> {
+impl<'a, S> fmt::Debug for EnumMeta<'a, S> where S: fmt::Debug {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("EnumMeta")
             .field("name", &self.name)
@@ -620,7 +650,7 @@ impl<'a> fmt::Debug for EnumMeta<'a> {
     }
 }
 
-impl<'a> fmt::Debug for InputObjectMeta<'a> {
+impl<'a, S> fmt::Debug for InputObjectMeta<'a, S> where S: fmt::Debug {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("InputObjectMeta")
             .field("name", &self.name)
@@ -629,3 +659,22 @@ impl<'a> fmt::Debug for InputObjectMeta<'a> {
             .finish()
     }
 }
+
+fn index<T, F>(fields: &[T], name: F) -> HashMap<Name, usize>
+    where F: Fn(&T) -> &Name
+{
+    fields.iter().enumerate().map(|(i, f)| (name(f).clone(), i)).collect()
+}
+
+// Types that aren't part of a schema yet, or had fields added since, aren't
+// indexed and are searched instead
+fn lookup<'f, T, F>(fields: &'f [T], indices: &HashMap<Name, usize>, name: &str, field_name: F)
+    -> Option<&'f T>
+    where F: Fn(&T) -> &Name
+{
+    if indices.len() == fields.len() {
+        indices.get(name).map(|&i| &fields[i])
+    } else {
+        fields.iter().find(|f| field_name(f) == name)
+    }
+}
@@ -1,3 +1,5 @@
 pub mod model;
 pub mod schema;
 pub mod meta;
+mod name;
+mod printer;
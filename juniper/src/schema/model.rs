@@ -1,49 +1,119 @@
 use std::collections::HashMap;
 use std::fmt;
+use std::mem;
+use std::sync::{Arc, RwLock};
 
+use cache::{DocumentCache, IntrospectionCache};
 use types::base::{GraphQLType};
-use executor::{Registry, Context};
+use value::{ScalarValue, DefaultScalarValue};
+use executor::{Registry, Context, ExecutionError};
+use instrumentation::Instrumentation;
+use options::ExecutionOptions;
 use ast::Type;
-use schema::meta::{MetaType, ObjectMeta, PlaceholderMeta, UnionMeta, InterfaceMeta, Argument};
+use schema::meta::{Name, MetaType, ObjectMeta, PlaceholderMeta, UnionMeta, InterfaceMeta, Argument};
 
 /// Root query node of a schema
 ///
 /// This brings the mutation and query types together, and provides the
 /// predefined metadata fields.
-pub struct RootNode<'a, QueryT: GraphQLType, MutationT: GraphQLType> {
+///
+/// All parts of a root node are reference counted, so cloning it is cheap and
+/// clones share the schema and its caches. Multi-threaded servers can hand a
+/// clone to every worker. A root node is `Send` and `Sync` if its query and
+/// mutation types and their type info are.
+pub struct RootNode<'a, QueryT: GraphQLType<S>, MutationT: GraphQLType<S>, S = DefaultScalarValue>
+    where S: ScalarValue
+{
+    #[doc(hidden)]
+    pub query_type: Arc<QueryT>,
+    #[doc(hidden)]
+    pub query_info: Arc<QueryT::TypeInfo>,
+    #[doc(hidden)]
+    pub mutation_type: Arc<MutationT>,
     #[doc(hidden)]
-    pub query_type: QueryT,
+    pub mutation_info: Arc<MutationT::TypeInfo>,
     #[doc(hidden)]
-    pub query_info: QueryT::TypeInfo,
+    pub schema: Arc<SchemaType<'a, S>>,
     #[doc(hidden)]
-    pub mutation_type: MutationT,
+    pub document_cache: Option<Arc<DocumentCache<S>>>,
     #[doc(hidden)]
-    pub mutation_info: MutationT::TypeInfo,
+    pub introspection_cache: Option<Arc<IntrospectionCache<S>>>,
     #[doc(hidden)]
-    pub schema: SchemaType<'a>,
+    pub options: ExecutionOptions,
+    #[doc(hidden)]
+    pub error_mapper: Option<Arc<ErrorMapper<S>>>,
+    #[doc(hidden)]
+    pub instruments: Vec<Arc<Instrumentation<S>>>,
+}
+
+#[doc(hidden)]
+pub type ErrorMapper<S> = Fn(ExecutionError<S>) -> ExecutionError<S> + Send + Sync;
+
+/// A root node that can be replaced while requests are executed with it
+///
+/// Servers that rebuild their schema at runtime, e.g. when the configuration
+/// it's derived from changes, can hand one of these to the integrations
+/// instead of a root node, and swap in the new root node once it's built.
+/// Every request executes with the root node that was current when it
+/// started, so requests in flight aren't affected by a swap:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// use juniper::{EmptyMutation, RootNode, SwappableRootNode, Variables};
+///
+/// struct Query {
+///     greeting: String,
+/// }
+///
+/// graphql_object!(Query: () |&self| {
+///     field greeting() -> &str { &self.greeting }
+/// });
+///
+/// # fn main() {
+/// let root_node = SwappableRootNode::new(RootNode::new(
+///     Query { greeting: "Hello".to_owned() },
+///     EmptyMutation::<()>::new()));
+///
+/// let in_flight = root_node.load();
+/// root_node.swap(RootNode::new(Query { greeting: "Hi".to_owned() }, EmptyMutation::<()>::new()));
+///
+/// let greeting = |root_node: &RootNode<Query, EmptyMutation<()>>| {
+///     let (value, _) = juniper::execute("{ greeting }", None, root_node, &Variables::new(), &()).unwrap();
+///     value.get("greeting").and_then(|greeting| greeting.as_string_value()).unwrap().to_owned()
+/// };
+/// assert_eq!(greeting(&in_flight), "Hello");
+/// assert_eq!(greeting(&root_node.load()), "Hi");
+/// # }
+/// ```
+pub struct SwappableRootNode<'a, QueryT, MutationT, S = DefaultScalarValue>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    current: RwLock<Arc<RootNode<'a, QueryT, MutationT, S>>>,
 }
 
 /// Metadata for a schema
-pub struct SchemaType<'a> {
-    types: HashMap<String, MetaType<'a>>,
+pub struct SchemaType<'a, S = DefaultScalarValue> {
+    types: HashMap<Name, MetaType<'a, S>>,
     query_type_name: String,
     mutation_type_name: Option<String>,
-    directives: HashMap<String, DirectiveType<'a>>,
+    directives: HashMap<String, DirectiveType<'a, S>>,
 }
 
-impl<'a> Context for SchemaType<'a> {}
+impl<'a, S> Context for SchemaType<'a, S> {}
 
-pub enum TypeType<'a> {
-    Concrete(&'a MetaType<'a>),
-    NonNull(Box<TypeType<'a>>),
-    List(Box<TypeType<'a>>),
+pub enum TypeType<'a, S: 'a = DefaultScalarValue> {
+    Concrete(&'a MetaType<'a, S>),
+    NonNull(Box<TypeType<'a, S>>),
+    List(Box<TypeType<'a, S>>),
 }
 
-pub struct DirectiveType<'a> {
+pub struct DirectiveType<'a, S = DefaultScalarValue> {
     pub name: String,
     pub description: Option<String>,
     pub locations: Vec<DirectiveLocation>,
-    pub arguments: Vec<Argument<'a>>,
+    pub arguments: Vec<Argument<'a, S>>,
 }
 
 #[derive(Clone, PartialEq, Eq, Debug)]
@@ -56,41 +126,204 @@ pub enum DirectiveLocation {
     InlineFragment,
 }
 
-impl<'a, QueryT, MutationT> RootNode<'a, QueryT, MutationT>
-    where QueryT: GraphQLType<TypeInfo=()>,
-          MutationT: GraphQLType<TypeInfo=()>,
+impl<'a, QueryT, MutationT, S> RootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S, TypeInfo=()>,
+          MutationT: GraphQLType<S, TypeInfo=()>,
+          S: ScalarValue,
 {
     /// Construct a new root node from query and mutation nodes
     ///
     /// If the schema should not support mutations, use the
     /// `new` constructor instead.
-    pub fn new(query_obj: QueryT, mutation_obj: MutationT) -> RootNode<'a, QueryT, MutationT> {
+    pub fn new(query_obj: QueryT, mutation_obj: MutationT) -> RootNode<'a, QueryT, MutationT, S> {
         RootNode::new_with_info(query_obj, mutation_obj, (), ())
     }
 }
 
-impl<'a, QueryT, MutationT> RootNode<'a, QueryT, MutationT>
-    where QueryT: GraphQLType,
-          MutationT: GraphQLType,
+impl<'a, QueryT, MutationT, S> RootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
 {
     /// Construct a new root node from query and mutation nodes,
     /// while also providing type info objects for the query and
     /// mutation types.
-     pub fn new_with_info(query_obj: QueryT, mutation_obj: MutationT, query_info: QueryT::TypeInfo, mutation_info: MutationT::TypeInfo) -> RootNode<'a, QueryT, MutationT> {
+     pub fn new_with_info(query_obj: QueryT, mutation_obj: MutationT, query_info: QueryT::TypeInfo, mutation_info: MutationT::TypeInfo) -> RootNode<'a, QueryT, MutationT, S> {
+        RootNode {
+            query_type: Arc::new(query_obj),
+            mutation_type: Arc::new(mutation_obj),
+            schema: Arc::new(SchemaType::new::<QueryT, MutationT>(&query_info, &mutation_info)),
+            query_info: Arc::new(query_info),
+            mutation_info: Arc::new(mutation_info),
+            document_cache: None,
+            introspection_cache: None,
+            options: ExecutionOptions::default(),
+            error_mapper: None,
+            instruments: Vec::new(),
+        }
+    }
+
+    /// Cache the documents executed against this schema, see `DocumentCache`
+    pub fn with_document_cache(mut self, cache: DocumentCache<S>) -> RootNode<'a, QueryT, MutationT, S> {
+        self.document_cache = Some(Arc::new(cache));
+        self
+    }
+
+    /// Remember the results of introspection queries
+    ///
+    /// Since they only depend on the schema, GraphiQL reloads and tools
+    /// polling the schema are then answered without resolving every type
+    /// again. Only queries that select nothing but `__schema`, `__type` and
+    /// `__typename`, and don't have variables, are cached.
+    pub fn with_introspection_cache(mut self) -> RootNode<'a, QueryT, MutationT, S> {
+        self.introspection_cache = Some(Arc::new(IntrospectionCache::new()));
+        self
+    }
+
+    /// Abort queries whose response grows beyond roughly the given number of
+    /// bytes
+    ///
+    /// This protects servers from queries that expand small inputs into
+    /// enormous responses, e.g. by nesting lists. The size of every resolved
+    /// value is added up as the query executes, and once it exceeds the
+    /// budget the rest of the query is skipped. The response then has no
+    /// data, and an error with a `RESPONSE_TOO_LARGE` code in its extensions.
+    pub fn with_response_budget(mut self, bytes: usize) -> RootNode<'a, QueryT, MutationT, S> {
+        self.options = self.options.with_response_budget(bytes);
+        self
+    }
+
+    /// Execute every request with the given options, see `ExecutionOptions`
+    ///
+    /// This replaces the options set before, including the response budget.
+    pub fn with_execution_options(mut self, options: ExecutionOptions) -> RootNode<'a, QueryT, MutationT, S> {
+        self.options = options;
+        self
+    }
+
+    /// Pass every error of a query through a function before it ends up in
+    /// the response
+    ///
+    /// Production servers can use this to keep internal details, like SQL
+    /// statements or file paths, away from clients, and replace them with a
+    /// generic message and an ID to find the original error in the logs:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate juniper;
+    /// use juniper::{EmptyMutation, ExecutionError, FieldResult, RootNode, Value, Variables};
+    ///
+    /// struct Query;
+    ///
+    /// graphql_object!(Query: () |&self| {
+    ///     field users() -> FieldResult<Vec<String>> {
+    ///         Err("relation \"users\" does not exist")?
+    ///     }
+    /// });
+    ///
+    /// # fn main() {
+    /// let root_node = RootNode::new(Query, EmptyMutation::<()>::new())
+    ///     .with_error_mapper(|err: ExecutionError| {
+    ///         let id = 42; // e.g. a random ID the original error is logged with
+    ///         err.with_message("Internal server error")
+    ///             .with_extension("errorId", Value::int(id))
+    ///     });
+    ///
+    /// let (_, errors) = juniper::execute("{ users }", None, &root_node, &Variables::new(), &()).unwrap();
+    /// assert_eq!(errors[0].message(), "Internal server error");
+    /// # }
+    /// ```
+    ///
+    /// Only errors that occur while the query is executed are mapped, parse
+    /// and validation errors are about the query itself.
+    pub fn with_error_mapper<F>(mut self, f: F) -> RootNode<'a, QueryT, MutationT, S>
+        where F: Fn(ExecutionError<S>) -> ExecutionError<S> + Send + Sync + 'static,
+    {
+        self.error_mapper = Some(Arc::new(f));
+        self
+    }
+
+    /// Call the hooks of an `Instrumentation` while requests are executed,
+    /// see `instrumentation`
+    ///
+    /// Several instruments can be added, and their hooks are called in the
+    /// order they were added in.
+    pub fn with_instrumentation<I>(mut self, instrumentation: I) -> RootNode<'a, QueryT, MutationT, S>
+        where I: Instrumentation<S> + 'static,
+    {
+        self.instruments.push(Arc::new(instrumentation));
+        self
+    }
+
+    /// The document cache of this schema, if it has one
+    pub fn document_cache(&self) -> Option<&DocumentCache<S>> {
+        self.document_cache.as_ref().map(|cache| &**cache)
+    }
+}
+
+impl<'a, QueryT, MutationT, S> Clone for RootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    fn clone(&self) -> Self {
         RootNode {
-            query_type: query_obj,
-            mutation_type: mutation_obj,
-            schema: SchemaType::new::<QueryT, MutationT>(&query_info, &mutation_info),
-            query_info: query_info,
-            mutation_info: mutation_info
+            query_type: self.query_type.clone(),
+            query_info: self.query_info.clone(),
+            mutation_type: self.mutation_type.clone(),
+            mutation_info: self.mutation_info.clone(),
+            schema: self.schema.clone(),
+            document_cache: self.document_cache.clone(),
+            introspection_cache: self.introspection_cache.clone(),
+            options: self.options.clone(),
+            error_mapper: self.error_mapper.clone(),
+            instruments: self.instruments.clone(),
         }
     }
 }
 
-impl<'a> SchemaType<'a> {
-    pub fn new<QueryT, MutationT>(query_info: &QueryT::TypeInfo, mutation_info: &MutationT::TypeInfo) -> SchemaType<'a>
-        where QueryT: GraphQLType,
-              MutationT: GraphQLType,
+impl<'a, QueryT, MutationT, S> SwappableRootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    /// Start out with the given root node
+    pub fn new(root_node: RootNode<'a, QueryT, MutationT, S>) -> SwappableRootNode<'a, QueryT, MutationT, S> {
+        SwappableRootNode {
+            current: RwLock::new(Arc::new(root_node)),
+        }
+    }
+
+    /// The current root node, to execute a request with
+    pub fn load(&self) -> Arc<RootNode<'a, QueryT, MutationT, S>> {
+        // Only ever replaced as a whole, so a panic elsewhere can't leave it
+        // half-updated
+        self.current.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// Replace the root node, returning the previous one
+    ///
+    /// Requests that already loaded the previous root node finish executing
+    /// with it.
+    pub fn swap(&self, root_node: RootNode<'a, QueryT, MutationT, S>) -> Arc<RootNode<'a, QueryT, MutationT, S>> {
+        let mut current = self.current.write().unwrap_or_else(|err| err.into_inner());
+        mem::replace(&mut *current, Arc::new(root_node))
+    }
+}
+
+impl<'a, QueryT, MutationT, S> From<RootNode<'a, QueryT, MutationT, S>> for SwappableRootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    fn from(root_node: RootNode<'a, QueryT, MutationT, S>) -> SwappableRootNode<'a, QueryT, MutationT, S> {
+        SwappableRootNode::new(root_node)
+    }
+}
+
+impl<'a, S> SchemaType<'a, S> where S: ScalarValue {
+    pub fn new<QueryT, MutationT>(query_info: &QueryT::TypeInfo, mutation_info: &MutationT::TypeInfo) -> SchemaType<'a, S>
+        where QueryT: GraphQLType<S>,
+              MutationT: GraphQLType<S>,
     {
         let mut directives = HashMap::new();
         let query_type_name: String;
@@ -100,7 +333,7 @@ impl<'a> SchemaType<'a> {
         query_type_name = registry.get_type_with_info::<QueryT>(query_info).innermost_name().to_owned();
         mutation_type_name = registry.get_type_with_info::<MutationT>(mutation_info).innermost_name().to_owned();
 
-        registry.get_type::<SchemaType>();
+        registry.get_type::<SchemaType<S>>();
         directives.insert(
             "skip".to_owned(),
             DirectiveType::new_skip(&mut registry));
@@ -109,8 +342,8 @@ impl<'a> SchemaType<'a> {
             DirectiveType::new_include(&mut registry));
 
         let mut meta_fields = vec![
-            registry.field::<SchemaType>("__schema"),
-            registry.field::<TypeType>("__type")
+            registry.field::<SchemaType<S>>("__schema"),
+            registry.field::<TypeType<S>>("__type")
                 .argument(registry.arg::<String>("name")),
         ];
 
@@ -126,44 +359,46 @@ impl<'a> SchemaType<'a> {
             panic!("Root type not found");
         }
 
-        for meta_type in registry.types.values() {
+        for meta_type in registry.types.values_mut() {
             if let MetaType::Placeholder(PlaceholderMeta { ref of_type }) = *meta_type {
                 panic!("Type {:?} is still a placeholder type", of_type);
             }
+
+            meta_type.index_fields();
         }
 
         SchemaType {
-            types: registry.types,
+            types: registry.types.into_iter().map(|(name, t)| (Name::new(&name), t)).collect(),
             query_type_name: query_type_name,
             mutation_type_name: if &mutation_type_name != "_EmptyMutation" { Some(mutation_type_name) } else { None },
             directives: directives,
         }
     }
 
-    pub fn add_directive(&mut self, directive: DirectiveType<'a>) {
+    pub fn add_directive(&mut self, directive: DirectiveType<'a, S>) {
         self.directives.insert(directive.name.clone(), directive);
     }
 
-    pub fn type_by_name(&self, name: &str) -> Option<TypeType> {
+    pub fn type_by_name(&self, name: &str) -> Option<TypeType<S>> {
         self.types.get(name).map(|t| TypeType::Concrete(t))
     }
 
-    pub fn concrete_type_by_name(&self, name: &str) -> Option<&MetaType> {
+    pub fn concrete_type_by_name(&self, name: &str) -> Option<&MetaType<S>> {
         self.types.get(name)
     }
 
-    pub fn query_type(&self) -> TypeType {
+    pub fn query_type(&self) -> TypeType<S> {
         TypeType::Concrete(
-            self.types.get(&self.query_type_name)
+            self.types.get(self.query_type_name.as_str())
                 .expect("Query type does not exist in schema"))
     }
 
-    pub fn concrete_query_type(&self) -> &MetaType {
-        self.types.get(&self.query_type_name)
+    pub fn concrete_query_type(&self) -> &MetaType<S> {
+        self.types.get(self.query_type_name.as_str())
             .expect("Query type does not exist in schema")
     }
 
-    pub fn mutation_type(&self) -> Option<TypeType> {
+    pub fn mutation_type(&self) -> Option<TypeType<S>> {
         if let Some(ref mutation_type_name) = self.mutation_type_name {
             Some(self.type_by_name(mutation_type_name)
                 .expect("Mutation type does not exist in schema"))
@@ -173,21 +408,21 @@ impl<'a> SchemaType<'a> {
         }
     }
 
-    pub fn concrete_mutation_type(&self) -> Option<&MetaType> {
+    pub fn concrete_mutation_type(&self) -> Option<&MetaType<S>> {
         self.mutation_type_name.as_ref().map(|name|
             self.concrete_type_by_name(name)
                 .expect("Mutation type does not exist in schema"))
     }
 
-    pub fn type_list(&self) -> Vec<TypeType> {
+    pub fn type_list(&self) -> Vec<TypeType<S>> {
         self.types.values().map(|t| TypeType::Concrete(t)).collect()
     }
 
-    pub fn concrete_type_list(&self) -> Vec<&MetaType> {
+    pub fn concrete_type_list(&self) -> Vec<&MetaType<S>> {
         self.types.values().collect()
     }
 
-    pub fn make_type(&self, t: &Type) -> TypeType {
+    pub fn make_type(&self, t: &Type) -> TypeType<S> {
         match *t {
             Type::NonNullNamed(ref n) =>
                 TypeType::NonNull(Box::new(
@@ -201,16 +436,16 @@ impl<'a> SchemaType<'a> {
         }
     }
 
-    pub fn directive_list(&self) -> Vec<&DirectiveType> {
+    pub fn directive_list(&self) -> Vec<&DirectiveType<S>> {
         self.directives.values().collect()
     }
 
-    pub fn directive_by_name(&self, name: &str) -> Option<&DirectiveType> {
+    pub fn directive_by_name(&self, name: &str) -> Option<&DirectiveType<S>> {
         self.directives.get(name)
     }
 
-    pub fn type_overlap(&self, t1: &MetaType, t2: &MetaType) -> bool {
-        if (t1 as *const MetaType) == (t2 as *const MetaType) {
+    pub fn type_overlap(&self, t1: &MetaType<S>, t2: &MetaType<S>) -> bool {
+        if (t1 as *const MetaType<S>) == (t2 as *const MetaType<S>) {
             return true;
         }
 
@@ -222,7 +457,7 @@ impl<'a> SchemaType<'a> {
         }
     }
 
-    pub fn possible_types(&self, t: &MetaType) -> Vec<&MetaType> {
+    pub fn possible_types(&self, t: &MetaType<S>) -> Vec<&MetaType<S>> {
         match *t {
             MetaType::Union(UnionMeta { ref of_type_names, .. }) =>
                 of_type_names
@@ -234,7 +469,7 @@ impl<'a> SchemaType<'a> {
                     .into_iter()
                     .filter(|t| match **t {
                         MetaType::Object(ObjectMeta { ref interface_names, .. }) =>
-                            interface_names.iter().any(|iname| iname == name),
+                            interface_names.iter().any(|iname| iname == &**name),
                         _ => false
                     })
                     .collect(),
@@ -242,10 +477,10 @@ impl<'a> SchemaType<'a> {
         }
     }
 
-    pub fn is_possible_type(&self, abstract_type: &MetaType, possible_type: &MetaType) -> bool {
+    pub fn is_possible_type(&self, abstract_type: &MetaType<S>, possible_type: &MetaType<S>) -> bool {
         self.possible_types(abstract_type)
             .into_iter()
-            .any(|t| (t as *const MetaType) == (possible_type as *const MetaType))
+            .any(|t| (t as *const MetaType<S>) == (possible_type as *const MetaType<S>))
     }
 
     pub fn is_subtype<'b>(&self, sub_type: &Type<'b>, super_type: &Type<'b>) -> bool {
@@ -283,8 +518,8 @@ impl<'a> SchemaType<'a> {
     }
 }
 
-impl<'a> TypeType<'a> {
-    pub fn to_concrete(&self) -> Option<&'a MetaType> {
+impl<'a, S> TypeType<'a, S> {
+    pub fn to_concrete(&self) -> Option<&'a MetaType<S>> {
         match *self {
             TypeType::Concrete(t) => Some(t),
             _ => None
@@ -292,8 +527,8 @@ impl<'a> TypeType<'a> {
     }
 }
 
-impl<'a> DirectiveType<'a> {
-    pub fn new(name: &str, locations: &[DirectiveLocation], arguments: &[Argument<'a>]) -> DirectiveType<'a> {
+impl<'a, S> DirectiveType<'a, S> where S: ScalarValue {
+    pub fn new(name: &str, locations: &[DirectiveLocation], arguments: &[Argument<'a, S>]) -> DirectiveType<'a, S> {
         DirectiveType {
             name: name.to_owned(),
             description: None,
@@ -302,7 +537,7 @@ impl<'a> DirectiveType<'a> {
         }
     }
 
-    fn new_skip(registry: &mut Registry<'a>) -> DirectiveType<'a> {
+    fn new_skip(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S> {
         Self::new(
             "skip",
             &[
@@ -315,7 +550,7 @@ impl<'a> DirectiveType<'a> {
             ])
     }
 
-    fn new_include(registry: &mut Registry<'a>) -> DirectiveType<'a> {
+    fn new_include(registry: &mut Registry<'a, S>) -> DirectiveType<'a, S> {
         Self::new(
             "include",
             &[
@@ -328,7 +563,7 @@ impl<'a> DirectiveType<'a> {
             ])
     }
 
-    pub fn description(mut self, description: &str) -> DirectiveType<'a> {
+    pub fn description(mut self, description: &str) -> DirectiveType<'a, S> {
         self.description = Some(description.to_owned());
         self
     }
@@ -347,7 +582,7 @@ impl fmt::Display for DirectiveLocation {
     }
 }
 
-impl<'a> fmt::Display for TypeType<'a> {
+impl<'a, S> fmt::Display for TypeType<'a, S> where S: ScalarValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             TypeType::Concrete(t) => f.write_str(t.name().unwrap()),
@@ -0,0 +1,152 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+/// An interned name of a type, field, argument or enum value
+///
+/// Every distinct name is stored once, and all `Name`s with the same text
+/// point to it, so cloning and comparing them is as cheap as for a pointer.
+/// Names are never freed, which is why only names defined by the schema are
+/// interned, and never text from queries.
+#[derive(Clone)]
+pub struct Name(Arc<str>);
+
+static INTERNED: Mutex<Option<HashSet<Arc<str>>>> = Mutex::new(None);
+
+impl Name {
+    /// Intern a name
+    pub fn new(name: &str) -> Name {
+        let mut interned = INTERNED.lock().unwrap_or_else(|err| err.into_inner());
+        let interned = interned.get_or_insert_with(HashSet::new);
+
+        if let Some(name) = interned.get(name) {
+            return Name(name.clone());
+        }
+
+        let name: Arc<str> = Arc::from(name);
+        interned.insert(name.clone());
+        Name(name)
+    }
+
+    /// The text of the name
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for Name {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+// Lets maps keyed by names be queried with a `&str`. This is sound since equal
+// names have the same text, and `Hash` only looks at the text.
+impl Borrow<str> for Name {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq for Name {
+    fn eq(&self, other: &Name) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Name {}
+
+impl Hash for Name {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl PartialOrd for Name {
+    fn partial_cmp(&self, other: &Name) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Name {
+    fn cmp(&self, other: &Name) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl PartialEq<str> for Name {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for Name {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Name {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl<'a> From<&'a str> for Name {
+    fn from(name: &'a str) -> Name {
+        Name::new(name)
+    }
+}
+
+impl From<String> for Name {
+    fn from(name: String) -> Name {
+        Name::new(&name)
+    }
+}
+
+impl fmt::Display for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Debug for Name {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&*self.0, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::Name;
+
+    #[test]
+    fn interns_names() {
+        let a = Name::new("droid");
+        let b = Name::from("droid".to_owned());
+
+        assert_eq!(a, b);
+        assert!(a.as_ptr() == b.as_ptr());
+        assert!(Name::new("human") != a);
+        assert_eq!(a, "droid");
+        assert_eq!(format!("{} {:?}", a, a), "droid \"droid\"");
+
+        let mut map = HashMap::new();
+        map.insert(a, 1);
+        assert_eq!(map.get("droid"), Some(&1));
+    }
+}
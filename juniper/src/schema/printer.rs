@@ -0,0 +1,275 @@
+use std::fmt::Write;
+
+use ast::{Type, InputValue};
+use value::ScalarValue;
+use types::base::GraphQLType;
+use schema::model::{RootNode, SchemaType, DirectiveType, DirectiveLocation};
+use schema::meta::{MetaType, Field, Argument, EnumValue};
+
+const BUILTIN_SCALARS: &'static [&'static str] = &["Int", "Float", "String", "Boolean", "ID"];
+const BUILTIN_DIRECTIVES: &'static [&'static str] = &["skip", "include"];
+
+impl<'a, QueryT, MutationT, S> RootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S>,
+          MutationT: GraphQLType<S>,
+          S: ScalarValue,
+{
+    /// Describe the schema in the GraphQL schema definition language
+    ///
+    /// See `SchemaType::as_schema_language`.
+    pub fn as_schema_language(&self) -> String {
+        self.schema.as_schema_language()
+    }
+}
+
+impl<'a, S> SchemaType<'a, S> where S: ScalarValue {
+    /// Describe the schema in the GraphQL schema definition language
+    ///
+    /// Built-in scalars and directives, and the introspection types and
+    /// fields, are left out. Types are sorted by name.
+    pub fn as_schema_language(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("schema {\n");
+        write_line(&mut out, 1, &format!("query: {}", self.concrete_query_type().name().unwrap_or("")));
+        if let Some(mutation_type) = self.concrete_mutation_type() {
+            write_line(&mut out, 1, &format!("mutation: {}", mutation_type.name().unwrap_or("")));
+        }
+        out.push_str("}\n");
+
+        let mut directives: Vec<_> = self.directive_list().into_iter()
+            .filter(|d| !BUILTIN_DIRECTIVES.contains(&&*d.name))
+            .collect();
+        directives.sort_by(|a, b| a.name.cmp(&b.name));
+
+        for directive in directives {
+            out.push('\n');
+            write_directive(&mut out, self, directive);
+        }
+
+        let mut types: Vec<_> = self.concrete_type_list().into_iter()
+            .filter(|t| match t.name() {
+                Some(name) => !name.starts_with("__") && name != "_EmptyMutation" && !BUILTIN_SCALARS.contains(&name),
+                None => false,
+            })
+            .collect();
+        types.sort_by(|a, b| a.name().cmp(&b.name()));
+
+        for meta_type in types {
+            out.push('\n');
+            write_type(&mut out, self, meta_type);
+        }
+
+        out
+    }
+}
+
+fn write_line(out: &mut String, indent: usize, line: &str) {
+    for _ in 0..indent {
+        out.push_str("  ");
+    }
+    out.push_str(line);
+    out.push('\n');
+}
+
+fn write_description(out: &mut String, indent: usize, description: Option<&String>) {
+    if let Some(description) = description {
+        write_line(out, indent, "\"\"\"");
+        for line in description.replace("\"\"\"", "\\\"\"\"").lines() {
+            write_line(out, indent, line);
+        }
+        write_line(out, indent, "\"\"\"");
+    }
+}
+
+fn write_type<S>(out: &mut String, schema: &SchemaType<S>, meta_type: &MetaType<S>) where S: ScalarValue {
+    write_description(out, 0, meta_type.description());
+
+    match *meta_type {
+        MetaType::Scalar(ref s) => write_line(out, 0, &format!("scalar {}", s.name)),
+        MetaType::Object(ref o) => {
+            let mut header = format!("type {}", o.name);
+            if !o.interface_names.is_empty() {
+                let _ = write!(header, " implements {}", o.interface_names.join(" & "));
+            }
+            write_fields(out, schema, &header, &o.fields);
+        },
+        MetaType::Interface(ref i) => write_fields(out, schema, &format!("interface {}", i.name), &i.fields),
+        MetaType::Union(ref u) =>
+            write_line(out, 0, &format!("union {} = {}", u.name, u.of_type_names.join(" | "))),
+        MetaType::Enum(ref e) => {
+            write_line(out, 0, &format!("enum {} {{", e.name));
+            for value in &e.values {
+                write_enum_value(out, value);
+            }
+            write_line(out, 0, "}");
+        },
+        MetaType::InputObject(ref i) => {
+            write_line(out, 0, &format!("input {} {{", i.name));
+            for field in &i.input_fields {
+                write_description(out, 1, field.description.as_ref());
+                write_line(out, 1, &argument(schema, field));
+            }
+            write_line(out, 0, "}");
+        },
+        MetaType::List(_) | MetaType::Nullable(_) | MetaType::Placeholder(_) => (),
+    }
+}
+
+fn write_fields<S>(out: &mut String, schema: &SchemaType<S>, header: &str, fields: &[Field<S>]) where S: ScalarValue {
+    write_line(out, 0, &format!("{} {{", header));
+
+    for field in fields.iter().filter(|f| !f.name.starts_with("__")) {
+        write_description(out, 1, field.description.as_ref());
+
+        let mut line = field.name.to_string();
+        if let Some(ref arguments) = field.arguments {
+            if !arguments.is_empty() {
+                let arguments: Vec<_> = arguments.iter().map(|arg| argument(schema, arg)).collect();
+                let _ = write!(line, "({})", arguments.join(", "));
+            }
+        }
+        let _ = write!(line, ": {}{}", field.field_type, deprecation(field.deprecation_reason.as_ref()));
+
+        write_line(out, 1, &line);
+    }
+
+    write_line(out, 0, "}");
+}
+
+fn write_enum_value(out: &mut String, value: &EnumValue) {
+    write_description(out, 1, value.description.as_ref());
+    write_line(out, 1, &format!("{}{}", value.name, deprecation(value.deprecation_reason.as_ref())));
+}
+
+fn write_directive<S>(out: &mut String, schema: &SchemaType<S>, directive: &DirectiveType<S>) where S: ScalarValue {
+    write_description(out, 0, directive.description.as_ref());
+
+    let mut line = format!("directive @{}", directive.name);
+    if !directive.arguments.is_empty() {
+        let arguments: Vec<_> = directive.arguments.iter().map(|arg| argument(schema, arg)).collect();
+        let _ = write!(line, "({})", arguments.join(", "));
+    }
+
+    let locations: Vec<_> = directive.locations.iter()
+        .map(|location| match *location {
+            DirectiveLocation::Query => "QUERY",
+            DirectiveLocation::Mutation => "MUTATION",
+            DirectiveLocation::Field => "FIELD",
+            DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+        })
+        .collect();
+    let _ = write!(line, " on {}", locations.join(" | "));
+
+    write_line(out, 0, &line);
+}
+
+fn argument<S>(schema: &SchemaType<S>, arg: &Argument<S>) -> String where S: ScalarValue {
+    let arg_type: &Type = &arg.arg_type;
+
+    match arg.default_value {
+        Some(ref default) => format!("{}: {} = {}", arg.name, arg_type, enum_default(schema, arg_type, default)),
+        None => format!("{}: {}", arg.name, arg_type),
+    }
+}
+
+// Enum defaults are stored as strings, which have to be printed as enum values
+fn enum_default<S>(schema: &SchemaType<S>, arg_type: &Type, default: &InputValue<S>) -> InputValue<S>
+    where S: ScalarValue
+{
+    let is_enum = match schema.concrete_type_by_name(arg_type.innermost_name()) {
+        Some(&MetaType::Enum(_)) => true,
+        _ => false,
+    };
+
+    match *default {
+        InputValue::String(ref s) if is_enum => InputValue::enum_value(s.as_str()),
+        InputValue::List(ref items) if is_enum => InputValue::list(
+            items.iter().map(|item| enum_default(schema, arg_type, &item.item)).collect()),
+        ref other => other.clone(),
+    }
+}
+
+fn deprecation(reason: Option<&String>) -> String {
+    match reason {
+        Some(reason) => format!(
+            " @deprecated(reason: \"{}\")",
+            reason.replace('\\', "\\\\").replace('"', "\\\"")),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use executor::Context;
+    use schema::model::RootNode;
+    use types::scalars::EmptyMutation;
+
+    struct Database;
+
+    impl Context for Database {}
+
+    enum Episode {
+        NewHope,
+        Holiday,
+    }
+
+    graphql_enum!(Episode {
+        Episode::NewHope => "NEW_HOPE" as "Released in 1977",
+        Episode::Holiday => "HOLIDAY" deprecated "Not a real episode",
+    });
+
+    struct Query;
+
+    graphql_object!(Query: Database |&self| {
+        description: "The root query"
+
+        field hero(episode = (Episode::NewHope): Episode, first: Option<i32>) -> Vec<String> {
+            vec![]
+        }
+
+        field deprecated "Use hero instead" villain() -> Option<String> {
+            None
+        }
+    });
+
+    #[test]
+    fn prints_schema_language() {
+        let schema = RootNode::new(Query, EmptyMutation::<Database>::new());
+
+        assert_eq!(schema.as_schema_language(), r#"schema {
+  query: Query
+}
+
+enum Episode {
+  """
+  Released in 1977
+  """
+  NEW_HOPE
+  HOLIDAY @deprecated(reason: "Not a real episode")
+}
+
+"""
+The root query
+"""
+type Query {
+  hero(episode: Episode = NEW_HOPE, first: Int): [String!]!
+  villain: String @deprecated(reason: "Use hero instead")
+}
+"#);
+    }
+
+    #[test]
+    fn prints_interfaces_and_implementations() {
+        let schema = RootNode::new(::tests::model::Database::new(), EmptyMutation::<::tests::model::Database>::new());
+        let sdl = schema.as_schema_language();
+
+        assert!(sdl.contains("\ninterface Character {\n"));
+        assert!(sdl.contains("\ntype Human implements Character {\n"));
+        assert!(sdl.contains("  friends: [Character!]!\n"));
+        assert!(!sdl.contains("__schema"));
+        assert!(!sdl.contains("scalar String"));
+    }
+}
@@ -1,13 +1,15 @@
 use types::base::{GraphQLType, Arguments, TypeKind};
 use executor::{Executor, Registry, ExecutionResult};
+use value::ScalarValue;
 
 use schema::meta::{MetaType, ObjectMeta, EnumMeta, InputObjectMeta, UnionMeta, InterfaceMeta,
                    Field, Argument, EnumValue};
 use schema::model::{RootNode, SchemaType, TypeType, DirectiveType, DirectiveLocation};
 
-impl<'a, CtxT, QueryT, MutationT> GraphQLType for RootNode<'a, QueryT, MutationT>
-    where QueryT: GraphQLType<Context=CtxT>,
-          MutationT: GraphQLType<Context=CtxT>
+impl<'a, CtxT, QueryT, MutationT, S> GraphQLType<S> for RootNode<'a, QueryT, MutationT, S>
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
 {
     type Context = CtxT;
     type TypeInfo = QueryT::TypeInfo;
@@ -16,49 +18,49 @@ impl<'a, CtxT, QueryT, MutationT> GraphQLType for RootNode<'a, QueryT, MutationT
         QueryT::name(info)
     }
 
-    fn meta<'r>(info: &QueryT::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(info: &QueryT::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         QueryT::meta(info, registry)
     }
 
-    fn resolve_field(&self, info: &QueryT::TypeInfo, field: &str, args: &Arguments, executor: &Executor<CtxT>) -> ExecutionResult {
+    fn resolve_field(&self, info: &QueryT::TypeInfo, field: &str, args: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
         match field {
-            "__schema" => executor.replaced_context(&self.schema).resolve(&(), &self.schema),
+            "__schema" => executor.replaced_context(&*self.schema).resolve(&(), &*self.schema),
             "__type" => {
                 let type_name: String = args.get("name").unwrap();
-                executor.replaced_context(&self.schema).resolve(&(), &self.schema.type_by_name(&type_name))
+                executor.replaced_context(&*self.schema).resolve(&(), &self.schema.type_by_name(&type_name))
             },
             _=> self.query_type.resolve_field(info, field, args, executor),
         }
     }
 }
 
-graphql_object!(<'a> SchemaType<'a>: SchemaType<'a> as "__Schema" |&self| {
-    field types() -> Vec<TypeType> {
+graphql_object!(<'a, S> SchemaType<'a, S>: SchemaType<'a, S> as "__Schema" where Scalar = S |&self| {
+    field types() -> Vec<TypeType<S>> {
         self.type_list()
             .into_iter()
             .filter(|t| t.to_concrete().map(|t| t.name() != Some("_EmptyMutation")).unwrap_or(false))
             .collect()
     }
 
-    field query_type() -> TypeType {
+    field query_type() -> TypeType<S> {
         self.query_type()
     }
 
-    field mutation_type() -> Option<TypeType> {
+    field mutation_type() -> Option<TypeType<S>> {
         self.mutation_type()
     }
 
     // Included for compatibility with the introspection query in GraphQL.js
-    field subscription_type() -> Option<TypeType> {
+    field subscription_type() -> Option<TypeType<S>> {
         None
     }
 
-    field directives() -> Vec<&DirectiveType> {
+    field directives() -> Vec<&DirectiveType<S>> {
         self.directive_list()
     }
 });
 
-graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
+graphql_object!(<'a, S> TypeType<'a, S>: SchemaType<'a, S> as "__Type" where Scalar = S |&self| {
     field name() -> Option<&str> {
         match *self {
             TypeType::Concrete(t) => t.name(),
@@ -81,7 +83,7 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
         }
     }
 
-    field fields(include_deprecated = false: bool) -> Option<Vec<&Field>> {
+    field fields(include_deprecated = false: bool) -> Option<Vec<&Field<S>>> {
         match *self {
             TypeType::Concrete(&MetaType::Interface(InterfaceMeta { ref fields, .. })) |
             TypeType::Concrete(&MetaType::Object(ObjectMeta { ref fields, .. })) =>
@@ -94,14 +96,14 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
         }
     }
 
-    field of_type() -> Option<&Box<TypeType>> {
+    field of_type() -> Option<&Box<TypeType<S>>> {
         match *self {
             TypeType::Concrete(_) => None,
             TypeType::List(ref l) | TypeType::NonNull(ref l) => Some(l),
         }
     }
 
-    field input_fields() -> Option<&Vec<Argument>> {
+    field input_fields() -> Option<&Vec<Argument<S>>> {
         match *self {
             TypeType::Concrete(&MetaType::InputObject(InputObjectMeta { ref input_fields, .. })) =>
                 Some(input_fields),
@@ -109,7 +111,7 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
         }
     }
 
-    field interfaces(&executor) -> Option<Vec<TypeType>> {
+    field interfaces(&executor) -> Option<Vec<TypeType<S>>> {
         match *self {
             TypeType::Concrete(&MetaType::Object(ObjectMeta { ref interface_names, .. })) => {
                 let schema = executor.context();
@@ -122,7 +124,7 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
         }
     }
 
-    field possible_types(&executor) -> Option<Vec<TypeType>> {
+    field possible_types(&executor) -> Option<Vec<TypeType<S>>> {
         let schema = executor.context();
         match *self {
             TypeType::Concrete(&MetaType::Union(UnionMeta { ref of_type_names, .. })) => {
@@ -136,7 +138,7 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
                     .iter()
                     .filter_map(|&ct|
                         if let MetaType::Object(ObjectMeta { ref name, ref interface_names, .. }) = *ct {
-                            if interface_names.contains(&iface_name.to_string()) {
+                            if interface_names.iter().any(|iname| iname == &**iface_name) {
                                 schema.type_by_name(name)
                             } else { None }
                         } else { None }
@@ -159,8 +161,8 @@ graphql_object!(<'a> TypeType<'a>: SchemaType<'a> as "__Type" |&self| {
     }
 });
 
-graphql_object!(<'a> Field<'a>: SchemaType<'a> as "__Field" |&self| {
-    field name() -> &String {
+graphql_object!(<'a, S> Field<'a, S>: SchemaType<'a, S> as "__Field" where Scalar = S |&self| {
+    field name() -> &str {
         &self.name
     }
 
@@ -168,11 +170,11 @@ graphql_object!(<'a> Field<'a>: SchemaType<'a> as "__Field" |&self| {
         &self.description
     }
 
-    field args() -> Vec<&Argument> {
+    field args() -> Vec<&Argument<S>> {
         self.arguments.as_ref().map_or_else(Vec::new, |v| v.iter().collect())
     }
 
-    field type(&executor) -> TypeType {
+    field type(&executor) -> TypeType<S> {
         executor.context().make_type(&self.field_type)
     }
 
@@ -185,8 +187,8 @@ graphql_object!(<'a> Field<'a>: SchemaType<'a> as "__Field" |&self| {
     }
 });
 
-graphql_object!(<'a> Argument<'a>: SchemaType<'a> as "__InputValue" |&self| {
-    field name() -> &String {
+graphql_object!(<'a, S> Argument<'a, S>: SchemaType<'a, S> as "__InputValue" where Scalar = S |&self| {
+    field name() -> &str {
         &self.name
     }
 
@@ -194,7 +196,7 @@ graphql_object!(<'a> Argument<'a>: SchemaType<'a> as "__InputValue" |&self| {
         &self.description
     }
 
-    field type(&executor) -> TypeType {
+    field type(&executor) -> TypeType<S> {
         executor.context().make_type(&self.arg_type)
     }
 
@@ -203,8 +205,8 @@ graphql_object!(<'a> Argument<'a>: SchemaType<'a> as "__InputValue" |&self| {
     }
 });
 
-graphql_object!(EnumValue: () as "__EnumValue" |&self| {
-    field name() -> &String {
+graphql_object!(<S> EnumValue: () as "__EnumValue" where Scalar = S |&self| {
+    field name() -> &str {
         &self.name
     }
 
@@ -233,8 +235,8 @@ graphql_enum!(TypeKind as "__TypeKind" {
 });
 
 
-graphql_object!(<'a> DirectiveType<'a>: SchemaType<'a> as "__Directive" |&self| {
-    field name() -> &String {
+graphql_object!(<'a, S> DirectiveType<'a, S>: SchemaType<'a, S> as "__Directive" where Scalar = S |&self| {
+    field name() -> &str {
         &self.name
     }
 
@@ -246,7 +248,7 @@ graphql_object!(<'a> DirectiveType<'a>: SchemaType<'a> as "__Directive" |&self|
         &self.locations
     }
 
-    field args() -> &Vec<Argument> {
+    field args() -> &Vec<Argument<S>> {
         &self.arguments
     }
 
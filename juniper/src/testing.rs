@@ -0,0 +1,182 @@
+//! Helpers for testing schemas
+//!
+//! Comparing the result of a query to a `Value` means constructing the whole
+//! value by hand. These helpers execute a query and compare its result to the
+//! JSON it should serialize to instead. They're enabled with the `testing`
+//! feature, e.g. as a dev-dependency:
+//!
+//! ```rust
+//! #[macro_use] extern crate juniper;
+//! use juniper::{EmptyMutation, RootNode};
+//!
+//! struct Query;
+//!
+//! graphql_object!(Query: () |&self| {
+//!     field hero() -> Vec<&str> { vec!["R2-D2", "Luke"] }
+//!     field villain() -> &str { "Vader" }
+//! });
+//!
+//! # fn main() {
+//! let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+//!
+//! assert_query_result!(schema, (), "{ hero villain }", r#"
+//!     { "villain": "Vader", "hero": ["R2-D2", "Luke"] }
+//! "#);
+//! assert_query_errors!(schema, (), "{ sidekick }", [
+//!     r#"Unknown field "sidekick" on type "Query""#,
+//! ]);
+//! # }
+//! ```
+//!
+//! Objects are equal regardless of the order of their fields, and errors are
+//! compared by their messages regardless of their order. Errors that prevent
+//! the query from executing, e.g. validation errors, are reported the same way
+//! as errors of fields.
+
+use serde_json;
+
+use executor::Variables;
+use schema::model::RootNode;
+use types::base::GraphQLType;
+use value::ScalarValue;
+
+/// The result of executing a query, as it would be serialized
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryResult {
+    /// The data of the query, or `null` if it couldn't be executed
+    pub data: serde_json::Value,
+    /// The messages of the errors of the query, in sorted order
+    pub errors: Vec<String>,
+}
+
+/// Execute a query without variables
+pub fn run_query<QueryT, MutationT, CtxT, S>(
+    schema: &RootNode<QueryT, MutationT, S>,
+    context: &CtxT,
+    query: &str,
+)
+    -> QueryResult
+    where QueryT: GraphQLType<S, Context=CtxT>,
+          MutationT: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    let (data, errors) = match ::execute(query, None, schema, &Variables::new(), context) {
+        Ok((value, errors)) => (to_json(&value), to_json(&errors)),
+        Err(err) => (serde_json::Value::Null, to_json(&err)),
+    };
+
+    let mut messages = match errors {
+        serde_json::Value::Array(errors) => errors.iter()
+            .map(|err| err["message"].as_str().unwrap_or_default().to_owned())
+            .collect(),
+        _ => vec![],
+    };
+    messages.sort();
+
+    QueryResult {
+        data: data,
+        errors: messages,
+    }
+}
+
+/// Panic unless the query succeeded with the given JSON as its data
+pub fn assert_result(result: &QueryResult, expected: &str) {
+    if !result.errors.is_empty() {
+        panic!("Expected no errors, found: {:?}", result.errors);
+    }
+
+    let expected = parse_expected(expected);
+    if result.data != expected {
+        panic!("Expected data:\n{}\nfound:\n{}", pretty(&expected), pretty(&result.data));
+    }
+}
+
+/// Panic unless the query failed with errors of the given messages
+pub fn assert_errors(result: &QueryResult, expected: &[&str]) {
+    let mut expected = expected.iter().map(|msg| (*msg).to_owned()).collect::<Vec<_>>();
+    expected.sort();
+
+    if result.errors != expected {
+        panic!("Expected errors:\n{:#?}\nfound:\n{:#?}", expected, result.errors);
+    }
+}
+
+fn to_json<T: ::serde::Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("Query results are serializable as JSON")
+}
+
+fn parse_expected(expected: &str) -> serde_json::Value {
+    serde_json::from_str(expected).unwrap_or_else(|err| panic!("Expected data is not valid JSON: {}", err))
+}
+
+fn pretty(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+/**
+Execute a query and check that it returns the given JSON without errors
+
+Takes the schema, the context, the query, and the JSON of the expected data,
+see the `testing` module.
+*/
+#[macro_export]
+macro_rules! assert_query_result {
+    ($schema:expr, $context:expr, $query:expr, $expected:expr) => {
+        $crate::testing::assert_result(
+            &$crate::testing::run_query(&$schema, &$context, $query),
+            $expected)
+    };
+}
+
+/**
+Execute a query and check that it returns errors with the given messages
+
+Takes the schema, the context, the query, and a list of the messages of the
+expected errors, see the `testing` module.
+*/
+#[macro_export]
+macro_rules! assert_query_errors {
+    ($schema:expr, $context:expr, $query:expr, [ $( $message:expr ),* $(,)* ]) => {
+        $crate::testing::assert_errors(
+            &$crate::testing::run_query(&$schema, &$context, $query),
+            &[ $( $message ),* ])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use schema::model::RootNode;
+    use tests::model::Database;
+    use types::scalars::EmptyMutation;
+
+    use super::run_query;
+
+    #[test]
+    fn compares_objects_regardless_of_field_order() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+        assert_query_result!(schema, Database::new(), "{ hero { id name } }", r#"
+            { "hero": { "name": "R2-D2", "id": "2001" } }
+        "#);
+    }
+
+    #[test]
+    #[should_panic(expected = "Expected data")]
+    fn fails_on_other_data() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+        assert_query_result!(schema, Database::new(), "{ hero { name } }", r#"{ "hero": { "name": "Luke" } }"#);
+    }
+
+    #[test]
+    fn reports_errors_that_prevent_execution() {
+        let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+        let result = run_query(&schema, &Database::new(), "{ hero { nope } }");
+        assert!(result.data.is_null());
+        assert_query_errors!(schema, Database::new(), "{ hero { nope } villain }", [
+            r#"Unknown field "villain" on type "Query""#,
+            r#"Unknown field "nope" on type "Character""#,
+        ]);
+    }
+}
@@ -1,7 +1,9 @@
+use std::sync::Arc;
+
 use ast::InputValue;
-use executor::Variables;
+use executor::{ExecutionError, ExecutionScratch, Variables};
 use value::Value;
-use schema::model::RootNode;
+use schema::model::{RootNode, SwappableRootNode};
 use types::scalars::EmptyMutation;
 use tests::model::Database;
 
@@ -331,8 +333,8 @@ fn test_query_inline_fragments_droid() {
         ::execute(doc, None, &schema, &Variables::new(), &database),
         Ok((Value::object(vec![
                 ("hero", Value::object(vec![
-                    ("__typename", Value::string("Droid")),
                     ("name", Value::string("R2-D2")),
+                    ("__typename", Value::string("Droid")),
                     ("primaryFunction", Value::string("Astromech")),
                 ].into_iter().collect())),
             ].into_iter().collect()),
@@ -356,8 +358,8 @@ fn test_query_inline_fragments_human() {
         ::execute(doc, None, &schema, &Variables::new(), &database),
         Ok((Value::object(vec![
                 ("hero", Value::object(vec![
-                    ("__typename", Value::string("Human")),
                     ("name", Value::string("Luke Skywalker")),
+                    ("__typename", Value::string("Human")),
                 ].into_iter().collect())),
             ].into_iter().collect()),
             vec![])));
@@ -383,3 +385,107 @@ fn test_object_typename() {
             ].into_iter().collect()),
             vec![])));
 }
+
+#[test]
+fn test_schema_shared_across_threads() {
+    let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+
+    let workers = (0..2).map(|_| {
+        let schema = schema.clone();
+        ::std::thread::spawn(move || {
+            ::execute("{ hero { name } }", None, &schema, &Variables::new(), &Database::new())
+                .map(|(value, _)| value)
+                .unwrap()
+        })
+    }).collect::<Vec<_>>();
+
+    for worker in workers {
+        assert_eq!(
+            worker.join().unwrap(),
+            Value::object(vec![
+                ("hero", Value::object(vec![
+                    ("name", Value::string("R2-D2")),
+                ].into_iter().collect())),
+            ].into_iter().collect()));
+    }
+}
+
+#[test]
+fn test_response_budget() {
+    let database = Database::new();
+    let query = "{ hero { friends { name friends { name friends { name } } } } }";
+
+    let unlimited = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+    let (_, errors) = ::execute(query, None, &unlimited, &Variables::new(), &database).unwrap();
+    assert!(errors.is_empty());
+
+    let limited = unlimited.clone().with_response_budget(1024);
+    let (value, errors) = ::execute(query, None, &limited, &Variables::new(), &database).unwrap();
+    assert_eq!(value, Value::null());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].error().message(), "Response exceeds the memory budget of 1024 bytes");
+    assert_eq!(
+        errors[0].error().extensions(),
+        &Value::object(vec![
+            ("code", Value::string("RESPONSE_TOO_LARGE")),
+            ("budget", Value::int(1024)),
+        ].into_iter().collect()));
+
+    let generous = unlimited.with_response_budget(1 << 20);
+    assert!(::execute(query, None, &generous, &Variables::new(), &database).unwrap().1.is_empty());
+}
+
+#[test]
+fn test_swappable_schema() {
+    let schema = SwappableRootNode::new(RootNode::new(Database::new(), EmptyMutation::<Database>::new()));
+    let database = Database::new();
+
+    let in_flight = schema.load();
+    let previous = schema.swap(
+        RootNode::new(Database::new(), EmptyMutation::<Database>::new()).with_response_budget(0));
+    assert!(Arc::ptr_eq(&in_flight, &previous));
+
+    let (_, errors) = ::execute("{ hero { name } }", None, &in_flight, &Variables::new(), &database).unwrap();
+    assert!(errors.is_empty());
+
+    let (value, errors) = ::execute("{ hero { name } }", None, &schema.load(), &Variables::new(), &database).unwrap();
+    assert_eq!(value, Value::null());
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn test_reused_execution_scratch() {
+    let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new());
+    let database = Database::new();
+    let mut scratch = ExecutionScratch::new();
+
+    let queries = [
+        "{ hero { name ...Friends } } fragment Friends on Character { friends { name } }",
+        "query Q($id: String = \"1000\") { human(id: $id) { name } }",
+        "{ hero { nope: name } }",
+    ];
+
+    for _ in 0..2 {
+        for query in &queries {
+            let expected = ::execute(query, None, &schema, &Variables::new(), &database);
+            let actual = ::execute_with_scratch(query, None, &schema, &Variables::new(), &database, &mut scratch);
+            assert_eq!(actual, expected);
+        }
+    }
+}
+
+#[test]
+fn test_error_mapper() {
+    let schema = RootNode::new(Database::new(), EmptyMutation::<Database>::new())
+        .with_response_budget(0)
+        .with_error_mapper(|err: ExecutionError| err.with_message("Something went wrong"));
+    let database = Database::new();
+
+    let (_, errors) = ::execute("{ hero { name } }", None, &schema, &Variables::new(), &database).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message(), "Something went wrong");
+    assert_eq!(errors[0].extensions().get("code"), Some(&Value::string("RESPONSE_TOO_LARGE")));
+
+    let err = ::execute("{ hero { nope } }", None, &schema, &Variables::new(), &database).unwrap_err();
+    assert!(match err { ::GraphQLError::ValidationError(_) => true, _ => false });
+}
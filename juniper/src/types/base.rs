@@ -1,13 +1,17 @@
+use std::cell::RefCell;
 use std::collections::HashMap;
-use std::collections::hash_map::Entry;
+use std::mem;
+use std::sync::Arc;
 
-use ast::{InputValue, Selection, Directive, FromInputValue};
+use serde::ser::{self, SerializeMap, SerializeSeq};
+
+use ast::{InputValue, Selection, Directive, Field, FromInputValue, Type};
 use executor::Variables;
-use value::Value;
+use value::{Value, Object, ScalarValue, DefaultScalarValue};
 
 use schema::meta::{Argument, MetaType};
-use executor::{Executor, Registry, ExecutionResult};
-use parser::Spanning;
+use executor::{Executor, Registry, ExecutionResult, FromContext, ListSink};
+use parser::{Spanning, SourcePosition};
 
 /// GraphQL type kind
 ///
@@ -53,8 +57,8 @@ pub enum TypeKind {
     /// ## List types
     ///
     /// Represent lists of other types. This library provides implementations
-    /// for vectors and slices, but other Rust types can be extended to serve
-    /// as GraphQL lists.
+    /// for vectors, slices, deques, and sets, but other Rust types can be
+    /// extended to serve as GraphQL lists.
     List,
 
     /// ## Non-null types
@@ -65,13 +69,22 @@ pub enum TypeKind {
 }
 
 /// Field argument container
-pub struct Arguments<'a> {
-    args: Option<HashMap<&'a str, InputValue>>,
+pub struct Arguments<'a, S = DefaultScalarValue> {
+    args: Option<HashMap<&'a str, InputValue<S>>>,
+    locations: HashMap<&'a str, SourcePosition>,
 }
 
-impl<'a> Arguments<'a> {
+impl<'a, S> Arguments<'a, S> where S: ScalarValue {
     #[doc(hidden)]
-    pub fn new(mut args: Option<HashMap<&'a str, InputValue>>, meta_args: &'a Option<Vec<Argument>>) -> Arguments<'a> {
+    pub fn new(args: Option<HashMap<&'a str, Spanning<InputValue<S>>>>, meta_args: &'a Option<Vec<Argument<S>>>) -> Arguments<'a, S> {
+        let mut locations = HashMap::new();
+        let mut args = args.map(|args| args.into_iter()
+            .map(|(k, v)| {
+                locations.insert(k, v.start);
+                (k, v.item)
+            })
+            .collect::<HashMap<_, _>>());
+
         if meta_args.is_some() && args.is_none() {
             args = Some(HashMap::new());
         }
@@ -81,30 +94,49 @@ impl<'a> Arguments<'a> {
                 if !args.contains_key(arg.name.as_str()) || args[arg.name.as_str()].is_null() {
                     if let Some(ref default_value) = arg.default_value {
                         args.insert(arg.name.as_str(), default_value.clone());
-                    } else {
-                        args.insert(arg.name.as_str(), InputValue::null());
+                        locations.remove(arg.name.as_str());
                     }
                 }
             }
         }
 
         Arguments {
-            args: args
+            args: args,
+            locations: locations,
         }
     }
 
+    /// The location of an argument's value in the query
+    ///
+    /// Returns `None` if the argument was omitted or replaced by its default
+    /// value.
+    pub fn location(&self, key: &str) -> Option<&SourcePosition> {
+        self.locations.get(key)
+    }
+
+    /// The value of an argument, with variables replaced by their values
+    ///
+    /// Returns the default value of the argument if it was omitted, or
+    /// `None` if it has none.
+    pub fn get_input_value(&self, key: &str) -> Option<&InputValue<S>> {
+        self.args.as_ref().and_then(|args| args.get(key))
+    }
+
     /// Get and convert an argument into the desired type.
     ///
     /// If the argument is found, or a default argument has been provided,
     /// the `InputValue` will be converted into the type `T`.
     ///
-    /// Returns `Some` if the argument is present _and_ type conversion
-    /// succeeeds.
-    pub fn get<T>(&self, key: &str) -> Option<T> where T: FromInputValue {
+    /// Arguments that were omitted are converted using
+    /// `FromInputValue::from_implicit_null`.
+    ///
+    /// Returns `Some` if the argument is present or nullable _and_ type
+    /// conversion succeeeds.
+    pub fn get<T>(&self, key: &str) -> Option<T> where T: FromInputValue<S> {
         match self.args {
             Some(ref args) => match args.get(key) {
-                Some(v) => Some(v.convert().unwrap()),
-                None => None,
+                Some(v) => v.convert(),
+                None => T::from_implicit_null(),
             },
             None => None,
         }
@@ -211,8 +243,16 @@ impl GraphQLType for User {
 }
 ```
 
+## Custom scalar values
+
+The trait is generic over the `ScalarValue` representation used by the
+schema, which defaults to `DefaultScalarValue`. Types that only make sense
+with a specific representation implement `GraphQLType<MyScalarValue>`, while
+types that work with any of them, like the built-in scalars and containers,
+are implemented for all `S: ScalarValue`.
+
 */
-pub trait GraphQLType: Sized {
+pub trait GraphQLType<S = DefaultScalarValue>: Sized where S: ScalarValue {
     /// The expected context type for this GraphQL type
     ///
     /// The context is threaded through query execution to all affected nodes,
@@ -235,7 +275,7 @@ pub trait GraphQLType: Sized {
     fn name(info: &Self::TypeInfo) -> Option<&str>;
 
     /// The meta type representing this GraphQL type.
-    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r>;
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S>;
 
     /// Resolve the value of a single field on this type.
     ///
@@ -246,8 +286,8 @@ pub trait GraphQLType: Sized {
     ///
     /// The default implementation panics.
     #[allow(unused_variables)]
-    fn resolve_field(&self, info: &Self::TypeInfo, field_name: &str, arguments: &Arguments, executor: &Executor<Self::Context>)
-        -> ExecutionResult
+    fn resolve_field(&self, info: &Self::TypeInfo, field_name: &str, arguments: &Arguments<S>, executor: &Executor<Self::Context, S>)
+        -> ExecutionResult<S>
     {
         panic!("resolve_field must be implemented by object types");
     }
@@ -259,7 +299,7 @@ pub trait GraphQLType: Sized {
     ///
     /// The default implementation panics.
     #[allow(unused_variables)]
-    fn resolve_into_type(&self, info: &Self::TypeInfo, type_name: &str, selection_set: Option<&[Selection]>, executor: &Executor<Self::Context>) -> ExecutionResult {
+    fn resolve_into_type(&self, info: &Self::TypeInfo, type_name: &str, selection_set: Option<&[Selection<S>]>, executor: &Executor<Self::Context, S>) -> ExecutionResult<S> {
         if Self::name(info).unwrap() == type_name {
             Ok(self.resolve(info, selection_set, executor))
         } else {
@@ -285,9 +325,9 @@ pub trait GraphQLType: Sized {
     /// The default implementation uses `resolve_field` to resolve all fields,
     /// including those through fragment expansion, for object types. For
     /// non-object types, this method panics.
-    fn resolve(&self, info: &Self::TypeInfo, selection_set: Option<&[Selection]>, executor: &Executor<Self::Context>) -> Value {
+    fn resolve(&self, info: &Self::TypeInfo, selection_set: Option<&[Selection<S>]>, executor: &Executor<Self::Context, S>) -> Value<S> {
         if let Some(selection_set) = selection_set {
-            let mut result = HashMap::new();
+            let mut result = Object::with_capacity(selection_set.len());
             resolve_selection_set_into(self, info, selection_set, executor, &mut result);
             Value::object(result)
         }
@@ -297,13 +337,54 @@ pub trait GraphQLType: Sized {
     }
 }
 
-fn resolve_selection_set_into<T, CtxT>(
+/// Object-safe counterpart of `GraphQLType` for trait objects
+///
+/// `GraphQLType` can't be used as a trait object, which makes it impossible to
+/// resolve a `Box<SomeTrait>` without knowing all of its implementors. Adding
+/// this trait as a supertrait of `SomeTrait` lets the concrete object type be
+/// resolved through dynamic dispatch instead, using the `instance_types` item
+/// of `graphql_interface!`.
+///
+/// This trait is implemented for all types without type info that implement
+/// `GraphQLType` with a context that can be converted from `CtxT`:
+///
+/// ```rust
+/// # use juniper::DynGraphQLType;
+/// trait Pet: DynGraphQLType<()> {
+///     fn name(&self) -> &str;
+/// }
+/// # fn main() { }
+/// ```
+pub trait DynGraphQLType<CtxT, S = DefaultScalarValue> where S: ScalarValue {
+    /// The name of the concrete GraphQL type of this value
+    fn dyn_type_name(&self) -> String;
+
+    /// Resolve this value using the executor's current selection set
+    fn dyn_resolve(&self, executor: &Executor<CtxT, S>) -> ExecutionResult<S>;
+}
+
+impl<T, CtxT, S> DynGraphQLType<CtxT, S> for T
+    where T: GraphQLType<S, TypeInfo=()>,
+          T::Context: FromContext<CtxT>,
+          S: ScalarValue,
+{
+    fn dyn_type_name(&self) -> String {
+        T::name(&()).expect("Trait object implementors must be named types").to_owned()
+    }
+
+    fn dyn_resolve(&self, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
+        executor.resolve_with_ctx(&(), self)
+    }
+}
+
+fn resolve_selection_set_into<T, CtxT, S>(
     instance: &T,
     info: &T::TypeInfo,
-    selection_set: &[Selection],
-    executor: &Executor<CtxT>,
-    result: &mut HashMap<String, Value>)
-    where T: GraphQLType<Context=CtxT>
+    selection_set: &[Selection<S>],
+    executor: &Executor<CtxT, S>,
+    result: &mut Object<S>)
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
 {
     let meta_type = executor.schema()
         .concrete_type_by_name(T::name(info).expect("Resolving named type's selection set").as_ref())
@@ -320,37 +401,16 @@ fn resolve_selection_set_into<T, CtxT>(
 
                 if f.name.item == "__typename" {
                     result.insert(
-                        (*response_name).to_owned(),
+                        executor.response_name(response_name),
                         Value::string(
                             instance.concrete_type_name(executor.context())));
                     continue;
                 }
 
-                let meta_field = meta_type.field_by_name(f.name.item)
-                    .unwrap_or_else(|| panic!(format!("Field {} not found on type {:?}", f.name.item, meta_type.name())));
-
-                let exec_vars = executor.variables();
-
-                let sub_exec = executor.sub_executor(
-                    Some(response_name),
-                    start_pos.clone(),
-                    f.selection_set.as_ref().map(|v| &v[..]));
-
-                let field_result = instance.resolve_field(
-                    info,
-                    f.name.item,
-                    &Arguments::new(
-                        f.arguments.as_ref().map(|m|
-                            m.item.iter().map(|&(ref k, ref v)|
-                                (k.item, v.item.clone().into_const(exec_vars))).collect()),
-                        &meta_field.arguments),
-                    &sub_exec);
-
-                match field_result {
-                    Ok(v) => merge_key_into(result, response_name, v),
-                    Err(e) => {
-                        sub_exec.push_error(e, start_pos.clone());
-                        result.insert((*response_name).to_owned(), Value::null());
+                match resolve_field(instance, info, meta_type, f, start_pos, executor, None) {
+                    Some(v) => merge_key_into(result, executor.response_name(response_name), v),
+                    None => {
+                        result.insert(executor.response_name(response_name), Value::null());
                     }
                 }
             },
@@ -382,8 +442,8 @@ fn resolve_selection_set_into<T, CtxT>(
                         Some(&fragment.selection_set[..]),
                         &sub_exec);
 
-                    if let Ok(Value::Object(mut hash_map)) = sub_result {
-                        for (k, v) in hash_map.drain() {
+                    if let Ok(Value::Object(object)) = sub_result {
+                        for (k, v) in object {
                             result.insert(k, v);
                         }
                     }
@@ -404,7 +464,298 @@ fn resolve_selection_set_into<T, CtxT>(
     }
 }
 
-fn is_excluded(directives: &Option<Vec<Spanning<Directive>>>, vars: &Variables) -> bool {
+// Resolve a field of an object, returns `None` if this failed and an error was
+// added to the executor
+fn resolve_field<T, CtxT, S>(
+    instance: &T,
+    info: &T::TypeInfo,
+    meta_type: &MetaType<S>,
+    f: &Field<S>,
+    start_pos: &SourcePosition,
+    executor: &Executor<CtxT, S>,
+    list_sink: Option<&ListSink<S>>,
+)
+    -> Option<Value<S>>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    let meta_field = meta_type.field_by_name(f.name.item)
+        .unwrap_or_else(|| panic!(format!("Field {} not found on type {:?}", f.name.item, meta_type.name())));
+
+    let exec_vars = executor.variables();
+    let response_name = &f.alias.as_ref().unwrap_or(&f.name).item;
+
+    let sub_exec = executor.streaming_sub_executor(
+        Some(response_name),
+        start_pos.clone(),
+        f.selection_set.as_ref().map(|v| &v[..]),
+        list_sink);
+
+    if !sub_exec.check_deadline() {
+        return None;
+    }
+
+    let arguments = Arguments::new(
+        f.arguments.as_ref().map(|m|
+            m.item.iter()
+                .filter(|&&(_, ref v)| !is_missing_variable(&v.item, exec_vars))
+                .map(|&(ref k, ref v)|
+                    (k.item, v.clone().map(|v| v.into_const(exec_vars)))).collect()),
+        &meta_field.arguments);
+
+    let parent_type_name = meta_type.name().unwrap_or_default();
+    let field_result = sub_exec.instrument_field(parent_type_name, f.name.item, &arguments, || {
+        instance.resolve_field(info, f.name.item, &arguments, &sub_exec)
+    });
+
+    match field_result {
+        Ok(v) => Some(v),
+        Err(e) => {
+            sub_exec.push_error(e, start_pos.clone());
+            None
+        }
+    }
+}
+
+/// The fields of an object, serialized while they are resolved
+///
+/// Lists the fields resolve to are handed to the serializer item by item. This
+/// is only used for the root types of the schema, which are object types, so
+/// every fragment in the selection set applies.
+#[doc(hidden)]
+pub struct StreamedObject<'e, 'a: 'e, T: 'e, CtxT: 'a, S: 'a>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    instance: &'e T,
+    info: &'e T::TypeInfo,
+    selection_set: &'a [Selection<'a, S>],
+    executor: &'e Executor<'a, CtxT, S>,
+}
+
+// The fields selected with the same response name
+struct StreamedField<'e, 'a: 'e, T: 'e, CtxT: 'a, S: 'a>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    object: &'e StreamedObject<'e, 'a, T, CtxT, S>,
+    meta_type: &'e MetaType<'a, S>,
+    fields: &'e [&'a Spanning<Field<'a, S>>],
+}
+
+// Serializes the list a field resolves to
+struct SeqSink<Ser> where Ser: ser::Serializer {
+    state: RefCell<SeqState<Ser>>,
+}
+
+enum SeqState<Ser> where Ser: ser::Serializer {
+    Pending(Ser),
+    Streaming(Ser::SerializeSeq),
+    Failed(Ser::Error),
+    Taken,
+}
+
+impl<'e, 'a, T, CtxT, S> StreamedObject<'e, 'a, T, CtxT, S>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    #[doc(hidden)]
+    pub fn new(
+        instance: &'e T,
+        info: &'e T::TypeInfo,
+        selection_set: &'a [Selection<'a, S>],
+        executor: &'e Executor<'a, CtxT, S>,
+    )
+        -> StreamedObject<'e, 'a, T, CtxT, S>
+    {
+        StreamedObject {
+            instance: instance,
+            info: info,
+            selection_set: selection_set,
+            executor: executor,
+        }
+    }
+
+    fn collect_fields(
+        &self,
+        selection_set: &'a [Selection<'a, S>],
+        fields: &mut Vec<(&'a str, Vec<&'a Spanning<Field<'a, S>>>)>,
+    ) {
+        let executor = self.executor;
+
+        for selection in selection_set {
+            match *selection {
+                Selection::Field(ref field) => {
+                    if is_excluded(&field.item.directives, executor.variables()) {
+                        continue;
+                    }
+
+                    let response_name = field.item.alias.as_ref().unwrap_or(&field.item.name).item;
+                    match fields.iter().position(|&(name, _)| name == response_name) {
+                        Some(i) => fields[i].1.push(field),
+                        None => fields.push((response_name, vec![field])),
+                    }
+                },
+                Selection::FragmentSpread(Spanning { item: ref spread, .. }) => {
+                    if is_excluded(&spread.directives, executor.variables()) {
+                        continue;
+                    }
+
+                    let fragment = executor.fragment_by_name(spread.name.item)
+                        .expect("Fragment could not be found");
+
+                    self.collect_fields(&fragment.selection_set[..], fields);
+                },
+                Selection::InlineFragment(Spanning { item: ref fragment, .. }) => {
+                    if is_excluded(&fragment.directives, executor.variables()) {
+                        continue;
+                    }
+
+                    self.collect_fields(&fragment.selection_set[..], fields);
+                },
+            }
+        }
+    }
+}
+
+impl<'e, 'a, T, CtxT, S> ser::Serialize for StreamedObject<'e, 'a, T, CtxT, S>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+        where Ser: ser::Serializer,
+    {
+        let meta_type = self.executor.schema()
+            .concrete_type_by_name(T::name(self.info).expect("Resolving named type's selection set").as_ref())
+            .expect("Type not found in schema");
+
+        let mut fields = Vec::new();
+        self.collect_fields(self.selection_set, &mut fields);
+
+        let mut map = try!(serializer.serialize_map(Some(fields.len())));
+        for &(response_name, ref fields) in &fields {
+            try!(map.serialize_key(response_name));
+            try!(map.serialize_value(&StreamedField {
+                object: self,
+                meta_type: meta_type,
+                fields: fields,
+            }));
+        }
+        map.end()
+    }
+}
+
+impl<'e, 'a, T, CtxT, S> ser::Serialize for StreamedField<'e, 'a, T, CtxT, S>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+        where Ser: ser::Serializer,
+    {
+        let object = self.object;
+        let first = &self.fields[0].item;
+
+        if first.name.item == "__typename" {
+            return serializer.serialize_str(&object.instance.concrete_type_name(object.executor.context()));
+        }
+
+        // The values of a field selected more than once are merged, which
+        // needs all of them
+        if self.fields.len() > 1 {
+            let response_name = object.executor.response_name(first.alias.as_ref().unwrap_or(&first.name).item);
+            let mut result = Object::with_capacity(1);
+
+            for field in self.fields {
+                match resolve_field(
+                    object.instance, object.info, self.meta_type, &field.item, &field.start, object.executor, None)
+                {
+                    Some(v) => merge_key_into(&mut result, response_name.clone(), v),
+                    None => {
+                        result.insert(response_name.clone(), Value::null());
+                    }
+                }
+            }
+
+            return match result.get(&response_name) {
+                Some(value) => value.serialize(serializer),
+                None => serializer.serialize_unit(),
+            };
+        }
+
+        // Only fields of list types resolve to a list the sink may receive,
+        // scalars like `ObjectMap` can contain lists of their own
+        let is_list = match self.meta_type.field_by_name(first.name.item).map(|field| &field.field_type) {
+            Some(&Type::List(_)) | Some(&Type::NonNullList(_)) => true,
+            _ => false,
+        };
+        if !is_list {
+            return resolve_field(
+                object.instance, object.info, self.meta_type, first, &self.fields[0].start, object.executor, None)
+                .unwrap_or_else(Value::null)
+                .serialize(serializer);
+        }
+
+        let sink = SeqSink {
+            state: RefCell::new(SeqState::Pending(serializer)),
+        };
+
+        let value = resolve_field(
+            object.instance, object.info, self.meta_type, first, &self.fields[0].start, object.executor, Some(&sink))
+            .unwrap_or_else(Value::null);
+
+        match sink.state.into_inner() {
+            SeqState::Pending(serializer) => value.serialize(serializer),
+            SeqState::Streaming(seq) => seq.end(),
+            SeqState::Failed(err) => Err(err),
+            SeqState::Taken => unreachable!(),
+        }
+    }
+}
+
+impl<Ser, S> ListSink<S> for SeqSink<Ser>
+    where Ser: ser::Serializer,
+          S: ScalarValue,
+{
+    fn begin(&self, len: Option<usize>) -> bool {
+        let mut state = self.state.borrow_mut();
+
+        match mem::replace(&mut *state, SeqState::Taken) {
+            SeqState::Pending(serializer) => {
+                *state = match serializer.serialize_seq(len) {
+                    Ok(seq) => SeqState::Streaming(seq),
+                    Err(err) => SeqState::Failed(err),
+                };
+                true
+            },
+            other => {
+                *state = other;
+                false
+            },
+        }
+    }
+
+    fn push(&self, item: Value<S>) -> bool {
+        let mut state = self.state.borrow_mut();
+
+        let result = match *state {
+            SeqState::Streaming(ref mut seq) => seq.serialize_element(&item),
+            _ => return false,
+        };
+
+        match result {
+            Ok(()) => true,
+            Err(err) => {
+                *state = SeqState::Failed(err);
+                false
+            },
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn is_excluded<S>(directives: &Option<Vec<Spanning<Directive<S>>>>, vars: &Variables<S>) -> bool
+    where S: ScalarValue
+{
     if let Some(ref directives) = *directives {
         for &Spanning { item: ref directive, .. } in directives {
             let condition: bool = directive.arguments.iter()
@@ -421,33 +772,41 @@ fn is_excluded(directives: &Option<Vec<Spanning<Directive>>>, vars: &Variables)
     false
 }
 
-fn merge_key_into(
-    result: &mut HashMap<String, Value>,
-    response_name: &str,
-    value: Value,
-) {
-    match result.entry(response_name.to_owned()) {
-        Entry::Occupied(mut e) => {
-            match (e.get_mut().as_mut_object_value(), value) {
-                (Some(dest_obj), Value::Object(src_obj)) => {
-                    merge_maps(dest_obj, src_obj);
-                },
-                _ => {}
-            }
-        },
-        Entry::Vacant(e) => {
-            e.insert(value);
-        },
+// Arguments bound to variables that were not provided are treated as if the
+// argument itself had been omitted.
+fn is_missing_variable<S>(value: &InputValue<S>, vars: &Variables<S>) -> bool {
+    match *value {
+        InputValue::Variable(ref name) => !vars.contains_key(name),
+        _ => false,
     }
 }
 
-fn merge_maps(
-    dest: &mut HashMap<String, Value>,
-    src: HashMap<String, Value>,
-) {
+#[doc(hidden)]
+pub fn merge_key_into<S>(
+    result: &mut Object<S>,
+    response_name: Arc<str>,
+    value: Value<S>,
+) where S: ScalarValue {
+    if let Some(existing) = result.get_mut(&response_name) {
+        match (existing.as_mut_object_value(), value) {
+            (Some(dest_obj), Value::Object(src_obj)) => {
+                merge_maps(dest_obj, src_obj);
+            },
+            _ => {}
+        }
+        return;
+    }
+
+    result.insert(response_name, value);
+}
+
+fn merge_maps<S>(
+    dest: &mut Object<S>,
+    src: Object<S>,
+) where S: ScalarValue {
     for (key, value) in src {
         if dest.contains_key(&key) {
-            merge_key_into(dest, &key, value);
+            merge_key_into(dest, key, value);
         }
         else {
             dest.insert(key, value);
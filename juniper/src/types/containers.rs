@@ -1,11 +1,19 @@
+use std::borrow::Cow;
+use std::collections::{HashSet, BTreeSet, VecDeque};
+use std::convert::TryFrom;
+use std::hash::Hash;
+
 use ast::{InputValue, ToInputValue, FromInputValue, Selection};
-use value::Value;
+use value::{Value, ScalarValue};
 use schema::meta::MetaType;
 
 use executor::{Executor, Registry};
 use types::base::{GraphQLType};
 
-impl<T, CtxT> GraphQLType for Option<T> where T: GraphQLType<Context=CtxT> {
+impl<T, CtxT, S> GraphQLType<S> for Option<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
     type Context = CtxT;
     type TypeInfo = T::TypeInfo;
 
@@ -13,20 +21,23 @@ impl<T, CtxT> GraphQLType for Option<T> where T: GraphQLType<Context=CtxT> {
         None
     }
 
-    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         registry.build_nullable_type::<T>(info).into_meta()
     }
 
-    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection]>, executor: &Executor<CtxT>) -> Value {
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
         match *self {
-            Some(ref obj) => executor.resolve_into_value(info, obj),
+            Some(ref obj) => executor.resolve_nullable_into_value(info, obj),
             None => Value::null(),
         }
     }
 }
 
-impl<T> FromInputValue for Option<T> where T: FromInputValue {
-    fn from(v: &InputValue) -> Option<Option<T>> {
+impl<T, S> FromInputValue<S> for Option<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Option<T>> {
         match v {
             &InputValue::Null => Some(None),
             v => match v.convert() {
@@ -37,8 +48,11 @@ impl<T> FromInputValue for Option<T> where T: FromInputValue {
     }
 }
 
-impl<T> ToInputValue for Option<T> where T: ToInputValue {
-    fn to(&self) -> InputValue {
+impl<T, S> ToInputValue<S> for Option<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
         match *self {
             Some(ref v) => v.to(),
             None => InputValue::null(),
@@ -46,7 +60,10 @@ impl<T> ToInputValue for Option<T> where T: ToInputValue {
     }
 }
 
-impl<T, CtxT> GraphQLType for Vec<T> where T: GraphQLType<Context=CtxT> {
+impl<T, CtxT, S> GraphQLType<S> for Vec<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
     type Context = CtxT;
     type TypeInfo = T::TypeInfo;
 
@@ -54,19 +71,20 @@ impl<T, CtxT> GraphQLType for Vec<T> where T: GraphQLType<Context=CtxT> {
         None
     }
 
-    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         registry.build_list_type::<T>(info).into_meta()
     }
 
-    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection]>, executor: &Executor<CtxT>) -> Value {
-        Value::list(
-            self.iter().map(|e| executor.resolve_into_value(info, e)).collect()
-        )
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
     }
 }
 
-impl<T> FromInputValue for Vec<T> where T: FromInputValue {
-    fn from(v: &InputValue) -> Option<Vec<T>> {
+impl<T, S> FromInputValue<S> for Vec<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Vec<T>> {
         match *v {
             InputValue::List(ref ls) => {
                 let v: Vec<_> = ls.iter().filter_map(|i| i.item.convert()).collect();
@@ -88,13 +106,48 @@ impl<T> FromInputValue for Vec<T> where T: FromInputValue {
     }
 }
 
-impl<T> ToInputValue for Vec<T> where T: ToInputValue {
-    fn to(&self) -> InputValue {
+impl<T, S> ToInputValue<S> for Vec<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(|v| v.to()).collect())
+    }
+}
+
+impl<'a, T, CtxT, S> GraphQLType<S> for &'a [T]
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_list_type::<T>(info).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
+    }
+}
+
+impl<'a, T, S> ToInputValue<S> for &'a [T]
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
         InputValue::list(self.iter().map(|v| v.to()).collect())
     }
 }
 
-impl<'a, T, CtxT> GraphQLType for &'a [T] where T: GraphQLType<Context=CtxT> {
+impl<T, CtxT, S, const N: usize> GraphQLType<S> for [T; N]
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
     type Context = CtxT;
     type TypeInfo = T::TypeInfo;
 
@@ -102,19 +155,182 @@ impl<'a, T, CtxT> GraphQLType for &'a [T] where T: GraphQLType<Context=CtxT> {
         None
     }
 
-    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         registry.build_list_type::<T>(info).into_meta()
     }
 
-    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection]>, executor: &Executor<CtxT>) -> Value {
-        Value::list(
-            self.iter().map(|e| executor.resolve_into_value(info, e)).collect()
-        )
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
+    }
+}
+
+/// Fixed-size arrays only accept lists of exactly `N` items
+impl<T, S, const N: usize> FromInputValue<S> for [T; N]
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<[T; N]> {
+        <Vec<T> as FromInputValue<S>>::from(v).and_then(|v| <[T; N]>::try_from(v).ok())
+    }
+}
+
+impl<T, S, const N: usize> ToInputValue<S> for [T; N]
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(|v| v.to()).collect())
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for VecDeque<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_list_type::<T>(info).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
+    }
+}
+
+impl<T, S> FromInputValue<S> for VecDeque<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<VecDeque<T>> {
+        <Vec<T> as FromInputValue<S>>::from(v).map(|v| v.into_iter().collect())
+    }
+}
+
+impl<T, S> ToInputValue<S> for VecDeque<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(|v| v.to()).collect())
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for HashSet<T>
+    where T: GraphQLType<S, Context=CtxT> + Eq + Hash,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_list_type::<T>(info).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
+    }
+}
+
+impl<T, S> FromInputValue<S> for HashSet<T>
+    where T: FromInputValue<S> + Eq + Hash,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<HashSet<T>> {
+        <Vec<T> as FromInputValue<S>>::from(v).map(|v| v.into_iter().collect())
+    }
+}
+
+impl<T, S> ToInputValue<S> for HashSet<T>
+    where T: ToInputValue<S> + Eq + Hash,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(|v| v.to()).collect())
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for BTreeSet<T>
+    where T: GraphQLType<S, Context=CtxT> + Ord,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_list_type::<T>(info).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
+    }
+}
+
+impl<T, S> FromInputValue<S> for BTreeSet<T>
+    where T: FromInputValue<S> + Ord,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<BTreeSet<T>> {
+        <Vec<T> as FromInputValue<S>>::from(v).map(|v| v.into_iter().collect())
+    }
+}
+
+impl<T, S> ToInputValue<S> for BTreeSet<T>
+    where T: ToInputValue<S> + Ord,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::list(self.iter().map(|v| v.to()).collect())
+    }
+}
+
+impl<'a, T, CtxT, S> GraphQLType<S> for Cow<'a, [T]>
+    where T: GraphQLType<S, Context=CtxT> + Clone,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_list_type::<T>(info).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        executor.resolve_into_list(info, self.iter())
+    }
+}
+
+impl<'a, T, S> FromInputValue<S> for Cow<'a, [T]>
+    where T: FromInputValue<S> + Clone,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Cow<'a, [T]>> {
+        <Vec<T> as FromInputValue<S>>::from(v).map(Cow::Owned)
     }
 }
 
-impl<'a, T> ToInputValue for &'a [T] where T: ToInputValue {
-    fn to(&self) -> InputValue {
+impl<'a, T, S> ToInputValue<S> for Cow<'a, [T]>
+    where T: ToInputValue<S> + Clone,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
         InputValue::list(self.iter().map(|v| v.to()).collect())
     }
 }
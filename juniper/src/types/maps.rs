@@ -0,0 +1,263 @@
+//! Wrapper types to expose string-keyed maps in a schema
+//!
+//! GraphQL has no native map type, so a `HashMap<String, T>` or
+//! `BTreeMap<String, T>` has to be exposed in one of two ways. Wrap the map in
+//! an `ObjectMap` to return it as an opaque scalar, serialized as a JSON
+//! object, or in a `KeyValueList` to return it as a list of `{ key, value }`
+//! objects that clients can select from like any other type.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, BTreeMap};
+use std::hash::Hash;
+
+use ast::{InputValue, Selection, FromInputValue, ToInputValue, Type};
+use value::{Value, ScalarValue};
+use schema::meta::{MetaType, ScalarMeta};
+use executor::{Executor, Registry, ExecutionResult};
+use types::base::{Arguments, GraphQLType};
+
+/// A string-keyed map exposed as a scalar
+///
+/// The map is resolved into a JSON object where every value is resolved as
+/// the leaf type `T`. Since scalars can't have selection sets, `T` must be a
+/// scalar or enum, or a list of them. The scalar is named after `T`, e.g. an
+/// `ObjectMap<i32>` is of the type `IntMap` and an `ObjectMap<Vec<i32>>` of
+/// the type `IntListMap`, so maps of different value types accept different
+/// arguments.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectMap<T>(Vec<(String, T)>);
+
+/// A string-keyed map exposed as a list of key/value entry objects
+///
+/// Each entry is an object with a `key: String!` field and a `value` field of
+/// type `T`. The entry type is named after `T`, e.g. entries of a
+/// `KeyValueList<User>` are of the type `UserEntry`. `T` must therefore be a
+/// named type.
+///
+/// Entries are resolved in the iteration order of the map the list was
+/// created from; use a `BTreeMap` to get a stable order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KeyValueList<T>(Vec<(String, T)>);
+
+struct Entry<'a, T: 'a> {
+    type_name: &'a str,
+    key: &'a str,
+    value: &'a T,
+}
+
+// The name of the entry type is derived from the value type at runtime, so
+// it's carried in the type info of entries along with the info of the values.
+struct EntryInfo<'a, I: 'a> {
+    name: String,
+    value_info: &'a I,
+}
+
+impl<'a, I> EntryInfo<'a, I> {
+    fn new<T, S>(value_info: &'a I) -> EntryInfo<'a, I>
+        where T: GraphQLType<S, TypeInfo=I>,
+              S: ScalarValue,
+    {
+        EntryInfo {
+            name: format!("{}Entry", T::name(value_info).expect("KeyValueList values must be named types")),
+            value_info: value_info,
+        }
+    }
+}
+
+impl<T> ObjectMap<T> {
+    /// The key/value pairs in this map
+    pub fn entries(&self) -> &[(String, T)] {
+        &self.0
+    }
+
+    /// Unwrap the key/value pairs in this map
+    pub fn into_entries(self) -> Vec<(String, T)> {
+        self.0
+    }
+}
+
+impl<T> KeyValueList<T> {
+    /// The key/value pairs in this list
+    pub fn entries(&self) -> &[(String, T)] {
+        &self.0
+    }
+
+    /// Unwrap the key/value pairs in this list
+    pub fn into_entries(self) -> Vec<(String, T)> {
+        self.0
+    }
+}
+
+impl<K, T> From<HashMap<K, T>> for ObjectMap<T> where K: Into<String> + Eq + Hash {
+    fn from(map: HashMap<K, T>) -> ObjectMap<T> {
+        ObjectMap(map.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl<K, T> From<BTreeMap<K, T>> for ObjectMap<T> where K: Into<String> + Ord {
+    fn from(map: BTreeMap<K, T>) -> ObjectMap<T> {
+        ObjectMap(map.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl<K, T> From<HashMap<K, T>> for KeyValueList<T> where K: Into<String> + Eq + Hash {
+    fn from(map: HashMap<K, T>) -> KeyValueList<T> {
+        KeyValueList(map.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl<K, T> From<BTreeMap<K, T>> for KeyValueList<T> where K: Into<String> + Ord {
+    fn from(map: BTreeMap<K, T>) -> KeyValueList<T> {
+        KeyValueList(map.into_iter().map(|(k, v)| (k.into(), v)).collect())
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for ObjectMap<T>
+    where T: GraphQLType<S, Context=CtxT> + FromInputValue<S>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    // The scalar is registered under the name derived from the value type
+    // here, since `name` can only return names that outlive the type info.
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        let name = format!("{}Map", value_type_name(&registry.get_type_with_info::<T>(info)));
+
+        if !registry.types.contains_key(&name) {
+            registry.types.insert(name.clone(), map_meta::<T, S>(name.clone()));
+        }
+
+        map_meta::<T, S>(name)
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        Value::object(
+            self.0.iter()
+                .map(|&(ref k, ref v)| (k.clone(), executor.resolve_into_value(info, v)))
+                .collect()
+        )
+    }
+}
+
+impl<T, S> FromInputValue<S> for ObjectMap<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<ObjectMap<T>> {
+        match *v {
+            InputValue::Object(ref o) => {
+                let entries: Vec<_> = o.iter()
+                    .filter_map(|&(ref k, ref v)| v.item.convert().map(|v| (k.item.clone(), v)))
+                    .collect();
+
+                if entries.len() == o.len() {
+                    Some(ObjectMap(entries))
+                }
+                else {
+                    None
+                }
+            },
+            _ => None,
+        }
+    }
+}
+
+impl<T, S> ToInputValue<S> for ObjectMap<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        InputValue::object(
+            self.0.iter()
+                .map(|&(ref k, ref v)| (k.as_str(), v.to()))
+                .collect::<HashMap<_, _>>()
+        )
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for KeyValueList<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_list_type::<Entry<T>>(&EntryInfo::new::<T, S>(info)).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        let entry_info = EntryInfo::new::<T, S>(info);
+
+        Value::list(
+            self.0.iter()
+                .enumerate()
+                .map(|(i, &(ref k, ref v))| executor.resolve_item_into_value(
+                    i, &entry_info, &Entry { type_name: &entry_info.name, key: k, value: v }))
+                .collect()
+        )
+    }
+}
+
+impl<'a, T, CtxT, S> GraphQLType<S> for Entry<'a, T>
+    where T: GraphQLType<S, Context=CtxT>,
+          T::TypeInfo: 'a,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = EntryInfo<'a, T::TypeInfo>;
+
+    fn name(info: &Self::TypeInfo) -> Option<&str> {
+        Some(&info.name)
+    }
+
+    fn meta<'r>(info: &Self::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        let fields = &[
+            registry.field::<String>("key"),
+            registry.field_with_info::<T>("value", info.value_info),
+        ];
+
+        registry.build_object_type_with_info::<Self>(info, fields).into_meta()
+    }
+
+    fn resolve_field(&self, info: &Self::TypeInfo, field_name: &str, _: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
+        match field_name {
+            "key" => executor.resolve_with_ctx(&(), &self.key),
+            "value" => executor.resolve(info.value_info, self.value),
+            _ => panic!("Field {} not found on map entry type", field_name),
+        }
+    }
+
+    fn concrete_type_name(&self, _: &CtxT) -> String {
+        self.type_name.to_owned()
+    }
+}
+
+// Spells out nullability and lists, so that e.g. `ObjectMap<i32>` is an
+// `IntMap` and `ObjectMap<Option<Vec<i32>>>` a `NullableIntListMap`
+fn value_type_name(value_type: &Type) -> String {
+    match *value_type {
+        Type::NonNullNamed(ref name) => name.to_string(),
+        Type::Named(ref name) => format!("Nullable{}", name),
+        Type::NonNullList(ref item_type) => format!("{}List", value_type_name(item_type)),
+        Type::List(ref item_type) => format!("Nullable{}List", value_type_name(item_type)),
+    }
+}
+
+fn map_meta<'r, T, S>(name: String) -> MetaType<'r, S>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    ScalarMeta::new::<ObjectMap<T>>(Cow::Owned(name))
+        .description("A string-keyed map, represented as an object")
+        .into_meta()
+}
@@ -2,4 +2,8 @@ pub mod base;
 pub mod scalars;
 pub mod pointers;
 pub mod containers;
+pub mod maps;
+pub mod nullable;
+pub mod time;
+pub mod upload;
 pub mod utilities;
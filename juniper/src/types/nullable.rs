@@ -0,0 +1,176 @@
+//! A nullable wrapper that tells omitted values apart from explicit nulls
+//!
+//! `Option<T>` coerces both a missing argument or input field and an explicit
+//! `null` into `None`. That's fine for most inputs, but partial updates
+//! usually need to know whether a field should be left untouched or cleared.
+//! `Nullable<T>` exposes the same nullable type in the schema while keeping
+//! the two cases separate.
+
+use ast::{InputValue, Selection, FromInputValue, ToInputValue};
+use value::{Value, ScalarValue};
+use schema::meta::MetaType;
+use executor::{Executor, Registry};
+use types::base::GraphQLType;
+
+/// A nullable input value that remembers whether it was provided
+///
+/// Use it in place of `Option<T>` for arguments and input object fields:
+///
+/// ```rust
+/// # #[macro_use] extern crate juniper;
+/// # use juniper::Nullable;
+/// graphql_input_object!(
+///     struct UserPatch {
+///         name: Nullable<String>,
+///     }
+/// );
+///
+/// # fn main() {
+/// # let patch = UserPatch { name: Nullable::ExplicitNull };
+/// match patch.name {
+///     Nullable::ImplicitNull => { /* leave the name unchanged */ },
+///     Nullable::ExplicitNull => { /* clear the name */ },
+///     Nullable::Some(_name) => { /* set the name */ },
+/// }
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Nullable<T> {
+    /// The value was omitted
+    ImplicitNull,
+    /// The value was explicitly set to `null`
+    ExplicitNull,
+    /// A non-null value was provided
+    Some(T),
+}
+
+impl<T> Nullable<T> {
+    /// Does this contain a value?
+    pub fn is_some(&self) -> bool {
+        match *self {
+            Nullable::Some(_) => true,
+            _ => false,
+        }
+    }
+
+    /// Is this either kind of null?
+    pub fn is_null(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Was the value omitted?
+    pub fn is_implicit_null(&self) -> bool {
+        match *self {
+            Nullable::ImplicitNull => true,
+            _ => false,
+        }
+    }
+
+    /// Was the value explicitly set to `null`?
+    pub fn is_explicit_null(&self) -> bool {
+        match *self {
+            Nullable::ExplicitNull => true,
+            _ => false,
+        }
+    }
+
+    /// Convert from `&Nullable<T>` to `Nullable<&T>`
+    pub fn as_ref(&self) -> Nullable<&T> {
+        match *self {
+            Nullable::ImplicitNull => Nullable::ImplicitNull,
+            Nullable::ExplicitNull => Nullable::ExplicitNull,
+            Nullable::Some(ref v) => Nullable::Some(v),
+        }
+    }
+
+    /// Map the contained value, preserving the kind of null
+    pub fn map<U, F>(self, f: F) -> Nullable<U> where F: FnOnce(T) -> U {
+        match self {
+            Nullable::ImplicitNull => Nullable::ImplicitNull,
+            Nullable::ExplicitNull => Nullable::ExplicitNull,
+            Nullable::Some(v) => Nullable::Some(f(v)),
+        }
+    }
+
+    /// The contained value, treating both kinds of null as `None`
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Nullable::Some(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// The value as provided by the client
+    ///
+    /// Returns `None` if the value was omitted, `Some(None)` for an explicit
+    /// `null`, and `Some(Some(v))` otherwise.
+    pub fn explicit(self) -> Option<Option<T>> {
+        match self {
+            Nullable::ImplicitNull => None,
+            Nullable::ExplicitNull => Some(None),
+            Nullable::Some(v) => Some(Some(v)),
+        }
+    }
+
+    /// The contained value, or `default` if this is either kind of null
+    pub fn unwrap_or(self, default: T) -> T {
+        self.into_option().unwrap_or(default)
+    }
+}
+
+impl<T> Default for Nullable<T> {
+    fn default() -> Nullable<T> {
+        Nullable::ImplicitNull
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for Nullable<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(_: &T::TypeInfo) -> Option<&str> {
+        None
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_nullable_type::<T>(info).into_meta()
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, _: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        match *self {
+            Nullable::Some(ref obj) => executor.resolve_into_value(info, obj),
+            _ => Value::null(),
+        }
+    }
+}
+
+impl<T, S> FromInputValue<S> for Nullable<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Nullable<T>> {
+        match v {
+            &InputValue::Null => Some(Nullable::ExplicitNull),
+            v => v.convert().map(Nullable::Some),
+        }
+    }
+
+    fn from_implicit_null() -> Option<Nullable<T>> {
+        Some(Nullable::ImplicitNull)
+    }
+}
+
+impl<T, S> ToInputValue<S> for Nullable<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        match *self {
+            Nullable::Some(ref v) => v.to(),
+            _ => InputValue::null(),
+        }
+    }
+}
@@ -1,11 +1,18 @@
+use std::borrow::Cow;
+use std::rc::Rc;
+use std::sync::Arc;
+
 use ast::{Selection, InputValue, ToInputValue, FromInputValue};
-use value::Value;
+use value::{Value, ScalarValue};
 
 use schema::meta::MetaType;
 use executor::{Executor, Registry, ExecutionResult};
 use types::base::{Arguments, GraphQLType};
 
-impl<T, CtxT> GraphQLType for Box<T> where T: GraphQLType<Context=CtxT> {
+impl<T, CtxT, S> GraphQLType<S> for Box<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
     type Context = CtxT;
     type TypeInfo = T::TypeInfo;
 
@@ -13,40 +20,87 @@ impl<T, CtxT> GraphQLType for Box<T> where T: GraphQLType<Context=CtxT> {
         T::name(info)
     }
 
-    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         T::meta(info, registry)
     }
 
-    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection]>, executor: &Executor<CtxT>) -> ExecutionResult {
+    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
         (**self).resolve_into_type(info, name, selection_set, executor)
     }
 
-    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments, executor: &Executor<CtxT>) -> ExecutionResult
+    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S>
     {
         (**self).resolve_field(info, field, args, executor)
     }
 
-    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection]>, executor: &Executor<CtxT>) -> Value {
+    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
         (**self).resolve(info, selection_set, executor)
     }
 }
 
-impl<T> FromInputValue for Box<T> where T: FromInputValue {
-    fn from(v: &InputValue) -> Option<Box<T>> {
-        match <T as FromInputValue>::from(v) {
+impl<T, S> FromInputValue<S> for Box<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Box<T>> {
+        match <T as FromInputValue<S>>::from(v) {
             Some(v) => Some(Box::new(v)),
             None => None,
         }
     }
 }
 
-impl<T> ToInputValue for Box<T> where T: ToInputValue {
-    fn to(&self) -> InputValue {
+impl<T, S> ToInputValue<S> for Box<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        (**self).to()
+    }
+}
+
+impl<'a, T, CtxT, S> GraphQLType<S> for &'a T
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(info: &T::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        T::meta(info, registry)
+    }
+
+    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S>
+    {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<'a, T, S> ToInputValue<S> for &'a T
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
         (**self).to()
     }
 }
 
-impl<'a, T, CtxT> GraphQLType for &'a T where T: GraphQLType<Context=CtxT> {
+impl<T, CtxT, S> GraphQLType<S> for Arc<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
     type Context = CtxT;
     type TypeInfo = T::TypeInfo;
 
@@ -54,26 +108,141 @@ impl<'a, T, CtxT> GraphQLType for &'a T where T: GraphQLType<Context=CtxT> {
         T::name(info)
     }
 
-    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         T::meta(info, registry)
     }
 
-    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection]>, executor: &Executor<CtxT>) -> ExecutionResult {
+    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
         (**self).resolve_into_type(info, name, selection_set, executor)
     }
 
-    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments, executor: &Executor<CtxT>) -> ExecutionResult
+    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S>
     {
         (**self).resolve_field(info, field, args, executor)
     }
 
-    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection]>, executor: &Executor<CtxT>) -> Value {
+    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
         (**self).resolve(info, selection_set, executor)
     }
 }
 
-impl<'a, T> ToInputValue for &'a T where T: ToInputValue {
-    fn to(&self) -> InputValue {
+impl<T, S> FromInputValue<S> for Arc<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Arc<T>> {
+        match <T as FromInputValue<S>>::from(v) {
+            Some(v) => Some(Arc::new(v)),
+            None => None,
+        }
+    }
+}
+
+impl<T, S> ToInputValue<S> for Arc<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        (**self).to()
+    }
+}
+
+impl<T, CtxT, S> GraphQLType<S> for Rc<T>
+    where T: GraphQLType<S, Context=CtxT>,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(info: &T::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        T::meta(info, registry)
+    }
+
+    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S>
+    {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<T, S> FromInputValue<S> for Rc<T>
+    where T: FromInputValue<S>,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Rc<T>> {
+        match <T as FromInputValue<S>>::from(v) {
+            Some(v) => Some(Rc::new(v)),
+            None => None,
+        }
+    }
+}
+
+impl<T, S> ToInputValue<S> for Rc<T>
+    where T: ToInputValue<S>,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
+        (**self).to()
+    }
+}
+
+impl<'a, T, CtxT, S> GraphQLType<S> for Cow<'a, T>
+    where T: GraphQLType<S, Context=CtxT> + Clone,
+          S: ScalarValue,
+{
+    type Context = CtxT;
+    type TypeInfo = T::TypeInfo;
+
+    fn name(info: &T::TypeInfo) -> Option<&str> {
+        T::name(info)
+    }
+
+    fn meta<'r>(info: &T::TypeInfo, registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        T::meta(info, registry)
+    }
+
+    fn resolve_into_type(&self, info: &T::TypeInfo, name: &str, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> ExecutionResult<S> {
+        (**self).resolve_into_type(info, name, selection_set, executor)
+    }
+
+    fn resolve_field(&self, info: &T::TypeInfo, field: &str, args: &Arguments<S>, executor: &Executor<CtxT, S>) -> ExecutionResult<S>
+    {
+        (**self).resolve_field(info, field, args, executor)
+    }
+
+    fn resolve(&self, info: &T::TypeInfo, selection_set: Option<&[Selection<S>]>, executor: &Executor<CtxT, S>) -> Value<S> {
+        (**self).resolve(info, selection_set, executor)
+    }
+}
+
+impl<'a, T, S> FromInputValue<S> for Cow<'a, T>
+    where T: FromInputValue<S> + Clone,
+          S: ScalarValue,
+{
+    fn from(v: &InputValue<S>) -> Option<Cow<'a, T>> {
+        match <T as FromInputValue<S>>::from(v) {
+            Some(v) => Some(Cow::Owned(v)),
+            None => None,
+        }
+    }
+}
+
+impl<'a, T, S> ToInputValue<S> for Cow<'a, T>
+    where T: ToInputValue<S> + Clone,
+          S: ScalarValue,
+{
+    fn to(&self) -> InputValue<S> {
         (**self).to()
     }
 }
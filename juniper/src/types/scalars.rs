@@ -1,13 +1,15 @@
+use std::borrow::Cow;
 use std::convert::From;
 use std::marker::PhantomData;
 use std::ops::Deref;
 
 use ast::{InputValue, Selection, FromInputValue, ToInputValue};
-use value::Value;
+use value::{Value, ScalarValue};
 
 use schema::meta::MetaType;
 
-use executor::{Executor, Registry};
+use error_code::GraphQLErrorCode;
+use executor::{Executor, Registry, FieldError};
 use types::base::GraphQLType;
 
 /// An ID as defined by the GraphQL specification
@@ -59,7 +61,7 @@ graphql_scalar!(String as "String" {
 });
 
 
-impl<'a> GraphQLType for &'a str {
+impl<'a, S> GraphQLType<S> for &'a str where S: ScalarValue {
     type Context = ();
     type TypeInfo = ();
 
@@ -67,17 +69,50 @@ impl<'a> GraphQLType for &'a str {
         Some("String")
     }
 
-    fn meta<'r>(_: &(), registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         registry.build_scalar_type::<String>(&()).into_meta()
     }
 
-    fn resolve(&self, _: &(), _: Option<&[Selection]>, _: &Executor<Self::Context>) -> Value {
+    fn resolve(&self, _: &(), _: Option<&[Selection<S>]>, _: &Executor<Self::Context, S>) -> Value<S> {
         Value::string(self)
     }
 }
 
-impl<'a> ToInputValue for &'a str {
-    fn to(&self) -> InputValue {
+impl<'a, S> ToInputValue<S> for &'a str where S: ScalarValue {
+    fn to(&self) -> InputValue<S> {
+        InputValue::string(self)
+    }
+}
+
+
+impl<'a, S> GraphQLType<S> for Cow<'a, str> where S: ScalarValue {
+    type Context = ();
+    type TypeInfo = ();
+
+    fn name(_: &()) -> Option<&str> {
+        Some("String")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_scalar_type::<String>(&()).into_meta()
+    }
+
+    fn resolve(&self, _: &(), _: Option<&[Selection<S>]>, _: &Executor<Self::Context, S>) -> Value<S> {
+        Value::string(self)
+    }
+}
+
+impl<'a, S> FromInputValue<S> for Cow<'a, str> where S: ScalarValue {
+    fn from(v: &InputValue<S>) -> Option<Cow<'a, str>> {
+        match *v {
+            InputValue::String(ref s) => Some(Cow::Owned(s.clone())),
+            _ => None,
+        }
+    }
+}
+
+impl<'a, S> ToInputValue<S> for Cow<'a, str> where S: ScalarValue {
+    fn to(&self) -> InputValue<S> {
         InputValue::string(self)
     }
 }
@@ -121,13 +156,71 @@ graphql_scalar!(f64 as "Float" {
         match *v {
             InputValue::Int(i) => Some(i as f64),
             InputValue::Float(f) => Some(f),
+            InputValue::Scalar(ref s) => s.as_float(),
             _ => None,
         }
     }
 });
 
 
-impl GraphQLType for () {
+// Unsigned integers are exposed as `Int`, and are range-checked in both
+// directions instead of wrapping around.
+macro_rules! graphql_unsigned_int {
+    ( $t:ty ) => {
+        impl<S> GraphQLType<S> for $t where S: ScalarValue {
+            type Context = ();
+            type TypeInfo = ();
+
+            fn name(_: &()) -> Option<&str> {
+                Some("Int")
+            }
+
+            fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+                registry.build_scalar_type::<i32>(&()).into_meta()
+            }
+
+            fn resolve(&self, _: &(), _: Option<&[Selection<S>]>, executor: &Executor<Self::Context, S>) -> Value<S> {
+                if *self as u64 <= i32::max_value() as u64 {
+                    Value::int(*self as i32)
+                } else {
+                    executor.push_error(
+                        FieldError::from(format!("Int cannot represent {}", self))
+                            .with_code(GraphQLErrorCode::InternalServerError),
+                        executor.location().clone());
+                    Value::null()
+                }
+            }
+        }
+
+        impl<S> FromInputValue<S> for $t where S: ScalarValue {
+            fn from(v: &InputValue<S>) -> Option<$t> {
+                match *v {
+                    InputValue::Int(i) if i >= 0 && i as u64 <= <$t>::max_value() as u64 => Some(i as $t),
+                    _ => None,
+                }
+            }
+        }
+
+        // Values an `Int` can't represent become `null`, like the results
+        // of fields
+        impl<S> ToInputValue<S> for $t where S: ScalarValue {
+            fn to(&self) -> InputValue<S> {
+                if *self as u64 <= i32::max_value() as u64 {
+                    InputValue::int(*self as i32)
+                } else {
+                    InputValue::null()
+                }
+            }
+        }
+    };
+}
+
+graphql_unsigned_int!(u16);
+graphql_unsigned_int!(u32);
+graphql_unsigned_int!(usize);
+
+
+impl<S> GraphQLType<S> for () where S: ScalarValue {
     type Context = ();
     type TypeInfo = ();
 
@@ -135,13 +228,13 @@ impl GraphQLType for () {
         Some("__Unit")
     }
 
-    fn meta<'r>(_: &(), registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         registry.build_scalar_type::<Self>(&()).into_meta()
     }
 }
 
-impl FromInputValue for () {
-    fn from(_: &InputValue) -> Option<()> {
+impl<S> FromInputValue<S> for () where S: ScalarValue {
+    fn from(_: &InputValue<S>) -> Option<()> {
         None
     }
 }
@@ -152,7 +245,8 @@ impl FromInputValue for () {
 /// If you instantiate `RootNode` with this as the mutation, no mutation will be
 /// generated for the schema.
 pub struct EmptyMutation<T> {
-    phantom: PhantomData<T>,
+    // Doesn't own a `T`, so it's `Send` and `Sync` whatever the context is
+    phantom: PhantomData<fn() -> T>,
 }
 
 impl<T> EmptyMutation<T> {
@@ -164,7 +258,7 @@ impl<T> EmptyMutation<T> {
     }
 }
 
-impl<T> GraphQLType for EmptyMutation<T> {
+impl<T, S> GraphQLType<S> for EmptyMutation<T> where S: ScalarValue {
     type Context = T;
     type TypeInfo = ();
 
@@ -172,14 +266,50 @@ impl<T> GraphQLType for EmptyMutation<T> {
         Some("_EmptyMutation")
     }
 
-    fn meta<'r>(_: &(), registry: &mut Registry<'r>) -> MetaType<'r> {
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
+        registry.build_object_type::<Self>(&[]).into_meta()
+    }
+}
+
+/// Utility type to define schemas without subscriptions
+///
+/// The subscription counterpart of `EmptyMutation`, for schemas that don't
+/// support subscriptions.
+pub struct EmptySubscription<T> {
+    phantom: PhantomData<fn() -> T>,
+}
+
+impl<T> EmptySubscription<T> {
+    /// Construct a new empty subscription
+    pub fn new() -> EmptySubscription<T> {
+        EmptySubscription {
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, S> GraphQLType<S> for EmptySubscription<T> where S: ScalarValue {
+    type Context = T;
+    type TypeInfo = ();
+
+    fn name(_: &()) -> Option<&str> {
+        Some("_EmptySubscription")
+    }
+
+    fn meta<'r>(_: &(), registry: &mut Registry<'r, S>) -> MetaType<'r, S> {
         registry.build_object_type::<Self>(&[]).into_meta()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::ID;
+    use std::collections::HashMap;
+
+    use super::{ID, EmptySubscription};
+    use ast::{InputValue, ToInputValue};
+    use executor::Registry;
+    use types::base::GraphQLType;
+    use value::DefaultScalarValue;
 
     #[test]
     fn test_id_from_string() {
@@ -193,4 +323,39 @@ mod tests {
         let id = ID(String::from("foo"));
         assert_eq!(id.len(), 3);
     }
+
+    #[test]
+    fn test_empty_subscription() {
+        let mut registry: Registry = Registry::new(HashMap::new());
+        let meta = <EmptySubscription<()> as GraphQLType>::meta(&(), &mut registry);
+
+        assert_eq!(meta.name(), Some("_EmptySubscription"));
+    }
+
+    #[test]
+    fn test_unsigned_from_input_value() {
+        let int = |i| InputValue::<DefaultScalarValue>::int(i);
+        let float = |f| InputValue::<DefaultScalarValue>::float(f);
+
+        assert_eq!(int(0).convert::<u16>(), Some(0));
+        assert_eq!(int(65535).convert::<u16>(), Some(65535));
+        assert_eq!(int(65536).convert::<u16>(), None);
+        assert_eq!(int(-1).convert::<u16>(), None);
+
+        assert_eq!(int(i32::max_value()).convert::<u32>(), Some(i32::max_value() as u32));
+        assert_eq!(int(-1).convert::<u32>(), None);
+
+        assert_eq!(int(42).convert::<usize>(), Some(42));
+        assert_eq!(int(-42).convert::<usize>(), None);
+        assert_eq!(float(1.0).convert::<usize>(), None);
+    }
+
+    #[test]
+    fn test_unsigned_to_input_value() {
+        assert_eq!(ToInputValue::<DefaultScalarValue>::to(&42u32), InputValue::int(42));
+        assert_eq!(ToInputValue::<DefaultScalarValue>::to(&(i32::max_value() as u32)), InputValue::int(i32::max_value()));
+        assert_eq!(ToInputValue::<DefaultScalarValue>::to(&(i32::max_value() as u32 + 1)), InputValue::null());
+        assert_eq!(ToInputValue::<DefaultScalarValue>::to(&u32::max_value()), InputValue::null());
+        assert_eq!(ToInputValue::<DefaultScalarValue>::to(&usize::max_value()), InputValue::null());
+    }
 }
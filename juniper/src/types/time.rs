@@ -0,0 +1,348 @@
+//! Scalars for the `std::time` types
+//!
+//! `SystemTime` is exposed as the `DateTime` scalar, serialized as an
+//! RFC 3339 string in UTC, e.g. `"2017-08-01T12:34:56.5Z"`. Timestamps with a
+//! different offset are accepted as input and converted to UTC.
+//!
+//! `Duration` is exposed as the `Duration` scalar, serialized as an ISO 8601
+//! duration in seconds, e.g. `"PT90.25S"`. As input, any ISO 8601 duration
+//! made up of weeks, days, hours, minutes and seconds is accepted, as well as
+//! a non-negative integer number of milliseconds. Years and months are
+//! rejected since their length is ambiguous.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use ast::InputValue;
+use value::Value;
+
+graphql_scalar!(SystemTime as "DateTime" {
+    description: "A point in time, represented as an RFC 3339 string"
+
+    resolve(&self) -> Value {
+        Value::string(format_rfc3339(self))
+    }
+
+    from_input_value(v: &InputValue) -> Option<SystemTime> {
+        match *v {
+            InputValue::String(ref s) => parse_rfc3339(s),
+            _ => None,
+        }
+    }
+});
+
+graphql_scalar!(Duration as "Duration" {
+    description: "A span of time, represented as an ISO 8601 duration string"
+
+    resolve(&self) -> Value {
+        Value::string(format_duration(self))
+    }
+
+    from_input_value(v: &InputValue) -> Option<Duration> {
+        match *v {
+            InputValue::String(ref s) => parse_duration(s),
+            InputValue::Int(i) if i >= 0 => Some(Duration::from_millis(i as u64)),
+            _ => None,
+        }
+    }
+});
+
+const SECS_PER_DAY: i64 = 86400;
+
+fn format_rfc3339(time: &SystemTime) -> String {
+    let (secs, nanos) = match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => (d.as_secs() as i64, d.subsec_nanos()),
+        Err(e) => {
+            let d = e.duration();
+            if d.subsec_nanos() == 0 {
+                (-(d.as_secs() as i64), 0)
+            } else {
+                (-(d.as_secs() as i64) - 1, 1_000_000_000 - d.subsec_nanos())
+            }
+        }
+    };
+
+    let mut days = secs / SECS_PER_DAY;
+    let mut rem = secs % SECS_PER_DAY;
+    if rem < 0 {
+        days -= 1;
+        rem += SECS_PER_DAY;
+    }
+
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}{}Z",
+        year, month, day, rem / 3600, rem % 3600 / 60, rem % 60, format_nanos(nanos))
+}
+
+fn parse_rfc3339(s: &str) -> Option<SystemTime> {
+    let b = s.as_bytes();
+
+    if b.len() < 20 || b[4] != b'-' || b[7] != b'-' || (b[10] != b'T' && b[10] != b't')
+        || b[13] != b':' || b[16] != b':'
+    {
+        return None;
+    }
+
+    let year = digits(&b[0..4])? as i64;
+    let month = digits(&b[5..7])?;
+    let day = digits(&b[8..10])?;
+    let hour = digits(&b[11..13])? as i64;
+    let minute = digits(&b[14..16])? as i64;
+    let second = digits(&b[17..19])? as i64;
+
+    if month < 1 || month > 12 || day < 1 || day > days_in_month(year, month)
+        || hour > 23 || minute > 59 || second > 59
+    {
+        return None;
+    }
+
+    let mut rest = &b[19..];
+    let mut nanos = 0;
+
+    if rest[0] == b'.' {
+        let len = rest[1..].iter().take_while(|c| c.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+        nanos = parse_fraction(&rest[1..len + 1])?;
+        rest = &rest[len + 1..];
+    }
+
+    let offset = if rest == b"Z" || rest == b"z" {
+        0
+    } else if rest.len() == 6 && (rest[0] == b'+' || rest[0] == b'-') && rest[3] == b':' {
+        let hours = digits(&rest[1..3])? as i64;
+        let minutes = digits(&rest[4..6])? as i64;
+        if hours > 23 || minutes > 59 {
+            return None;
+        }
+        let offset = hours * 3600 + minutes * 60;
+        if rest[0] == b'-' { -offset } else { offset }
+    } else {
+        return None;
+    };
+
+    let secs = days_from_civil(year, month, day) * SECS_PER_DAY
+        + hour * 3600 + minute * 60 + second - offset;
+
+    if secs >= 0 {
+        UNIX_EPOCH.checked_add(Duration::new(secs as u64, nanos))
+    } else {
+        UNIX_EPOCH.checked_sub(Duration::new((-secs) as u64, 0))
+            .and_then(|t| t.checked_add(Duration::new(0, nanos)))
+    }
+}
+
+fn format_duration(duration: &Duration) -> String {
+    format!("PT{}{}S", duration.as_secs(), format_nanos(duration.subsec_nanos()))
+}
+
+fn parse_duration(s: &str) -> Option<Duration> {
+    let b = s.as_bytes();
+
+    if b.first() != Some(&b'P') {
+        return None;
+    }
+
+    // Units in the order they have to appear in, and their length in seconds.
+    const DATE_UNITS: &[(u8, u64)] = &[(b'W', 604800), (b'D', 86400)];
+    const TIME_UNITS: &[(u8, u64)] = &[(b'H', 3600), (b'M', 60), (b'S', 1)];
+
+    let (date, time) = match b[1..].iter().position(|&c| c == b'T') {
+        Some(i) => (&b[1..i + 1], Some(&b[i + 2..])),
+        None => (&b[1..], None),
+    };
+
+    if time.map_or(date.is_empty(), |t| t.is_empty()) {
+        return None;
+    }
+
+    let mut secs: u64 = 0;
+    let mut nanos = 0;
+
+    let parts = vec![(date, DATE_UNITS)].into_iter()
+        .chain(time.map(|t| (t, TIME_UNITS)));
+
+    for (mut part, mut units) in parts {
+        while !part.is_empty() {
+            let len = part.iter().take_while(|c| c.is_ascii_digit() || **c == b'.').count();
+            if len == 0 || len == part.len() {
+                return None;
+            }
+
+            let unit = part[len];
+            let pos = units.iter().position(|&(u, _)| u == unit)?;
+            let factor = units[pos].1;
+            units = &units[pos + 1..];
+
+            let (whole, fraction) = match part[..len].iter().position(|&c| c == b'.') {
+                Some(i) if unit == b'S' => (&part[..i], Some(&part[i + 1..len])),
+                Some(_) => return None,
+                None => (&part[..len], None),
+            };
+
+            let value = digits(whole)?;
+            secs = value.checked_mul(factor).and_then(|v| secs.checked_add(v))?;
+
+            if let Some(fraction) = fraction {
+                if fraction.is_empty() {
+                    return None;
+                }
+                nanos = parse_fraction(fraction)?;
+            }
+
+            part = &part[len + 1..];
+        }
+    }
+
+    Some(Duration::new(secs, nanos))
+}
+
+// Fractional seconds are printed with millisecond, microsecond or nanosecond
+// precision, whichever is the shortest without losing information.
+fn format_nanos(nanos: u32) -> String {
+    if nanos == 0 {
+        String::new()
+    } else if nanos % 1_000_000 == 0 {
+        format!(".{:03}", nanos / 1_000_000)
+    } else if nanos % 1_000 == 0 {
+        format!(".{:06}", nanos / 1_000)
+    } else {
+        format!(".{:09}", nanos)
+    }
+}
+
+// Parses the digits after the decimal point into nanoseconds, ignoring any
+// digits beyond nanosecond precision.
+fn parse_fraction(b: &[u8]) -> Option<u32> {
+    let mut nanos = 0;
+    let mut scale = 100_000_000;
+
+    for &c in b {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        nanos += (c - b'0') as u32 * scale;
+        scale /= 10;
+    }
+
+    Some(nanos)
+}
+
+fn digits(b: &[u8]) -> Option<u64> {
+    if b.is_empty() {
+        return None;
+    }
+
+    b.iter().fold(Some(0u64), |acc, &c| {
+        if c.is_ascii_digit() {
+            acc.and_then(|v| v.checked_mul(10)).and_then(|v| v.checked_add((c - b'0') as u64))
+        } else {
+            None
+        }
+    })
+}
+
+fn days_in_month(year: i64, month: u64) -> u64 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+// Conversions between days since the epoch and the proleptic Gregorian
+// calendar, see http://howardhinnant.github.io/date_algorithms.html
+fn days_from_civil(year: i64, month: u64, day: u64) -> i64 {
+    let (month, day) = (month as i64, day as i64);
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let yoe = year - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use super::{format_rfc3339, parse_rfc3339, format_duration, parse_duration};
+
+    #[test]
+    fn formats_system_time() {
+        assert_eq!(format_rfc3339(&UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            format_rfc3339(&(UNIX_EPOCH + Duration::new(1501590896, 500_000_000))),
+            "2017-08-01T12:34:56.500Z");
+        assert_eq!(
+            format_rfc3339(&(UNIX_EPOCH + Duration::new(951782400, 1_000))),
+            "2000-02-29T00:00:00.000001Z");
+        assert_eq!(
+            format_rfc3339(&(UNIX_EPOCH - Duration::new(1, 0))),
+            "1969-12-31T23:59:59Z");
+        assert_eq!(
+            format_rfc3339(&(UNIX_EPOCH - Duration::new(0, 250_000_000))),
+            "1969-12-31T23:59:59.750Z");
+    }
+
+    #[test]
+    fn parses_system_time() {
+        assert_eq!(parse_rfc3339("1970-01-01T00:00:00Z"), Some(UNIX_EPOCH));
+        assert_eq!(
+            parse_rfc3339("2017-08-01T12:34:56.5Z"),
+            Some(UNIX_EPOCH + Duration::new(1501590896, 500_000_000)));
+        assert_eq!(
+            parse_rfc3339("2017-08-01t14:34:56+02:00"),
+            Some(UNIX_EPOCH + Duration::new(1501590896, 0)));
+        assert_eq!(
+            parse_rfc3339("1969-12-31T23:59:59.750Z"),
+            Some(UNIX_EPOCH - Duration::new(0, 250_000_000)));
+
+        assert_eq!(parse_rfc3339("2017-08-01"), None);
+        assert_eq!(parse_rfc3339("2017-08-01T12:34:56"), None);
+        assert_eq!(parse_rfc3339("2017-02-29T00:00:00Z"), None);
+        assert_eq!(parse_rfc3339("2017-08-01T24:00:00Z"), None);
+        assert_eq!(parse_rfc3339("2017-08-01T12:34:56.Z"), None);
+        assert_eq!(parse_rfc3339("2017-08-01T12:34:56+0200"), None);
+    }
+
+    #[test]
+    fn formats_duration() {
+        assert_eq!(format_duration(&Duration::new(0, 0)), "PT0S");
+        assert_eq!(format_duration(&Duration::new(90, 250_000_000)), "PT90.250S");
+    }
+
+    #[test]
+    fn parses_duration() {
+        assert_eq!(parse_duration("PT0S"), Some(Duration::new(0, 0)));
+        assert_eq!(parse_duration("PT90.25S"), Some(Duration::new(90, 250_000_000)));
+        assert_eq!(parse_duration("P1DT1H1M1S"), Some(Duration::new(90061, 0)));
+        assert_eq!(parse_duration("P2W"), Some(Duration::new(1209600, 0)));
+        assert_eq!(parse_duration("PT1M"), Some(Duration::new(60, 0)));
+
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("P1DT"), None);
+        assert_eq!(parse_duration("P1Y"), None);
+        assert_eq!(parse_duration("P1H"), None);
+        assert_eq!(parse_duration("PT1S1M"), None);
+        assert_eq!(parse_duration("PT1.5M"), None);
+        assert_eq!(parse_duration("1S"), None);
+    }
+}
@@ -0,0 +1,44 @@
+//! The `Upload` scalar for files sent along with a request
+//!
+//! Files are sent using the GraphQL multipart request protocol, where each file
+//! is referenced from the variables. The `http::multipart` module parses such
+//! requests, replacing every reference with a placeholder that converts into an
+//! `Upload`. The contents of the file are looked up in the `UploadedFiles` of
+//! the request, which resolvers usually reach through their context.
+
+use ast::InputValue;
+use value::Value;
+
+const PLACEHOLDER_PREFIX: &'static str = "juniper-upload:";
+
+/// A reference to a file uploaded along with the request
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Upload(String);
+
+impl Upload {
+    /// The name of the multipart form field containing the file
+    pub fn key(&self) -> &str {
+        &self.0
+    }
+
+    #[doc(hidden)]
+    pub fn placeholder(key: &str) -> String {
+        format!("{}{}", PLACEHOLDER_PREFIX, key)
+    }
+}
+
+graphql_scalar!(Upload as "Upload" {
+    description: "A file uploaded using a multipart request"
+
+    resolve(&self) -> Value {
+        Value::string(&self.0)
+    }
+
+    from_input_value(v: &InputValue) -> Option<Upload> {
+        match *v {
+            InputValue::String(ref s) if s.starts_with(PLACEHOLDER_PREFIX) =>
+                Some(Upload(s[PLACEHOLDER_PREFIX.len()..].to_owned())),
+            _ => None,
+        }
+    }
+});
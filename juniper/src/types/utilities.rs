@@ -1,9 +1,12 @@
 use std::collections::HashSet;
 use ast::InputValue;
+use value::ScalarValue;
 use schema::model::{SchemaType, TypeType};
 use schema::meta::{MetaType, InputObjectMeta, EnumMeta};
 
-pub fn is_valid_literal_value(schema: &SchemaType, arg_type: &TypeType, arg_value: &InputValue) -> bool {
+pub fn is_valid_literal_value<S>(schema: &SchemaType<S>, arg_type: &TypeType<S>, arg_value: &InputValue<S>) -> bool
+    where S: ScalarValue,
+{
     match *arg_type {
         TypeType::NonNull(ref inner) => {
             if arg_value.is_null() {
@@ -34,7 +37,8 @@ pub fn is_valid_literal_value(schema: &SchemaType, arg_type: &TypeType, arg_valu
                 ref v @ InputValue::Float(_) |
                 ref v @ InputValue::String(_) |
                 ref v @ InputValue::Boolean(_) |
-                ref v @ InputValue::Enum(_) => {
+                ref v @ InputValue::Enum(_) |
+                ref v @ InputValue::Scalar(_) => {
                     if let Some(parse_fn) = t.input_value_parse_fn() {
                         parse_fn(v)
                     } else {
@@ -45,11 +49,11 @@ pub fn is_valid_literal_value(schema: &SchemaType, arg_type: &TypeType, arg_valu
                 InputValue::Object(ref obj) => {
                     if let MetaType::InputObject(InputObjectMeta { ref input_fields, .. }) = *t {
                         let mut remaining_required_fields = input_fields.iter()
-                            .filter_map(|f| if f.arg_type.is_non_null() { Some(&f.name) } else { None })
+                            .filter_map(|f| if f.arg_type.is_non_null() { Some(f.name.as_str()) } else { None })
                             .collect::<HashSet<_>>();
 
                         let all_types_ok = obj.iter().all(|&(ref key, ref value)| {
-                            remaining_required_fields.remove(&key.item);
+                            remaining_required_fields.remove(key.item.as_str());
                             if let Some(ref arg_type) = input_fields.iter()
                                 .filter(|f| f.name == key.item)
                                 .map(|f| schema.make_type(&f.arg_type))
@@ -64,6 +68,10 @@ pub fn is_valid_literal_value(schema: &SchemaType, arg_type: &TypeType, arg_valu
 
                         all_types_ok && remaining_required_fields.is_empty()
                     }
+                    else if let MetaType::Scalar(_) = *t {
+                        // Scalars such as `Map` may be represented as objects
+                        t.input_value_parse_fn().map_or(false, |parse_fn| parse_fn(arg_value))
+                    }
                     else {
                         false
                     }
@@ -5,24 +5,27 @@ use ast::{Document, Definition, Type};
 use schema::meta::MetaType;
 use schema::model::SchemaType;
 
+use error_code::GraphQLErrorCode;
 use parser::SourcePosition;
+use value::ScalarValue;
 
 /// Query validation error
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RuleError {
     locations: Vec<SourcePosition>,
     message: String,
+    code: GraphQLErrorCode,
 }
 
 #[doc(hidden)]
-pub struct ValidatorContext<'a> {
-    pub schema: &'a SchemaType<'a>,
+pub struct ValidatorContext<'a, S> {
+    pub schema: &'a SchemaType<'a, S>,
     errors: Vec<RuleError>,
-    type_stack: Vec<Option<&'a MetaType<'a>>>,
+    type_stack: Vec<Option<&'a MetaType<'a, S>>>,
     type_literal_stack: Vec<Option<Type<'a>>>,
-    input_type_stack: Vec<Option<&'a MetaType<'a>>>,
+    input_type_stack: Vec<Option<&'a MetaType<'a, S>>>,
     input_type_literal_stack: Vec<Option<Type<'a>>>,
-    parent_type_stack: Vec<Option<&'a MetaType<'a>>>,
+    parent_type_stack: Vec<Option<&'a MetaType<'a, S>>>,
     fragment_names: HashSet<&'a str>,
 }
 
@@ -32,9 +35,16 @@ impl RuleError {
         RuleError {
             message: message.to_owned(),
             locations: locations.to_vec(),
+            code: GraphQLErrorCode::GraphQLValidationFailed,
         }
     }
 
+    #[doc(hidden)]
+    pub fn with_code(mut self, code: GraphQLErrorCode) -> RuleError {
+        self.code = code;
+        self
+    }
+
     /// Access the message for a validation error
     pub fn message(&self) -> &str {
         &self.message
@@ -47,11 +57,17 @@ impl RuleError {
     pub fn locations(&self) -> &[SourcePosition] {
         &self.locations
     }
+
+    /// The code of the error, which is `BadUserInput` for errors in the
+    /// values of variables, and `GraphQLValidationFailed` otherwise
+    pub fn code(&self) -> GraphQLErrorCode {
+        self.code
+    }
 }
 
-impl<'a> ValidatorContext<'a> {
+impl<'a, S> ValidatorContext<'a, S> where S: ScalarValue {
     #[doc(hidden)]
-    pub fn new(schema: &'a SchemaType, document: &Document<'a>) -> ValidatorContext<'a> {
+    pub fn new(schema: &'a SchemaType<S>, document: &Document<'a, S>) -> ValidatorContext<'a, S> {
         ValidatorContext {
             errors: Vec::new(),
             schema: schema,
@@ -88,7 +104,7 @@ impl<'a> ValidatorContext<'a> {
     #[doc(hidden)]
     pub fn with_pushed_type<F, R>(&mut self, t: Option<&Type<'a>>, f: F)
         -> R
-        where F: FnOnce(&mut ValidatorContext<'a>) -> R
+        where F: FnOnce(&mut ValidatorContext<'a, S>) -> R
     {
         if let Some(t) = t {
             self.type_stack.push(self.schema.concrete_type_by_name(t.innermost_name()));
@@ -110,7 +126,7 @@ impl<'a> ValidatorContext<'a> {
     #[doc(hidden)]
     pub fn with_pushed_parent_type<F, R>(&mut self, f: F)
         -> R
-        where F: FnOnce(&mut ValidatorContext<'a>) -> R
+        where F: FnOnce(&mut ValidatorContext<'a, S>) -> R
     {
         self.parent_type_stack.push(*self.type_stack.last().unwrap_or(&None));
         let res = f(self);
@@ -122,7 +138,7 @@ impl<'a> ValidatorContext<'a> {
     #[doc(hidden)]
     pub fn with_pushed_input_type<F, R>(&mut self, t: Option<&Type<'a>>, f: F)
         -> R
-        where F: FnOnce(&mut ValidatorContext<'a>) -> R
+        where F: FnOnce(&mut ValidatorContext<'a, S>) -> R
     {
         if let Some(t) = t {
             self.input_type_stack.push(self.schema.concrete_type_by_name(t.innermost_name()));
@@ -142,7 +158,7 @@ impl<'a> ValidatorContext<'a> {
     }
 
     #[doc(hidden)]
-    pub fn current_type(&self) -> Option<&'a MetaType<'a>> {
+    pub fn current_type(&self) -> Option<&'a MetaType<'a, S>> {
         *self.type_stack.last().unwrap_or(&None)
     }
 
@@ -155,7 +171,7 @@ impl<'a> ValidatorContext<'a> {
     }
 
     #[doc(hidden)]
-    pub fn parent_type(&self) -> Option<&'a MetaType<'a>> {
+    pub fn parent_type(&self) -> Option<&'a MetaType<'a, S>> {
         *self.parent_type_stack.last().unwrap_or(&None)
     }
 
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 use std::fmt;
 
+use error_code::GraphQLErrorCode;
 use parser::SourcePosition;
+use value::ScalarValue;
 use ast::{InputValue, Document, Definition, VariableDefinitions};
 use executor::Variables;
 use validation::RuleError;
@@ -15,12 +17,13 @@ enum Path<'a> {
     ObjectField(&'a str, &'a Path<'a>),
 }
 
-pub fn validate_input_values(
-    values: &Variables,
-    document: &Document,
-    schema: &SchemaType,
+pub fn validate_input_values<S>(
+    values: &Variables<S>,
+    document: &Document<S>,
+    schema: &SchemaType<S>,
 )
     -> Vec<RuleError>
+    where S: ScalarValue,
 {
     let mut errs = vec![];
 
@@ -36,12 +39,14 @@ pub fn validate_input_values(
     errs
 }
 
-fn validate_var_defs(
-    values: &Variables,
-    var_defs: &VariableDefinitions,
-    schema: &SchemaType,
+fn validate_var_defs<S>(
+    values: &Variables<S>,
+    var_defs: &VariableDefinitions<S>,
+    schema: &SchemaType<S>,
     errors: &mut Vec<RuleError>,
-) {
+)
+    where S: ScalarValue,
+{
     for &(ref name, ref def) in var_defs.iter() {
         let raw_type_name = def.var_type.item.innermost_name();
         match schema.concrete_type_by_name(raw_type_name) {
@@ -55,7 +60,7 @@ fn validate_var_defs(
                             name.item, def.var_type.item,
                         ),
                         &[ name.start.clone() ],
-                    ));
+                    ).with_code(GraphQLErrorCode::BadUserInput));
                 } else if let Some(v) = values.get(name.item) {
                     unify_value(name.item, &name.start, v, &ct, schema, errors, Path::Root);
                 }
@@ -71,15 +76,17 @@ fn validate_var_defs(
     }
 }
 
-fn unify_value<'a>(
+fn unify_value<'a, S>(
     var_name: &str,
     var_pos: &SourcePosition,
-    value: &InputValue,
-    meta_type: &TypeType<'a>,
-    schema: &SchemaType,
+    value: &InputValue<S>,
+    meta_type: &TypeType<'a, S>,
+    schema: &SchemaType<S>,
     errors: &mut Vec<RuleError>,
     path: Path<'a>,
-) {
+)
+    where S: ScalarValue,
+{
     match *meta_type {
         TypeType::NonNull(ref inner) => {
             if value.is_null() {
@@ -125,14 +132,16 @@ fn unify_value<'a>(
     }
 }
 
-fn unify_scalar<'a>(
+fn unify_scalar<'a, S>(
     var_name: &str,
     var_pos: &SourcePosition,
-    value: &InputValue,
-    meta: &ScalarMeta,
+    value: &InputValue<S>,
+    meta: &ScalarMeta<S>,
     errors: &mut Vec<RuleError>,
     path: &Path<'a>,
-) {
+)
+    where S: ScalarValue,
+{
     if !(meta.try_parse_fn)(value) {
         push_unification_error(
             errors,
@@ -153,26 +162,20 @@ fn unify_scalar<'a>(
                 path,
                 &format!(r#"Expected "{}", found list"#, meta.name),
             ),
-        InputValue::Object(_) =>
-            push_unification_error(
-                errors,
-                var_name,
-                var_pos,
-                path,
-                &format!(r#"Expected "{}", found object"#, meta.name),
-            ),
         _ => (),
     }
 }
 
-fn unify_enum<'a>(
+fn unify_enum<'a, S>(
     var_name: &str,
     var_pos: &SourcePosition,
-    value: &InputValue,
-    meta: &EnumMeta,
+    value: &InputValue<S>,
+    meta: &EnumMeta<S>,
     errors: &mut Vec<RuleError>,
     path: &Path<'a>,
-) {
+)
+    where S: ScalarValue,
+{
     match *value {
         InputValue::String(ref name) | InputValue::Enum(ref name) => {
             if !meta.values.iter().any(|ev| &ev.name == name) {
@@ -195,15 +198,17 @@ fn unify_enum<'a>(
     }
 }
 
-fn unify_input_object<'a>(
+fn unify_input_object<'a, S>(
     var_name: &str,
     var_pos: &SourcePosition,
-    value: &InputValue,
-    meta: &InputObjectMeta,
-    schema: &SchemaType,
+    value: &InputValue<S>,
+    meta: &InputObjectMeta<S>,
+    schema: &SchemaType<S>,
     errors: &mut Vec<RuleError>,
     path: &Path<'a>,
-) {
+)
+    where S: ScalarValue,
+{
     if let Some(ref obj) = value.to_object_value() {
         let mut keys = obj.keys().collect::<HashSet<&&str>>();
 
@@ -259,7 +264,9 @@ fn unify_input_object<'a>(
     }
 }
 
-fn is_absent_or_null(v: Option<&InputValue>) -> bool {
+fn is_absent_or_null<S>(v: Option<&InputValue<S>>) -> bool
+    where S: ScalarValue,
+{
     v.map_or(true, InputValue::is_null)
 }
 
@@ -276,7 +283,7 @@ fn push_unification_error<'a>(
             var_name, path, message,
         ),
         &[ var_pos.clone() ],
-    ));
+    ).with_code(GraphQLErrorCode::BadUserInput));
 }
 
 impl<'a> fmt::Display for Path<'a> {
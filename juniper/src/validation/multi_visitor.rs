@@ -1,13 +1,14 @@
 use ast::{Document, Operation, Fragment, VariableDefinition, Selection,
           Directive, InputValue, Field, FragmentSpread, InlineFragment};
 use parser::Spanning;
+use value::ScalarValue;
 use validation::{ValidatorContext, Visitor};
 
 #[doc(hidden)]
-pub trait MultiVisitor<'a> {
-    fn visit_all<F: FnMut(&mut Visitor<'a>) -> ()>(&mut self, f: F);
+pub trait MultiVisitor<'a, S> {
+    fn visit_all<F: FnMut(&mut Visitor<'a, S>) -> ()>(&mut self, f: F);
 
-    fn with<V: Visitor<'a>>(self, visitor: V) -> MultiVisitorCons<V, Self> where Self: Sized {
+    fn with<V: Visitor<'a, S>>(self, visitor: V) -> MultiVisitorCons<V, Self> where Self: Sized {
         MultiVisitorCons(visitor, self)
     }
 }
@@ -15,159 +16,169 @@ pub trait MultiVisitor<'a> {
 #[doc(hidden)]
 pub struct MultiVisitorNil;
 
-impl<'a> MultiVisitor<'a> for MultiVisitorNil {
-    fn visit_all<F: FnMut(&mut Visitor<'a>) -> ()>(&mut self, _: F) {}
+impl<'a, S> MultiVisitor<'a, S> for MultiVisitorNil where S: ScalarValue {
+    fn visit_all<F: FnMut(&mut Visitor<'a, S>) -> ()>(&mut self, _: F) {}
 }
 
 #[doc(hidden)]
 pub struct MultiVisitorCons<A, B>(A, B);
 
-impl<'a, A: Visitor<'a>, B: MultiVisitor<'a>> MultiVisitor<'a> for MultiVisitorCons<A, B> {
-    fn visit_all<F: FnMut(&mut Visitor<'a>) -> ()>(&mut self, mut f: F) {
+impl<'a, A: Visitor<'a, S>, B: MultiVisitor<'a, S>, S> MultiVisitor<'a, S> for MultiVisitorCons<A, B> where S: ScalarValue {
+    fn visit_all<F: FnMut(&mut Visitor<'a, S>) -> ()>(&mut self, mut f: F) {
         f(&mut self.0);
         self.1.visit_all(f);
     }
 }
 
-impl<'a, M> Visitor<'a> for M where M: MultiVisitor<'a> {
-    fn enter_document(&mut self, ctx: &mut ValidatorContext<'a>, doc: &'a Document) {
+impl<'a, A, B, S> Visitor<'a, S> for MultiVisitorCons<A, B>
+    where MultiVisitorCons<A, B>: MultiVisitor<'a, S>,
+          S: ScalarValue,
+{
+    fn enter_document(&mut self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
         self.visit_all(|v| v.enter_document(ctx, doc));
     }
 
-    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a>, doc: &'a Document) {
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
         self.visit_all(|v| v.exit_document(ctx, doc));
     }
 
-    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         self.visit_all(|v| v.enter_operation_definition(ctx, op));
     }
-    fn exit_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+    fn exit_operation_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         self.visit_all(|v| v.exit_operation_definition(ctx, op));
     }
 
-    fn enter_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<Fragment<S>>) {
         self.visit_all(|v| v.enter_fragment_definition(ctx, f));
     }
-    fn exit_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+    fn exit_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<Fragment<S>>) {
         self.visit_all(|v| v.exit_fragment_definition(ctx, f));
     }
 
-    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, def: &'a (Spanning<&'a str>, VariableDefinition)) {
+    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, def: &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         self.visit_all(|v| v.enter_variable_definition(ctx, def));
     }
-    fn exit_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, def: &'a (Spanning<&'a str>, VariableDefinition)) {
+    fn exit_variable_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, def: &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         self.visit_all(|v| v.exit_variable_definition(ctx, def));
     }
 
-    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a>, d: &'a Spanning<Directive>) {
+    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, d: &'a Spanning<Directive<S>>) {
         self.visit_all(|v| v.enter_directive(ctx, d));
     }
-    fn exit_directive(&mut self, ctx: &mut ValidatorContext<'a>, d: &'a Spanning<Directive>) {
+    fn exit_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, d: &'a Spanning<Directive<S>>) {
         self.visit_all(|v| v.exit_directive(ctx, d));
     }
 
-    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a>, arg: &'a (Spanning<&'a str>, Spanning<InputValue>)) {
+    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a, S>, arg: &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
         self.visit_all(|v| v.enter_argument(ctx, arg));
     }
-    fn exit_argument(&mut self, ctx: &mut ValidatorContext<'a>, arg: &'a (Spanning<&'a str>, Spanning<InputValue>)) {
+    fn exit_argument(&mut self, ctx: &mut ValidatorContext<'a, S>, arg: &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
         self.visit_all(|v| v.exit_argument(ctx, arg));
     }
 
-    fn enter_selection_set(&mut self, ctx: &mut ValidatorContext<'a>, s: &'a Vec<Selection>) {
+    fn enter_selection_set(&mut self, ctx: &mut ValidatorContext<'a, S>, s: &'a Vec<Selection<S>>) {
         self.visit_all(|v| v.enter_selection_set(ctx, s));
     }
-    fn exit_selection_set(&mut self, ctx: &mut ValidatorContext<'a>, s: &'a Vec<Selection>) {
+    fn exit_selection_set(&mut self, ctx: &mut ValidatorContext<'a, S>, s: &'a Vec<Selection<S>>) {
         self.visit_all(|v| v.exit_selection_set(ctx, s));
     }
 
-    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<Field>) {
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<Field<S>>) {
         self.visit_all(|v| v.enter_field(ctx, f));
     }
-    fn exit_field(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<Field>) {
+    fn exit_field(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<Field<S>>) {
         self.visit_all(|v| v.exit_field(ctx, f));
     }
 
-    fn enter_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a>, s: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a, S>, s: &'a Spanning<FragmentSpread<S>>) {
         self.visit_all(|v| v.enter_fragment_spread(ctx, s));
     }
-    fn exit_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a>, s: &'a Spanning<FragmentSpread>) {
+    fn exit_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a, S>, s: &'a Spanning<FragmentSpread<S>>) {
         self.visit_all(|v| v.exit_fragment_spread(ctx, s));
     }
 
-    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<InlineFragment>) {
+    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<InlineFragment<S>>) {
         self.visit_all(|v| v.enter_inline_fragment(ctx, f));
     }
-    fn exit_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a Spanning<InlineFragment>) {
+    fn exit_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a Spanning<InlineFragment<S>>) {
         self.visit_all(|v| v.exit_inline_fragment(ctx, f));
     }
 
-    fn enter_null_value(&mut self, ctx: &mut ValidatorContext<'a>, n: Spanning<()>) {
+    fn enter_null_value(&mut self, ctx: &mut ValidatorContext<'a, S>, n: Spanning<()>) {
         self.visit_all(|v| v.enter_null_value(ctx, n.clone()));
     }
-    fn exit_null_value(&mut self, ctx: &mut ValidatorContext<'a>, n: Spanning<()>) {
+    fn exit_null_value(&mut self, ctx: &mut ValidatorContext<'a, S>, n: Spanning<()>) {
         self.visit_all(|v| v.exit_null_value(ctx, n.clone()));
     }
 
-    fn enter_int_value(&mut self, ctx: &mut ValidatorContext<'a>, i: Spanning<i32>) {
+    fn enter_int_value(&mut self, ctx: &mut ValidatorContext<'a, S>, i: Spanning<i32>) {
         self.visit_all(|v| v.enter_int_value(ctx, i.clone()));
     }
-    fn exit_int_value(&mut self, ctx: &mut ValidatorContext<'a>, i: Spanning<i32>) {
+    fn exit_int_value(&mut self, ctx: &mut ValidatorContext<'a, S>, i: Spanning<i32>) {
         self.visit_all(|v| v.exit_int_value(ctx, i.clone()));
     }
 
-    fn enter_float_value(&mut self, ctx: &mut ValidatorContext<'a>, f: Spanning<f64>) {
+    fn enter_float_value(&mut self, ctx: &mut ValidatorContext<'a, S>, f: Spanning<f64>) {
         self.visit_all(|v| v.enter_float_value(ctx, f.clone()));
     }
-    fn exit_float_value(&mut self, ctx: &mut ValidatorContext<'a>, f: Spanning<f64>) {
+    fn exit_float_value(&mut self, ctx: &mut ValidatorContext<'a, S>, f: Spanning<f64>) {
         self.visit_all(|v| v.exit_float_value(ctx, f.clone()));
     }
 
-    fn enter_string_value(&mut self, ctx: &mut ValidatorContext<'a>, s: Spanning<&'a String>) {
+    fn enter_string_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
         self.visit_all(|v| v.enter_string_value(ctx, s.clone()));
     }
-    fn exit_string_value(&mut self, ctx: &mut ValidatorContext<'a>, s: Spanning<&'a String>) {
+    fn exit_string_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
         self.visit_all(|v| v.exit_string_value(ctx, s.clone()));
     }
 
-    fn enter_boolean_value(&mut self, ctx: &mut ValidatorContext<'a>, b: Spanning<bool>) {
+    fn enter_boolean_value(&mut self, ctx: &mut ValidatorContext<'a, S>, b: Spanning<bool>) {
         self.visit_all(|v| v.enter_boolean_value(ctx, b.clone()));
     }
-    fn exit_boolean_value(&mut self, ctx: &mut ValidatorContext<'a>, b: Spanning<bool>) {
+    fn exit_boolean_value(&mut self, ctx: &mut ValidatorContext<'a, S>, b: Spanning<bool>) {
         self.visit_all(|v| v.exit_boolean_value(ctx, b.clone()));
     }
 
-    fn enter_enum_value(&mut self, ctx: &mut ValidatorContext<'a>, s: Spanning<&'a String>) {
+    fn enter_enum_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
         self.visit_all(|v| v.enter_enum_value(ctx, s.clone()));
     }
-    fn exit_enum_value(&mut self, ctx: &mut ValidatorContext<'a>, s: Spanning<&'a String>) {
+    fn exit_enum_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
         self.visit_all(|v| v.exit_enum_value(ctx, s.clone()));
     }
 
-    fn enter_variable_value(&mut self, ctx: &mut ValidatorContext<'a>, s: Spanning<&'a String>) {
+    fn enter_scalar_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a S>) {
+        self.visit_all(|v| v.enter_scalar_value(ctx, s.clone()));
+    }
+    fn exit_scalar_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a S>) {
+        self.visit_all(|v| v.exit_scalar_value(ctx, s.clone()));
+    }
+
+    fn enter_variable_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
         self.visit_all(|v| v.enter_variable_value(ctx, s.clone()));
     }
-    fn exit_variable_value(&mut self, ctx: &mut ValidatorContext<'a>, s: Spanning<&'a String>) {
+    fn exit_variable_value(&mut self, ctx: &mut ValidatorContext<'a, S>, s: Spanning<&'a String>) {
         self.visit_all(|v| v.exit_variable_value(ctx, s.clone()));
     }
 
-    fn enter_list_value(&mut self, ctx: &mut ValidatorContext<'a>, l: Spanning<&'a Vec<Spanning<InputValue>>>) {
+    fn enter_list_value(&mut self, ctx: &mut ValidatorContext<'a, S>, l: Spanning<&'a Vec<Spanning<InputValue<S>>>>) {
         self.visit_all(|v| v.enter_list_value(ctx, l.clone()));
     }
-    fn exit_list_value(&mut self, ctx: &mut ValidatorContext<'a>, l: Spanning<&'a Vec<Spanning<InputValue>>>) {
+    fn exit_list_value(&mut self, ctx: &mut ValidatorContext<'a, S>, l: Spanning<&'a Vec<Spanning<InputValue<S>>>>) {
         self.visit_all(|v| v.exit_list_value(ctx, l.clone()));
     }
 
-    fn enter_object_value(&mut self, ctx: &mut ValidatorContext<'a>, o: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue>)>>) {
+    fn enter_object_value(&mut self, ctx: &mut ValidatorContext<'a, S>, o: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue<S>>)>>) {
         self.visit_all(|v| v.enter_object_value(ctx, o.clone()));
     }
-    fn exit_object_value(&mut self, ctx: &mut ValidatorContext<'a>, o: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue>)>>) {
+    fn exit_object_value(&mut self, ctx: &mut ValidatorContext<'a, S>, o: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue<S>>)>>) {
         self.visit_all(|v| v.exit_object_value(ctx, o.clone()));
     }
 
-    fn enter_object_field(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a (Spanning<String>, Spanning<InputValue>)) {
+    fn enter_object_field(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a (Spanning<String>, Spanning<InputValue<S>>)) {
         self.visit_all(|v| v.enter_object_field(ctx, f));
     }
-    fn exit_object_field(&mut self, ctx: &mut ValidatorContext<'a>, f: &'a (Spanning<String>, Spanning<InputValue>)) {
+    fn exit_object_field(&mut self, ctx: &mut ValidatorContext<'a, S>, f: &'a (Spanning<String>, Spanning<InputValue<S>>)) {
         self.visit_all(|v| v.exit_object_field(ctx, f));
     }
 }
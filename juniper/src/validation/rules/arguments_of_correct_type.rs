@@ -3,39 +3,42 @@ use schema::meta::Argument;
 use types::utilities::is_valid_literal_value;
 use parser::Spanning;
 use validation::{Visitor, ValidatorContext};
+use value::ScalarValue;
 
-pub struct ArgumentsOfCorrectType<'a> {
-    current_args: Option<&'a Vec<Argument<'a>>>,
+pub struct ArgumentsOfCorrectType<'a, S: 'a> {
+    current_args: Option<&'a Vec<Argument<'a, S>>>,
 }
 
-pub fn factory<'a>() -> ArgumentsOfCorrectType<'a> {
+pub fn factory<'a, S>() -> ArgumentsOfCorrectType<'a, S> {
     ArgumentsOfCorrectType {
         current_args: None,
     }
 }
 
-impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
-    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a>, directive: &'a Spanning<Directive>) {
+impl<'a, S> Visitor<'a, S> for ArgumentsOfCorrectType<'a, S>
+    where S: ScalarValue,
+{
+    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, directive: &'a Spanning<Directive<S>>) {
         self.current_args = ctx.schema
             .directive_by_name(directive.item.name.item)
             .map(|d| &d.arguments);
     }
 
-    fn exit_directive(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Directive>) {
+    fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
         self.current_args = None;
     }
 
-    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
         self.current_args = ctx.parent_type()
             .and_then(|t| t.field_by_name(field.item.name.item))
             .and_then(|f| f.arguments.as_ref());
     }
 
-    fn exit_field(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Field>) {
+    fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
         self.current_args = None;
     }
 
-    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a>, &(ref arg_name, ref arg_value): &'a (Spanning<&'a str>, Spanning<InputValue>)) {
+    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref arg_name, ref arg_value): &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
         if let Some(argument_meta) = self.current_args
             .and_then(|args| args.iter().find(|a| a.name == arg_name.item))
         {
@@ -2,6 +2,7 @@ use ast::VariableDefinition;
 use types::utilities::is_valid_literal_value;
 use parser::Spanning;
 use validation::{Visitor, ValidatorContext};
+use value::ScalarValue;
 
 pub struct DefaultValuesOfCorrectType {
 }
@@ -11,15 +12,18 @@ pub fn factory() -> DefaultValuesOfCorrectType {
     }
 }
 
-impl<'a> Visitor<'a> for DefaultValuesOfCorrectType {
-    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, &(ref var_name, ref var_def): &'a (Spanning<&'a str>, VariableDefinition)) {
+impl<'a, S> Visitor<'a, S> for DefaultValuesOfCorrectType
+    where S: ScalarValue,
+{
+    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref var_name, ref var_def): &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         if let Some(Spanning { item: ref var_value, ref start, .. }) = var_def.default_value {
             if var_def.var_type.item.is_non_null() {
                 ctx.report_error(
                     &non_null_error_message(var_name.item, &format!("{}", var_def.var_type.item)),
                     &[start.clone()])
             }
-            else {
+            else if ctx.schema.type_by_name(var_def.var_type.item.innermost_name()).is_some() {
+                // Unknown types are reported by `KnownTypeNames`
                 let meta_type = ctx.schema.make_type(&var_def.var_type.item);
 
                 if !is_valid_literal_value(ctx.schema, &meta_type, var_value) {
@@ -151,4 +155,13 @@ mod tests {
             ]);
     }
 
+    #[test]
+    fn variables_of_unknown_types_with_default_values() {
+        expect_passes_rule(factory, r#"
+          query UnknownType($a: Unknown = 1, $b: [Unknown] = [1]) {
+            dog { name }
+          }
+        "#);
+    }
+
 }
@@ -1,5 +1,6 @@
 use ast::Field;
 use validation::{Visitor, ValidatorContext};
+use value::ScalarValue;
 use parser::Spanning;
 
 pub struct FieldsOnCorrectType {}
@@ -8,8 +9,10 @@ pub fn factory() -> FieldsOnCorrectType {
     FieldsOnCorrectType {}
 }
 
-impl<'a> Visitor<'a> for FieldsOnCorrectType {
-    fn enter_field(&mut self, context: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+impl<'a, S> Visitor<'a, S> for FieldsOnCorrectType
+    where S: ScalarValue,
+{
+    fn enter_field(&mut self, context: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
         {
             if let Some(parent_type) = context.parent_type() {
                 let field_name = &field.item.name;
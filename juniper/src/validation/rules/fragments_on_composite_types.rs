@@ -1,6 +1,7 @@
 use ast::{Fragment, InlineFragment};
 use parser::Spanning;
 use validation::{Visitor, ValidatorContext};
+use value::ScalarValue;
 
 pub struct FragmentsOnCompositeTypes {}
 
@@ -8,8 +9,10 @@ pub fn factory() -> FragmentsOnCompositeTypes {
     FragmentsOnCompositeTypes {}
 }
 
-impl<'a> Visitor<'a> for FragmentsOnCompositeTypes {
-    fn enter_fragment_definition(&mut self, context: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+impl<'a, S> Visitor<'a, S> for FragmentsOnCompositeTypes
+    where S: ScalarValue,
+{
+    fn enter_fragment_definition(&mut self, context: &mut ValidatorContext<'a, S>, f: &'a Spanning<Fragment<S>>) {
         {
             if let Some(current_type) = context.current_type() {
                 if !current_type.is_composite() {
@@ -26,7 +29,7 @@ impl<'a> Visitor<'a> for FragmentsOnCompositeTypes {
         }
     }
 
-    fn enter_inline_fragment(&mut self, context: &mut ValidatorContext<'a>, f: &'a Spanning<InlineFragment>) {
+    fn enter_inline_fragment(&mut self, context: &mut ValidatorContext<'a, S>, f: &'a Spanning<InlineFragment<S>>) {
         {
             if let Some(ref type_cond) = f.item.type_condition {
                 let invalid_type_name = context.current_type().iter()
@@ -2,6 +2,7 @@ use ast::{Field, InputValue, Directive};
 use schema::meta::Argument;
 use parser::Spanning;
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 
 #[derive(Debug)]
 enum ArgumentPosition<'a> {
@@ -9,28 +10,30 @@ enum ArgumentPosition<'a> {
     Field(&'a str, &'a str),
 }
 
-pub struct KnownArgumentNames<'a> {
-    current_args: Option<(ArgumentPosition<'a>, &'a Vec<Argument<'a>>)>,
+pub struct KnownArgumentNames<'a, S: 'a> {
+    current_args: Option<(ArgumentPosition<'a>, &'a Vec<Argument<'a, S>>)>,
 }
 
-pub fn factory<'a>() -> KnownArgumentNames<'a> {
+pub fn factory<'a, S>() -> KnownArgumentNames<'a, S> {
     KnownArgumentNames {
         current_args: None,
     }
 }
 
-impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
-    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a>, directive: &'a Spanning<Directive>) {
+impl<'a, S> Visitor<'a, S> for KnownArgumentNames<'a, S>
+    where S: ScalarValue,
+{
+    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, directive: &'a Spanning<Directive<S>>) {
         self.current_args = ctx.schema
             .directive_by_name(directive.item.name.item)
             .map(|d| (ArgumentPosition::Directive(directive.item.name.item), &d.arguments));
     }
 
-    fn exit_directive(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Directive>) {
+    fn exit_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
         self.current_args = None;
     }
 
-    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
         self.current_args = ctx.parent_type()
             .and_then(|t| t.field_by_name(field.item.name.item))
             .and_then(|f| f.arguments.as_ref())
@@ -42,11 +45,11 @@ impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
                 args));
     }
 
-    fn exit_field(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Field>) {
+    fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
         self.current_args = None;
     }
 
-    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a>, &(ref arg_name, _): &'a (Spanning<&'a str>, Spanning<InputValue>)) {
+    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref arg_name, _): &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
         if let Some((ref pos, args)) = self.current_args {
             if args.iter().find(|a| a.name == arg_name.item).is_none() {
                 let message = match *pos {
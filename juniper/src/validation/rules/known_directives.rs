@@ -1,5 +1,6 @@
 use ast::{Directive, Operation, OperationType, Fragment, FragmentSpread, Field, InlineFragment};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use schema::model::DirectiveLocation;
 use parser::Spanning;
 
@@ -13,56 +14,58 @@ pub fn factory() -> KnownDirectives {
     }
 }
 
-impl<'a> Visitor<'a> for KnownDirectives {
-    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+impl<'a, S> Visitor<'a, S> for KnownDirectives
+    where S: ScalarValue,
+{
+    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         self.location_stack.push(match op.item.operation_type {
             OperationType::Query => DirectiveLocation::Query,
             OperationType::Mutation => DirectiveLocation::Mutation,
         });
     }
 
-    fn exit_operation_definition(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Operation>) {
+    fn exit_operation_definition(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Operation<S>>) {
         let top = self.location_stack.pop();
         assert!(top == Some(DirectiveLocation::Query) || top == Some(DirectiveLocation::Mutation));
     }
 
-    fn enter_field(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Field>) {
+    fn enter_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
         self.location_stack.push(DirectiveLocation::Field);
     }
 
-    fn exit_field(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Field>) {
+    fn exit_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
         let top = self.location_stack.pop();
         assert_eq!(top, Some(DirectiveLocation::Field));
     }
 
-    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Fragment<S>>) {
         self.location_stack.push(DirectiveLocation::FragmentDefinition);
     }
 
-    fn exit_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Fragment>) {
+    fn exit_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Fragment<S>>) {
         let top = self.location_stack.pop();
         assert_eq!(top, Some(DirectiveLocation::FragmentDefinition));
     }
 
-    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<FragmentSpread<S>>) {
         self.location_stack.push(DirectiveLocation::FragmentSpread);
     }
 
-    fn exit_fragment_spread(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<FragmentSpread>) {
+    fn exit_fragment_spread(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<FragmentSpread<S>>) {
         let top = self.location_stack.pop();
         assert_eq!(top, Some(DirectiveLocation::FragmentSpread));
     }
 
-    fn enter_inline_fragment(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<InlineFragment>) {
+    fn enter_inline_fragment(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<InlineFragment<S>>) {
         self.location_stack.push(DirectiveLocation::InlineFragment);
     }
 
-    fn exit_inline_fragment(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<InlineFragment>) {
+    fn exit_inline_fragment(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<InlineFragment<S>>) {
         let top = self.location_stack.pop();
         assert_eq!(top, Some(DirectiveLocation::InlineFragment));
     }
 
-    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a>, directive: &'a Spanning<Directive>) {
+    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, directive: &'a Spanning<Directive<S>>) {
         let directive_name = &directive.item.name.item;
 
         if let Some(directive_type) = ctx.schema.directive_by_name(directive_name) {
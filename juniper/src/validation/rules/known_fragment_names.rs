@@ -1,5 +1,6 @@
 use ast::FragmentSpread;
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::Spanning;
 
 pub struct KnownFragmentNames {}
@@ -8,8 +9,10 @@ pub fn factory() -> KnownFragmentNames {
     KnownFragmentNames {}
 }
 
-impl<'a> Visitor<'a> for KnownFragmentNames {
-    fn enter_fragment_spread(&mut self, context: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+impl<'a, S> Visitor<'a, S> for KnownFragmentNames
+    where S: ScalarValue,
+{
+    fn enter_fragment_spread(&mut self, context: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>) {
         let spread_name = &spread.item.name;
         if !context.is_known_fragment(spread_name.item) {
             context.report_error(
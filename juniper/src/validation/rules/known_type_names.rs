@@ -1,5 +1,6 @@
 use ast::{Fragment, InlineFragment, VariableDefinition};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::{SourcePosition, Spanning};
 
 pub struct KnownTypeNames {}
@@ -8,25 +9,29 @@ pub fn factory() -> KnownTypeNames {
     KnownTypeNames {}
 }
 
-impl<'a> Visitor<'a> for KnownTypeNames {
-    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a>, fragment: &'a Spanning<InlineFragment>) {
+impl<'a, S> Visitor<'a, S> for KnownTypeNames
+    where S: ScalarValue,
+{
+    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a, S>, fragment: &'a Spanning<InlineFragment<S>>) {
         if let Some(ref type_cond) = fragment.item.type_condition {
             validate_type(ctx, type_cond.item, &type_cond.start);
         }
     }
 
-    fn enter_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a>, fragment: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, fragment: &'a Spanning<Fragment<S>>) {
         let type_cond = &fragment.item.type_condition;
         validate_type(ctx, type_cond.item, &type_cond.start);
     }
 
-    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, &(_, ref var_def): &'a (Spanning<&'a str>, VariableDefinition)) {
+    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, &(_, ref var_def): &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         let type_name = var_def.var_type.item.innermost_name();
         validate_type(ctx, type_name, &var_def.var_type.start);
     }
 }
 
-fn validate_type<'a>(ctx: &mut ValidatorContext<'a>, type_name: &str, location: &SourcePosition) {
+fn validate_type<'a, S>(ctx: &mut ValidatorContext<'a, S>, type_name: &str, location: &SourcePosition)
+    where S: ScalarValue,
+{
     if ctx.schema.type_by_name(type_name).is_none() {
         ctx.report_error(
             &error_message(type_name),
@@ -1,5 +1,6 @@
 use ast::{Definition, Document, Operation};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::Spanning;
 
 pub struct LoneAnonymousOperation {
@@ -12,8 +13,10 @@ pub fn factory() -> LoneAnonymousOperation {
     }
 }
 
-impl<'a> Visitor<'a> for LoneAnonymousOperation {
-    fn enter_document(&mut self, _: &mut ValidatorContext<'a>, doc: &'a Document) {
+impl<'a, S> Visitor<'a, S> for LoneAnonymousOperation
+    where S: ScalarValue,
+{
+    fn enter_document(&mut self, _: &mut ValidatorContext<'a, S>, doc: &'a Document<S>) {
         self.operation_count = Some(doc
             .iter()
             .filter(|d| match **d {
@@ -23,7 +26,7 @@ impl<'a> Visitor<'a> for LoneAnonymousOperation {
             .count());
     }
 
-    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         if let Some(operation_count) = self.operation_count {
             if operation_count > 1 && op.item.name.is_none() {
                 ctx.report_error(error_message(), &[op.start.clone()]);
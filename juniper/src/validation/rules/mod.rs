@@ -25,9 +25,12 @@ mod variables_in_allowed_position;
 
 use ast::Document;
 use validation::{ValidatorContext, MultiVisitor, MultiVisitorNil, visit};
+use value::ScalarValue;
 
 #[doc(hidden)]
-pub fn visit_all_rules<'a>(ctx: &mut ValidatorContext<'a>, doc: &'a Document) {
+pub fn visit_all_rules<'a, S>(ctx: &mut ValidatorContext<'a, S>, doc: &'a Document<S>)
+    where S: ScalarValue,
+{
     let mut mv = MultiVisitorNil
         .with(self::arguments_of_correct_type::factory())
         .with(self::default_values_of_correct_type::factory())
@@ -2,6 +2,7 @@ use std::collections::{HashMap, HashSet};
 
 use ast::{Fragment, FragmentSpread, Document};
 use validation::{ValidatorContext, Visitor, RuleError};
+use value::ScalarValue;
 use parser::Spanning;
 
 pub struct NoFragmentCycles<'a> {
@@ -25,8 +26,10 @@ pub fn factory<'a>() -> NoFragmentCycles<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a>, _: &'a Document) {
+impl<'a, S> Visitor<'a, S> for NoFragmentCycles<'a>
+    where S: ScalarValue,
+{
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, _: &'a Document<S>) {
         assert!(self.current_fragment.is_none());
 
         let mut detector = CycleDetector {
@@ -46,7 +49,7 @@ impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
         ctx.append_errors(detector.errors);
     }
 
-    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, fragment: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, fragment: &'a Spanning<Fragment<S>>) {
         assert!(self.current_fragment.is_none());
 
         let fragment_name = &fragment.item.name.item;
@@ -54,12 +57,12 @@ impl<'a> Visitor<'a> for NoFragmentCycles<'a> {
         self.fragment_order.push(fragment_name);
     }
 
-    fn exit_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, fragment: &'a Spanning<Fragment>) {
+    fn exit_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, fragment: &'a Spanning<Fragment<S>>) {
         assert_eq!(Some(fragment.item.name.item), self.current_fragment);
         self.current_fragment = None;
     }
 
-    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>) {
         if let Some(current_fragment) = self.current_fragment {
             self.spreads
                 .entry(current_fragment)
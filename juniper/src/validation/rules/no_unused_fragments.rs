@@ -2,6 +2,7 @@ use std::collections::{HashSet, HashMap};
 
 use ast::{Document, Definition, Operation, Fragment, FragmentSpread};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::Spanning;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -43,8 +44,10 @@ impl<'a> NoUnusedFragments<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for NoUnusedFragments<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a>, defs: &'a Document) {
+impl<'a, S> Visitor<'a, S> for NoUnusedFragments<'a>
+    where S: ScalarValue,
+{
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, defs: &'a Document<S>) {
         let mut reachable = HashSet::new();
 
         for def in defs {
@@ -63,12 +66,12 @@ impl<'a> Visitor<'a> for NoUnusedFragments<'a> {
         }
     }
 
-    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         let op_name = op.item.name.as_ref().map(|s| s.item.as_ref());
         self.current_scope = Some(Scope::Operation(op_name));
     }
 
-    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, f: &'a Spanning<Fragment<S>>) {
         self.current_scope = Some(Scope::Fragment(f.item.name.item));
         self.defined_fragments.insert(Spanning::start_end(
             &f.start,
@@ -76,7 +79,7 @@ impl<'a> Visitor<'a> for NoUnusedFragments<'a> {
             f.item.name.item));
     }
 
-    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>) {
         if let Some(ref scope) = self.current_scope {
             self.spreads.entry(scope.clone())
                 .or_insert_with(Vec::new)
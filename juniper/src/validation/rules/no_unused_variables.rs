@@ -1,6 +1,7 @@
 use std::collections::{HashSet, HashMap};
 use ast::{Document, Fragment, FragmentSpread, VariableDefinition, Operation, InputValue};
 use validation::{ValidatorContext, Visitor, RuleError};
+use value::ScalarValue;
 use parser::Spanning;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -49,8 +50,10 @@ impl<'a> NoUnusedVariables<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a>, _: &'a Document) {
+impl<'a, S> Visitor<'a, S> for NoUnusedVariables<'a>
+    where S: ScalarValue,
+{
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, _: &'a Document<S>) {
         for (op_name, def_vars) in &self.defined_variables {
             let mut used = HashSet::new();
             let mut visited = HashSet::new();
@@ -70,17 +73,17 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
         }
     }
 
-    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         let op_name = op.item.name.as_ref().map(|s| s.item);
         self.current_scope = Some(Scope::Operation(op_name));
         self.defined_variables.insert(op_name, HashSet::new());
     }
 
-    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, f: &'a Spanning<Fragment<S>>) {
         self.current_scope = Some(Scope::Fragment(f.item.name.item));
     }
 
-    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>) {
         if let Some(ref scope) = self.current_scope {
             self.spreads.entry(scope.clone())
                 .or_insert_with(Vec::new)
@@ -88,7 +91,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
         }
     }
 
-    fn enter_variable_definition(&mut self, _: &mut ValidatorContext<'a>, &(ref var_name, _): &'a (Spanning<&'a str>, VariableDefinition)) {
+    fn enter_variable_definition(&mut self, _: &mut ValidatorContext<'a, S>, &(ref var_name, _): &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         if let Some(Scope::Operation(ref name)) = self.current_scope {
             if let Some(vars) = self.defined_variables.get_mut(name) {
                 vars.insert(var_name);
@@ -96,7 +99,7 @@ impl<'a> Visitor<'a> for NoUnusedVariables<'a> {
         }
     }
 
-    fn enter_argument(&mut self, _: &mut ValidatorContext<'a>, &(_, ref value): &'a (Spanning<&'a str>, Spanning<InputValue>)) {
+    fn enter_argument(&mut self, _: &mut ValidatorContext<'a, S>, &(_, ref value): &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
         if let Some(ref scope) = self.current_scope {
             self.used_variables
                 .entry(scope.clone())
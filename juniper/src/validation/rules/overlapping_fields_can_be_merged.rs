@@ -1,9 +1,10 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::cell::RefCell;
 use std::hash::Hash;
 use std::borrow::Borrow;
 use ast::{Arguments, Document, Definition, Fragment, FragmentSpread, Selection, Field, Type};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::{SourcePosition, Spanning};
 use schema::meta::{Field as FieldType, MetaType};
 
@@ -14,9 +15,9 @@ struct Conflict(ConflictReason, Vec<SourcePosition>, Vec<SourcePosition>);
 struct ConflictReason(String, ConflictReasonMessage);
 
 #[derive(Debug)]
-struct AstAndDef<'a>(Option<&'a str>, &'a Spanning<Field<'a>>, Option<&'a FieldType<'a>>);
+struct AstAndDef<'a, S: 'a>(Option<&'a str>, &'a Spanning<Field<'a, S>>, Option<&'a FieldType<'a, S>>);
 
-type AstAndDefCollection<'a> = OrderedMap<&'a str, Vec<AstAndDef<'a>>>;
+type AstAndDefCollection<'a, S> = OrderedMap<&'a str, Vec<AstAndDef<'a, S>>>;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 enum ConflictReasonMessage {
@@ -115,24 +116,24 @@ impl<'a> PairSet<'a> {
     }
 }
 
-pub struct OverlappingFieldsCanBeMerged<'a> {
-    named_fragments: HashMap<&'a str, &'a Fragment<'a>>,
+pub struct OverlappingFieldsCanBeMerged<'a, S: 'a> {
+    named_fragments: HashMap<&'a str, &'a Fragment<'a, S>>,
     compared_fragments: RefCell<PairSet<'a>>,
 }
 
-pub fn factory<'a>() -> OverlappingFieldsCanBeMerged<'a> {
+pub fn factory<'a, S>() -> OverlappingFieldsCanBeMerged<'a, S> {
     OverlappingFieldsCanBeMerged {
         named_fragments: HashMap::new(),
         compared_fragments: RefCell::new(PairSet::new()),
     }
 }
 
-impl<'a> OverlappingFieldsCanBeMerged<'a> {
+impl<'a, S> OverlappingFieldsCanBeMerged<'a, S> where S: ScalarValue {
     fn find_conflicts_within_selection_set(
         &self,
-        parent_type: Option<&'a MetaType>,
-        selection_set: &'a [Selection],
-        ctx: &ValidatorContext<'a>,
+        parent_type: Option<&'a MetaType<S>>,
+        selection_set: &'a [Selection<S>],
+        ctx: &ValidatorContext<'a, S>,
     )
         -> Vec<Conflict>
     {
@@ -169,7 +170,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
         fragment_name1: &'a str,
         fragment_name2: &'a str,
         mutually_exclusive: bool,
-        ctx: &ValidatorContext<'a>,
+        ctx: &ValidatorContext<'a, S>,
     )
     {
         if fragment_name1 == fragment_name2 {
@@ -228,12 +229,38 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     fn collect_conflicts_between_fields_and_fragment(
         &self,
         conflicts: &mut Vec<Conflict>,
-        field_map: &AstAndDefCollection<'a>,
+        field_map: &AstAndDefCollection<'a, S>,
         fragment_name: &str,
         mutually_exclusive: bool,
-        ctx: &ValidatorContext<'a>,
+        ctx: &ValidatorContext<'a, S>,
     )
     {
+        self.collect_conflicts_between_fields_and_referenced_fragment(
+            conflicts,
+            field_map,
+            fragment_name,
+            mutually_exclusive,
+            &mut HashSet::new(),
+            ctx);
+    }
+
+    // Fragments that spread themselves are reported by `NoFragmentCycles`,
+    // but mustn't be followed forever
+    fn collect_conflicts_between_fields_and_referenced_fragment<'f>(
+        &self,
+        conflicts: &mut Vec<Conflict>,
+        field_map: &AstAndDefCollection<'a, S>,
+        fragment_name: &'f str,
+        mutually_exclusive: bool,
+        visited: &mut HashSet<&'f str>,
+        ctx: &ValidatorContext<'a, S>,
+    )
+        where 'a: 'f
+    {
+        if !visited.insert(fragment_name) {
+            return;
+        }
+
         let fragment = match self.named_fragments.get(fragment_name) {
             Some(f) => f,
             None => return,
@@ -249,11 +276,12 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
             ctx);
 
         for fragment_name2 in fragment_names2 {
-            self.collect_conflicts_between_fields_and_fragment(
+            self.collect_conflicts_between_fields_and_referenced_fragment(
                 conflicts,
                 field_map,
                 fragment_name2,
                 mutually_exclusive,
+                visited,
                 ctx);
         }
     }
@@ -262,9 +290,9 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
         &self,
         conflicts: &mut Vec<Conflict>,
         mutually_exclusive: bool,
-        field_map1: &AstAndDefCollection<'a>,
-        field_map2: &AstAndDefCollection<'a>,
-        ctx: &ValidatorContext<'a>,
+        field_map1: &AstAndDefCollection<'a, S>,
+        field_map2: &AstAndDefCollection<'a, S>,
+        ctx: &ValidatorContext<'a, S>,
     )
     {
         for (response_name, fields1) in field_map1.iter() {
@@ -283,8 +311,8 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     fn collect_conflicts_within(
         &self,
         conflicts: &mut Vec<Conflict>,
-        field_map: &AstAndDefCollection<'a>,
-        ctx: &ValidatorContext<'a>,
+        field_map: &AstAndDefCollection<'a, S>,
+        ctx: &ValidatorContext<'a, S>,
     )
     {
         for (response_name, fields) in field_map.iter() {
@@ -301,10 +329,10 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     fn find_conflict(
         &self,
         response_name: &str,
-        field1: &AstAndDef<'a>,
-        field2: &AstAndDef<'a>,
+        field1: &AstAndDef<'a, S>,
+        field2: &AstAndDef<'a, S>,
         parents_mutually_exclusive: bool,
-        ctx: &ValidatorContext<'a>,
+        ctx: &ValidatorContext<'a, S>,
     )
         -> Option<Conflict>
     {
@@ -379,10 +407,10 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
         &self,
         mutually_exclusive: bool,
         parent_type1: Option<&str>,
-        selection_set1: &'a [Selection],
+        selection_set1: &'a [Selection<S>],
         parent_type2: Option<&str>,
-        selection_set2: &'a [Selection],
-        ctx: &ValidatorContext<'a>,
+        selection_set2: &'a [Selection<S>],
+        ctx: &ValidatorContext<'a, S>,
     )
         -> Vec<Conflict>
     {
@@ -460,7 +488,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn is_type_conflict(
         &self,
-        ctx: &ValidatorContext<'a>,
+        ctx: &ValidatorContext<'a, S>,
         t1: &Type,
         t2: &Type,
     )
@@ -490,8 +518,8 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn is_same_arguments(
         &self,
-        args1: &Option<Spanning<Arguments>>,
-        args2: &Option<Spanning<Arguments>>,
+        args1: &Option<Spanning<Arguments<S>>>,
+        args2: &Option<Spanning<Arguments<S>>>,
     )
         -> bool
     {
@@ -517,7 +545,7 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn is_object_type(
         &self,
-        ctx: &ValidatorContext<'a>,
+        ctx: &ValidatorContext<'a, S>,
         type_name: Option<&str>,
     )
         -> bool
@@ -530,10 +558,10 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn get_referenced_fields_and_fragment_names(
         &self,
-        fragment: &'a Fragment,
-        ctx: &ValidatorContext<'a>,
+        fragment: &'a Fragment<S>,
+        ctx: &ValidatorContext<'a, S>,
     )
-        -> (AstAndDefCollection<'a>, Vec<&'a str>)
+        -> (AstAndDefCollection<'a, S>, Vec<&'a str>)
     {
         let fragment_type = ctx.schema.concrete_type_by_name(fragment.type_condition.item);
 
@@ -542,11 +570,11 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn get_fields_and_fragment_names(
         &self,
-        parent_type: Option<&'a MetaType>,
-        selection_set: &'a [Selection],
-        ctx: &ValidatorContext<'a>,
+        parent_type: Option<&'a MetaType<S>>,
+        selection_set: &'a [Selection<S>],
+        ctx: &ValidatorContext<'a, S>,
     )
-        -> (AstAndDefCollection<'a>, Vec<&'a str>)
+        -> (AstAndDefCollection<'a, S>, Vec<&'a str>)
     {
         let mut ast_and_defs = OrderedMap::new();
         let mut fragment_names = Vec::new();
@@ -558,10 +586,10 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
 
     fn collect_fields_and_fragment_names(
         &self,
-        parent_type: Option<&'a MetaType>,
-        selection_set: &'a [Selection],
-        ctx: &ValidatorContext<'a>,
-        ast_and_defs: &mut AstAndDefCollection<'a>,
+        parent_type: Option<&'a MetaType<S>>,
+        selection_set: &'a [Selection<S>],
+        ctx: &ValidatorContext<'a, S>,
+        ast_and_defs: &mut AstAndDefCollection<'a, S>,
         fragment_names: &mut Vec<&'a str>,
     )
     {
@@ -596,8 +624,10 @@ impl<'a> OverlappingFieldsCanBeMerged<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged<'a> {
-    fn enter_document(&mut self, _: &mut ValidatorContext<'a>, defs: &'a Document) {
+impl<'a, S> Visitor<'a, S> for OverlappingFieldsCanBeMerged<'a, S>
+    where S: ScalarValue,
+{
+    fn enter_document(&mut self, _: &mut ValidatorContext<'a, S>, defs: &'a Document<S>) {
         for def in defs {
             if let Definition::Fragment(Spanning { ref item, .. }) = *def {
                 self.named_fragments.insert(item.name.item, item);
@@ -605,7 +635,7 @@ impl<'a> Visitor<'a> for OverlappingFieldsCanBeMerged<'a> {
         }
     }
 
-    fn enter_selection_set(&mut self, ctx: &mut ValidatorContext<'a>, selection_set: &'a Vec<Selection>) {
+    fn enter_selection_set(&mut self, ctx: &mut ValidatorContext<'a, S>, selection_set: &'a Vec<Selection<S>>) {
         for Conflict(ConflictReason(reason_name, reason_msg), mut p1, mut p2)
             in self.find_conflicts_within_selection_set(ctx.parent_type(), selection_set, ctx)
         {
@@ -670,6 +700,22 @@ mod tests {
         "#);
     }
 
+    #[test]
+    fn fragments_spreading_themselves() {
+        expect_passes_rule(factory, r#"
+          fragment nameFragment on Dog {
+            name
+            ...nameFragment
+            ...otherNameFragment
+          }
+
+          fragment otherNameFragment on Dog {
+            name
+            ...nameFragment
+          }
+        "#);
+    }
+
     #[test]
     fn identical_fields_with_identical_args() {
         expect_passes_rule(factory, r#"
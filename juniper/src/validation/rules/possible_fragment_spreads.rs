@@ -1,21 +1,24 @@
 use std::collections::HashMap;
 use ast::{Document, Definition, InlineFragment, FragmentSpread};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::Spanning;
 use schema::meta::MetaType;
 
-pub struct PossibleFragmentSpreads<'a> {
-    fragment_types: HashMap<&'a str, &'a MetaType<'a>>,
+pub struct PossibleFragmentSpreads<'a, S: 'a> {
+    fragment_types: HashMap<&'a str, &'a MetaType<'a, S>>,
 }
 
-pub fn factory<'a>() -> PossibleFragmentSpreads<'a> {
+pub fn factory<'a, S>() -> PossibleFragmentSpreads<'a, S> {
     PossibleFragmentSpreads {
         fragment_types: HashMap::new(),
     }
 }
 
-impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
-    fn enter_document(&mut self, ctx: &mut ValidatorContext<'a>, defs: &'a Document) {
+impl<'a, S> Visitor<'a, S> for PossibleFragmentSpreads<'a, S>
+    where S: ScalarValue,
+{
+    fn enter_document(&mut self, ctx: &mut ValidatorContext<'a, S>, defs: &'a Document<S>) {
         for def in defs {
             if let Definition::Fragment(Spanning { ref item, .. }) = *def {
                 if let Some(t) = ctx.schema.concrete_type_by_name(item.type_condition.item) {
@@ -25,7 +28,7 @@ impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
         }
     }
 
-    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a>, frag: &'a Spanning<InlineFragment>) {
+    fn enter_inline_fragment(&mut self, ctx: &mut ValidatorContext<'a, S>, frag: &'a Spanning<InlineFragment<S>>) {
         if let (Some(parent_type), Some(frag_type))
             = (ctx.parent_type(), frag.item.type_condition.as_ref().and_then(|s| ctx.schema.concrete_type_by_name(s.item)))
         {
@@ -40,7 +43,7 @@ impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
         }
     }
 
-    fn enter_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, ctx: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>) {
         if let (Some(parent_type), Some(frag_type))
             = (ctx.parent_type(), self.fragment_types.get(spread.item.name.item))
         {
@@ -1,5 +1,6 @@
 use ast::{Field, Directive};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::Spanning;
 use schema::meta::{Field as FieldType};
 use schema::model::DirectiveType;
@@ -11,8 +12,10 @@ pub fn factory() -> ProvidedNonNullArguments {
     ProvidedNonNullArguments {}
 }
 
-impl<'a> Visitor<'a> for ProvidedNonNullArguments {
-    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+impl<'a, S> Visitor<'a, S> for ProvidedNonNullArguments
+    where S: ScalarValue,
+{
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
         let field_name = &field.item.name.item;
 
         if let Some(&FieldType { arguments: Some(ref meta_args), ..}) = ctx.parent_type().and_then(|t| t.field_by_name(field_name)) {
@@ -28,7 +31,7 @@ impl<'a> Visitor<'a> for ProvidedNonNullArguments {
         }
     }
 
-    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a>, directive: &'a Spanning<Directive>) {
+    fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a, S>, directive: &'a Spanning<Directive<S>>) {
         let directive_name = &directive.item.name.item;
 
         if let Some(&DirectiveType { arguments: ref meta_args, ..}) = ctx.schema.directive_by_name(directive_name) {
@@ -1,5 +1,6 @@
 use ast::Field;
 use validation::{ValidatorContext, Visitor, RuleError};
+use value::ScalarValue;
 use parser::Spanning;
 
 pub struct ScalarLeafs {}
@@ -8,8 +9,10 @@ pub fn factory() -> ScalarLeafs {
     ScalarLeafs {}
 }
 
-impl<'a> Visitor<'a> for ScalarLeafs {
-    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+impl<'a, S> Visitor<'a, S> for ScalarLeafs
+    where S: ScalarValue,
+{
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>) {
         let field_name = &field.item.name.item;
 
         let error = if let (Some(field_type), Some(field_type_literal)) = (ctx.current_type(), ctx.current_type_literal()) {
@@ -2,6 +2,7 @@ use std::collections::hash_map::{HashMap, Entry};
 
 use ast::{Directive, Field, InputValue};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::{SourcePosition, Spanning};
 
 pub struct UniqueArgumentNames<'a> {
@@ -14,16 +15,18 @@ pub fn factory<'a>() -> UniqueArgumentNames<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for UniqueArgumentNames<'a> {
-    fn enter_directive(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Directive>) {
+impl<'a, S> Visitor<'a, S> for UniqueArgumentNames<'a>
+    where S: ScalarValue,
+{
+    fn enter_directive(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Directive<S>>) {
         self.known_names = HashMap::new();
     }
 
-    fn enter_field(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Field>) {
+    fn enter_field(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Field<S>>) {
         self.known_names = HashMap::new();
     }
 
-    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a>, &(ref arg_name, _): &'a (Spanning<&'a str>, Spanning<InputValue>)) {
+    fn enter_argument(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref arg_name, _): &'a (Spanning<&'a str>, Spanning<InputValue<S>>)) {
         match self.known_names.entry(arg_name.item) {
             Entry::Occupied(e) => {
                 ctx.report_error(
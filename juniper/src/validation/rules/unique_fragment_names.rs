@@ -3,6 +3,7 @@ use std::collections::hash_map::{HashMap, Entry};
 use ast::Fragment;
 use parser::{SourcePosition, Spanning};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 
 pub struct UniqueFragmentNames<'a> {
     names: HashMap<&'a str, SourcePosition>,
@@ -14,8 +15,10 @@ pub fn factory<'a>() -> UniqueFragmentNames<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for UniqueFragmentNames<'a> {
-    fn enter_fragment_definition(&mut self, context: &mut ValidatorContext<'a>, f: &'a Spanning<Fragment>) {
+impl<'a, S> Visitor<'a, S> for UniqueFragmentNames<'a>
+    where S: ScalarValue,
+{
+    fn enter_fragment_definition(&mut self, context: &mut ValidatorContext<'a, S>, f: &'a Spanning<Fragment<S>>) {
         match self.names.entry(f.item.name.item) {
             Entry::Occupied(e) => {
                 context.report_error(
@@ -2,6 +2,7 @@ use std::collections::hash_map::{HashMap, Entry};
 
 use ast::InputValue;
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 use parser::{SourcePosition, Spanning};
 
 pub struct UniqueInputFieldNames<'a> {
@@ -14,16 +15,18 @@ pub fn factory<'a>() -> UniqueInputFieldNames<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for UniqueInputFieldNames<'a> {
-    fn enter_object_value(&mut self, _: &mut ValidatorContext<'a>, _: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue>)>>) {
+impl<'a, S> Visitor<'a, S> for UniqueInputFieldNames<'a>
+    where S: ScalarValue,
+{
+    fn enter_object_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue<S>>)>>) {
         self.known_name_stack.push(HashMap::new());
     }
 
-    fn exit_object_value(&mut self, _: &mut ValidatorContext<'a>, _: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue>)>>) {
+    fn exit_object_value(&mut self, _: &mut ValidatorContext<'a, S>, _: Spanning<&'a Vec<(Spanning<String>, Spanning<InputValue<S>>)>>) {
         self.known_name_stack.pop();
     }
 
-    fn enter_object_field(&mut self, ctx: &mut ValidatorContext<'a>, &(ref field_name, _): &'a (Spanning<String>, Spanning<InputValue>)) {
+    fn enter_object_field(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref field_name, _): &'a (Spanning<String>, Spanning<InputValue<S>>)) {
         if let Some(ref mut known_names) = self.known_name_stack.last_mut() {
             match known_names.entry(&field_name.item) {
                 Entry::Occupied(e) => {
@@ -3,6 +3,7 @@ use std::collections::hash_map::{HashMap, Entry};
 use ast::Operation;
 use parser::{SourcePosition, Spanning};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 
 pub struct UniqueOperationNames<'a> {
     names: HashMap<&'a str, SourcePosition>,
@@ -14,8 +15,10 @@ pub fn factory<'a>() -> UniqueOperationNames<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for UniqueOperationNames<'a> {
-    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+impl<'a, S> Visitor<'a, S> for UniqueOperationNames<'a>
+    where S: ScalarValue,
+{
+    fn enter_operation_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         if let Some(ref op_name) = op.item.name {
             match self.names.entry(op_name.item) {
                 Entry::Occupied(e) => {
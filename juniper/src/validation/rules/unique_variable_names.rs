@@ -3,6 +3,7 @@ use std::collections::hash_map::{HashMap, Entry};
 use ast::{Operation, VariableDefinition};
 use parser::{SourcePosition, Spanning};
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 
 pub struct UniqueVariableNames<'a> {
     names: HashMap<&'a str, SourcePosition>,
@@ -14,12 +15,14 @@ pub fn factory<'a>() -> UniqueVariableNames<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for UniqueVariableNames<'a> {
-    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a>, _: &'a Spanning<Operation>) {
+impl<'a, S> Visitor<'a, S> for UniqueVariableNames<'a>
+    where S: ScalarValue,
+{
+    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a, S>, _: &'a Spanning<Operation<S>>) {
         self.names = HashMap::new();
     }
 
-    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, &(ref var_name, _): &'a (Spanning<&'a str>, VariableDefinition)) {
+    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref var_name, _): &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         match self.names.entry(var_name.item) {
             Entry::Occupied(e) => {
                 ctx.report_error(
@@ -1,6 +1,7 @@
 use ast::VariableDefinition;
 use parser::Spanning;
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 
 pub struct UniqueVariableNames {}
 
@@ -8,8 +9,10 @@ pub fn factory() -> UniqueVariableNames {
     UniqueVariableNames {}
 }
 
-impl<'a> Visitor<'a> for UniqueVariableNames {
-    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a>, &(ref var_name, ref var_def): &'a (Spanning<&'a str>, VariableDefinition)) {
+impl<'a, S> Visitor<'a, S> for UniqueVariableNames
+    where S: ScalarValue,
+{
+    fn enter_variable_definition(&mut self, ctx: &mut ValidatorContext<'a, S>, &(ref var_name, ref var_def): &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         if let Some(var_type) = ctx.schema.concrete_type_by_name(var_def.var_type.item.innermost_name()) {
             if !var_type.is_input() {
                 ctx.report_error(
@@ -3,6 +3,7 @@ use std::collections::{HashSet, HashMap};
 use ast::{Type, VariableDefinition, Document, Fragment, Operation, FragmentSpread};
 use parser::Spanning;
 use validation::{ValidatorContext, Visitor};
+use value::ScalarValue;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Scope<'a> {
@@ -10,14 +11,14 @@ pub enum Scope<'a> {
     Fragment(&'a str),
 }
 
-pub struct VariableInAllowedPosition<'a> {
+pub struct VariableInAllowedPosition<'a, S: 'a> {
     spreads: HashMap<Scope<'a>, HashSet<&'a str>>,
     variable_usages: HashMap<Scope<'a>, Vec<(Spanning<&'a String>, Type<'a>)>>,
-    variable_defs: HashMap<Scope<'a>, Vec<&'a (Spanning<&'a str>, VariableDefinition<'a>)>>,
+    variable_defs: HashMap<Scope<'a>, Vec<&'a (Spanning<&'a str>, VariableDefinition<'a, S>)>>,
     current_scope: Option<Scope<'a>>,
 }
 
-pub fn factory<'a>() -> VariableInAllowedPosition<'a> {
+pub fn factory<'a, S>() -> VariableInAllowedPosition<'a, S> {
     VariableInAllowedPosition {
         spreads: HashMap::new(),
         variable_usages: HashMap::new(),
@@ -26,12 +27,12 @@ pub fn factory<'a>() -> VariableInAllowedPosition<'a> {
     }
 }
 
-impl<'a> VariableInAllowedPosition<'a> {
+impl<'a, S> VariableInAllowedPosition<'a, S> where S: ScalarValue {
     fn collect_incorrect_usages(
         &self,
         from: &Scope<'a>,
-        var_defs: &Vec<&'a (Spanning<&'a str>, VariableDefinition)>,
-        ctx: &mut ValidatorContext<'a>,
+        var_defs: &Vec<&'a (Spanning<&'a str>, VariableDefinition<S>)>,
+        ctx: &mut ValidatorContext<'a, S>,
         visited: &mut HashSet<Scope<'a>>,
     )
     {
@@ -70,22 +71,24 @@ impl<'a> VariableInAllowedPosition<'a> {
     }
 }
 
-impl<'a> Visitor<'a> for VariableInAllowedPosition<'a> {
-    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a>, _: &'a Document) {
+impl<'a, S> Visitor<'a, S> for VariableInAllowedPosition<'a, S>
+    where S: ScalarValue,
+{
+    fn exit_document(&mut self, ctx: &mut ValidatorContext<'a, S>, _: &'a Document<S>) {
         for (op_scope, var_defs) in &self.variable_defs {
             self.collect_incorrect_usages(op_scope, var_defs, ctx, &mut HashSet::new());
         }
     }
 
-    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a>, fragment: &'a Spanning<Fragment>) {
+    fn enter_fragment_definition(&mut self, _: &mut ValidatorContext<'a, S>, fragment: &'a Spanning<Fragment<S>>) {
         self.current_scope = Some(Scope::Fragment(fragment.item.name.item));
     }
 
-    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a>, op: &'a Spanning<Operation>) {
+    fn enter_operation_definition(&mut self, _: &mut ValidatorContext<'a, S>, op: &'a Spanning<Operation<S>>) {
         self.current_scope = Some(Scope::Operation(op.item.name.as_ref().map(|s| s.item)));
     }
 
-    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+    fn enter_fragment_spread(&mut self, _: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>) {
         if let Some(ref scope) = self.current_scope {
             self.spreads
                 .entry(scope.clone())
@@ -94,7 +97,7 @@ impl<'a> Visitor<'a> for VariableInAllowedPosition<'a> {
         }
     }
 
-    fn enter_variable_definition(&mut self, _: &mut ValidatorContext<'a>, def: &'a (Spanning<&'a str>, VariableDefinition)) {
+    fn enter_variable_definition(&mut self, _: &mut ValidatorContext<'a, S>, def: &'a (Spanning<&'a str>, VariableDefinition<S>)) {
         if let Some(ref scope) = self.current_scope {
             self.variable_defs
                 .entry(scope.clone())
@@ -103,7 +106,7 @@ impl<'a> Visitor<'a> for VariableInAllowedPosition<'a> {
         }
     }
 
-    fn enter_variable_value(&mut self, ctx: &mut ValidatorContext<'a>, var_name: Spanning<&'a String>) {
+    fn enter_variable_value(&mut self, ctx: &mut ValidatorContext<'a, S>, var_name: Spanning<&'a String>) {
         if let (&Some(ref scope), Some(input_type)) = (&self.current_scope, ctx.current_input_type_literal()) {
             self.variable_usages
                 .entry(scope.clone())
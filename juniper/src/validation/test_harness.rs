@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use parser::parse_document_source;
 use ast::{FromInputValue, InputValue};
 use types::base::GraphQLType;
@@ -6,6 +8,7 @@ use types::scalars::{EmptyMutation, ID};
 use schema::model::{DirectiveType, DirectiveLocation, RootNode};
 use schema::meta::{EnumValue, MetaType};
 use validation::{Visitor, RuleError, ValidatorContext, MultiVisitor, MultiVisitorNil, visit};
+use value::DefaultScalarValue;
 
 struct Being;
 struct Pet;
@@ -471,22 +474,22 @@ impl GraphQLType for QueryRoot {
 pub fn validate<'a, R, V, F>(r: R, q: &'a str, factory: F)
     -> Vec<RuleError>
     where R: GraphQLType<TypeInfo=()>,
-          V: Visitor<'a> + 'a,
+          V: Visitor<'a, DefaultScalarValue> + 'a,
           F: Fn() -> V
 {
     let mut root = RootNode::new(r, EmptyMutation::<()>::new());
 
-    root.schema.add_directive(DirectiveType::new("onQuery", &[DirectiveLocation::Query], &[]));
-    root.schema.add_directive(DirectiveType::new("onMutation", &[DirectiveLocation::Mutation], &[]));
-    root.schema.add_directive(DirectiveType::new("onField", &[DirectiveLocation::Field], &[]));
-    root.schema.add_directive(DirectiveType::new("onFragmentDefinition", &[DirectiveLocation::FragmentDefinition], &[]));
-    root.schema.add_directive(DirectiveType::new("onFragmentSpread", &[DirectiveLocation::FragmentSpread], &[]));
-    root.schema.add_directive(DirectiveType::new("onInlineFragment", &[DirectiveLocation::InlineFragment], &[]));
+    Arc::get_mut(&mut root.schema).unwrap().add_directive(DirectiveType::new("onQuery", &[DirectiveLocation::Query], &[]));
+    Arc::get_mut(&mut root.schema).unwrap().add_directive(DirectiveType::new("onMutation", &[DirectiveLocation::Mutation], &[]));
+    Arc::get_mut(&mut root.schema).unwrap().add_directive(DirectiveType::new("onField", &[DirectiveLocation::Field], &[]));
+    Arc::get_mut(&mut root.schema).unwrap().add_directive(DirectiveType::new("onFragmentDefinition", &[DirectiveLocation::FragmentDefinition], &[]));
+    Arc::get_mut(&mut root.schema).unwrap().add_directive(DirectiveType::new("onFragmentSpread", &[DirectiveLocation::FragmentSpread], &[]));
+    Arc::get_mut(&mut root.schema).unwrap().add_directive(DirectiveType::new("onInlineFragment", &[DirectiveLocation::InlineFragment], &[]));
 
     let doc = parse_document_source(q)
         .expect(&format!("Parse error on input {:#?}", q));
     let mut ctx = ValidatorContext::new(
-        unsafe { ::std::mem::transmute(&root.schema) },
+        unsafe { ::std::mem::transmute(&*root.schema) },
         &doc);
 
     let mut mv = MultiVisitorNil.with(factory());
@@ -496,7 +499,7 @@ pub fn validate<'a, R, V, F>(r: R, q: &'a str, factory: F)
 }
 
 pub fn expect_passes_rule<'a, V, F>(factory: F, q: &'a str)
-    where V: Visitor<'a> + 'a,
+    where V: Visitor<'a, DefaultScalarValue> + 'a,
           F: Fn() -> V
 {
     expect_passes_rule_with_schema(QueryRoot, factory, q);
@@ -504,7 +507,7 @@ pub fn expect_passes_rule<'a, V, F>(factory: F, q: &'a str)
 
 pub fn expect_passes_rule_with_schema<'a, R, V, F>(r: R, factory: F, q: &'a str)
     where R: GraphQLType<TypeInfo=()>,
-          V: Visitor<'a> + 'a,
+          V: Visitor<'a, DefaultScalarValue> + 'a,
           F: Fn() -> V
 {
     let errs = validate(r, q, factory);
@@ -516,7 +519,7 @@ pub fn expect_passes_rule_with_schema<'a, R, V, F>(r: R, factory: F, q: &'a str)
 }
 
 pub fn expect_fails_rule<'a, V, F>(factory: F, q: &'a str, expected_errors: &[RuleError])
-    where V: Visitor<'a> + 'a,
+    where V: Visitor<'a, DefaultScalarValue> + 'a,
           F: Fn() -> V
 {
     expect_fails_rule_with_schema(QueryRoot, factory, q, expected_errors);
@@ -524,7 +527,7 @@ pub fn expect_fails_rule<'a, V, F>(factory: F, q: &'a str, expected_errors: &[Ru
 
 pub fn expect_fails_rule_with_schema<'a, R, V, F>(r: R, factory: F, q: &'a str, expected_errors: &[RuleError])
     where R: GraphQLType<TypeInfo=()>,
-          V: Visitor<'a> + 'a,
+          V: Visitor<'a, DefaultScalarValue> + 'a,
           F: Fn() -> V
 {
     let errs = validate(r, q, factory);
@@ -5,16 +5,21 @@ use ast::{Definition, Document, Fragment, VariableDefinitions, Type, InputValue,
           Operation, OperationType};
 use schema::meta::Argument;
 use parser::Spanning;
+use value::ScalarValue;
 use validation::{Visitor, ValidatorContext};
 
 #[doc(hidden)]
-pub fn visit<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, d: &'a Document) {
+pub fn visit<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, d: &'a Document<S>)
+    where S: ScalarValue,
+{
     v.enter_document(ctx, d);
     visit_definitions(v, ctx, d);
     v.exit_document(ctx, d);
 }
 
-fn visit_definitions<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, d: &'a Vec<Definition>) {
+fn visit_definitions<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, d: &'a Vec<Definition<S>>)
+    where S: ScalarValue,
+{
     for def in d {
         let def_type = match *def {
                 Definition::Fragment(Spanning {
@@ -37,21 +42,27 @@ fn visit_definitions<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'
     }
 }
 
-fn enter_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, def: &'a Definition) {
+fn enter_definition<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, def: &'a Definition<S>)
+    where S: ScalarValue,
+{
     match *def {
         Definition::Operation(ref op) => v.enter_operation_definition(ctx, op),
         Definition::Fragment(ref f) => v.enter_fragment_definition(ctx, f),
     }
 }
 
-fn exit_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, def: &'a Definition) {
+fn exit_definition<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, def: &'a Definition<S>)
+    where S: ScalarValue,
+{
     match *def {
         Definition::Operation(ref op) => v.exit_operation_definition(ctx, op),
         Definition::Fragment(ref f) => v.exit_fragment_definition(ctx, f),
     }
 }
 
-fn visit_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, def: &'a Definition) {
+fn visit_definition<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, def: &'a Definition<S>)
+    where S: ScalarValue,
+{
     match *def {
         Definition::Operation(ref op) => {
             visit_variable_definitions(v, ctx, &op.item.variable_definitions);
@@ -65,7 +76,9 @@ fn visit_definition<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a
     }
 }
 
-fn visit_variable_definitions<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, defs: &'a Option<Spanning<VariableDefinitions>>) {
+fn visit_variable_definitions<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, defs: &'a Option<Spanning<VariableDefinitions<S>>>)
+    where S: ScalarValue,
+{
     if let Some(ref defs) = *defs {
         for def in defs.item.iter() {
             let var_type = def.1.var_type.item.clone();
@@ -83,7 +96,9 @@ fn visit_variable_definitions<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut Validator
     }
 }
 
-fn visit_directives<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, directives: &'a Option<Vec<Spanning<Directive>>>) {
+fn visit_directives<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, directives: &'a Option<Vec<Spanning<Directive<S>>>>)
+    where S: ScalarValue,
+{
     if let Some(ref directives) = *directives {
         for directive in directives {
             let directive_arguments = ctx.schema.directive_by_name(directive.item.name.item).map(|d| &d.arguments);
@@ -95,7 +110,9 @@ fn visit_directives<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a
     }
 }
 
-fn visit_arguments<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, meta_args: &Option<&Vec<Argument<'a>>>, arguments: &'a Option<Spanning<Arguments>>) {
+fn visit_arguments<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, meta_args: &Option<&Vec<Argument<'a, S>>>, arguments: &'a Option<Spanning<Arguments<S>>>)
+    where S: ScalarValue,
+{
     if let Some(ref arguments) = *arguments {
         for argument in arguments.item.iter() {
             let arg_type = meta_args
@@ -113,7 +130,9 @@ fn visit_arguments<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>
     }
 }
 
-fn visit_selection_set<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, selection_set: &'a Vec<Selection>) {
+fn visit_selection_set<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, selection_set: &'a Vec<Selection<S>>)
+    where S: ScalarValue,
+{
     ctx.with_pushed_parent_type(|ctx| {
         v.enter_selection_set(ctx, selection_set);
 
@@ -125,7 +144,9 @@ fn visit_selection_set<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext
     });
 }
 
-fn visit_selection<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, selection: &'a Selection) {
+fn visit_selection<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, selection: &'a Selection<S>)
+    where S: ScalarValue,
+{
     match *selection {
         Selection::Field(ref field) => visit_field(v, ctx, field),
         Selection::FragmentSpread(ref spread) => visit_fragment_spread(v, ctx, spread),
@@ -133,7 +154,9 @@ fn visit_selection<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>
     }
 }
 
-fn visit_field<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, field: &'a Spanning<Field>) {
+fn visit_field<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, field: &'a Spanning<Field<S>>)
+    where S: ScalarValue,
+{
     let meta_field = ctx.parent_type()
         .and_then(|t| t.field_by_name(field.item.name.item));
 
@@ -154,7 +177,9 @@ fn visit_field<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, fi
     });
 }
 
-fn visit_fragment_spread<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, spread: &'a Spanning<FragmentSpread>) {
+fn visit_fragment_spread<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, spread: &'a Spanning<FragmentSpread<S>>)
+    where S: ScalarValue,
+{
     v.enter_fragment_spread(ctx, spread);
 
     visit_directives(v, ctx, &spread.item.directives);
@@ -162,8 +187,10 @@ fn visit_fragment_spread<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorConte
     v.exit_fragment_spread(ctx, spread);
 }
 
-fn visit_inline_fragment<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, fragment: &'a Spanning<InlineFragment>) {
-    let mut visit_fn = move |ctx: &mut ValidatorContext<'a>| {
+fn visit_inline_fragment<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, fragment: &'a Spanning<InlineFragment<S>>)
+    where S: ScalarValue,
+{
+    let mut visit_fn = move |ctx: &mut ValidatorContext<'a, S>| {
         v.enter_inline_fragment(ctx, fragment);
 
         visit_directives(v, ctx, &fragment.item.directives);
@@ -180,7 +207,9 @@ fn visit_inline_fragment<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorConte
     }
 }
 
-fn visit_input_value<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, input_value: &'a Spanning<InputValue>) {
+fn visit_input_value<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, input_value: &'a Spanning<InputValue<S>>)
+    where S: ScalarValue,
+{
     enter_input_value(v, ctx, input_value);
 
     match input_value.item {
@@ -221,7 +250,9 @@ fn visit_input_value<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'
     exit_input_value(v, ctx, input_value);
 }
 
-fn enter_input_value<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, input_value: &'a Spanning<InputValue>) {
+fn enter_input_value<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, input_value: &'a Spanning<InputValue<S>>)
+    where S: ScalarValue,
+{
     use InputValue::*;
 
     let start = &input_value.start;
@@ -234,13 +265,16 @@ fn enter_input_value<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'
         String(ref s) => v.enter_string_value(ctx, Spanning::start_end(start, end, s)),
         Boolean(ref b) => v.enter_boolean_value(ctx, Spanning::start_end(start, end, *b)),
         Enum(ref s) => v.enter_enum_value(ctx, Spanning::start_end(start, end, s)),
+        Scalar(ref s) => v.enter_scalar_value(ctx, Spanning::start_end(start, end, s)),
         Variable(ref s) => v.enter_variable_value(ctx, Spanning::start_end(start, end, s)),
         List(ref l) => v.enter_list_value(ctx, Spanning::start_end(start, end, l)),
         Object(ref o) => v.enter_object_value(ctx, Spanning::start_end(start, end, o)),
     }
 }
 
-fn exit_input_value<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a>, input_value: &'a Spanning<InputValue>) {
+fn exit_input_value<'a, S, V: Visitor<'a, S>>(v: &mut V, ctx: &mut ValidatorContext<'a, S>, input_value: &'a Spanning<InputValue<S>>)
+    where S: ScalarValue,
+{
     use InputValue::*;
 
     let start = &input_value.start;
@@ -253,6 +287,7 @@ fn exit_input_value<'a, V: Visitor<'a>>(v: &mut V, ctx: &mut ValidatorContext<'a
         String(ref s) => v.exit_string_value(ctx, Spanning::start_end(start, end, s)),
         Boolean(ref b) => v.exit_boolean_value(ctx, Spanning::start_end(start, end, *b)),
         Enum(ref s) => v.exit_enum_value(ctx, Spanning::start_end(start, end, s)),
+        Scalar(ref s) => v.exit_scalar_value(ctx, Spanning::start_end(start, end, s)),
         Variable(ref s) => v.exit_variable_value(ctx, Spanning::start_end(start, end, s)),
         List(ref l) => v.exit_list_value(ctx, Spanning::start_end(start, end, l)),
         Object(ref o) => v.exit_object_value(ctx, Spanning::start_end(start, end, o)),
@@ -1,9 +1,95 @@
 use std::collections::HashMap;
-use std::hash::Hash;
+use std::fmt;
+use std::iter::FromIterator;
+use std::mem;
+use std::slice;
+use std::sync::Arc;
+use std::vec;
+
+use serde::ser;
 
 use parser::Spanning;
 use ast::{InputValue, ToInputValue};
 
+/// Application-defined representation of scalar values
+///
+/// `Value` and `InputValue` natively carry the built-in `Int`, `Float`,
+/// `String`, and `Boolean` scalars. Values that can't be represented
+/// losslessly by those, e.g. 64-bit integers or arbitrary precision decimals,
+/// can be carried in the `Scalar` variant of both types instead. The type
+/// stored there is chosen by the schema through this trait, and passed along
+/// unchanged from the query parser, through the executor, and into the
+/// serialized response.
+///
+/// Schemas that don't need this use `DefaultScalarValue`, which can't hold any
+/// value at all.
+pub trait ScalarValue
+    : fmt::Debug + fmt::Display + Clone + PartialEq + Send + Sync + ser::Serialize + 'static
+{
+    /// Construct a scalar from an integer literal that doesn't fit in an `Int`
+    ///
+    /// This is consulted both by the query parser and when deserializing
+    /// variables. Returning `None` rejects the literal, which is what the
+    /// default implementation does.
+    fn from_int_literal(_: &str) -> Option<Self> {
+        None
+    }
+
+    /// Construct a scalar from a float literal, as written in the query
+    ///
+    /// This is consulted for every float literal, so that e.g. a decimal
+    /// scalar can keep all of its digits, before it becomes a `Float`.
+    /// Variables are deserialized into an `f64` first, and are handed to
+    /// this method as that float formats. Returning `None` makes the literal
+    /// a `Float`, which is what the default implementation does.
+    fn from_float_literal(_: &str) -> Option<Self> {
+        None
+    }
+
+    /// The value of the scalar as a `Float`, if it has one
+    ///
+    /// Lets `Float` arguments accept the literals `from_float_literal` turned
+    /// into scalars. The default implementation returns `None`.
+    fn as_float(&self) -> Option<f64> {
+        None
+    }
+}
+
+/// The scalar value representation used unless a schema asks for another one
+///
+/// This type has no values, so schemas using it only ever see the built-in
+/// scalar variants of `Value` and `InputValue`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefaultScalarValue {}
+
+impl fmt::Display for DefaultScalarValue {
+    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+        match *self {}
+    }
+}
+
+impl ser::Serialize for DefaultScalarValue {
+    fn serialize<S>(&self, _: S) -> Result<S::Ok, S::Error>
+        where S: ser::Serializer,
+    {
+        match *self {}
+    }
+}
+
+impl ScalarValue for DefaultScalarValue {}
+
+/// A step on the way from the root of a `Value` to one of its descendants
+///
+/// Visitors such as `Value::visit_mut` receive the path to the value being
+/// visited as a slice of these.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PathSegment<'a> {
+    /// A field of an object
+    Field(&'a str),
+    /// An element of a list
+    Index(usize),
+}
+
 /// Serializable value returned from query and field execution.
 ///
 /// Used by the execution engine and resolvers to build up the response
@@ -13,47 +99,48 @@ use ast::{InputValue, ToInputValue};
 /// values or variables. Also, lists and objects do not contain any location
 /// information since they are generated by resolving fields and values rather
 /// than parsing a source query.
-#[derive(Debug, PartialEq)]
+///
+/// Scalars that don't map to any of the built-in variants are stored in the
+/// `Scalar` variant, see `ScalarValue`.
+#[derive(Clone, Debug, PartialEq)]
 #[allow(missing_docs)]
-pub enum Value {
+pub enum Value<S = DefaultScalarValue> {
     Null,
     Int(i32),
     Float(f64),
     String(String),
     Boolean(bool),
-    List(Vec<Value>),
-    Object(HashMap<String, Value>),
+    List(Vec<Value<S>>),
+    Object(Object<S>),
+    Scalar(S),
 }
 
-impl Value {
+impl<S> Value<S> where S: ScalarValue {
     // CONSTRUCTORS
 
     /// Construct a null value.
-    pub fn null() -> Value { Value::Null }
+    pub fn null() -> Value<S> { Value::Null }
 
     /// Construct an integer value.
-    pub fn int(i: i32) -> Value { Value::Int(i) }
+    pub fn int(i: i32) -> Value<S> { Value::Int(i) }
 
     /// Construct a floating point value.
-    pub fn float(f: f64) -> Value { Value::Float(f) }
+    pub fn float(f: f64) -> Value<S> { Value::Float(f) }
 
     /// Construct a string value.
-    pub fn string<T: AsRef<str>>(s: T) -> Value { Value::String(s.as_ref().to_owned()) }
+    pub fn string<T: AsRef<str>>(s: T) -> Value<S> { Value::String(s.as_ref().to_owned()) }
 
     /// Construct a boolean value.
-    pub fn boolean(b: bool) -> Value { Value::Boolean(b) }
+    pub fn boolean(b: bool) -> Value<S> { Value::Boolean(b) }
 
     /// Construct a list value.
-    pub fn list(l: Vec<Value>) -> Value { Value::List(l) }
+    pub fn list(l: Vec<Value<S>>) -> Value<S> { Value::List(l) }
 
     /// Construct an object value.
-    pub fn object<K>(o: HashMap<K, Value>) -> Value
-        where K: Into<String> + Eq + Hash
-    {
-        Value::Object(
-            o.into_iter().map(|(k, v)| (k.into(), v)).collect()
-        )
-    }
+    pub fn object(o: Object<S>) -> Value<S> { Value::Object(o) }
+
+    /// Construct a custom scalar value.
+    pub fn scalar(s: S) -> Value<S> { Value::Scalar(s) }
 
     // DISCRIMINATORS
 
@@ -66,7 +153,7 @@ impl Value {
     }
 
     /// View the underlying object value, if present.
-    pub fn as_object_value(&self) -> Option<&HashMap<String, Value>> {
+    pub fn as_object_value(&self) -> Option<&Object<S>> {
         match *self {
             Value::Object(ref o) => Some(o),
             _ => None,
@@ -74,7 +161,7 @@ impl Value {
     }
 
     /// Mutable view into the underlying object value, if present.
-    pub fn as_mut_object_value(&mut self) -> Option<&mut HashMap<String, Value>> {
+    pub fn as_mut_object_value(&mut self) -> Option<&mut Object<S>> {
         match *self {
             Value::Object(ref mut o) => Some(o),
             _ => None,
@@ -82,13 +169,21 @@ impl Value {
     }
 
     /// View the underlying list value, if present.
-    pub fn as_list_value(&self) -> Option<&Vec<Value>> {
+    pub fn as_list_value(&self) -> Option<&Vec<Value<S>>> {
         match *self {
             Value::List(ref l) => Some(l),
             _ => None,
         }
     }
 
+    /// View the underlying custom scalar value, if present.
+    pub fn as_scalar_value(&self) -> Option<&S> {
+        match *self {
+            Value::Scalar(ref s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// View the underlying string value, if present.
     pub fn as_string_value(&self) -> Option<&str> {
         match *self {
@@ -96,10 +191,136 @@ impl Value {
             _ => None,
         }
     }
+
+    /// View the underlying integer value, if present.
+    pub fn as_int_value(&self) -> Option<i32> {
+        match *self {
+            Value::Int(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// View the underlying float value, if present.
+    pub fn as_float_value(&self) -> Option<f64> {
+        match *self {
+            Value::Float(f) => Some(f),
+            _ => None,
+        }
+    }
+
+    /// View the underlying boolean value, if present.
+    pub fn as_boolean_value(&self) -> Option<bool> {
+        match *self {
+            Value::Boolean(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    // NAVIGATION
+
+    /// Get a field of an object value
+    ///
+    /// Returns `None` if this isn't an object or the field doesn't exist.
+    pub fn get(&self, key: &str) -> Option<&Value<S>> {
+        self.as_object_value().and_then(|o| o.get(key))
+    }
+
+    /// Look up a nested value by a JSON Pointer
+    ///
+    /// The pointer is a `/`-separated path of object keys and list indices,
+    /// e.g. `/hero/friends/0/name`, where `~1` and `~0` escape `/` and `~` in
+    /// keys. The empty pointer refers to the value itself.
+    pub fn pointer(&self, pointer: &str) -> Option<&Value<S>> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+
+        pointer[1..].split('/')
+            .map(|token| token.replace("~1", "/").replace("~0", "~"))
+            .fold(Some(self), |value, token| match value {
+                Some(&Value::Object(ref o)) => o.get(&token),
+                Some(&Value::List(ref l)) => parse_list_index(&token).and_then(|i| l.get(i)),
+                _ => None,
+            })
+    }
+
+    // TRANSFORMATION
+
+    /// Visit every value in the tree, allowing the callback to modify it
+    ///
+    /// Values are visited before their children, and the callback receives
+    /// the path from the root to the value. Children are visited after the
+    /// callback returns, so replacing an object or list with another value
+    /// skips its original contents:
+    ///
+    /// ```rust
+    /// # #[macro_use] extern crate juniper;
+    /// # use juniper::{Value, PathSegment};
+    /// # fn main() {
+    /// let mut value: Value = graphql_value!({
+    ///     "user": { "name": "Leia", "password": { "hash": "abc" } },
+    /// });
+    ///
+    /// value.visit_mut(|path, v| {
+    ///     if path.last() == Some(&PathSegment::Field("password")) {
+    ///         *v = Value::null();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(value, graphql_value!({ "user": { "name": "Leia", "password": null } }));
+    /// # }
+    /// ```
+    pub fn visit_mut<F>(&mut self, mut f: F) where F: FnMut(&[PathSegment], &mut Value<S>) {
+        visit_mut_at(self, &mut Vec::new(), &mut f);
+    }
+
+    /// Rebuild the tree by passing every value through a function
+    ///
+    /// This is the by-value counterpart of `visit_mut`: each value is handed
+    /// to the callback along with its path, and replaced by the result before
+    /// its children are visited.
+    pub fn map_values<F>(mut self, mut f: F) -> Value<S> where F: FnMut(&[PathSegment], Value<S>) -> Value<S> {
+        self.visit_mut(|path, v| {
+            let old = mem::replace(v, Value::Null);
+            *v = f(path, old);
+        });
+        self
+    }
+
+    // COMPARISON
+
+    /// Compare two values while ignoring the order of object fields
+    ///
+    /// Lists are still compared element by element, since their order is
+    /// significant.
+    pub fn structural_eq(&self, other: &Value<S>) -> bool {
+        self.structural_eq_with_tolerance(other, 0.0)
+    }
+
+    /// Compare two values like `structural_eq`, treating floats that differ by
+    /// at most `epsilon` as equal
+    pub fn structural_eq_with_tolerance(&self, other: &Value<S>, epsilon: f64) -> bool {
+        match (self, other) {
+            (&Value::Float(a), &Value::Float(b)) => a == b || (a - b).abs() <= epsilon,
+            (&Value::List(ref a), &Value::List(ref b)) => {
+                a.len() == b.len() &&
+                    a.iter().zip(b.iter()).all(|(a, b)| a.structural_eq_with_tolerance(b, epsilon))
+            },
+            (&Value::Object(ref a), &Value::Object(ref b)) => {
+                a.len() == b.len() &&
+                    a.iter().all(|&(ref k, ref a)| b.get(k)
+                        .map_or(false, |b| a.structural_eq_with_tolerance(b, epsilon)))
+            },
+            (a, b) => a == b,
+        }
+    }
 }
 
-impl ToInputValue for Value {
-    fn to(&self) -> InputValue {
+impl<S> ToInputValue<S> for Value<S> where S: ScalarValue {
+    fn to(&self) -> InputValue<S> {
         match *self {
             Value::Null => InputValue::Null,
             Value::Int(i) => InputValue::Int(i),
@@ -108,8 +329,368 @@ impl ToInputValue for Value {
             Value::Boolean(b) => InputValue::Boolean(b),
             Value::List(ref l) => InputValue::List(l.iter().map(|x|
                 Spanning::unlocated(x.to())).collect()),
-            Value::Object(ref o) => InputValue::Object(o.iter().map(|(k,v)|
-                (Spanning::unlocated(k.clone()), Spanning::unlocated(v.to()))).collect()),
+            Value::Object(ref o) => InputValue::Object(o.iter().map(|&(ref k, ref v)|
+                (Spanning::unlocated(k.to_string()), Spanning::unlocated(v.to()))).collect()),
+            Value::Scalar(ref s) => InputValue::Scalar(s.clone()),
         }
     }
 }
+
+impl<'a, S> From<&'a str> for Value<S> where S: ScalarValue {
+    fn from(s: &'a str) -> Value<S> {
+        Value::string(s)
+    }
+}
+
+impl<S> From<String> for Value<S> where S: ScalarValue {
+    fn from(s: String) -> Value<S> {
+        Value::String(s)
+    }
+}
+
+impl<S> From<bool> for Value<S> where S: ScalarValue {
+    fn from(b: bool) -> Value<S> {
+        Value::boolean(b)
+    }
+}
+
+impl<S> From<i32> for Value<S> where S: ScalarValue {
+    fn from(i: i32) -> Value<S> {
+        Value::int(i)
+    }
+}
+
+impl<S> From<f64> for Value<S> where S: ScalarValue {
+    fn from(f: f64) -> Value<S> {
+        Value::float(f)
+    }
+}
+
+impl<T, S> From<Option<T>> for Value<S> where Value<S>: From<T>, S: ScalarValue {
+    fn from(v: Option<T>) -> Value<S> {
+        match v {
+            Some(v) => v.into(),
+            None => Value::null(),
+        }
+    }
+}
+
+/// Fields of an object value, in insertion order
+///
+/// The GraphQL specification requires response objects to be serialized with
+/// their fields in the order they were requested in the query. Resolved
+/// objects add their fields in that order, and inserting a field that is
+/// already present replaces its value without moving it.
+///
+/// Keys are reference counted, so objects built by the executor share a single
+/// allocation for each field name in the query. Objects with more than a few
+/// fields keep an index of them, so looking fields up stays cheap.
+///
+/// Two objects are only equal if they have the same fields in the same order,
+/// use `Value::structural_eq` to ignore the order.
+#[derive(Clone)]
+pub struct Object<S = DefaultScalarValue> {
+    fields: Vec<(Arc<str>, Value<S>)>,
+    index: Option<HashMap<Arc<str>, usize>>,
+}
+
+/// Iterator over the fields of an object with mutable values, see
+/// `Object::iter_mut`
+pub struct ObjectIterMut<'a, S: 'a>(slice::IterMut<'a, (Arc<str>, Value<S>)>);
+
+// Objects with up to this many fields are searched by scanning them
+const OBJECT_INDEX_THRESHOLD: usize = 16;
+
+impl<S> Object<S> {
+    /// Create an empty object
+    pub fn new() -> Object<S> {
+        Object { fields: Vec::new(), index: None }
+    }
+
+    /// Create an empty object with room for `capacity` fields
+    pub fn with_capacity(capacity: usize) -> Object<S> {
+        Object { fields: Vec::with_capacity(capacity), index: None }
+    }
+
+    /// Add a field, returning the previous value if it was already present
+    pub fn insert<K>(&mut self, key: K, value: Value<S>) -> Option<Value<S>> where K: Into<Arc<str>> {
+        let key = key.into();
+
+        if let Some(i) = self.position(&key) {
+            return Some(mem::replace(&mut self.fields[i].1, value));
+        }
+
+        if let Some(ref mut index) = self.index {
+            index.insert(key.clone(), self.fields.len());
+        }
+        self.fields.push((key, value));
+
+        if self.index.is_none() && self.fields.len() > OBJECT_INDEX_THRESHOLD {
+            self.index = Some(self.fields.iter().enumerate().map(|(i, &(ref k, _))| (k.clone(), i)).collect());
+        }
+
+        None
+    }
+
+    /// Get the value of a field
+    pub fn get(&self, key: &str) -> Option<&Value<S>> {
+        self.position(key).map(|i| &self.fields[i].1)
+    }
+
+    /// Get a mutable reference to the value of a field
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Value<S>> {
+        match self.position(key) {
+            Some(i) => Some(&mut self.fields[i].1),
+            None => None,
+        }
+    }
+
+    /// Is there a field with this name?
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// The number of fields
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Does this object have no fields?
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Iterate over the fields in order
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, (Arc<str>, Value<S>)> {
+        self.fields.iter()
+    }
+
+    /// Iterate over the fields in order, with mutable values
+    pub fn iter_mut<'a>(&'a mut self) -> ObjectIterMut<'a, S> {
+        ObjectIterMut(self.fields.iter_mut())
+    }
+
+    fn position(&self, key: &str) -> Option<usize> {
+        match self.index {
+            Some(ref index) => index.get(key).cloned(),
+            None => self.fields.iter().position(|&(ref k, _)| &**k == key),
+        }
+    }
+}
+
+impl<S> fmt::Debug for Object<S> where S: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Object").field("fields", &self.fields).finish()
+    }
+}
+
+impl<S> PartialEq for Object<S> where S: PartialEq {
+    fn eq(&self, other: &Object<S>) -> bool {
+        self.fields == other.fields
+    }
+}
+
+impl<'a, S> Iterator for ObjectIterMut<'a, S> {
+    type Item = (&'a Arc<str>, &'a mut Value<S>);
+
+    fn next(&mut self) -> Option<(&'a Arc<str>, &'a mut Value<S>)> {
+        self.0.next().map(|&mut (ref k, ref mut v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<S> Default for Object<S> {
+    fn default() -> Object<S> {
+        Object::new()
+    }
+}
+
+impl<K, S> FromIterator<(K, Value<S>)> for Object<S> where K: Into<Arc<str>> {
+    fn from_iter<I>(iter: I) -> Object<S> where I: IntoIterator<Item=(K, Value<S>)> {
+        let mut object = Object::new();
+
+        for (k, v) in iter {
+            object.insert(k, v);
+        }
+
+        object
+    }
+}
+
+impl<S> IntoIterator for Object<S> {
+    type Item = (Arc<str>, Value<S>);
+    type IntoIter = vec::IntoIter<(Arc<str>, Value<S>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.into_iter()
+    }
+}
+
+impl<'a, S> IntoIterator for &'a Object<S> {
+    type Item = &'a (Arc<str>, Value<S>);
+    type IntoIter = slice::Iter<'a, (Arc<str>, Value<S>)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.fields.iter()
+    }
+}
+
+fn visit_mut_at<'a, S, F>(value: &'a mut Value<S>, path: &mut Vec<PathSegment<'a>>, f: &mut F)
+    where F: FnMut(&[PathSegment], &mut Value<S>)
+{
+    f(path, value);
+
+    match *value {
+        Value::List(ref mut l) => for (i, v) in l.iter_mut().enumerate() {
+            path.push(PathSegment::Index(i));
+            visit_mut_at(v, path, f);
+            path.pop();
+        },
+        Value::Object(ref mut o) => for (k, v) in o.iter_mut() {
+            path.push(PathSegment::Field(k));
+            visit_mut_at(v, path, f);
+            path.pop();
+        },
+        _ => (),
+    }
+}
+
+// List indices in pointers are plain decimal numbers without leading zeros.
+fn parse_list_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() > 1) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Value, Object, PathSegment};
+
+    fn hero() -> Value {
+        Value::object(vec![
+            ("hero", Value::object(vec![
+                ("name", Value::string("R2-D2")),
+                ("friends", Value::list(vec![
+                    Value::object(vec![
+                        ("name", Value::string("Luke Skywalker")),
+                    ].into_iter().collect()),
+                ])),
+                ("a/b", Value::int(1)),
+            ].into_iter().collect())),
+        ].into_iter().collect())
+    }
+
+    #[test]
+    fn test_get() {
+        let value = hero();
+
+        assert_eq!(
+            value.get("hero").and_then(|v| v.get("name")).and_then(Value::as_string_value),
+            Some("R2-D2"));
+        assert_eq!(value.get("villain"), None);
+        assert_eq!(value.pointer("/hero/name").and_then(|v| v.get("hero")), None);
+    }
+
+    #[test]
+    fn test_structural_eq() {
+        let a: Value = Value::object(vec![
+            ("x", Value::float(0.1 + 0.2)),
+            ("y", Value::list(vec![Value::int(1), Value::int(2)])),
+        ].into_iter().collect());
+        let b: Value = Value::object(vec![
+            ("y", Value::list(vec![Value::int(1), Value::int(2)])),
+            ("x", Value::float(0.3)),
+        ].into_iter().collect());
+        let c: Value = Value::object(vec![
+            ("x", Value::float(0.3)),
+            ("y", Value::list(vec![Value::int(2), Value::int(1)])),
+        ].into_iter().collect());
+
+        assert!(a != b);
+        assert!(!a.structural_eq(&b));
+        assert!(a.structural_eq_with_tolerance(&b, 1e-9));
+        assert!(!b.structural_eq(&c));
+        assert!(hero().structural_eq(&hero()));
+
+        let one: Value = Value::int(1);
+        assert!(!one.structural_eq(&Value::float(1.0)));
+    }
+
+    #[test]
+    fn test_visit_mut_paths() {
+        let mut value = hero();
+        let mut paths = Vec::new();
+
+        value.visit_mut(|path, _| paths.push(format!("{:?}", path)));
+
+        assert_eq!(paths, vec![
+            "[]",
+            r#"[Field("hero")]"#,
+            r#"[Field("hero"), Field("name")]"#,
+            r#"[Field("hero"), Field("friends")]"#,
+            r#"[Field("hero"), Field("friends"), Index(0)]"#,
+            r#"[Field("hero"), Field("friends"), Index(0), Field("name")]"#,
+            r#"[Field("hero"), Field("a/b")]"#,
+        ]);
+    }
+
+    #[test]
+    fn test_map_values() {
+        let value = hero().map_values(|path, v| match v {
+            Value::String(ref s) if s.len() > 4 => Value::string(format!("{}...", &s[..4])),
+            _ if path.last() == Some(&PathSegment::Field("friends")) => Value::list(vec![]),
+            v => v,
+        });
+
+        assert_eq!(
+            value,
+            Value::object(vec![
+                ("hero", Value::object(vec![
+                    ("name", Value::string("R2-D...")),
+                    ("friends", Value::list(vec![])),
+                    ("a/b", Value::int(1)),
+                ].into_iter().collect())),
+            ].into_iter().collect()));
+    }
+
+    #[test]
+    fn test_pointer() {
+        let value = hero();
+
+        assert_eq!(value.pointer(""), Some(&value));
+        assert_eq!(
+            value.pointer("/hero/friends/0/name").and_then(Value::as_string_value),
+            Some("Luke Skywalker"));
+        assert_eq!(value.pointer("/hero/a~1b").and_then(Value::as_int_value), Some(1));
+        assert_eq!(value.pointer("/hero/friends/1"), None);
+        assert_eq!(value.pointer("/hero/friends/00"), None);
+        assert_eq!(value.pointer("/hero/name/0"), None);
+        assert_eq!(value.pointer("hero"), None);
+    }
+
+    #[test]
+    fn test_wide_objects() {
+        let mut object: Object = (0..100).map(|i| (format!("f{}", i), Value::int(i))).collect();
+
+        assert_eq!(object.insert("f50", Value::int(-1)), Some(Value::int(50)));
+        assert_eq!(object.insert("f100", Value::int(100)), None);
+        assert_eq!(object.len(), 101);
+        assert_eq!(object.get("f50"), Some(&Value::int(-1)));
+        assert_eq!(object.get("f100"), Some(&Value::int(100)));
+        assert_eq!(object.get("f101"), None);
+        assert_eq!(&*object.iter().nth(50).unwrap().0, "f50");
+
+        for (_, v) in object.iter_mut() {
+            *v = Value::null();
+        }
+        assert_eq!(object.get("f99"), Some(&Value::null()));
+
+        let reversed: Object = object.clone().into_iter().rev().collect();
+        assert!(object != reversed);
+        assert!(Value::object(object).structural_eq(&Value::object(reversed)));
+    }
+}
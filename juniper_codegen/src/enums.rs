@@ -9,11 +9,16 @@ use ::util::*;
 struct EnumAttrs {
     name: Option<String>,
     description: Option<String>,
+    rename_all: RenameRule,
+    name_converter: Option<Path>,
+    union: bool,
+    context: Option<Ty>,
 }
 
 impl EnumAttrs {
     fn from_input(input: &DeriveInput) -> EnumAttrs {
         let mut res = EnumAttrs::default();
+        let mut has_rename_all = false;
 
         // Check attributes for name and description.
         if let Some(items) = get_graphl_attr(&input.attrs) {
@@ -26,11 +31,45 @@ impl EnumAttrs {
                     res.description = Some(val);
                     continue;
                 }
+                if let Some(val) = keyed_item_value(item, "rename_all", true) {
+                    res.rename_all = match RenameRule::from_str(&val) {
+                        Some(rule) => rule,
+                        None => panic!(format!(
+                            "Unknown value for #[graphql(rename_all)]: {:?}",
+                            val)),
+                    };
+                    has_rename_all = true;
+                    continue;
+                }
+                if let Some(val) = keyed_item_value(item, "name_converter", true) {
+                    res.name_converter = Some(parse_name_converter(&val));
+                    continue;
+                }
+                if is_word_item(item, "union") {
+                    res.union = true;
+                    continue;
+                }
+                if let Some(val) = keyed_item_value(item, "context", true) {
+                    res.context = match parse_type(&val) {
+                        Ok(ty) => Some(ty),
+                        Err(_) => panic!("#[graphql(context = ?) must be a valid Rust type inside a string"),
+                    };
+                    continue;
+                }
                 panic!(format!(
                     "Unknown attribute for #[derive(GraphQLEnum)]: {:?}",
                     item));
             }
         }
+        if has_rename_all && res.name_converter.is_some() {
+            panic!("#[graphql(rename_all)] can't be combined with name_converter");
+        }
+        if res.context.is_some() && !res.union {
+            panic!("#[graphql(context)] is only supported on unions");
+        }
+        if res.union && (has_rename_all || res.name_converter.is_some()) {
+            panic!("#[graphql(union)] can't be combined with rename_all or name_converter");
+        }
         res
     }
 }
@@ -82,6 +121,9 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
     // Parse attributes.
     let ident = &ast.ident;
     let attrs = EnumAttrs::from_input(ast);
+    if attrs.union {
+        return impl_union(ast, variants, attrs);
+    }
     let name = attrs.name.unwrap_or(ast.ident.to_string());
 
     let mut values = Vec::<Tokens>::new();
@@ -98,8 +140,21 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
         let var_attrs = EnumVariantAttrs::from_input(variant);
         let var_ident = &variant.ident;
 
-        // Build value.
-        let name = var_attrs.name.unwrap_or(variant.ident.as_ref().to_uppercase());
+        // Build value. Names produced by a name converter are only known at
+        // runtime, so they are matched with a guard instead of a pattern.
+        let (name, from_pattern) = match (var_attrs.name, attrs.name_converter.as_ref()) {
+            (Some(name), _) => (quote!{ #name }, quote!{ Some(#name) }),
+            (None, Some(converter)) => {
+                let rust_name = variant.ident.as_ref();
+                let name = quote!{ #converter(#rust_name).as_str() };
+                let pattern = quote!{ Some(name) if name == #name };
+                (name, pattern)
+            },
+            (None, None) => {
+                let name = attrs.rename_all.apply_to_variant(variant.ident.as_ref());
+                (quote!{ #name }, quote!{ Some(#name) })
+            },
+        };
         let descr = match var_attrs.description {
             Some(s) => quote!{ Some(#s.to_string())  },
             None => quote!{ None },
@@ -110,7 +165,7 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
         };
         let value = quote!{
             ::juniper::meta::EnumValue{
-                name: #name.to_string(),
+                name: ::juniper::meta::Name::new(#name),
                 description: #descr,
                 deprecation_reason: #depr,
             },
@@ -125,7 +180,7 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
 
         // Buil from_input clause.
         let from_input = quote!{
-            Some(#name) => Some(#ident::#var_ident),
+            #from_pattern => Some(#ident::#var_ident),
         };
         from_inputs.push(from_input);
 
@@ -138,7 +193,7 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
     };
 
     quote! {
-        impl ::juniper::GraphQLType for #ident {
+        impl<__S> ::juniper::GraphQLType<__S> for #ident where __S: ::juniper::ScalarValue {
             type Context = ();
             type TypeInfo = ();
 
@@ -146,22 +201,22 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
                 Some(#name)
             }
 
-            fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r>) -> ::juniper::meta::MetaType<'r> {
+            fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r, __S>) -> ::juniper::meta::MetaType<'r, __S> {
                 registry.build_enum_type::<#ident>(&[
                     #(#values)*
                 ])
                 .into_meta()
             }
 
-            fn resolve(&self, _: &(), _: Option<&[::juniper::Selection]>, _: &::juniper::Executor<Self::Context>) -> ::juniper::Value {
+            fn resolve(&self, _: &(), _: Option<&[::juniper::Selection<__S>]>, _: &::juniper::Executor<Self::Context, __S>) -> ::juniper::Value<__S> {
                 match self {
                     #(#resolves)*
                 }
             }
         }
 
-        impl ::juniper::FromInputValue for #ident {
-            fn from(v: &::juniper::InputValue) -> Option<#ident> {
+        impl<__S> ::juniper::FromInputValue<__S> for #ident where __S: ::juniper::ScalarValue {
+            fn from(v: &::juniper::InputValue<__S>) -> Option<#ident> {
                 match v.as_enum_value().or_else(|| v.as_string_value()) {
                     #(#from_inputs)*
                     _ => None,
@@ -169,8 +224,8 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
             }
         }
 
-        impl ::juniper::ToInputValue for #ident {
-            fn to(&self) -> ::juniper::InputValue {
+        impl<__S> ::juniper::ToInputValue<__S> for #ident where __S: ::juniper::ScalarValue {
+            fn to(&self) -> ::juniper::InputValue<__S> {
                 match self {
                     #(#to_inputs)*
                 }
@@ -178,3 +233,98 @@ pub fn impl_enum(ast: &syn::DeriveInput) -> Tokens {
         }
     }
 }
+
+// Enums with `#[graphql(union)]` become unions of their variants' payload types.
+fn impl_union(ast: &syn::DeriveInput, variants: &Vec<Variant>, attrs: EnumAttrs) -> Tokens {
+    let ident = &ast.ident;
+    let name = attrs.name.unwrap_or(ast.ident.to_string());
+    let context = match attrs.context {
+        Some(ty) => quote!{ #ty },
+        None => quote!{ () },
+    };
+    let descr = match attrs.description {
+        Some(s) => quote!{ mt = mt.description(#s); },
+        None => quote!{},
+    };
+
+    let mut types = Vec::<Tokens>::new();
+    let mut type_bounds = Vec::<Tokens>::new();
+    let mut concrete_names = Vec::<Tokens>::new();
+    let mut resolves = Vec::<Tokens>::new();
+
+    for variant in variants {
+        let ty = match variant.data {
+            VariantData::Tuple(ref fields) if fields.len() == 1 => &fields[0].ty,
+            _ => {
+                panic!(format!(
+                    "Invalid union variant {}.\nGraphQL unions may only contain variants with a single unnamed field.",
+                    variant.ident));
+            },
+        };
+        let var_ident = &variant.ident;
+
+        types.push(quote!{
+            registry.get_type::<#ty>(),
+        });
+        type_bounds.push(quote!{
+            #ty: ::juniper::GraphQLType<__S, TypeInfo=()>,
+            <#ty as ::juniper::GraphQLType<__S>>::Context: ::juniper::FromContext<#context>,
+        });
+        concrete_names.push(quote!{
+            #ident::#var_ident(_) =>
+                <#ty as ::juniper::GraphQLType<__S>>::name(&()).unwrap().to_owned(),
+        });
+        resolves.push(quote!{
+            #ident::#var_ident(ref v)
+                if type_name == <#ty as ::juniper::GraphQLType<__S>>::name(&()).unwrap() =>
+                executor.resolve_with_ctx(&(), v),
+        });
+    }
+
+    let type_bounds = quote!{ #(#type_bounds)* };
+
+    quote! {
+        impl<__S> ::juniper::GraphQLType<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #type_bounds
+        {
+            type Context = #context;
+            type TypeInfo = ();
+
+            fn name(_: &()) -> Option<&'static str> {
+                Some(#name)
+            }
+
+            #[allow(unused_mut)]
+            fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r, __S>) -> ::juniper::meta::MetaType<'r, __S> {
+                let types = &[
+                    #(#types)*
+                ];
+                let mut mt = registry.build_union_type::<#ident>(types);
+                #descr
+                mt.into_meta()
+            }
+
+            fn concrete_type_name(&self, _: &Self::Context) -> String {
+                match *self {
+                    #(#concrete_names)*
+                }
+            }
+
+            fn resolve_into_type(
+                &self,
+                _: &(),
+                type_name: &str,
+                _: Option<&[::juniper::Selection<__S>]>,
+                executor: &::juniper::Executor<Self::Context, __S>,
+            )
+                -> ::juniper::ExecutionResult<__S>
+            {
+                match *self {
+                    #(#resolves)*
+                    _ => Ok(::juniper::Value::null()),
+                }
+            }
+        }
+    }
+}
@@ -9,6 +9,7 @@ use ::util::*;
 struct ObjAttrs {
     name: Option<String>,
     description: Option<String>,
+    name_converter: Option<Path>,
 }
 
 impl ObjAttrs {
@@ -26,6 +27,10 @@ impl ObjAttrs {
                     res.description = Some(val);
                     continue;
                 }
+                if let Some(val) = keyed_item_value(item, "name_converter", true) {
+                    res.name_converter = Some(parse_name_converter(&val));
+                    continue;
+                }
                 panic!(format!(
                     "Unknown attribute for #[derive(GraphQLInputObject)]: {:?}",
                     item));
@@ -40,6 +45,8 @@ struct ObjFieldAttrs {
     name: Option<String>,
     description: Option<String>,
     default: Option<String>,
+    flatten: bool,
+    skip: bool,
 }
 
 impl ObjFieldAttrs {
@@ -61,11 +68,29 @@ impl ObjFieldAttrs {
                     res.default = Some(val);
                     continue;
                 }
+                if is_word_item(item, "default") {
+                    res.default = Some("::std::default::Default::default()".to_owned());
+                    continue;
+                }
+                if is_word_item(item, "flatten") {
+                    res.flatten = true;
+                    continue;
+                }
+                if is_word_item(item, "skip") {
+                    res.skip = true;
+                    continue;
+                }
                 panic!(format!(
                     "Unknown attribute for #[derive(GraphQLInputObject)]: {:?}",
                     item));
             }
         }
+        if res.flatten && (res.name.is_some() || res.default.is_some()) {
+            panic!("#[graphql(flatten)] can't be combined with name or default");
+        }
+        if res.skip && (res.flatten || res.name.is_some() || res.default.is_some()) {
+            panic!("#[graphql(skip)] can't be combined with other field attributes");
+        }
         res
     }
 }
@@ -88,26 +113,65 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
     // Parse attributes.
     let ident = &ast.ident;
     let attrs = ObjAttrs::from_input(ast);
-    let name = attrs.name.unwrap_or(ast.ident.to_string());
+    let name = attrs.name.clone().unwrap_or(ast.ident.to_string());
 
     let mut meta_fields = Vec::<Tokens>::new();
     let mut from_inputs = Vec::<Tokens>::new();
     let mut to_inputs = Vec::<Tokens>::new();
+    let mut field_bounds = Vec::<Tokens>::new();
 
     for field in fields {
         let field_ty = &field.ty;
         let field_attrs = ObjFieldAttrs::from_input(field);
         let field_ident = field.ident.as_ref().unwrap();
 
+        // Skipped fields are not exposed and always take their default value.
+        if field_attrs.skip {
+            from_inputs.push(quote!{
+                #field_ident: ::std::default::Default::default(),
+            });
+            continue;
+        }
+
+        field_bounds.push(quote!{
+            #field_ty: ::juniper::GraphQLType<__S, TypeInfo=()>
+                + ::juniper::FromInputValue<__S> + ::juniper::ToInputValue<__S>,
+        });
+
+        // Flattened fields are read from and written to the parent object.
+        if field_attrs.flatten {
+            meta_fields.push(quote!{
+                fields.extend(registry.flattened_args::<#field_ty>());
+            });
+            from_inputs.push(quote!{
+                #field_ident: match ::juniper::FromInputValue::from(value) {
+                    Some(v) => v,
+                    None => return None,
+                },
+            });
+            to_inputs.push(quote!{
+                if let ::juniper::InputValue::Object(o) = self.#field_ident.to() {
+                    fields.extend(o.into_iter().map(|(k, v)| (k.item, v.item)));
+                }
+            });
+            continue;
+        }
+
         // Build value.
-        let name = match field_attrs.name {
-            Some(ref name) => {
+        let name = match (field_attrs.name, attrs.name_converter.as_ref()) {
+            (Some(ref name), _) => {
                 // Custom name specified.
-                name.to_string()
+                quote!{ #name }
             },
-            None => {
+            (None, Some(converter)) => {
+                // Custom naming function, called at runtime.
+                let rust_name = field_ident.as_ref();
+                quote!{ #converter(#rust_name).as_str() }
+            },
+            (None, None) => {
                 // Note: auto camel casing when no custom name specified.
-                ::util::to_camel_case(field_ident.as_ref())
+                let name = ::util::to_camel_case(field_ident.as_ref());
+                quote!{ #name }
             },
         };
         let descr = match field_attrs.description {
@@ -130,12 +194,12 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
         let meta_field = match default {
             Some(ref def) => {
                 quote!{
-                    registry.arg_with_default::<#field_ty>( #name, &#def),
+                    fields.push(registry.arg_with_default::<#field_ty>( #name, &#def));
                 }
             },
             None => {
                 quote!{
-                    registry.arg::<#field_ty>(#name),
+                    fields.push(registry.arg::<#field_ty>(#name));
                 }
             }
         };
@@ -158,7 +222,7 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
                 match obj.get(#name) {
                     #from_input_default
                     Some(v) => ::juniper::FromInputValue::from(v).unwrap(),
-                    _ => ::juniper::FromInputValue::from(&::juniper::InputValue::null()).unwrap()
+                    _ => ::juniper::FromInputValue::from_implicit_null().unwrap()
                 }
             },
         };
@@ -166,13 +230,18 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
 
         // Build to_input clause.
         let to_input = quote!{
-            (#name, self.#field_ident.to()),
+            fields.push((#name.to_owned(), self.#field_ident.to()));
         };
         to_inputs.push(to_input);
     }
 
+    let field_bounds = quote!{ #(#field_bounds)* };
+
     quote! {
-        impl ::juniper::GraphQLType for #ident {
+        impl<__S> ::juniper::GraphQLType<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #field_bounds
+        {
             type Context = ();
             type TypeInfo = ();
 
@@ -180,16 +249,18 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
                 Some(#name)
             }
 
-            fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r>) -> ::juniper::meta::MetaType<'r> {
-                let fields = &[
-                    #(#meta_fields)*
-                ];
-                registry.build_input_object_type::<#ident>(fields).into_meta()
+            fn meta<'r>(_: &(), registry: &mut ::juniper::Registry<'r, __S>) -> ::juniper::meta::MetaType<'r, __S> {
+                let mut fields = Vec::new();
+                #(#meta_fields)*
+                registry.build_input_object_type::<#ident>(&fields).into_meta()
             }
         }
 
-        impl ::juniper::FromInputValue for #ident {
-            fn from(value: &::juniper::InputValue) -> Option<#ident> {
+        impl<__S> ::juniper::FromInputValue<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #field_bounds
+        {
+            fn from(value: &::juniper::InputValue<__S>) -> Option<#ident> {
                 if let Some(obj) = value.to_object_value() {
                     let item = #ident {
                         #(#from_inputs)*
@@ -202,11 +273,14 @@ pub fn impl_input_object(ast: &syn::DeriveInput) -> Tokens {
             }
         }
 
-        impl ::juniper::ToInputValue for #ident {
-            fn to(&self) -> ::juniper::InputValue {
-                ::juniper::InputValue::object(vec![
-                    #(#to_inputs)*
-                ].into_iter().collect())
+        impl<__S> ::juniper::ToInputValue<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #field_bounds
+        {
+            fn to(&self) -> ::juniper::InputValue<__S> {
+                let mut fields = Vec::<(String, ::juniper::InputValue<__S>)>::new();
+                #(#to_inputs)*
+                ::juniper::InputValue::object(fields.into_iter().collect())
             }
         }
     }
@@ -8,6 +8,7 @@ extern crate quote;
 mod util;
 mod enums;
 mod input_objects;
+mod scalar_values;
 
 use proc_macro::TokenStream;
 
@@ -26,3 +27,11 @@ pub fn derive_input_object(input: TokenStream) -> TokenStream {
     let gen = input_objects::impl_input_object(&ast);
     gen.parse().unwrap()
 }
+
+#[proc_macro_derive(GraphQLScalarValue, attributes(graphql))]
+pub fn derive_scalar_value(input: TokenStream) -> TokenStream {
+    let s = input.to_string();
+    let ast = syn::parse_derive_input(&s).unwrap();
+    let gen = scalar_values::impl_scalar_value(&ast);
+    gen.parse().unwrap()
+}
@@ -0,0 +1,106 @@
+use syn;
+use syn::*;
+use quote::Tokens;
+
+use ::util::*;
+
+
+#[derive(Default, Debug)]
+struct ScalarAttrs {
+    name: Option<String>,
+    description: Option<String>,
+}
+
+impl ScalarAttrs {
+    fn from_input(input: &DeriveInput) -> ScalarAttrs {
+        let mut res = ScalarAttrs::default();
+
+        // Check attributes for name and description.
+        if let Some(items) = get_graphl_attr(&input.attrs) {
+            for item in items {
+                if let Some(val) = keyed_item_value(item, "name", true) {
+                    res.name = Some(val);
+                    continue;
+                }
+                if let Some(val) = keyed_item_value(item, "description", true) {
+                    res.description = Some(val);
+                    continue;
+                }
+                panic!(format!(
+                    "Unknown attribute for #[derive(GraphQLScalarValue)]: {:?}",
+                    item));
+            }
+        }
+        res
+    }
+}
+
+pub fn impl_scalar_value(ast: &syn::DeriveInput) -> Tokens {
+    let field = match ast.body {
+        Body::Struct(VariantData::Tuple(ref fields)) if fields.len() == 1 => &fields[0],
+        Body::Struct(_) => {
+            panic!("#[derive(GraphQLScalarValue)] may only be applied to tuple structs with a single field");
+        },
+        Body::Enum(_) => {
+            panic!("#[derive(GraphQLScalarValue)] may only be applied to structs, not to enums");
+        },
+    };
+
+    // Parse attributes.
+    let ident = &ast.ident;
+    let inner_ty = &field.ty;
+    let attrs = ScalarAttrs::from_input(ast);
+    let name = attrs.name.unwrap_or(ast.ident.to_string());
+
+    let description = match attrs.description {
+        Some(ref descr) => quote!{ .description(#descr) },
+        None => quote!{},
+    };
+
+    quote! {
+        impl<__S> ::juniper::GraphQLType<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #inner_ty: ::juniper::GraphQLType<__S, Context=(), TypeInfo=()>,
+        {
+            type Context = ();
+            type TypeInfo = ();
+
+            fn name(_: &()) -> Option<&'static str> {
+                Some(#name)
+            }
+
+            fn meta<'r>(info: &(), registry: &mut ::juniper::Registry<'r, __S>) -> ::juniper::meta::MetaType<'r, __S> {
+                registry.build_scalar_type::<#ident>(info)
+                    #description
+                    .into_meta()
+            }
+
+            fn resolve(
+                &self,
+                info: &(),
+                selection_set: Option<&[::juniper::Selection<__S>]>,
+                executor: &::juniper::Executor<Self::Context, __S>,
+            ) -> ::juniper::Value<__S> {
+                <#inner_ty as ::juniper::GraphQLType<__S>>::resolve(&self.0, info, selection_set, executor)
+            }
+        }
+
+        impl<__S> ::juniper::FromInputValue<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #inner_ty: ::juniper::FromInputValue<__S>,
+        {
+            fn from(v: &::juniper::InputValue<__S>) -> Option<#ident> {
+                <#inner_ty as ::juniper::FromInputValue<__S>>::from(v).map(#ident)
+            }
+        }
+
+        impl<__S> ::juniper::ToInputValue<__S> for #ident
+            where __S: ::juniper::ScalarValue,
+                  #inner_ty: ::juniper::ToInputValue<__S>,
+        {
+            fn to(&self) -> ::juniper::InputValue<__S> {
+                ::juniper::ToInputValue::to(&self.0)
+            }
+        }
+    }
+}
@@ -47,6 +47,23 @@ pub fn keyed_item_value(item: &NestedMetaItem, name: &str, must_be_string: bool)
     }
 }
 
+pub fn is_word_item(item: &NestedMetaItem, name: &str) -> bool {
+    match item {
+        &NestedMetaItem::MetaItem(MetaItem::Word(ref ident)) => ident == name,
+        _ => false,
+    }
+}
+
+/// Parse the function path given in `#[graphql(name_converter = "...")]`
+pub fn parse_name_converter(val: &str) -> Path {
+    match parse_path(val) {
+        Ok(path) => path,
+        Err(_) => {
+            panic!("#[graphql(name_converter = ?) must be a path to a function inside a string");
+        },
+    }
+}
+
 // Note: duplicated from juniper crate!
 #[doc(hidden)]
 pub fn to_camel_case(s: &str) -> String {
@@ -71,6 +88,79 @@ pub fn to_camel_case(s: &str) -> String {
     dest
 }
 
+/// Casing convention applied to enum values without an explicit name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenameRule {
+    /// Keep the Rust variant name as is
+    None,
+    /// `lowercase`
+    LowerCase,
+    /// `UPPERCASE`
+    UpperCase,
+    /// `camelCase`
+    CamelCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `SCREAMING_SNAKE_CASE`, the GraphQL convention and the default
+    ScreamingSnakeCase,
+}
+
+impl RenameRule {
+    pub fn from_str(rule: &str) -> Option<RenameRule> {
+        match rule {
+            "none" => Some(RenameRule::None),
+            "lowercase" => Some(RenameRule::LowerCase),
+            "UPPERCASE" => Some(RenameRule::UpperCase),
+            "camelCase" => Some(RenameRule::CamelCase),
+            "snake_case" => Some(RenameRule::SnakeCase),
+            "SCREAMING_SNAKE_CASE" => Some(RenameRule::ScreamingSnakeCase),
+            _ => None,
+        }
+    }
+
+    /// Rename a `PascalCase` variant name according to this rule
+    pub fn apply_to_variant(&self, variant: &str) -> String {
+        match *self {
+            RenameRule::None => variant.to_owned(),
+            RenameRule::LowerCase => variant.to_lowercase(),
+            RenameRule::UpperCase => variant.to_uppercase(),
+            RenameRule::CamelCase => {
+                let mut chars = variant.chars();
+                match chars.next() {
+                    Some(first) => first.to_lowercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            },
+            RenameRule::SnakeCase => to_snake_case(variant),
+            RenameRule::ScreamingSnakeCase => to_snake_case(variant).to_uppercase(),
+        }
+    }
+}
+
+impl Default for RenameRule {
+    fn default() -> RenameRule {
+        RenameRule::ScreamingSnakeCase
+    }
+}
+
+fn to_snake_case(s: &str) -> String {
+    let mut dest = String::new();
+
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                dest.push('_');
+            }
+            dest.extend(c.to_lowercase());
+        }
+        else {
+            dest.push(c);
+        }
+    }
+
+    dest
+}
+
 #[test]
 fn test_to_camel_case() {
     assert_eq!(&to_camel_case("test")[..], "test");
@@ -83,3 +173,15 @@ fn test_to_camel_case() {
     assert_eq!(&to_camel_case("a")[..], "a");
     assert_eq!(&to_camel_case("")[..], "");
 }
+
+#[test]
+fn test_rename_rules() {
+    assert_eq!(&RenameRule::None.apply_to_variant("NewHope")[..], "NewHope");
+    assert_eq!(&RenameRule::LowerCase.apply_to_variant("NewHope")[..], "newhope");
+    assert_eq!(&RenameRule::UpperCase.apply_to_variant("NewHope")[..], "NEWHOPE");
+    assert_eq!(&RenameRule::CamelCase.apply_to_variant("NewHope")[..], "newHope");
+    assert_eq!(&RenameRule::SnakeCase.apply_to_variant("NewHope")[..], "new_hope");
+    assert_eq!(&RenameRule::ScreamingSnakeCase.apply_to_variant("NewHope")[..], "NEW_HOPE");
+    assert_eq!(&RenameRule::ScreamingSnakeCase.apply_to_variant("Jedi")[..], "JEDI");
+    assert_eq!(&RenameRule::ScreamingSnakeCase.apply_to_variant("A")[..], "A");
+}
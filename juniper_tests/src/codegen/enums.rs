@@ -15,32 +15,103 @@ enum SomeEnum {
   Full,
 }
 
+#[derive(GraphQLEnum, Debug, PartialEq)]
+enum Episode {
+  NewHope,
+  EmpireStrikesBack,
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(rename_all="camelCase")]
+enum Ordering {
+  MostRecent,
+  #[graphql(name="TOP")]
+  MostPopular,
+}
+
+fn prefixed(name: &str) -> String {
+  format!("STATUS_{}", name.to_uppercase())
+}
+
+#[derive(GraphQLEnum, Debug, PartialEq)]
+#[graphql(name_converter="prefixed")]
+enum Status {
+  Active,
+  #[graphql(name="GONE")]
+  Deleted,
+}
+
 #[test]
 fn test_derived_enum() {
   // Ensure that rename works.
-  assert_eq!(SomeEnum::name(&()), Some("Some"));
+  assert_eq!(<SomeEnum as GraphQLType>::name(&()), Some("Some"));
 
   // Ensure validity of meta info.
-  let mut registry = juniper::Registry::new(HashMap::new());
-  let meta = SomeEnum::meta(&(), &mut registry);
+  let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+  let meta = <SomeEnum as GraphQLType>::meta(&(), &mut registry);
 
   // Test Regular variant.
   assert_eq!(
-    SomeEnum::Regular.to(),
+    <SomeEnum as ToInputValue>::to(&SomeEnum::Regular),
     InputValue::String("REGULAR".into())
   );
   assert_eq!(
-    FromInputValue::from(&InputValue::String("REGULAR".into())),
+    <SomeEnum as FromInputValue>::from(&InputValue::String("REGULAR".into())),
     Some(SomeEnum::Regular)
   );
 
   // Test FULL variant.
   assert_eq!(
-    SomeEnum::Full.to(),
+    <SomeEnum as ToInputValue>::to(&SomeEnum::Full),
     InputValue::String("FULL".into())
   );
   assert_eq!(
-    FromInputValue::from(&InputValue::String("FULL".into())),
+    <SomeEnum as FromInputValue>::from(&InputValue::String("FULL".into())),
     Some(SomeEnum::Full)
   );
 }
+
+#[test]
+fn test_derived_enum_renaming() {
+  // Values default to SCREAMING_SNAKE_CASE.
+  assert_eq!(
+    <Episode as ToInputValue>::to(&Episode::EmpireStrikesBack),
+    InputValue::String("EMPIRE_STRIKES_BACK".into())
+  );
+  assert_eq!(
+    <Episode as FromInputValue>::from(&InputValue::Enum("NEW_HOPE".into())),
+    Some(Episode::NewHope)
+  );
+
+  // An explicit name takes precedence over rename_all.
+  assert_eq!(
+    <Ordering as ToInputValue>::to(&Ordering::MostRecent),
+    InputValue::String("mostRecent".into())
+  );
+  assert_eq!(
+    <Ordering as FromInputValue>::from(&InputValue::Enum("TOP".into())),
+    Some(Ordering::MostPopular)
+  );
+}
+
+#[test]
+fn test_derived_enum_name_converter() {
+  assert_eq!(
+    <Status as ToInputValue>::to(&Status::Active),
+    InputValue::String("STATUS_ACTIVE".into())
+  );
+  assert_eq!(
+    <Status as FromInputValue>::from(&InputValue::Enum("STATUS_ACTIVE".into())),
+    Some(Status::Active)
+  );
+  assert_eq!(
+    <Status as FromInputValue>::from(&InputValue::Enum("ACTIVE".into())),
+    None
+  );
+
+  // An explicit name takes precedence over the converter.
+  assert_eq!(
+    <Status as FromInputValue>::from(&InputValue::Enum("GONE".into())),
+    Some(Status::Deleted)
+  );
+}
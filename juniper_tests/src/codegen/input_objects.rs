@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use juniper::{self, InputValue, ToInputValue, GraphQLType, FromInputValue};
 
 #[derive(GraphQLInputObject, Debug, PartialEq)]
@@ -8,14 +10,141 @@ struct Input {
   c: i32,
 }
 
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+struct Paging {
+  #[graphql(default="10")]
+  limit: i32,
+  #[graphql(default)]
+  after: String,
+}
+
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+struct UserFilter {
+  name: Option<String>,
+  #[graphql(flatten)]
+  paging: Paging,
+}
+
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+struct NewUser {
+  name: String,
+  #[graphql(skip)]
+  password_hash: Vec<u8>,
+}
+
+fn screaming_snake_case(name: &str) -> String {
+  name.to_uppercase()
+}
+
+#[derive(GraphQLInputObject, Debug, PartialEq)]
+#[graphql(name_converter="screaming_snake_case")]
+struct Address {
+  street_name: String,
+  #[graphql(name="zip")]
+  postal_code: String,
+}
+
 #[test]
 fn test_derived_input_object() {
-  assert_eq!(Input::name(&()), Some("MyInput"));
+  assert_eq!(<Input as GraphQLType>::name(&()), Some("MyInput"));
 
   let obj = Input {
     regular_field: "a".to_string(),
     c: 33,
   };
-  let restored: Input = FromInputValue::from(&obj.to()).unwrap();
+  let restored: Input = <Input as FromInputValue>::from(&obj.to()).unwrap();
   assert_eq!(obj, restored);
 }
+
+#[test]
+fn test_derived_input_object_defaults() {
+  let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+  let meta = <Paging as GraphQLType>::meta(&(), &mut registry);
+
+  let limit = meta.input_field_by_name("limit").unwrap();
+  assert_eq!(limit.default_value, Some(InputValue::int(10)));
+  assert!(!limit.arg_type.is_non_null());
+
+  let after = meta.input_field_by_name("after").unwrap();
+  assert_eq!(after.default_value, Some(InputValue::string("")));
+
+  let empty: InputValue = InputValue::object(HashMap::<String, InputValue>::new());
+  assert_eq!(
+    <Paging as FromInputValue>::from(&empty),
+    Some(Paging { limit: 10, after: String::new() })
+  );
+}
+
+#[test]
+fn test_derived_input_object_flatten() {
+  let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+  let meta = <UserFilter as GraphQLType>::meta(&(), &mut registry);
+
+  assert!(meta.input_field_by_name("name").is_some());
+  assert!(meta.input_field_by_name("paging").is_none());
+  assert_eq!(
+    meta.input_field_by_name("limit").unwrap().default_value,
+    Some(InputValue::int(10))
+  );
+  assert!(meta.input_field_by_name("after").is_some());
+  assert!(registry.types.get("Paging").is_none());
+
+  let iv: InputValue = InputValue::object(vec![
+    ("name", InputValue::string("bob")),
+    ("limit", InputValue::int(5)),
+  ].into_iter().collect());
+  let filter = UserFilter {
+    name: Some("bob".to_owned()),
+    paging: Paging { limit: 5, after: String::new() },
+  };
+  assert_eq!(<UserFilter as FromInputValue>::from(&iv), Some(filter));
+
+  let restored = <UserFilter as FromInputValue>::from(&UserFilter {
+    name: None,
+    paging: Paging { limit: 1, after: "x".to_owned() },
+  }.to()).unwrap();
+  assert_eq!(restored.paging, Paging { limit: 1, after: "x".to_owned() });
+}
+
+#[test]
+fn test_derived_input_object_skip() {
+  let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+  let meta = <NewUser as GraphQLType>::meta(&(), &mut registry);
+
+  assert!(meta.input_field_by_name("name").is_some());
+  assert!(meta.input_field_by_name("passwordHash").is_none());
+
+  let iv: InputValue = InputValue::object(vec![
+    ("name", InputValue::string("bob")),
+    ("passwordHash", InputValue::string("secret")),
+  ].into_iter().collect());
+  assert_eq!(
+    <NewUser as FromInputValue>::from(&iv),
+    Some(NewUser { name: "bob".to_owned(), password_hash: Vec::new() })
+  );
+
+  let user = NewUser { name: "bob".to_owned(), password_hash: vec![1, 2, 3] };
+  let out: InputValue = user.to();
+  assert!(out.to_object_value().unwrap().get("passwordHash").is_none());
+}
+
+#[test]
+fn test_derived_input_object_name_converter() {
+  let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+  let meta = <Address as GraphQLType>::meta(&(), &mut registry);
+
+  assert!(meta.input_field_by_name("STREET_NAME").is_some());
+  assert!(meta.input_field_by_name("zip").is_some());
+
+  let iv: InputValue = InputValue::object(vec![
+    ("STREET_NAME", InputValue::string("Main St")),
+    ("zip", InputValue::string("12345")),
+  ].into_iter().collect());
+  let address = Address {
+    street_name: "Main St".to_owned(),
+    postal_code: "12345".to_owned(),
+  };
+  let restored = <Address as FromInputValue>::from(&address.to());
+  assert_eq!(restored, Some(address));
+  assert_eq!(<Address as FromInputValue>::from(&iv), restored);
+}
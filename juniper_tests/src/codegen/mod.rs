@@ -1,2 +1,4 @@
 mod enums;
 mod input_objects;
+mod scalar_values;
+mod unions;
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use juniper::{self, InputValue, ToInputValue, GraphQLType, FromInputValue, Value, RootNode, EmptyMutation};
+
+#[derive(GraphQLScalarValue, Debug, PartialEq)]
+struct UserId(String);
+
+#[derive(GraphQLScalarValue, Debug, PartialEq)]
+#[graphql(name="Counter", description="A monotonic counter")]
+struct CounterValue(i32);
+
+struct Query;
+
+graphql_object!(Query: () |&self| {
+    field user_id(id: UserId) -> UserId { id }
+    field counter() -> CounterValue { CounterValue(3) }
+});
+
+#[test]
+fn test_derived_scalar_value() {
+    assert_eq!(<UserId as GraphQLType>::name(&()), Some("UserId"));
+    assert_eq!(<CounterValue as GraphQLType>::name(&()), Some("Counter"));
+
+    let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+    let meta = <CounterValue as GraphQLType>::meta(&(), &mut registry);
+    assert_eq!(meta.name(), Some("Counter"));
+    assert_eq!(meta.description(), Some(&"A monotonic counter".to_owned()));
+
+    assert_eq!(<UserId as ToInputValue>::to(&UserId("abc".to_owned())), InputValue::string("abc"));
+    assert_eq!(
+        <UserId as FromInputValue>::from(&InputValue::string("abc")),
+        Some(UserId("abc".to_owned())));
+    assert_eq!(<UserId as FromInputValue>::from(&InputValue::int(1)), None);
+}
+
+#[test]
+fn test_derived_scalar_value_execution() {
+    let schema = RootNode::new(Query, EmptyMutation::<()>::new());
+    let doc = r#"{ userId(id: "u1") counter }"#;
+
+    let (result, errs) = juniper::execute(doc, None, &schema, &HashMap::new(), &())
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("userId", Value::string("u1")),
+            ("counter", Value::int(3)),
+        ].into_iter().collect()));
+}
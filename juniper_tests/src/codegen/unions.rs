@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use juniper::{self, GraphQLType, Value, RootNode, EmptyMutation};
+
+struct Database {
+    prefix: String,
+}
+
+impl juniper::Context for Database {}
+
+struct Human {
+    name: String,
+}
+
+struct Droid {
+    model: String,
+}
+
+graphql_object!(Human: Database |&self| {
+    field name(&executor) -> String {
+        format!("{}{}", executor.context().prefix, self.name)
+    }
+});
+
+graphql_object!(Droid: () |&self| {
+    field model() -> &str { &self.model }
+});
+
+#[derive(GraphQLEnum)]
+#[graphql(union, context="Database", description="A character")]
+enum Character {
+    Human(Human),
+    Droid(Droid),
+}
+
+struct Query;
+
+graphql_object!(Query: Database |&self| {
+    field characters() -> Vec<Character> {
+        vec![
+            Character::Human(Human { name: "Luke".to_owned() }),
+            Character::Droid(Droid { model: "R2-D2".to_owned() }),
+        ]
+    }
+});
+
+#[test]
+fn test_derived_union() {
+    assert_eq!(<Character as GraphQLType>::name(&()), Some("Character"));
+
+    let mut registry: juniper::Registry = juniper::Registry::new(HashMap::new());
+    let meta = <Character as GraphQLType>::meta(&(), &mut registry);
+    assert_eq!(meta.description(), Some(&"A character".to_owned()));
+    assert!(registry.types.get("Human").is_some());
+    assert!(registry.types.get("Droid").is_some());
+}
+
+#[test]
+fn test_derived_union_execution() {
+    let schema = RootNode::new(Query, EmptyMutation::<Database>::new());
+    let doc = r"{
+        characters {
+            ... on Human { name }
+            ... on Droid { model }
+        }
+    }";
+    let db = Database { prefix: "Sir ".to_owned() };
+
+    let (result, errs) = juniper::execute(doc, None, &schema, &HashMap::new(), &db)
+        .expect("Execution failed");
+
+    assert_eq!(errs, []);
+    assert_eq!(
+        result,
+        Value::object(vec![
+            ("characters", Value::list(vec![
+                Value::object(vec![
+                    ("name", Value::string("Sir Luke")),
+                ].into_iter().collect()),
+                Value::object(vec![
+                    ("model", Value::string("R2-D2")),
+                ].into_iter().collect()),
+            ])),
+        ].into_iter().collect()));
+}
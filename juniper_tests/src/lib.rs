@@ -3,3 +3,4 @@
 #[macro_use] extern crate serde_json;
 
 mod codegen;
+mod value_serde;
@@ -0,0 +1,41 @@
+use serde_json;
+
+use juniper::Value;
+
+#[test]
+fn test_value_deserialization() {
+    let value: Value = serde_json::from_str(r#"{
+        "hero": {
+            "name": "R2-D2",
+            "height": 0.96,
+            "episodes": [4, 5, 6],
+            "isDroid": true,
+            "homePlanet": null
+        }
+    }"#).expect("Invalid JSON");
+
+    assert_eq!(
+        value,
+        Value::object(vec![
+            ("hero", Value::object(vec![
+                ("name", Value::string("R2-D2")),
+                ("height", Value::float(0.96)),
+                ("episodes", Value::list(vec![Value::int(4), Value::int(5), Value::int(6)])),
+                ("isDroid", Value::boolean(true)),
+                ("homePlanet", Value::null()),
+            ].into_iter().collect())),
+        ].into_iter().collect()));
+}
+
+#[test]
+fn test_value_round_trip() {
+    let json = r#"{"b":[1,{"c":"x"}],"a":null}"#;
+    let value: Value = serde_json::from_str(json).expect("Invalid JSON");
+
+    assert_eq!(serde_json::to_string(&value).unwrap(), json);
+}
+
+#[test]
+fn test_value_deserialization_out_of_range() {
+    assert!(serde_json::from_str::<Value>("3000000000").is_err());
+}